@@ -104,6 +104,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         page_size: 10,
         sort_by: "created_at".to_string(),
         sort_order: "desc".to_string(),
+        ..Default::default()
     };
 
     let list_response = incident_client.list_incidents(list_request).await?;
@@ -124,6 +125,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         status: Some("Investigating".to_string()),
         assigned_to: Some("sre-team@example.com".to_string()),
         metadata: std::collections::HashMap::new(),
+        expected_version: None,
     };
 
     let update_response = incident_client.update_incident(update_request).await?;
@@ -131,6 +133,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Incident updated:");
     println!("  New State: {:?}", incidents::IncidentState::try_from(updated.incident_state));
     println!("  Assigned To: {}", updated.assigned_to);
+    println!("  Version: {}", updated.version);
 
     // Example 6: Resolve incident
     println!("\n=== Example 6: Resolve Incident ===");
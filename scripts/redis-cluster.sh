@@ -0,0 +1,81 @@
+#!/bin/bash
+#
+# Start or stop a local Redis Cluster for the RedisCluster storage tests
+#
+# Usage:
+#   ./scripts/redis-cluster.sh start   # 3 masters + 3 replicas on ports 7000-7005
+#   ./scripts/redis-cluster.sh stop
+#
+# The tests connect to REDIS_CLUSTER_NODES, which defaults to
+# redis://127.0.0.1:7000,redis://127.0.0.1:7001,redis://127.0.0.1:7002
+#
+
+set -e
+
+BASE_PORT=${REDIS_CLUSTER_BASE_PORT:-7000}
+NODES=6
+DATA_DIR=${REDIS_CLUSTER_DIR:-/tmp/llm-im-redis-cluster}
+
+start() {
+    command -v redis-server >/dev/null || { echo "redis-server not found"; exit 1; }
+    command -v redis-cli >/dev/null || { echo "redis-cli not found"; exit 1; }
+
+    mkdir -p "$DATA_DIR"
+
+    local addresses=()
+    for i in $(seq 0 $((NODES - 1))); do
+        local port=$((BASE_PORT + i))
+        local dir="$DATA_DIR/$port"
+        mkdir -p "$dir"
+
+        redis-server \
+            --port "$port" \
+            --dir "$dir" \
+            --cluster-enabled yes \
+            --cluster-config-file nodes.conf \
+            --cluster-node-timeout 5000 \
+            --appendonly no \
+            --save "" \
+            --daemonize yes \
+            --logfile "$dir/redis.log"
+
+        addresses+=("127.0.0.1:$port")
+    done
+
+    # Wait for the nodes to accept connections
+    for address in "${addresses[@]}"; do
+        until redis-cli -p "${address#*:}" ping >/dev/null 2>&1; do
+            sleep 0.1
+        done
+    done
+
+    redis-cli --cluster create "${addresses[@]}" --cluster-replicas 1 --cluster-yes
+
+    # Wait until every slot is served
+    until redis-cli -p "$BASE_PORT" cluster info | grep -q "cluster_state:ok"; do
+        sleep 0.5
+    done
+
+    echo "Redis cluster running on ports $BASE_PORT-$((BASE_PORT + NODES - 1))"
+}
+
+stop() {
+    for i in $(seq 0 $((NODES - 1))); do
+        redis-cli -p $((BASE_PORT + i)) shutdown nosave >/dev/null 2>&1 || true
+    done
+    rm -rf "$DATA_DIR"
+    echo "Redis cluster stopped"
+}
+
+case "$1" in
+    start)
+        start
+        ;;
+    stop)
+        stop
+        ;;
+    *)
+        echo "Usage: $0 {start|stop}"
+        exit 1
+        ;;
+esac
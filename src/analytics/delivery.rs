@@ -0,0 +1,510 @@
+//! Scheduled report generation and delivery
+//!
+//! [`ReportDeliveryService`] loads the incidents of a reporting period from
+//! the incident store, generates the configured reports with the
+//! [`AnalyticsEngine`], exports them with [`ReportExporter`] and delivers them
+//! by email or Slack through the [`NotificationService`].
+
+use crate::analytics::engine::AnalyticsEngine;
+use crate::analytics::error::{AnalyticsError, AnalyticsResult};
+use crate::analytics::export::{ExportFormat, ReportExporter};
+use crate::analytics::reports::{Report, ReportFilter, ReportRequest, ReportType};
+use crate::notifications::{EmailAttachment, NotificationService};
+use crate::state::{IncidentFilter, IncidentStore, TimeRange};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const PAGE_SIZE: u32 = 1000;
+
+/// Report generation and delivery configuration
+///
+/// ```toml
+/// [reporting]
+/// output_dir = "./data/reports"
+/// report_types = ["Summary", "SLA", "Trend"]
+/// formats = ["Json", "Html"]
+///
+/// [[reporting.subscriptions]]
+/// name = "storage-team"
+/// report_types = ["Summary", "SLA"]
+/// schedule = "0 7 * * 1-5"
+/// email = ["storage-oncall@example.com"]
+/// slack_channel = "#storage-incidents"
+/// filters = { teams = ["storage"] }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportingConfig {
+    /// Directory exported reports are written to, one subdirectory per day
+    pub output_dir: PathBuf,
+
+    /// Reports generated for everyone by the daily job
+    pub report_types: Vec<ReportType>,
+
+    /// Formats every report is exported in
+    pub formats: Vec<ExportFormat>,
+
+    /// Recipients of the organisation-wide reports
+    pub email: Vec<String>,
+
+    /// Slack channel for the organisation-wide reports
+    pub slack_channel: Option<String>,
+
+    /// Per-team subscriptions
+    pub subscriptions: Vec<ReportSubscription>,
+}
+
+impl Default for ReportingConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("./data/reports"),
+            report_types: vec![ReportType::Summary, ReportType::SLA, ReportType::Trend],
+            formats: vec![ExportFormat::Json, ExportFormat::Html],
+            email: Vec::new(),
+            slack_channel: None,
+            subscriptions: Vec::new(),
+        }
+    }
+}
+
+impl ReportingConfig {
+    /// Subscription for the organisation-wide reports
+    fn default_subscription(&self) -> ReportSubscription {
+        ReportSubscription {
+            name: DEFAULT_SUBSCRIPTION.to_string(),
+            report_types: self.report_types.clone(),
+            filters: ReportFilter::default(),
+            formats: Vec::new(),
+            schedule: None,
+            email: self.email.clone(),
+            slack_channel: self.slack_channel.clone(),
+        }
+    }
+}
+
+/// Name of the subscription built from the top-level `[reporting]` settings
+pub const DEFAULT_SUBSCRIPTION: &str = "default";
+
+/// A set of reports generated for one audience
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportSubscription {
+    /// Unique name, used in file names and job names
+    pub name: String,
+
+    /// Reports to generate
+    pub report_types: Vec<ReportType>,
+
+    /// Incidents the reports cover
+    #[serde(default)]
+    pub filters: ReportFilter,
+
+    /// Export formats, defaulting to `[reporting].formats`
+    #[serde(default)]
+    pub formats: Vec<ExportFormat>,
+
+    /// Cron schedule; `None` runs with the daily report job
+    #[serde(default)]
+    pub schedule: Option<String>,
+
+    /// Email recipients
+    #[serde(default)]
+    pub email: Vec<String>,
+
+    /// Slack channel
+    #[serde(default)]
+    pub slack_channel: Option<String>,
+}
+
+/// A report produced by a delivery run
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveredReport {
+    pub subscription: String,
+    pub report_type: ReportType,
+    pub title: String,
+    pub summary: String,
+
+    /// Exported files
+    pub files: Vec<PathBuf>,
+
+    /// Channels the report was delivered to
+    pub delivered_to: Vec<String>,
+}
+
+/// Outcome of a delivery run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportRun {
+    pub period_start: Option<DateTime<Utc>>,
+    pub period_end: Option<DateTime<Utc>>,
+
+    /// Incidents created in the period
+    pub incidents: usize,
+
+    pub reports: Vec<DeliveredReport>,
+
+    /// Reports skipped for lack of data
+    pub skipped: Vec<String>,
+
+    /// Reports that failed to generate, export or deliver
+    pub failures: Vec<String>,
+}
+
+/// Generates, exports and delivers subscribed reports
+pub struct ReportDeliveryService {
+    store: Arc<dyn IncidentStore>,
+    engine: AnalyticsEngine,
+    notifications: Option<Arc<NotificationService>>,
+    config: ReportingConfig,
+}
+
+impl ReportDeliveryService {
+    pub fn new(
+        store: Arc<dyn IncidentStore>,
+        engine: AnalyticsEngine,
+        config: ReportingConfig,
+    ) -> Self {
+        Self {
+            store,
+            engine,
+            notifications: None,
+            config,
+        }
+    }
+
+    /// Deliver reports through a notification service
+    pub fn with_notifications(mut self, notifications: Arc<NotificationService>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Subscriptions that run on their own schedule
+    pub fn scheduled_subscriptions(&self) -> impl Iterator<Item = &ReportSubscription> {
+        self.config
+            .subscriptions
+            .iter()
+            .filter(|subscription| subscription.schedule.is_some())
+    }
+
+    /// Run the default subscription and every subscription without its own
+    /// schedule over the previous UTC day
+    ///
+    /// `report_types` overrides the report types of the default subscription.
+    pub async fn run_daily(
+        &self,
+        report_types: Option<Vec<ReportType>>,
+    ) -> AnalyticsResult<ReportRun> {
+        let mut default = self.config.default_subscription();
+        if let Some(report_types) = report_types {
+            default.report_types = report_types;
+        }
+
+        let subscriptions: Vec<_> = std::iter::once(default)
+            .chain(
+                self.config
+                    .subscriptions
+                    .iter()
+                    .filter(|subscription| subscription.schedule.is_none())
+                    .cloned(),
+            )
+            .collect();
+
+        let (start, end) = previous_day(Utc::now());
+        self.run(&subscriptions, start, end).await
+    }
+
+    /// Run one named subscription over the previous UTC day
+    pub async fn run_subscription(&self, name: &str) -> AnalyticsResult<ReportRun> {
+        let subscription = if name == DEFAULT_SUBSCRIPTION {
+            self.config.default_subscription()
+        } else {
+            self.config
+                .subscriptions
+                .iter()
+                .find(|subscription| subscription.name == name)
+                .cloned()
+                .ok_or_else(|| {
+                    AnalyticsError::InvalidConfiguration(format!(
+                        "Unknown report subscription '{}'",
+                        name
+                    ))
+                })?
+        };
+
+        let (start, end) = previous_day(Utc::now());
+        self.run(&[subscription], start, end).await
+    }
+
+    /// Generate and deliver the reports of some subscriptions for a period
+    pub async fn run(
+        &self,
+        subscriptions: &[ReportSubscription],
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AnalyticsResult<ReportRun> {
+        let incidents = self.load_incidents(start, end).await?;
+        let mut run = ReportRun {
+            period_start: Some(start),
+            period_end: Some(end),
+            incidents: incidents.len(),
+            ..Default::default()
+        };
+        self.engine.update_incident_cache(incidents).await;
+
+        let output_dir = self
+            .config
+            .output_dir
+            .join(start.format("%Y-%m-%d").to_string());
+        tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+            AnalyticsError::ExportFailed(format!(
+                "Failed to create {}: {}",
+                output_dir.display(),
+                e
+            ))
+        })?;
+
+        for subscription in subscriptions {
+            for report_type in &subscription.report_types {
+                let label = format!("{}/{}", subscription.name, report_type.as_str());
+                let request = ReportRequest::new(*report_type, start, end)
+                    .with_filters(subscription.filters.clone());
+
+                let report = match self.engine.generate_report(&request).await {
+                    Ok(report) => report,
+                    Err(AnalyticsError::InsufficientData(reason)) => {
+                        tracing::debug!(report = %label, reason = %reason, "Skipping report");
+                        run.skipped.push(format!("{}: {}", label, reason));
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(report = %label, error = %e, "Failed to generate report");
+                        run.failures.push(format!("{}: {}", label, e));
+                        continue;
+                    }
+                };
+
+                match self
+                    .export_and_deliver(subscription, &report, &output_dir, &mut run.failures)
+                    .await
+                {
+                    Ok(delivered) => run.reports.push(delivered),
+                    Err(e) => {
+                        tracing::error!(report = %label, error = %e, "Failed to export report");
+                        run.failures.push(format!("{}: {}", label, e));
+                    }
+                }
+            }
+        }
+
+        tracing::info!(
+            incidents = run.incidents,
+            reports = run.reports.len(),
+            skipped = run.skipped.len(),
+            failures = run.failures.len(),
+            "Report run completed"
+        );
+
+        Ok(run)
+    }
+
+    async fn load_incidents(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> AnalyticsResult<Vec<crate::models::Incident>> {
+        let filter = IncidentFilter {
+            created: TimeRange::new(Some(start), Some(end)),
+            ..Default::default()
+        };
+
+        let mut incidents = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .store
+                .query_incidents(&filter, cursor.as_deref(), PAGE_SIZE)
+                .await
+                .map_err(|e| AnalyticsError::DatabaseError(e.to_string()))?;
+            incidents.extend(page.incidents);
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(incidents)
+    }
+
+    async fn export_and_deliver(
+        &self,
+        subscription: &ReportSubscription,
+        report: &Report,
+        output_dir: &std::path::Path,
+        failures: &mut Vec<String>,
+    ) -> AnalyticsResult<DeliveredReport> {
+        let formats = if subscription.formats.is_empty() {
+            &self.config.formats
+        } else {
+            &subscription.formats
+        };
+
+        let mut files = Vec::new();
+        let mut attachments = Vec::new();
+        for format in formats {
+            let path = output_dir.join(format!(
+                "{}-{}.{}",
+                subscription.name,
+                report.report_type.as_str(),
+                format.extension()
+            ));
+            let content = ReportExporter::export(report, *format, &path).await?;
+            attachments.push(EmailAttachment {
+                filename: file_name(&path),
+                content_type: format.mime_type().to_string(),
+                content,
+            });
+            files.push(path);
+        }
+
+        let mut delivered_to = Vec::new();
+        if let Some(ref notifications) = self.notifications {
+            let period = report.period_start.format("%Y-%m-%d");
+
+            if !subscription.email.is_empty() {
+                let subject = format!("{} - {}", report.title, period);
+                let body = format!(
+                    "{}\n\nPeriod: {} to {}",
+                    report.summary, report.period_start, report.period_end
+                );
+                match notifications
+                    .send_email_message(&subscription.email, &subject, &body, &attachments)
+                    .await
+                {
+                    Ok(()) => delivered_to.push("email".to_string()),
+                    Err(e) => failures.push(format!(
+                        "{}/{}: email delivery failed: {}",
+                        subscription.name,
+                        report.report_type.as_str(),
+                        e
+                    )),
+                }
+            }
+
+            if let Some(ref channel) = subscription.slack_channel {
+                let text = format!(
+                    "📊 *{}* for {}\n{}\nFiles: {}",
+                    report.title,
+                    period,
+                    report.summary,
+                    files
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                match notifications.send_slack_message(Some(channel), &text).await {
+                    Ok(()) => delivered_to.push(format!("slack:{}", channel)),
+                    Err(e) => failures.push(format!(
+                        "{}/{}: Slack delivery failed: {}",
+                        subscription.name,
+                        report.report_type.as_str(),
+                        e
+                    )),
+                }
+            }
+        }
+
+        Ok(DeliveredReport {
+            subscription: subscription.name.clone(),
+            report_type: report.report_type,
+            title: report.title.clone(),
+            summary: report.summary.clone(),
+            files,
+            delivered_to,
+        })
+    }
+}
+
+/// Start and end of the UTC day before `now`
+fn previous_day(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let end = now.date_naive().and_time(NaiveTime::MIN).and_utc();
+    (end - Duration::days(1), end)
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Incident, IncidentType, Severity};
+    use crate::state::InMemoryStore;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_previous_day() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 15, 8, 30, 0).unwrap();
+        let (start, end) = previous_day(now);
+        assert_eq!(start, Utc.with_ymd_and_hms(2024, 3, 14, 0, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2024, 3, 15, 0, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_run_exports_reports_per_subscription() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+
+        let start = Utc::now() - Duration::hours(2);
+        let end = Utc::now() + Duration::hours(1);
+        for team in ["web", "db"] {
+            let mut incident = Incident::new(
+                "monitoring".to_string(),
+                "Incident".to_string(),
+                "Description".to_string(),
+                Severity::P2,
+                IncidentType::Application,
+            );
+            incident.labels.insert("team".to_string(), team.to_string());
+            store.save_incident(&incident).await.unwrap();
+        }
+
+        let config = ReportingConfig {
+            output_dir: dir.path().to_path_buf(),
+            report_types: vec![ReportType::Summary, ReportType::Trend],
+            formats: vec![ExportFormat::Json],
+            ..Default::default()
+        };
+        let web = ReportSubscription {
+            name: "web-team".to_string(),
+            report_types: vec![ReportType::Summary],
+            filters: ReportFilter::new().with_teams(vec!["web".to_string()]),
+            formats: vec![ExportFormat::Csv],
+            schedule: None,
+            email: Vec::new(),
+            slack_channel: None,
+        };
+        let service =
+            ReportDeliveryService::new(store, AnalyticsEngine::with_defaults(), config.clone());
+
+        let run = service
+            .run(&[config.default_subscription(), web], start, end)
+            .await
+            .unwrap();
+        assert_eq!(run.incidents, 2);
+        assert_eq!(run.reports.len(), 2);
+        // Two incidents are too few for trend analysis
+        assert_eq!(run.skipped.len(), 1);
+        assert!(run.failures.is_empty());
+
+        let web_report = &run.reports[1];
+        assert_eq!(web_report.subscription, "web-team");
+        assert!(web_report.summary.starts_with("1 total incidents"));
+        assert_eq!(web_report.files.len(), 1);
+        assert!(web_report.files[0].ends_with("web-team-summary.csv"));
+        assert!(web_report.files[0].exists());
+    }
+}
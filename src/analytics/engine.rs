@@ -82,8 +82,11 @@ impl AnalyticsEngine {
     pub async fn generate_report(&self, request: &ReportRequest) -> AnalyticsResult<Report> {
         // Check cache first
         let cache_key = format!(
-            "{:?}-{}-{}",
-            request.report_type, request.start_date, request.end_date
+            "{:?}-{}-{}-{}",
+            request.report_type,
+            request.start_date,
+            request.end_date,
+            serde_json::to_string(&request.filters).unwrap_or_default()
         );
 
         {
@@ -164,6 +167,24 @@ impl AnalyticsEngine {
                     }
                 }
 
+                // Team filter, on the `team` label
+                if let Some(ref teams) = request.filters.teams {
+                    if !i.labels.get("team").is_some_and(|team| teams.contains(team)) {
+                        return false;
+                    }
+                }
+
+                // Tag filter, on label keys or `key=value` pairs
+                if let Some(ref tags) = request.filters.tags {
+                    let tagged = i.labels.iter().any(|(key, value)| {
+                        tags.iter()
+                            .any(|tag| tag == key || *tag == format!("{}={}", key, value))
+                    });
+                    if !tagged {
+                        return false;
+                    }
+                }
+
                 true
             })
             .cloned()
@@ -414,4 +435,38 @@ mod tests {
         let stats = engine.get_cache_stats().await;
         assert_eq!(stats.cached_reports, 0);
     }
+
+    #[tokio::test]
+    async fn test_team_filter_and_cache_key() {
+        use crate::models::{IncidentType, Severity};
+
+        let engine = AnalyticsEngine::with_defaults();
+        let mut incidents = Vec::new();
+        for team in ["web", "web", "db"] {
+            let mut incident = Incident::new(
+                "monitoring".to_string(),
+                "Incident".to_string(),
+                "Description".to_string(),
+                Severity::P2,
+                IncidentType::Application,
+            );
+            incident.labels.insert("team".to_string(), team.to_string());
+            incidents.push(incident);
+        }
+        engine.update_incident_cache(incidents).await;
+
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let end = Utc::now() + chrono::Duration::hours(1);
+        let total = |report: &Report| report.data["incident_metrics"]["total_incidents"].clone();
+
+        let web = ReportRequest::new(ReportType::Summary, start, end)
+            .with_filters(ReportFilter::new().with_teams(vec!["web".to_string()]));
+        let report = engine.generate_report(&web).await.unwrap();
+        assert_eq!(total(&report), 2);
+
+        let db = ReportRequest::new(ReportType::Summary, start, end)
+            .with_filters(ReportFilter::new().with_tags(vec!["team=db".to_string()]));
+        let report = engine.generate_report(&db).await.unwrap();
+        assert_eq!(total(&report), 1);
+    }
 }
@@ -10,6 +10,7 @@
 //! - **Report Generation**: Multiple report types (summary, SLA, trends, team performance)
 //! - **Export Formats**: JSON, CSV, PDF support
 //! - **Dashboard Data**: Real-time metrics for dashboards
+//! - **Scheduled Delivery**: Daily and per-team reports by email or Slack
 //! - **Statistical Analysis**: Percentiles, distributions, correlations
 //!
 //! # Report Types
@@ -46,6 +47,7 @@
 
 mod aggregation;
 mod dashboard;
+mod delivery;
 mod engine;
 mod error;
 mod export;
@@ -57,6 +59,10 @@ pub use aggregation::{
     AggregationPeriod, MetricsAggregator, TimeSeriesData, TimeSeriesPoint,
 };
 pub use dashboard::{DashboardData, DashboardMetrics, DashboardProvider};
+pub use delivery::{
+    DeliveredReport, ReportDeliveryService, ReportRun, ReportSubscription, ReportingConfig,
+    DEFAULT_SUBSCRIPTION,
+};
 pub use engine::{AnalyticsEngine, AnalyticsConfig};
 pub use error::{AnalyticsError, AnalyticsResult};
 pub use export::{ExportFormat, ReportExporter};
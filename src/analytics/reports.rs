@@ -17,6 +17,34 @@ pub enum ReportType {
     IncidentAnalysis,
 }
 
+impl ReportType {
+    /// Short name used in configuration and file names
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportType::Summary => "summary",
+            ReportType::SLA => "sla",
+            ReportType::Trend => "trend",
+            ReportType::TeamPerformance => "team_performance",
+            ReportType::IncidentAnalysis => "incident_analysis",
+        }
+    }
+}
+
+impl std::str::FromStr for ReportType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "summary" => Ok(ReportType::Summary),
+            "sla" => Ok(ReportType::SLA),
+            "trend" | "trends" => Ok(ReportType::Trend),
+            "team_performance" | "teamperformance" => Ok(ReportType::TeamPerformance),
+            "incident_analysis" | "incidentanalysis" => Ok(ReportType::IncidentAnalysis),
+            _ => Err(format!("Unknown report type '{}'", s)),
+        }
+    }
+}
+
 /// Filter criteria for reports
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ReportFilter {
@@ -476,4 +504,16 @@ mod tests {
         assert_eq!(filter.severities.unwrap().len(), 1);
         assert_eq!(filter.sources.unwrap().len(), 1);
     }
+
+    #[test]
+    fn test_report_type_from_str() {
+        assert_eq!("summary".parse::<ReportType>().unwrap(), ReportType::Summary);
+        assert_eq!("SLA".parse::<ReportType>().unwrap(), ReportType::SLA);
+        assert_eq!("trends".parse::<ReportType>().unwrap(), ReportType::Trend);
+        assert_eq!(
+            "team-performance".parse::<ReportType>().unwrap(),
+            ReportType::TeamPerformance
+        );
+        assert!("weekly".parse::<ReportType>().is_err());
+    }
 }
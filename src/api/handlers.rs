@@ -1,12 +1,17 @@
 use crate::api::AppState;
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::escalation::{validate_schedule, EscalationEngine};
 use crate::execution::{ExecutionContext, ExecutionResponse};
 use crate::models::*;
-use crate::state::IncidentFilter;
+use crate::notifications::{ActionLinkParams, ActionOutcome, NotificationActions};
+use crate::playbooks::PlaybookService;
+use crate::postmortem::{DecisionEvent, PostMortem, PostMortemStatus};
+use crate::state::{IncidentFilter, IncidentSort, LabelSelector, TimeRange};
 use axum::{
     extract::{Extension, Path, Query, State},
-    http::StatusCode,
-    Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{Html, IntoResponse},
+    Form, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -131,42 +136,64 @@ pub struct CreateIncidentRequest {
 pub async fn get_incident(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<IncidentResponse>> {
+) -> Result<impl IntoResponse> {
     let incident = state.processor.get_incident(&id).await?;
-    Ok(Json(IncidentResponse::from(incident)))
+    Ok((
+        [(header::ETAG, incident.etag())],
+        Json(IncidentResponse::from(incident)),
+    ))
 }
 
 /// List incidents
+///
+/// Supports page/offset pagination (`page`, `page_size`) and, when `cursor`
+/// or `limit` is given, cursor pagination: the response carries a
+/// `next_cursor` to pass back for the following page.
 pub async fn list_incidents(
     State(state): State<AppState>,
     Query(params): Query<ListIncidentsQuery>,
 ) -> Result<Json<ListIncidentsResponse>> {
-    let filter = IncidentFilter {
-        states: params.states.unwrap_or_default(),
-        severities: params.severities.unwrap_or_default(),
-        sources: params.sources.unwrap_or_default(),
-        active_only: params.active_only.unwrap_or(false),
-    };
+    let filter = params.filter()?;
+    let store = state.processor.store();
+
+    if params.cursor.is_some() || params.limit.is_some() {
+        let limit = params.limit.unwrap_or(20).min(100); // Max 100 per page
+        let page = store
+            .query_incidents(&filter, params.cursor.as_deref(), limit)
+            .await?;
+        let total = store.count_incidents(&filter).await?;
+
+        return Ok(Json(ListIncidentsResponse {
+            incidents: page.incidents.into_iter().map(IncidentResponse::from).collect(),
+            total,
+            page: 0,
+            page_size: limit,
+            next_cursor: page.next_cursor,
+        }));
+    }
 
     let page = params.page.unwrap_or(0);
     let page_size = params.page_size.unwrap_or(20).min(100); // Max 100 per page
 
-    let incidents = state
-        .processor
-        .store()
-        .list_incidents(&filter, page, page_size)
-        .await?;
+    let incidents = store.list_incidents(&filter, page, page_size).await?;
 
-    let total = state.processor.store().count_incidents(&filter).await?;
+    let total = store.count_incidents(&filter).await?;
 
     Ok(Json(ListIncidentsResponse {
         incidents: incidents.into_iter().map(IncidentResponse::from).collect(),
         total,
         page,
         page_size,
+        next_cursor: None,
     }))
 }
 
+/// Query parameters for listing incidents
+///
+/// List parameters added for rich filtering (`types`, `assignees`, `labels`,
+/// `resources`) are comma-separated. Labels are `key` or `key=value`; time
+/// bounds are RFC 3339; `sort` is a field name, prefixed with `-` for
+/// descending order (e.g. `-updated_at`).
 #[derive(Debug, Deserialize)]
 pub struct ListIncidentsQuery {
     pub page: Option<u32>,
@@ -175,16 +202,100 @@ pub struct ListIncidentsQuery {
     pub severities: Option<Vec<Severity>>,
     pub sources: Option<Vec<String>>,
     pub active_only: Option<bool>,
+    pub types: Option<String>,
+    pub assignees: Option<String>,
+    pub labels: Option<String>,
+    pub resources: Option<String>,
+    pub correlation_group_id: Option<Uuid>,
+    pub text: Option<String>,
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub sort: Option<String>,
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+impl ListIncidentsQuery {
+    /// Build the store filter from the query parameters
+    fn filter(&self) -> Result<IncidentFilter> {
+        let incident_types = split_list(self.types.as_deref())
+            .map(|t| {
+                t.parse::<IncidentType>()
+                    .map_err(|_| AppError::Validation(format!("Invalid incident type: {}", t)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let labels = split_list(self.labels.as_deref())
+            .map(str::parse::<LabelSelector>)
+            .collect::<Result<Vec<_>>>()?;
+
+        let sort = match self.sort.as_deref() {
+            Some(sort) => sort.parse::<IncidentSort>()?,
+            None => IncidentSort::default(),
+        };
+
+        Ok(IncidentFilter {
+            states: self.states.clone().unwrap_or_default(),
+            severities: self.severities.clone().unwrap_or_default(),
+            sources: self.sources.clone().unwrap_or_default(),
+            active_only: self.active_only.unwrap_or(false),
+            incident_types,
+            assignees: split_list(self.assignees.as_deref()).map(str::to_string).collect(),
+            labels,
+            affected_resources: split_list(self.resources.as_deref())
+                .map(str::to_string)
+                .collect(),
+            correlation_group_id: self.correlation_group_id,
+            text: self.text.clone().filter(|t| !t.is_empty()),
+            created: TimeRange::new(self.created_after, self.created_before),
+            updated: TimeRange::new(self.updated_after, self.updated_before),
+            resolved: TimeRange::new(self.resolved_after, self.resolved_before),
+            sort,
+        })
+    }
+}
+
+/// Split a comma-separated query parameter, skipping empty items
+fn split_list(value: Option<&str>) -> impl Iterator<Item = &str> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
 }
 
 /// Update incident
+///
+/// The write is conditional on the incident version, taken from the
+/// `If-Match` header or the `version` field. A stale version yields
+/// `409 Conflict` and a weak `If-Match` validator `400 Bad Request`;
+/// without either, the version read here is used.
 pub async fn update_incident(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    headers: HeaderMap,
     Json(request): Json<UpdateIncidentRequest>,
-) -> Result<Json<IncidentResponse>> {
+) -> Result<impl IntoResponse> {
     let mut incident = state.processor.get_incident(&id).await?;
 
+    let expected = match request.version {
+        Some(version) => Some(version),
+        None => parse_if_match(&headers)?,
+    };
+    if let Some(expected) = expected {
+        if expected != incident.version {
+            return Err(AppError::VersionConflict {
+                resource: format!("incident {}", id),
+                expected,
+                actual: incident.version,
+            });
+        }
+    }
+
     if let Some(new_state) = request.state {
         incident.update_state(new_state, request.actor.unwrap_or_else(|| "api".to_string()));
     }
@@ -194,8 +305,42 @@ pub async fn update_incident(
     }
 
     state.processor.store().update_incident(&incident).await?;
+    incident.version += 1;
+
+    Ok((
+        [(header::ETAG, incident.etag())],
+        Json(IncidentResponse::from(incident)),
+    ))
+}
+
+/// Parse an `If-Match` header into an incident version (`*` matches any)
+///
+/// `If-Match` uses strong comparison (RFC 7232 §3.1), which a weak
+/// `W/"…"` validator can never satisfy, so those are rejected.
+fn parse_if_match(headers: &HeaderMap) -> Result<Option<u64>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let value = value
+        .to_str()
+        .map_err(|_| AppError::Validation("Invalid If-Match header".to_string()))?
+        .trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    if value.starts_with("W/") {
+        return Err(AppError::Validation(format!(
+            "Weak ETags cannot be used with If-Match: {}",
+            value
+        )));
+    }
 
-    Ok(Json(IncidentResponse::from(incident)))
+    value
+        .trim_matches('"')
+        .parse()
+        .map(Some)
+        .map_err(|_| AppError::Validation(format!("Invalid If-Match header: {}", value)))
 }
 
 #[derive(Debug, Deserialize)]
@@ -203,6 +348,8 @@ pub struct UpdateIncidentRequest {
     pub state: Option<IncidentState>,
     pub assignees: Option<Vec<String>>,
     pub actor: Option<String>,
+    /// Expected incident version (alternative to `If-Match`)
+    pub version: Option<u64>,
 }
 
 /// Resolve incident
@@ -261,6 +408,8 @@ pub struct IncidentResponse {
     pub labels: HashMap<String, String>,
     pub assignees: Vec<String>,
     pub resolution: Option<Resolution>,
+    pub correlation_group_id: Option<Uuid>,
+    pub version: u64,
 }
 
 impl From<Incident> for IncidentResponse {
@@ -279,6 +428,8 @@ impl From<Incident> for IncidentResponse {
             labels: incident.labels,
             assignees: incident.assignees,
             resolution: incident.resolution,
+            correlation_group_id: incident.correlation_group_id,
+            version: incident.version,
         }
     }
 }
@@ -289,6 +440,8 @@ pub struct ListIncidentsResponse {
     pub total: u64,
     pub page: u32,
     pub page_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Ingest a security event from internal core-bundle fanout
@@ -339,6 +492,692 @@ pub struct IngestEventResponse {
     pub execution_id: String,
 }
 
+/// Generate (or regenerate) the draft post-mortem for a resolved incident
+pub async fn generate_postmortem(
+    State(state): State<AppState>,
+    Path(incident_id): Path<Uuid>,
+    Query(params): Query<GeneratePostMortemQuery>,
+) -> Result<Json<PostMortemResponse>> {
+    let postmortem = state.postmortems.generate(incident_id).await?;
+
+    let markdown = match params.output.as_deref() {
+        None | Some("json") => None,
+        Some("markdown") | Some("md") => Some(postmortem.to_markdown()),
+        Some(other) => {
+            return Err(AppError::Validation(format!(
+                "Unsupported output format: {}",
+                other
+            )))
+        }
+    };
+
+    Ok(Json(PostMortemResponse::new(
+        postmortem,
+        markdown,
+        params.include_raw.unwrap_or(false),
+    )))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GeneratePostMortemQuery {
+    /// Output format: json or markdown
+    pub output: Option<String>,
+    /// Include the decision event in the response
+    pub include_raw: Option<bool>,
+}
+
+/// Get a post-mortem by ID
+pub async fn get_postmortem(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PostMortem>> {
+    let postmortem = state
+        .postmortems
+        .get(&id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Post-mortem {} not found", id)))?;
+
+    Ok(Json(postmortem))
+}
+
+/// List post-mortems
+pub async fn list_postmortems(
+    State(state): State<AppState>,
+    Query(params): Query<ListPostMortemsQuery>,
+) -> Result<Json<ListPostMortemsResponse>> {
+    let status = params
+        .status
+        .as_deref()
+        .map(str::parse::<PostMortemStatus>)
+        .transpose()
+        .map_err(AppError::Validation)?;
+    let limit = params.limit.unwrap_or(20).min(100) as usize;
+
+    let postmortems = state
+        .postmortems
+        .list(params.incident_id.as_ref(), status, limit)
+        .await?;
+
+    Ok(Json(ListPostMortemsResponse {
+        total: postmortems.len(),
+        postmortems,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListPostMortemsQuery {
+    pub limit: Option<u32>,
+    pub incident_id: Option<Uuid>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPostMortemsResponse {
+    pub postmortems: Vec<PostMortem>,
+    pub total: usize,
+}
+
+/// Publish a post-mortem
+pub async fn publish_postmortem(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<PublishPostMortemRequest>,
+) -> Result<Json<PostMortem>> {
+    request.validate()?;
+
+    let postmortem = state.postmortems.publish(&id, request.reviewed_by).await?;
+    Ok(Json(postmortem))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct PublishPostMortemRequest {
+    #[validate(length(min = 1))]
+    pub reviewed_by: String,
+}
+
+/// Get the decision event recorded for a post-mortem
+pub async fn get_postmortem_decision_event(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<DecisionEvent>> {
+    let event = state.postmortems.decision_event(&id).await?.ok_or_else(|| {
+        AppError::NotFound(format!("No decision event recorded for post-mortem {}", id))
+    })?;
+
+    Ok(Json(event))
+}
+
+/// Post-mortem response DTO
+#[derive(Debug, Serialize)]
+pub struct PostMortemResponse {
+    #[serde(flatten)]
+    pub postmortem: PostMortem,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markdown: Option<String>,
+}
+
+impl PostMortemResponse {
+    fn new(mut postmortem: PostMortem, markdown: Option<String>, include_raw: bool) -> Self {
+        if !include_raw {
+            postmortem.decision_event = None;
+        }
+        Self {
+            postmortem,
+            markdown,
+        }
+    }
+}
+
+/// Playbook service the API was started with
+fn playbook_service(state: &AppState) -> Result<&std::sync::Arc<PlaybookService>> {
+    state
+        .processor
+        .playbook_service()
+        .ok_or_else(|| AppError::Configuration("Playbook service is not configured".to_string()))
+}
+
+/// List playbook executions
+pub async fn list_playbook_executions(
+    State(state): State<AppState>,
+    Query(params): Query<ListPlaybookExecutionsQuery>,
+) -> Result<Json<ListPlaybookExecutionsResponse>> {
+    let playbooks = playbook_service(&state)?;
+
+    let mut executions = match params.incident_id {
+        Some(ref id) => playbooks.list_executions_for_incident(id),
+        None => playbooks.list_executions(),
+    };
+    if let Some(status) = params.status {
+        executions.retain(|e| e.status == status);
+    }
+    executions.sort_by_key(|e| std::cmp::Reverse(e.started_at));
+
+    Ok(Json(ListPlaybookExecutionsResponse {
+        total: executions.len(),
+        executions,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListPlaybookExecutionsQuery {
+    pub incident_id: Option<Uuid>,
+    pub status: Option<ExecutionStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPlaybookExecutionsResponse {
+    pub executions: Vec<PlaybookExecution>,
+    pub total: usize,
+}
+
+/// Get a playbook execution by ID
+pub async fn get_playbook_execution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PlaybookExecution>> {
+    let execution = playbook_service(&state)?
+        .get_execution(&id)
+        .ok_or_else(|| AppError::NotFound(format!("Execution {} not found", id)))?;
+
+    Ok(Json(execution))
+}
+
+/// Approve the step a playbook execution is waiting on
+pub async fn approve_playbook_execution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ApprovalDecisionRequest>,
+) -> Result<Json<PlaybookExecution>> {
+    request.validate()?;
+
+    let execution = playbook_service(&state)?
+        .approve_execution(
+            id,
+            request.step_id.as_deref(),
+            &request.decided_by,
+            request.comment,
+        )
+        .await?;
+    Ok(Json(execution))
+}
+
+/// Reject the step a playbook execution is waiting on
+pub async fn reject_playbook_execution(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<ApprovalDecisionRequest>,
+) -> Result<Json<PlaybookExecution>> {
+    request.validate()?;
+
+    let execution = playbook_service(&state)?
+        .reject_execution(
+            id,
+            request.step_id.as_deref(),
+            &request.decided_by,
+            request.comment,
+        )
+        .await?;
+    Ok(Json(execution))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApprovalDecisionRequest {
+    #[validate(length(min = 1))]
+    pub decided_by: String,
+    /// Needed only while more than one step is waiting
+    pub step_id: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// Trace what a playbook would do for an incident, without side effects
+pub async fn simulate_playbook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<SimulatePlaybookRequest>,
+) -> Result<Json<PlaybookSimulation>> {
+    let playbooks = playbook_service(&state)?;
+
+    let incident = match (request.incident_id, request.incident) {
+        (Some(incident_id), None) => state.processor.get_incident(&incident_id).await?,
+        (None, Some(synthetic)) => {
+            synthetic.validate()?;
+            Incident::new(
+                synthetic.source,
+                synthetic.title,
+                synthetic.description,
+                synthetic.severity,
+                synthetic.incident_type,
+            )
+        }
+        _ => {
+            return Err(AppError::Validation(
+                "Pass exactly one of incident_id and incident".to_string(),
+            ))
+        }
+    };
+
+    let simulation = playbooks.simulate_playbook(id, &incident).await?;
+    Ok(Json(simulation))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SimulatePlaybookRequest {
+    /// Existing incident to simulate against
+    pub incident_id: Option<Uuid>,
+    /// Synthetic incident, which is not saved
+    pub incident: Option<CreateIncidentRequest>,
+}
+
+/// List every published revision of a playbook
+pub async fn list_playbook_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ListPlaybookRevisionsResponse>> {
+    let revisions = playbook_service(&state)?.list_revisions(&id)?;
+
+    Ok(Json(ListPlaybookRevisionsResponse {
+        total: revisions.len(),
+        revisions,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPlaybookRevisionsResponse {
+    pub revisions: Vec<PlaybookRevision>,
+    pub total: usize,
+}
+
+/// Get one revision of a playbook
+pub async fn get_playbook_revision(
+    State(state): State<AppState>,
+    Path((id, revision)): Path<(Uuid, u32)>,
+) -> Result<Json<PlaybookRevision>> {
+    let revision = playbook_service(&state)?.get_revision(&id, revision)?;
+    Ok(Json(revision))
+}
+
+/// Changes between two revisions of a playbook
+pub async fn diff_playbook_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PlaybookDiffQuery>,
+) -> Result<Json<PlaybookDiff>> {
+    let diff = playbook_service(&state)?.diff_revisions(&id, params.from, params.to)?;
+    Ok(Json(diff))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlaybookDiffQuery {
+    pub from: u32,
+    /// Defaults to the active revision
+    pub to: Option<u32>,
+}
+
+/// Make an earlier revision of a playbook the active one
+pub async fn rollback_playbook(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RollbackPlaybookRequest>,
+) -> Result<Json<Playbook>> {
+    let playbook = playbook_service(&state)?.rollback_playbook(&id, request.revision)?;
+    Ok(Json(playbook))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollbackPlaybookRequest {
+    /// Defaults to the revision published before the active one
+    pub revision: Option<u32>,
+}
+
+/// Escalation engine the API was started with
+fn escalation_engine(state: &AppState) -> Result<&std::sync::Arc<EscalationEngine>> {
+    state
+        .processor
+        .escalation_engine()
+        .ok_or_else(|| AppError::Configuration("Escalation engine is not configured".to_string()))
+}
+
+/// List on-call schedules
+pub async fn list_schedules(State(state): State<AppState>) -> Result<Json<ListSchedulesResponse>> {
+    let mut schedules = escalation_engine(&state)?.executor().list_schedules();
+    schedules.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Json(ListSchedulesResponse {
+        total: schedules.len(),
+        schedules,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<OnCallSchedule>,
+    pub total: usize,
+}
+
+/// Register an on-call schedule, replacing any with the same ID
+pub async fn create_schedule(
+    State(state): State<AppState>,
+    Json(schedule): Json<OnCallSchedule>,
+) -> Result<(StatusCode, Json<OnCallSchedule>)> {
+    validate_schedule(&schedule)?;
+    escalation_engine(&state)?
+        .executor()
+        .register_schedule(schedule.clone());
+
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
+
+/// Get an on-call schedule by ID
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<OnCallSchedule>> {
+    let schedule = escalation_engine(&state)?
+        .executor()
+        .get_schedule(&id)
+        .ok_or_else(|| AppError::NotFound(format!("Schedule {} not found", id)))?;
+
+    Ok(Json(schedule))
+}
+
+/// Add an override to an on-call schedule
+pub async fn create_schedule_override(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateScheduleOverrideRequest>,
+) -> Result<(StatusCode, Json<ScheduleOverride>)> {
+    request.validate()?;
+
+    let schedule_override = ScheduleOverride {
+        id: Uuid::new_v4(),
+        layer: request.layer,
+        user: request.user,
+        replaces: request.replaces,
+        start: request.start,
+        end: request.end,
+        reason: request.reason,
+        created_by: request.created_by,
+        created_at: chrono::Utc::now(),
+    };
+    let created = escalation_engine(&state)?
+        .executor()
+        .add_schedule_override(&id, schedule_override)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateScheduleOverrideRequest {
+    /// Every layer when omitted
+    pub layer: Option<String>,
+    #[validate(length(min = 1))]
+    pub user: String,
+    /// Only cover this user's shifts
+    pub replaces: Option<String>,
+    pub start: chrono::DateTime<chrono::Utc>,
+    pub end: chrono::DateTime<chrono::Utc>,
+    pub reason: Option<String>,
+    pub created_by: Option<String>,
+}
+
+/// Remove an override from an on-call schedule
+pub async fn delete_schedule_override(
+    State(state): State<AppState>,
+    Path((id, override_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ScheduleOverride>> {
+    let removed = escalation_engine(&state)?
+        .executor()
+        .remove_schedule_override(&id, &override_id)?;
+
+    Ok(Json(removed))
+}
+
+/// Add a shift swap to an on-call schedule
+pub async fn create_shift_swap(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CreateShiftSwapRequest>,
+) -> Result<(StatusCode, Json<ShiftSwap>)> {
+    request.validate()?;
+
+    let swap = ShiftSwap {
+        id: Uuid::new_v4(),
+        layer: request.layer,
+        user: request.user,
+        shift: request.shift,
+        with_user: request.with_user,
+        return_shift: request.return_shift,
+        created_by: request.created_by,
+        created_at: chrono::Utc::now(),
+    };
+    let created = escalation_engine(&state)?
+        .executor()
+        .add_shift_swap(&id, swap)?;
+
+    Ok((StatusCode::CREATED, Json(created)))
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateShiftSwapRequest {
+    /// Every layer when omitted
+    pub layer: Option<String>,
+    /// User whose `shift` is covered by `with_user`
+    #[validate(length(min = 1))]
+    pub user: String,
+    pub shift: ShiftWindow,
+    /// User whose `return_shift` is covered by `user`
+    #[validate(length(min = 1))]
+    pub with_user: String,
+    pub return_shift: ShiftWindow,
+    pub created_by: Option<String>,
+}
+
+/// Remove a shift swap from an on-call schedule
+pub async fn delete_shift_swap(
+    State(state): State<AppState>,
+    Path((id, swap_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<ShiftSwap>> {
+    let removed = escalation_engine(&state)?
+        .executor()
+        .remove_shift_swap(&id, &swap_id)?;
+
+    Ok(Json(removed))
+}
+
+/// Preview who will be on call for a schedule, with overrides and swaps applied
+pub async fn preview_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SchedulePreviewQuery>,
+) -> Result<Json<SchedulePreviewResponse>> {
+    let from = params.from.unwrap_or_else(chrono::Utc::now);
+    let weeks = params.weeks.unwrap_or(2);
+    let shifts = escalation_engine(&state)?
+        .executor()
+        .preview_schedule(&id, from, weeks)?;
+
+    Ok(Json(SchedulePreviewResponse {
+        schedule_id: id,
+        from,
+        until: from + chrono::Duration::weeks(weeks as i64),
+        shifts,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SchedulePreviewQuery {
+    /// Defaults to now
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Defaults to 2
+    pub weeks: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchedulePreviewResponse {
+    pub schedule_id: Uuid,
+    pub from: chrono::DateTime<chrono::Utc>,
+    pub until: chrono::DateTime<chrono::Utc>,
+    pub shifts: Vec<ScheduleShift>,
+}
+
+/// Calendar feed of a schedule's upcoming shifts
+pub async fn schedule_calendar(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<CalendarQuery>,
+) -> Result<impl IntoResponse> {
+    let ics = escalation_engine(&state)?.executor().schedule_calendar(
+        &id,
+        params.from.unwrap_or_else(chrono::Utc::now),
+        params.weeks.unwrap_or(4),
+    )?;
+
+    Ok(([(header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE)], ics))
+}
+
+/// Calendar feed of a user's upcoming shifts across all schedules
+pub async fn user_calendar(
+    State(state): State<AppState>,
+    Path(user): Path<String>,
+    Query(params): Query<CalendarQuery>,
+) -> Result<impl IntoResponse> {
+    let ics = escalation_engine(&state)?.executor().user_calendar(
+        &user,
+        params.from.unwrap_or_else(chrono::Utc::now),
+        params.weeks.unwrap_or(4),
+    )?;
+
+    Ok(([(header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE)], ics))
+}
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    /// Defaults to now
+    pub from: Option<chrono::DateTime<chrono::Utc>>,
+    /// Defaults to 4
+    pub weeks: Option<u32>,
+}
+
+/// Add overrides to a schedule from the events of an `.ics` file sent as
+/// the request body
+pub async fn import_schedule_overrides(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ImportOverridesQuery>,
+    body: String,
+) -> Result<Json<ImportOverridesResponse>> {
+    let overrides = escalation_engine(&state)?
+        .executor()
+        .import_schedule_overrides(&id, &body, params.created_by)?;
+
+    Ok(Json(ImportOverridesResponse {
+        schedule_id: id,
+        imported: overrides.len(),
+        overrides,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportOverridesQuery {
+    pub created_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportOverridesResponse {
+    pub schedule_id: Uuid,
+    pub imported: usize,
+    pub overrides: Vec<ScheduleOverride>,
+}
+
+fn notification_actions(state: &AppState) -> Result<&NotificationActions> {
+    state
+        .processor
+        .notification_service()
+        .map(|service| service.actions())
+        .ok_or_else(|| {
+            AppError::Configuration("Notification service is not configured".to_string())
+        })
+}
+
+fn required_header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::Authentication(format!("Missing {} header", name)))
+}
+
+/// Show the confirmation page of a one-click email link
+///
+/// Nothing is applied here: the page's form posts the signed link parameters
+/// to [`email_notification_action`], so link scanners that open the link do
+/// not act on the incident.
+pub async fn email_notification_confirmation(
+    State(state): State<AppState>,
+    Query(params): Query<ActionLinkParams>,
+) -> Result<Html<String>> {
+    let page = notification_actions(&state)?.email_confirmation(&params, chrono::Utc::now())?;
+
+    Ok(Html(page))
+}
+
+/// Apply the action of a confirmed email link
+///
+/// Answers with plain text, as the form is submitted from a browser.
+pub async fn email_notification_action(
+    State(state): State<AppState>,
+    Form(params): Form<ActionLinkParams>,
+) -> Result<String> {
+    let action = notification_actions(&state)?.email_action(&params, chrono::Utc::now())?;
+    let outcome = state.processor.apply_notification_action(&action).await?;
+
+    Ok(outcome.message)
+}
+
+/// Apply the actions of a Slack interaction payload (button clicks)
+pub async fn slack_notification_action(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<Vec<ActionOutcome>>> {
+    let actions = notification_actions(&state)?.slack_actions(
+        required_header(&headers, "x-slack-request-timestamp")?,
+        required_header(&headers, "x-slack-signature")?,
+        body.as_bytes(),
+        chrono::Utc::now(),
+    )?;
+
+    let mut outcomes = Vec::with_capacity(actions.len());
+    for action in &actions {
+        outcomes.push(state.processor.apply_notification_action(action).await?);
+    }
+
+    Ok(Json(outcomes))
+}
+
+/// Apply a PagerDuty v3 webhook event
+///
+/// Events other than acknowledged, resolved and reassigned are accepted and
+/// ignored.
+pub async fn pagerduty_notification_action(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Json<Vec<ActionOutcome>>> {
+    let action = notification_actions(&state)?.pagerduty_action(
+        required_header(&headers, "x-pagerduty-signature")?,
+        body.as_bytes(),
+    )?;
+
+    let mut outcomes = Vec::new();
+    if let Some(action) = action {
+        outcomes.push(state.processor.apply_notification_action(&action).await?);
+    }
+
+    Ok(Json(outcomes))
+}
+
 /// Prometheus metrics endpoint
 ///
 /// Returns metrics in Prometheus text exposition format
@@ -346,3 +1185,26 @@ pub async fn metrics() -> (StatusCode, String) {
     let metrics = crate::metrics::gather_metrics();
     (StatusCode::OK, metrics)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_parse_if_match() {
+        assert_eq!(parse_if_match(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(parse_if_match(&if_match("*")).unwrap(), None);
+        assert_eq!(parse_if_match(&if_match("\"3\"")).unwrap(), Some(3));
+
+        let err = parse_if_match(&if_match("W/\"3\"")).unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert!(parse_if_match(&if_match("\"abc\"")).is_err());
+    }
+}
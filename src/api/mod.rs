@@ -3,7 +3,9 @@ pub mod routes;
 
 pub use routes::*;
 
-use crate::{processing::IncidentProcessor, websocket::WebSocketState};
+use crate::{
+    postmortem::PostMortemGenerator, processing::IncidentProcessor, websocket::WebSocketState,
+};
 use std::sync::Arc;
 
 /// Shared application state
@@ -11,13 +13,16 @@ use std::sync::Arc;
 pub struct AppState {
     pub processor: Arc<IncidentProcessor>,
     pub websocket: Option<Arc<WebSocketState>>,
+    pub postmortems: Arc<PostMortemGenerator>,
 }
 
 impl AppState {
     pub fn new(processor: Arc<IncidentProcessor>) -> Self {
+        let postmortems = Arc::new(PostMortemGenerator::new(processor.store().clone()));
         Self {
             processor,
             websocket: None,
+            postmortems,
         }
     }
 
@@ -26,4 +31,10 @@ impl AppState {
         self.websocket = Some(websocket);
         self
     }
+
+    /// Set the post-mortem generator
+    pub fn with_postmortem_generator(mut self, generator: Arc<PostMortemGenerator>) -> Self {
+        self.postmortems = generator;
+        self
+    }
 }
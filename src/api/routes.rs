@@ -2,7 +2,7 @@ use crate::api::{handlers, AppState};
 use crate::execution::middleware::execution_context_middleware;
 use axum::{
     middleware,
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
     Router,
 };
 use tower_http::{
@@ -27,6 +27,85 @@ pub fn build_router(state: AppState) -> Router {
         .route("/v1/incidents/:id", get(handlers::get_incident))
         .route("/v1/incidents/:id", put(handlers::update_incident))
         .route("/v1/incidents/:id/resolve", post(handlers::resolve_incident))
+        // Post-mortems
+        .route(
+            "/v1/postmortems/generate/:incident_id",
+            post(handlers::generate_postmortem),
+        )
+        .route("/v1/postmortems", get(handlers::list_postmortems))
+        .route("/v1/postmortems/:id", get(handlers::get_postmortem))
+        .route("/v1/postmortems/:id/publish", post(handlers::publish_postmortem))
+        .route(
+            "/v1/postmortems/:id/decision-event",
+            get(handlers::get_postmortem_decision_event),
+        )
+        // Playbooks
+        .route("/v1/playbooks/:id/simulate", post(handlers::simulate_playbook))
+        .route(
+            "/v1/playbooks/:id/revisions",
+            get(handlers::list_playbook_revisions),
+        )
+        .route(
+            "/v1/playbooks/:id/revisions/:revision",
+            get(handlers::get_playbook_revision),
+        )
+        .route("/v1/playbooks/:id/diff", get(handlers::diff_playbook_revisions))
+        .route("/v1/playbooks/:id/rollback", post(handlers::rollback_playbook))
+        // Playbook executions
+        .route("/v1/playbook-executions", get(handlers::list_playbook_executions))
+        .route("/v1/playbook-executions/:id", get(handlers::get_playbook_execution))
+        .route(
+            "/v1/playbook-executions/:id/approve",
+            post(handlers::approve_playbook_execution),
+        )
+        .route(
+            "/v1/playbook-executions/:id/reject",
+            post(handlers::reject_playbook_execution),
+        )
+        // On-call schedules
+        .route("/v1/schedules", get(handlers::list_schedules))
+        .route("/v1/schedules", post(handlers::create_schedule))
+        .route("/v1/schedules/:id", get(handlers::get_schedule))
+        .route(
+            "/v1/schedules/:id/overrides",
+            post(handlers::create_schedule_override),
+        )
+        .route(
+            "/v1/schedules/:id/overrides/:override_id",
+            delete(handlers::delete_schedule_override),
+        )
+        .route("/v1/schedules/:id/swaps", post(handlers::create_shift_swap))
+        .route(
+            "/v1/schedules/:id/swaps/:swap_id",
+            delete(handlers::delete_shift_swap),
+        )
+        .route("/v1/schedules/:id/preview", get(handlers::preview_schedule))
+        .route(
+            "/v1/schedules/:id/calendar.ics",
+            get(handlers::schedule_calendar),
+        )
+        .route(
+            "/v1/schedules/:id/overrides/import",
+            post(handlers::import_schedule_overrides),
+        )
+        .route(
+            "/v1/oncall/:user/calendar.ics",
+            get(handlers::user_calendar),
+        )
+        // Incident actions taken from notifications
+        .route(
+            "/v1/notification-actions/email",
+            get(handlers::email_notification_confirmation)
+                .post(handlers::email_notification_action),
+        )
+        .route(
+            "/v1/notification-actions/slack",
+            post(handlers::slack_notification_action),
+        )
+        .route(
+            "/v1/notification-actions/pagerduty",
+            post(handlers::pagerduty_notification_action),
+        )
         // Internal event ingestion (core-bundle fanout)
         .route("/api/v1/events", post(handlers::ingest_event))
         // Add WebSocket endpoint if WebSocket is enabled
@@ -2,6 +2,7 @@ use clap::{Parser, Subcommand};
 use reqwest::Client;
 use serde_json::json;
 use std::error::Error;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "llm-im-cli")]
@@ -95,6 +96,383 @@ enum Commands {
         #[command(subcommand)]
         action: PostmortemCommands,
     },
+
+    /// Playbook and playbook execution commands
+    Playbook {
+        #[command(subcommand)]
+        action: PlaybookCommands,
+    },
+
+    /// On-call schedule commands
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleCommands,
+    },
+
+    /// Copy incidents, post-mortems, playbook executions and escalation states between backends
+    Migrate {
+        /// Source backend: sled, redb, redis or redis_cluster
+        #[arg(long)]
+        from: String,
+
+        /// Source database path (sled/redb)
+        #[arg(long)]
+        from_path: Option<PathBuf>,
+
+        /// Source Redis URL (repeat for cluster nodes)
+        #[arg(long)]
+        from_url: Vec<String>,
+
+        /// Target backend: sled, redb, redis or redis_cluster
+        #[arg(long)]
+        to: String,
+
+        /// Target database path (sled/redb)
+        #[arg(long)]
+        to_path: Option<PathBuf>,
+
+        /// Target Redis URL (repeat for cluster nodes)
+        #[arg(long)]
+        to_url: Vec<String>,
+
+        /// Incidents read per page
+        #[arg(long, default_value = "500")]
+        page_size: u32,
+
+        /// Read the source without writing to the target
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Only compare counts and checksums of the two backends
+        #[arg(long)]
+        verify_only: bool,
+
+        /// Do not copy post-mortems
+        #[arg(long)]
+        skip_postmortems: bool,
+    },
+
+    /// Archive expired incidents or restore archived ones
+    ///
+    /// Uses the state backend and `[retention]` settings from the server
+    /// configuration (`CONFIG_PATH`).
+    Retention {
+        #[command(subcommand)]
+        action: RetentionCommands,
+    },
+
+    /// Check configuration kept outside the server
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Check a directory of playbooks, escalation policies and routing rules
+    ///
+    /// Runs the checks the server runs before loading it, and exits with a
+    /// non-zero status listing every problem if any file is invalid.
+    Validate {
+        /// The definition directory
+        dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum RetentionCommands {
+    /// Archive and delete incidents past their retention period
+    Run {
+        /// Report what would be archived without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Restore an archived incident into the store
+    Restore {
+        /// The incident ID to restore
+        #[arg(value_name = "INCIDENT_ID", required_unless_present = "file")]
+        id: Option<String>,
+
+        /// Restore every incident in an archive file instead
+        #[arg(long, conflicts_with = "id")]
+        file: Option<PathBuf>,
+    },
+
+    /// List archive files
+    List,
+}
+
+#[derive(Subcommand)]
+enum PlaybookCommands {
+    /// List playbook executions
+    Executions {
+        /// Filter by incident ID
+        #[arg(short, long)]
+        incident_id: Option<String>,
+
+        /// Filter by status, e.g. waiting_for_approval
+        #[arg(short, long)]
+        status: Option<String>,
+    },
+
+    /// Retrieve a playbook execution
+    Execution {
+        /// The execution ID to retrieve
+        #[arg(value_name = "EXECUTION_ID")]
+        id: String,
+    },
+
+    /// Approve the step an execution is waiting on and resume it
+    Approve {
+        /// The execution ID to approve
+        #[arg(value_name = "EXECUTION_ID")]
+        id: String,
+
+        /// The approver, who must be listed on the step
+        #[arg(long)]
+        by: String,
+
+        /// The waiting step, if more than one is waiting
+        #[arg(long)]
+        step: Option<String>,
+
+        /// Note recorded with the decision
+        #[arg(short, long)]
+        comment: Option<String>,
+    },
+
+    /// Reject the step an execution is waiting on, cancelling it
+    Reject {
+        /// The execution ID to reject
+        #[arg(value_name = "EXECUTION_ID")]
+        id: String,
+
+        /// The approver, who must be listed on the step
+        #[arg(long)]
+        by: String,
+
+        /// The waiting step, if more than one is waiting
+        #[arg(long)]
+        step: Option<String>,
+
+        /// Note recorded with the decision
+        #[arg(short, long)]
+        comment: Option<String>,
+    },
+
+    /// Show step by step what a playbook would do, without side effects
+    ///
+    /// Runs against an existing incident with --incident-id, otherwise
+    /// against a synthetic incident built from the other options.
+    Simulate {
+        /// The playbook ID to simulate
+        #[arg(value_name = "PLAYBOOK_ID")]
+        id: String,
+
+        /// Existing incident to simulate against
+        #[arg(short, long)]
+        incident_id: Option<String>,
+
+        #[arg(short, long, default_value = "simulation")]
+        source: String,
+
+        #[arg(short, long, default_value = "Simulated incident")]
+        title: String,
+
+        #[arg(short, long, default_value = "")]
+        description: String,
+
+        #[arg(short = 'S', long, default_value = "P2")]
+        severity: String,
+
+        #[arg(short = 'T', long, default_value = "Application")]
+        incident_type: String,
+    },
+
+    /// List the published revisions of a playbook
+    Revisions {
+        /// The playbook ID
+        #[arg(value_name = "PLAYBOOK_ID")]
+        id: String,
+    },
+
+    /// Show what changed between two revisions of a playbook
+    Diff {
+        /// The playbook ID
+        #[arg(value_name = "PLAYBOOK_ID")]
+        id: String,
+
+        /// Revision to compare from
+        #[arg(long)]
+        from: u32,
+
+        /// Revision to compare to, by default the active one
+        #[arg(long)]
+        to: Option<u32>,
+    },
+
+    /// Make an earlier revision of a playbook the active one
+    Rollback {
+        /// The playbook ID
+        #[arg(value_name = "PLAYBOOK_ID")]
+        id: String,
+
+        /// Revision to activate, by default the one before the active one
+        #[arg(short, long)]
+        revision: Option<u32>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScheduleCommands {
+    /// List on-call schedules
+    List,
+
+    /// Get schedule details, including overrides and swaps
+    Get {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+    },
+
+    /// Put a user on call for a period in place of the rotation
+    Override {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// User who is on call during the override
+        #[arg(short, long)]
+        user: String,
+
+        /// Only cover this user's shifts
+        #[arg(short, long)]
+        replaces: Option<String>,
+
+        /// Layer to override, by default every layer
+        #[arg(short, long)]
+        layer: Option<String>,
+
+        /// Start time (RFC 3339), e.g. 2024-01-16T09:00:00-05:00
+        #[arg(long)]
+        start: String,
+
+        /// End time (RFC 3339)
+        #[arg(long)]
+        end: String,
+
+        /// Why the override was made
+        #[arg(long)]
+        reason: Option<String>,
+
+        /// Who made the override
+        #[arg(long)]
+        created_by: Option<String>,
+    },
+
+    /// Remove an override
+    RemoveOverride {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// The override ID
+        #[arg(value_name = "OVERRIDE_ID")]
+        override_id: String,
+    },
+
+    /// Trade shifts between two users
+    Swap {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// User giving away the shift
+        #[arg(short, long)]
+        user: String,
+
+        /// User taking the shift and giving away the return shift
+        #[arg(short, long)]
+        with_user: String,
+
+        /// Start of the shift `--with-user` covers (RFC 3339)
+        #[arg(long)]
+        shift_start: String,
+
+        /// End of the shift `--with-user` covers (RFC 3339)
+        #[arg(long)]
+        shift_end: String,
+
+        /// Start of the shift `--user` covers in return (RFC 3339)
+        #[arg(long)]
+        return_start: String,
+
+        /// End of the shift `--user` covers in return (RFC 3339)
+        #[arg(long)]
+        return_end: String,
+
+        /// Layer the swap applies to, by default every layer
+        #[arg(short, long)]
+        layer: Option<String>,
+    },
+
+    /// Remove a shift swap
+    RemoveSwap {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// The swap ID
+        #[arg(value_name = "SWAP_ID")]
+        swap_id: String,
+    },
+
+    /// Show who will be on call, with overrides, swaps and holidays applied
+    Preview {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// Number of weeks to show
+        #[arg(short, long, default_value = "2")]
+        weeks: u32,
+
+        /// Start time (RFC 3339), by default now
+        #[arg(long)]
+        from: Option<String>,
+    },
+
+    /// Print upcoming shifts as an iCalendar (.ics) feed
+    Calendar {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID", required_unless_present = "user")]
+        id: Option<String>,
+
+        /// Show this user's shifts across all schedules instead
+        #[arg(short, long, conflicts_with = "id")]
+        user: Option<String>,
+
+        /// Number of weeks to include
+        #[arg(short, long, default_value = "4")]
+        weeks: u32,
+    },
+
+    /// Add overrides from the events of an iCalendar (.ics) file
+    ImportOverrides {
+        /// The schedule ID
+        #[arg(value_name = "SCHEDULE_ID")]
+        id: String,
+
+        /// Path to the .ics file
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Who made the overrides
+        #[arg(long)]
+        created_by: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -481,7 +859,407 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 println!("{}", serde_json::to_string_pretty(&body)?);
             }
         },
+
+        Commands::Playbook { action } => {
+            let response = match action {
+                PlaybookCommands::Executions {
+                    incident_id,
+                    status,
+                } => {
+                    let mut query_params = Vec::new();
+                    if let Some(ref inc_id) = incident_id {
+                        query_params.push(format!("incident_id={}", inc_id));
+                    }
+                    if let Some(ref s) = status {
+                        query_params.push(format!("status={}", s));
+                    }
+
+                    let mut url = format!("{}/v1/playbook-executions", cli.endpoint);
+                    if !query_params.is_empty() {
+                        url.push('?');
+                        url.push_str(&query_params.join("&"));
+                    }
+                    client.get(&url).send().await?
+                }
+
+                PlaybookCommands::Execution { id } => {
+                    client
+                        .get(format!("{}/v1/playbook-executions/{}", cli.endpoint, id))
+                        .send()
+                        .await?
+                }
+
+                PlaybookCommands::Approve {
+                    id,
+                    by,
+                    step,
+                    comment,
+                } => {
+                    client
+                        .post(format!(
+                            "{}/v1/playbook-executions/{}/approve",
+                            cli.endpoint, id
+                        ))
+                        .json(&json!({
+                            "decided_by": by,
+                            "step_id": step,
+                            "comment": comment,
+                        }))
+                        .send()
+                        .await?
+                }
+
+                PlaybookCommands::Reject {
+                    id,
+                    by,
+                    step,
+                    comment,
+                } => {
+                    client
+                        .post(format!(
+                            "{}/v1/playbook-executions/{}/reject",
+                            cli.endpoint, id
+                        ))
+                        .json(&json!({
+                            "decided_by": by,
+                            "step_id": step,
+                            "comment": comment,
+                        }))
+                        .send()
+                        .await?
+                }
+
+                PlaybookCommands::Simulate {
+                    id,
+                    incident_id,
+                    source,
+                    title,
+                    description,
+                    severity,
+                    incident_type,
+                } => {
+                    let body = match incident_id {
+                        Some(incident_id) => json!({ "incident_id": incident_id }),
+                        None => json!({
+                            "incident": {
+                                "source": source,
+                                "title": title,
+                                "description": description,
+                                "severity": severity,
+                                "incident_type": incident_type,
+                            }
+                        }),
+                    };
+                    client
+                        .post(format!("{}/v1/playbooks/{}/simulate", cli.endpoint, id))
+                        .json(&body)
+                        .send()
+                        .await?
+                }
+
+                PlaybookCommands::Revisions { id } => {
+                    client
+                        .get(format!("{}/v1/playbooks/{}/revisions", cli.endpoint, id))
+                        .send()
+                        .await?
+                }
+
+                PlaybookCommands::Diff { id, from, to } => {
+                    let mut url =
+                        format!("{}/v1/playbooks/{}/diff?from={}", cli.endpoint, id, from);
+                    if let Some(to) = to {
+                        url.push_str(&format!("&to={}", to));
+                    }
+                    client.get(&url).send().await?
+                }
+
+                PlaybookCommands::Rollback { id, revision } => {
+                    client
+                        .post(format!("{}/v1/playbooks/{}/rollback", cli.endpoint, id))
+                        .json(&json!({ "revision": revision }))
+                        .send()
+                        .await?
+                }
+            };
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body: serde_json::Value = response.json().await?;
+                eprintln!("Error ({}): {}", status, serde_json::to_string_pretty(&body)?);
+                std::process::exit(1);
+            }
+
+            let body: serde_json::Value = response.json().await?;
+            println!("{}", serde_json::to_string_pretty(&body)?);
+        }
+
+        Commands::Schedule { action } => {
+            let calendar = matches!(action, ScheduleCommands::Calendar { .. });
+            let response = match action {
+                ScheduleCommands::List => {
+                    client
+                        .get(format!("{}/v1/schedules", cli.endpoint))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::Get { id } => {
+                    client
+                        .get(format!("{}/v1/schedules/{}", cli.endpoint, id))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::Override {
+                    id,
+                    user,
+                    replaces,
+                    layer,
+                    start,
+                    end,
+                    reason,
+                    created_by,
+                } => {
+                    client
+                        .post(format!("{}/v1/schedules/{}/overrides", cli.endpoint, id))
+                        .json(&json!({
+                            "user": user,
+                            "replaces": replaces,
+                            "layer": layer,
+                            "start": start,
+                            "end": end,
+                            "reason": reason,
+                            "created_by": created_by,
+                        }))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::RemoveOverride { id, override_id } => {
+                    client
+                        .delete(format!(
+                            "{}/v1/schedules/{}/overrides/{}",
+                            cli.endpoint, id, override_id
+                        ))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::Swap {
+                    id,
+                    user,
+                    with_user,
+                    shift_start,
+                    shift_end,
+                    return_start,
+                    return_end,
+                    layer,
+                } => {
+                    client
+                        .post(format!("{}/v1/schedules/{}/swaps", cli.endpoint, id))
+                        .json(&json!({
+                            "user": user,
+                            "with_user": with_user,
+                            "shift": { "start": shift_start, "end": shift_end },
+                            "return_shift": { "start": return_start, "end": return_end },
+                            "layer": layer,
+                        }))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::RemoveSwap { id, swap_id } => {
+                    client
+                        .delete(format!(
+                            "{}/v1/schedules/{}/swaps/{}",
+                            cli.endpoint, id, swap_id
+                        ))
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::Preview { id, weeks, from } => {
+                    let mut query = vec![("weeks", weeks.to_string())];
+                    if let Some(from) = from {
+                        query.push(("from", from));
+                    }
+                    client
+                        .get(format!("{}/v1/schedules/{}/preview", cli.endpoint, id))
+                        .query(&query)
+                        .send()
+                        .await?
+                }
+
+                ScheduleCommands::Calendar { id, user, weeks } => {
+                    let url = match (id, user) {
+                        (_, Some(user)) => {
+                            format!("{}/v1/oncall/{}/calendar.ics", cli.endpoint, user)
+                        }
+                        (Some(id), None) => {
+                            format!("{}/v1/schedules/{}/calendar.ics", cli.endpoint, id)
+                        }
+                        (None, None) => unreachable!("clap requires a schedule ID or --user"),
+                    };
+                    client.get(url).query(&[("weeks", weeks)]).send().await?
+                }
+
+                ScheduleCommands::ImportOverrides {
+                    id,
+                    file,
+                    created_by,
+                } => {
+                    let ics = std::fs::read_to_string(&file)?;
+                    let mut request = client
+                        .post(format!(
+                            "{}/v1/schedules/{}/overrides/import",
+                            cli.endpoint, id
+                        ))
+                        .header("Content-Type", "text/calendar")
+                        .body(ics);
+                    if let Some(created_by) = created_by {
+                        request = request.query(&[("created_by", created_by)]);
+                    }
+                    request.send().await?
+                }
+            };
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let body: serde_json::Value = response.json().await?;
+                eprintln!("Error ({}): {}", status, serde_json::to_string_pretty(&body)?);
+                std::process::exit(1);
+            }
+
+            if calendar {
+                print!("{}", response.text().await?);
+            } else {
+                let body: serde_json::Value = response.json().await?;
+                println!("{}", serde_json::to_string_pretty(&body)?);
+            }
+        }
+
+        Commands::Migrate {
+            from,
+            from_path,
+            from_url,
+            to,
+            to_path,
+            to_url,
+            page_size,
+            dry_run,
+            verify_only,
+            skip_postmortems,
+        } => {
+            use llm_incident_manager::state::{create_backend, StoreMigrator};
+
+            let source = create_backend(&state_config(&from, from_path, from_url)?).await?;
+            let target = create_backend(&state_config(&to, to_path, to_url)?).await?;
+
+            let migrator = StoreMigrator::new(source, target)
+                .with_page_size(page_size)
+                .with_dry_run(dry_run)
+                .with_postmortems(!skip_postmortems);
+
+            let verification = if verify_only {
+                let report = migrator.verify().await?;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                Some(report)
+            } else {
+                let report = migrator.run().await?;
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                report.verification
+            };
+
+            if verification.is_some_and(|v| !v.is_consistent()) {
+                eprintln!("Error: target backend does not match source");
+                std::process::exit(1);
+            }
+        }
+
+        Commands::Retention { action } => {
+            use llm_incident_manager::config::Config;
+            use llm_incident_manager::retention::RetentionEngine;
+            use llm_incident_manager::state::create_store;
+
+            let config = Config::load()?;
+            let store = create_store(&config.state).await?;
+            let engine = RetentionEngine::new(store, &config.retention)?;
+
+            match action {
+                RetentionCommands::Run { dry_run } => {
+                    let report = if dry_run {
+                        engine.dry_run().await?
+                    } else {
+                        engine.run().await?
+                    };
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                }
+
+                RetentionCommands::Restore { id, file } => {
+                    if let Some(file) = file {
+                        let report = engine.restore_file(&file).await?;
+                        println!("{}", serde_json::to_string_pretty(&report)?);
+                    } else if let Some(id) = id {
+                        let id = uuid::Uuid::parse_str(&id)?;
+                        let incident = engine.restore(&id).await?;
+                        println!("{}", serde_json::to_string_pretty(&incident)?);
+                    }
+                }
+
+                RetentionCommands::List => {
+                    for path in engine.archive().list()? {
+                        println!("{}", path.display());
+                    }
+                }
+            }
+        }
+
+        Commands::Config { action } => match action {
+            ConfigCommands::Validate { dir } => {
+                match llm_incident_manager::gitops::load_dir(&dir) {
+                    Ok(set) => println!(
+                        "{}: {} playbooks, {} escalation policies, {} routing rules",
+                        dir.display(),
+                        set.playbooks.len(),
+                        set.escalation_policies.len(),
+                        set.routing_rules.len()
+                    ),
+                    Err(errors) => {
+                        for e in &errors {
+                            eprintln!("{}", e);
+                        }
+                        eprintln!("Error: {} problems found", errors.len());
+                        std::process::exit(1);
+                    }
+                }
+            }
+        },
     }
 
     Ok(())
 }
+
+/// Build a state backend configuration from `migrate` arguments
+fn state_config(
+    backend: &str,
+    path: Option<PathBuf>,
+    urls: Vec<String>,
+) -> Result<llm_incident_manager::config::StateConfig, Box<dyn Error>> {
+    use llm_incident_manager::config::{StateBackend, StateConfig};
+
+    let backend: StateBackend = serde_json::from_value(json!(backend))
+        .map_err(|_| format!("Unknown backend '{}'", backend))?;
+
+    Ok(StateConfig {
+        redis_url: urls.first().cloned(),
+        redis_cluster_nodes: if backend == StateBackend::RedisCluster {
+            urls
+        } else {
+            vec![]
+        },
+        backend,
+        path,
+        pool_size: 10,
+        dual_write: None,
+    })
+}
@@ -1,3 +1,9 @@
+use crate::analytics::ReportingConfig;
+use crate::gitops::GitOpsConfig;
+use crate::notifications::NotificationActionsConfig;
+use crate::playbooks::PlaybookConfig;
+use crate::retention::RetentionConfig;
+use crate::staleness::StalenessConfig;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -29,6 +35,27 @@ pub struct Config {
 
     /// Notification configuration
     pub notifications: NotificationConfig,
+
+    /// Retention and archival configuration
+    #[serde(default)]
+    pub retention: RetentionConfig,
+
+    /// Stale incident detection configuration
+    #[serde(default)]
+    pub staleness: StalenessConfig,
+
+    /// Scheduled report configuration
+    #[serde(default)]
+    pub reporting: ReportingConfig,
+
+    /// Playbook engine configuration
+    #[serde(default)]
+    pub playbooks: PlaybookConfig,
+
+    /// Directory playbooks, escalation policies and routing rules are
+    /// loaded from
+    #[serde(default)]
+    pub gitops: GitOpsConfig,
 }
 
 impl Config {
@@ -147,6 +174,11 @@ pub struct StateConfig {
     /// Database connection pool size
     #[serde(default = "default_pool_size")]
     pub pool_size: u32,
+
+    /// Secondary backend that receives a copy of every write while
+    /// migrating between backends (see `llm-im-cli migrate`)
+    #[serde(default)]
+    pub dual_write: Option<Box<StateConfig>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
@@ -360,6 +392,10 @@ pub struct NotificationConfig {
     /// Number of worker threads for sending notifications
     #[serde(default = "default_notification_workers")]
     pub worker_threads: usize,
+
+    /// Acknowledging, resolving and reassigning incidents from notifications
+    #[serde(default)]
+    pub actions: NotificationActionsConfig,
 }
 
 // Default value functions
@@ -205,6 +205,10 @@ impl CorrelationEngine {
         // Process correlations: update or create groups
         if !result.correlations.is_empty() {
             self.process_correlations(incident, &mut result).await?;
+
+            for group_id in &result.groups_affected {
+                self.persist_group_membership(group_id).await;
+            }
         }
 
         result.processing_time_ms = start.elapsed().as_millis() as u64;
@@ -229,12 +233,7 @@ impl CorrelationEngine {
     ) -> Result<Vec<Incident>> {
         // For now, use a simple filter based on temporal window
         // In production, this should use more sophisticated queries
-        let filter = crate::state::IncidentFilter {
-            severities: Vec::new(),
-            states: Vec::new(),
-            sources: Vec::new(),
-            active_only: false,
-        };
+        let filter = crate::state::IncidentFilter::default();
 
         // Fetch incidents
         let candidates = self
@@ -251,6 +250,42 @@ impl CorrelationEngine {
         Ok(candidates)
     }
 
+    /// Record a group's ID on each of its incidents so they can be queried by group
+    ///
+    /// Failures are logged rather than returned; the in-memory group stays
+    /// authoritative for the engine itself.
+    async fn persist_group_membership(&self, group_id: &Uuid) {
+        let Some(incident_ids) = self.groups.get(group_id).map(|g| g.all_incident_ids()) else {
+            return;
+        };
+
+        for incident_id in incident_ids {
+            let current = self.incident_store.get_incident(&incident_id).await;
+            if let Ok(Some(ref stored)) = current {
+                if stored.correlation_group_id == Some(*group_id) {
+                    continue;
+                }
+            }
+
+            let result = crate::state::modify_incident(
+                self.incident_store.as_ref(),
+                &incident_id,
+                |stored| {
+                    stored.correlation_group_id = Some(*group_id);
+                    Ok(())
+                },
+            )
+            .await;
+
+            if let Err(e) = result {
+                warn!(
+                    "Failed to record correlation group {} on incident {}: {}",
+                    group_id, incident_id, e
+                );
+            }
+        }
+    }
+
     /// Process detected correlations and update/create groups
     async fn process_correlations(
         &self,
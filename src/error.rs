@@ -69,6 +69,14 @@ pub enum AppError {
     #[error("Invalid state transition: {0}")]
     InvalidStateTransition(String),
 
+    /// Optimistic concurrency conflict (stale version on compare-and-swap)
+    #[error("Version conflict on {resource}: expected version {expected}, found {actual}")]
+    VersionConflict {
+        resource: String,
+        expected: u64,
+        actual: u64,
+    },
+
     /// Execution context violation (missing or invalid execution headers)
     #[error("Execution violation: {0}")]
     ExecutionViolation(String),
@@ -93,6 +101,7 @@ impl AppError {
             AppError::Integration { .. } => StatusCode::BAD_GATEWAY,
             AppError::Processing(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InvalidStateTransition(_) => StatusCode::CONFLICT,
+            AppError::VersionConflict { .. } => StatusCode::CONFLICT,
             AppError::ExecutionViolation(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -115,6 +124,7 @@ impl AppError {
             AppError::Integration { .. } => "INTEGRATION_ERROR",
             AppError::Processing(_) => "PROCESSING_ERROR",
             AppError::InvalidStateTransition(_) => "INVALID_STATE_TRANSITION",
+            AppError::VersionConflict { .. } => "VERSION_CONFLICT",
             AppError::ExecutionViolation(_) => "EXECUTION_VIOLATION",
         }
     }
@@ -213,4 +223,15 @@ mod tests {
         );
         assert_eq!(AppError::RateLimit.error_code(), "RATE_LIMIT_EXCEEDED");
     }
+
+    #[test]
+    fn test_version_conflict() {
+        let err = AppError::VersionConflict {
+            resource: "incident 1".to_string(),
+            expected: 1,
+            actual: 2,
+        };
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(err.error_code(), "VERSION_CONFLICT");
+    }
 }
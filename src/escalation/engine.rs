@@ -2,10 +2,11 @@ use crate::error::{AppError, Result};
 use crate::escalation::executor::{EscalationLevelExecutor, EscalationLevelResult};
 use crate::escalation::state::{EscalationState, EscalationStatus};
 use crate::models::policy::EscalationPolicy;
-use crate::models::Incident;
+use crate::models::{EventType, Incident, TimelineEvent};
 use crate::notifications::NotificationService;
-use crate::state::IncidentStore;
+use crate::state::{modify_incident, IncidentStore};
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::time::{sleep, Duration};
 use uuid::Uuid;
@@ -36,7 +37,9 @@ impl EscalationEngine {
         Self {
             escalations: Arc::new(DashMap::new()),
             policies: Arc::new(DashMap::new()),
-            executor: Arc::new(EscalationLevelExecutor::new(notification_service)),
+            executor: Arc::new(
+                EscalationLevelExecutor::new(notification_service).with_store(store.clone()),
+            ),
             store,
             check_interval_secs: 30, // Check every 30 seconds by default
         }
@@ -55,11 +58,7 @@ impl EscalationEngine {
 
     /// Register an escalation policy
     pub fn register_policy(&self, policy: EscalationPolicy) -> Result<()> {
-        if policy.levels.is_empty() {
-            return Err(AppError::Validation(
-                "Escalation policy must have at least one level".to_string(),
-            ));
-        }
+        validate_policy(&policy)?;
 
         tracing::info!(
             policy_id = %policy.id,
@@ -72,6 +71,11 @@ impl EscalationEngine {
         Ok(())
     }
 
+    /// Remove a policy
+    pub fn remove_policy(&self, policy_id: &Uuid) -> Option<EscalationPolicy> {
+        self.policies.remove(policy_id).map(|(_, policy)| policy)
+    }
+
     /// Get a policy by ID
     pub fn get_policy(&self, policy_id: &Uuid) -> Option<EscalationPolicy> {
         self.policies.get(policy_id).map(|e| e.value().clone())
@@ -99,7 +103,7 @@ impl EscalationEngine {
     }
 
     /// Start escalation for an incident
-    pub fn start_escalation(&self, incident: &Incident, policy_id: Uuid) -> Result<Uuid> {
+    pub async fn start_escalation(&self, incident: &Incident, policy_id: Uuid) -> Result<Uuid> {
         // Check if incident already has active escalation
         if let Some(existing) = self.escalations.get(&incident.id) {
             if existing.status == EscalationStatus::Active {
@@ -139,25 +143,43 @@ impl EscalationEngine {
         );
 
         self.escalations.insert(incident.id, state);
+        self.persist(&incident.id).await;
 
         Ok(incident.id)
     }
 
     /// Acknowledge an escalation
-    pub fn acknowledge_escalation(&self, incident_id: &Uuid, acknowledged_by: String) -> Result<()> {
-        let mut state = self
-            .escalations
-            .get_mut(incident_id)
-            .ok_or_else(|| AppError::NotFound(format!("No escalation for incident {}", incident_id)))?;
+    ///
+    /// If the acknowledging user belongs to a team notified by a
+    /// first-to-acknowledge target, they are assigned the incident.
+    pub async fn acknowledge_escalation(
+        &self,
+        incident_id: &Uuid,
+        acknowledged_by: String,
+    ) -> Result<()> {
+        let claimed = {
+            let mut state = self.escalations.get_mut(incident_id).ok_or_else(|| {
+                AppError::NotFound(format!("No escalation for incident {}", incident_id))
+            })?;
 
-        if state.status != EscalationStatus::Active {
-            return Err(AppError::Validation(format!(
-                "Escalation for incident {} is not active",
-                incident_id
-            )));
-        }
+            if state.status != EscalationStatus::Active {
+                return Err(AppError::Validation(format!(
+                    "Escalation for incident {} is not active",
+                    incident_id
+                )));
+            }
 
-        state.acknowledge(acknowledged_by.clone());
+            state.acknowledge(acknowledged_by.clone());
+            state.claim_first_to_acknowledge(&acknowledged_by, |team_id| {
+                self.executor.is_team_member(team_id, &acknowledged_by)
+            })
+        };
+        self.persist(incident_id).await;
+
+        if !claimed.is_empty() {
+            self.assign_acknowledger(incident_id, &acknowledged_by, &claimed)
+                .await;
+        }
 
         tracing::info!(
             incident_id = %incident_id,
@@ -168,14 +190,59 @@ impl EscalationEngine {
         Ok(())
     }
 
+    /// Assign an incident to the winner of its first-to-acknowledge targets
+    ///
+    /// Failures are logged rather than returned, as the acknowledgement has
+    /// already been recorded.
+    async fn assign_acknowledger(&self, incident_id: &Uuid, user: &str, teams: &[String]) {
+        let assigned = modify_incident(self.store.as_ref(), incident_id, |incident| {
+            if incident.assignees.iter().any(|assignee| assignee == user) {
+                return Ok(());
+            }
+            incident.assignees.push(user.to_string());
+            incident.add_timeline_event(TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: EventType::AssignmentChanged,
+                actor: "system".to_string(),
+                description: format!(
+                    "Assigned to {} as first to acknowledge for team {}",
+                    user,
+                    teams.join(", ")
+                ),
+                metadata: HashMap::from([(
+                    "strategy".to_string(),
+                    "first_to_acknowledge".to_string(),
+                )]),
+            });
+            Ok(())
+        })
+        .await;
+
+        match assigned {
+            Ok(_) => tracing::info!(
+                incident_id = %incident_id,
+                assignee = %user,
+                teams = ?teams,
+                "Assigned incident to first responder to acknowledge"
+            ),
+            Err(e) => tracing::error!(
+                incident_id = %incident_id,
+                assignee = %user,
+                error = %e,
+                "Failed to assign incident to acknowledging responder"
+            ),
+        }
+    }
+
     /// Resolve an escalation (when incident is resolved)
-    pub fn resolve_escalation(&self, incident_id: &Uuid) -> Result<()> {
-        let mut state = self
-            .escalations
+    pub async fn resolve_escalation(&self, incident_id: &Uuid) -> Result<()> {
+        self.escalations
             .get_mut(incident_id)
-            .ok_or_else(|| AppError::NotFound(format!("No escalation for incident {}", incident_id)))?;
-
-        state.resolve();
+            .ok_or_else(|| {
+                AppError::NotFound(format!("No escalation for incident {}", incident_id))
+            })?
+            .resolve();
+        self.persist(incident_id).await;
 
         tracing::info!(
             incident_id = %incident_id,
@@ -186,13 +253,14 @@ impl EscalationEngine {
     }
 
     /// Cancel an escalation
-    pub fn cancel_escalation(&self, incident_id: &Uuid) -> Result<()> {
-        let mut state = self
-            .escalations
+    pub async fn cancel_escalation(&self, incident_id: &Uuid) -> Result<()> {
+        self.escalations
             .get_mut(incident_id)
-            .ok_or_else(|| AppError::NotFound(format!("No escalation for incident {}", incident_id)))?;
-
-        state.cancel();
+            .ok_or_else(|| {
+                AppError::NotFound(format!("No escalation for incident {}", incident_id))
+            })?
+            .cancel();
+        self.persist(incident_id).await;
 
         tracing::info!(
             incident_id = %incident_id,
@@ -202,6 +270,46 @@ impl EscalationEngine {
         Ok(())
     }
 
+    /// Escalate an incident immediately
+    ///
+    /// Moves an active escalation to its next level without waiting for the
+    /// level delay, or starts one under the incident's policy if none is
+    /// active. Acknowledged escalations are left alone. Returns whether the
+    /// incident was escalated.
+    pub async fn escalate_now(&self, incident: &Incident) -> Result<bool> {
+        let status = self
+            .escalations
+            .get(&incident.id)
+            .map(|state| state.status.clone());
+
+        match status {
+            Some(EscalationStatus::Acknowledged) => return Ok(false),
+            Some(EscalationStatus::Active) => {}
+            _ => {
+                let Some(policy) = self.find_policy_for_incident(incident) else {
+                    tracing::debug!(
+                        incident_id = %incident.id,
+                        "No escalation policy applies to incident"
+                    );
+                    return Ok(false);
+                };
+
+                // Replace any finished escalation with a new one
+                self.escalations.remove(&incident.id);
+                self.start_escalation(incident, policy.id).await?;
+            }
+        }
+
+        if let Some(mut state) = self.escalations.get_mut(&incident.id) {
+            state.next_escalation_at = Some(chrono::Utc::now());
+        }
+        self.check_and_escalate(&incident.id).await?;
+
+        tracing::info!(incident_id = %incident.id, "Escalated incident on demand");
+
+        Ok(true)
+    }
+
     /// Get escalation state for an incident
     pub fn get_escalation_state(&self, incident_id: &Uuid) -> Option<EscalationState> {
         self.escalations.get(incident_id).map(|e| e.value().clone())
@@ -216,13 +324,115 @@ impl EscalationEngine {
             .collect()
     }
 
+    /// Load escalation states saved by an earlier process
+    ///
+    /// Escalations already in memory are kept. A loaded escalation that is
+    /// still active is resolved if its incident has since been resolved or
+    /// closed, cancelled if the incident no longer exists, and otherwise
+    /// caught up: every level that became due while the process was down is
+    /// run in order. Returns how many escalations were loaded.
+    pub async fn restore_escalations(&self) -> Result<usize> {
+        let states = self.store.list_escalation_states().await?;
+        let count = states.len();
+
+        let mut active = Vec::new();
+        for state in states {
+            if self.escalations.contains_key(&state.incident_id) {
+                continue;
+            }
+            if state.status == EscalationStatus::Active {
+                active.push(state.incident_id);
+            }
+            self.escalations.insert(state.incident_id, state);
+        }
+
+        tracing::info!(
+            escalations = count,
+            active = active.len(),
+            "Restored escalation states"
+        );
+
+        for incident_id in active {
+            if let Err(e) = self.recover_escalation(&incident_id).await {
+                tracing::error!(
+                    incident_id = %incident_id,
+                    error = %e,
+                    "Failed to catch up on escalation"
+                );
+            }
+        }
+        Ok(count)
+    }
+
+    /// Bring an active escalation loaded after a restart up to date
+    async fn recover_escalation(&self, incident_id: &Uuid) -> Result<()> {
+        match self.store.get_incident(incident_id).await? {
+            None => return self.cancel_escalation(incident_id).await,
+            Some(incident) if !incident.is_active() => {
+                return self.resolve_escalation(incident_id).await
+            }
+            Some(_) => {}
+        }
+
+        loop {
+            let due = self
+                .escalations
+                .get(incident_id)
+                .filter(|state| state.should_escalate())
+                .and_then(|state| state.next_escalation_at);
+            let Some(due) = due else {
+                return Ok(());
+            };
+
+            tracing::info!(
+                incident_id = %incident_id,
+                due_at = %due,
+                "Catching up on escalation level missed during restart"
+            );
+            self.check_and_escalate(incident_id).await?;
+
+            // The next level is due its delay after this one was due rather
+            // than after it was caught up on, so overdue levels run in turn
+            if let Some(mut state) = self.escalations.get_mut(incident_id) {
+                if state.status == EscalationStatus::Active {
+                    let late = state.level_reached_at - due;
+                    state.next_escalation_at = state.next_escalation_at.map(|next| next - late);
+                }
+            }
+            self.persist(incident_id).await;
+        }
+    }
+
+    /// Save an incident's escalation state to the store
+    ///
+    /// Failures are logged rather than returned: the escalation carries on
+    /// in memory and is saved again on its next change.
+    async fn persist(&self, incident_id: &Uuid) {
+        let Some(state) = self.get_escalation_state(incident_id) else {
+            return;
+        };
+        if let Err(e) = self.store.save_escalation_state(&state).await {
+            tracing::error!(
+                incident_id = %incident_id,
+                error = %e,
+                "Failed to persist escalation state"
+            );
+        }
+    }
+
     /// Run the escalation monitor loop
+    ///
+    /// Escalations saved before a restart are restored first.
     pub async fn run_monitor(self: Arc<Self>) {
         tracing::info!(
             check_interval = self.check_interval_secs,
             "Starting escalation monitor"
         );
 
+        if let Err(e) = self.restore_escalations().await {
+            tracing::error!(error = %e, "Failed to restore escalation states");
+        }
+
         loop {
             // Check all active escalations
             let active_escalations = self.list_active_escalations();
@@ -304,6 +514,7 @@ impl EscalationEngine {
 
         // Move to next level or complete
         self.advance_escalation(incident_id, &policy, &_result).await?;
+        self.persist(incident_id).await;
 
         Ok(())
     }
@@ -405,6 +616,16 @@ impl EscalationEngine {
     }
 }
 
+/// Check that a policy can be registered
+pub fn validate_policy(policy: &EscalationPolicy) -> Result<()> {
+    if policy.levels.is_empty() {
+        return Err(AppError::Validation(
+            "Escalation policy must have at least one level".to_string(),
+        ));
+    }
+    Ok(())
+}
+
 /// Escalation engine statistics
 #[derive(Debug, Clone)]
 pub struct EscalationStats {
@@ -493,7 +714,7 @@ mod tests {
         let incident = create_test_incident();
         store.save_incident(&incident).await.unwrap();
 
-        let result = engine.start_escalation(&incident, policy_id);
+        let result = engine.start_escalation(&incident, policy_id).await;
         assert!(result.is_ok());
 
         let state = engine.get_escalation_state(&incident.id);
@@ -513,9 +734,10 @@ mod tests {
         let incident = create_test_incident();
         store.save_incident(&incident).await.unwrap();
 
-        engine.start_escalation(&incident, policy_id).unwrap();
+        engine.start_escalation(&incident, policy_id).await.unwrap();
         engine
             .acknowledge_escalation(&incident.id, "oncall@example.com".to_string())
+            .await
             .unwrap();
 
         let state = engine.get_escalation_state(&incident.id).unwrap();
@@ -527,6 +749,50 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_first_to_acknowledge_assigns_incident() {
+        let store = Arc::new(InMemoryStore::new());
+        let engine = EscalationEngine::new(None, store.clone());
+        engine.executor().register_team(
+            "platform".to_string(),
+            vec![
+                "alice@example.com".to_string(),
+                "bob@example.com".to_string(),
+            ],
+        );
+
+        let mut policy = create_test_policy();
+        policy.levels[0].targets = vec![EscalationTarget::FirstToAcknowledge {
+            team_id: "platform".to_string(),
+        }];
+        let policy_id = policy.id;
+        engine.register_policy(policy).unwrap();
+
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        engine.start_escalation(&incident, policy_id).await.unwrap();
+        assert!(engine.escalate_now(&incident).await.unwrap());
+        engine
+            .acknowledge_escalation(&incident.id, "bob@example.com".to_string())
+            .await
+            .unwrap();
+
+        let state = engine.get_escalation_state(&incident.id).unwrap();
+        assert_eq!(state.selections.len(), 1);
+        assert_eq!(
+            state.selections[0].responder.as_deref(),
+            Some("bob@example.com")
+        );
+
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(stored.assignees, vec!["bob@example.com".to_string()]);
+        assert!(stored
+            .timeline
+            .iter()
+            .any(|event| event.event_type == EventType::AssignmentChanged));
+    }
+
     #[tokio::test]
     async fn test_resolve_escalation() {
         let store = Arc::new(InMemoryStore::new());
@@ -539,8 +805,8 @@ mod tests {
         let incident = create_test_incident();
         store.save_incident(&incident).await.unwrap();
 
-        engine.start_escalation(&incident, policy_id).unwrap();
-        engine.resolve_escalation(&incident.id).unwrap();
+        engine.start_escalation(&incident, policy_id).await.unwrap();
+        engine.resolve_escalation(&incident.id).await.unwrap();
 
         let state = engine.get_escalation_state(&incident.id).unwrap();
         assert_eq!(state.status, EscalationStatus::Resolved);
@@ -561,8 +827,14 @@ mod tests {
         store.save_incident(&incident1).await.unwrap();
         store.save_incident(&incident2).await.unwrap();
 
-        engine.start_escalation(&incident1, policy_id).unwrap();
-        engine.start_escalation(&incident2, policy_id).unwrap();
+        engine
+            .start_escalation(&incident1, policy_id)
+            .await
+            .unwrap();
+        engine
+            .start_escalation(&incident2, policy_id)
+            .await
+            .unwrap();
 
         let active = engine.list_active_escalations();
         assert_eq!(active.len(), 2);
@@ -570,6 +842,7 @@ mod tests {
         // Acknowledge one
         engine
             .acknowledge_escalation(&incident1.id, "user@example.com".to_string())
+            .await
             .unwrap();
 
         let active = engine.list_active_escalations();
@@ -606,10 +879,17 @@ mod tests {
         store.save_incident(&incident1).await.unwrap();
         store.save_incident(&incident2).await.unwrap();
 
-        engine.start_escalation(&incident1, policy_id).unwrap();
-        engine.start_escalation(&incident2, policy_id).unwrap();
+        engine
+            .start_escalation(&incident1, policy_id)
+            .await
+            .unwrap();
+        engine
+            .start_escalation(&incident2, policy_id)
+            .await
+            .unwrap();
         engine
             .acknowledge_escalation(&incident1.id, "user@example.com".to_string())
+            .await
             .unwrap();
 
         let stats = engine.get_stats();
@@ -631,9 +911,107 @@ mod tests {
         let incident = create_test_incident();
         store.save_incident(&incident).await.unwrap();
 
-        engine.start_escalation(&incident, policy_id).unwrap();
-        let result = engine.start_escalation(&incident, policy_id);
+        engine.start_escalation(&incident, policy_id).await.unwrap();
+        let result = engine.start_escalation(&incident, policy_id).await;
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_escalations_restored_after_restart() {
+        let store = Arc::new(InMemoryStore::new());
+        let policy = create_test_policy();
+
+        let overdue = create_test_incident();
+        let mut resolved = create_test_incident();
+        let acknowledged = create_test_incident();
+        {
+            let engine = EscalationEngine::new(None, store.clone());
+            engine.register_policy(policy.clone()).unwrap();
+            for incident in [&overdue, &resolved, &acknowledged] {
+                store.save_incident(incident).await.unwrap();
+                engine.start_escalation(incident, policy.id).await.unwrap();
+            }
+            engine
+                .acknowledge_escalation(&acknowledged.id, "oncall@example.com".to_string())
+                .await
+                .unwrap();
+        }
+
+        // The process was down while the first level of two escalations
+        // became due and one of their incidents was resolved
+        let due = chrono::Utc::now() - chrono::Duration::minutes(3);
+        for incident in [&overdue, &resolved] {
+            let mut state = store
+                .get_escalation_state(&incident.id)
+                .await
+                .unwrap()
+                .unwrap();
+            state.next_escalation_at = Some(due);
+            store.save_escalation_state(&state).await.unwrap();
+        }
+        resolved.state = crate::models::IncidentState::Resolved;
+        store.save_incident(&resolved).await.unwrap();
+
+        let engine = EscalationEngine::new(None, store.clone());
+        engine.register_policy(policy).unwrap();
+        assert_eq!(engine.restore_escalations().await.unwrap(), 3);
+
+        // The missed level ran, and the next one is due five minutes after
+        // the missed one was, not five minutes after the restart
+        let state = engine.get_escalation_state(&overdue.id).unwrap();
+        assert_eq!(state.current_level, 1);
+        assert_eq!(state.status, EscalationStatus::Active);
+        let next_due = state.next_escalation_at.unwrap() - (due + chrono::Duration::minutes(5));
+        assert!(next_due.num_seconds().abs() < 1);
+        let saved = store
+            .get_escalation_state(&overdue.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.current_level, 1);
+        assert_eq!(
+            saved.notification_history.len(),
+            state.notification_history.len()
+        );
+
+        let state = engine.get_escalation_state(&resolved.id).unwrap();
+        assert_eq!(state.status, EscalationStatus::Resolved);
+        assert_eq!(state.current_level, 0);
+
+        let state = engine.get_escalation_state(&acknowledged.id).unwrap();
+        assert_eq!(state.status, EscalationStatus::Acknowledged);
+        assert_eq!(state.acknowledged_by.as_deref(), Some("oncall@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_escalate_now() {
+        let store = Arc::new(InMemoryStore::new());
+        let engine = EscalationEngine::new(None, store.clone());
+        engine.register_policy(create_test_policy()).unwrap();
+
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        // Starts an escalation and runs its first level
+        assert!(engine.escalate_now(&incident).await.unwrap());
+        assert_eq!(engine.get_escalation_state(&incident.id).unwrap().current_level, 1);
+
+        // Skips the delay of the next level
+        assert!(engine.escalate_now(&incident).await.unwrap());
+        assert_eq!(
+            engine.get_escalation_state(&incident.id).unwrap().repeat_count,
+            1
+        );
+
+        engine
+            .acknowledge_escalation(&incident.id, "user@example.com".to_string())
+            .await
+            .unwrap();
+        assert!(!engine.escalate_now(&incident).await.unwrap());
+
+        let mut low = create_test_incident();
+        low.severity = Severity::P3;
+        assert!(!engine.escalate_now(&low).await.unwrap());
+    }
 }
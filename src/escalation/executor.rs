@@ -1,12 +1,21 @@
 use crate::error::{AppError, Result};
-use crate::escalation::schedule::{OnCallUser, ScheduleResolver};
-use crate::escalation::state::{EscalationNotification, EscalationState};
-use crate::models::policy::{EscalationLevel, EscalationTarget, OnCallSchedule};
+use crate::escalation::ical;
+use crate::escalation::schedule::{
+    validate_override, validate_swap, OnCallUser, ScheduleResolver, MAX_PREVIEW_WEEKS,
+};
+use crate::escalation::state::{
+    EscalationNotification, EscalationState, ResponderSelection, SelectionStrategy,
+};
+use crate::models::policy::{
+    EscalationLevel, EscalationTarget, OnCallSchedule, ScheduleOverride, ScheduleShift, ShiftSwap,
+};
 use crate::models::Incident;
 use crate::notifications::NotificationService;
-use chrono::Utc;
+use crate::state::{IncidentFilter, IncidentStore};
+use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 /// Executes escalation levels by resolving targets and sending notifications
 pub struct EscalationLevelExecutor {
@@ -14,6 +23,10 @@ pub struct EscalationLevelExecutor {
     schedule_resolver: ScheduleResolver,
     schedules: Arc<DashMap<String, OnCallSchedule>>,
     teams: Arc<DashMap<String, Vec<String>>>,
+    /// Next member index per team for round-robin selection
+    rotations: Arc<DashMap<String, usize>>,
+    /// Store used to count each responder's active incidents
+    store: Option<Arc<dyn IncidentStore>>,
 }
 
 impl EscalationLevelExecutor {
@@ -23,9 +36,17 @@ impl EscalationLevelExecutor {
             schedule_resolver: ScheduleResolver::new(),
             schedules: Arc::new(DashMap::new()),
             teams: Arc::new(DashMap::new()),
+            rotations: Arc::new(DashMap::new()),
+            store: None,
         }
     }
 
+    /// Set the store used to count active incidents for least-loaded targets
+    pub fn with_store(mut self, store: Arc<dyn IncidentStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
     /// Register an on-call schedule
     pub fn register_schedule(&self, schedule: OnCallSchedule) {
         self.schedules.insert(schedule.id.to_string(), schedule);
@@ -54,11 +75,13 @@ impl EscalationLevelExecutor {
             notifications_sent: 0,
             notifications_failed: 0,
             targets_resolved: Vec::new(),
+            selections: Vec::new(),
             errors: Vec::new(),
         };
 
         // Resolve all targets to actual recipients
         let recipients = self.resolve_targets(&level.targets, &mut result).await?;
+        state.selections.extend(result.selections.iter().cloned());
 
         // Send notifications to each recipient
         for recipient in recipients {
@@ -162,6 +185,33 @@ impl EscalationLevelExecutor {
                         }
                     }
                 }
+                EscalationTarget::RoundRobin { team_id } => {
+                    self.resolve_team_selection(
+                        team_id,
+                        SelectionStrategy::RoundRobin,
+                        &mut recipients,
+                        result,
+                    )
+                    .await;
+                }
+                EscalationTarget::LeastLoaded { team_id } => {
+                    self.resolve_team_selection(
+                        team_id,
+                        SelectionStrategy::LeastLoaded,
+                        &mut recipients,
+                        result,
+                    )
+                    .await;
+                }
+                EscalationTarget::FirstToAcknowledge { team_id } => {
+                    self.resolve_team_selection(
+                        team_id,
+                        SelectionStrategy::FirstToAcknowledge,
+                        &mut recipients,
+                        result,
+                    )
+                    .await;
+                }
                 EscalationTarget::Schedule { schedule_id } => {
                     match self.resolve_schedule(schedule_id) {
                         Ok(oncall_users) => {
@@ -207,6 +257,181 @@ impl EscalationLevelExecutor {
         Ok(recipients)
     }
 
+    /// Resolve a target that picks responders from a team
+    ///
+    /// The selection made is recorded on the level result.
+    async fn resolve_team_selection(
+        &self,
+        team_id: &str,
+        strategy: SelectionStrategy,
+        recipients: &mut Vec<NotificationRecipient>,
+        result: &mut EscalationLevelResult,
+    ) {
+        let (notify, selection) = match self.select_responder(team_id, strategy, result.level).await
+        {
+            Ok(selected) => selected,
+            Err(e) => {
+                result
+                    .errors
+                    .push(format!("Failed to resolve team {}: {}", team_id, e));
+                tracing::warn!(
+                    team_id = %team_id,
+                    error = %e,
+                    "Failed to resolve team"
+                );
+                return;
+            }
+        };
+
+        tracing::info!(
+            team_id = %team_id,
+            strategy = ?strategy,
+            responder = ?selection.responder,
+            reason = %selection.reason,
+            "Selected responder from team"
+        );
+
+        let chosen = match &selection.responder {
+            Some(responder) => responder.clone(),
+            None => format!("{} members", notify.len()),
+        };
+        result
+            .targets_resolved
+            .push(format!("Team: {} ({:?}: {})", team_id, strategy, chosen));
+        for member in notify {
+            recipients.push(NotificationRecipient {
+                email: member,
+                channel: "email".to_string(),
+                source: format!("team:{}", team_id),
+            });
+        }
+        result.selections.push(selection);
+    }
+
+    /// Pick who to notify from a team
+    ///
+    /// Returns the members to notify along with the selection made: the one
+    /// chosen member for round-robin and least-loaded targets, or the whole
+    /// team for first-to-acknowledge targets.
+    async fn select_responder(
+        &self,
+        team_id: &str,
+        strategy: SelectionStrategy,
+        level: u32,
+    ) -> Result<(Vec<String>, ResponderSelection)> {
+        let members = self.resolve_team(team_id)?;
+        if members.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Team {} has no members",
+                team_id
+            )));
+        }
+
+        let (responder, reason) = match strategy {
+            SelectionStrategy::RoundRobin => {
+                let index = self.next_in_rotation(team_id, members.len(), |_| true);
+                let reason = format!("Next in rotation ({} of {})", index + 1, members.len());
+                (Some(members[index].clone()), reason)
+            }
+            SelectionStrategy::LeastLoaded => match self.count_active_assignments(&members).await {
+                Some(counts) => {
+                    let fewest = counts.iter().copied().min().unwrap_or(0);
+                    let tied = counts.iter().filter(|&&count| count == fewest).count();
+                    let index =
+                        self.next_in_rotation(team_id, members.len(), |i| counts[i] == fewest);
+                    let mut reason = format!("Fewest active incidents assigned ({})", fewest);
+                    if tied > 1 {
+                        reason.push_str(&format!(", next in rotation of {} tied", tied));
+                    }
+                    (Some(members[index].clone()), reason)
+                }
+                None => {
+                    let index = self.next_in_rotation(team_id, members.len(), |_| true);
+                    let reason = "Active incidents could not be counted; next in rotation";
+                    (Some(members[index].clone()), reason.to_string())
+                }
+            },
+            SelectionStrategy::FirstToAcknowledge => (
+                None,
+                format!(
+                    "Notified all {} members; the first to acknowledge is assigned",
+                    members.len()
+                ),
+            ),
+        };
+
+        let notify = match &responder {
+            Some(responder) => vec![responder.clone()],
+            None => members,
+        };
+        Ok((
+            notify,
+            ResponderSelection {
+                level,
+                team_id: team_id.to_string(),
+                strategy,
+                responder,
+                reason,
+            },
+        ))
+    }
+
+    /// Take the next member of a team's rotation accepted by `eligible`
+    ///
+    /// Members are tried in turn from where the rotation last stopped. If
+    /// none is eligible the next member is taken regardless.
+    fn next_in_rotation(
+        &self,
+        team_id: &str,
+        team_size: usize,
+        eligible: impl Fn(usize) -> bool,
+    ) -> usize {
+        let mut cursor = self.rotations.entry(team_id.to_string()).or_insert(0);
+        let start = *cursor % team_size;
+        let index = (0..team_size)
+            .map(|offset| (start + offset) % team_size)
+            .find(|&i| eligible(i))
+            .unwrap_or(start);
+        *cursor = (index + 1) % team_size;
+        index
+    }
+
+    /// Count the active incidents assigned to each member
+    ///
+    /// Returns `None` if there is no store or it could not be queried.
+    async fn count_active_assignments(&self, members: &[String]) -> Option<Vec<u64>> {
+        let store = self.store.as_ref()?;
+        let mut counts = Vec::with_capacity(members.len());
+        for member in members {
+            let filter = IncidentFilter {
+                assignees: vec![member.clone()],
+                active_only: true,
+                ..Default::default()
+            };
+            match store.count_incidents(&filter).await {
+                Ok(count) => counts.push(count),
+                Err(e) => {
+                    tracing::warn!(
+                        assignee = %member,
+                        error = %e,
+                        "Failed to count active incidents"
+                    );
+                    return None;
+                }
+            }
+        }
+        Some(counts)
+    }
+
+    /// Check whether a user is a member of a registered team
+    pub fn is_team_member(&self, team_id: &str, user: &str) -> bool {
+        self.teams.get(team_id).is_some_and(|members| {
+            members
+                .iter()
+                .any(|member| member.eq_ignore_ascii_case(user))
+        })
+    }
+
     /// Resolve team members
     fn resolve_team(&self, team_id: &str) -> Result<Vec<String>> {
         self.teams
@@ -250,13 +475,25 @@ impl EscalationLevelExecutor {
         level: u32,
     ) -> Result<()> {
         if let Some(ref notif_service) = self.notification_service {
+            let mut body = self.build_notification_message(incident, level);
+            // One-click links are signed for this recipient, so they can only
+            // go into emails with a single recipient
+            if let Some(links) = notif_service.actions().links() {
+                body.push_str("\n\n");
+                body.push_str(&links.render_text(
+                    incident.id,
+                    &recipient.email,
+                    chrono::Utc::now(),
+                ));
+            }
+
             let notification = crate::models::Notification {
                 id: uuid::Uuid::new_v4(),
                 incident_id: incident.id,
                 channel: crate::models::NotificationChannel::Email {
                     to: vec![recipient.email.clone()],
                     subject: format!("Escalation Level {} - {}", level, incident.title),
-                    body: self.build_notification_message(incident, level),
+                    body,
                 },
                 status: crate::models::NotificationStatus::Pending,
                 created_at: chrono::Utc::now(),
@@ -342,6 +579,156 @@ impl EscalationLevelExecutor {
             .collect()
     }
 
+    /// Get a registered schedule
+    pub fn get_schedule(&self, schedule_id: &Uuid) -> Option<OnCallSchedule> {
+        self.schedules
+            .get(&schedule_id.to_string())
+            .map(|entry| entry.value().clone())
+    }
+
+    /// Add an override to a registered schedule
+    pub fn add_schedule_override(
+        &self,
+        schedule_id: &Uuid,
+        schedule_override: ScheduleOverride,
+    ) -> Result<ScheduleOverride> {
+        let mut schedule = self.schedule_mut(schedule_id)?;
+        validate_override(&schedule, &schedule_override)?;
+        schedule.overrides.push(schedule_override.clone());
+        Ok(schedule_override)
+    }
+
+    /// Remove an override from a registered schedule
+    pub fn remove_schedule_override(
+        &self,
+        schedule_id: &Uuid,
+        override_id: &Uuid,
+    ) -> Result<ScheduleOverride> {
+        let mut schedule = self.schedule_mut(schedule_id)?;
+        let index = schedule
+            .overrides
+            .iter()
+            .position(|o| o.id == *override_id)
+            .ok_or_else(|| AppError::NotFound(format!("Override {} not found", override_id)))?;
+        Ok(schedule.overrides.remove(index))
+    }
+
+    /// Add a shift swap to a registered schedule
+    pub fn add_shift_swap(&self, schedule_id: &Uuid, swap: ShiftSwap) -> Result<ShiftSwap> {
+        let mut schedule = self.schedule_mut(schedule_id)?;
+        validate_swap(&schedule, &swap)?;
+        schedule.swaps.push(swap.clone());
+        Ok(swap)
+    }
+
+    /// Remove a shift swap from a registered schedule
+    pub fn remove_shift_swap(&self, schedule_id: &Uuid, swap_id: &Uuid) -> Result<ShiftSwap> {
+        let mut schedule = self.schedule_mut(schedule_id)?;
+        let index = schedule
+            .swaps
+            .iter()
+            .position(|s| s.id == *swap_id)
+            .ok_or_else(|| AppError::NotFound(format!("Swap {} not found", swap_id)))?;
+        Ok(schedule.swaps.remove(index))
+    }
+
+    /// Work out who will be on call for a schedule over the next `weeks`
+    pub fn preview_schedule(
+        &self,
+        schedule_id: &Uuid,
+        from: DateTime<Utc>,
+        weeks: u32,
+    ) -> Result<Vec<ScheduleShift>> {
+        if weeks == 0 || weeks > MAX_PREVIEW_WEEKS {
+            return Err(AppError::Validation(format!(
+                "Preview must cover 1 to {} weeks",
+                MAX_PREVIEW_WEEKS
+            )));
+        }
+        let schedule = self
+            .get_schedule(schedule_id)
+            .ok_or_else(|| AppError::NotFound(format!("Schedule {} not found", schedule_id)))?;
+
+        self.schedule_resolver
+            .preview(&schedule, from, from + Duration::weeks(weeks as i64))
+    }
+
+    /// Render a schedule's shifts over the next `weeks` as an iCalendar feed
+    pub fn schedule_calendar(
+        &self,
+        schedule_id: &Uuid,
+        from: DateTime<Utc>,
+        weeks: u32,
+    ) -> Result<String> {
+        let shifts = self.preview_schedule(schedule_id, from, weeks)?;
+        let schedule = self
+            .get_schedule(schedule_id)
+            .ok_or_else(|| AppError::NotFound(format!("Schedule {} not found", schedule_id)))?;
+
+        Ok(ical::render_calendar(
+            &schedule.name,
+            &[(&schedule, &shifts)],
+            Utc::now(),
+        ))
+    }
+
+    /// Render a user's shifts across every schedule over the next `weeks` as
+    /// an iCalendar feed
+    pub fn user_calendar(&self, user: &str, from: DateTime<Utc>, weeks: u32) -> Result<String> {
+        let mut calendars = Vec::new();
+        for schedule in self.list_schedules() {
+            let shifts: Vec<ScheduleShift> = self
+                .preview_schedule(&schedule.id, from, weeks)?
+                .into_iter()
+                .filter(|shift| shift.user.eq_ignore_ascii_case(user))
+                .collect();
+            if !shifts.is_empty() {
+                calendars.push((schedule, shifts));
+            }
+        }
+
+        let calendars: Vec<(&OnCallSchedule, &[ScheduleShift])> = calendars
+            .iter()
+            .map(|(schedule, shifts)| (schedule, shifts.as_slice()))
+            .collect();
+        Ok(ical::render_calendar(
+            &format!("On call: {}", user),
+            &calendars,
+            Utc::now(),
+        ))
+    }
+
+    /// Add the overrides described by the events of an iCalendar file
+    ///
+    /// Nothing is added unless every event is valid. An override whose ID
+    /// matches an existing one, such as one imported from the same event
+    /// before, replaces it.
+    pub fn import_schedule_overrides(
+        &self,
+        schedule_id: &Uuid,
+        ics: &str,
+        created_by: Option<String>,
+    ) -> Result<Vec<ScheduleOverride>> {
+        let mut schedule = self.schedule_mut(schedule_id)?;
+        let mut overrides = ical::parse_overrides(ics, &schedule)?;
+
+        for schedule_override in &mut overrides {
+            schedule_override.created_by = created_by.clone();
+            schedule.overrides.retain(|o| o.id != schedule_override.id);
+            schedule.overrides.push(schedule_override.clone());
+        }
+        Ok(overrides)
+    }
+
+    fn schedule_mut(
+        &self,
+        schedule_id: &Uuid,
+    ) -> Result<dashmap::mapref::one::RefMut<'_, String, OnCallSchedule>> {
+        self.schedules
+            .get_mut(&schedule_id.to_string())
+            .ok_or_else(|| AppError::NotFound(format!("Schedule {} not found", schedule_id)))
+    }
+
     /// Get all registered teams
     pub fn list_teams(&self) -> Vec<(String, Vec<String>)> {
         self.teams
@@ -367,6 +754,8 @@ pub struct EscalationLevelResult {
     pub notifications_sent: usize,
     pub notifications_failed: usize,
     pub targets_resolved: Vec<String>,
+    /// Responders picked from teams, and why
+    pub selections: Vec<ResponderSelection>,
     pub errors: Vec<String>,
 }
 
@@ -403,7 +792,10 @@ mod tests {
                 users: vec!["oncall@example.com".to_string()],
                 rotation: RotationStrategy::Daily { handoff_hour: 9 },
                 restrictions: None,
+                holidays: vec![],
             }],
+            overrides: vec![],
+            swaps: vec![],
         }
     }
 
@@ -463,6 +855,120 @@ mod tests {
         assert!(result.is_successful());
     }
 
+    fn team_level(target: EscalationTarget) -> EscalationLevel {
+        EscalationLevel {
+            level: 0,
+            delay_minutes: 0,
+            targets: vec![target],
+            stop_on_ack: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_level_with_round_robin_target() {
+        let executor = EscalationLevelExecutor::new(None);
+        executor.register_team(
+            "platform".to_string(),
+            vec![
+                "user1@example.com".to_string(),
+                "user2@example.com".to_string(),
+            ],
+        );
+        let incident = create_test_incident();
+        let level = team_level(EscalationTarget::RoundRobin {
+            team_id: "platform".to_string(),
+        });
+
+        let mut chosen = Vec::new();
+        for _ in 0..3 {
+            let mut state = EscalationState::new(incident.id, Uuid::new_v4(), 5);
+            let result = executor.execute_level(&incident, &level, &mut state).await.unwrap();
+            assert_eq!(result.notifications_sent, 1);
+            assert_eq!(state.selections.len(), 1);
+            chosen.push(result.selections[0].responder.clone().unwrap());
+        }
+
+        assert_eq!(
+            chosen,
+            vec![
+                "user1@example.com",
+                "user2@example.com",
+                "user1@example.com"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_level_with_least_loaded_target() {
+        let store = Arc::new(crate::state::InMemoryStore::new());
+        let executor = EscalationLevelExecutor::new(None).with_store(store.clone());
+        executor.register_team(
+            "platform".to_string(),
+            vec![
+                "busy@example.com".to_string(),
+                "idle@example.com".to_string(),
+            ],
+        );
+
+        let mut assigned = create_test_incident();
+        assigned.assignees = vec!["busy@example.com".to_string()];
+        store.save_incident(&assigned).await.unwrap();
+
+        let incident = create_test_incident();
+        let level = team_level(EscalationTarget::LeastLoaded {
+            team_id: "platform".to_string(),
+        });
+        for _ in 0..2 {
+            let mut state = EscalationState::new(incident.id, Uuid::new_v4(), 5);
+            let result = executor.execute_level(&incident, &level, &mut state).await.unwrap();
+            let selection = &result.selections[0];
+            assert_eq!(selection.strategy, SelectionStrategy::LeastLoaded);
+            assert_eq!(selection.responder.as_deref(), Some("idle@example.com"));
+            assert!(selection.reason.contains("(0)"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_level_with_first_to_acknowledge_target() {
+        let executor = EscalationLevelExecutor::new(None);
+        executor.register_team(
+            "platform".to_string(),
+            vec![
+                "user1@example.com".to_string(),
+                "user2@example.com".to_string(),
+            ],
+        );
+        let incident = create_test_incident();
+        let level = team_level(EscalationTarget::FirstToAcknowledge {
+            team_id: "platform".to_string(),
+        });
+        let mut state = EscalationState::new(incident.id, Uuid::new_v4(), 5);
+
+        let result = executor.execute_level(&incident, &level, &mut state).await.unwrap();
+
+        assert_eq!(result.notifications_sent, 2);
+        assert!(result.selections[0].responder.is_none());
+        assert!(executor.is_team_member("platform", "USER2@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_team_selection_without_members_fails() {
+        let executor = EscalationLevelExecutor::new(None);
+        executor.register_team("empty".to_string(), vec![]);
+        let incident = create_test_incident();
+        let level = team_level(EscalationTarget::RoundRobin {
+            team_id: "empty".to_string(),
+        });
+        let mut state = EscalationState::new(incident.id, Uuid::new_v4(), 5);
+
+        let result = executor.execute_level(&incident, &level, &mut state).await.unwrap();
+
+        assert_eq!(result.notifications_sent, 0);
+        assert!(result.selections.is_empty());
+        assert_eq!(result.errors.len(), 1);
+        assert!(!result.is_successful());
+    }
+
     #[tokio::test]
     async fn test_execute_level_with_schedule_target() {
         let executor = EscalationLevelExecutor::new(None);
@@ -586,6 +1092,78 @@ mod tests {
         assert_eq!(schedules[0].id, schedule_id);
     }
 
+    #[tokio::test]
+    async fn test_schedule_override_applies_to_targets() {
+        let executor = EscalationLevelExecutor::new(None);
+        let schedule = create_test_schedule();
+        let schedule_id = schedule.id;
+        executor.register_schedule(schedule);
+
+        let schedule_override = ScheduleOverride {
+            id: Uuid::new_v4(),
+            layer: Some("Primary".to_string()),
+            user: "cover@example.com".to_string(),
+            replaces: Some("oncall@example.com".to_string()),
+            start: Utc::now() - Duration::hours(1),
+            end: Utc::now() + Duration::hours(1),
+            reason: Some("Dentist".to_string()),
+            created_by: None,
+            created_at: Utc::now(),
+        };
+        let override_id = schedule_override.id;
+        executor
+            .add_schedule_override(&schedule_id, schedule_override)
+            .unwrap();
+
+        let users = executor.resolve_schedule(&schedule_id.to_string()).unwrap();
+        assert_eq!(users[0].email, "cover@example.com");
+
+        let shifts = executor
+            .preview_schedule(&schedule_id, Utc::now(), 1)
+            .unwrap();
+        assert_eq!(shifts[0].user, "cover@example.com");
+        assert!(executor
+            .preview_schedule(&schedule_id, Utc::now(), 0)
+            .is_err());
+
+        executor
+            .remove_schedule_override(&schedule_id, &override_id)
+            .unwrap();
+        let users = executor.resolve_schedule(&schedule_id.to_string()).unwrap();
+        assert_eq!(users[0].email, "oncall@example.com");
+    }
+
+    #[test]
+    fn test_import_schedule_overrides_from_calendar() {
+        let executor = EscalationLevelExecutor::new(None);
+        let schedule = create_test_schedule();
+        let schedule_id = schedule.id;
+        executor.register_schedule(schedule);
+
+        let start = Utc::now() - Duration::hours(1);
+        let ics = format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:cover-1\r\nDTSTART:{}\r\n\
+             DURATION:PT2H\r\nATTENDEE:mailto:cover@example.com\r\n\
+             END:VEVENT\r\nEND:VCALENDAR\r\n",
+            start.format("%Y%m%dT%H%M%SZ")
+        );
+        for _ in 0..2 {
+            executor
+                .import_schedule_overrides(&schedule_id, &ics, Some("ops".to_string()))
+                .unwrap();
+        }
+        // Importing the same event twice replaces the first override
+        let schedule = executor.get_schedule(&schedule_id).unwrap();
+        assert_eq!(schedule.overrides.len(), 1);
+        assert_eq!(schedule.overrides[0].created_by.as_deref(), Some("ops"));
+
+        let feed = executor
+            .user_calendar("COVER@example.com", Utc::now(), 1)
+            .unwrap();
+        assert!(feed.contains("ATTENDEE;CN=cover@example.com:mailto:cover@example.com"));
+        assert!(!feed.contains("oncall@example.com"));
+    }
+
     #[test]
     fn test_register_and_list_teams() {
         let executor = EscalationLevelExecutor::new(None);
@@ -0,0 +1,652 @@
+//! iCalendar (RFC 5545) feeds of on-call shifts and import of overrides
+//!
+//! Feeds list shifts with UTC times, so they need no `VTIMEZONE`. Imported
+//! events may use UTC, a `TZID` naming an IANA zone, floating times (read in
+//! the schedule's timezone) or whole dates.
+
+use crate::error::{AppError, Result};
+use crate::escalation::schedule::{local_to_utc, validate_override};
+use crate::models::policy::{OnCallSchedule, ScheduleOverride, ScheduleShift};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Product identifier written to every feed
+pub const PRODID: &str = "-//LLM Incident Manager//On-Call Schedules//EN";
+
+/// Lines are folded after this many octets
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Render shifts as an iCalendar feed, one event per shift
+pub fn render_calendar(
+    name: &str,
+    schedules: &[(&OnCallSchedule, &[ScheduleShift])],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let mut out = String::new();
+    for line in [
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ] {
+        push_line(&mut out, &line);
+    }
+
+    for (schedule, shifts) in schedules {
+        for shift in shifts.iter() {
+            let uid = format!(
+                "{}-{}-{}@llm-incident-manager",
+                schedule.id,
+                slug(&shift.layer_name),
+                format_utc(shift.start)
+            );
+            let source = serde_json::to_value(shift.source)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default();
+
+            for line in [
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}", uid),
+                format!("DTSTAMP:{}", format_utc(generated_at)),
+                format!("DTSTART:{}", format_utc(shift.start)),
+                format!("DTEND:{}", format_utc(shift.end)),
+                format!(
+                    "SUMMARY:{}",
+                    escape_text(&format!(
+                        "On call: {} ({})",
+                        schedule.name, shift.layer_name
+                    ))
+                ),
+                format!(
+                    "DESCRIPTION:{}",
+                    escape_text(&format!("{} is on call ({})", shift.user, source))
+                ),
+                format!(
+                    "ATTENDEE;CN={}:mailto:{}",
+                    quote_param(&shift.user),
+                    shift.user
+                ),
+                "TRANSP:TRANSPARENT".to_string(),
+                format!("X-ONCALL-SCHEDULE:{}", schedule.id),
+                format!("X-ONCALL-LAYER:{}", escape_text(&shift.layer_name)),
+                format!("X-ONCALL-SOURCE:{}", source),
+                "END:VEVENT".to_string(),
+            ] {
+                push_line(&mut out, &line);
+            }
+        }
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+/// Read overrides for a schedule from the events of an iCalendar file
+///
+/// The user on call is taken from `X-ONCALL-USER` or the first `ATTENDEE`;
+/// `X-ONCALL-LAYER` and `X-ONCALL-REPLACES` map to the override's layer and
+/// replaced user, and `SUMMARY` to its reason. An event's `UID` determines
+/// the override ID, so importing the same file again gives the same IDs.
+/// Cancelled events are skipped and recurring events are rejected.
+pub fn parse_overrides(ics: &str, schedule: &OnCallSchedule) -> Result<Vec<ScheduleOverride>> {
+    let schedule_tz: Tz = schedule
+        .timezone
+        .parse()
+        .map_err(|_| AppError::Validation(format!("Invalid timezone: {}", schedule.timezone)))?;
+
+    let mut overrides = Vec::new();
+    let mut event: Option<Vec<Property>> = None;
+    let mut count = 0;
+    for line in unfold(ics) {
+        let property = Property::parse(&line)?;
+        match (property.name.as_str(), property.value.as_str()) {
+            ("BEGIN", "VEVENT") => {
+                count += 1;
+                event = Some(Vec::new());
+            }
+            ("END", "VEVENT") => {
+                let properties = event.take().ok_or_else(|| {
+                    AppError::Validation("END:VEVENT without BEGIN:VEVENT".to_string())
+                })?;
+                let label = match find(&properties, "UID") {
+                    Some(uid) => format!("Event {} ({})", count, uid.value),
+                    None => format!("Event {}", count),
+                };
+                let parsed = event_override(&properties, schedule, &schedule_tz)
+                    .map_err(|e| AppError::Validation(format!("{}: {}", label, message(e))))?;
+                overrides.extend(parsed);
+            }
+            _ => {
+                if let Some(ref mut properties) = event {
+                    properties.push(property);
+                }
+            }
+        }
+    }
+
+    if event.is_some() {
+        return Err(AppError::Validation(
+            "BEGIN:VEVENT without END:VEVENT".to_string(),
+        ));
+    }
+    Ok(overrides)
+}
+
+fn event_override(
+    properties: &[Property],
+    schedule: &OnCallSchedule,
+    schedule_tz: &Tz,
+) -> Result<Option<ScheduleOverride>> {
+    if find(properties, "STATUS").is_some_and(|p| p.value.eq_ignore_ascii_case("CANCELLED")) {
+        return Ok(None);
+    }
+    if find(properties, "RRULE").is_some() || find(properties, "RDATE").is_some() {
+        return Err(AppError::Validation(
+            "recurring events are not supported".to_string(),
+        ));
+    }
+
+    let dtstart = find(properties, "DTSTART")
+        .ok_or_else(|| AppError::Validation("DTSTART is required".to_string()))?;
+    let (start, all_day) = parse_date_time(dtstart, schedule_tz)?;
+    let end = match (find(properties, "DTEND"), find(properties, "DURATION")) {
+        (Some(dtend), _) => parse_date_time(dtend, schedule_tz)?.0,
+        (None, Some(duration)) => start + parse_duration(&duration.value)?,
+        (None, None) if all_day => start + Duration::days(1),
+        (None, None) => {
+            return Err(AppError::Validation(
+                "DTEND or DURATION is required".to_string(),
+            ))
+        }
+    };
+
+    let user = find(properties, "X-ONCALL-USER")
+        .or_else(|| find(properties, "ATTENDEE"))
+        .map(|p| {
+            let value = unescape_text(&p.value);
+            match value.get(..7) {
+                Some(scheme) if scheme.eq_ignore_ascii_case("mailto:") => value[7..].to_string(),
+                _ => value,
+            }
+        })
+        .ok_or_else(|| AppError::Validation("ATTENDEE or X-ONCALL-USER is required".to_string()))?;
+
+    let id = match find(properties, "UID") {
+        Some(uid) => uid_to_id(&uid.value),
+        None => Uuid::new_v4(),
+    };
+    let text = |name| find(properties, name).map(|p| unescape_text(&p.value));
+
+    let schedule_override = ScheduleOverride {
+        id,
+        layer: text("X-ONCALL-LAYER"),
+        user,
+        replaces: text("X-ONCALL-REPLACES"),
+        start,
+        end,
+        reason: text("SUMMARY"),
+        created_by: None,
+        created_at: Utc::now(),
+    };
+    validate_override(schedule, &schedule_override)?;
+    Ok(Some(schedule_override))
+}
+
+/// A content line: `NAME;PARAM=value:value`
+#[derive(Debug)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn parse(line: &str) -> Result<Self> {
+        // The value starts at the first colon outside a quoted parameter
+        let mut quoted = false;
+        let colon = line
+            .char_indices()
+            .find(|&(_, c)| {
+                if c == '"' {
+                    quoted = !quoted;
+                }
+                c == ':' && !quoted
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| AppError::Validation(format!("Invalid content line: {}", line)))?;
+
+        let (head, value) = (&line[..colon], &line[colon + 1..]);
+        let mut parts = head.split(';');
+        let name = parts.next().unwrap_or_default().trim().to_ascii_uppercase();
+        let params = parts
+            .filter_map(|param| param.split_once('='))
+            .map(|(k, v)| (k.to_ascii_uppercase(), v.trim_matches('"').to_string()))
+            .collect();
+
+        Ok(Self {
+            name,
+            params,
+            value: value.to_string(),
+        })
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn find<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+    properties.iter().find(|p| p.name == name)
+}
+
+/// Join folded lines and drop blank ones
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match line.chars().next() {
+            Some(' ') | Some('\t') => {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(&line[1..]);
+                }
+            }
+            Some(_) => lines.push(line.to_string()),
+            None => {}
+        }
+    }
+    lines
+}
+
+/// Parse a DATE or DATE-TIME property; the flag is set for whole dates
+fn parse_date_time(property: &Property, schedule_tz: &Tz) -> Result<(DateTime<Utc>, bool)> {
+    let value = property.value.trim();
+    let tz = match property.param("TZID") {
+        Some(tzid) => tzid
+            .trim_start_matches('/')
+            .parse::<Tz>()
+            .map_err(|_| AppError::Validation(format!("Unknown TZID: {}", tzid)))?,
+        None => *schedule_tz,
+    };
+
+    if property.param("VALUE") == Some("DATE") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d")
+            .map_err(|_| AppError::Validation(format!("Invalid date: {}", value)))?;
+        let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+        return Ok((local_to_utc(&tz, &midnight)?, true));
+    }
+
+    let invalid = || AppError::Validation(format!("Invalid date-time: {}", value));
+    if let Some(utc) = value.strip_suffix('Z') {
+        let time = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+        return Ok((time.and_utc(), false));
+    }
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| invalid())?;
+    Ok((local_to_utc(&tz, &local)?, false))
+}
+
+/// Parse a DURATION value such as `PT8H`, `P1D` or `P1W`
+fn parse_duration(value: &str) -> Result<Duration> {
+    let invalid = || AppError::Validation(format!("Invalid duration: {}", value));
+    let (negative, rest) = match value.trim().strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.trim().trim_start_matches('+')),
+    };
+    let rest = rest.strip_prefix('P').ok_or_else(invalid)?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            unit => {
+                let n: i64 = number.parse().map_err(|_| invalid())?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return Err(invalid()),
+                };
+            }
+        }
+    }
+    if !number.is_empty() || total.is_zero() {
+        return Err(invalid());
+    }
+    Ok(if negative { -total } else { total })
+}
+
+/// Override ID for an event UID
+///
+/// A UID that is a UUID, optionally followed by `@host`, is used as is;
+/// any other UID is hashed.
+fn uid_to_id(uid: &str) -> Uuid {
+    let local = uid.split('@').next().unwrap_or(uid);
+    if let Ok(id) = Uuid::parse_str(local) {
+        return id;
+    }
+    let digest = Sha256::digest(uid.as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Message of an error without its kind prefix
+fn message(error: AppError) -> String {
+    match error {
+        AppError::Validation(message) => message,
+        other => other.to_string(),
+    }
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+fn quote_param(value: &str) -> String {
+    if value.contains([':', ';', ',']) {
+        format!("\"{}\"", value.replace('"', ""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Append a content line, folded at 75 octets, with a CRLF
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::escalation::schedule::ScheduleResolver;
+    use crate::models::policy::{OnCallSource, RotationStrategy, ScheduleLayer};
+    use chrono::TimeZone;
+
+    fn schedule(timezone: &str, rotation: RotationStrategy) -> OnCallSchedule {
+        OnCallSchedule {
+            id: Uuid::new_v4(),
+            name: "Platform, primary".to_string(),
+            timezone: timezone.to_string(),
+            layers: vec![ScheduleLayer {
+                name: "Primary".to_string(),
+                users: vec![
+                    "alice@example.com".to_string(),
+                    "bob@example.com".to_string(),
+                    "carol@example.com".to_string(),
+                ],
+                rotation,
+                restrictions: None,
+                holidays: vec![],
+            }],
+            overrides: vec![],
+            swaps: vec![],
+        }
+    }
+
+    /// Export a schedule's shifts, import them as overrides onto the same
+    /// schedule without a rotation, and check both give the same shifts
+    fn assert_round_trip(schedule: &OnCallSchedule, from: DateTime<Utc>, until: DateTime<Utc>) {
+        let resolver = ScheduleResolver::new();
+        let shifts = resolver.preview(schedule, from, until).unwrap();
+        let ics = render_calendar(&schedule.name, &[(schedule, &shifts)], from);
+
+        let mut imported = schedule.clone();
+        imported.layers[0].users.clear();
+        imported.overrides = parse_overrides(&ics, &imported).unwrap();
+        assert_eq!(imported.overrides.len(), shifts.len());
+        assert_eq!(
+            parse_overrides(&ics, &imported).unwrap()[0].id,
+            imported.overrides[0].id
+        );
+
+        let replayed = resolver.preview(&imported, from, until).unwrap();
+        let times = |shifts: &[ScheduleShift]| {
+            shifts
+                .iter()
+                .map(|s| (s.user.clone(), s.start, s.end))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(times(&replayed), times(&shifts));
+        assert!(replayed.iter().all(|s| s.source == OnCallSource::Override));
+    }
+
+    /// Local times at which the shifts of a schedule start
+    fn local_starts(
+        schedule: &OnCallSchedule,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Vec<String> {
+        let tz: Tz = schedule.timezone.parse().unwrap();
+        ScheduleResolver::new()
+            .preview(schedule, from, until)
+            .unwrap()
+            .iter()
+            .skip(1)
+            .map(|s| s.start.with_timezone(&tz).format("%m-%d %H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_daily_round_trip_across_dst() {
+        // Clocks go forward on 2024-03-10 and back on 2024-11-03 in New York
+        let daily = schedule(
+            "America/New_York",
+            RotationStrategy::Daily { handoff_hour: 9 },
+        );
+        let from = Utc.with_ymd_and_hms(2024, 3, 8, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 3, 12, 0, 0, 0).unwrap();
+        assert_eq!(
+            local_starts(&daily, from, until),
+            vec!["03-08 09:00", "03-09 09:00", "03-10 09:00", "03-11 09:00"]
+        );
+        assert_round_trip(&daily, from, until);
+
+        let from = Utc.with_ymd_and_hms(2024, 11, 1, 0, 0, 0).unwrap();
+        assert_round_trip(&daily, from, from + Duration::days(5));
+
+        // 02:00 does not exist on 2024-03-10, so that handoff is at 03:00
+        let skipped = schedule(
+            "America/New_York",
+            RotationStrategy::Daily { handoff_hour: 2 },
+        );
+        let from = Utc.with_ymd_and_hms(2024, 3, 9, 12, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 3, 11, 12, 0, 0).unwrap();
+        assert_eq!(
+            local_starts(&skipped, from, until),
+            vec!["03-10 03:00", "03-11 02:00"]
+        );
+        assert_round_trip(&skipped, from, until);
+    }
+
+    #[test]
+    fn test_weekly_round_trip_across_dst() {
+        // Clocks go forward on 2024-03-31 and back on 2024-10-27 in Berlin
+        let weekly = schedule(
+            "Europe/Berlin",
+            RotationStrategy::Weekly {
+                handoff_day: "Monday".to_string(),
+                handoff_hour: 10,
+            },
+        );
+        let from = Utc.with_ymd_and_hms(2024, 3, 20, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 4, 10, 0, 0, 0).unwrap();
+        assert_eq!(
+            local_starts(&weekly, from, until),
+            vec!["03-25 10:00", "04-01 10:00", "04-08 10:00"]
+        );
+        assert_round_trip(&weekly, from, until);
+
+        let from = Utc.with_ymd_and_hms(2024, 10, 15, 0, 0, 0).unwrap();
+        assert_round_trip(&weekly, from, from + Duration::weeks(3));
+    }
+
+    #[test]
+    fn test_custom_round_trip_across_dst() {
+        // Clocks go back on 2024-04-07 and forward on 2024-10-06 in Sydney;
+        // custom shifts last a fixed number of hours whatever the clocks say
+        let custom = schedule(
+            "Australia/Sydney",
+            RotationStrategy::Custom { duration_hours: 12 },
+        );
+        let from = Utc.with_ymd_and_hms(2024, 4, 5, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 4, 9, 0, 0, 0).unwrap();
+        let shifts = ScheduleResolver::new()
+            .preview(&custom, from, until)
+            .unwrap();
+        assert!(shifts
+            .iter()
+            .skip(1)
+            .all(|s| s.end - s.start == Duration::hours(12) || s.end == until));
+        assert_round_trip(&custom, from, until);
+
+        let from = Utc.with_ymd_and_hms(2024, 10, 4, 0, 0, 0).unwrap();
+        assert_round_trip(&custom, from, from + Duration::days(4));
+    }
+
+    #[test]
+    fn test_render_escapes_and_folds() {
+        let daily = schedule("UTC", RotationStrategy::Daily { handoff_hour: 9 });
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let shifts = ScheduleResolver::new()
+            .preview(&daily, from, from + Duration::days(1))
+            .unwrap();
+        let ics = render_calendar("On call, everyone", &[(&daily, &shifts)], from);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("X-WR-CALNAME:On call\\, everyone\r\n"));
+        assert!(ics.contains("DTSTART:20240101T090000Z\r\n"));
+        assert!(ics.lines().all(|line| line.len() <= MAX_LINE_OCTETS));
+        assert!(unfold(&ics)
+            .iter()
+            .any(|line| line == "SUMMARY:On call: Platform\\, primary (Primary)"));
+    }
+
+    #[test]
+    fn test_parse_overrides_with_tzid_and_dates() {
+        let daily = schedule(
+            "America/New_York",
+            RotationStrategy::Daily { handoff_hour: 9 },
+        );
+        let ics = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:conference-1\r\n\
+            DTSTART;TZID=America/New_York:20240310T090000\r\n\
+            DURATION:PT8H\r\n\
+            SUMMARY:Bob at a conference\\, day 1\r\n\
+            ATTENDEE;CN=Alice:MAILTO:alice@example.com\r\n\
+            X-ONCALL-REPLACES:bob@example.com\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:holiday-cover\r\n\
+            DTSTART;VALUE=DATE:20240311\r\n\
+            X-ONCALL-USER:carol@example.com\r\n\
+            X-ONCALL-LAYER:Primary\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:cancelled\r\n\
+            STATUS:CANCELLED\r\n\
+            DTSTART:20240312T000000Z\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        let overrides = parse_overrides(ics, &daily).unwrap();
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].user, "alice@example.com");
+        assert_eq!(overrides[0].replaces.as_deref(), Some("bob@example.com"));
+        assert_eq!(
+            overrides[0].reason.as_deref(),
+            Some("Bob at a conference, day 1")
+        );
+        // 09:00 EDT, after the clocks went forward that morning
+        assert_eq!(
+            overrides[0].start,
+            Utc.with_ymd_and_hms(2024, 3, 10, 13, 0, 0).unwrap()
+        );
+        assert_eq!(
+            overrides[0].end,
+            Utc.with_ymd_and_hms(2024, 3, 10, 21, 0, 0).unwrap()
+        );
+        assert_eq!(
+            overrides[1].start,
+            Utc.with_ymd_and_hms(2024, 3, 11, 4, 0, 0).unwrap()
+        );
+        assert_eq!(
+            overrides[1].end,
+            Utc.with_ymd_and_hms(2024, 3, 12, 4, 0, 0).unwrap()
+        );
+
+        let recurring = ics.replace("DURATION:PT8H", "DURATION:PT8H\r\nRRULE:FREQ=DAILY");
+        let error = parse_overrides(&recurring, &daily).unwrap_err().to_string();
+        assert!(error.contains("Event 1 (conference-1)"), "{}", error);
+
+        let unknown_layer = ics.replace("X-ONCALL-LAYER:Primary", "X-ONCALL-LAYER:Tertiary");
+        assert!(parse_overrides(&unknown_layer, &daily).is_err());
+    }
+}
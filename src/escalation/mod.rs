@@ -1,11 +1,20 @@
 pub mod engine;
 pub mod executor;
+pub mod ical;
 pub mod routing;
 pub mod schedule;
 pub mod state;
 
-pub use engine::{EscalationEngine, EscalationStats};
+pub use engine::{validate_policy, EscalationEngine, EscalationStats};
 pub use executor::{EscalationLevelExecutor, EscalationLevelResult};
-pub use routing::{RoutingActionResult, RoutingRuleEvaluator, RoutingRuleMatch, RoutingStats};
-pub use schedule::{OnCallUser, ScheduleResolver};
-pub use state::{EscalationNotification, EscalationState, EscalationStatus};
+pub use routing::{
+    validate_rule, RoutingActionResult, RoutingRuleEvaluator, RoutingRuleMatch, RoutingStats,
+};
+pub use schedule::{
+    validate_override, validate_schedule, validate_swap, OnCallUser, ScheduleResolver,
+    MAX_PREVIEW_WEEKS,
+};
+pub use state::{
+    EscalationNotification, EscalationState, EscalationStatus, ResponderSelection,
+    SelectionStrategy,
+};
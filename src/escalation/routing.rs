@@ -29,17 +29,7 @@ impl RoutingRuleEvaluator {
 
     /// Register a routing rule
     pub fn register_rule(&self, rule: RoutingRule) -> Result<()> {
-        if rule.conditions.is_empty() {
-            return Err(AppError::Validation(
-                "Routing rule must have at least one condition".to_string(),
-            ));
-        }
-
-        if rule.actions.is_empty() {
-            return Err(AppError::Validation(
-                "Routing rule must have at least one action".to_string(),
-            ));
-        }
+        validate_rule(&rule)?;
 
         tracing::info!(
             rule_id = %rule.id,
@@ -334,6 +324,36 @@ impl RoutingRuleEvaluator {
     }
 }
 
+/// Check that a routing rule can be registered
+pub fn validate_rule(rule: &RoutingRule) -> Result<()> {
+    if rule.conditions.is_empty() {
+        return Err(AppError::Validation(
+            "Routing rule must have at least one condition".to_string(),
+        ));
+    }
+
+    if rule.actions.is_empty() {
+        return Err(AppError::Validation(
+            "Routing rule must have at least one action".to_string(),
+        ));
+    }
+
+    for condition in &rule.conditions {
+        if condition.operator != ConditionOperator::Matches {
+            continue;
+        }
+        if let Some(pattern) = condition.value.as_str() {
+            Regex::new(pattern).map_err(|e| {
+                AppError::Validation(format!(
+                    "Condition on '{}' has an invalid pattern: {}",
+                    condition.field, e
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
 /// A matched routing rule
 #[derive(Debug, Clone)]
 pub struct RoutingRuleMatch {
@@ -406,6 +426,30 @@ mod tests {
         assert!(retrieved.is_some());
     }
 
+    #[test]
+    fn test_register_rule_rejects_invalid_pattern() {
+        let evaluator = RoutingRuleEvaluator::new(None);
+
+        let rule = RoutingRule {
+            id: Uuid::new_v4(),
+            name: "Broken Pattern".to_string(),
+            priority: 10,
+            enabled: true,
+            conditions: vec![RuleCondition {
+                field: "title".to_string(),
+                operator: ConditionOperator::Matches,
+                value: JsonValue::String("(cpu".to_string()),
+            }],
+            actions: vec![RoutingAction::Notify {
+                channels: vec!["#ops".to_string()],
+            }],
+        };
+
+        let result = evaluator.register_rule(rule);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(evaluator.list_rules().is_empty());
+    }
+
     #[tokio::test]
     async fn test_evaluate_equals_condition() {
         let evaluator = RoutingRuleEvaluator::new(None);
@@ -1,7 +1,17 @@
 use crate::error::{AppError, Result};
-use crate::models::policy::{OnCallSchedule, RotationStrategy, ScheduleLayer, TimeRestrictions};
-use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc, Weekday};
+use crate::models::policy::{
+    OnCallSchedule, OnCallSource, RotationStrategy, ScheduleLayer, ScheduleOverride, ScheduleShift,
+    ShiftSwap, ShiftWindow, TimeRestrictions,
+};
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone, Timelike, Utc,
+    Weekday,
+};
 use chrono_tz::Tz;
+use std::collections::{BTreeSet, HashSet};
+
+/// Longest period a schedule preview may cover
+pub const MAX_PREVIEW_WEEKS: u32 = 12;
 
 /// Resolves who is currently on-call based on schedule configuration
 pub struct ScheduleResolver {
@@ -30,32 +40,206 @@ impl ScheduleResolver {
 
     /// Resolve who is currently on-call for a schedule
     pub fn resolve_oncall(&self, schedule: &OnCallSchedule) -> Result<Vec<OnCallUser>> {
-        let now = self.now();
+        self.resolve_at(schedule, self.now())
+    }
+
+    /// Resolve who is on-call for a schedule at a given time
+    ///
+    /// Each layer is resolved by precedence, highest first:
+    /// 1. the most recently created override in effect
+    /// 2. the most recently created swap covering the rotation's user
+    /// 3. nobody, on a date in one of the layer's holiday calendars
+    /// 4. the rotation, within the layer's time restrictions
+    pub fn resolve_at(
+        &self,
+        schedule: &OnCallSchedule,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<OnCallUser>> {
+        let oncall_users = self
+            .resolve_layers(schedule, at)?
+            .into_iter()
+            .zip(&schedule.layers)
+            .filter_map(|(oncall, layer)| {
+                oncall.map(|(email, source)| OnCallUser {
+                    email,
+                    layer_name: layer.name.clone(),
+                    schedule_id: schedule.id,
+                    schedule_name: schedule.name.clone(),
+                    source,
+                })
+            })
+            .collect();
 
-        // Parse timezone
-        let tz: Tz = schedule
-            .timezone
-            .parse()
-            .map_err(|_| AppError::Validation(format!("Invalid timezone: {}", schedule.timezone)))?;
+        Ok(oncall_users)
+    }
 
-        // Convert current time to schedule's timezone
-        let local_time = now.with_timezone(&tz);
+    /// Resolve each layer of a schedule, in layer order
+    fn resolve_layers(
+        &self,
+        schedule: &OnCallSchedule,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Option<(String, OnCallSource)>>> {
+        let tz = parse_timezone(&schedule.timezone)?;
 
-        let mut oncall_users = Vec::new();
+        // Convert the time to the schedule's timezone
+        let local_time = at.with_timezone(&tz);
 
-        // Process each layer
+        let mut layers = Vec::with_capacity(schedule.layers.len());
         for layer in &schedule.layers {
-            if let Some(user) = self.resolve_layer_oncall(layer, &local_time, &tz)? {
-                oncall_users.push(OnCallUser {
-                    email: user,
-                    layer_name: layer.name.clone(),
-                    schedule_id: schedule.id,
-                    schedule_name: schedule.name.clone(),
+            let holiday = layer
+                .holidays
+                .iter()
+                .any(|calendar| calendar.dates.contains(&local_time.date_naive()));
+
+            let mut oncall = if holiday {
+                None
+            } else {
+                self.resolve_layer_oncall(layer, &local_time, &tz)?
+                    .map(|user| (user, OnCallSource::Rotation))
+            };
+
+            if let Some((user, _)) = &oncall {
+                if let Some(covering) = swapped_user(&schedule.swaps, &layer.name, user, at) {
+                    oncall = Some((covering, OnCallSource::Swap));
+                }
+            }
+
+            let current = oncall.as_ref().map(|(user, _)| user.as_str());
+            if let Some(o) = active_override(&schedule.overrides, &layer.name, current, at) {
+                oncall = Some((o.user.clone(), OnCallSource::Override));
+            }
+
+            layers.push(oncall);
+        }
+
+        Ok(layers)
+    }
+
+    /// Work out the shifts of every layer between `from` and `until`
+    ///
+    /// Consecutive periods with the same user and source are merged; periods
+    /// in which a layer has no one on call are left out.
+    pub fn preview(
+        &self,
+        schedule: &OnCallSchedule,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ScheduleShift>> {
+        if until <= from {
+            return Err(AppError::Validation(
+                "Preview must end after it starts".to_string(),
+            ));
+        }
+
+        let times = self.change_times(schedule, from, until)?;
+
+        let mut shifts = Vec::new();
+        let mut open: Vec<Option<(String, OnCallSource, DateTime<Utc>)>> =
+            vec![None; schedule.layers.len()];
+        for &at in &times {
+            for (index, oncall) in self.resolve_layers(schedule, at)?.into_iter().enumerate() {
+                let unchanged = match (&open[index], &oncall) {
+                    (Some((user, source, _)), Some((next_user, next_source))) => {
+                        user == next_user && source == next_source
+                    }
+                    (None, None) => true,
+                    _ => false,
+                };
+                if unchanged {
+                    continue;
+                }
+                if let Some((user, source, start)) = open[index].take() {
+                    shifts.push(ScheduleShift {
+                        layer_name: schedule.layers[index].name.clone(),
+                        user,
+                        source,
+                        start,
+                        end: at,
+                    });
+                }
+                open[index] = oncall.map(|(user, source)| (user, source, at));
+            }
+        }
+        for (index, oncall) in open.into_iter().enumerate() {
+            if let Some((user, source, start)) = oncall {
+                shifts.push(ScheduleShift {
+                    layer_name: schedule.layers[index].name.clone(),
+                    user,
+                    source,
+                    start,
+                    end: until,
                 });
             }
         }
 
-        Ok(oncall_users)
+        shifts.sort_by_key(|s| s.start);
+        Ok(shifts)
+    }
+
+    /// Every time in `[from, until)` at which who is on call may change
+    ///
+    /// These are `from` itself, rotation handoffs, the start and end of time
+    /// restrictions, local midnights for layers with restrictions or
+    /// holidays, and the edges of overrides and swaps.
+    fn change_times(
+        &self,
+        schedule: &OnCallSchedule,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>> {
+        let tz = parse_timezone(&schedule.timezone)?;
+        let mut times = BTreeSet::from([from]);
+
+        for layer in &schedule.layers {
+            let mut at = from;
+            loop {
+                let next = self.next_handoff_time(layer, &at.with_timezone(&tz), &tz)?;
+                if next <= at {
+                    return Err(AppError::Internal(format!(
+                        "Handoff for layer '{}' did not advance past {}",
+                        layer.name, at
+                    )));
+                }
+                if next >= until {
+                    break;
+                }
+                times.insert(next);
+                at = next;
+            }
+
+            if layer.restrictions.is_none() && layer.holidays.is_empty() {
+                continue;
+            }
+            let mut hours = vec![0];
+            if let Some(ref restrictions) = layer.restrictions {
+                hours.extend([restrictions.start_hour, restrictions.end_hour]);
+            }
+            let mut date = from.with_timezone(&tz).date_naive();
+            while date <= until.with_timezone(&tz).date_naive() {
+                for &hour in &hours {
+                    if let Some(local) = date.and_hms_opt(hour, 0, 0) {
+                        times.insert(local_to_utc(&tz, &local)?);
+                    }
+                }
+                date += Duration::days(1);
+            }
+        }
+
+        times.extend(
+            schedule
+                .overrides
+                .iter()
+                .flat_map(|o| [o.start, o.end])
+                .chain(
+                    schedule
+                        .swaps
+                        .iter()
+                        .flat_map(|s| [s.shift, s.return_shift])
+                        .flat_map(|w| [w.start, w.end]),
+                ),
+        );
+
+        Ok(times.range(from..until).copied().collect())
     }
 
     /// Resolve who is on-call for a specific layer
@@ -127,18 +311,12 @@ impl ScheduleResolver {
 
         match &layer.rotation {
             RotationStrategy::Daily { handoff_hour } => {
-                self.calculate_daily_rotation(local_time, *handoff_hour, user_count, tz)
+                self.calculate_daily_rotation(local_time, *handoff_hour, user_count)
             }
             RotationStrategy::Weekly {
                 handoff_day,
                 handoff_hour,
-            } => self.calculate_weekly_rotation(
-                local_time,
-                handoff_day,
-                *handoff_hour,
-                user_count,
-                tz,
-            ),
+            } => self.calculate_weekly_rotation(local_time, handoff_day, *handoff_hour, user_count),
             RotationStrategy::Custom { duration_hours } => {
                 self.calculate_custom_rotation(local_time, *duration_hours, user_count, tz)
             }
@@ -146,21 +324,16 @@ impl ScheduleResolver {
     }
 
     /// Calculate rotation index for daily rotation
+    ///
+    /// Days are counted on the local calendar so the handoff stays at
+    /// `handoff_hour` across daylight saving changes.
     fn calculate_daily_rotation<Tz: TimeZone>(
         &self,
         local_time: &DateTime<Tz>,
         handoff_hour: u32,
         user_count: usize,
-        tz: &Tz,
     ) -> Result<usize> {
-        // Calculate days since epoch
-        let epoch = tz
-            .with_ymd_and_hms(2020, 1, 1, handoff_hour, 0, 0)
-            .single()
-            .ok_or_else(|| AppError::Internal("Failed to create epoch time".to_string()))?;
-
-        let duration = local_time.clone().signed_duration_since(epoch);
-        let days = duration.num_days();
+        let days = (local_time.date_naive() - rotation_epoch(2020, 1, 1)?).num_days();
 
         // Adjust if we haven't reached handoff hour today
         let adjusted_days = if local_time.hour() < handoff_hour {
@@ -169,7 +342,7 @@ impl ScheduleResolver {
             days
         };
 
-        Ok((adjusted_days as usize) % user_count)
+        Ok(adjusted_days.rem_euclid(user_count as i64) as usize)
     }
 
     /// Calculate rotation index for weekly rotation
@@ -179,7 +352,6 @@ impl ScheduleResolver {
         handoff_day: &str,
         handoff_hour: u32,
         user_count: usize,
-        tz: &Tz,
     ) -> Result<usize> {
         // Parse handoff day
         let target_weekday = parse_weekday(handoff_day)?;
@@ -202,17 +374,13 @@ impl ScheduleResolver {
             }
         };
 
-        // Calculate weeks since epoch
-        let epoch = tz
-            .with_ymd_and_hms(2020, 1, 6, handoff_hour, 0, 0) // 2020-01-06 was a Monday
-            .single()
-            .ok_or_else(|| AppError::Internal("Failed to create epoch time".to_string()))?;
-
-        let duration = local_time.clone().signed_duration_since(epoch);
-        let total_days = duration.num_days() - days_since_handoff as i64;
-        let weeks = total_days / 7;
+        // Calculate weeks since epoch (2020-01-06 was a Monday)
+        let handoff_date = local_time.date_naive() - Duration::days(days_since_handoff as i64);
+        let weeks = (handoff_date - rotation_epoch(2020, 1, 6)?)
+            .num_days()
+            .div_euclid(7);
 
-        Ok((weeks as usize) % user_count)
+        Ok(weeks.rem_euclid(user_count as i64) as usize)
     }
 
     /// Calculate rotation index for custom duration rotation
@@ -252,6 +420,9 @@ impl ScheduleResolver {
     }
 
     /// Get the next handoff time for a schedule layer
+    ///
+    /// A handoff hour that a daylight saving change skips happens when the
+    /// clocks go forward; one that occurs twice happens the first time.
     pub fn next_handoff_time<Tz: TimeZone>(
         &self,
         layer: &ScheduleLayer,
@@ -266,15 +437,11 @@ impl ScheduleResolver {
                     .ok_or_else(|| AppError::Internal("Invalid handoff hour".to_string()))?;
 
                 // If handoff already happened today, move to tomorrow
-                if local_time.time() >= next.time() {
+                if local_time.hour() >= *handoff_hour {
                     next += Duration::days(1);
                 }
 
-                Ok(tz
-                    .from_local_datetime(&next)
-                    .single()
-                    .ok_or_else(|| AppError::Internal("Failed to convert to UTC".to_string()))?
-                    .with_timezone(&Utc))
+                local_to_utc(tz, &next)
             }
             RotationStrategy::Weekly {
                 handoff_day,
@@ -304,15 +471,19 @@ impl ScheduleResolver {
                     .and_hms_opt(*handoff_hour, 0, 0)
                     .ok_or_else(|| AppError::Internal("Invalid handoff hour".to_string()))?;
 
-                Ok(tz
-                    .from_local_datetime(&next)
-                    .single()
-                    .ok_or_else(|| AppError::Internal("Failed to convert to UTC".to_string()))?
-                    .with_timezone(&Utc))
+                local_to_utc(tz, &next)
             }
             RotationStrategy::Custom { duration_hours } => {
-                let next = local_time.clone() + Duration::hours(*duration_hours as i64);
-                Ok(next.with_timezone(&Utc))
+                // Shifts are counted from the same epoch as the rotation index
+                let epoch = tz
+                    .with_ymd_and_hms(2020, 1, 1, 0, 0, 0)
+                    .single()
+                    .ok_or_else(|| AppError::Internal("Failed to create epoch time".to_string()))?;
+                let duration = Duration::hours(*duration_hours as i64);
+                let elapsed = local_time.clone().signed_duration_since(epoch.clone());
+                let rotations = elapsed.num_hours().div_euclid(*duration_hours as i64);
+
+                Ok((epoch + duration * (rotations as i32 + 1)).with_timezone(&Utc))
             }
         }
     }
@@ -331,6 +502,203 @@ pub struct OnCallUser {
     pub layer_name: String,
     pub schedule_id: uuid::Uuid,
     pub schedule_name: String,
+    pub source: OnCallSource,
+}
+
+/// Check a schedule before it is registered
+pub fn validate_schedule(schedule: &OnCallSchedule) -> Result<()> {
+    if schedule.name.trim().is_empty() {
+        return Err(AppError::Validation(
+            "Schedule name is required".to_string(),
+        ));
+    }
+    parse_timezone(&schedule.timezone)?;
+
+    let mut names = HashSet::new();
+    for layer in &schedule.layers {
+        if !names.insert(layer.name.as_str()) {
+            return Err(AppError::Validation(format!(
+                "Schedule '{}' has more than one layer named '{}'",
+                schedule.name, layer.name
+            )));
+        }
+        match &layer.rotation {
+            RotationStrategy::Daily { handoff_hour } => check_hour(*handoff_hour, 23)?,
+            RotationStrategy::Weekly {
+                handoff_day,
+                handoff_hour,
+            } => {
+                parse_weekday(handoff_day)?;
+                check_hour(*handoff_hour, 23)?;
+            }
+            RotationStrategy::Custom { duration_hours } => {
+                if *duration_hours == 0 {
+                    return Err(AppError::Validation(format!(
+                        "Layer '{}' must rotate after at least one hour",
+                        layer.name
+                    )));
+                }
+            }
+        }
+        if let Some(ref restrictions) = layer.restrictions {
+            if restrictions.days_of_week.iter().any(|day| *day > 6) {
+                return Err(AppError::Validation(format!(
+                    "Layer '{}' restricts to a day of week outside 0-6",
+                    layer.name
+                )));
+            }
+            check_hour(restrictions.start_hour, 23)?;
+            check_hour(restrictions.end_hour, 24)?;
+        }
+    }
+
+    for o in &schedule.overrides {
+        validate_override(schedule, o)?;
+    }
+    for swap in &schedule.swaps {
+        validate_swap(schedule, swap)?;
+    }
+    Ok(())
+}
+
+/// Check an override against the schedule it is added to
+pub fn validate_override(schedule: &OnCallSchedule, o: &ScheduleOverride) -> Result<()> {
+    if o.user.trim().is_empty() {
+        return Err(AppError::Validation(
+            "Override user is required".to_string(),
+        ));
+    }
+    if o.end <= o.start {
+        return Err(AppError::Validation(
+            "Override must end after it starts".to_string(),
+        ));
+    }
+    if o.replaces.as_deref() == Some(o.user.as_str()) {
+        return Err(AppError::Validation(
+            "Override cannot replace its own user".to_string(),
+        ));
+    }
+    check_layer(schedule, o.layer.as_deref())
+}
+
+/// Check a swap against the schedule it is added to
+pub fn validate_swap(schedule: &OnCallSchedule, swap: &ShiftSwap) -> Result<()> {
+    if swap.user.trim().is_empty() || swap.with_user.trim().is_empty() {
+        return Err(AppError::Validation("Swap needs two users".to_string()));
+    }
+    if swap.user.eq_ignore_ascii_case(&swap.with_user) {
+        return Err(AppError::Validation(
+            "Swap needs two different users".to_string(),
+        ));
+    }
+    for window in [&swap.shift, &swap.return_shift] {
+        if window.end <= window.start {
+            return Err(AppError::Validation(
+                "Swapped shifts must end after they start".to_string(),
+            ));
+        }
+    }
+    if overlaps(&swap.shift, &swap.return_shift) {
+        return Err(AppError::Validation(
+            "Swapped shifts must not overlap".to_string(),
+        ));
+    }
+    check_layer(schedule, swap.layer.as_deref())
+}
+
+fn check_layer(schedule: &OnCallSchedule, layer: Option<&str>) -> Result<()> {
+    match layer {
+        Some(name) if !schedule.layers.iter().any(|l| l.name == name) => Err(AppError::Validation(
+            format!("Schedule '{}' has no layer '{}'", schedule.name, name),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn check_hour(hour: u32, max: u32) -> Result<()> {
+    if hour > max {
+        return Err(AppError::Validation(format!(
+            "Hour {} is outside 0-{}",
+            hour, max
+        )));
+    }
+    Ok(())
+}
+
+fn overlaps(a: &ShiftWindow, b: &ShiftWindow) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+fn applies_to_layer(layer: Option<&str>, name: &str) -> bool {
+    layer.is_none_or(|l| l == name)
+}
+
+/// User covering for `user` through a swap at `at`, if any
+fn swapped_user(swaps: &[ShiftSwap], layer: &str, user: &str, at: DateTime<Utc>) -> Option<String> {
+    swaps
+        .iter()
+        .filter(|swap| applies_to_layer(swap.layer.as_deref(), layer))
+        .filter_map(|swap| {
+            if swap.shift.contains(at) && swap.user.eq_ignore_ascii_case(user) {
+                Some((swap.created_at, &swap.with_user))
+            } else if swap.return_shift.contains(at) && swap.with_user.eq_ignore_ascii_case(user) {
+                Some((swap.created_at, &swap.user))
+            } else {
+                None
+            }
+        })
+        .max_by_key(|(created_at, _)| *created_at)
+        .map(|(_, covering)| covering.clone())
+}
+
+/// Override in effect for a layer at `at`, given who is otherwise on call
+fn active_override<'a>(
+    overrides: &'a [ScheduleOverride],
+    layer: &str,
+    current: Option<&str>,
+    at: DateTime<Utc>,
+) -> Option<&'a ScheduleOverride> {
+    overrides
+        .iter()
+        .filter(|o| o.is_active(at) && applies_to_layer(o.layer.as_deref(), layer))
+        .filter(|o| match (&o.replaces, current) {
+            (None, _) => true,
+            (Some(replaces), Some(current)) => replaces.eq_ignore_ascii_case(current),
+            (Some(_), None) => false,
+        })
+        .max_by_key(|o| o.created_at)
+}
+
+/// Convert a local time to UTC, resolving daylight saving changes
+///
+/// As in RFC 5545, an ambiguous time is taken the first time it occurs and
+/// a time skipped when the clocks go forward is read with the UTC offset in
+/// effect before the change.
+pub(crate) fn local_to_utc<Tz: TimeZone>(tz: &Tz, local: &NaiveDateTime) -> Result<DateTime<Utc>> {
+    if let Some(time) = tz.from_local_datetime(local).earliest() {
+        return Ok(time.with_timezone(&Utc));
+    }
+    (1..=48)
+        .find_map(|hours| {
+            tz.offset_from_local_datetime(&(*local - Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|offset| {
+            let utc = *local - Duration::seconds(offset.fix().local_minus_utc() as i64);
+            utc.and_utc()
+        })
+        .ok_or_else(|| AppError::Internal(format!("Local time {} does not exist", local)))
+}
+
+fn parse_timezone(timezone: &str) -> Result<Tz> {
+    timezone
+        .parse()
+        .map_err(|_| AppError::Validation(format!("Invalid timezone: {}", timezone)))
+}
+
+fn rotation_epoch(year: i32, month: u32, day: u32) -> Result<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, day)
+        .ok_or_else(|| AppError::Internal("Failed to create epoch date".to_string()))
 }
 
 /// Parse weekday from string
@@ -350,7 +718,7 @@ fn parse_weekday(day: &str) -> Result<Weekday> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::policy::ScheduleLayer;
+    use crate::models::policy::{HolidayCalendar, ScheduleLayer};
     use chrono::TimeZone;
     use uuid::Uuid;
 
@@ -360,6 +728,8 @@ mod tests {
             name: "Test Schedule".to_string(),
             timezone: "America/New_York".to_string(),
             layers,
+            overrides: vec![],
+            swaps: vec![],
         }
     }
 
@@ -374,6 +744,7 @@ mod tests {
             ],
             rotation: RotationStrategy::Daily { handoff_hour: 9 },
             restrictions: None,
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![layer]);
@@ -400,6 +771,7 @@ mod tests {
                 handoff_hour: 9,
             },
             restrictions: None,
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![layer]);
@@ -423,6 +795,7 @@ mod tests {
                 start_hour: 9,
                 end_hour: 17,
             }),
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![layer]);
@@ -447,6 +820,7 @@ mod tests {
             users: vec!["primary@example.com".to_string()],
             rotation: RotationStrategy::Daily { handoff_hour: 9 },
             restrictions: None,
+            holidays: vec![],
         };
 
         let secondary = ScheduleLayer {
@@ -454,6 +828,7 @@ mod tests {
             users: vec!["secondary@example.com".to_string()],
             rotation: RotationStrategy::Daily { handoff_hour: 9 },
             restrictions: None,
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![primary, secondary]);
@@ -477,6 +852,7 @@ mod tests {
             ],
             rotation: RotationStrategy::Custom { duration_hours: 12 },
             restrictions: None,
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![layer]);
@@ -508,6 +884,7 @@ mod tests {
                 start_hour: 0,
                 end_hour: 24,
             }),
+            holidays: vec![],
         };
 
         let schedule = create_test_schedule(vec![layer]);
@@ -524,4 +901,233 @@ mod tests {
         let result = resolver.resolve_oncall(&schedule).unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    fn pair_schedule() -> OnCallSchedule {
+        OnCallSchedule {
+            id: Uuid::new_v4(),
+            name: "Pair".to_string(),
+            timezone: "UTC".to_string(),
+            layers: vec![ScheduleLayer {
+                name: "Primary".to_string(),
+                users: vec![
+                    "alice@example.com".to_string(),
+                    "bob@example.com".to_string(),
+                ],
+                rotation: RotationStrategy::Daily { handoff_hour: 9 },
+                restrictions: None,
+                holidays: vec![],
+            }],
+            overrides: vec![],
+            swaps: vec![],
+        }
+    }
+
+    fn schedule_override(
+        user: &str,
+        replaces: Option<&str>,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> ScheduleOverride {
+        ScheduleOverride {
+            id: Uuid::new_v4(),
+            layer: None,
+            user: user.to_string(),
+            replaces: replaces.map(String::from),
+            start,
+            end,
+            reason: None,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn oncall_at(schedule: &OnCallSchedule, at: DateTime<Utc>) -> (String, OnCallSource) {
+        let users = ScheduleResolver::new().resolve_at(schedule, at).unwrap();
+        assert_eq!(users.len(), 1);
+        (users[0].email.clone(), users[0].source)
+    }
+
+    #[test]
+    fn test_daily_rotation_hands_off_once_a_day() {
+        let schedule = pair_schedule();
+        let before = Utc.with_ymd_and_hms(2024, 1, 18, 8, 59, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2024, 1, 18, 9, 0, 0).unwrap();
+        let evening = Utc.with_ymd_and_hms(2024, 1, 17, 22, 0, 0).unwrap();
+
+        assert_eq!(oncall_at(&schedule, evening).0, "bob@example.com");
+        assert_eq!(oncall_at(&schedule, before).0, "bob@example.com");
+        assert_eq!(oncall_at(&schedule, after).0, "alice@example.com");
+    }
+
+    #[test]
+    fn test_override_precedence() {
+        let mut schedule = pair_schedule();
+        let at = |hour| Utc.with_ymd_and_hms(2024, 1, 17, hour, 0, 0).unwrap();
+
+        // Alice covers Bob from 12:00 to 14:00; an override for someone
+        // who is not on call does nothing
+        schedule.overrides.push(schedule_override(
+            "alice@example.com",
+            Some("bob@example.com"),
+            at(12),
+            at(14),
+        ));
+        schedule.overrides.push(schedule_override(
+            "carol@example.com",
+            Some("alice@example.com"),
+            at(12),
+            at(14),
+        ));
+        assert_eq!(
+            oncall_at(&schedule, at(11)),
+            ("bob@example.com".to_string(), OnCallSource::Rotation)
+        );
+        assert_eq!(
+            oncall_at(&schedule, at(12)),
+            ("alice@example.com".to_string(), OnCallSource::Override)
+        );
+        assert_eq!(oncall_at(&schedule, at(14)).0, "bob@example.com");
+
+        // A later override wins, and one without `replaces` covers holidays
+        let mut dave = schedule_override("dave@example.com", None, at(13), at(23));
+        dave.created_at = Utc::now() + Duration::seconds(1);
+        schedule.overrides.push(dave);
+        schedule.layers[0].holidays.push(HolidayCalendar {
+            name: "Company".to_string(),
+            dates: vec![NaiveDate::from_ymd_opt(2024, 1, 17).unwrap()],
+        });
+        assert_eq!(oncall_at(&schedule, at(13)).0, "dave@example.com");
+        assert_eq!(oncall_at(&schedule, at(22)).0, "dave@example.com");
+        assert!(ScheduleResolver::new()
+            .resolve_at(&schedule, at(23))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_swap_trades_shifts() {
+        let mut schedule = pair_schedule();
+        let day = |day| Utc.with_ymd_and_hms(2024, 1, day, 9, 0, 0).unwrap();
+        let noon = |day| Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap();
+
+        // Bob has the 17th and Alice the 18th; they trade
+        schedule.swaps.push(ShiftSwap {
+            id: Uuid::new_v4(),
+            layer: Some("Primary".to_string()),
+            user: "alice@example.com".to_string(),
+            shift: ShiftWindow {
+                start: day(18),
+                end: day(19),
+            },
+            with_user: "bob@example.com".to_string(),
+            return_shift: ShiftWindow {
+                start: day(17),
+                end: day(18),
+            },
+            created_by: None,
+            created_at: Utc::now(),
+        });
+        validate_swap(&schedule, &schedule.swaps[0]).unwrap();
+
+        assert_eq!(
+            oncall_at(&schedule, noon(17)),
+            ("alice@example.com".to_string(), OnCallSource::Swap)
+        );
+        assert_eq!(
+            oncall_at(&schedule, noon(18)),
+            ("bob@example.com".to_string(), OnCallSource::Swap)
+        );
+        assert_eq!(
+            oncall_at(&schedule, noon(19)),
+            ("bob@example.com".to_string(), OnCallSource::Rotation)
+        );
+
+        // Overrides still take precedence over swaps
+        schedule.overrides.push(schedule_override(
+            "carol@example.com",
+            Some("bob@example.com"),
+            noon(18),
+            noon(18) + Duration::hours(1),
+        ));
+        assert_eq!(oncall_at(&schedule, noon(18)).0, "carol@example.com");
+    }
+
+    #[test]
+    fn test_preview_merges_shifts() {
+        let mut schedule = pair_schedule();
+        let at = |day, hour| Utc.with_ymd_and_hms(2024, 1, day, hour, 0, 0).unwrap();
+        schedule.overrides.push(schedule_override(
+            "alice@example.com",
+            Some("bob@example.com"),
+            at(17, 12),
+            at(17, 14),
+        ));
+        schedule.layers[0].holidays.push(HolidayCalendar {
+            name: "Company".to_string(),
+            dates: vec![NaiveDate::from_ymd_opt(2024, 1, 19).unwrap()],
+        });
+
+        // Bob's shift from 09:00 on the 19th starts on a holiday
+        let shifts = ScheduleResolver::new()
+            .preview(&schedule, at(17, 9), at(20, 9))
+            .unwrap();
+        let summary: Vec<_> = shifts
+            .iter()
+            .map(|s| (s.user.as_str(), s.source, s.start, s.end))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "bob@example.com",
+                    OnCallSource::Rotation,
+                    at(17, 9),
+                    at(17, 12)
+                ),
+                (
+                    "alice@example.com",
+                    OnCallSource::Override,
+                    at(17, 12),
+                    at(17, 14)
+                ),
+                (
+                    "bob@example.com",
+                    OnCallSource::Rotation,
+                    at(17, 14),
+                    at(18, 9)
+                ),
+                (
+                    "alice@example.com",
+                    OnCallSource::Rotation,
+                    at(18, 9),
+                    at(19, 0)
+                ),
+                (
+                    "bob@example.com",
+                    OnCallSource::Rotation,
+                    at(20, 0),
+                    at(20, 9)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_schedule_changes() {
+        let schedule = pair_schedule();
+        let start = Utc.with_ymd_and_hms(2024, 1, 17, 9, 0, 0).unwrap();
+
+        let backwards = schedule_override("alice@example.com", None, start, start);
+        assert!(validate_override(&schedule, &backwards).is_err());
+
+        let mut unknown_layer =
+            schedule_override("alice@example.com", None, start, start + Duration::hours(1));
+        unknown_layer.layer = Some("Secondary".to_string());
+        assert!(validate_override(&schedule, &unknown_layer).is_err());
+
+        let mut duplicate = pair_schedule();
+        duplicate.layers.push(duplicate.layers[0].clone());
+        assert!(validate_schedule(&duplicate).is_err());
+        assert!(validate_schedule(&schedule).is_ok());
+    }
 }
@@ -40,6 +40,10 @@ pub struct EscalationState {
 
     /// History of notifications sent
     pub notification_history: Vec<EscalationNotification>,
+
+    /// Responders picked from teams by selecting targets
+    #[serde(default)]
+    pub selections: Vec<ResponderSelection>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -82,6 +86,33 @@ pub struct EscalationNotification {
     pub error: Option<String>,
 }
 
+/// How a responder was picked from a team
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionStrategy {
+    RoundRobin,
+    LeastLoaded,
+    FirstToAcknowledge,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponderSelection {
+    /// Escalation level that made the selection
+    pub level: u32,
+
+    /// Team the responder was picked from
+    pub team_id: String,
+
+    pub strategy: SelectionStrategy,
+
+    /// Chosen team member; `None` while a first-to-acknowledge selection
+    /// is waiting for someone to acknowledge
+    pub responder: Option<String>,
+
+    /// Why the responder was chosen
+    pub reason: String,
+}
+
 impl EscalationState {
     /// Create a new escalation state
     pub fn new(incident_id: Uuid, policy_id: Uuid, first_level_delay_minutes: u32) -> Self {
@@ -105,6 +136,7 @@ impl EscalationState {
             repeat_count: 0,
             status: EscalationStatus::Active,
             notification_history: Vec::new(),
+            selections: Vec::new(),
         }
     }
 
@@ -180,6 +212,32 @@ impl EscalationState {
         self.notification_history.push(notification);
     }
 
+    /// Give open first-to-acknowledge selections to the acknowledging user
+    ///
+    /// Only selections for teams `is_member` reports the user belongs to are
+    /// claimed. Returns the IDs of the teams claimed.
+    pub fn claim_first_to_acknowledge(
+        &mut self,
+        user: &str,
+        is_member: impl Fn(&str) -> bool,
+    ) -> Vec<String> {
+        let mut claimed = Vec::new();
+        for selection in &mut self.selections {
+            if selection.strategy != SelectionStrategy::FirstToAcknowledge
+                || selection.responder.is_some()
+                || !is_member(&selection.team_id)
+            {
+                continue;
+            }
+            selection.responder = Some(user.to_string());
+            selection.reason = "First team member to acknowledge".to_string();
+            if !claimed.contains(&selection.team_id) {
+                claimed.push(selection.team_id.clone());
+            }
+        }
+        claimed
+    }
+
     /// Get time until next escalation
     pub fn time_until_next_escalation(&self) -> Option<chrono::Duration> {
         self.next_escalation_at.map(|next| next - Utc::now())
@@ -257,4 +315,34 @@ mod tests {
         assert_eq!(state.repeat_count, 1);
         assert_eq!(state.status, EscalationStatus::Active);
     }
+
+    #[test]
+    fn test_claim_first_to_acknowledge() {
+        let mut state = EscalationState::new(Uuid::new_v4(), Uuid::new_v4(), 0);
+        for team_id in ["db-team", "web-team"] {
+            state.selections.push(ResponderSelection {
+                level: 0,
+                team_id: team_id.to_string(),
+                strategy: SelectionStrategy::FirstToAcknowledge,
+                responder: None,
+                reason: "Notified 2 members".to_string(),
+            });
+        }
+
+        let claimed = state.claim_first_to_acknowledge("dba@example.com", |team| team == "db-team");
+        assert_eq!(claimed, vec!["db-team".to_string()]);
+        assert_eq!(
+            state.selections[0].responder.as_deref(),
+            Some("dba@example.com")
+        );
+        assert!(state.selections[1].responder.is_none());
+
+        // A selection that has been claimed is not handed out again
+        let claimed = state.claim_first_to_acknowledge("other@example.com", |_| true);
+        assert_eq!(claimed, vec!["web-team".to_string()]);
+        assert_eq!(
+            state.selections[0].responder.as_deref(),
+            Some("dba@example.com")
+        );
+    }
 }
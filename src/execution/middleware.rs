@@ -15,6 +15,15 @@ use super::context::ExecutionContext;
 /// These are infrastructure endpoints that don't participate in the agentics execution graph.
 const EXCLUDED_PATHS: &[&str] = &["/health", "/health/live", "/health/ready", "/metrics", "/ws"];
 
+/// `/v1/*` path prefixes that are excluded from execution context enforcement.
+/// Notification actions are called by Slack, PagerDuty and email link clicks, which cannot
+/// send execution headers; their handlers authenticate each request by its signature instead.
+const EXCLUDED_PATH_PREFIXES: &[&str] = &["/v1/notification-actions/"];
+
+/// `/v1/*` path suffixes that are excluded from execution context enforcement.
+/// Calendar apps subscribe to `.ics` feeds with plain GET requests and cannot add headers.
+const EXCLUDED_PATH_SUFFIXES: &[&str] = &["/calendar.ics"];
+
 /// Axum middleware that extracts execution context from request headers.
 ///
 /// For `/v1/*` paths:
@@ -22,13 +31,16 @@ const EXCLUDED_PATHS: &[&str] = &["/health", "/health/live", "/health/ready", "/
 /// - Returns 400 if either header is missing or invalid
 /// - Creates an `ExecutionContext` and inserts it into request extensions
 ///
-/// For excluded paths (health, metrics, ws):
+/// For excluded paths (health, metrics, ws, notification actions, calendar feeds):
 /// - Passes through without requiring headers
 pub async fn execution_context_middleware(mut req: Request<Body>, next: Next) -> Response {
     let path = req.uri().path().to_string();
 
     // Skip excluded paths
-    if EXCLUDED_PATHS.iter().any(|p| path == *p) {
+    if EXCLUDED_PATHS.iter().any(|p| path == *p)
+        || EXCLUDED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
+        || EXCLUDED_PATH_SUFFIXES.iter().any(|p| path.ends_with(p))
+    {
         return next.run(req).await;
     }
 
@@ -0,0 +1,30 @@
+//! GitOps configuration
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Definition directory configuration
+///
+/// ```toml
+/// [gitops]
+/// dir = "./definitions"
+/// reload_interval_secs = 10
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitOpsConfig {
+    /// Directory definitions are loaded from; nothing is loaded when unset
+    pub dir: Option<PathBuf>,
+
+    /// Seconds between checks for changed files, 0 to only load at startup
+    pub reload_interval_secs: u64,
+}
+
+impl Default for GitOpsConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            reload_interval_secs: 10,
+        }
+    }
+}
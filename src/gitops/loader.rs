@@ -0,0 +1,526 @@
+//! Definition directory loading
+
+use crate::error::AppError;
+use crate::escalation::{validate_policy, validate_rule};
+use crate::models::policy::{EscalationPolicy, RoutingAction, RoutingRule};
+use crate::models::Playbook;
+use crate::playbooks::validate_playbook;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Subdirectory holding playbooks
+pub const PLAYBOOKS_DIR: &str = "playbooks";
+
+/// Subdirectory holding escalation policies
+pub const ESCALATION_POLICIES_DIR: &str = "escalation_policies";
+
+/// Subdirectory holding routing rules
+pub const ROUTING_RULES_DIR: &str = "routing_rules";
+
+/// Definitions loaded from a directory
+#[derive(Debug, Clone, Default)]
+pub struct DefinitionSet {
+    pub playbooks: Vec<Playbook>,
+    pub escalation_policies: Vec<EscalationPolicy>,
+    pub routing_rules: Vec<RoutingRule>,
+}
+
+/// Problem found in a definition file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionError {
+    pub path: PathBuf,
+
+    /// 1-based line, when the problem can be placed
+    pub line: Option<usize>,
+
+    /// 1-based column, when the problem can be placed
+    pub column: Option<usize>,
+
+    pub message: String,
+}
+
+impl DefinitionError {
+    fn new(path: &Path, line: Option<usize>, column: Option<usize>, message: String) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+            if let Some(column) = self.column {
+                write!(f, ":{}", column)?;
+            }
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Load every definition under `dir`
+///
+/// Playbooks are read from `playbooks/`, escalation policies from
+/// `escalation_policies/` and routing rules from `routing_rules/`, each
+/// searched recursively. `.yaml` and `.yml` files may hold several documents
+/// separated by `---`; `.toml` files hold one definition. Other files are
+/// ignored.
+///
+/// Definitions are checked the same way registration checks them, ids must
+/// be unique, and playbooks applied by routing rules must be defined in the
+/// directory. All problems are returned, not just the first.
+pub fn load_dir(dir: &Path) -> std::result::Result<DefinitionSet, Vec<DefinitionError>> {
+    if !dir.is_dir() {
+        return Err(vec![DefinitionError::new(
+            dir,
+            None,
+            None,
+            "Not a directory".to_string(),
+        )]);
+    }
+
+    let mut errors = Vec::new();
+    let playbooks = load_kind(
+        &dir.join(PLAYBOOKS_DIR),
+        "playbook",
+        |p: &Playbook| p.id,
+        validate_playbook,
+        &mut errors,
+    );
+    let escalation_policies = load_kind(
+        &dir.join(ESCALATION_POLICIES_DIR),
+        "escalation policy",
+        |p: &EscalationPolicy| p.id,
+        validate_policy,
+        &mut errors,
+    );
+    let routing_rules = load_kind(
+        &dir.join(ROUTING_RULES_DIR),
+        "routing rule",
+        |r: &RoutingRule| r.id,
+        validate_rule,
+        &mut errors,
+    );
+
+    let playbook_ids: HashSet<Uuid> = playbooks.iter().map(|d| d.value.id).collect();
+    for rule in &routing_rules {
+        for action in &rule.value.actions {
+            if let RoutingAction::ApplyPlaybook { playbook_id } = action {
+                if !playbook_ids.contains(playbook_id) {
+                    errors.push(rule.error(format!(
+                        "Routing rule '{}' applies playbook {} which is not defined",
+                        rule.value.name, playbook_id
+                    )));
+                }
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(DefinitionSet {
+        playbooks: playbooks.into_iter().map(|d| d.value).collect(),
+        escalation_policies: escalation_policies.into_iter().map(|d| d.value).collect(),
+        routing_rules: routing_rules.into_iter().map(|d| d.value).collect(),
+    })
+}
+
+/// Definition files under `dir`, in the order they are loaded
+pub fn definition_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for kind in [PLAYBOOKS_DIR, ESCALATION_POLICIES_DIR, ROUTING_RULES_DIR] {
+        collect_files(&dir.join(kind), &mut files);
+    }
+    files
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else if Format::of(&path).is_some() {
+            files.push(path);
+        }
+    }
+}
+
+/// A definition and where it was read from
+struct Document<T> {
+    value: T,
+    path: PathBuf,
+    line: usize,
+}
+
+impl<T> Document<T> {
+    fn error(&self, message: String) -> DefinitionError {
+        DefinitionError::new(&self.path, Some(self.line), None, message)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    fn of(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => Some(Format::Yaml),
+            Some("toml") => Some(Format::Toml),
+            _ => None,
+        }
+    }
+}
+
+fn load_kind<T: DeserializeOwned>(
+    dir: &Path,
+    kind: &str,
+    id: impl Fn(&T) -> Uuid,
+    validate: impl Fn(&T) -> crate::error::Result<()>,
+    errors: &mut Vec<DefinitionError>,
+) -> Vec<Document<T>> {
+    let mut files = Vec::new();
+    collect_files(dir, &mut files);
+
+    let mut documents = Vec::new();
+    let mut seen: HashMap<Uuid, (PathBuf, usize)> = HashMap::new();
+    for path in files {
+        for document in parse_file::<T>(&path, errors) {
+            if let Err(e) = validate(&document.value) {
+                errors.push(document.error(validation_message(e)));
+                continue;
+            }
+            let id = id(&document.value);
+            if let Some((first_path, first_line)) = seen.get(&id) {
+                errors.push(document.error(format!(
+                    "Duplicate {} id {}, first defined at {}:{}",
+                    kind,
+                    id,
+                    first_path.display(),
+                    first_line
+                )));
+                continue;
+            }
+            seen.insert(id, (document.path.clone(), document.line));
+            documents.push(document);
+        }
+    }
+    documents
+}
+
+fn parse_file<T: DeserializeOwned>(
+    path: &Path,
+    errors: &mut Vec<DefinitionError>,
+) -> Vec<Document<T>> {
+    let Some(format) = Format::of(path) else {
+        return Vec::new();
+    };
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            errors.push(DefinitionError::new(path, None, None, e.to_string()));
+            return Vec::new();
+        }
+    };
+
+    match format {
+        Format::Yaml => parse_yaml(path, &text, errors),
+        Format::Toml => match toml::from_str::<T>(&text) {
+            Ok(value) => vec![Document {
+                value,
+                path: path.to_path_buf(),
+                line: 1,
+            }],
+            Err(e) => {
+                let (line, column) = e.span().map(|span| line_column(&text, span.start)).unzip();
+                errors.push(DefinitionError::new(
+                    path,
+                    line,
+                    column,
+                    e.message().to_string(),
+                ));
+                Vec::new()
+            }
+        },
+    }
+}
+
+fn parse_yaml<T: DeserializeOwned>(
+    path: &Path,
+    text: &str,
+    errors: &mut Vec<DefinitionError>,
+) -> Vec<Document<T>> {
+    let starts = document_starts(text);
+    let mut documents = Vec::new();
+
+    for (i, document) in serde_yaml::Deserializer::from_str(text).enumerate() {
+        // Empty documents, such as a trailing `---`, are skipped
+        match Option::<T>::deserialize(document) {
+            Ok(Some(value)) => documents.push(Document {
+                value,
+                path: path.to_path_buf(),
+                line: starts.get(i).copied().unwrap_or(1),
+            }),
+            Ok(None) => {}
+            Err(e) => {
+                let location = e.location();
+                let line = location.as_ref().map(|l| l.line());
+                let column = location.as_ref().map(|l| l.column());
+                let mut message = e.to_string();
+                if let (Some(line), Some(column)) = (line, column) {
+                    let suffix = format!(" at line {} column {}", line, column);
+                    if let Some(stripped) = message.strip_suffix(&suffix) {
+                        message = stripped.to_string();
+                    }
+                }
+                errors.push(DefinitionError::new(path, line, column, message));
+                // The parser keeps returning the same error after a syntax
+                // error, so the rest of the file is not read
+                break;
+            }
+        }
+    }
+    documents
+}
+
+/// First line of each YAML document in `text`
+fn document_starts(text: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut content_seen = false;
+
+    for (i, line) in text.lines().enumerate() {
+        if line == "---" || line.starts_with("--- ") || line.starts_with("---\t") {
+            // A leading marker opens the first document rather than ending it
+            starts.push(i + 2);
+            content_seen = true;
+        } else if !content_seen {
+            let trimmed = line.trim();
+            if !trimmed.is_empty() && !trimmed.starts_with('#') && !trimmed.starts_with('%') {
+                starts.push(i + 1);
+                content_seen = true;
+            }
+        }
+    }
+    if starts.is_empty() {
+        starts.push(1);
+    }
+    starts
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+fn validation_message(error: AppError) -> String {
+    match error {
+        AppError::Validation(message) => message,
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYBOOK_ID: &str = "6f1c2a7e-0d4b-4a53-9a55-2f0b9c6e1a01";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gitops-{}-{}", name, Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write(dir: &Path, file: &str, contents: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+
+    fn playbook_yaml(id: &str, name: &str) -> String {
+        format!(
+            r#"id: {id}
+name: {name}
+version: "1.0.0"
+description: Restart the API
+owner: sre
+triggers:
+  severity_trigger: [P0, P1]
+steps:
+  - id: restart
+    step_type: remediation
+    actions:
+      - action_type: service_restart
+        parameters:
+          service: api
+enabled: true
+"#
+        )
+    }
+
+    const POLICY_TOML: &str = r#"
+id = "0b7d7f3e-41a4-4d84-8f43-8a3f3c2f9b11"
+name = "critical"
+description = "Page the on-call engineer"
+enabled = true
+severity_filter = ["P0"]
+
+[[levels]]
+level = 0
+delay_minutes = 0
+targets = [{ type = "user", email = "oncall@example.com" }]
+"#;
+
+    #[test]
+    fn test_load_definitions() {
+        let dir = temp_dir("load");
+        write(
+            &dir,
+            "playbooks/api.yaml",
+            &format!(
+                "---\n{}---\n{}",
+                playbook_yaml(PLAYBOOK_ID, "api"),
+                playbook_yaml("3d1fcb8e-6b0e-4f0a-b1a4-7c0d7c1d7a02", "db")
+            ),
+        );
+        write(&dir, "escalation_policies/critical.toml", POLICY_TOML);
+        write(
+            &dir,
+            "routing_rules/team/api.yml",
+            &format!(
+                r#"id: 9a8e8f1c-2f55-4f3b-a9a5-0b5a7c2e4f03
+name: api
+priority: 10
+enabled: true
+conditions:
+  - field: source
+    operator: matches
+    value: "^api-"
+actions:
+  - type: apply_playbook
+    playbook_id: {PLAYBOOK_ID}
+"#
+            ),
+        );
+        write(&dir, "playbooks/README.md", "not a definition");
+
+        let set = load_dir(&dir).unwrap();
+        assert_eq!(set.playbooks.len(), 2);
+        assert_eq!(set.playbooks[1].name, "db");
+        assert_eq!(set.escalation_policies.len(), 1);
+        assert_eq!(set.routing_rules.len(), 1);
+        assert_eq!(definition_files(&dir).len(), 3);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors_point_at_lines() {
+        let dir = temp_dir("errors");
+        // The second document starts on line 17 and its owner is a list
+        let second = playbook_yaml("3d1fcb8e-6b0e-4f0a-b1a4-7c0d7c1d7a02", "db")
+            .replace("owner: sre", "owner: [sre]");
+        write(
+            &dir,
+            "playbooks/api.yaml",
+            &format!("{}---\n{}", playbook_yaml(PLAYBOOK_ID, "api"), second),
+        );
+        write(
+            &dir,
+            "escalation_policies/critical.toml",
+            &POLICY_TOML.replace("delay_minutes = 0", "delay_minutes = \"soon\""),
+        );
+        write(
+            &dir,
+            "escalation_policies/empty.yaml",
+            "id: 5c2e9d4a-1b3f-4e8a-9c7d-6e5f4a3b2c04\nname: empty\ndescription: none\n\
+             enabled: true\nlevels: []\n",
+        );
+
+        let errors = load_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 3);
+
+        let yaml = &errors[0];
+        assert!(yaml.path.ends_with("playbooks/api.yaml"));
+        assert_eq!((yaml.line, yaml.column), (Some(21), Some(8)));
+        assert!(yaml
+            .to_string()
+            .ends_with("api.yaml:21:8: owner: invalid type: sequence, expected a string"));
+
+        let toml = &errors[1];
+        assert!(toml.path.ends_with("critical.toml"));
+        assert_eq!(toml.line, Some(10));
+        assert_eq!(toml.message, "invalid type: string \"soon\", expected u32");
+
+        let empty = &errors[2];
+        assert_eq!(empty.line, Some(1));
+        assert_eq!(
+            empty.message,
+            "Escalation policy must have at least one level"
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_duplicates_and_missing_playbooks() {
+        let dir = temp_dir("refs");
+        write(&dir, "playbooks/a.yaml", &playbook_yaml(PLAYBOOK_ID, "a"));
+        write(
+            &dir,
+            "playbooks/b.yaml",
+            &format!("\n\n{}", playbook_yaml(PLAYBOOK_ID, "b")),
+        );
+        write(
+            &dir,
+            "routing_rules/rule.toml",
+            r#"id = "9a8e8f1c-2f55-4f3b-a9a5-0b5a7c2e4f03"
+name = "db"
+priority = 1
+enabled = true
+conditions = [{ field = "source", operator = "equals", value = "db" }]
+actions = [{ type = "apply_playbook", playbook_id = "11111111-1111-1111-1111-111111111111" }]
+"#,
+        );
+
+        let errors = load_dir(&dir).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].line, Some(3));
+        assert!(errors[0].message.starts_with("Duplicate playbook id"));
+        assert!(errors[1].message.contains("which is not defined"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_document_starts() {
+        assert_eq!(document_starts(""), vec![1]);
+        assert_eq!(document_starts("# header\na: 1\n---\nb: 2\n"), vec![2, 4]);
+        assert_eq!(
+            document_starts("---\na: 1\n--- \nb: 2\n---\n"),
+            vec![2, 4, 6]
+        );
+        assert_eq!(line_column("a = 1\nb = x\n", 10), (2, 5));
+    }
+}
@@ -0,0 +1,42 @@
+//! Definitions loaded from a directory
+//!
+//! Playbooks, escalation policies and routing rules can be kept as YAML or
+//! TOML files in a directory, typically a git checkout, instead of being
+//! registered through the API. The server loads the directory set in
+//! `[gitops]` at startup and reloads it when files change; invalid files
+//! are rejected with the line they went wrong on and the definitions
+//! already loaded are kept. `llm-im-cli config validate <dir>` runs the
+//! same checks, e.g. in CI before a merge.
+//!
+//! ```text
+//! definitions/
+//! ├── playbooks/
+//! │   └── api-restart.yaml
+//! ├── escalation_policies/
+//! │   └── critical.toml
+//! └── routing_rules/
+//!     └── api.yaml
+//! ```
+//!
+//! # Example
+//!
+//! ```no_run
+//! use llm_incident_manager::gitops::load_dir;
+//! use std::path::Path;
+//!
+//! match load_dir(Path::new("./definitions")) {
+//!     Ok(set) => println!("{} playbooks", set.playbooks.len()),
+//!     Err(errors) => errors.iter().for_each(|e| eprintln!("{}", e)),
+//! }
+//! ```
+
+mod config;
+mod loader;
+mod reloader;
+
+pub use config::GitOpsConfig;
+pub use loader::{
+    definition_files, load_dir, DefinitionError, DefinitionSet, ESCALATION_POLICIES_DIR,
+    PLAYBOOKS_DIR, ROUTING_RULES_DIR,
+};
+pub use reloader::{GitOpsReloader, ReloadReport};
@@ -0,0 +1,288 @@
+//! Applying a definition directory and watching it for changes
+
+use crate::escalation::{EscalationEngine, RoutingRuleEvaluator};
+use crate::gitops::loader::{definition_files, load_dir, DefinitionError};
+use crate::playbooks::PlaybookService;
+use parking_lot::Mutex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Loads a definition directory into the running services and keeps them
+/// in sync with it
+///
+/// A reload is all or nothing: when any file is invalid the errors are
+/// logged and the definitions already loaded stay in place. Definitions
+/// that were loaded from the directory and have since been removed from it
+/// are unregistered; ones registered through the API are left alone.
+pub struct GitOpsReloader {
+    dir: PathBuf,
+    playbook_service: Arc<PlaybookService>,
+    escalation_engine: Arc<EscalationEngine>,
+    routing_evaluator: Arc<RoutingRuleEvaluator>,
+    reload_interval_secs: u64,
+    state: Mutex<ReloaderState>,
+}
+
+/// What the last reload saw and registered
+#[derive(Default)]
+struct ReloaderState {
+    fingerprint: Option<Vec<FileStamp>>,
+    playbooks: HashSet<Uuid>,
+    escalation_policies: HashSet<Uuid>,
+    routing_rules: HashSet<Uuid>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Outcome of a successful reload
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReloadReport {
+    pub playbooks: usize,
+    pub escalation_policies: usize,
+    pub routing_rules: usize,
+
+    /// Definitions unregistered because their files no longer define them
+    pub removed: usize,
+}
+
+impl GitOpsReloader {
+    pub fn new(
+        dir: impl Into<PathBuf>,
+        playbook_service: Arc<PlaybookService>,
+        escalation_engine: Arc<EscalationEngine>,
+        routing_evaluator: Arc<RoutingRuleEvaluator>,
+    ) -> Self {
+        Self {
+            dir: dir.into(),
+            playbook_service,
+            escalation_engine,
+            routing_evaluator,
+            reload_interval_secs: 10,
+            state: Mutex::new(ReloaderState::default()),
+        }
+    }
+
+    /// Set how often the directory is checked for changes
+    pub fn with_reload_interval(mut self, secs: u64) -> Self {
+        self.reload_interval_secs = secs;
+        self
+    }
+
+    /// Directory definitions are loaded from
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Load the directory and apply it
+    pub fn reload(&self) -> std::result::Result<ReloadReport, Vec<DefinitionError>> {
+        let mut state = self.state.lock();
+        state.fingerprint = Some(fingerprint(&self.dir));
+
+        let set = match load_dir(&self.dir) {
+            Ok(set) => set,
+            Err(errors) => {
+                for e in &errors {
+                    error!(dir = %self.dir.display(), "Invalid definition: {}", e);
+                }
+                warn!(
+                    dir = %self.dir.display(),
+                    errors = errors.len(),
+                    "Definitions not reloaded, keeping the ones already loaded"
+                );
+                return Err(errors);
+            }
+        };
+
+        let mut report = ReloadReport {
+            playbooks: set.playbooks.len(),
+            escalation_policies: set.escalation_policies.len(),
+            routing_rules: set.routing_rules.len(),
+            removed: 0,
+        };
+
+        let playbooks: HashSet<Uuid> = set.playbooks.iter().map(|p| p.id).collect();
+        let escalation_policies: HashSet<Uuid> =
+            set.escalation_policies.iter().map(|p| p.id).collect();
+        let routing_rules: HashSet<Uuid> = set.routing_rules.iter().map(|r| r.id).collect();
+
+        for playbook in set.playbooks {
+            if let Err(e) = self.playbook_service.register_playbook(playbook) {
+                error!("Failed to register playbook: {}", e);
+            }
+        }
+        for policy in set.escalation_policies {
+            if let Err(e) = self.escalation_engine.register_policy(policy) {
+                error!("Failed to register escalation policy: {}", e);
+            }
+        }
+        for rule in set.routing_rules {
+            if let Err(e) = self.routing_evaluator.register_rule(rule) {
+                error!("Failed to register routing rule: {}", e);
+            }
+        }
+
+        for id in state.playbooks.difference(&playbooks) {
+            if self.playbook_service.delete_playbook(id).is_ok() {
+                report.removed += 1;
+            }
+        }
+        for id in state.escalation_policies.difference(&escalation_policies) {
+            if self.escalation_engine.remove_policy(id).is_some() {
+                report.removed += 1;
+            }
+        }
+        for id in state.routing_rules.difference(&routing_rules) {
+            if self.routing_evaluator.remove_rule(id).is_some() {
+                report.removed += 1;
+            }
+        }
+
+        state.playbooks = playbooks;
+        state.escalation_policies = escalation_policies;
+        state.routing_rules = routing_rules;
+
+        info!(
+            dir = %self.dir.display(),
+            playbooks = report.playbooks,
+            escalation_policies = report.escalation_policies,
+            routing_rules = report.routing_rules,
+            removed = report.removed,
+            "Loaded definitions"
+        );
+        Ok(report)
+    }
+
+    /// Reload if any definition file was added, removed or modified since
+    /// the last reload
+    ///
+    /// Returns `None` when nothing changed.
+    pub fn reload_if_changed(
+        &self,
+    ) -> Option<std::result::Result<ReloadReport, Vec<DefinitionError>>> {
+        let current = fingerprint(&self.dir);
+        if self.state.lock().fingerprint.as_ref() == Some(&current) {
+            return None;
+        }
+        Some(self.reload())
+    }
+
+    /// Check the directory for changes periodically
+    pub async fn run_watcher(self: Arc<Self>) {
+        if self.reload_interval_secs == 0 {
+            return;
+        }
+        info!(
+            dir = %self.dir.display(),
+            reload_interval = self.reload_interval_secs,
+            "Watching definitions for changes"
+        );
+
+        loop {
+            sleep(Duration::from_secs(self.reload_interval_secs)).await;
+            let _ = self.reload_if_changed();
+        }
+    }
+}
+
+fn fingerprint(dir: &Path) -> Vec<FileStamp> {
+    definition_files(dir)
+        .into_iter()
+        .map(|path| {
+            let metadata = std::fs::metadata(&path).ok();
+            FileStamp {
+                modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+                len: metadata.map_or(0, |m| m.len()),
+                path,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::InMemoryStore;
+
+    fn playbook_yaml(id: Uuid, name: &str) -> String {
+        format!(
+            r#"id: {id}
+name: {name}
+version: "1.0.0"
+description: Restart the API
+owner: sre
+triggers: {{}}
+steps:
+  - id: restart
+    step_type: remediation
+    actions:
+      - action_type: service_restart
+        parameters:
+          service: api
+enabled: true
+"#
+        )
+    }
+
+    fn reloader(dir: &Path) -> GitOpsReloader {
+        let store = Arc::new(InMemoryStore::new());
+        let playbook_service = Arc::new(PlaybookService::new(store.clone(), None, false));
+        let escalation_engine = Arc::new(EscalationEngine::new(None, store));
+        let routing_evaluator = Arc::new(RoutingRuleEvaluator::new(Some(playbook_service.clone())));
+        GitOpsReloader::new(dir, playbook_service, escalation_engine, routing_evaluator)
+    }
+
+    #[test]
+    fn test_reload_applies_changes() {
+        let dir = std::env::temp_dir().join(format!("gitops-reload-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("playbooks")).unwrap();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        std::fs::write(
+            dir.join("playbooks/first.yaml"),
+            playbook_yaml(first, "first"),
+        )
+        .unwrap();
+
+        let reloader = reloader(&dir);
+        let api_playbook = playbook_yaml(Uuid::new_v4(), "api");
+        let api_playbook: crate::models::Playbook = serde_yaml::from_str(&api_playbook).unwrap();
+        reloader
+            .playbook_service
+            .register_playbook(api_playbook)
+            .unwrap();
+
+        let report = reloader.reload().unwrap();
+        assert_eq!(report.playbooks, 1);
+        assert!(reloader.reload_if_changed().is_none());
+
+        // An invalid file leaves the loaded definitions in place
+        std::fs::write(dir.join("playbooks/second.yaml"), "id: [").unwrap();
+        assert!(reloader.reload_if_changed().unwrap().is_err());
+        assert!(reloader.playbook_service.get_playbook(&first).is_some());
+        assert!(reloader.reload_if_changed().is_none());
+
+        std::fs::write(
+            dir.join("playbooks/second.yaml"),
+            playbook_yaml(second, "second"),
+        )
+        .unwrap();
+        std::fs::remove_file(dir.join("playbooks/first.yaml")).unwrap();
+        let report = reloader.reload_if_changed().unwrap().unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(reloader.playbook_service.get_playbook(&first).is_none());
+        assert!(reloader.playbook_service.get_playbook(&second).is_some());
+        // Playbooks registered through the API are kept
+        assert_eq!(reloader.playbook_service.list_playbooks().len(), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -5,10 +5,30 @@
 use async_graphql::*;
 use uuid::Uuid;
 
+use crate::error::AppError;
 use crate::models;
+use crate::state::modify_incident;
 use super::context::GraphQLContext;
 use super::types::*;
 
+/// Map a failed incident write to a GraphQL error
+///
+/// Version conflicts carry `code`, `expectedVersion` and `currentVersion`
+/// extensions so clients can refetch and retry.
+fn update_error(e: AppError) -> Error {
+    let message = format!("Failed to update incident: {}", e);
+    match e {
+        AppError::VersionConflict {
+            expected, actual, ..
+        } => Error::new(message).extend_with(|_, ext| {
+            ext.set("code", "VERSION_CONFLICT");
+            ext.set("expectedVersion", expected);
+            ext.set("currentVersion", actual);
+        }),
+        _ => Error::new(message),
+    }
+}
+
 /// Root mutation object
 pub struct MutationRoot;
 
@@ -93,6 +113,16 @@ impl MutationRoot {
             .await
             .map_err(|e| Error::new(format!("Failed to get incident: {}", e)))?;
 
+        if let Some(expected) = input.expected_version {
+            if expected != incident.version {
+                return Err(update_error(AppError::VersionConflict {
+                    resource: format!("incident {}", id),
+                    expected,
+                    actual: incident.version,
+                }));
+            }
+        }
+
         // Apply updates
         if let Some(new_state) = input.state {
             let actor = gql_ctx.current_user();
@@ -124,7 +154,8 @@ impl MutationRoot {
             .store()
             .update_incident(&incident)
             .await
-            .map_err(|e| Error::new(format!("Failed to update incident: {}", e)))?;
+            .map_err(update_error)?;
+        incident.version += 1;
 
         Ok(Incident(incident))
     }
@@ -165,32 +196,21 @@ impl MutationRoot {
     ) -> Result<Incident> {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
 
-        // Get existing incident
-        let mut incident = gql_ctx
-            .processor
-            .get_incident(&incident_id)
-            .await
-            .map_err(|e| Error::new(format!("Failed to get incident: {}", e)))?;
-
         // Add comment as timeline event
         let actor = gql_ctx.current_user();
-        let event = models::TimelineEvent {
-            timestamp: chrono::Utc::now(),
-            event_type: models::EventType::CommentAdded,
-            actor,
-            description: comment,
-            metadata: std::collections::HashMap::new(),
-        };
-
-        incident.add_timeline_event(event);
-
-        // Save the updated incident
-        gql_ctx
-            .processor
-            .store()
-            .update_incident(&incident)
-            .await
-            .map_err(|e| Error::new(format!("Failed to update incident: {}", e)))?;
+        let store = gql_ctx.processor.store().as_ref();
+        let incident = modify_incident(store, &incident_id, |incident| {
+            incident.add_timeline_event(models::TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: models::EventType::CommentAdded,
+                actor: actor.clone(),
+                description: comment.clone(),
+                metadata: std::collections::HashMap::new(),
+            });
+            Ok(())
+        })
+        .await
+        .map_err(update_error)?;
 
         Ok(Incident(incident))
     }
@@ -204,36 +224,25 @@ impl MutationRoot {
     ) -> Result<Incident> {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
 
-        // Get existing incident
-        let mut incident = gql_ctx
-            .processor
-            .get_incident(&incident_id)
-            .await
-            .map_err(|e| Error::new(format!("Failed to get incident: {}", e)))?;
-
-        // Update assignees
-        incident.assignees = assignees.clone();
-        incident.updated_at = chrono::Utc::now();
-
-        // Add timeline event
         let actor = gql_ctx.current_user();
-        let event = models::TimelineEvent {
-            timestamp: chrono::Utc::now(),
-            event_type: models::EventType::AssignmentChanged,
-            actor,
-            description: format!("Assigned to: {}", assignees.join(", ")),
-            metadata: std::collections::HashMap::new(),
-        };
-
-        incident.add_timeline_event(event);
+        let store = gql_ctx.processor.store().as_ref();
+        let incident = modify_incident(store, &incident_id, |incident| {
+            // Update assignees
+            incident.assignees = assignees.clone();
+            incident.updated_at = chrono::Utc::now();
 
-        // Save the updated incident
-        gql_ctx
-            .processor
-            .store()
-            .update_incident(&incident)
-            .await
-            .map_err(|e| Error::new(format!("Failed to update incident: {}", e)))?;
+            // Add timeline event
+            incident.add_timeline_event(models::TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: models::EventType::AssignmentChanged,
+                actor: actor.clone(),
+                description: format!("Assigned to: {}", assignees.join(", ")),
+                metadata: std::collections::HashMap::new(),
+            });
+            Ok(())
+        })
+        .await
+        .map_err(update_error)?;
 
         Ok(Incident(incident))
     }
@@ -248,7 +257,7 @@ impl MutationRoot {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
 
         // Get both incidents
-        let mut incident = gql_ctx
+        let incident = gql_ctx
             .processor
             .get_incident(&incident_id)
             .await
@@ -261,32 +270,33 @@ impl MutationRoot {
             .map_err(|e| Error::new(format!("Failed to get related incident: {}", e)))?;
 
         // Add to related incidents if not already present
-        if !incident.related_incidents.contains(&related_id) {
+        if incident.related_incidents.contains(&related_id) {
+            return Ok(Incident(incident));
+        }
+
+        let actor = gql_ctx.current_user();
+        let store = gql_ctx.processor.store().as_ref();
+        let incident = modify_incident(store, &incident_id, |incident| {
+            if incident.related_incidents.contains(&related_id) {
+                return Ok(());
+            }
             incident.related_incidents.push(related_id);
             incident.updated_at = chrono::Utc::now();
 
             // Add timeline event
-            let actor = gql_ctx.current_user();
-            let event = models::TimelineEvent {
+            incident.add_timeline_event(models::TimelineEvent {
                 timestamp: chrono::Utc::now(),
                 event_type: models::EventType::StateChanged,
-                actor,
+                actor: actor.clone(),
                 description: format!("Linked to incident: {}", related_incident.title),
                 metadata: std::collections::HashMap::from([
                     ("related_incident_id".to_string(), related_id.to_string()),
                 ]),
-            };
-
-            incident.add_timeline_event(event);
-
-            // Save the updated incident
-            gql_ctx
-                .processor
-                .store()
-                .update_incident(&incident)
-                .await
-                .map_err(|e| Error::new(format!("Failed to update incident: {}", e)))?;
-        }
+            });
+            Ok(())
+        })
+        .await
+        .map_err(update_error)?;
 
         Ok(Incident(incident))
     }
@@ -301,44 +311,242 @@ impl MutationRoot {
     ) -> Result<Incident> {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
 
-        // Get existing incident
-        let mut incident = gql_ctx
+        let actor = gql_ctx.current_user();
+        let store = gql_ctx.processor.store().as_ref();
+        let incident = modify_incident(store, &incident_id, |incident| {
+            let old_severity = incident.severity;
+            incident.severity = new_severity.into();
+            incident.updated_at = chrono::Utc::now();
+
+            // Add timeline event
+            incident.add_timeline_event(models::TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: models::EventType::Escalated,
+                actor: actor.clone(),
+                description: format!(
+                    "Escalated from {:?} to {:?}: {}",
+                    old_severity, new_severity, reason
+                ),
+                metadata: std::collections::HashMap::from([
+                    ("old_severity".to_string(), format!("{:?}", old_severity)),
+                    ("new_severity".to_string(), format!("{:?}", new_severity)),
+                    ("reason".to_string(), reason.clone()),
+                ]),
+            });
+            Ok(())
+        })
+        .await
+        .map_err(update_error)?;
+
+        Ok(Incident(incident))
+    }
+
+    /// Approve a step a playbook execution is waiting on
+    ///
+    /// `stepId` may be left out while only one step is waiting.
+    async fn approve_playbook_execution(
+        &self,
+        ctx: &Context<'_>,
+        execution_id: Uuid,
+        step_id: Option<String>,
+        comment: Option<String>,
+    ) -> Result<PlaybookExecution> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
             .processor
-            .get_incident(&incident_id)
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let execution = playbooks
+            .approve_execution(
+                execution_id,
+                step_id.as_deref(),
+                &gql_ctx.current_user(),
+                comment,
+            )
             .await
-            .map_err(|e| Error::new(format!("Failed to get incident: {}", e)))?;
+            .map_err(|e| Error::new(format!("Failed to approve execution: {}", e)))?;
 
-        let old_severity = incident.severity;
-        incident.severity = new_severity.into();
-        incident.updated_at = chrono::Utc::now();
+        Ok(PlaybookExecution(execution))
+    }
 
-        // Add timeline event
-        let actor = gql_ctx.current_user();
-        let event = models::TimelineEvent {
-            timestamp: chrono::Utc::now(),
-            event_type: models::EventType::Escalated,
-            actor,
-            description: format!(
-                "Escalated from {:?} to {:?}: {}",
-                old_severity, new_severity, reason
-            ),
-            metadata: std::collections::HashMap::from([
-                ("old_severity".to_string(), format!("{:?}", old_severity)),
-                ("new_severity".to_string(), format!("{:?}", new_severity)),
-                ("reason".to_string(), reason),
-            ]),
+    /// Reject a step a playbook execution is waiting on, cancelling it
+    ///
+    /// `stepId` may be left out while only one step is waiting.
+    async fn reject_playbook_execution(
+        &self,
+        ctx: &Context<'_>,
+        execution_id: Uuid,
+        step_id: Option<String>,
+        comment: Option<String>,
+    ) -> Result<PlaybookExecution> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
+            .processor
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let execution = playbooks
+            .reject_execution(
+                execution_id,
+                step_id.as_deref(),
+                &gql_ctx.current_user(),
+                comment,
+            )
+            .await
+            .map_err(|e| Error::new(format!("Failed to reject execution: {}", e)))?;
+
+        Ok(PlaybookExecution(execution))
+    }
+
+    /// Make an earlier revision of a playbook the active one
+    ///
+    /// Without `revision` the playbook goes back to the revision published
+    /// before the active one. Executions already started are not affected.
+    async fn rollback_playbook(
+        &self,
+        ctx: &Context<'_>,
+        playbook_id: Uuid,
+        revision: Option<u32>,
+    ) -> Result<Playbook> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
+            .processor
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let playbook = playbooks
+            .rollback_playbook(&playbook_id, revision)
+            .map_err(|e| Error::new(format!("Failed to roll back playbook: {}", e)))?;
+
+        Ok(Playbook(playbook))
+    }
+
+    /// Put a user on call for a period in place of a schedule's rotation
+    async fn create_schedule_override(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        input: CreateScheduleOverrideInput,
+    ) -> Result<ScheduleOverride> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let schedule_override = models::ScheduleOverride {
+            id: Uuid::new_v4(),
+            layer: input.layer,
+            user: input.user,
+            replaces: input.replaces,
+            start: input.start.into(),
+            end: input.end.into(),
+            reason: input.reason,
+            created_by: Some(gql_ctx.current_user()),
+            created_at: chrono::Utc::now(),
         };
+        let created = escalation
+            .executor()
+            .add_schedule_override(&schedule_id, schedule_override)
+            .map_err(|e| Error::new(format!("Failed to create override: {}", e)))?;
 
-        incident.add_timeline_event(event);
+        Ok(ScheduleOverride(created))
+    }
 
-        // Save the updated incident
-        gql_ctx
+    /// Remove an override from a schedule
+    async fn delete_schedule_override(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        override_id: Uuid,
+    ) -> Result<ScheduleOverride> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
             .processor
-            .store()
-            .update_incident(&incident)
-            .await
-            .map_err(|e| Error::new(format!("Failed to update incident: {}", e)))?;
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
 
-        Ok(Incident(incident))
+        let removed = escalation
+            .executor()
+            .remove_schedule_override(&schedule_id, &override_id)
+            .map_err(|e| Error::new(format!("Failed to delete override: {}", e)))?;
+
+        Ok(ScheduleOverride(removed))
+    }
+
+    /// Add overrides to a schedule from the events of an iCalendar file
+    ///
+    /// Re-importing an event replaces the override created from it before.
+    async fn import_schedule_overrides(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        #[graphql(desc = "Contents of an .ics file")] ics: String,
+    ) -> Result<Vec<ScheduleOverride>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let imported = escalation
+            .executor()
+            .import_schedule_overrides(&schedule_id, &ics, Some(gql_ctx.current_user()))
+            .map_err(|e| Error::new(format!("Failed to import overrides: {}", e)))?;
+
+        Ok(imported.into_iter().map(ScheduleOverride).collect())
+    }
+
+    /// Swap shifts between two users of a schedule
+    async fn create_shift_swap(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        input: CreateShiftSwapInput,
+    ) -> Result<ShiftSwap> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let swap = models::ShiftSwap {
+            id: Uuid::new_v4(),
+            layer: input.layer,
+            user: input.user,
+            shift: input.shift.into(),
+            with_user: input.with_user,
+            return_shift: input.return_shift.into(),
+            created_by: Some(gql_ctx.current_user()),
+            created_at: chrono::Utc::now(),
+        };
+        let created = escalation
+            .executor()
+            .add_shift_swap(&schedule_id, swap)
+            .map_err(|e| Error::new(format!("Failed to create swap: {}", e)))?;
+
+        Ok(ShiftSwap(created))
+    }
+
+    /// Remove a shift swap from a schedule
+    async fn delete_shift_swap(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        swap_id: Uuid,
+    ) -> Result<ShiftSwap> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let removed = escalation
+            .executor()
+            .remove_shift_swap(&schedule_id, &swap_id)
+            .map_err(|e| Error::new(format!("Failed to delete swap: {}", e)))?;
+
+        Ok(ShiftSwap(removed))
     }
 }
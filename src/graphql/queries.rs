@@ -5,7 +5,7 @@
 use async_graphql::*;
 use uuid::Uuid;
 
-use crate::state::IncidentFilter;
+use crate::state::{IncidentCursor, IncidentFilter};
 use super::context::GraphQLContext;
 use super::types::*;
 
@@ -44,60 +44,12 @@ impl QueryRoot {
         #[graphql(desc = "Pagination parameters")] pagination: Option<PaginationInput>,
         #[graphql(desc = "Sort parameters")] sort: Option<IncidentSortInput>,
     ) -> Result<IncidentConnection> {
-        let gql_ctx = ctx.data::<GraphQLContext>()?;
-        let pagination = pagination.unwrap_or_default();
-
-        // Build filter from input
-        let incident_filter = if let Some(filter) = filter {
-            IncidentFilter {
-                states: filter
-                    .states
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|s| s.into())
-                    .collect(),
-                severities: filter
-                    .severities
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(|s| s.into())
-                    .collect(),
-                sources: filter.sources.unwrap_or_default(),
-                active_only: filter.active_only.unwrap_or(false),
-            }
-        } else {
-            IncidentFilter {
-                states: vec![],
-                severities: vec![],
-                sources: vec![],
-                active_only: false,
-            }
-        };
-
-        // Get incidents and total count
-        let incidents = gql_ctx
-            .processor
-            .store()
-            .list_incidents(&incident_filter, pagination.page, pagination.page_size)
-            .await
-            .map_err(|e| Error::new(format!("Failed to list incidents: {}", e)))?;
-
-        let total_count = gql_ctx
-            .processor
-            .store()
-            .count_incidents(&incident_filter)
-            .await
-            .map_err(|e| Error::new(format!("Failed to count incidents: {}", e)))?;
-
-        // TODO: Implement sorting based on sort parameter
-        let _ = sort; // Suppress unused warning for now
-
-        let page_info = PageInfo::new(pagination.page, pagination.page_size, total_count);
+        let mut incident_filter: IncidentFilter = filter.map(Into::into).unwrap_or_default();
+        if let Some(sort) = sort {
+            incident_filter.sort = sort.into();
+        }
 
-        Ok(IncidentConnection {
-            incidents: incidents.into_iter().map(Incident).collect(),
-            page_info,
-        })
+        Self::list_page(ctx, &incident_filter, pagination.unwrap_or_default()).await
     }
 
     /// Get active incidents (convenience query)
@@ -113,6 +65,7 @@ impl QueryRoot {
                 severities: None,
                 sources: None,
                 active_only: Some(true),
+                ..IncidentFilterInput::default()
             }),
             pagination,
             None,
@@ -133,6 +86,7 @@ impl QueryRoot {
                 severities: Some(vec![Severity::P0, Severity::P1]),
                 sources: None,
                 active_only: Some(true),
+                ..IncidentFilterInput::default()
             }),
             pagination,
             None,
@@ -154,6 +108,164 @@ impl QueryRoot {
         Ok(Playbook(playbook))
     }
 
+    /// List playbook executions, e.g. those waiting for approval
+    async fn playbook_executions(
+        &self,
+        ctx: &Context<'_>,
+        incident_id: Option<Uuid>,
+        status: Option<ExecutionStatus>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let Some(playbooks) = gql_ctx.processor.playbook_service() else {
+            return Ok(vec![]);
+        };
+
+        let executions = match incident_id {
+            Some(id) => playbooks.list_executions_for_incident(&id),
+            None => playbooks.list_executions(),
+        };
+        Ok(executions
+            .into_iter()
+            .filter(|e| status.is_none_or(|s| ExecutionStatus::from(e.status.clone()) == s))
+            .map(PlaybookExecution)
+            .collect())
+    }
+
+    /// Trace what a playbook would do for an incident, without side effects
+    ///
+    /// Pass `incidentId` for an existing incident or `incident` for a
+    /// synthetic one, which is not saved.
+    async fn simulate_playbook(
+        &self,
+        ctx: &Context<'_>,
+        playbook_id: Uuid,
+        incident_id: Option<Uuid>,
+        incident: Option<CreateIncidentInput>,
+    ) -> Result<PlaybookSimulation> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
+            .processor
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let incident = match (incident_id, incident) {
+            (Some(id), None) => gql_ctx
+                .processor
+                .get_incident(&id)
+                .await
+                .map_err(|e| Error::new(format!("Failed to load incident: {}", e)))?,
+            (None, Some(input)) => {
+                let mut incident = crate::models::Incident::new(
+                    input.source,
+                    input.title,
+                    input.description,
+                    input.severity.into(),
+                    input.incident_type.into(),
+                );
+                incident.affected_resources = input.affected_resources;
+                incident.labels = input.labels;
+                incident
+            }
+            _ => return Err(Error::new("Pass exactly one of incidentId and incident")),
+        };
+
+        let simulation = playbooks
+            .simulate_playbook(playbook_id, &incident)
+            .await
+            .map_err(|e| Error::new(format!("Failed to simulate playbook: {}", e)))?;
+
+        Ok(PlaybookSimulation(simulation))
+    }
+
+    /// Every published revision of a playbook, oldest first
+    async fn playbook_revisions(
+        &self,
+        ctx: &Context<'_>,
+        playbook_id: Uuid,
+    ) -> Result<Vec<PlaybookRevision>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
+            .processor
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let revisions = playbooks
+            .list_revisions(&playbook_id)
+            .map_err(|e| Error::new(format!("Failed to list revisions: {}", e)))?;
+        Ok(revisions.into_iter().map(PlaybookRevision).collect())
+    }
+
+    /// Changes between two revisions of a playbook
+    ///
+    /// `toRevision` defaults to the active revision.
+    async fn playbook_diff(
+        &self,
+        ctx: &Context<'_>,
+        playbook_id: Uuid,
+        from_revision: u32,
+        to_revision: Option<u32>,
+    ) -> Result<PlaybookDiff> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let playbooks = gql_ctx
+            .processor
+            .playbook_service()
+            .ok_or_else(|| Error::new("Playbook service is not configured"))?;
+
+        let diff = playbooks
+            .diff_revisions(&playbook_id, from_revision, to_revision)
+            .map_err(|e| Error::new(format!("Failed to diff revisions: {}", e)))?;
+        Ok(PlaybookDiff(diff))
+    }
+
+    /// List on-call schedules
+    async fn schedules(&self, ctx: &Context<'_>) -> Result<Vec<OnCallSchedule>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let mut schedules = escalation.executor().list_schedules();
+        schedules.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(schedules.into_iter().map(OnCallSchedule).collect())
+    }
+
+    /// Get an on-call schedule by ID
+    async fn schedule(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<OnCallSchedule>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        Ok(escalation.executor().get_schedule(&id).map(OnCallSchedule))
+    }
+
+    /// Who will be on call for a schedule, with overrides, swaps and
+    /// holidays applied
+    ///
+    /// `from` defaults to now and `weeks` to 2.
+    async fn schedule_preview(
+        &self,
+        ctx: &Context<'_>,
+        schedule_id: Uuid,
+        from: Option<DateTimeScalar>,
+        #[graphql(default = 2)] weeks: u32,
+    ) -> Result<Vec<ScheduleShift>> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let escalation = gql_ctx
+            .processor
+            .escalation_engine()
+            .ok_or_else(|| Error::new("Escalation engine is not configured"))?;
+
+        let from = from.map(Into::into).unwrap_or_else(chrono::Utc::now);
+        let shifts = escalation
+            .executor()
+            .preview_schedule(&schedule_id, from, weeks)
+            .map_err(|e| Error::new(format!("Failed to preview schedule: {}", e)))?;
+        Ok(shifts.into_iter().map(ScheduleShift).collect())
+    }
+
     /// List all playbooks
     async fn playbooks(&self, ctx: &Context<'_>) -> Result<Vec<Playbook>> {
         let _gql_ctx = ctx.data::<GraphQLContext>()?;
@@ -172,52 +284,12 @@ impl QueryRoot {
         #[graphql(desc = "Search query")] query: String,
         #[graphql(desc = "Pagination parameters")] pagination: Option<PaginationInput>,
     ) -> Result<IncidentConnection> {
-        let gql_ctx = ctx.data::<GraphQLContext>()?;
-        let pagination = pagination.unwrap_or_default();
-
-        // Simple search: list all incidents and filter by query string
-        // In production, this should use a proper search index
         let filter = IncidentFilter {
-            states: vec![],
-            severities: vec![],
-            sources: vec![],
-            active_only: false,
+            text: Some(query),
+            ..Default::default()
         };
 
-        let all_incidents = gql_ctx
-            .processor
-            .store()
-            .list_incidents(&filter, 0, 1000) // Get more to search through
-            .await
-            .map_err(|e| Error::new(format!("Failed to list incidents: {}", e)))?;
-
-        // Filter by query (case-insensitive search in title and description)
-        let query_lower = query.to_lowercase();
-        let filtered: Vec<_> = all_incidents
-            .into_iter()
-            .filter(|i| {
-                i.title.to_lowercase().contains(&query_lower)
-                    || i.description.to_lowercase().contains(&query_lower)
-            })
-            .collect();
-
-        let total_count = filtered.len() as u64;
-
-        // Apply pagination
-        let start = (pagination.page * pagination.page_size) as usize;
-        let _end = start + pagination.page_size as usize;
-        let paginated = filtered
-            .into_iter()
-            .skip(start)
-            .take(pagination.page_size as usize)
-            .collect::<Vec<_>>();
-
-        let page_info = PageInfo::new(pagination.page, pagination.page_size, total_count);
-
-        Ok(IncidentConnection {
-            incidents: paginated.into_iter().map(Incident).collect(),
-            page_info,
-        })
+        Self::list_page(ctx, &filter, pagination.unwrap_or_default()).await
     }
 
     /// Get incident statistics
@@ -225,12 +297,7 @@ impl QueryRoot {
         let gql_ctx = ctx.data::<GraphQLContext>()?;
 
         // Get all incidents to calculate stats
-        let filter = IncidentFilter {
-            states: vec![],
-            severities: vec![],
-            sources: vec![],
-            active_only: false,
-        };
+        let filter = IncidentFilter::default();
 
         let total = gql_ctx
             .processor
@@ -302,6 +369,57 @@ impl QueryRoot {
     }
 }
 
+impl QueryRoot {
+    /// Fetch one page of incidents, by cursor if `pagination.after` is set
+    async fn list_page(
+        ctx: &Context<'_>,
+        filter: &IncidentFilter,
+        pagination: PaginationInput,
+    ) -> Result<IncidentConnection> {
+        let gql_ctx = ctx.data::<GraphQLContext>()?;
+        let store = gql_ctx.processor.store();
+
+        let total_count = store
+            .count_incidents(filter)
+            .await
+            .map_err(|e| Error::new(format!("Failed to count incidents: {}", e)))?;
+
+        if let Some(ref after) = pagination.after {
+            let page = store
+                .query_incidents(filter, Some(after.as_str()), pagination.page_size)
+                .await
+                .map_err(|e| Error::new(format!("Failed to list incidents: {}", e)))?;
+
+            let mut page_info = PageInfo::new(0, pagination.page_size, total_count);
+            page_info.has_next_page = page.next_cursor.is_some();
+            page_info.has_previous_page = true;
+
+            return Ok(IncidentConnection {
+                incidents: page.incidents.into_iter().map(Incident).collect(),
+                page_info,
+                end_cursor: page.next_cursor,
+            });
+        }
+
+        let incidents = store
+            .list_incidents(filter, pagination.page, pagination.page_size)
+            .await
+            .map_err(|e| Error::new(format!("Failed to list incidents: {}", e)))?;
+
+        let page_info = PageInfo::new(pagination.page, pagination.page_size, total_count);
+        let end_cursor = incidents
+            .last()
+            .filter(|_| page_info.has_next_page)
+            .map(|last| IncidentCursor::after(last, &filter.sort).encode());
+
+        Ok(IncidentConnection {
+            incidents: incidents.into_iter().map(Incident).collect(),
+            page_info,
+            end_cursor,
+        })
+    }
+}
+
 /// Health information
 #[derive(SimpleObject)]
 pub struct HealthInfo {
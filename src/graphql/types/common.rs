@@ -48,6 +48,10 @@ pub struct PaginationInput {
     /// Number of items per page (max 100)
     #[graphql(default = 20, validator(maximum = 100, minimum = 1))]
     pub page_size: u32,
+
+    /// Cursor from a previous `endCursor`; when set, `page` is ignored and
+    /// the page starts right after that incident
+    pub after: Option<String>,
 }
 
 impl Default for PaginationInput {
@@ -55,6 +59,7 @@ impl Default for PaginationInput {
         Self {
             page: 0,
             page_size: 20,
+            after: None,
         }
     }
 }
@@ -7,6 +7,7 @@ use uuid::Uuid;
 use crate::models;
 use super::common::{DateTimeScalar, PageInfo, SortOrder};
 use crate::graphql::context::GraphQLContext;
+use crate::state::{IncidentFilter, IncidentSort, LabelSelector, SortDirection, SortField, TimeRange};
 
 /// Incident object type
 #[derive(Clone)]
@@ -133,6 +134,16 @@ impl Incident {
         self.0.correlation_score
     }
 
+    /// Correlation group the incident belongs to
+    async fn correlation_group_id(&self) -> Option<Uuid> {
+        self.0.correlation_group_id
+    }
+
+    /// Concurrency version; pass it as `expectedVersion` when updating
+    async fn version(&self) -> u64 {
+        self.0.version
+    }
+
     /// Check if incident is active
     async fn is_active(&self) -> bool {
         self.0.is_active()
@@ -382,6 +393,7 @@ impl From<models::EventType> for EventType {
             models::EventType::Resolved => EventType::Resolved,
             models::EventType::AlertReceived => EventType::Created, // Map to closest equivalent
             models::EventType::SeverityChanged => EventType::StateChanged, // Map to closest equivalent
+            models::EventType::Acknowledged => EventType::StateChanged, // Map to closest equivalent
         }
     }
 }
@@ -394,7 +406,7 @@ pub struct Label {
 }
 
 /// Filter input for incidents
-#[derive(InputObject, Debug, Clone)]
+#[derive(InputObject, Debug, Clone, Default)]
 pub struct IncidentFilterInput {
     /// Filter by states
     pub states: Option<Vec<IncidentState>>,
@@ -407,6 +419,100 @@ pub struct IncidentFilterInput {
 
     /// Show only active incidents
     pub active_only: Option<bool>,
+
+    /// Filter by incident types
+    pub incident_types: Option<Vec<IncidentType>>,
+
+    /// Filter by assignees
+    pub assignees: Option<Vec<String>>,
+
+    /// Label selectors, all of which must match
+    pub labels: Option<Vec<LabelSelectorInput>>,
+
+    /// Filter by affected resources
+    pub affected_resources: Option<Vec<String>>,
+
+    /// Filter by correlation group
+    pub correlation_group_id: Option<Uuid>,
+
+    /// Case-insensitive text search in title and description
+    pub text: Option<String>,
+
+    /// Created at or after this time
+    pub created_after: Option<DateTimeScalar>,
+
+    /// Created before this time
+    pub created_before: Option<DateTimeScalar>,
+
+    /// Updated at or after this time
+    pub updated_after: Option<DateTimeScalar>,
+
+    /// Updated before this time
+    pub updated_before: Option<DateTimeScalar>,
+
+    /// Resolved at or after this time
+    pub resolved_after: Option<DateTimeScalar>,
+
+    /// Resolved before this time
+    pub resolved_before: Option<DateTimeScalar>,
+}
+
+impl From<IncidentFilterInput> for IncidentFilter {
+    fn from(input: IncidentFilterInput) -> Self {
+        let range = |after: Option<DateTimeScalar>, before: Option<DateTimeScalar>| {
+            TimeRange::new(after.map(Into::into), before.map(Into::into))
+        };
+
+        IncidentFilter {
+            states: input
+                .states
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            severities: input
+                .severities
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            sources: input.sources.unwrap_or_default(),
+            active_only: input.active_only.unwrap_or(false),
+            incident_types: input
+                .incident_types
+                .unwrap_or_default()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            assignees: input.assignees.unwrap_or_default(),
+            labels: input
+                .labels
+                .unwrap_or_default()
+                .into_iter()
+                .map(|label| LabelSelector {
+                    key: label.key,
+                    value: label.value,
+                })
+                .collect(),
+            affected_resources: input.affected_resources.unwrap_or_default(),
+            correlation_group_id: input.correlation_group_id,
+            text: input.text.filter(|t| !t.is_empty()),
+            created: range(input.created_after, input.created_before),
+            updated: range(input.updated_after, input.updated_before),
+            resolved: range(input.resolved_after, input.resolved_before),
+            sort: IncidentSort::default(),
+        }
+    }
+}
+
+/// Label selector input
+#[derive(InputObject, Debug, Clone)]
+pub struct LabelSelectorInput {
+    /// Label key
+    pub key: String,
+
+    /// Required value; the label only has to exist if omitted
+    pub value: Option<String>,
 }
 
 /// Sort field for incidents
@@ -414,6 +520,7 @@ pub struct IncidentFilterInput {
 pub enum IncidentSortField {
     CreatedAt,
     UpdatedAt,
+    ResolvedAt,
     Severity,
     State,
 }
@@ -429,6 +536,23 @@ pub struct IncidentSortInput {
     pub order: SortOrder,
 }
 
+impl From<IncidentSortInput> for IncidentSort {
+    fn from(input: IncidentSortInput) -> Self {
+        let field = match input.field {
+            IncidentSortField::CreatedAt => SortField::CreatedAt,
+            IncidentSortField::UpdatedAt => SortField::UpdatedAt,
+            IncidentSortField::ResolvedAt => SortField::ResolvedAt,
+            IncidentSortField::Severity => SortField::Severity,
+            IncidentSortField::State => SortField::State,
+        };
+        let direction = match input.order {
+            SortOrder::Asc => SortDirection::Asc,
+            SortOrder::Desc => SortDirection::Desc,
+        };
+        IncidentSort::new(field, direction)
+    }
+}
+
 /// Paginated incidents response
 #[derive(SimpleObject)]
 pub struct IncidentConnection {
@@ -437,6 +561,9 @@ pub struct IncidentConnection {
 
     /// Pagination information
     pub page_info: PageInfo,
+
+    /// Cursor to pass as `after` for the next page, if there is one
+    pub end_cursor: Option<String>,
 }
 
 /// Create incident input
@@ -482,6 +609,9 @@ pub struct UpdateIncidentInput {
 
     /// Remove label keys
     pub remove_labels: Option<Vec<String>>,
+
+    /// Only apply the update if the incident is still at this version
+    pub expected_version: Option<u64>,
 }
 
 /// Resolve incident input
@@ -6,10 +6,12 @@ pub mod incident;
 pub mod alert;
 pub mod playbook;
 pub mod notification;
+pub mod schedule;
 pub mod common;
 
 pub use incident::*;
 pub use alert::*;
 pub use playbook::*;
 pub use notification::*;
+pub use schedule::*;
 pub use common::*;
@@ -25,6 +25,11 @@ impl Playbook {
         &self.0.version
     }
 
+    /// Published revision, counting from 1
+    async fn revision(&self) -> u32 {
+        self.0.revision
+    }
+
     async fn description(&self) -> &str {
         &self.0.description
     }
@@ -137,6 +142,33 @@ impl PlaybookStep {
     async fn condition(&self) -> Option<&str> {
         self.0.condition.as_deref()
     }
+
+    async fn depends_on(&self) -> Option<&[String]> {
+        self.0.depends_on.as_deref()
+    }
+
+    async fn trigger_rule(&self) -> TriggerRule {
+        TriggerRule::from(self.0.trigger_rule)
+    }
+
+    async fn approvers(&self) -> Vec<String> {
+        self.0
+            .approval
+            .as_ref()
+            .map(|a| a.approvers.clone())
+            .unwrap_or_default()
+    }
+
+    async fn on_approval_timeout(&self) -> Option<ApprovalTimeoutAction> {
+        self.0
+            .approval
+            .as_ref()
+            .map(|a| ApprovalTimeoutAction::from(a.on_timeout))
+    }
+
+    async fn idempotent(&self) -> bool {
+        self.0.idempotent
+    }
 }
 
 /// Step type enum
@@ -148,6 +180,7 @@ pub enum StepType {
     Escalation,
     Resolution,
     Custom,
+    Approval,
 }
 
 impl From<models::StepType> for StepType {
@@ -159,6 +192,7 @@ impl From<models::StepType> for StepType {
             models::StepType::Escalation => StepType::Escalation,
             models::StepType::Resolution => StepType::Resolution,
             models::StepType::Custom => StepType::Custom,
+            models::StepType::Approval => StepType::Approval,
         }
     }
 }
@@ -271,6 +305,40 @@ impl From<models::BackoffStrategy> for BackoffStrategy {
     }
 }
 
+/// Step trigger rule enum
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum TriggerRule {
+    AllSuccess,
+    OneSuccess,
+    AllDone,
+}
+
+impl From<models::TriggerRule> for TriggerRule {
+    fn from(rule: models::TriggerRule) -> Self {
+        match rule {
+            models::TriggerRule::AllSuccess => TriggerRule::AllSuccess,
+            models::TriggerRule::OneSuccess => TriggerRule::OneSuccess,
+            models::TriggerRule::AllDone => TriggerRule::AllDone,
+        }
+    }
+}
+
+/// Approval timeout action enum
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ApprovalTimeoutAction {
+    Reject,
+    Approve,
+}
+
+impl From<models::ApprovalTimeoutAction> for ApprovalTimeoutAction {
+    fn from(action: models::ApprovalTimeoutAction) -> Self {
+        match action {
+            models::ApprovalTimeoutAction::Reject => ApprovalTimeoutAction::Reject,
+            models::ApprovalTimeoutAction::Approve => ApprovalTimeoutAction::Approve,
+        }
+    }
+}
+
 /// Playbook execution
 #[derive(Clone)]
 pub struct PlaybookExecution(pub models::PlaybookExecution);
@@ -285,6 +353,11 @@ impl PlaybookExecution {
         &self.0.playbook_id
     }
 
+    /// Revision of the playbook the execution runs
+    async fn playbook_revision(&self) -> u32 {
+        self.0.playbook_revision
+    }
+
     async fn incident_id(&self) -> &Uuid {
         &self.0.incident_id
     }
@@ -319,6 +392,14 @@ impl PlaybookExecution {
     async fn error(&self) -> Option<&str> {
         self.0.error.as_deref()
     }
+
+    async fn approvals(&self) -> Vec<ApprovalRequest> {
+        self.0
+            .approvals
+            .iter()
+            .map(|a| ApprovalRequest(a.clone()))
+            .collect()
+    }
 }
 
 /// Execution status enum
@@ -328,6 +409,8 @@ pub enum ExecutionStatus {
     Completed,
     Failed,
     Cancelled,
+    WaitingForApproval,
+    Abandoned,
 }
 
 impl From<models::ExecutionStatus> for ExecutionStatus {
@@ -337,10 +420,55 @@ impl From<models::ExecutionStatus> for ExecutionStatus {
             models::ExecutionStatus::Completed => ExecutionStatus::Completed,
             models::ExecutionStatus::Failed => ExecutionStatus::Failed,
             models::ExecutionStatus::Cancelled => ExecutionStatus::Cancelled,
+            models::ExecutionStatus::WaitingForApproval => ExecutionStatus::WaitingForApproval,
+            models::ExecutionStatus::Abandoned => ExecutionStatus::Abandoned,
         }
     }
 }
 
+/// Approval requested by an approval step
+#[derive(Clone)]
+pub struct ApprovalRequest(pub models::ApprovalRequest);
+
+#[Object]
+impl ApprovalRequest {
+    async fn step_id(&self) -> &str {
+        &self.0.step_id
+    }
+
+    async fn approvers(&self) -> &[String] {
+        &self.0.approvers
+    }
+
+    async fn requested_at(&self) -> DateTimeScalar {
+        self.0.requested_at.into()
+    }
+
+    async fn expires_at(&self) -> Option<DateTimeScalar> {
+        self.0.expires_at.map(|dt| dt.into())
+    }
+
+    async fn on_timeout(&self) -> ApprovalTimeoutAction {
+        ApprovalTimeoutAction::from(self.0.on_timeout)
+    }
+
+    async fn approved(&self) -> Option<bool> {
+        self.0.decision.as_ref().map(|d| d.approved)
+    }
+
+    async fn decided_by(&self) -> Option<&str> {
+        self.0.decision.as_ref().map(|d| d.decided_by.as_str())
+    }
+
+    async fn decided_at(&self) -> Option<DateTimeScalar> {
+        self.0.decision.as_ref().map(|d| d.decided_at.into())
+    }
+
+    async fn comment(&self) -> Option<&str> {
+        self.0.decision.as_ref().and_then(|d| d.comment.as_deref())
+    }
+}
+
 /// Step result entry
 #[derive(SimpleObject)]
 pub struct StepResultEntry {
@@ -378,3 +506,195 @@ impl StepResult {
         self.0.error.as_deref()
     }
 }
+
+/// Step-by-step trace of a playbook dry run
+#[derive(Clone)]
+pub struct PlaybookSimulation(pub models::PlaybookSimulation);
+
+#[Object]
+impl PlaybookSimulation {
+    async fn playbook_id(&self) -> &Uuid {
+        &self.0.playbook_id
+    }
+
+    async fn playbook_name(&self) -> &str {
+        &self.0.playbook_name
+    }
+
+    async fn incident_id(&self) -> &Uuid {
+        &self.0.incident_id
+    }
+
+    async fn status(&self) -> ExecutionStatus {
+        ExecutionStatus::from(self.0.status.clone())
+    }
+
+    async fn error(&self) -> Option<&str> {
+        self.0.error.as_deref()
+    }
+
+    async fn steps(&self) -> Vec<SimulatedStep> {
+        self.0.steps.iter().map(|s| SimulatedStep(s.clone())).collect()
+    }
+}
+
+/// What a step would do in a dry run
+#[derive(Clone)]
+pub struct SimulatedStep(pub models::SimulatedStep);
+
+#[Object]
+impl SimulatedStep {
+    async fn step_id(&self) -> &str {
+        &self.0.step_id
+    }
+
+    async fn step_type(&self) -> StepType {
+        StepType::from(self.0.step_type.clone())
+    }
+
+    async fn outcome(&self) -> SimulatedOutcome {
+        SimulatedOutcome::from(self.0.outcome)
+    }
+
+    async fn condition(&self) -> Option<&str> {
+        self.0.condition.as_deref()
+    }
+
+    async fn reason(&self) -> Option<&str> {
+        self.0.reason.as_deref()
+    }
+
+    async fn approvers(&self) -> &[String] {
+        &self.0.approvers
+    }
+
+    async fn actions(&self) -> Vec<SimulatedAction> {
+        self.0.actions.iter().map(|a| SimulatedAction(a.clone())).collect()
+    }
+}
+
+/// An action call recorded in a dry run
+#[derive(Clone)]
+pub struct SimulatedAction(pub models::SimulatedAction);
+
+#[Object]
+impl SimulatedAction {
+    async fn action_type(&self) -> ActionType {
+        ActionType::from(self.0.action_type.clone())
+    }
+
+    /// Parameters with templates resolved
+    async fn parameters(&self) -> HashMap<String, serde_json::Value> {
+        self.0.parameters.clone()
+    }
+}
+
+/// Simulated step outcome enum
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SimulatedOutcome {
+    Run,
+    ConditionFalse,
+    Skipped,
+    ApprovalAssumed,
+    NotReached,
+}
+
+impl From<models::SimulatedOutcome> for SimulatedOutcome {
+    fn from(outcome: models::SimulatedOutcome) -> Self {
+        match outcome {
+            models::SimulatedOutcome::Run => SimulatedOutcome::Run,
+            models::SimulatedOutcome::ConditionFalse => SimulatedOutcome::ConditionFalse,
+            models::SimulatedOutcome::Skipped => SimulatedOutcome::Skipped,
+            models::SimulatedOutcome::ApprovalAssumed => SimulatedOutcome::ApprovalAssumed,
+            models::SimulatedOutcome::NotReached => SimulatedOutcome::NotReached,
+        }
+    }
+}
+
+/// A published version of a playbook
+#[derive(Clone)]
+pub struct PlaybookRevision(pub models::PlaybookRevision);
+
+#[Object]
+impl PlaybookRevision {
+    async fn revision(&self) -> u32 {
+        self.0.revision
+    }
+
+    async fn digest(&self) -> &str {
+        &self.0.digest
+    }
+
+    async fn published_at(&self) -> DateTimeScalar {
+        self.0.published_at.into()
+    }
+
+    async fn playbook(&self) -> Playbook {
+        Playbook(self.0.playbook.clone())
+    }
+}
+
+/// Differences between two revisions of a playbook
+#[derive(Clone)]
+pub struct PlaybookDiff(pub models::PlaybookDiff);
+
+#[Object]
+impl PlaybookDiff {
+    async fn playbook_id(&self) -> &Uuid {
+        &self.0.playbook_id
+    }
+
+    async fn from_revision(&self) -> u32 {
+        self.0.from_revision
+    }
+
+    async fn to_revision(&self) -> u32 {
+        self.0.to_revision
+    }
+
+    async fn changes(&self) -> Vec<PlaybookChange> {
+        self.0.changes.iter().map(|c| PlaybookChange(c.clone())).collect()
+    }
+}
+
+/// A value that differs between two revisions
+#[derive(Clone)]
+pub struct PlaybookChange(pub models::PlaybookChange);
+
+#[Object]
+impl PlaybookChange {
+    /// Where the value is; steps are named by their id
+    async fn path(&self) -> &str {
+        &self.0.path
+    }
+
+    async fn kind(&self) -> ChangeKind {
+        ChangeKind::from(self.0.kind)
+    }
+
+    async fn from(&self) -> Option<serde_json::Value> {
+        self.0.from.clone()
+    }
+
+    async fn to(&self) -> Option<serde_json::Value> {
+        self.0.to.clone()
+    }
+}
+
+/// Playbook change kind enum
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl From<models::ChangeKind> for ChangeKind {
+    fn from(kind: models::ChangeKind) -> Self {
+        match kind {
+            models::ChangeKind::Added => ChangeKind::Added,
+            models::ChangeKind::Removed => ChangeKind::Removed,
+            models::ChangeKind::Modified => ChangeKind::Modified,
+        }
+    }
+}
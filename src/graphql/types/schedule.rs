@@ -0,0 +1,290 @@
+//! GraphQL types for on-call schedules
+
+use async_graphql::*;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use super::common::DateTimeScalar;
+use crate::models;
+
+/// On-call schedule object type
+#[derive(Clone)]
+pub struct OnCallSchedule(pub models::OnCallSchedule);
+
+#[Object]
+impl OnCallSchedule {
+    async fn id(&self) -> &Uuid {
+        &self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn timezone(&self) -> &str {
+        &self.0.timezone
+    }
+
+    async fn layers(&self) -> Vec<ScheduleLayer> {
+        self.0.layers.iter().cloned().map(ScheduleLayer).collect()
+    }
+
+    async fn overrides(&self) -> Vec<ScheduleOverride> {
+        self.0
+            .overrides
+            .iter()
+            .cloned()
+            .map(ScheduleOverride)
+            .collect()
+    }
+
+    async fn swaps(&self) -> Vec<ShiftSwap> {
+        self.0.swaps.iter().cloned().map(ShiftSwap).collect()
+    }
+}
+
+/// A rotation within a schedule (primary, secondary, etc.)
+#[derive(Clone)]
+pub struct ScheduleLayer(pub models::ScheduleLayer);
+
+#[Object]
+impl ScheduleLayer {
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn users(&self) -> &[String] {
+        &self.0.users
+    }
+
+    /// Rotation strategy as JSON
+    async fn rotation(&self) -> serde_json::Value {
+        serde_json::to_value(&self.0.rotation).unwrap_or_default()
+    }
+
+    /// Time restrictions as JSON
+    async fn restrictions(&self) -> Option<serde_json::Value> {
+        self.0
+            .restrictions
+            .as_ref()
+            .and_then(|r| serde_json::to_value(r).ok())
+    }
+
+    async fn holidays(&self) -> Vec<HolidayCalendar> {
+        self.0
+            .holidays
+            .iter()
+            .cloned()
+            .map(HolidayCalendar)
+            .collect()
+    }
+}
+
+/// Days on which a layer has no one on call
+#[derive(Clone)]
+pub struct HolidayCalendar(pub models::HolidayCalendar);
+
+#[Object]
+impl HolidayCalendar {
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn dates(&self) -> &[NaiveDate] {
+        &self.0.dates
+    }
+}
+
+/// A user put on call in place of the rotation
+#[derive(Clone)]
+pub struct ScheduleOverride(pub models::ScheduleOverride);
+
+#[Object]
+impl ScheduleOverride {
+    async fn id(&self) -> &Uuid {
+        &self.0.id
+    }
+
+    /// Layer the override applies to; every layer when null
+    async fn layer(&self) -> Option<&str> {
+        self.0.layer.as_deref()
+    }
+
+    async fn user(&self) -> &str {
+        &self.0.user
+    }
+
+    /// User whose shifts are covered; whoever is on call when null
+    async fn replaces(&self) -> Option<&str> {
+        self.0.replaces.as_deref()
+    }
+
+    async fn start(&self) -> DateTimeScalar {
+        self.0.start.into()
+    }
+
+    async fn end(&self) -> DateTimeScalar {
+        self.0.end.into()
+    }
+
+    async fn reason(&self) -> Option<&str> {
+        self.0.reason.as_deref()
+    }
+
+    async fn created_by(&self) -> Option<&str> {
+        self.0.created_by.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTimeScalar {
+        self.0.created_at.into()
+    }
+}
+
+/// Two users covering each other's shifts
+#[derive(Clone)]
+pub struct ShiftSwap(pub models::ShiftSwap);
+
+#[Object]
+impl ShiftSwap {
+    async fn id(&self) -> &Uuid {
+        &self.0.id
+    }
+
+    async fn layer(&self) -> Option<&str> {
+        self.0.layer.as_deref()
+    }
+
+    async fn user(&self) -> &str {
+        &self.0.user
+    }
+
+    /// Period in which `withUser` covers `user`
+    async fn shift(&self) -> ShiftWindow {
+        ShiftWindow(self.0.shift)
+    }
+
+    async fn with_user(&self) -> &str {
+        &self.0.with_user
+    }
+
+    /// Period in which `user` covers `withUser`
+    async fn return_shift(&self) -> ShiftWindow {
+        ShiftWindow(self.0.return_shift)
+    }
+
+    async fn created_by(&self) -> Option<&str> {
+        self.0.created_by.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTimeScalar {
+        self.0.created_at.into()
+    }
+}
+
+/// Period of time, end exclusive
+#[derive(Clone)]
+pub struct ShiftWindow(pub models::ShiftWindow);
+
+#[Object]
+impl ShiftWindow {
+    async fn start(&self) -> DateTimeScalar {
+        self.0.start.into()
+    }
+
+    async fn end(&self) -> DateTimeScalar {
+        self.0.end.into()
+    }
+}
+
+/// A period during which one user is on call for a layer
+#[derive(Clone)]
+pub struct ScheduleShift(pub models::ScheduleShift);
+
+#[Object]
+impl ScheduleShift {
+    async fn layer_name(&self) -> &str {
+        &self.0.layer_name
+    }
+
+    async fn user(&self) -> &str {
+        &self.0.user
+    }
+
+    async fn source(&self) -> OnCallSource {
+        OnCallSource::from(self.0.source)
+    }
+
+    async fn start(&self) -> DateTimeScalar {
+        self.0.start.into()
+    }
+
+    async fn end(&self) -> DateTimeScalar {
+        self.0.end.into()
+    }
+}
+
+/// Why a user is on call
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum OnCallSource {
+    Rotation,
+    Swap,
+    Override,
+}
+
+impl From<models::OnCallSource> for OnCallSource {
+    fn from(source: models::OnCallSource) -> Self {
+        match source {
+            models::OnCallSource::Rotation => OnCallSource::Rotation,
+            models::OnCallSource::Swap => OnCallSource::Swap,
+            models::OnCallSource::Override => OnCallSource::Override,
+        }
+    }
+}
+
+/// Input for adding an override to a schedule
+#[derive(InputObject, Debug, Clone)]
+pub struct CreateScheduleOverrideInput {
+    /// Layer to override; every layer if omitted
+    pub layer: Option<String>,
+
+    /// User who is on call during the override
+    pub user: String,
+
+    /// Only cover this user's shifts
+    pub replaces: Option<String>,
+
+    pub start: DateTimeScalar,
+    pub end: DateTimeScalar,
+    pub reason: Option<String>,
+}
+
+/// Input for a period of time
+#[derive(InputObject, Debug, Clone)]
+pub struct ShiftWindowInput {
+    pub start: DateTimeScalar,
+    pub end: DateTimeScalar,
+}
+
+impl From<ShiftWindowInput> for models::ShiftWindow {
+    fn from(input: ShiftWindowInput) -> Self {
+        models::ShiftWindow {
+            start: input.start.into(),
+            end: input.end.into(),
+        }
+    }
+}
+
+/// Input for swapping shifts between two users
+#[derive(InputObject, Debug, Clone)]
+pub struct CreateShiftSwapInput {
+    /// Layer the swap applies to; every layer if omitted
+    pub layer: Option<String>,
+
+    /// User whose `shift` is covered by `withUser`
+    pub user: String,
+    pub shift: ShiftWindowInput,
+
+    /// User whose `returnShift` is covered by `user`
+    pub with_user: String,
+    pub return_shift: ShiftWindowInput,
+}
@@ -153,6 +153,7 @@ impl From<EventType> for incidents::EventType {
             EventType::Resolved => incidents::EventType::Resolved,
             EventType::AlertReceived => incidents::EventType::Created, // Map to closest equivalent
             EventType::SeverityChanged => incidents::EventType::StateChanged, // Map to closest equivalent
+            EventType::Acknowledged => incidents::EventType::StateChanged, // Map to closest equivalent
         }
     }
 }
@@ -243,6 +244,11 @@ impl From<Incident> for incidents::Incident {
                 .collect(),
             incident_type: incidents::IncidentType::from(incident.incident_type) as i32,
             incident_state: incidents::IncidentState::from(incident.state) as i32,
+            version: incident.version,
+            correlation_group_id: incident
+                .correlation_group_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
         }
     }
 }
@@ -6,7 +6,10 @@ use crate::grpc::conversions::*;
 use crate::grpc::proto::incidents::*;
 use crate::models::{Incident, IncidentState, Severity};
 use crate::processing::IncidentProcessor;
-use crate::state::IncidentFilter;
+use crate::state::{
+    modify_incident, IncidentFilter, IncidentSort, LabelSelector, SortDirection, SortField,
+    TimeRange,
+};
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use uuid::Uuid;
@@ -29,9 +32,72 @@ impl IncidentServiceImpl {
             AppError::RateLimit => Status::resource_exhausted("Rate limit exceeded"),
             AppError::Timeout(msg) => Status::deadline_exceeded(msg),
             AppError::InvalidStateTransition(msg) => Status::failed_precondition(msg),
+            AppError::VersionConflict { .. } => Status::aborted(error.to_string()),
             _ => Status::internal(error.to_string()),
         }
     }
+
+    /// Build the store filter for a ListIncidents request
+    fn list_filter(
+        req: &ListIncidentsRequest,
+        states: Vec<IncidentState>,
+        severities: Vec<Severity>,
+    ) -> std::result::Result<IncidentFilter, Status> {
+        let labels = req
+            .labels
+            .iter()
+            .map(|label| label.parse::<LabelSelector>())
+            .collect::<crate::error::Result<Vec<_>>>()
+            .map_err(Self::app_error_to_status)?;
+
+        let correlation_group_id = req
+            .correlation_group_id
+            .as_deref()
+            .map(Uuid::parse_str)
+            .transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid correlation group ID: {}", e)))?;
+
+        let mut assignees = req.assignees.clone();
+        assignees.extend(req.assigned_to.clone());
+
+        let sort = if req.sort_by.is_empty() {
+            IncidentSort::default()
+        } else {
+            let field = req
+                .sort_by
+                .parse::<SortField>()
+                .map_err(Self::app_error_to_status)?;
+            let direction = match req.sort_order.to_lowercase().as_str() {
+                "asc" => SortDirection::Asc,
+                _ => SortDirection::Desc,
+            };
+            IncidentSort::new(field, direction)
+        };
+
+        let time = |ts: &Option<prost_types::Timestamp>| {
+            ts.clone().map(|ts| timestamp_to_datetime(Some(ts)))
+        };
+
+        Ok(IncidentFilter {
+            states,
+            severities,
+            sources: req.sources.clone(),
+            active_only: false,
+            incident_types: req
+                .incident_types()
+                .map(crate::models::IncidentType::from)
+                .collect(),
+            assignees,
+            labels,
+            affected_resources: req.affected_resources.clone(),
+            correlation_group_id,
+            text: req.text.clone().filter(|t| !t.is_empty()),
+            created: TimeRange::new(time(&req.created_after), time(&req.created_before)),
+            updated: TimeRange::new(time(&req.updated_after), time(&req.updated_before)),
+            resolved: TimeRange::new(time(&req.resolved_after), time(&req.resolved_before)),
+            sort,
+        })
+    }
 }
 
 #[tonic::async_trait]
@@ -115,8 +181,8 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
         let req = request.into_inner();
 
         // Parse status and severity from strings
-        let states = if let Some(status_str) = req.status {
-            vec![match status_str.as_str() {
+        let states = if let Some(status_str) = req.status.as_deref() {
+            vec![match status_str {
                 "Open" => IncidentState::Detected,
                 "Acknowledged" => IncidentState::Triaged,
                 "Investigating" => IncidentState::Investigating,
@@ -128,8 +194,8 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
             vec![]
         };
 
-        let severities = if let Some(severity_str) = req.severity {
-            vec![match severity_str.as_str() {
+        let severities = if let Some(severity_str) = req.severity.as_deref() {
+            vec![match severity_str {
                 "P0" => Severity::P0,
                 "P1" => Severity::P1,
                 "P2" => Severity::P2,
@@ -141,28 +207,34 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
             vec![]
         };
 
-        let filter = IncidentFilter {
-            states,
-            severities,
-            sources: vec![],
-            active_only: false,
-        };
+        let filter = Self::list_filter(&req, states, severities)?;
 
         let page = req.page;
         let page_size = req.page_size.min(100); // Max 100 per page
 
         tracing::debug!(page = page, page_size = page_size, "gRPC: Listing incidents");
 
-        let incidents = self
-            .processor
-            .store()
-            .list_incidents(&filter, page as u32, page_size as u32)
-            .await
-            .map_err(Self::app_error_to_status)?;
+        let store = self.processor.store();
 
-        let total = self
-            .processor
-            .store()
+        let (incidents, next_cursor) = match req.cursor.as_deref() {
+            Some(cursor) => {
+                let cursor = Some(cursor).filter(|c| !c.is_empty());
+                let result = store
+                    .query_incidents(&filter, cursor, page_size as u32)
+                    .await
+                    .map_err(Self::app_error_to_status)?;
+                (result.incidents, result.next_cursor.unwrap_or_default())
+            }
+            None => {
+                let incidents = store
+                    .list_incidents(&filter, page as u32, page_size as u32)
+                    .await
+                    .map_err(Self::app_error_to_status)?;
+                (incidents, String::new())
+            }
+        };
+
+        let total = store
             .count_incidents(&filter)
             .await
             .map_err(Self::app_error_to_status)?;
@@ -172,6 +244,7 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
             total_count: total as i32,
             page,
             page_size,
+            next_cursor,
         }))
     }
 
@@ -191,6 +264,16 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
             .await
             .map_err(Self::app_error_to_status)?;
 
+        if let Some(expected) = req.expected_version {
+            if expected != incident.version {
+                return Err(Self::app_error_to_status(AppError::VersionConflict {
+                    resource: format!("incident {}", id),
+                    expected,
+                    actual: incident.version,
+                }));
+            }
+        }
+
         // Update title if provided
         if let Some(title) = req.title {
             incident.title = title;
@@ -236,6 +319,7 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
             .update_incident(&incident)
             .await
             .map_err(Self::app_error_to_status)?;
+        incident.version += 1;
 
         Ok(Response::new(IncidentResponse {
             incident: Some(incident.into()),
@@ -291,20 +375,13 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
 
         tracing::info!(incident_id = %id, "gRPC: Adding note to incident");
 
-        let mut incident = self
-            .processor
-            .get_incident(&id)
-            .await
-            .map_err(Self::app_error_to_status)?;
-
-        // Add note to incident
-        incident.add_note(req.author, req.note);
-
-        self.processor
-            .store()
-            .update_incident(&incident)
-            .await
-            .map_err(Self::app_error_to_status)?;
+        // Add note to the latest copy, retrying on concurrent writes
+        let incident = modify_incident(self.processor.store().as_ref(), &id, |incident| {
+            incident.add_note(req.author.clone(), req.note.clone());
+            Ok(())
+        })
+        .await
+        .map_err(Self::app_error_to_status)?;
 
         Ok(Response::new(IncidentResponse {
             incident: Some(incident.into()),
@@ -353,8 +430,8 @@ impl incident_service_server::IncidentService for IncidentServiceImpl {
         let filter = IncidentFilter {
             states,
             severities,
-            sources: vec![],
             active_only: true,
+            ..Default::default()
         };
 
         // Create channel for streaming
@@ -8,6 +8,7 @@ pub mod correlation;
 pub mod enrichment;
 pub mod error;
 pub mod escalation;
+pub mod gitops;
 #[macro_use]
 pub mod execution;
 pub mod graphql;
@@ -18,9 +19,12 @@ pub mod ml;
 pub mod models;
 pub mod notifications;
 pub mod playbooks;
+pub mod postmortem;
 pub mod processing;
+pub mod retention;
 pub mod scheduler;
 pub mod search;
+pub mod staleness;
 pub mod state;
 pub mod messaging;
 pub mod websocket;
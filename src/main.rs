@@ -3,11 +3,13 @@ use llm_incident_manager::{
     config::Config,
     correlation::{CorrelationConfig, CorrelationEngine},
     escalation::EscalationEngine,
+    gitops::GitOpsReloader,
     grpc::start_grpc_server,
     notifications::NotificationService,
-    playbooks::PlaybookService,
+    playbooks::{PlaybookService, RemediationTargets, ScriptSandbox},
+    postmortem::{PostMortemGenerator, RuvectorClient, RuvectorConfig},
     processing::{DeduplicationEngine, IncidentProcessor},
-    state::create_store,
+    state::{create_dual_write_store, create_store, IncidentStore, StoreMigrator},
     websocket::{WebSocketConfig, WebSocketState},
 };
 use std::sync::Arc;
@@ -48,7 +50,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize storage backend
     tracing::info!("Storage backend: {:?}", config.state.backend);
-    let store = create_store(&config.state).await?;
+    let store: Arc<dyn IncidentStore> = if config.state.dual_write.is_some() {
+        let store = create_dual_write_store(&config.state).await?;
+
+        // Backfill the secondary while new writes are mirrored to it
+        let migrator = StoreMigrator::new(store.primary().clone(), store.secondary().clone());
+        tokio::spawn(async move {
+            match migrator.run().await {
+                Ok(report) => {
+                    let consistent = report
+                        .verification
+                        .as_ref()
+                        .is_some_and(|v| v.is_consistent());
+                    tracing::info!(
+                        incidents = report.incidents_written,
+                        postmortems = report.postmortems_written,
+                        playbook_executions = report.playbook_executions_written,
+                        escalation_states = report.escalation_states_written,
+                        consistent,
+                        "Dual-write backfill finished"
+                    );
+                }
+                Err(e) => tracing::error!("Dual-write backfill failed: {}", e),
+            }
+        });
+
+        store
+    } else {
+        create_store(&config.state).await?
+    };
     tracing::info!("✅ Storage backend initialized");
 
     // Initialize components
@@ -71,13 +101,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     // Initialize playbook service
-    let playbook_service = Arc::new(PlaybookService::new(
-        store.clone(),
-        notification_service.clone(),
-        true, // Enable auto-execution
-    ));
+    let remediation_targets = RemediationTargets::from_config(&config.playbooks.remediation)?;
+    let playbook_service = Arc::new(
+        PlaybookService::new(
+            store.clone(),
+            notification_service.clone(),
+            true, // Enable auto-execution
+        )
+        .with_remediation_targets(remediation_targets)
+        .with_script_sandbox(ScriptSandbox::new(config.playbooks.scripts.clone())),
+    );
     tracing::info!("✅ Playbook service initialized with auto-execution enabled");
 
+    // Spawn approval monitor
+    let approval_monitor = playbook_service.clone();
+    tokio::spawn(async move {
+        approval_monitor.run_approval_monitor().await;
+    });
+    tracing::info!("✅ Playbook approval monitor started");
+
     // Initialize escalation engine
     let escalation_engine = Arc::new(EscalationEngine::new(
         notification_service.clone(),
@@ -85,18 +127,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ).with_check_interval(30));
     tracing::info!("✅ Escalation engine initialized");
 
-    // Spawn escalation monitor
+    // Initialize routing rule evaluator
+    let routing_evaluator = Arc::new(llm_incident_manager::escalation::RoutingRuleEvaluator::new(
+        Some(playbook_service.clone()),
+    ));
+    tracing::info!("✅ Routing rule evaluator initialized");
+
+    // Load definitions kept in a directory and watch it for changes
+    if let Some(ref dir) = config.gitops.dir {
+        let reloader = Arc::new(
+            GitOpsReloader::new(
+                dir.clone(),
+                playbook_service.clone(),
+                escalation_engine.clone(),
+                routing_evaluator.clone(),
+            )
+            .with_reload_interval(config.gitops.reload_interval_secs),
+        );
+        match reloader.reload() {
+            Ok(_) => tracing::info!("✅ Definitions loaded from {}", dir.display()),
+            Err(_) => {
+                tracing::warn!("⚠️  Invalid definitions in {}", dir.display());
+                tracing::warn!("   Continuing without them until they are fixed");
+            }
+        }
+        tokio::spawn(async move {
+            reloader.run_watcher().await;
+        });
+    }
+
+    // Spawn escalation monitor once policies are registered; it first
+    // restores escalations saved before a restart and catches up on them
     let monitor_engine = escalation_engine.clone();
     tokio::spawn(async move {
         monitor_engine.run_monitor().await;
     });
     tracing::info!("✅ Escalation monitor started");
 
-    // Initialize routing rule evaluator
-    let routing_evaluator = Arc::new(llm_incident_manager::escalation::RoutingRuleEvaluator::new(
-        Some(playbook_service.clone()),
-    ));
-    tracing::info!("✅ Routing rule evaluator initialized");
+    // Reload executions so ones paused for approval can still be decided
+    // and interrupted ones are resumed or abandoned; recovery may run steps,
+    // so it happens in the background once playbooks are registered
+    let restorer = playbook_service.clone();
+    tokio::spawn(async move {
+        if let Err(e) = restorer.restore_executions().await {
+            tracing::error!("Failed to restore playbook executions: {}", e);
+        }
+    });
 
     // Initialize correlation engine
     let correlation_config = if config.processing.correlation_enabled {
@@ -143,6 +219,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::info!("✅ Correlation engine integrated with processor");
     }
 
+    // Initialize post-mortem generator
+    let mut postmortem_generator = PostMortemGenerator::new(processor.store().clone())
+        .with_playbook_service(playbook_service.clone())
+        .with_escalation_engine(escalation_engine.clone());
+    if std::env::var("RUVECTOR_SERVICE_URL").is_ok() {
+        match RuvectorClient::new(RuvectorConfig::default()) {
+            Ok(client) => {
+                postmortem_generator = postmortem_generator.with_ruvector_client(client);
+                tracing::info!("✅ Post-mortem decision events will be persisted to ruvector-service");
+            }
+            Err(e) => tracing::warn!("⚠️  Failed to initialize ruvector client: {}", e),
+        }
+    }
+    let postmortem_generator = Arc::new(postmortem_generator);
+    tracing::info!("✅ Post-mortem generator initialized");
+
     // Initialize WebSocket state
     let ws_config = WebSocketConfig::default();
     let ws_state = Arc::new(WebSocketState::new(ws_config));
@@ -162,7 +254,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let processor = Arc::new(processor);
 
     // Create application state for HTTP API with WebSocket
-    let app_state = AppState::new(processor.clone()).with_websocket(ws_state.clone());
+    let app_state = AppState::new(processor.clone())
+        .with_websocket(ws_state.clone())
+        .with_postmortem_generator(postmortem_generator);
 
     // Build HTTP router with REST API
     let app = build_router(app_state.clone());
@@ -177,6 +271,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("🚀 HTTP API server listening on http://{}", http_addr);
     tracing::info!("   Health check: http://{}/health", http_addr);
     tracing::info!("   REST API: http://{}/v1/incidents", http_addr);
+    tracing::info!("   Post-mortems: http://{}/v1/postmortems", http_addr);
     tracing::info!("   GraphQL API: http://{}/graphql", http_addr);
     tracing::info!("   GraphQL Playground: http://{}/graphql/playground", http_addr);
     tracing::info!("   GraphQL WebSocket: ws://{}/graphql/ws", http_addr);
@@ -255,6 +350,7 @@ fn default_config() -> Config {
             redis_url: None,
             redis_cluster_nodes: vec![],
             pool_size: 100,
+            dual_write: None,
         },
         messaging: None,
         integrations: IntegrationsConfig::default(),
@@ -297,6 +393,12 @@ fn default_config() -> Config {
             retry_backoff_secs: 5,
             queue_size: 10000,
             worker_threads: 4,
+            actions: Default::default(),
         },
+        retention: Default::default(),
+        staleness: Default::default(),
+        reporting: Default::default(),
+        playbooks: Default::default(),
+        gitops: Default::default(),
     }
 }
@@ -1,5 +1,7 @@
 use crate::error::{AppError, Result};
-use crate::ml::models::{ModelMetadata, ModelMetrics, ModelType, Prediction, TrainingDataset};
+use crate::ml::models::{
+    ModelMetadata, ModelMetrics, ModelTarget, ModelType, Prediction, TrainingDataset,
+};
 use crate::models::Severity;
 use ndarray::{Array1, Array2};
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,9 @@ pub trait Classifier: Send + Sync {
     /// Get model metadata
     fn metadata(&self) -> &ModelMetadata;
 
+    /// Get mutable model metadata, used to record versions and validation results
+    fn metadata_mut(&mut self) -> &mut ModelMetadata;
+
     /// Get model type
     fn model_type(&self) -> ModelType;
 
@@ -48,6 +53,10 @@ pub struct LogisticRegressionClassifier {
     /// Model bias (for serialization)
     bias: Option<Array1<f64>>,
 
+    /// What the model predicts
+    #[serde(default)]
+    target: ModelTarget,
+
     /// Number of classes
     n_classes: usize,
 
@@ -72,11 +81,18 @@ impl LogisticRegressionClassifier {
             model: None,
             weights: None,
             bias: None,
+            target: ModelTarget::Severity,
             n_classes,
             trained: false,
         }
     }
 
+    /// Train on the labels of `target` instead of severity
+    pub fn with_target(mut self, target: ModelTarget) -> Self {
+        self.target = target;
+        self
+    }
+
     fn ndarray_to_densematrix(arr: &Array2<f64>) -> DenseMatrix<f64> {
         let shape = arr.shape();
         let data: Vec<f64> = arr.iter().copied().collect();
@@ -91,16 +107,7 @@ impl LogisticRegressionClassifier {
 impl Classifier for LogisticRegressionClassifier {
     fn train(&mut self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
         // Convert labels to indices
-        let labels: Vec<usize> = if let Some(ref severity_labels) = dataset.severity_labels {
-            severity_labels
-                .iter()
-                .map(|s| Self::severity_to_index(s))
-                .collect()
-        } else {
-            return Err(AppError::Internal(
-                "No severity labels provided for training".to_string(),
-            ));
-        };
+        let labels = training_labels(dataset, self.target)?;
 
         // Convert to smartcore format
         let x = Self::ndarray_to_densematrix(&dataset.features);
@@ -162,6 +169,10 @@ impl Classifier for LogisticRegressionClassifier {
         &self.metadata
     }
 
+    fn metadata_mut(&mut self) -> &mut ModelMetadata {
+        &mut self.metadata
+    }
+
     fn model_type(&self) -> ModelType {
         ModelType::LogisticRegression
     }
@@ -171,28 +182,14 @@ impl Classifier for LogisticRegressionClassifier {
     }
 }
 
-impl LogisticRegressionClassifier {
-    fn severity_to_index(severity: &Severity) -> usize {
-        match severity {
-            Severity::P0 => 0,
-            Severity::P1 => 1,
-            Severity::P2 => 2,
-            Severity::P3 => 3,
-            Severity::P4 => 4,
-        }
-    }
-
-    fn index_to_severity(index: usize) -> Severity {
-        match index {
-            0 => Severity::P0,
-            1 => Severity::P1,
-            2 => Severity::P2,
-            3 => Severity::P3,
-            4 => Severity::P4,
-            _ => Severity::P4,
-        }
-    }
+/// Class labels of `target` to train on
+fn training_labels(dataset: &TrainingDataset, target: ModelTarget) -> Result<Vec<usize>> {
+    dataset
+        .class_labels(target)
+        .ok_or_else(|| AppError::Internal(format!("No {} labels provided for training", target)))
+}
 
+impl LogisticRegressionClassifier {
     fn calculate_metrics(
         y_true: &[usize],
         y_pred: &[usize],
@@ -293,6 +290,10 @@ pub struct DecisionTreeClassifierWrapper {
     #[serde(skip)]
     model: Option<DecisionTreeClassifier<f64, i32, DenseMatrix<f64>, Vec<i32>>>,
 
+    /// What the model predicts
+    #[serde(default)]
+    target: ModelTarget,
+
     /// Number of classes
     n_classes: usize,
 
@@ -321,25 +322,23 @@ impl DecisionTreeClassifierWrapper {
                     .collect(),
             },
             model: None,
+            target: ModelTarget::Severity,
             n_classes,
             max_depth,
             trained: false,
         }
     }
+
+    /// Train on the labels of `target` instead of severity
+    pub fn with_target(mut self, target: ModelTarget) -> Self {
+        self.target = target;
+        self
+    }
 }
 
 impl Classifier for DecisionTreeClassifierWrapper {
     fn train(&mut self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
-        let labels: Vec<usize> = if let Some(ref severity_labels) = dataset.severity_labels {
-            severity_labels
-                .iter()
-                .map(|s| LogisticRegressionClassifier::severity_to_index(s))
-                .collect()
-        } else {
-            return Err(AppError::Internal(
-                "No severity labels provided for training".to_string(),
-            ));
-        };
+        let labels = training_labels(dataset, self.target)?;
 
         let x = LogisticRegressionClassifier::ndarray_to_densematrix(&dataset.features);
         let y = LogisticRegressionClassifier::vec_to_labels(&labels);
@@ -400,6 +399,10 @@ impl Classifier for DecisionTreeClassifierWrapper {
         &self.metadata
     }
 
+    fn metadata_mut(&mut self) -> &mut ModelMetadata {
+        &mut self.metadata
+    }
+
     fn model_type(&self) -> ModelType {
         ModelType::RandomForest
     }
@@ -419,6 +422,10 @@ pub struct NaiveBayesClassifier {
     #[serde(skip)]
     model: Option<GaussianNB<f64, usize, DenseMatrix<f64>, Vec<usize>>>,
 
+    /// What the model predicts
+    #[serde(default)]
+    target: ModelTarget,
+
     /// Number of classes
     n_classes: usize,
 
@@ -441,24 +448,22 @@ impl NaiveBayesClassifier {
                 hyperparameters: HashMap::new(),
             },
             model: None,
+            target: ModelTarget::Severity,
             n_classes,
             trained: false,
         }
     }
+
+    /// Train on the labels of `target` instead of severity
+    pub fn with_target(mut self, target: ModelTarget) -> Self {
+        self.target = target;
+        self
+    }
 }
 
 impl Classifier for NaiveBayesClassifier {
     fn train(&mut self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
-        let labels: Vec<usize> = if let Some(ref severity_labels) = dataset.severity_labels {
-            severity_labels
-                .iter()
-                .map(|s| LogisticRegressionClassifier::severity_to_index(s))
-                .collect()
-        } else {
-            return Err(AppError::Internal(
-                "No severity labels provided for training".to_string(),
-            ));
-        };
+        let labels = training_labels(dataset, self.target)?;
 
         let x = LogisticRegressionClassifier::ndarray_to_densematrix(&dataset.features);
         let y = labels.clone(); // Use labels directly as usize
@@ -515,6 +520,10 @@ impl Classifier for NaiveBayesClassifier {
         &self.metadata
     }
 
+    fn metadata_mut(&mut self) -> &mut ModelMetadata {
+        &mut self.metadata
+    }
+
     fn model_type(&self) -> ModelType {
         ModelType::NaiveBayes
     }
@@ -524,39 +533,63 @@ impl Classifier for NaiveBayesClassifier {
     }
 }
 
-/// Severity classifier using ensemble of models
-pub struct SeverityClassifier {
+/// Classifier for a single prediction target
+pub struct IncidentClassifier {
+    /// What the classifier predicts
+    target: ModelTarget,
+
     /// Primary model
     primary_model: Box<dyn Classifier>,
-
-    /// Model type
-    #[allow(dead_code)]
-    model_type: ModelType,
 }
 
-impl SeverityClassifier {
-    /// Create a new severity classifier with specified model type
-    pub fn new(model_type: ModelType) -> Self {
+impl IncidentClassifier {
+    /// Create a new classifier for `target` with specified model type
+    pub fn new(target: ModelTarget, model_type: ModelType) -> Self {
+        let n_classes = target.n_classes();
         let primary_model: Box<dyn Classifier> = match model_type {
-            ModelType::LogisticRegression => Box::new(LogisticRegressionClassifier::new(5)),
-            ModelType::RandomForest => Box::new(DecisionTreeClassifierWrapper::new(5, 10)),
-            ModelType::NaiveBayes => Box::new(NaiveBayesClassifier::new(5)),
-            _ => Box::new(LogisticRegressionClassifier::new(5)), // Default
+            ModelType::RandomForest => {
+                Box::new(DecisionTreeClassifierWrapper::new(n_classes, 10).with_target(target))
+            }
+            ModelType::NaiveBayes => {
+                Box::new(NaiveBayesClassifier::new(n_classes).with_target(target))
+            }
+            // Logistic regression is also the default
+            _ => Box::new(LogisticRegressionClassifier::new(n_classes).with_target(target)),
         };
 
         Self {
+            target,
             primary_model,
-            model_type,
         }
     }
 
+    /// Target the classifier predicts
+    pub fn target(&self) -> ModelTarget {
+        self.target
+    }
+
     /// Train the classifier
     pub fn train(&mut self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
         self.primary_model.train(dataset)
     }
 
-    /// Predict severity for a single incident
-    pub fn predict_severity(&self, features: &[f64]) -> Result<Prediction<Severity>> {
+    /// Compute metrics on a dataset the classifier was not trained on
+    pub fn evaluate(&self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
+        let labels = training_labels(dataset, self.target)?;
+        if labels.is_empty() {
+            return Ok(ModelMetrics::new());
+        }
+
+        let predictions = self.primary_model.predict(&dataset.features)?;
+        Ok(LogisticRegressionClassifier::calculate_metrics(
+            &labels,
+            &predictions,
+            self.target.n_classes(),
+        ))
+    }
+
+    /// Predict the class index for a single incident
+    pub fn predict_class(&self, features: &[f64]) -> Result<Prediction<usize>> {
         let features_array = Array2::from_shape_vec((1, features.len()), features.to_vec())
             .map_err(|e| AppError::Internal(format!("Failed to create feature array: {}", e)))?;
 
@@ -564,19 +597,13 @@ impl SeverityClassifier {
         let proba = self.primary_model.predict_proba(&features_array)?;
 
         let pred_idx = predictions[0];
-        let severity = LogisticRegressionClassifier::index_to_severity(pred_idx);
-        let confidence = proba[[0, pred_idx]];
+        let confidence = proba.get([0, pred_idx]).copied().unwrap_or(0.0);
 
-        let probabilities: HashMap<String, f64> = (0..5)
-            .map(|i| {
-                (
-                    format!("{:?}", LogisticRegressionClassifier::index_to_severity(i)),
-                    proba[[0, i]],
-                )
-            })
+        let probabilities: HashMap<String, f64> = (0..self.target.n_classes())
+            .map(|i| (self.target.class_name(i), proba[[0, i]]))
             .collect();
 
-        Ok(Prediction::new(severity, confidence).with_probabilities(probabilities))
+        Ok(Prediction::new(pred_idx, confidence).with_probabilities(probabilities))
     }
 
     /// Check if model is trained
@@ -588,12 +615,58 @@ impl SeverityClassifier {
     pub fn metadata(&self) -> &ModelMetadata {
         self.primary_model.metadata()
     }
+
+    /// Get mutable model metadata
+    pub fn metadata_mut(&mut self) -> &mut ModelMetadata {
+        self.primary_model.metadata_mut()
+    }
+}
+
+/// Severity classifier using ensemble of models
+pub struct SeverityClassifier {
+    /// Underlying classifier
+    inner: IncidentClassifier,
+}
+
+impl SeverityClassifier {
+    /// Create a new severity classifier with specified model type
+    pub fn new(model_type: ModelType) -> Self {
+        Self {
+            inner: IncidentClassifier::new(ModelTarget::Severity, model_type),
+        }
+    }
+
+    /// Train the classifier
+    pub fn train(&mut self, dataset: &TrainingDataset) -> Result<ModelMetrics> {
+        self.inner.train(dataset)
+    }
+
+    /// Predict severity for a single incident
+    pub fn predict_severity(&self, features: &[f64]) -> Result<Prediction<Severity>> {
+        let prediction = self.inner.predict_class(features)?;
+
+        let severity = ModelTarget::index_to_severity(prediction.value);
+
+        Ok(Prediction::new(severity, prediction.confidence)
+            .with_probabilities(prediction.probabilities))
+    }
+
+    /// Check if model is trained
+    pub fn is_trained(&self) -> bool {
+        self.inner.is_trained()
+    }
+
+    /// Get model metadata
+    pub fn metadata(&self) -> &ModelMetadata {
+        self.inner.metadata()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ml::models::TrainingSample;
+    use crate::models::IncidentType;
 
     fn create_test_dataset(n_samples: usize) -> TrainingDataset {
         let samples: Vec<TrainingSample> = (0..n_samples)
@@ -661,4 +734,43 @@ mod tests {
         assert!(prediction.confidence >= 0.0 && prediction.confidence <= 1.0);
         assert!(prediction.probabilities.len() > 0);
     }
+
+    #[test]
+    fn test_incident_classifier_type_target() {
+        let samples: Vec<TrainingSample> = (0..60)
+            .map(|i| {
+                let incident_type = if i % 2 == 0 {
+                    IncidentType::Security
+                } else {
+                    IncidentType::Performance
+                };
+                let features = vec![(i % 2) as f64 * 10.0 + (i % 5) as f64 * 0.1, (i % 7) as f64];
+                TrainingSample::new(features, "test".to_string()).with_type(incident_type)
+            })
+            .collect();
+        let dataset = TrainingDataset::from_samples(&samples);
+        let (train, holdout) = dataset.train_test_split(0.25);
+
+        let mut classifier = IncidentClassifier::new(ModelTarget::Type, ModelType::NaiveBayes);
+        classifier.train(&train).unwrap();
+
+        let metrics = classifier.evaluate(&holdout).unwrap();
+        assert!(metrics.accuracy > 0.9);
+
+        let prediction = classifier.predict_class(&[0.2, 3.0]).unwrap();
+        assert_eq!(
+            ModelTarget::index_to_type(prediction.value),
+            IncidentType::Security
+        );
+        assert_eq!(prediction.probabilities.len(), 8);
+    }
+
+    #[test]
+    fn test_training_without_target_labels_fails() {
+        let dataset = create_test_dataset(20);
+        let mut classifier =
+            IncidentClassifier::new(ModelTarget::Priority, ModelType::LogisticRegression);
+
+        assert!(classifier.train(&dataset).is_err());
+    }
 }
@@ -6,17 +6,22 @@
 /// - Priority scoring
 /// - Feature extraction from incident text
 /// - Model training and retraining
+/// - Versioned model storage with promotion and rollback
 /// - Multiple ML algorithms (Logistic Regression, Decision Trees, Naive Bayes)
 
 pub mod classifier;
 pub mod features;
 pub mod models;
+pub mod registry;
 pub mod service;
 
-pub use classifier::{Classifier, LogisticRegressionClassifier, SeverityClassifier};
+pub use classifier::{
+    Classifier, IncidentClassifier, LogisticRegressionClassifier, SeverityClassifier,
+};
 pub use features::{FeatureExtractor, TextPreprocessor};
 pub use models::{
-    FeatureConfig, MLConfig, ModelMetadata, ModelMetrics, ModelType, Prediction, TrainingDataset,
-    TrainingSample,
+    FeatureConfig, MLConfig, ModelMetadata, ModelMetrics, ModelTarget, ModelType, Prediction,
+    TrainingDataset, TrainingSample,
 };
-pub use service::{IncidentPredictions, MLService, MLServiceStats};
+pub use registry::{ModelRegistry, ModelVersion};
+pub use service::{IncidentPredictions, MLService, MLServiceStats, RetrainOutcome, RetrainReport};
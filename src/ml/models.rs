@@ -331,6 +331,139 @@ impl TrainingDataset {
 
         (train_dataset, test_dataset)
     }
+
+    /// Class indices of `target` for every sample, if the dataset carries
+    /// that label for all of them
+    pub fn class_labels(&self, target: ModelTarget) -> Option<Vec<usize>> {
+        let labels: Vec<usize> = match target {
+            ModelTarget::Severity => self
+                .severity_labels
+                .as_ref()?
+                .iter()
+                .map(|s| s.priority() as usize)
+                .collect(),
+            ModelTarget::Type => self
+                .type_labels
+                .as_ref()?
+                .iter()
+                .map(ModelTarget::type_to_index)
+                .collect(),
+            ModelTarget::Priority => self
+                .priority_scores
+                .as_ref()?
+                .iter()
+                .map(|&p| p.round().clamp(0.0, 10.0) as usize)
+                .collect(),
+        };
+
+        (labels.len() == self.n_samples).then_some(labels)
+    }
+}
+
+/// What a model predicts
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTarget {
+    /// Incident severity (P0-P4)
+    #[default]
+    Severity,
+
+    /// Incident type
+    Type,
+
+    /// Priority score, predicted as one of the whole numbers 0-10
+    Priority,
+}
+
+impl ModelTarget {
+    /// All targets, in training order
+    pub const ALL: [ModelTarget; 3] = [
+        ModelTarget::Severity,
+        ModelTarget::Type,
+        ModelTarget::Priority,
+    ];
+
+    /// Stable name, used for the on-disk layout and in job config
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelTarget::Severity => "severity",
+            ModelTarget::Type => "type",
+            ModelTarget::Priority => "priority",
+        }
+    }
+
+    /// Number of classes a classifier for this target distinguishes
+    pub fn n_classes(&self) -> usize {
+        match self {
+            ModelTarget::Severity => 5,
+            ModelTarget::Type => 8,
+            ModelTarget::Priority => 11,
+        }
+    }
+
+    /// Human-readable name of a class index
+    pub fn class_name(&self, index: usize) -> String {
+        match self {
+            ModelTarget::Severity => Self::index_to_severity(index).to_string(),
+            ModelTarget::Type => Self::index_to_type(index).to_string(),
+            ModelTarget::Priority => index.to_string(),
+        }
+    }
+
+    pub fn index_to_severity(index: usize) -> Severity {
+        match index {
+            0 => Severity::P0,
+            1 => Severity::P1,
+            2 => Severity::P2,
+            3 => Severity::P3,
+            _ => Severity::P4,
+        }
+    }
+
+    pub fn type_to_index(incident_type: &IncidentType) -> usize {
+        match incident_type {
+            IncidentType::Infrastructure => 0,
+            IncidentType::Application => 1,
+            IncidentType::Security => 2,
+            IncidentType::Data => 3,
+            IncidentType::Performance => 4,
+            IncidentType::Availability => 5,
+            IncidentType::Compliance => 6,
+            IncidentType::Unknown => 7,
+        }
+    }
+
+    pub fn index_to_type(index: usize) -> IncidentType {
+        match index {
+            0 => IncidentType::Infrastructure,
+            1 => IncidentType::Application,
+            2 => IncidentType::Security,
+            3 => IncidentType::Data,
+            4 => IncidentType::Performance,
+            5 => IncidentType::Availability,
+            6 => IncidentType::Compliance,
+            _ => IncidentType::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for ModelTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ModelTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "severity" | "severity_classifier" => Ok(ModelTarget::Severity),
+            "type" | "type_classifier" => Ok(ModelTarget::Type),
+            "priority" | "priority_classifier" => Ok(ModelTarget::Priority),
+            other => Err(format!("Unknown model: {}", other)),
+        }
+    }
 }
 
 /// Model evaluation metrics
@@ -375,6 +508,17 @@ impl ModelMetrics {
             per_class_metrics: HashMap::new(),
         }
     }
+
+    /// Whether these metrics are strictly better than `other`
+    ///
+    /// Compares macro F1 first and falls back to accuracy on a tie.
+    pub fn beats(&self, other: &ModelMetrics) -> bool {
+        const EPSILON: f64 = 1e-9;
+        if (self.f1_score - other.f1_score).abs() > EPSILON {
+            return self.f1_score > other.f1_score;
+        }
+        self.accuracy > other.accuracy + EPSILON
+    }
 }
 
 impl Default for ModelMetrics {
@@ -512,6 +656,62 @@ mod tests {
         assert_eq!(test.n_features, 2);
     }
 
+    #[test]
+    fn test_class_labels_per_target() {
+        let samples = vec![
+            TrainingSample::new(vec![1.0], "test".to_string())
+                .with_severity(Severity::P2)
+                .with_type(IncidentType::Security)
+                .with_priority(7.6),
+            TrainingSample::new(vec![2.0], "test".to_string())
+                .with_severity(Severity::P0)
+                .with_type(IncidentType::Unknown)
+                .with_priority(12.0),
+        ];
+        let dataset = TrainingDataset::from_samples(&samples);
+
+        assert_eq!(dataset.class_labels(ModelTarget::Severity), Some(vec![2, 0]));
+        assert_eq!(dataset.class_labels(ModelTarget::Type), Some(vec![2, 7]));
+        assert_eq!(dataset.class_labels(ModelTarget::Priority), Some(vec![8, 10]));
+
+        let unlabeled = TrainingDataset::from_samples(&[TrainingSample::new(
+            vec![1.0],
+            "test".to_string(),
+        )]);
+        assert_eq!(unlabeled.class_labels(ModelTarget::Type), None);
+    }
+
+    #[test]
+    fn test_model_metrics_beats() {
+        let incumbent = ModelMetrics {
+            accuracy: 0.8,
+            f1_score: 0.6,
+            ..ModelMetrics::new()
+        };
+        let better_f1 = ModelMetrics {
+            accuracy: 0.7,
+            f1_score: 0.65,
+            ..ModelMetrics::new()
+        };
+        let tied_f1 = ModelMetrics {
+            accuracy: 0.85,
+            f1_score: 0.6,
+            ..ModelMetrics::new()
+        };
+
+        assert!(better_f1.beats(&incumbent));
+        assert!(tied_f1.beats(&incumbent));
+        assert!(!incumbent.beats(&incumbent));
+        assert!(!incumbent.beats(&better_f1));
+    }
+
+    #[test]
+    fn test_model_target_parse() {
+        assert_eq!("severity_classifier".parse::<ModelTarget>(), Ok(ModelTarget::Severity));
+        assert_eq!("Type".parse::<ModelTarget>(), Ok(ModelTarget::Type));
+        assert!("correlation_detector".parse::<ModelTarget>().is_err());
+    }
+
     #[test]
     fn test_prediction_creation() {
         let prediction = Prediction::new(Severity::P1, 0.85)
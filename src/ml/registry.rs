@@ -0,0 +1,310 @@
+//! Versioned on-disk storage for trained models
+//!
+//! Each target has its own directory under the registry root:
+//!
+//! ```text
+//! <root>/<target>/v1/metadata.json
+//! <root>/<target>/v1/model.json
+//! <root>/<target>/history.json
+//! ```
+//!
+//! `history.json` lists the versions that have been activated, most recent
+//! last. The last entry is the active version and rolling back drops it.
+
+use crate::error::{AppError, Result};
+use crate::ml::features::FeatureExtractor;
+use crate::ml::models::{ModelMetadata, ModelTarget, TrainingSample};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+const METADATA_FILE: &str = "metadata.json";
+const MODEL_FILE: &str = "model.json";
+const HISTORY_FILE: &str = "history.json";
+
+/// A persisted model version
+///
+/// The smartcore estimators are not serializable, so a version keeps the
+/// fitted feature extractor and the training samples it was built from and is
+/// refit on load. Fitting is deterministic, so the reloaded model makes the
+/// same predictions as the one that was evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelVersion {
+    /// What the model predicts
+    pub target: ModelTarget,
+
+    /// Metadata, including the holdout metrics it was promoted on
+    pub metadata: ModelMetadata,
+
+    /// Feature extractor the samples were produced with
+    pub extractor: FeatureExtractor,
+
+    /// Training samples
+    pub samples: Vec<TrainingSample>,
+}
+
+/// Directory of versioned models
+#[derive(Debug, Clone)]
+pub struct ModelRegistry {
+    root: PathBuf,
+}
+
+impl ModelRegistry {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Registry root directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Store a new version and make it the active one
+    ///
+    /// The version name is assigned here and written to
+    /// `model.metadata.version`.
+    pub fn publish(&self, model: &mut ModelVersion) -> Result<String> {
+        let version = format!("v{}", self.latest_number(model.target)? + 1);
+        model.metadata.version = version.clone();
+
+        let dir = self.version_dir(model.target, &version);
+        fs::create_dir_all(&dir)?;
+        write_json(&dir.join(MODEL_FILE), &*model)?;
+        // Written last: a version without metadata is ignored when listing
+        write_json(&dir.join(METADATA_FILE), &model.metadata)?;
+
+        let mut history = self.history(model.target)?;
+        history.push(version.clone());
+        write_json(&self.history_path(model.target), &history)?;
+
+        Ok(version)
+    }
+
+    /// Metadata of every stored version of `target`, oldest first
+    pub fn versions(&self, target: ModelTarget) -> Result<Vec<ModelMetadata>> {
+        let mut versions = Vec::new();
+        for (_, dir) in self.version_dirs(target)? {
+            let path = dir.join(METADATA_FILE);
+            if path.exists() {
+                versions.push(read_json(&path)?);
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Name of the active version of `target`
+    pub fn active_version(&self, target: ModelTarget) -> Result<Option<String>> {
+        Ok(self.history(target)?.pop())
+    }
+
+    /// Load a stored version
+    pub fn load(&self, target: ModelTarget, version: &str) -> Result<ModelVersion> {
+        let path = self.version_dir(target, version).join(MODEL_FILE);
+        if !path.exists() {
+            return Err(AppError::NotFound(format!(
+                "{} model version {} not found",
+                target, version
+            )));
+        }
+        read_json(&path)
+    }
+
+    /// Load the active version of `target`, if there is one
+    pub fn load_active(&self, target: ModelTarget) -> Result<Option<ModelVersion>> {
+        match self.active_version(target)? {
+            Some(version) => self.load(target, &version).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Make a stored version the active one
+    pub fn activate(&self, target: ModelTarget, version: &str) -> Result<()> {
+        if !self.version_dir(target, version).join(MODEL_FILE).exists() {
+            return Err(AppError::NotFound(format!(
+                "{} model version {} not found",
+                target, version
+            )));
+        }
+
+        let mut history = self.history(target)?;
+        if history.last().map(String::as_str) != Some(version) {
+            history.push(version.to_string());
+            write_json(&self.history_path(target), &history)?;
+        }
+        Ok(())
+    }
+
+    /// Reactivate the version that was active before the current one
+    ///
+    /// Returns the version that is active afterwards.
+    pub fn rollback(&self, target: ModelTarget) -> Result<String> {
+        let mut history = self.history(target)?;
+        if history.len() < 2 {
+            return Err(AppError::Validation(format!(
+                "No previous {} model version to roll back to",
+                target
+            )));
+        }
+
+        history.pop();
+        write_json(&self.history_path(target), &history)?;
+        Ok(history.last().cloned().unwrap_or_default())
+    }
+
+    fn target_dir(&self, target: ModelTarget) -> PathBuf {
+        self.root.join(target.as_str())
+    }
+
+    fn version_dir(&self, target: ModelTarget, version: &str) -> PathBuf {
+        self.target_dir(target).join(version)
+    }
+
+    fn history_path(&self, target: ModelTarget) -> PathBuf {
+        self.target_dir(target).join(HISTORY_FILE)
+    }
+
+    fn history(&self, target: ModelTarget) -> Result<Vec<String>> {
+        let path = self.history_path(target);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        read_json(&path)
+    }
+
+    /// Version directories of `target` with their number, in version order
+    fn version_dirs(&self, target: ModelTarget) -> Result<Vec<(u64, PathBuf)>> {
+        let dir = self.target_dir(target);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut dirs = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            let number = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix('v'))
+                .and_then(|n| n.parse::<u64>().ok());
+            if let (Some(number), true) = (number, entry.file_type()?.is_dir()) {
+                dirs.push((number, entry.path()));
+            }
+        }
+        dirs.sort_by_key(|(number, _)| *number);
+        Ok(dirs)
+    }
+
+    fn latest_number(&self, target: ModelTarget) -> Result<u64> {
+        Ok(self
+            .version_dirs(target)?
+            .last()
+            .map(|(number, _)| *number)
+            .unwrap_or(0))
+    }
+}
+
+/// Write JSON through a temporary file so readers never see a partial file
+fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    serde_json::to_writer(&mut writer, value)?;
+    writer.flush()?;
+    writer
+        .into_inner()
+        .map_err(|e| AppError::Io(e.into_error()))?
+        .sync_all()?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ml::models::{FeatureConfig, ModelMetrics, ModelType};
+    use std::collections::HashMap;
+
+    fn model_version(target: ModelTarget) -> ModelVersion {
+        ModelVersion {
+            target,
+            metadata: ModelMetadata {
+                name: "Logistic Regression".to_string(),
+                version: String::new(),
+                model_type: ModelType::LogisticRegression,
+                trained_at: chrono::Utc::now(),
+                n_training_samples: 1,
+                n_features: 1,
+                training_metrics: ModelMetrics::new(),
+                validation_metrics: None,
+                hyperparameters: HashMap::new(),
+            },
+            extractor: FeatureExtractor::new(FeatureConfig::default()),
+            samples: vec![TrainingSample::new(vec![1.0], "test".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_publish_assigns_increasing_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ModelRegistry::new(dir.path());
+
+        let mut first = model_version(ModelTarget::Severity);
+        let mut second = model_version(ModelTarget::Severity);
+        assert_eq!(registry.publish(&mut first).unwrap(), "v1");
+        assert_eq!(registry.publish(&mut second).unwrap(), "v2");
+        assert_eq!(second.metadata.version, "v2");
+
+        let versions = registry.versions(ModelTarget::Severity).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, "v1");
+
+        assert_eq!(
+            registry.active_version(ModelTarget::Severity).unwrap(),
+            Some("v2".to_string())
+        );
+        let loaded = registry
+            .load_active(ModelTarget::Severity)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.metadata.version, "v2");
+        assert_eq!(loaded.samples.len(), 1);
+
+        // Targets are versioned independently
+        assert!(registry.versions(ModelTarget::Type).unwrap().is_empty());
+        assert!(registry.load_active(ModelTarget::Type).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rollback_and_activate() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = ModelRegistry::new(dir.path());
+
+        for _ in 0..3 {
+            registry
+                .publish(&mut model_version(ModelTarget::Priority))
+                .unwrap();
+        }
+
+        assert_eq!(registry.rollback(ModelTarget::Priority).unwrap(), "v2");
+        assert_eq!(registry.rollback(ModelTarget::Priority).unwrap(), "v1");
+        assert!(registry.rollback(ModelTarget::Priority).is_err());
+
+        registry.activate(ModelTarget::Priority, "v3").unwrap();
+        assert_eq!(
+            registry.active_version(ModelTarget::Priority).unwrap(),
+            Some("v3".to_string())
+        );
+        assert!(registry.activate(ModelTarget::Priority, "v9").is_err());
+
+        // Publishing after a rollback never reuses a version name
+        let version = registry
+            .publish(&mut model_version(ModelTarget::Priority))
+            .unwrap();
+        assert_eq!(version, "v4");
+    }
+}
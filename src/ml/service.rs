@@ -1,17 +1,86 @@
 use crate::error::{AppError, Result};
-use crate::ml::classifier::SeverityClassifier;
+use crate::ml::classifier::IncidentClassifier;
 use crate::ml::features::FeatureExtractor;
 use crate::ml::models::{
-    MLConfig, ModelMetadata, ModelType, Prediction, TrainingDataset,
+    MLConfig, ModelMetadata, ModelMetrics, ModelTarget, ModelType, Prediction, TrainingDataset,
     TrainingSample,
 };
-use crate::models::{Incident, IncidentType, Severity};
-use crate::state::IncidentStore;
+use crate::ml::registry::{ModelRegistry, ModelVersion};
+use crate::models::{Incident, IncidentState, IncidentType, Severity};
+use crate::state::{IncidentFilter, IncidentSort, IncidentStore, SortDirection, SortField};
 use dashmap::DashMap;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+/// Fewest incidents any model is trained on
+const MIN_TRAINING_INCIDENTS: usize = 10;
+
+/// Share of the resolved incidents, most recently resolved, held out to
+/// compare a retrained model with the one it would replace
+const HOLDOUT_FRACTION: f64 = 0.2;
+
+/// Page size used when reading resolved incidents from the store
+const TRAINING_PAGE_SIZE: u32 = 500;
+
+/// Incident label whose numeric value (0-10) is used as the priority to
+/// train on instead of the one derived from severity
+pub const PRIORITY_LABEL: &str = "priority";
+
+/// A classifier together with the feature extractor it was trained against
+struct TrainedModel {
+    extractor: FeatureExtractor,
+    classifier: IncidentClassifier,
+}
+
+impl TrainedModel {
+    /// Train a classifier for `target` on samples produced by `extractor`
+    fn train(
+        target: ModelTarget,
+        extractor: &FeatureExtractor,
+        samples: &[TrainingSample],
+    ) -> Result<(Self, ModelMetrics)> {
+        let dataset = TrainingDataset::from_samples(samples);
+        let mut classifier = IncidentClassifier::new(target, ModelType::LogisticRegression);
+        let metrics = classifier.train(&dataset)?;
+
+        Ok((
+            Self {
+                extractor: extractor.clone(),
+                classifier,
+            },
+            metrics,
+        ))
+    }
+
+    /// Refit a stored version
+    fn from_version(version: &ModelVersion) -> Result<Self> {
+        let dataset = TrainingDataset::from_samples(&version.samples);
+        let mut classifier =
+            IncidentClassifier::new(version.target, version.metadata.model_type.clone());
+        classifier.train(&dataset)?;
+        *classifier.metadata_mut() = version.metadata.clone();
+
+        Ok(Self {
+            extractor: version.extractor.clone(),
+            classifier,
+        })
+    }
+
+    fn predict(&self, incident: &Incident) -> Result<Prediction<usize>> {
+        let features = self.extractor.transform(incident)?;
+        self.classifier.predict_class(&features)
+    }
+
+    /// Metrics on incidents the model was not trained on
+    fn evaluate(&self, incidents: &[Incident]) -> Result<ModelMetrics> {
+        let samples = training_samples(&self.extractor, incidents)?;
+        self.classifier
+            .evaluate(&TrainingDataset::from_samples(&samples))
+    }
+}
+
 /// ML prediction service
 pub struct MLService {
     /// Configuration
@@ -20,8 +89,11 @@ pub struct MLService {
     /// Feature extractor
     feature_extractor: Arc<RwLock<FeatureExtractor>>,
 
-    /// Severity classifier
-    severity_classifier: Arc<RwLock<Option<SeverityClassifier>>>,
+    /// Trained models by target
+    models: Arc<RwLock<HashMap<ModelTarget, TrainedModel>>>,
+
+    /// Versioned model storage under the configured model path
+    registry: Option<ModelRegistry>,
 
     /// Training samples cache
     training_samples: Arc<DashMap<uuid::Uuid, TrainingSample>>,
@@ -38,13 +110,17 @@ pub struct MLService {
 
 impl MLService {
     /// Create a new ML service
+    ///
+    /// Trained models are versioned under `config.model_path` when it is set.
     pub fn new(config: MLConfig, incident_store: Arc<dyn IncidentStore>) -> Self {
         let feature_extractor = FeatureExtractor::new(config.feature_config.clone());
+        let registry = config.model_path.as_ref().map(ModelRegistry::new);
 
         Self {
             config: Arc::new(RwLock::new(config)),
             feature_extractor: Arc::new(RwLock::new(feature_extractor)),
-            severity_classifier: Arc::new(RwLock::new(None)),
+            models: Arc::new(RwLock::new(HashMap::new())),
+            registry,
             training_samples: Arc::new(DashMap::new()),
             incident_store,
             samples_since_training: Arc::new(RwLock::new(0)),
@@ -87,13 +163,18 @@ impl MLService {
 
     /// Initialize models (load or train)
     async fn initialize_models(&self) -> Result<()> {
-        let config = self.config.read().await;
-
-        if !config.enabled {
+        if !self.config.read().await.enabled {
             info!("ML service is disabled in configuration");
             return Ok(());
         }
 
+        // Serve the active versions if models have been trained before
+        let loaded = self.load_models().await?;
+        if loaded > 0 {
+            info!("Loaded {} ML models from the model registry", loaded);
+            return Ok(());
+        }
+
         // Try to load historical incidents for training
         let filter = crate::state::IncidentFilter::default();
         let incidents = self.incident_store.list_incidents(&filter, 0, 1000).await?;
@@ -103,7 +184,10 @@ impl MLService {
             return Ok(());
         }
 
-        info!("Found {} historical incidents for training", incidents.len());
+        info!(
+            "Found {} historical incidents for training",
+            incidents.len()
+        );
 
         // Train initial models
         self.train_on_incidents(&incidents).await?;
@@ -111,7 +195,46 @@ impl MLService {
         Ok(())
     }
 
+    /// Load the active version of every enabled model from the registry
+    ///
+    /// Returns the number of models loaded.
+    pub async fn load_models(&self) -> Result<usize> {
+        let Some(registry) = &self.registry else {
+            return Ok(0);
+        };
+        let config = self.config.read().await.clone();
+
+        let mut loaded = 0;
+        for target in ModelTarget::ALL {
+            if !target_enabled(&config, target) {
+                continue;
+            }
+            let Some(version) = registry.load_active(target)? else {
+                continue;
+            };
+
+            let model = TrainedModel::from_version(&version)?;
+            info!(
+                model = %target,
+                version = %version.metadata.version,
+                "Loaded ML model"
+            );
+
+            if loaded == 0 {
+                *self.feature_extractor.write().await = model.extractor.clone();
+            }
+            self.models.write().await.insert(target, model);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
     /// Train models on a set of incidents
+    ///
+    /// The trained models replace the current ones without evaluation and
+    /// are not versioned; scheduled retraining goes through
+    /// [`retrain_from_store`](Self::retrain_from_store) instead.
     pub async fn train_on_incidents(&self, incidents: &[Incident]) -> Result<()> {
         let config = self.config.read().await;
 
@@ -119,8 +242,11 @@ impl MLService {
             return Ok(());
         }
 
-        if incidents.len() < 10 {
-            warn!("Too few incidents ({}) for effective training", incidents.len());
+        if incidents.len() < MIN_TRAINING_INCIDENTS {
+            warn!(
+                "Too few incidents ({}) for effective training",
+                incidents.len()
+            );
             return Ok(());
         }
 
@@ -133,42 +259,32 @@ impl MLService {
 
         // Extract features for all incidents
         let extractor = self.feature_extractor.read().await;
-        let samples: Result<Vec<TrainingSample>> = incidents
-            .iter()
-            .map(|inc| {
-                let features = extractor.transform(inc)?;
-                let sample = TrainingSample::new(features, inc.source.clone())
-                    .with_severity(inc.severity.clone())
-                    .with_type(inc.incident_type.clone());
-                Ok(sample)
-            })
-            .collect();
-
-        let samples = samples?;
-        drop(extractor);
-
-        // Create training dataset
-        let dataset = TrainingDataset::from_samples(&samples);
+        let samples = training_samples(&extractor, incidents)?;
 
-        // Train severity classifier
-        if config.enable_severity_prediction {
-            info!("Training severity classifier");
-            let mut classifier =
-                SeverityClassifier::new(ModelType::LogisticRegression);
+        // Train a classifier per enabled target
+        let mut models = self.models.write().await;
+        for target in ModelTarget::ALL {
+            if !target_enabled(&config, target) {
+                continue;
+            }
 
-            match classifier.train(&dataset) {
-                Ok(metrics) => {
+            info!("Training {} classifier", target);
+            match TrainedModel::train(target, &extractor, &samples) {
+                Ok((model, metrics)) => {
                     info!(
-                        "Severity classifier trained successfully - Accuracy: {:.2}%",
+                        "{} classifier trained successfully - Accuracy: {:.2}%",
+                        target,
                         metrics.accuracy * 100.0
                     );
-                    *self.severity_classifier.write().await = Some(classifier);
+                    models.insert(target, model);
                 }
                 Err(e) => {
-                    error!("Failed to train severity classifier: {}", e);
+                    error!("Failed to train {} classifier: {}", target, e);
                 }
             }
         }
+        drop(models);
+        drop(extractor);
 
         // Reset counter
         *self.samples_since_training.write().await = 0;
@@ -178,6 +294,230 @@ impl MLService {
         Ok(())
     }
 
+    /// Retrain models on resolved incidents, promoting those that improve
+    ///
+    /// The most recently resolved fifth of the incidents is held out. Each
+    /// candidate is trained on the rest and replaces the current model only
+    /// when its metrics on the holdout beat the current model's (see
+    /// [`ModelMetrics::beats`]); a target without a model always takes the
+    /// candidate. Promoted models are published to the model registry.
+    pub async fn retrain_from_store(
+        &self,
+        targets: &[ModelTarget],
+        min_samples: usize,
+    ) -> Result<RetrainReport> {
+        let config = self.config.read().await.clone();
+        let mut report = RetrainReport::default();
+
+        if !config.enabled {
+            report.skipped = Some("ML service is disabled".to_string());
+            return Ok(report);
+        }
+
+        let mut incidents = self.fetch_resolved_incidents().await?;
+        if incidents.len() > config.max_training_samples {
+            let excess = incidents.len() - config.max_training_samples;
+            incidents.drain(..excess);
+        }
+        report.samples = incidents.len();
+
+        let min_samples = min_samples.max(MIN_TRAINING_INCIDENTS);
+        if incidents.len() < min_samples {
+            info!(
+                resolved = incidents.len(),
+                required = min_samples,
+                "Not enough resolved incidents to retrain ML models"
+            );
+            report.skipped = Some(format!(
+                "{} resolved incidents, {} required",
+                incidents.len(),
+                min_samples
+            ));
+            return Ok(report);
+        }
+
+        let n_holdout = ((incidents.len() as f64 * HOLDOUT_FRACTION) as usize).max(1);
+        let (train, holdout) = incidents.split_at(incidents.len() - n_holdout);
+        report.holdout = holdout.len();
+
+        let mut extractor = FeatureExtractor::new(config.feature_config.clone());
+        extractor.fit(train)?;
+        let train_samples = training_samples(&extractor, train)?;
+        let holdout_dataset =
+            TrainingDataset::from_samples(&training_samples(&extractor, holdout)?);
+
+        for &target in targets {
+            if !target_enabled(&config, target) {
+                continue;
+            }
+
+            let outcome = match self
+                .retrain_target(
+                    target,
+                    &extractor,
+                    &train_samples,
+                    &holdout_dataset,
+                    holdout,
+                )
+                .await
+            {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    error!(model = %target, error = %e, "Failed to retrain ML model");
+                    RetrainOutcome {
+                        target,
+                        error: Some(e.to_string()),
+                        ..Default::default()
+                    }
+                }
+            };
+            report.outcomes.push(outcome);
+        }
+
+        if report.promoted() > 0 {
+            *self.feature_extractor.write().await = extractor;
+            *self.samples_since_training.write().await = 0;
+        }
+
+        info!(
+            samples = report.samples,
+            promoted = report.promoted(),
+            failed = report.failed(),
+            "ML model retraining completed"
+        );
+
+        Ok(report)
+    }
+
+    /// Train a candidate for one target and promote it if it wins
+    async fn retrain_target(
+        &self,
+        target: ModelTarget,
+        extractor: &FeatureExtractor,
+        train_samples: &[TrainingSample],
+        holdout_dataset: &TrainingDataset,
+        holdout: &[Incident],
+    ) -> Result<RetrainOutcome> {
+        let (mut candidate, _) = TrainedModel::train(target, extractor, train_samples)?;
+        let candidate_metrics = candidate.classifier.evaluate(holdout_dataset)?;
+
+        // The current model is scored with its own extractor
+        let incumbent_metrics = match self.models.read().await.get(&target) {
+            Some(model) => Some(model.evaluate(holdout)?),
+            None => None,
+        };
+
+        let promoted = incumbent_metrics
+            .as_ref()
+            .is_none_or(|incumbent| candidate_metrics.beats(incumbent));
+
+        let mut outcome = RetrainOutcome {
+            target,
+            promoted,
+            version: None,
+            candidate: Some(candidate_metrics.clone()),
+            incumbent: incumbent_metrics,
+            error: None,
+        };
+
+        if !promoted {
+            info!(
+                model = %target,
+                f1_score = candidate_metrics.f1_score,
+                "Retrained model did not beat the current one, keeping it"
+            );
+            return Ok(outcome);
+        }
+
+        candidate.classifier.metadata_mut().validation_metrics = Some(candidate_metrics);
+
+        if let Some(registry) = &self.registry {
+            let mut version = ModelVersion {
+                target,
+                metadata: candidate.classifier.metadata().clone(),
+                extractor: extractor.clone(),
+                samples: train_samples.to_vec(),
+            };
+            let name = registry.publish(&mut version)?;
+            candidate.classifier.metadata_mut().version = name.clone();
+            outcome.version = Some(name);
+        }
+
+        info!(
+            model = %target,
+            version = ?outcome.version,
+            "Promoted retrained ML model"
+        );
+        self.models.write().await.insert(target, candidate);
+
+        Ok(outcome)
+    }
+
+    /// Resolved and closed incidents, oldest resolution first
+    async fn fetch_resolved_incidents(&self) -> Result<Vec<Incident>> {
+        let filter = IncidentFilter {
+            states: vec![IncidentState::Resolved, IncidentState::Closed],
+            sort: IncidentSort::new(SortField::ResolvedAt, SortDirection::Asc),
+            ..Default::default()
+        };
+
+        let mut incidents = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .incident_store
+                .query_incidents(&filter, cursor.as_deref(), TRAINING_PAGE_SIZE)
+                .await?;
+            incidents.extend(page.incidents);
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(incidents)
+    }
+
+    /// Stored versions of a model, oldest first
+    pub fn model_versions(&self, target: ModelTarget) -> Result<Vec<ModelMetadata>> {
+        self.registry()?.versions(target)
+    }
+
+    /// Roll a model back to the version that was active before the current one
+    pub async fn rollback_model(&self, target: ModelTarget) -> Result<ModelMetadata> {
+        let version = self.registry()?.rollback(target)?;
+        let metadata = self.serve_version(target, &version).await?;
+        info!(model = %target, version = %version, "Rolled back ML model");
+        Ok(metadata)
+    }
+
+    /// Serve a specific stored version of a model
+    pub async fn activate_model_version(
+        &self,
+        target: ModelTarget,
+        version: &str,
+    ) -> Result<ModelMetadata> {
+        self.registry()?.activate(target, version)?;
+        let metadata = self.serve_version(target, version).await?;
+        info!(model = %target, version = %version, "Activated ML model version");
+        Ok(metadata)
+    }
+
+    async fn serve_version(&self, target: ModelTarget, version: &str) -> Result<ModelMetadata> {
+        let stored = self.registry()?.load(target, version)?;
+        let model = TrainedModel::from_version(&stored)?;
+        let metadata = model.classifier.metadata().clone();
+        self.models.write().await.insert(target, model);
+        Ok(metadata)
+    }
+
+    fn registry(&self) -> Result<&ModelRegistry> {
+        self.registry
+            .as_ref()
+            .ok_or_else(|| AppError::Configuration("No ML model path configured".to_string()))
+    }
+
     /// Add an incident to the training set
     pub async fn add_training_sample(&self, incident: &Incident) -> Result<()> {
         let config = self.config.read().await;
@@ -194,14 +534,9 @@ impl MLService {
             return Ok(());
         }
 
-        // Extract features
-        let features = extractor.transform(incident)?;
-        drop(extractor);
-
         // Create training sample
-        let sample = TrainingSample::new(features, incident.source.clone())
-            .with_severity(incident.severity.clone())
-            .with_type(incident.incident_type.clone());
+        let sample = training_sample(&extractor, incident)?;
+        drop(extractor);
 
         // Add to cache
         self.training_samples.insert(incident.id, sample);
@@ -224,17 +559,9 @@ impl MLService {
 
     /// Retrain models with accumulated samples
     async fn retrain_models(&self) -> Result<()> {
-        // Fetch recent incidents
-        let filter = crate::state::IncidentFilter::default();
-        let incidents = self
-            .incident_store
-            .list_incidents(&filter, 0, 1000)
+        self.retrain_from_store(&ModelTarget::ALL, MIN_TRAINING_INCIDENTS)
             .await?;
-
-        if incidents.len() >= 10 {
-            self.train_on_incidents(&incidents).await?;
-        }
-
+        *self.samples_since_training.write().await = 0;
         Ok(())
     }
 
@@ -251,18 +578,18 @@ impl MLService {
         drop(config);
 
         // Check if classifier is trained
-        let classifier_guard = self.severity_classifier.read().await;
-        let classifier = classifier_guard
-            .as_ref()
+        let models = self.models.read().await;
+        let model = models
+            .get(&ModelTarget::Severity)
             .ok_or_else(|| AppError::Internal("Severity classifier not trained".to_string()))?;
 
-        // Extract features
-        let extractor = self.feature_extractor.read().await;
-        let features = extractor.transform(incident)?;
-        drop(extractor);
-
         // Make prediction
-        let prediction = classifier.predict_severity(&features)?;
+        let prediction = model.predict(incident)?;
+        drop(models);
+
+        let severity = ModelTarget::index_to_severity(prediction.value);
+        let prediction = Prediction::new(severity, prediction.confidence)
+            .with_probabilities(prediction.probabilities);
 
         // Check confidence threshold
         let config = self.config.read().await;
@@ -276,26 +603,50 @@ impl MLService {
         Ok(prediction)
     }
 
-    /// Predict type for an incident (simplified - returns current type with confidence)
+    /// Predict type for an incident
+    ///
+    /// Until a type classifier is trained, returns the current type.
     pub async fn predict_type(&self, incident: &Incident) -> Result<Prediction<IncidentType>> {
-        // Simplified implementation: return current type with high confidence
-        // In production, this would use a separate classifier
+        let enabled = {
+            let config = self.config.read().await;
+            config.enabled && config.enable_type_prediction
+        };
+
+        if enabled {
+            if let Some(model) = self.models.read().await.get(&ModelTarget::Type) {
+                let prediction = model.predict(incident)?;
+                let incident_type = ModelTarget::index_to_type(prediction.value);
+                return Ok(Prediction::new(incident_type, prediction.confidence)
+                    .with_probabilities(prediction.probabilities));
+            }
+        }
+
         Ok(Prediction::new(incident.incident_type.clone(), 0.95))
     }
 
     /// Predict priority score for an incident (0-10)
+    ///
+    /// Until a priority classifier is trained, derives the score from the
+    /// predicted severity.
     pub async fn predict_priority(&self, incident: &Incident) -> Result<Prediction<f64>> {
-        // Simplified: derive priority from severity
-        let severity_pred = self.predict_severity(incident).await?;
-
-        let priority = match severity_pred.value {
-            Severity::P0 => 10.0,
-            Severity::P1 => 8.0,
-            Severity::P2 => 6.0,
-            Severity::P3 => 4.0,
-            Severity::P4 => 2.0,
+        let enabled = {
+            let config = self.config.read().await;
+            config.enabled && config.enable_priority_prediction
         };
 
+        if enabled {
+            if let Some(model) = self.models.read().await.get(&ModelTarget::Priority) {
+                let prediction = model.predict(incident)?;
+                return Ok(
+                    Prediction::new(prediction.value as f64, prediction.confidence)
+                        .with_probabilities(prediction.probabilities),
+                );
+            }
+        }
+
+        let severity_pred = self.predict_severity(incident).await?;
+        let priority = severity_priority(&severity_pred.value);
+
         Ok(Prediction::new(priority, severity_pred.confidence))
     }
 
@@ -314,14 +665,13 @@ impl MLService {
 
     /// Get model metadata
     pub async fn get_model_metadata(&self) -> Result<Vec<ModelMetadata>> {
-        let mut metadata = Vec::new();
-
-        let classifier_guard = self.severity_classifier.read().await;
-        if let Some(classifier) = classifier_guard.as_ref() {
-            metadata.push(classifier.metadata().clone());
-        }
+        let models = self.models.read().await;
 
-        Ok(metadata)
+        Ok(ModelTarget::ALL
+            .iter()
+            .filter_map(|target| models.get(target))
+            .map(|model| model.classifier.metadata().clone())
+            .collect())
     }
 
     /// Get service statistics
@@ -383,6 +733,108 @@ pub struct MLServiceStats {
     pub retrain_threshold: usize,
 }
 
+/// Outcome of retraining one model
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RetrainOutcome {
+    /// Model that was retrained
+    pub target: ModelTarget,
+
+    /// Whether the retrained model replaced the current one
+    pub promoted: bool,
+
+    /// Registry version of the promoted model
+    pub version: Option<String>,
+
+    /// Holdout metrics of the retrained model
+    pub candidate: Option<ModelMetrics>,
+
+    /// Holdout metrics of the model it was compared with
+    pub incumbent: Option<ModelMetrics>,
+
+    /// Why retraining failed
+    pub error: Option<String>,
+}
+
+/// Summary of a retraining run
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RetrainReport {
+    /// Resolved incidents used, including the holdout
+    pub samples: usize,
+
+    /// Incidents held out for evaluation
+    pub holdout: usize,
+
+    /// Why no model was retrained
+    pub skipped: Option<String>,
+
+    /// One entry per retrained model
+    pub outcomes: Vec<RetrainOutcome>,
+}
+
+impl RetrainReport {
+    /// Number of models promoted
+    pub fn promoted(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.promoted).count()
+    }
+
+    /// Number of models that failed to retrain
+    pub fn failed(&self) -> usize {
+        self.outcomes.iter().filter(|o| o.error.is_some()).count()
+    }
+}
+
+/// Whether `target` is enabled in `config`
+fn target_enabled(config: &MLConfig, target: ModelTarget) -> bool {
+    match target {
+        ModelTarget::Severity => config.enable_severity_prediction,
+        ModelTarget::Type => config.enable_type_prediction,
+        ModelTarget::Priority => config.enable_priority_prediction,
+    }
+}
+
+/// Priority score an incident is trained on
+///
+/// Taken from the [`PRIORITY_LABEL`] label when it holds a number from 0 to
+/// 10 and derived from the severity otherwise.
+pub fn priority_label(incident: &Incident) -> f64 {
+    incident
+        .labels
+        .get(PRIORITY_LABEL)
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|priority| (0.0..=10.0).contains(priority))
+        .unwrap_or_else(|| severity_priority(&incident.severity))
+}
+
+fn severity_priority(severity: &Severity) -> f64 {
+    match severity {
+        Severity::P0 => 10.0,
+        Severity::P1 => 8.0,
+        Severity::P2 => 6.0,
+        Severity::P3 => 4.0,
+        Severity::P4 => 2.0,
+    }
+}
+
+/// Labelled training sample for an incident
+fn training_sample(extractor: &FeatureExtractor, incident: &Incident) -> Result<TrainingSample> {
+    let features = extractor.transform(incident)?;
+
+    Ok(TrainingSample::new(features, incident.source.clone())
+        .with_severity(incident.severity.clone())
+        .with_type(incident.incident_type.clone())
+        .with_priority(priority_label(incident)))
+}
+
+fn training_samples(
+    extractor: &FeatureExtractor,
+    incidents: &[Incident],
+) -> Result<Vec<TrainingSample>> {
+    incidents
+        .iter()
+        .map(|incident| training_sample(extractor, incident))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -432,7 +884,11 @@ mod tests {
                 create_test_incident(
                     &format!("Incident {}", i),
                     &format!("Description {}", i),
-                    if i % 2 == 0 { Severity::P1 } else { Severity::P2 },
+                    if i % 2 == 0 {
+                        Severity::P1
+                    } else {
+                        Severity::P2
+                    },
                 )
             })
             .collect();
@@ -459,7 +915,11 @@ mod tests {
                 create_test_incident(
                     &format!("Database error {}", i),
                     &format!("Connection timeout {}", i),
-                    if i % 2 == 0 { Severity::P0 } else { Severity::P1 },
+                    if i % 2 == 0 {
+                        Severity::P0
+                    } else {
+                        Severity::P1
+                    },
                 )
             })
             .collect();
@@ -546,4 +1006,138 @@ mod tests {
         assert_eq!(stats.n_training_samples, 0);
         assert_eq!(stats.samples_since_training, 0);
     }
+
+    async fn resolved_store(count: usize) -> Arc<InMemoryStore> {
+        let store = Arc::new(InMemoryStore::new());
+        for i in 0..count {
+            let mut incident = if i % 2 == 0 {
+                Incident::new(
+                    "db-monitor".to_string(),
+                    format!("Database outage {}", i),
+                    "Primary database unreachable".to_string(),
+                    Severity::P0,
+                    IncidentType::Infrastructure,
+                )
+            } else {
+                Incident::new(
+                    "web-monitor".to_string(),
+                    format!("Slow page render {}", i),
+                    "Checkout page latency elevated".to_string(),
+                    Severity::P3,
+                    IncidentType::Application,
+                )
+            };
+            incident.resolve(
+                "oncall".to_string(),
+                crate::models::ResolutionMethod::Manual,
+                "Fixed".to_string(),
+                None,
+            );
+            store.save_incident(&incident).await.unwrap();
+        }
+        store
+    }
+
+    fn registry_config(dir: &std::path::Path) -> MLConfig {
+        MLConfig {
+            model_path: Some(dir.to_string_lossy().into_owned()),
+            ..MLConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retrain_from_store_promotes_and_versions_models() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = resolved_store(40).await;
+        let service = MLService::new(registry_config(dir.path()), store.clone());
+
+        let report = service
+            .retrain_from_store(&ModelTarget::ALL, 20)
+            .await
+            .unwrap();
+        assert_eq!(report.samples, 40);
+        assert_eq!(report.holdout, 8);
+        assert_eq!(report.failed(), 0);
+        assert_eq!(report.promoted(), 3);
+        assert!(report
+            .outcomes
+            .iter()
+            .all(|o| o.version.as_deref() == Some("v1") && o.incumbent.is_none()));
+
+        // The same data cannot beat the model it produced
+        let report = service
+            .retrain_from_store(&[ModelTarget::Severity], 20)
+            .await
+            .unwrap();
+        assert_eq!(report.promoted(), 0);
+        assert!(report.outcomes[0].incumbent.is_some());
+        assert_eq!(
+            service.model_versions(ModelTarget::Severity).unwrap().len(),
+            1
+        );
+
+        // A new service serves the stored versions
+        let restarted = MLService::new(registry_config(dir.path()), store);
+        assert_eq!(restarted.load_models().await.unwrap(), 3);
+
+        let metadata = restarted.get_model_metadata().await.unwrap();
+        assert_eq!(metadata.len(), 3);
+        assert!(metadata
+            .iter()
+            .all(|m| m.version == "v1" && m.validation_metrics.is_some()));
+
+        let incident = Incident::new(
+            "db-monitor".to_string(),
+            "Database outage".to_string(),
+            "Primary database unreachable".to_string(),
+            Severity::P0,
+            IncidentType::Infrastructure,
+        );
+        let original = service.predict_severity(&incident).await.unwrap();
+        let reloaded = restarted.predict_severity(&incident).await.unwrap();
+        assert_eq!(original.value, reloaded.value);
+
+        // Only one version exists, so there is nothing to roll back to
+        assert!(restarted
+            .rollback_model(ModelTarget::Severity)
+            .await
+            .is_err());
+        restarted
+            .activate_model_version(ModelTarget::Severity, "v1")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_retrain_from_store_requires_min_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = resolved_store(12).await;
+        let service = MLService::new(registry_config(dir.path()), store);
+
+        let report = service
+            .retrain_from_store(&ModelTarget::ALL, 100)
+            .await
+            .unwrap();
+
+        assert_eq!(report.samples, 12);
+        assert!(report.skipped.is_some());
+        assert!(report.outcomes.is_empty());
+        assert!(service.get_model_metadata().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_priority_label() {
+        let mut incident = create_test_incident("Test", "Description", Severity::P1);
+        assert_eq!(priority_label(&incident), 8.0);
+
+        incident
+            .labels
+            .insert(PRIORITY_LABEL.to_string(), "3".to_string());
+        assert_eq!(priority_label(&incident), 3.0);
+
+        incident
+            .labels
+            .insert(PRIORITY_LABEL.to_string(), "42".to_string());
+        assert_eq!(priority_label(&incident), 8.0);
+    }
 }
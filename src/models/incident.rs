@@ -6,7 +6,7 @@ use validator::Validate;
 use strum::{EnumString, Display};
 
 /// Represents an incident in the system
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
 pub struct Incident {
     /// Unique identifier
     pub id: Uuid,
@@ -66,6 +66,18 @@ pub struct Incident {
 
     /// Correlation score
     pub correlation_score: Option<f64>,
+
+    /// Correlation group the incident was placed in
+    #[serde(default)]
+    pub correlation_group_id: Option<Uuid>,
+
+    /// Legal hold preventing the incident from being archived or deleted
+    #[serde(default)]
+    pub legal_hold: Option<LegalHold>,
+
+    /// Optimistic concurrency version, incremented by the store on every update
+    #[serde(default)]
+    pub version: u64,
 }
 
 impl Incident {
@@ -106,9 +118,17 @@ impl Incident {
             notes: Vec::new(),
             fingerprint: None,
             correlation_score: None,
+            correlation_group_id: None,
+            legal_hold: None,
+            version: 0,
         }
     }
 
+    /// Entity tag for HTTP conditional requests
+    pub fn etag(&self) -> String {
+        format!("\"{}\"", self.version)
+    }
+
     /// Add a timeline event
     pub fn add_timeline_event(&mut self, event: TimelineEvent) {
         self.timeline.push(event);
@@ -162,6 +182,30 @@ impl Incident {
         )
     }
 
+    /// Place the incident under legal hold
+    pub fn place_legal_hold(&mut self, reason: String, placed_by: String) {
+        self.legal_hold = Some(LegalHold {
+            reason,
+            placed_by,
+            placed_at: Utc::now(),
+        });
+        self.updated_at = Utc::now();
+    }
+
+    /// Release the legal hold, returning it if one was set
+    pub fn release_legal_hold(&mut self) -> Option<LegalHold> {
+        let hold = self.legal_hold.take();
+        if hold.is_some() {
+            self.updated_at = Utc::now();
+        }
+        hold
+    }
+
+    /// Check if the incident is under legal hold
+    pub fn is_on_legal_hold(&self) -> bool {
+        self.legal_hold.is_some()
+    }
+
     /// Check if incident is critical
     pub fn is_critical(&self) -> bool {
         matches!(self.severity, Severity::P0 | Severity::P1)
@@ -254,7 +298,7 @@ pub enum IncidentType {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Resolution {
     pub resolved_at: DateTime<Utc>,
     pub resolved_by: String,
@@ -263,6 +307,14 @@ pub struct Resolution {
     pub notes: String,
 }
 
+/// Hold that exempts an incident from retention
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LegalHold {
+    pub reason: String,
+    pub placed_by: String,
+    pub placed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, EnumString, Display)]
 pub enum ResolutionMethod {
     Automated,
@@ -270,7 +322,7 @@ pub enum ResolutionMethod {
     AutoAssistedManual,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TimelineEvent {
     pub timestamp: DateTime<Utc>,
     pub event_type: EventType,
@@ -294,9 +346,10 @@ pub enum EventType {
     Resolved,
     AlertReceived,
     SeverityChanged,
+    Acknowledged,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Note {
     pub id: Uuid,
     pub author: String,
@@ -11,9 +11,17 @@ pub struct Playbook {
     pub id: Uuid,
     pub name: String,
     pub version: String,
+
+    /// Number the playbook service gave this definition when it was
+    /// published; 0 until then
+    #[serde(default)]
+    pub revision: u32,
+
     pub description: String,
     pub owner: String,
+    #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
     pub updated_at: DateTime<Utc>,
 
     /// Triggers that activate this playbook
@@ -23,7 +31,7 @@ pub struct Playbook {
     #[serde(default)]
     pub variables: HashMap<String, String>,
 
-    /// Sequence of steps to execute
+    /// Steps to execute, in order unless they declare dependencies
     pub steps: Vec<PlaybookStep>,
 
     /// Whether the playbook is enabled
@@ -88,6 +96,50 @@ pub struct PlaybookStep {
 
     /// Condition to execute this step
     pub condition: Option<String>,
+
+    /// Steps that must finish before this one starts
+    ///
+    /// When absent the step follows the previous step in the list, unless it
+    /// is only reached through an action's `on_success`/`on_failure`. An empty
+    /// list makes the step an entry point.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+
+    /// How the outcomes of `depends_on` decide whether the step runs
+    #[serde(default)]
+    pub trigger_rule: TriggerRule,
+
+    /// Who must approve an `approval` step
+    ///
+    /// The step's `timeout` bounds how long the execution waits; without one
+    /// it waits until someone decides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<ApprovalPolicy>,
+
+    /// Whether the step can safely run again after a restart interrupted it
+    ///
+    /// An execution interrupted in a step that is not idempotent is marked
+    /// abandoned instead of being resumed.
+    #[serde(default)]
+    pub idempotent: bool,
+}
+
+/// When a step with dependencies runs
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerRule {
+    /// Every dependency succeeded
+    AllSuccess,
+    /// At least one dependency succeeded, e.g. to join branches
+    OneSuccess,
+    /// Every dependency finished, whatever the outcome
+    AllDone,
+}
+
+impl Default for TriggerRule {
+    fn default() -> Self {
+        TriggerRule::AllSuccess
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -99,13 +151,47 @@ pub enum StepType {
     Escalation,
     Resolution,
     Custom,
+    /// Pauses the execution until a person approves or rejects it
+    Approval,
+}
+
+/// Approvers of an approval step and what happens when nobody decides
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApprovalPolicy {
+    /// People allowed to decide; those that look like email addresses are
+    /// also emailed
+    pub approvers: Vec<String>,
+
+    /// Decision taken when the step's timeout passes
+    #[serde(default)]
+    pub on_timeout: ApprovalTimeoutAction,
+}
+
+/// Decision taken for an approval that timed out
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalTimeoutAction {
+    /// Abort the execution
+    Reject,
+    /// Carry on as if approved
+    Approve,
+}
+
+impl Default for ApprovalTimeoutAction {
+    fn default() -> Self {
+        ApprovalTimeoutAction::Reject
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Action {
     pub action_type: ActionType,
     pub parameters: HashMap<String, serde_json::Value>,
+
+    /// Step to run when this action's step succeeds
     pub on_success: Option<String>,
+
+    /// Step to run when this action fails, e.g. to compensate
     pub on_failure: Option<String>,
 }
 
@@ -147,6 +233,33 @@ pub enum ActionType {
     RunScript,
 }
 
+impl ActionType {
+    pub const ALL: [ActionType; 22] = [
+        ActionType::Slack,
+        ActionType::Email,
+        ActionType::Pagerduty,
+        ActionType::Webhook,
+        ActionType::MetricsSnapshot,
+        ActionType::LogsCapture,
+        ActionType::HealthCheck,
+        ActionType::ServiceRestart,
+        ActionType::ServiceRollback,
+        ActionType::ScaleHorizontal,
+        ActionType::ScaleVertical,
+        ActionType::ConfigChange,
+        ActionType::CircuitBreaker,
+        ActionType::Wait,
+        ActionType::VerifyResolution,
+        ActionType::CreateWarRoom,
+        ActionType::SchedulePostmortem,
+        ActionType::IncidentResolve,
+        ActionType::SeverityIncrease,
+        ActionType::SeverityDecrease,
+        ActionType::HttpRequest,
+        ActionType::RunScript,
+    ];
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum BackoffStrategy {
@@ -173,6 +286,34 @@ pub struct PlaybookExecution {
     pub current_step: Option<String>,
     pub step_results: HashMap<String, StepResult>,
     pub error: Option<String>,
+
+    /// Branch steps chosen so far by `on_success`/`on_failure`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub activated_steps: Vec<String>,
+
+    /// Approvals requested by approval steps, decided or not
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvals: Vec<ApprovalRequest>,
+
+    /// Context variables as of the last checkpoint
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, serde_json::Value>,
+
+    /// Revision of the playbook the execution runs, to the end
+    #[serde(default)]
+    pub playbook_revision: u32,
+
+    /// Digest of that revision, which still finds it once revision numbers
+    /// have started again after a restart
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub playbook_digest: String,
+}
+
+impl PlaybookExecution {
+    /// Approval requests nobody has decided yet
+    pub fn pending_approvals(&self) -> impl Iterator<Item = &ApprovalRequest> {
+        self.approvals.iter().filter(|a| a.decision.is_none())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -182,6 +323,39 @@ pub enum ExecutionStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Paused at an approval step
+    WaitingForApproval,
+    /// Interrupted by a restart in a step that is not safe to run again
+    Abandoned,
+}
+
+/// An approval step waiting for, or given, a decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalRequest {
+    pub step_id: String,
+    pub approvers: Vec<String>,
+    pub requested_at: DateTime<Utc>,
+
+    /// When `on_timeout` applies; never if absent
+    pub expires_at: Option<DateTime<Utc>>,
+    pub on_timeout: ApprovalTimeoutAction,
+    pub decision: Option<ApprovalDecision>,
+}
+
+impl ApprovalRequest {
+    /// Whether the request is undecided and past its deadline
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.decision.is_none() && self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+/// Outcome of an approval request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    pub approved: bool,
+    pub decided_by: String,
+    pub decided_at: DateTime<Utc>,
+    pub comment: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -194,6 +368,114 @@ pub struct StepResult {
     pub error: Option<String>,
 }
 
+/// A published version of a playbook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybookRevision {
+    /// Revision number, counting from 1
+    pub revision: u32,
+
+    /// SHA-256 of the definition, not counting `revision` and timestamps
+    pub digest: String,
+
+    pub published_at: DateTime<Utc>,
+
+    /// The definition as published
+    pub playbook: Playbook,
+}
+
+/// Differences between two revisions of a playbook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybookDiff {
+    pub playbook_id: Uuid,
+    pub from_revision: u32,
+    pub to_revision: u32,
+    pub changes: Vec<PlaybookChange>,
+}
+
+/// A value that differs between two revisions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlaybookChange {
+    /// Where the value is, e.g. `steps.restart.actions[0].parameters.service`;
+    /// steps are named by their id
+    pub path: String,
+
+    pub kind: ChangeKind,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<serde_json::Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Step-by-step trace of a playbook dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybookSimulation {
+    pub playbook_id: Uuid,
+    pub playbook_name: String,
+    pub incident_id: Uuid,
+
+    /// `Completed`, or `Failed` if a condition could not be evaluated
+    pub status: ExecutionStatus,
+    pub error: Option<String>,
+
+    /// Every step, in playbook order
+    pub steps: Vec<SimulatedStep>,
+}
+
+/// What a step would do in a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedStep {
+    pub step_id: String,
+    pub step_type: StepType,
+    pub outcome: SimulatedOutcome,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+
+    /// Why the step would not run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    /// Who an approval step would ask
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub approvers: Vec<String>,
+
+    /// Actions that would be called, with templated parameters resolved
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<SimulatedAction>,
+}
+
+/// An action call recorded in a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulatedAction {
+    pub action_type: ActionType,
+    pub parameters: HashMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedOutcome {
+    /// Its actions would be called
+    Run,
+    /// Its condition evaluated to false
+    ConditionFalse,
+    /// Branch not taken, or dependencies not satisfied
+    Skipped,
+    /// Would wait for approval; assumed approved to trace the steps after it
+    ApprovalAssumed,
+    /// The playbook would stop before reaching it
+    NotReached,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +486,7 @@ mod tests {
             id: Uuid::new_v4(),
             name: "Critical Infrastructure Response".to_string(),
             version: "1.0.0".to_string(),
+            revision: 0,
             description: "Handles critical infrastructure failures".to_string(),
             owner: "platform-team".to_string(),
             created_at: Utc::now(),
@@ -231,6 +514,7 @@ mod tests {
             id: Uuid::new_v4(),
             name: "Test Playbook".to_string(),
             version: "1.0.0".to_string(),
+            revision: 0,
             description: "Test".to_string(),
             owner: "test".to_string(),
             created_at: Utc::now(),
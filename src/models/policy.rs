@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -12,7 +12,9 @@ pub struct EscalationPolicy {
     pub name: String,
     pub description: String,
     pub enabled: bool,
+    #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
+    #[serde(default = "Utc::now")]
     pub updated_at: DateTime<Utc>,
 
     /// Escalation levels
@@ -22,6 +24,7 @@ pub struct EscalationPolicy {
     pub repeat: Option<RepeatConfig>,
 
     /// Which severities this policy applies to
+    #[serde(default)]
     pub severity_filter: Vec<Severity>,
 }
 
@@ -45,11 +48,21 @@ fn default_true() -> bool {
     true
 }
 
+/// Who an escalation level notifies
+///
+/// `Team` notifies every member of a team. `RoundRobin` notifies one member,
+/// taking turns across escalations; `LeastLoaded` notifies the member with
+/// the fewest active incidents assigned to them; `FirstToAcknowledge`
+/// notifies every member and assigns the incident to whoever acknowledges
+/// first.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum EscalationTarget {
     User { email: String },
     Team { team_id: String },
+    RoundRobin { team_id: String },
+    LeastLoaded { team_id: String },
+    FirstToAcknowledge { team_id: String },
     Schedule { schedule_id: String },
     Webhook { url: String },
 }
@@ -131,6 +144,14 @@ pub struct OnCallSchedule {
 
     /// Schedule layers (primary, secondary, etc.)
     pub layers: Vec<ScheduleLayer>,
+
+    /// One-off changes to who is on call, taking precedence over everything else
+    #[serde(default)]
+    pub overrides: Vec<ScheduleOverride>,
+
+    /// Shifts traded between two users
+    #[serde(default)]
+    pub swaps: Vec<ShiftSwap>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -143,6 +164,10 @@ pub struct ScheduleLayer {
 
     /// When this layer is active
     pub restrictions: Option<TimeRestrictions>,
+
+    /// Days on which this layer has no one on call
+    #[serde(default)]
+    pub holidays: Vec<HolidayCalendar>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -165,6 +190,108 @@ pub struct TimeRestrictions {
     pub end_hour: u32,
 }
 
+/// Named set of days off, e.g. the public holidays of one country
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolidayCalendar {
+    pub name: String,
+
+    /// Dates in the schedule's timezone
+    pub dates: Vec<NaiveDate>,
+}
+
+/// Puts a user on call for a period in place of the rotation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleOverride {
+    pub id: Uuid,
+
+    /// Layer the override applies to; every layer when not set
+    #[serde(default)]
+    pub layer: Option<String>,
+
+    /// User who is on call during the override
+    pub user: String,
+
+    /// Only cover this user's shifts; when not set the override applies
+    /// whoever is on call, including when the layer would have no one
+    #[serde(default)]
+    pub replaces: Option<String>,
+
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+
+    #[serde(default)]
+    pub reason: Option<String>,
+
+    #[serde(default)]
+    pub created_by: Option<String>,
+
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl ScheduleOverride {
+    /// Whether the override is in effect at `at`
+    pub fn is_active(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// Two users covering each other's shifts
+///
+/// `with_user` takes over `user`'s shifts during `shift`, and `user` takes
+/// over `with_user`'s shifts during `return_shift`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShiftSwap {
+    pub id: Uuid,
+
+    /// Layer the swap applies to; every layer when not set
+    #[serde(default)]
+    pub layer: Option<String>,
+
+    pub user: String,
+    pub shift: ShiftWindow,
+    pub with_user: String,
+    pub return_shift: ShiftWindow,
+
+    #[serde(default)]
+    pub created_by: Option<String>,
+
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Half-open period of time, `start` inclusive and `end` exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShiftWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ShiftWindow {
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+}
+
+/// Why a user is on call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnCallSource {
+    Rotation,
+    Swap,
+    Override,
+}
+
+/// Continuous period during which one user is on call for a layer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleShift {
+    pub layer_name: String,
+    pub user: String,
+    pub source: OnCallSource,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,884 @@
+//! Incident actions taken from notifications
+//!
+//! Responders can acknowledge, resolve or take over an incident without
+//! leaving a notification: signed one-click links in emails, interactive
+//! buttons in Slack messages and PagerDuty webhook callbacks. This module
+//! authenticates those requests and turns them into [`NotificationAction`]s
+//! for `IncidentProcessor::apply_notification_action` to apply.
+
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fmt;
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Timeline event metadata key holding the key of the action that made it
+pub const ACTION_KEY_METADATA: &str = "action_key";
+
+/// Oldest Slack request accepted, in seconds, to limit replays
+pub const SLACK_MAX_REQUEST_AGE_SECS: i64 = 300;
+
+/// Actions offered in emails and Slack messages, with their labels
+///
+/// `take_ownership` reassigns the incident to whoever takes it.
+pub const OFFERED_ACTIONS: [(&str, &str); 3] = [
+    ("acknowledge", "Acknowledge"),
+    ("resolve", "Resolve"),
+    ("take_ownership", "Assign to me"),
+];
+
+/// Settings for acting on incidents from notifications
+///
+/// ```toml
+/// [notifications.actions]
+/// base_url = "https://incidents.example.com"
+/// link_secret_env = "ACTION_LINK_SECRET"
+/// slack_signing_secret_env = "SLACK_SIGNING_SECRET"
+/// pagerduty_webhook_secret_env = "PAGERDUTY_WEBHOOK_SECRET"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationActionsConfig {
+    /// Public URL of this server; email links are only added when set
+    pub base_url: Option<String>,
+
+    /// Environment variable holding the secret email links are signed with
+    pub link_secret_env: Option<String>,
+
+    /// Hours an email link stays valid
+    pub link_ttl_hours: u64,
+
+    /// Environment variable holding the Slack app's signing secret
+    pub slack_signing_secret_env: Option<String>,
+
+    /// Email addresses of Slack users by Slack user ID; other users act
+    /// under their Slack username
+    pub slack_users: HashMap<String, String>,
+
+    /// Environment variable holding the PagerDuty webhook subscription secret
+    pub pagerduty_webhook_secret_env: Option<String>,
+}
+
+impl Default for NotificationActionsConfig {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            link_secret_env: None,
+            link_ttl_hours: 24,
+            slack_signing_secret_env: None,
+            slack_users: HashMap::new(),
+            pagerduty_webhook_secret_env: None,
+        }
+    }
+}
+
+/// Change a responder can make to an incident from a notification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IncidentAction {
+    Acknowledge,
+    Resolve,
+    Reassign { assignee: String },
+}
+
+impl IncidentAction {
+    /// Action offered under `name` in an email link or Slack button
+    pub fn offered(name: &str, actor: &str) -> Option<Self> {
+        match name {
+            "acknowledge" => Some(Self::Acknowledge),
+            "resolve" => Some(Self::Resolve),
+            "take_ownership" => Some(Self::Reassign {
+                assignee: actor.to_string(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Where a notification action came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionSource {
+    Email,
+    Slack,
+    #[serde(rename = "pagerduty")]
+    PagerDuty,
+}
+
+impl ActionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Slack => "slack",
+            Self::PagerDuty => "pagerduty",
+        }
+    }
+}
+
+impl fmt::Display for ActionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Email => "email link",
+            Self::Slack => "Slack",
+            Self::PagerDuty => "PagerDuty",
+        })
+    }
+}
+
+/// Authenticated request to change an incident
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotificationAction {
+    pub incident_id: Uuid,
+    pub action: IncidentAction,
+
+    /// Who took the action
+    pub actor: String,
+
+    pub source: ActionSource,
+
+    /// Identifies the request, so that a repeated delivery is applied once
+    pub key: String,
+}
+
+/// Result of applying a notification action
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionOutcome {
+    pub incident_id: Uuid,
+    pub action: IncidentAction,
+    pub actor: String,
+    pub source: ActionSource,
+
+    /// Whether the incident changed; `false` for repeated requests and
+    /// actions that had already taken effect
+    pub applied: bool,
+
+    pub message: String,
+}
+
+impl ActionOutcome {
+    pub fn new(action: &NotificationAction, applied: bool, message: impl Into<String>) -> Self {
+        Self {
+            incident_id: action.incident_id,
+            action: action.action.clone(),
+            actor: action.actor.clone(),
+            source: action.source,
+            applied,
+            message: message.into(),
+        }
+    }
+}
+
+/// Query parameters of an email action link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionLinkParams {
+    pub incident: Uuid,
+
+    /// One of the [`OFFERED_ACTIONS`] names
+    pub action: String,
+
+    /// Recipient the link was made for, who the action is taken as
+    pub actor: String,
+
+    /// Unix time after which the link is refused
+    pub expires: i64,
+
+    /// Hex HMAC-SHA256 of the other parameters
+    pub sig: String,
+}
+
+/// Signs and checks one-click action links in emails
+#[derive(Clone)]
+pub struct ActionLinkSigner {
+    base_url: String,
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+impl ActionLinkSigner {
+    pub fn new(base_url: impl Into<String>, secret: impl Into<Vec<u8>>, ttl: Duration) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            secret: secret.into(),
+            ttl,
+        }
+    }
+
+    /// Build a link for `actor` to take the offered action `action`
+    pub fn link(&self, incident_id: Uuid, action: &str, actor: &str, now: DateTime<Utc>) -> String {
+        let expires = (now + self.ttl).timestamp();
+        let params = ActionLinkParams {
+            incident: incident_id,
+            action: action.to_string(),
+            actor: actor.to_string(),
+            expires,
+            sig: hex::encode(
+                self.mac(incident_id, action, actor, expires)
+                    .finalize()
+                    .into_bytes(),
+            ),
+        };
+        let query = serde_urlencoded::to_string(&params).unwrap_or_default();
+        format!("{}?{}", self.action_url(), query)
+    }
+
+    fn action_url(&self) -> String {
+        format!("{}/v1/notification-actions/email", self.base_url)
+    }
+
+    /// HTML page asking to confirm the action of a valid link
+    ///
+    /// Opening a link only shows this page. Its form posts the signed
+    /// parameters back, and the action is taken on that request, so mail
+    /// scanners and link previews that follow links do not act on incidents.
+    pub fn confirmation_page(
+        &self,
+        params: &ActionLinkParams,
+        now: DateTime<Utc>,
+    ) -> Result<String> {
+        self.verify(params, now)?;
+
+        let label = OFFERED_ACTIONS
+            .iter()
+            .find(|(action, _)| *action == params.action)
+            .map_or(params.action.as_str(), |(_, label)| label);
+        let fields = [
+            ("incident", params.incident.to_string()),
+            ("action", params.action.clone()),
+            ("actor", params.actor.clone()),
+            ("expires", params.expires.to_string()),
+            ("sig", params.sig.clone()),
+        ];
+        let inputs: String = fields
+            .iter()
+            .map(|(name, value)| {
+                format!(
+                    "<input type=\"hidden\" name=\"{}\" value=\"{}\">\n",
+                    name,
+                    escape_html(value)
+                )
+            })
+            .collect();
+
+        Ok(format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="robots" content="noindex">
+<title>{label} incident</title>
+</head>
+<body>
+<p>{label} incident {incident} as {actor}?</p>
+<form method="post" action="{url}">
+{inputs}<button type="submit">{label}</button>
+</form>
+</body>
+</html>
+"#,
+            label = escape_html(label),
+            incident = params.incident,
+            actor = escape_html(&params.actor),
+            url = escape_html(&self.action_url()),
+            inputs = inputs,
+        ))
+    }
+
+    /// Plain text listing a link for each offered action
+    pub fn render_text(&self, incident_id: Uuid, actor: &str, now: DateTime<Utc>) -> String {
+        OFFERED_ACTIONS
+            .iter()
+            .map(|(action, label)| {
+                format!("{}: {}", label, self.link(incident_id, action, actor, now))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Check a link's signature and expiry
+    pub fn verify(
+        &self,
+        params: &ActionLinkParams,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationAction> {
+        let sig = hex::decode(&params.sig)
+            .map_err(|_| AppError::Authentication("Invalid action link signature".to_string()))?;
+        self.mac(
+            params.incident,
+            &params.action,
+            &params.actor,
+            params.expires,
+        )
+        .verify_slice(&sig)
+        .map_err(|_| AppError::Authentication("Invalid action link signature".to_string()))?;
+
+        if now.timestamp() > params.expires {
+            return Err(AppError::Authentication(
+                "Action link has expired".to_string(),
+            ));
+        }
+
+        let action = IncidentAction::offered(&params.action, &params.actor)
+            .ok_or_else(|| AppError::Validation(format!("Unknown action '{}'", params.action)))?;
+
+        Ok(NotificationAction {
+            incident_id: params.incident,
+            action,
+            actor: params.actor.clone(),
+            source: ActionSource::Email,
+            key: format!("email:{}", params.sig.to_ascii_lowercase()),
+        })
+    }
+
+    fn mac(&self, incident_id: Uuid, action: &str, actor: &str, expires: i64) -> HmacSha256 {
+        let message = format!("v1\n{}\n{}\n{}\n{}", incident_id, action, actor, expires);
+        hmac_sha256(&self.secret, &[message.as_bytes()])
+    }
+}
+
+/// Escape HTML special characters
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#x27;")
+}
+
+fn hmac_sha256(secret: &[u8], parts: &[&[u8]]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac
+}
+
+/// Check the `X-Slack-Signature` of a Slack request
+///
+/// Requests whose `X-Slack-Request-Timestamp` is more than
+/// [`SLACK_MAX_REQUEST_AGE_SECS`] away from `now` are refused.
+pub fn verify_slack_signature(
+    signing_secret: &[u8],
+    timestamp: &str,
+    signature: &str,
+    body: &[u8],
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let sent_at: i64 = timestamp
+        .trim()
+        .parse()
+        .map_err(|_| AppError::Authentication("Invalid Slack request timestamp".to_string()))?;
+    if (now.timestamp() - sent_at).abs() > SLACK_MAX_REQUEST_AGE_SECS {
+        return Err(AppError::Authentication(
+            "Slack request timestamp is too far from the current time".to_string(),
+        ));
+    }
+
+    let sig = signature
+        .trim()
+        .strip_prefix("v0=")
+        .and_then(|hex_sig| hex::decode(hex_sig).ok())
+        .ok_or_else(|| AppError::Authentication("Invalid Slack signature".to_string()))?;
+    hmac_sha256(
+        signing_secret,
+        &[b"v0:", timestamp.trim().as_bytes(), b":", body],
+    )
+    .verify_slice(&sig)
+    .map_err(|_| AppError::Authentication("Invalid Slack signature".to_string()))
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackInteractionForm {
+    payload: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackInteraction {
+    #[serde(rename = "type")]
+    interaction_type: String,
+    user: SlackUser,
+    #[serde(default)]
+    actions: Vec<SlackAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackUser {
+    id: String,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SlackAction {
+    action_id: String,
+    #[serde(default)]
+    value: Option<String>,
+    action_ts: String,
+}
+
+/// Read the incident actions from a Slack interaction request body
+///
+/// Only `block_actions` interactions with our buttons are used. `users`
+/// maps Slack user IDs to the email addresses they act as.
+pub fn parse_slack_interaction(
+    body: &[u8],
+    users: &HashMap<String, String>,
+) -> Result<Vec<NotificationAction>> {
+    let form: SlackInteractionForm = serde_urlencoded::from_bytes(body)
+        .map_err(|e| AppError::Validation(format!("Invalid Slack interaction: {}", e)))?;
+    let interaction: SlackInteraction = serde_json::from_str(&form.payload)
+        .map_err(|e| AppError::Validation(format!("Invalid Slack interaction payload: {}", e)))?;
+
+    if interaction.interaction_type != "block_actions" {
+        return Ok(Vec::new());
+    }
+
+    let user = &interaction.user;
+    let actor = users
+        .get(&user.id)
+        .or(user.username.as_ref())
+        .or(user.name.as_ref())
+        .unwrap_or(&user.id)
+        .clone();
+
+    let mut actions = Vec::new();
+    for slack_action in &interaction.actions {
+        let Some(action) = IncidentAction::offered(&slack_action.action_id, &actor) else {
+            continue;
+        };
+        let incident_id = slack_action
+            .value
+            .as_deref()
+            .and_then(|value| Uuid::parse_str(value).ok())
+            .ok_or_else(|| {
+                AppError::Validation("Slack action does not name an incident".to_string())
+            })?;
+
+        actions.push(NotificationAction {
+            incident_id,
+            action,
+            actor: actor.clone(),
+            source: ActionSource::Slack,
+            key: format!("slack:{}:{}", user.id, slack_action.action_ts),
+        });
+    }
+    Ok(actions)
+}
+
+/// Check the `X-PagerDuty-Signature` of a PagerDuty v3 webhook
+///
+/// The header may hold several comma-separated `v1=` signatures while a
+/// secret is being rotated; any one matching is enough.
+pub fn verify_pagerduty_signature(secret: &[u8], signatures: &str, body: &[u8]) -> Result<()> {
+    let valid = signatures
+        .split(',')
+        .filter_map(|sig| sig.trim().strip_prefix("v1="))
+        .filter_map(|hex_sig| hex::decode(hex_sig).ok())
+        .any(|sig| hmac_sha256(secret, &[body]).verify_slice(&sig).is_ok());
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Authentication(
+            "Invalid PagerDuty signature".to_string(),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyWebhook {
+    event: PagerDutyWebhookEvent,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyWebhookEvent {
+    id: String,
+    event_type: String,
+    #[serde(default)]
+    agent: Option<PagerDutyReference>,
+    #[serde(default)]
+    data: PagerDutyIncidentData,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PagerDutyIncidentData {
+    #[serde(default)]
+    incident_key: Option<String>,
+    #[serde(default)]
+    assignees: Vec<PagerDutyReference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PagerDutyReference {
+    #[serde(default)]
+    summary: Option<String>,
+}
+
+/// Read the incident action from a PagerDuty v3 webhook body
+///
+/// `incident.acknowledged`, `incident.resolved` and `incident.reassigned`
+/// events for incidents raised by this service are used; anything else,
+/// including PagerDuty's test pings, gives `None`.
+pub fn parse_pagerduty_webhook(body: &[u8]) -> Result<Option<NotificationAction>> {
+    let webhook: PagerDutyWebhook = serde_json::from_slice(body)
+        .map_err(|e| AppError::Validation(format!("Invalid PagerDuty webhook: {}", e)))?;
+    let event = webhook.event;
+
+    // Incidents raised by this service use our incident ID as their key
+    let Some(incident_id) = event
+        .data
+        .incident_key
+        .as_deref()
+        .and_then(|key| Uuid::parse_str(key).ok())
+    else {
+        return Ok(None);
+    };
+
+    let action = match event.event_type.as_str() {
+        "incident.acknowledged" => IncidentAction::Acknowledge,
+        "incident.resolved" => IncidentAction::Resolve,
+        "incident.reassigned" => {
+            let Some(assignee) = event.data.assignees.iter().find_map(|a| a.summary.clone()) else {
+                return Ok(None);
+            };
+            IncidentAction::Reassign { assignee }
+        }
+        _ => return Ok(None),
+    };
+
+    let actor = event
+        .agent
+        .and_then(|agent| agent.summary)
+        .unwrap_or_else(|| "pagerduty".to_string());
+
+    Ok(Some(NotificationAction {
+        incident_id,
+        action,
+        actor,
+        source: ActionSource::PagerDuty,
+        key: format!("pagerduty:{}", event.id),
+    }))
+}
+
+/// Secrets for notification actions, read from the environment at startup
+///
+/// Each kind of action is only accepted once its secret is configured.
+#[derive(Clone, Default)]
+pub struct NotificationActions {
+    links: Option<ActionLinkSigner>,
+    slack_signing_secret: Option<String>,
+    slack_users: HashMap<String, String>,
+    pagerduty_webhook_secret: Option<String>,
+}
+
+impl NotificationActions {
+    pub fn from_config(config: &NotificationActionsConfig) -> Self {
+        let secret = |env: &Option<String>| {
+            env.as_ref()
+                .and_then(|env_var| std::env::var(env_var).ok())
+                .filter(|value| !value.is_empty())
+        };
+
+        let links = match (&config.base_url, secret(&config.link_secret_env)) {
+            (Some(base_url), Some(link_secret)) => Some(ActionLinkSigner::new(
+                base_url.clone(),
+                link_secret,
+                Duration::hours(config.link_ttl_hours as i64),
+            )),
+            _ => None,
+        };
+
+        Self {
+            links,
+            slack_signing_secret: secret(&config.slack_signing_secret_env),
+            slack_users: config.slack_users.clone(),
+            pagerduty_webhook_secret: secret(&config.pagerduty_webhook_secret_env),
+        }
+    }
+
+    /// Use `signer` for email links
+    pub fn with_links(mut self, signer: ActionLinkSigner) -> Self {
+        self.links = Some(signer);
+        self
+    }
+
+    /// Accept Slack interactions signed with `signing_secret`
+    pub fn with_slack(mut self, signing_secret: String, users: HashMap<String, String>) -> Self {
+        self.slack_signing_secret = Some(signing_secret);
+        self.slack_users = users;
+        self
+    }
+
+    /// Accept PagerDuty webhooks signed with `secret`
+    pub fn with_pagerduty(mut self, secret: String) -> Self {
+        self.pagerduty_webhook_secret = Some(secret);
+        self
+    }
+
+    /// Signer for email links, if they are configured
+    pub fn links(&self) -> Option<&ActionLinkSigner> {
+        self.links.as_ref()
+    }
+
+    /// Whether Slack messages should carry action buttons
+    pub fn slack_enabled(&self) -> bool {
+        self.slack_signing_secret.is_some()
+    }
+
+    /// Authenticate an email action link
+    pub fn email_action(
+        &self,
+        params: &ActionLinkParams,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationAction> {
+        self.links
+            .as_ref()
+            .ok_or_else(|| not_configured("Email action links"))?
+            .verify(params, now)
+    }
+
+    /// Confirmation page for an email action link
+    pub fn email_confirmation(
+        &self,
+        params: &ActionLinkParams,
+        now: DateTime<Utc>,
+    ) -> Result<String> {
+        self.links
+            .as_ref()
+            .ok_or_else(|| not_configured("Email action links"))?
+            .confirmation_page(params, now)
+    }
+
+    /// Authenticate a Slack interaction request and read its actions
+    pub fn slack_actions(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<NotificationAction>> {
+        let secret = self
+            .slack_signing_secret
+            .as_ref()
+            .ok_or_else(|| not_configured("Slack actions"))?;
+        verify_slack_signature(secret.as_bytes(), timestamp, signature, body, now)?;
+        parse_slack_interaction(body, &self.slack_users)
+    }
+
+    /// Authenticate a PagerDuty webhook and read its action
+    pub fn pagerduty_action(
+        &self,
+        signatures: &str,
+        body: &[u8],
+    ) -> Result<Option<NotificationAction>> {
+        let secret = self
+            .pagerduty_webhook_secret
+            .as_ref()
+            .ok_or_else(|| not_configured("PagerDuty webhooks"))?;
+        verify_pagerduty_signature(secret.as_bytes(), signatures, body)?;
+        parse_pagerduty_webhook(body)
+    }
+}
+
+fn not_configured(what: &str) -> AppError {
+    AppError::Configuration(format!("{} are not configured", what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer() -> ActionLinkSigner {
+        ActionLinkSigner::new("https://im.example.com/", "link-secret", Duration::hours(1))
+    }
+
+    fn link_params(link: &str) -> ActionLinkParams {
+        let query = link.split_once('?').unwrap().1;
+        serde_urlencoded::from_str(query).unwrap()
+    }
+
+    #[test]
+    fn test_action_link_round_trip() {
+        let now = Utc::now();
+        let incident_id = Uuid::new_v4();
+        let link = signer().link(incident_id, "take_ownership", "alice@example.com", now);
+        assert!(link.starts_with("https://im.example.com/v1/notification-actions/email?"));
+
+        let action = signer().verify(&link_params(&link), now).unwrap();
+        assert_eq!(action.incident_id, incident_id);
+        assert_eq!(action.source, ActionSource::Email);
+        assert_eq!(
+            action.action,
+            IncidentAction::Reassign {
+                assignee: "alice@example.com".to_string()
+            }
+        );
+
+        // The same link always maps to the same key
+        let again = signer().verify(&link_params(&link), now).unwrap();
+        assert_eq!(action.key, again.key);
+    }
+
+    #[test]
+    fn test_action_link_rejects_tampering_and_expiry() {
+        let now = Utc::now();
+        let link = signer().link(Uuid::new_v4(), "acknowledge", "alice@example.com", now);
+
+        let mut params = link_params(&link);
+        params.action = "resolve".to_string();
+        assert!(matches!(
+            signer().verify(&params, now),
+            Err(AppError::Authentication(_))
+        ));
+
+        let other = ActionLinkSigner::new("https://im.example.com", "other", Duration::hours(1));
+        assert!(other.verify(&link_params(&link), now).is_err());
+
+        let later = now + Duration::hours(2);
+        assert!(matches!(
+            signer().verify(&link_params(&link), later),
+            Err(AppError::Authentication(_))
+        ));
+    }
+
+    #[test]
+    fn test_confirmation_page_posts_signed_params() {
+        let now = Utc::now();
+        let incident_id = Uuid::new_v4();
+        let link = signer().link(incident_id, "resolve", "o'brien@example.com", now);
+        let params = link_params(&link);
+
+        let page = signer().confirmation_page(&params, now).unwrap();
+        assert!(page.contains(
+            r#"<form method="post" action="https://im.example.com/v1/notification-actions/email">"#
+        ));
+        assert!(page.contains(&format!(r#"name="sig" value="{}""#, params.sig)));
+        assert!(page.contains(&format!(r#"name="incident" value="{}""#, incident_id)));
+        assert!(page.contains(r#"name="actor" value="o&#x27;brien@example.com""#));
+        assert!(page.contains("<button type=\"submit\">Resolve</button>"));
+
+        // Invalid links get an error instead of a form
+        let mut tampered = params.clone();
+        tampered.actor = "mallory@example.com".to_string();
+        assert!(signer().confirmation_page(&tampered, now).is_err());
+        assert!(signer()
+            .confirmation_page(&params, now + Duration::hours(2))
+            .is_err());
+    }
+
+    fn slack_body(incident_id: Uuid, action_id: &str) -> Vec<u8> {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123", "username": "alice" },
+            "actions": [{
+                "action_id": action_id,
+                "value": incident_id.to_string(),
+                "action_ts": "1700000000.000100"
+            }]
+        });
+        serde_urlencoded::to_string([("payload", payload.to_string())])
+            .unwrap()
+            .into_bytes()
+    }
+
+    fn slack_signature(secret: &str, timestamp: &str, body: &[u8]) -> String {
+        let mac = hmac_sha256(
+            secret.as_bytes(),
+            &[b"v0:", timestamp.as_bytes(), b":", body],
+        );
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_slack_interaction() {
+        let now = Utc::now();
+        let timestamp = now.timestamp().to_string();
+        let incident_id = Uuid::new_v4();
+        let body = slack_body(incident_id, "acknowledge");
+        let signature = slack_signature("slack-secret", &timestamp, &body);
+
+        let actions = NotificationActions::default()
+            .with_slack(
+                "slack-secret".to_string(),
+                HashMap::from([("U123".to_string(), "alice@example.com".to_string())]),
+            )
+            .slack_actions(&timestamp, &signature, &body, now)
+            .unwrap();
+
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].incident_id, incident_id);
+        assert_eq!(actions[0].action, IncidentAction::Acknowledge);
+        assert_eq!(actions[0].actor, "alice@example.com");
+        assert_eq!(actions[0].key, "slack:U123:1700000000.000100");
+    }
+
+    #[test]
+    fn test_slack_signature_rejected() {
+        let now = Utc::now();
+        let timestamp = now.timestamp().to_string();
+        let body = slack_body(Uuid::new_v4(), "resolve");
+
+        let forged = slack_signature("wrong-secret", &timestamp, &body);
+        assert!(verify_slack_signature(b"slack-secret", &timestamp, &forged, &body, now).is_err());
+
+        // A correctly signed but old request is a possible replay
+        let old = (now - Duration::minutes(10)).timestamp().to_string();
+        let signature = slack_signature("slack-secret", &old, &body);
+        assert!(verify_slack_signature(b"slack-secret", &old, &signature, &body, now).is_err());
+    }
+
+    #[test]
+    fn test_pagerduty_webhook() {
+        let incident_id = Uuid::new_v4();
+        let body = serde_json::json!({
+            "event": {
+                "id": "01DEN3RQLQHTMGM7MQEEF1EQFF",
+                "event_type": "incident.reassigned",
+                "resource_type": "incident",
+                "agent": { "id": "PLH1HKV", "summary": "Tenex Engineer", "type": "user_reference" },
+                "data": {
+                    "id": "PGR0VU2",
+                    "type": "incident",
+                    "incident_key": incident_id.to_string(),
+                    "assignees": [
+                        { "id": "PTUXL6G", "summary": "User 123", "type": "user_reference" }
+                    ]
+                }
+            }
+        })
+        .to_string();
+        let mac = hmac_sha256(b"pd-secret", &[body.as_bytes()]);
+        let signatures = format!(
+            "v1=deadbeef,v1={}",
+            hex::encode(mac.finalize().into_bytes())
+        );
+
+        let actions = NotificationActions::default().with_pagerduty("pd-secret".to_string());
+        let action = actions
+            .pagerduty_action(&signatures, body.as_bytes())
+            .unwrap()
+            .unwrap();
+        assert_eq!(action.incident_id, incident_id);
+        assert_eq!(action.actor, "Tenex Engineer");
+        assert_eq!(
+            action.action,
+            IncidentAction::Reassign {
+                assignee: "User 123".to_string()
+            }
+        );
+        assert_eq!(action.key, "pagerduty:01DEN3RQLQHTMGM7MQEEF1EQFF");
+
+        assert!(actions
+            .pagerduty_action("v1=deadbeef", body.as_bytes())
+            .is_err());
+
+        let ping = br#"{"event":{"id":"1","event_type":"pagey.ping","data":{}}}"#;
+        assert!(parse_pagerduty_webhook(ping).unwrap().is_none());
+    }
+}
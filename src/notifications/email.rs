@@ -1,11 +1,19 @@
 use crate::error::{AppError, Result};
 use crate::models::{Incident, Notification, NotificationStatus};
 use chrono::Utc;
-use lettre::message::{header, MultiPart, SinglePart};
+use lettre::message::{header, Attachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use tracing::{error, info};
 
+/// File attached to an email
+#[derive(Debug, Clone)]
+pub struct EmailAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
 /// Email notification sender
 #[derive(Clone)]
 pub struct EmailSender {
@@ -76,7 +84,87 @@ impl EmailSender {
         let message = self.build_email_message(incident, &recipients, &subject, &body)?;
 
         // Send email
-        let result = tokio::task::spawn_blocking({
+        let result = self.transmit(message).await;
+
+        match result {
+            Ok(_) => {
+                notification.status = NotificationStatus::Sent;
+                notification.sent_at = Some(Utc::now());
+                info!(
+                    notification_id = %notification.id,
+                    incident_id = %incident.id,
+                    recipients = ?recipients,
+                    "Email notification sent successfully"
+                );
+                Ok(())
+            }
+            Err(e) => {
+                notification.status = NotificationStatus::Failed;
+                notification.error = Some(e.to_string());
+                error!(
+                    notification_id = %notification.id,
+                    incident_id = %incident.id,
+                    error = %e,
+                    "Failed to send email notification"
+                );
+                Err(e)
+            }
+        }
+    }
+
+    /// Send a plain text email that is not about a single incident
+    pub async fn send_message(
+        &self,
+        recipients: &[String],
+        subject: &str,
+        body: &str,
+        attachments: &[EmailAttachment],
+    ) -> Result<()> {
+        if recipients.is_empty() {
+            return Err(AppError::Validation("No recipients specified".to_string()));
+        }
+
+        let mut message_builder = Message::builder()
+            .from(self.from_mailbox()?)
+            .subject(subject);
+        for addr in recipients {
+            message_builder = message_builder.to(addr.parse().map_err(|e| {
+                AppError::Validation(format!("Invalid recipient address '{}': {}", addr, e))
+            })?);
+        }
+
+        let mut multipart = MultiPart::mixed().singlepart(
+            SinglePart::builder()
+                .header(header::ContentType::TEXT_PLAIN)
+                .body(body.to_string()),
+        );
+        for attachment in attachments {
+            let content_type = header::ContentType::parse(&attachment.content_type)
+                .map_err(|e| AppError::Validation(format!("Invalid content type: {}", e)))?;
+            multipart = multipart.singlepart(
+                Attachment::new(attachment.filename.clone())
+                    .body(attachment.content.clone(), content_type),
+            );
+        }
+
+        let message = message_builder
+            .multipart(multipart)
+            .map_err(|e| AppError::Internal(format!("Failed to build email: {}", e)))?;
+
+        self.transmit(message).await?;
+
+        info!(
+            recipients = ?recipients,
+            attachments = attachments.len(),
+            "Email message sent successfully"
+        );
+
+        Ok(())
+    }
+
+    /// Send a built message over SMTP
+    async fn transmit(&self, message: Message) -> Result<()> {
+        tokio::task::spawn_blocking({
             let smtp_server = self.smtp_server.clone();
             let smtp_port = self.smtp_port;
             let username = self.smtp_username.clone();
@@ -108,32 +196,20 @@ impl EmailSender {
             }
         })
         .await
-        .map_err(|e| AppError::Internal(format!("Task join error: {}", e)))?;
+        .map_err(|e| AppError::Internal(format!("Task join error: {}", e)))?
+    }
 
-        match result {
-            Ok(_) => {
-                notification.status = NotificationStatus::Sent;
-                notification.sent_at = Some(Utc::now());
-                info!(
-                    notification_id = %notification.id,
-                    incident_id = %incident.id,
-                    recipients = ?recipients,
-                    "Email notification sent successfully"
-                );
-                Ok(())
-            }
-            Err(e) => {
-                notification.status = NotificationStatus::Failed;
-                notification.error = Some(e.to_string());
-                error!(
-                    notification_id = %notification.id,
-                    incident_id = %incident.id,
-                    error = %e,
-                    "Failed to send email notification"
-                );
-                Err(e)
-            }
-        }
+    /// Sender mailbox, with display name if configured
+    fn from_mailbox(&self) -> Result<lettre::message::Mailbox> {
+        let from_mailbox = if let Some(name) = &self.from_name {
+            format!("{} <{}>", name, self.from_email)
+        } else {
+            self.from_email.clone()
+        };
+
+        from_mailbox
+            .parse()
+            .map_err(|e| AppError::Configuration(format!("Invalid from address: {}", e)))
     }
 
     /// Build email message with HTML and plain text parts
@@ -145,15 +221,7 @@ impl EmailSender {
         body: &str,
     ) -> Result<Message> {
         // Build from address
-        let from_mailbox = if let Some(name) = &self.from_name {
-            format!("{} <{}>", name, self.from_email)
-        } else {
-            self.from_email.clone()
-        };
-
-        let from = from_mailbox
-            .parse()
-            .map_err(|e| AppError::Configuration(format!("Invalid from address: {}", e)))?;
+        let from = self.from_mailbox()?;
 
         // Parse recipients
         let to_addresses: Result<Vec<_>> = recipients
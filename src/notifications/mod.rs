@@ -1,3 +1,4 @@
+pub mod actions;
 pub mod circuit_breaker_sender;
 pub mod email;
 pub mod pagerduty;
@@ -5,11 +6,15 @@ pub mod service;
 pub mod slack;
 pub mod webhook;
 
+pub use actions::{
+    ActionLinkParams, ActionLinkSigner, ActionOutcome, ActionSource, IncidentAction,
+    NotificationAction, NotificationActions, NotificationActionsConfig,
+};
 pub use circuit_breaker_sender::{
     CircuitBreakerNotificationSender, EmailSenderWithBreaker, NotificationSender,
     PagerDutySenderWithBreaker, SlackSenderWithBreaker, WebhookSenderWithBreaker,
 };
-pub use email::EmailSender;
+pub use email::{EmailAttachment, EmailSender};
 pub use pagerduty::PagerDutySender;
 pub use service::{NotificationService, NotificationStats};
 pub use slack::SlackSender;
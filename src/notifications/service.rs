@@ -1,7 +1,12 @@
 use crate::config::NotificationConfig;
 use crate::error::{AppError, Result};
-use crate::models::{Incident, Notification, NotificationChannel, NotificationStatus};
-use crate::notifications::{EmailSender, PagerDutySender, SlackSender, WebhookSender};
+use crate::models::{
+    ApprovalRequest, ApprovalTimeoutAction, Incident, Notification, NotificationChannel,
+    NotificationStatus,
+};
+use crate::notifications::{
+    EmailAttachment, EmailSender, NotificationActions, PagerDutySender, SlackSender, WebhookSender,
+};
 use crate::state::IncidentStore;
 use std::sync::Arc;
 use std::time::Duration;
@@ -17,6 +22,7 @@ pub struct NotificationService {
     email_sender: Option<EmailSender>,
     pagerduty_sender: Option<PagerDutySender>,
     webhook_sender: WebhookSender,
+    actions: NotificationActions,
     store: Arc<dyn IncidentStore>,
     notification_tx: mpsc::Sender<Notification>,
 }
@@ -24,6 +30,8 @@ pub struct NotificationService {
 impl NotificationService {
     /// Create a new notification service
     pub fn new(config: NotificationConfig, store: Arc<dyn IncidentStore>) -> Result<Self> {
+        let actions = NotificationActions::from_config(&config.actions);
+
         // Initialize Slack sender if enabled
         let slack_sender = if config.slack_enabled {
             let webhook_url = config
@@ -37,11 +45,13 @@ impl NotificationService {
                 .and_then(|env_var| std::env::var(env_var).ok());
 
             if webhook_url.is_some() || bot_token.is_some() {
-                Some(SlackSender::new(
-                    webhook_url,
-                    bot_token,
-                    config.slack_default_channel.clone(),
-                )?)
+                let sender =
+                    SlackSender::new(webhook_url, bot_token, config.slack_default_channel.clone())?;
+                if actions.slack_enabled() {
+                    Some(sender.with_action_buttons())
+                } else {
+                    Some(sender)
+                }
             } else {
                 warn!("Slack notifications enabled but no webhook URL or bot token configured");
                 None
@@ -109,6 +119,7 @@ impl NotificationService {
             email_sender: email_sender.clone(),
             pagerduty_sender: pagerduty_sender.clone(),
             webhook_sender: webhook_sender.clone(),
+            actions,
             store: store.clone(),
             notification_tx,
         };
@@ -130,6 +141,11 @@ impl NotificationService {
         Ok(service)
     }
 
+    /// Secrets for acting on incidents from notifications
+    pub fn actions(&self) -> &NotificationActions {
+        &self.actions
+    }
+
     /// Queue a notification for sending
     pub async fn queue_notification(&self, notification: Notification) -> Result<()> {
         self.notification_tx
@@ -235,6 +251,161 @@ impl NotificationService {
         self.notify_incident(incident, channels, "Incident resolved").await
     }
 
+    /// Remind the owners of a stale incident
+    ///
+    /// Posts to the default Slack channel and emails assignees that look like
+    /// email addresses.
+    pub async fn notify_incident_stale(&self, incident: &Incident, reason: &str) -> Result<Vec<Uuid>> {
+        let mut channels = Vec::new();
+
+        if self.slack_sender.is_some() {
+            let channel = self.config.slack_default_channel.clone()
+                .unwrap_or_else(|| "#incidents".to_string());
+
+            let owners = if incident.assignees.is_empty() {
+                "unassigned".to_string()
+            } else {
+                incident.assignees.join(", ")
+            };
+
+            channels.push(NotificationChannel::Slack {
+                channel,
+                message: format!(
+                    "⏰ Stale {} incident: {} ({}) - {}",
+                    incident.severity,
+                    incident.title,
+                    owners,
+                    reason
+                ),
+            });
+        }
+
+        let recipients: Vec<String> = incident
+            .assignees
+            .iter()
+            .filter(|assignee| assignee.contains('@'))
+            .cloned()
+            .collect();
+
+        if self.email_sender.is_some() && !recipients.is_empty() {
+            channels.push(NotificationChannel::Email {
+                to: recipients,
+                subject: format!("[Reminder] Stale incident: {}", incident.title),
+                body: format!(
+                    "Incident {} ({:?}, {:?}) needs attention.\n\n{}",
+                    incident.id, incident.severity, incident.state, reason
+                ),
+            });
+        }
+
+        self.notify_incident(incident, channels, reason).await
+    }
+
+    /// Ask the approvers of a paused playbook step for a decision
+    ///
+    /// Posts to the default Slack channel and emails approvers that look like
+    /// email addresses.
+    pub async fn notify_approval_requested(
+        &self,
+        incident: &Incident,
+        execution_id: Uuid,
+        playbook_name: &str,
+        request: &ApprovalRequest,
+    ) -> Result<Vec<Uuid>> {
+        let mut channels = Vec::new();
+
+        let deadline = match request.expires_at {
+            Some(at) => format!(
+                "Decide by {} or the step is {}.",
+                at.to_rfc3339(),
+                match request.on_timeout {
+                    ApprovalTimeoutAction::Reject => "rejected",
+                    ApprovalTimeoutAction::Approve => "approved",
+                }
+            ),
+            None => "The execution waits until someone decides.".to_string(),
+        };
+        let commands = format!(
+            "llm-im-cli playbook approve {} --step {} --by <you>\n\
+             llm-im-cli playbook reject {} --step {} --by <you>",
+            execution_id, request.step_id, execution_id, request.step_id
+        );
+
+        if self.slack_sender.is_some() {
+            let channel = self.config.slack_default_channel.clone()
+                .unwrap_or_else(|| "#incidents".to_string());
+
+            channels.push(NotificationChannel::Slack {
+                channel,
+                message: format!(
+                    "✋ Approval needed: step '{}' of playbook '{}' on {} incident {} ({}). \
+                     Approvers: {}. {}\n{}",
+                    request.step_id,
+                    playbook_name,
+                    incident.severity,
+                    incident.title,
+                    incident.id,
+                    request.approvers.join(", "),
+                    deadline,
+                    commands
+                ),
+            });
+        }
+
+        let recipients: Vec<String> = request
+            .approvers
+            .iter()
+            .filter(|approver| approver.contains('@'))
+            .cloned()
+            .collect();
+
+        if self.email_sender.is_some() && !recipients.is_empty() {
+            channels.push(NotificationChannel::Email {
+                to: recipients,
+                subject: format!(
+                    "[Approval needed] {}: {}",
+                    playbook_name, request.step_id
+                ),
+                body: format!(
+                    "Playbook '{}' is paused at step '{}' for incident {} ({:?}): {}.\n\n{}\n\n{}",
+                    playbook_name,
+                    request.step_id,
+                    incident.id,
+                    incident.severity,
+                    incident.title,
+                    deadline,
+                    commands
+                ),
+            });
+        }
+
+        self.notify_incident(incident, channels, "Approval requested").await
+    }
+
+    /// Post a message to Slack right away, outside the incident queue
+    ///
+    /// Uses the default channel if `channel` is `None`.
+    pub async fn send_slack_message(&self, channel: Option<&str>, text: &str) -> Result<()> {
+        let sender = self.slack_sender.as_ref().ok_or_else(|| {
+            AppError::Configuration("Slack notifications are not configured".to_string())
+        })?;
+        sender.send_text(channel, text).await
+    }
+
+    /// Send an email right away, outside the incident queue
+    pub async fn send_email_message(
+        &self,
+        to: &[String],
+        subject: &str,
+        body: &str,
+        attachments: &[EmailAttachment],
+    ) -> Result<()> {
+        let sender = self.email_sender.as_ref().ok_or_else(|| {
+            AppError::Configuration("Email notifications are not configured".to_string())
+        })?;
+        sender.send_message(to, subject, body, attachments).await
+    }
+
     /// Spawn a notification worker
     fn spawn_worker(&self, worker_id: usize, mut notification_rx: mpsc::Receiver<Notification>) {
         let slack_sender = self.slack_sender.clone();
@@ -425,6 +596,7 @@ mod tests {
             retry_backoff_secs: 5,
             queue_size: 1000,
             worker_threads: 2,
+            actions: Default::default(),
         }
     }
 
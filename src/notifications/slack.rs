@@ -1,5 +1,6 @@
 use crate::error::{AppError, Result};
 use crate::models::{Incident, Notification, NotificationStatus};
+use crate::notifications::actions::OFFERED_ACTIONS;
 use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,7 @@ pub struct SlackSender {
     pub(crate) bot_token: Option<String>,
     pub(crate) client: Client,
     pub(crate) default_channel: Option<String>,
+    pub(crate) action_buttons: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -58,6 +60,19 @@ enum SlackBlock {
     Divider {},
     #[serde(rename = "context")]
     Context { elements: Vec<SlackText> },
+    #[serde(rename = "actions")]
+    Actions { elements: Vec<SlackButton> },
+}
+
+#[derive(Debug, Serialize)]
+struct SlackButton {
+    #[serde(rename = "type")]
+    element_type: String,
+    text: SlackText,
+    action_id: String,
+    value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -91,9 +106,19 @@ impl SlackSender {
             bot_token,
             client,
             default_channel,
+            action_buttons: false,
         })
     }
 
+    /// Add acknowledge, resolve and assign buttons to incident messages
+    ///
+    /// Clicks are posted to the Slack app's interactivity URL, which should
+    /// point at `/v1/notification-actions/slack`.
+    pub fn with_action_buttons(mut self) -> Self {
+        self.action_buttons = true;
+        self
+    }
+
     /// Send a notification to Slack
     pub async fn send(&self, notification: &mut Notification, incident: &Incident) -> Result<()> {
         // Extract channel and message from notification
@@ -150,6 +175,28 @@ impl SlackSender {
         }
     }
 
+    /// Send a plain message that is not about a single incident
+    pub async fn send_text(&self, channel: Option<&str>, text: &str) -> Result<()> {
+        let payload = SlackWebhookPayload {
+            text: text.to_string(),
+            channel: channel
+                .map(String::from)
+                .or_else(|| self.default_channel.clone()),
+            attachments: None,
+            blocks: None,
+        };
+
+        if let Some(webhook_url) = &self.webhook_url {
+            self.send_via_webhook(webhook_url, &payload).await
+        } else if let Some(bot_token) = &self.bot_token {
+            self.send_via_api(bot_token, &payload).await
+        } else {
+            Err(AppError::Configuration(
+                "No Slack webhook URL or bot token configured".to_string(),
+            ))
+        }
+    }
+
     /// Build Slack message payload with rich formatting
     fn build_slack_payload(
         &self,
@@ -174,7 +221,7 @@ impl SlackSender {
         };
 
         // Use blocks for modern Slack formatting
-        let blocks = vec![
+        let mut blocks = vec![
             SlackBlock::Header {
                 text: SlackText {
                     text_type: "plain_text".to_string(),
@@ -221,6 +268,24 @@ impl SlackSender {
             },
         ];
 
+        if self.action_buttons && incident.is_active() {
+            blocks.push(SlackBlock::Actions {
+                elements: OFFERED_ACTIONS
+                    .iter()
+                    .map(|(action, label)| SlackButton {
+                        element_type: "button".to_string(),
+                        text: SlackText {
+                            text_type: "plain_text".to_string(),
+                            text: label.to_string(),
+                        },
+                        action_id: action.to_string(),
+                        value: incident.id.to_string(),
+                        style: (*action == "acknowledge").then(|| "primary".to_string()),
+                    })
+                    .collect(),
+            });
+        }
+
         // Fallback attachment for older clients
         let attachment = SlackAttachment {
             color: severity_color.to_string(),
@@ -359,6 +424,47 @@ mod tests {
         assert!(payload.attachments.is_some());
     }
 
+    #[test]
+    fn test_build_slack_payload_with_action_buttons() {
+        let sender = SlackSender::new(Some("https://hooks.slack.com/test".to_string()), None, None)
+            .unwrap()
+            .with_action_buttons();
+
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test Incident".to_string(),
+            "Test description".to_string(),
+            Severity::P1,
+            IncidentType::Infrastructure,
+        );
+
+        let payload = sender
+            .build_slack_payload(&incident, &None, "Test")
+            .unwrap();
+        let json = serde_json::to_value(&payload).unwrap();
+        let actions = json["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|block| block["type"] == "actions")
+            .unwrap();
+        assert_eq!(actions["elements"].as_array().unwrap().len(), 3);
+        assert_eq!(actions["elements"][0]["action_id"], "acknowledge");
+        assert_eq!(actions["elements"][0]["value"], incident.id.to_string());
+
+        // Resolved incidents have nothing left to act on
+        incident.state = IncidentState::Resolved;
+        let payload = sender
+            .build_slack_payload(&incident, &None, "Test")
+            .unwrap();
+        let json = serde_json::to_value(&payload).unwrap();
+        assert!(!json["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|block| block["type"] == "actions"));
+    }
+
     #[test]
     fn test_severity_colors() {
         let sender = SlackSender::new(
@@ -1,8 +1,12 @@
 use crate::error::{AppError, Result};
-use crate::models::{Action, ActionType, NotificationChannel};
+use crate::models::{Action, ActionType, EventType, NotificationChannel, TimelineEvent};
 use crate::notifications::NotificationService;
+use crate::playbooks::remediation::{
+    RemediationOperation, RemediationOutcome, RemediationRequest, RemediationTargets,
+};
+use crate::playbooks::script::ScriptSandbox;
 use crate::playbooks::ExecutionContext;
-use crate::state::IncidentStore;
+use crate::state::{modify_incident, IncidentStore};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde_json::Value as JsonValue;
@@ -10,7 +14,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Action executor trait
 #[async_trait]
@@ -76,14 +80,33 @@ impl ActionExecutorRegistry {
 }
 
 /// Create default action executor registry with all standard executors
+///
+/// Remediation and script actions are registered but fail until targets and
+/// script allow-lists are configured; use [`create_registry`] to provide them.
 pub fn create_default_registry(
     notification_service: Option<Arc<NotificationService>>,
     store: Arc<dyn IncidentStore>,
+) -> ActionExecutorRegistry {
+    create_registry(
+        notification_service,
+        store,
+        RemediationTargets::new(),
+        ScriptSandbox::default(),
+    )
+}
+
+/// Create the standard registry with remediation actions applied to
+/// `targets` and scripts run in `scripts`
+pub fn create_registry(
+    notification_service: Option<Arc<NotificationService>>,
+    store: Arc<dyn IncidentStore>,
+    targets: RemediationTargets,
+    scripts: ScriptSandbox,
 ) -> ActionExecutorRegistry {
     let mut registry = ActionExecutorRegistry::new();
 
     // Notification actions
-    if let Some(notif_service) = notification_service {
+    if let Some(notif_service) = notification_service.clone() {
         registry.register(
             ActionType::Slack,
             Arc::new(SlackActionExecutor::new(notif_service.clone())),
@@ -114,7 +137,28 @@ pub fn create_default_registry(
     );
     registry.register(
         ActionType::SeverityDecrease,
-        Arc::new(SeverityChangeActionExecutor::new(store, false)),
+        Arc::new(SeverityChangeActionExecutor::new(store.clone(), false)),
+    );
+    registry.register(
+        ActionType::CreateWarRoom,
+        Arc::new(CreateWarRoomActionExecutor::new(notification_service, store.clone())),
+    );
+    registry.register(
+        ActionType::SchedulePostmortem,
+        Arc::new(SchedulePostmortemActionExecutor::new(store.clone())),
+    );
+
+    // Remediation actions
+    for operation in RemediationOperation::ALL {
+        registry.register(
+            operation.action_type(),
+            Arc::new(RemediationActionExecutor::new(operation, targets.clone())),
+        );
+    }
+    registry.register(ActionType::RunScript, Arc::new(RunScriptActionExecutor::new(scripts)));
+    registry.register(
+        ActionType::VerifyResolution,
+        Arc::new(VerifyResolutionActionExecutor::new(targets, store)),
     );
 
     // Generic actions
@@ -123,6 +167,17 @@ pub fn create_default_registry(
     registry
 }
 
+/// Create a registry in which every action type is a
+/// [`RecordingActionExecutor`], for dry runs
+pub fn create_recording_registry() -> ActionExecutorRegistry {
+    let mut registry = ActionExecutorRegistry::new();
+    let recorder: Arc<dyn ActionExecutor> = Arc::new(RecordingActionExecutor);
+    for action_type in ActionType::ALL {
+        registry.register(action_type, recorder.clone());
+    }
+    registry
+}
+
 // ==================== Notification Action Executors ====================
 
 /// Slack notification executor
@@ -401,15 +456,19 @@ impl ActionExecutor for IncidentResolveActionExecutor {
             .and_then(|v| v.as_str())
             .unwrap_or("Resolved by playbook");
 
-        let mut incident = context.incident().clone();
-        incident.resolve(
-            "playbook-engine".to_string(),
-            crate::models::ResolutionMethod::Automated,
-            notes.to_string(),
-            params.get("root_cause").and_then(|v| v.as_str()).map(|s| s.to_string()),
-        );
+        let root_cause = params.get("root_cause").and_then(|v| v.as_str()).map(|s| s.to_string());
 
-        self.store.update_incident(&incident).await?;
+        // Work on the stored copy; the context snapshot may be stale by now
+        let incident = modify_incident(self.store.as_ref(), &context.incident().id, |incident| {
+            incident.resolve(
+                "playbook-engine".to_string(),
+                crate::models::ResolutionMethod::Automated,
+                notes.to_string(),
+                root_cause.clone(),
+            );
+            Ok(())
+        })
+        .await?;
 
         let mut output = HashMap::new();
         output.insert("incident_resolved".to_string(), JsonValue::Bool(true));
@@ -431,45 +490,414 @@ impl SeverityChangeActionExecutor {
 
 #[async_trait]
 impl ActionExecutor for SeverityChangeActionExecutor {
-    async fn execute(&self, _action: &Action, _context: &mut ExecutionContext) -> Result<ActionResult> {
-        let mut incident = _context.incident().clone();
-
-        let old_severity = incident.severity.clone();
-        let new_severity = if self.increase {
-            match incident.severity {
-                crate::models::Severity::P4 => crate::models::Severity::P3,
-                crate::models::Severity::P3 => crate::models::Severity::P2,
-                crate::models::Severity::P2 => crate::models::Severity::P1,
-                crate::models::Severity::P1 => crate::models::Severity::P0,
-                crate::models::Severity::P0 => crate::models::Severity::P0, // Already max
+    async fn execute(&self, _action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let increase = self.increase;
+        let mut change = None;
+
+        // Step from the stored severity so concurrent changes are not lost
+        modify_incident(self.store.as_ref(), &context.incident().id, |incident| {
+            let old_severity = incident.severity.clone();
+            let new_severity = if increase {
+                match incident.severity {
+                    crate::models::Severity::P4 => crate::models::Severity::P3,
+                    crate::models::Severity::P3 => crate::models::Severity::P2,
+                    crate::models::Severity::P2 => crate::models::Severity::P1,
+                    crate::models::Severity::P1 => crate::models::Severity::P0,
+                    crate::models::Severity::P0 => crate::models::Severity::P0, // Already max
+                }
+            } else {
+                match incident.severity {
+                    crate::models::Severity::P0 => crate::models::Severity::P1,
+                    crate::models::Severity::P1 => crate::models::Severity::P2,
+                    crate::models::Severity::P2 => crate::models::Severity::P3,
+                    crate::models::Severity::P3 => crate::models::Severity::P4,
+                    crate::models::Severity::P4 => crate::models::Severity::P4, // Already min
+                }
+            };
+
+            if old_severity != new_severity {
+                incident.severity = new_severity.clone();
+                incident.add_timeline_event(crate::models::TimelineEvent {
+                    timestamp: chrono::Utc::now(),
+                    event_type: crate::models::EventType::SeverityChanged,
+                    actor: "playbook-engine".to_string(),
+                    description: format!("Severity changed from {:?} to {:?}", old_severity, new_severity),
+                    metadata: HashMap::new(),
+                });
             }
+
+            change = Some((old_severity, new_severity));
+            Ok(())
+        })
+        .await?;
+
+        let (old_severity, new_severity) = change.expect("modify_incident applies the change");
+
+        let mut output = HashMap::new();
+        output.insert("old_severity".to_string(), JsonValue::String(format!("{:?}", old_severity)));
+        output.insert("new_severity".to_string(), JsonValue::String(format!("{:?}", new_severity)));
+        output.insert("changed".to_string(), JsonValue::Bool(old_severity != new_severity));
+        Ok(ActionResult::success(output))
+    }
+}
+
+// ==================== Remediation Executors ====================
+
+/// Convert a remediation outcome into an action result
+fn remediation_result(
+    target: &str,
+    operation: RemediationOperation,
+    outcome: RemediationOutcome,
+) -> ActionResult {
+    let mut output = outcome.output;
+    output.insert("target".to_string(), JsonValue::String(target.to_string()));
+    output.insert(
+        "operation".to_string(),
+        JsonValue::String(operation.as_str().to_string()),
+    );
+
+    ActionResult {
+        success: outcome.success,
+        output,
+        error: if outcome.success {
+            None
         } else {
-            match incident.severity {
-                crate::models::Severity::P0 => crate::models::Severity::P1,
-                crate::models::Severity::P1 => crate::models::Severity::P2,
-                crate::models::Severity::P2 => crate::models::Severity::P3,
-                crate::models::Severity::P3 => crate::models::Severity::P4,
-                crate::models::Severity::P4 => crate::models::Severity::P4, // Already min
+            Some(
+                outcome
+                    .message
+                    .unwrap_or_else(|| format!("{} failed on target '{}'", operation, target)),
+            )
+        },
+    }
+}
+
+/// Applies one remediation operation to the target named by the `target`
+/// parameter, or the default target
+struct RemediationActionExecutor {
+    operation: RemediationOperation,
+    targets: RemediationTargets,
+}
+
+impl RemediationActionExecutor {
+    fn new(operation: RemediationOperation, targets: RemediationTargets) -> Self {
+        Self { operation, targets }
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for RemediationActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+
+        let target = self
+            .targets
+            .resolve(params.get("target").and_then(|v| v.as_str()))?;
+        let request = RemediationRequest::new(self.operation, context.incident().id, params);
+
+        info!(
+            incident_id = %request.incident_id,
+            operation = %self.operation,
+            service = ?request.service,
+            remediation_target = %target.name(),
+            "Applying remediation"
+        );
+
+        let outcome = target.apply(&request).await?;
+        Ok(remediation_result(target.name(), self.operation, outcome))
+    }
+}
+
+/// Runs health checks until the service is healthy or the attempts run out,
+/// optionally resolving the incident once it is
+struct VerifyResolutionActionExecutor {
+    targets: RemediationTargets,
+    store: Arc<dyn IncidentStore>,
+}
+
+impl VerifyResolutionActionExecutor {
+    fn new(targets: RemediationTargets, store: Arc<dyn IncidentStore>) -> Self {
+        Self { targets, store }
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for VerifyResolutionActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+
+        let attempts = params
+            .get("attempts")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(3)
+            .max(1);
+        let interval_secs = params
+            .get("interval_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10);
+        let resolve = params
+            .get("resolve")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let target = self
+            .targets
+            .resolve(params.get("target").and_then(|v| v.as_str()))?;
+        let mut request_params = params;
+        for key in ["attempts", "interval_secs", "resolve"] {
+            request_params.remove(key);
+        }
+        let request = RemediationRequest::new(
+            RemediationOperation::HealthCheck,
+            context.incident().id,
+            request_params,
+        );
+
+        let mut outcome = target.apply(&request).await?;
+        let mut checks = 1;
+        while !outcome.success && checks < attempts {
+            sleep(Duration::from_secs(interval_secs)).await;
+            outcome = target.apply(&request).await?;
+            checks += 1;
+        }
+
+        let verified = outcome.success;
+        let mut result = remediation_result(target.name(), RemediationOperation::HealthCheck, outcome);
+        result.output.insert("verified".to_string(), JsonValue::Bool(verified));
+        result.output.insert("checks".to_string(), JsonValue::Number(checks.into()));
+
+        if !verified {
+            result.error = Some(format!(
+                "Resolution not verified after {} health checks: {}",
+                checks,
+                result.error.unwrap_or_default()
+            ));
+            return Ok(result);
+        }
+
+        if resolve {
+            modify_incident(self.store.as_ref(), &context.incident().id, |incident| {
+                if incident.resolution.is_none() {
+                    incident.resolve(
+                        "playbook-engine".to_string(),
+                        crate::models::ResolutionMethod::Automated,
+                        format!("Resolution verified by {} health checks", checks),
+                        None,
+                    );
+                }
+                Ok(())
+            })
+            .await?;
+        }
+        result.output.insert("incident_resolved".to_string(), JsonValue::Bool(resolve));
+
+        Ok(result)
+    }
+}
+
+/// Runs a script from the allow-listed paths in the script sandbox
+///
+/// A non-zero exit fails the step unless `fail_on_error` is false, in which
+/// case later steps can inspect `exit_code`. A timeout always fails it.
+struct RunScriptActionExecutor {
+    sandbox: ScriptSandbox,
+}
+
+impl RunScriptActionExecutor {
+    fn new(sandbox: ScriptSandbox) -> Self {
+        Self { sandbox }
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for RunScriptActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+
+        let invocation = self.sandbox.prepare(&params)?;
+        let fail_on_error = params
+            .get("fail_on_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true);
+
+        info!(
+            incident_id = %context.incident().id,
+            interpreter = %invocation.interpreter,
+            script = %invocation.script.display(),
+            "Running playbook script"
+        );
+
+        let output = match self.sandbox.run(&invocation).await {
+            Ok(output) => output,
+            Err(e) => return Ok(ActionResult::failure(e.to_string())),
+        };
+
+        let success = output.succeeded() || (!fail_on_error && !output.timed_out);
+        let error = (!success).then(|| output.failure_reason(&invocation.script));
+        Ok(ActionResult {
+            success,
+            output: output.into_output(),
+            error,
+        })
+    }
+}
+
+// ==================== Coordination Executors ====================
+
+/// Opens a war room channel for the incident and records it on the incident
+struct CreateWarRoomActionExecutor {
+    notification_service: Option<Arc<NotificationService>>,
+    store: Arc<dyn IncidentStore>,
+}
+
+impl CreateWarRoomActionExecutor {
+    fn new(
+        notification_service: Option<Arc<NotificationService>>,
+        store: Arc<dyn IncidentStore>,
+    ) -> Self {
+        Self {
+            notification_service,
+            store,
+        }
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for CreateWarRoomActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+        let incident = context.incident();
+
+        let channel = params
+            .get("channel")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| {
+                format!("#inc-{}", &incident.id.simple().to_string()[..8])
+            });
+        let participants: Vec<String> = params
+            .get("participants")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let message = params
+            .get("message")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| {
+                format!(
+                    "War room opened for {:?} incident: {} ({})",
+                    incident.severity, incident.title, incident.id
+                )
+            });
+
+        let announced = match &self.notification_service {
+            Some(service) => {
+                let text = if participants.is_empty() {
+                    message.clone()
+                } else {
+                    format!("{}\nParticipants: {}", message, participants.join(", "))
+                };
+                if let Err(e) = service.send_slack_message(Some(&channel), &text).await {
+                    return Ok(ActionResult::failure(format!(
+                        "Failed to announce war room in {}: {}",
+                        channel, e
+                    )));
+                }
+                true
+            }
+            None => {
+                warn!(incident_id = %incident.id, "No notification service; war room not announced");
+                false
             }
         };
 
-        if old_severity != new_severity {
-            incident.severity = new_severity.clone();
-            incident.add_timeline_event(crate::models::TimelineEvent {
+        modify_incident(self.store.as_ref(), &incident.id, |incident| {
+            incident.labels.insert("war_room".to_string(), channel.clone());
+            let mut metadata = HashMap::new();
+            metadata.insert("channel".to_string(), channel.clone());
+            if !participants.is_empty() {
+                metadata.insert("participants".to_string(), participants.join(","));
+            }
+            incident.add_timeline_event(TimelineEvent {
                 timestamp: chrono::Utc::now(),
-                event_type: crate::models::EventType::SeverityChanged,
+                event_type: EventType::ActionExecuted,
                 actor: "playbook-engine".to_string(),
-                description: format!("Severity changed from {:?} to {:?}", old_severity, new_severity),
-                metadata: HashMap::new(),
+                description: format!("War room opened in {}", channel),
+                metadata,
             });
+            Ok(())
+        })
+        .await?;
 
-            self.store.update_incident(&incident).await?;
-        }
+        let mut output = HashMap::new();
+        output.insert("channel".to_string(), JsonValue::String(channel));
+        output.insert("announced".to_string(), JsonValue::Bool(announced));
+        output.insert(
+            "participants".to_string(),
+            JsonValue::Array(participants.into_iter().map(JsonValue::String).collect()),
+        );
+        Ok(ActionResult::success(output))
+    }
+}
+
+/// Records a postmortem due date and owner on the incident
+struct SchedulePostmortemActionExecutor {
+    store: Arc<dyn IncidentStore>,
+}
+
+impl SchedulePostmortemActionExecutor {
+    fn new(store: Arc<dyn IncidentStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for SchedulePostmortemActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+
+        let days = params.get("days").and_then(|v| v.as_i64()).unwrap_or(5);
+        let owner = params
+            .get("owner")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let due_date = (chrono::Utc::now() + chrono::Duration::days(days))
+            .format("%Y-%m-%d")
+            .to_string();
+
+        modify_incident(self.store.as_ref(), &context.incident().id, |incident| {
+            incident
+                .labels
+                .insert("postmortem_due".to_string(), due_date.clone());
+            let mut metadata = HashMap::new();
+            metadata.insert("due_date".to_string(), due_date.clone());
+            if let Some(owner) = &owner {
+                incident
+                    .labels
+                    .insert("postmortem_owner".to_string(), owner.clone());
+                metadata.insert("owner".to_string(), owner.clone());
+            }
+            incident.add_timeline_event(TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: EventType::ActionExecuted,
+                actor: "playbook-engine".to_string(),
+                description: format!("Postmortem scheduled for {}", due_date),
+                metadata,
+            });
+            Ok(())
+        })
+        .await?;
 
         let mut output = HashMap::new();
-        output.insert("old_severity".to_string(), JsonValue::String(format!("{:?}", old_severity)));
-        output.insert("new_severity".to_string(), JsonValue::String(format!("{:?}", new_severity)));
-        output.insert("changed".to_string(), JsonValue::Bool(old_severity != new_severity));
+        output.insert("due_date".to_string(), JsonValue::String(due_date));
+        output.insert(
+            "owner".to_string(),
+            owner.map(JsonValue::String).unwrap_or(JsonValue::Null),
+        );
         Ok(ActionResult::success(output))
     }
 }
@@ -553,10 +981,31 @@ impl ActionExecutor for HttpRequestActionExecutor {
     }
 }
 
+// ==================== Dry Run ====================
+
+/// Stand-in for any action in a dry run
+///
+/// Resolves the action's templated parameters and returns them as its
+/// output (`dry_run`, `parameters`) without acting.
+pub struct RecordingActionExecutor;
+
+#[async_trait]
+impl ActionExecutor for RecordingActionExecutor {
+    async fn execute(&self, action: &Action, context: &mut ExecutionContext) -> Result<ActionResult> {
+        let params = context.substitute_parameters(&action.parameters);
+
+        let mut output = HashMap::new();
+        output.insert("dry_run".to_string(), JsonValue::Bool(true));
+        output.insert("parameters".to_string(), serde_json::to_value(params)?);
+        Ok(ActionResult::success(output))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{Incident, IncidentType, Severity};
+    use crate::playbooks::remediation::RecordingRemediationTarget;
     use crate::state::InMemoryStore;
 
     fn create_test_incident() -> Incident {
@@ -625,4 +1074,209 @@ mod tests {
         let updated = store.get_incident(&incident.id).await.unwrap().unwrap();
         assert!(updated.resolution.is_some());
     }
+
+    fn action(action_type: ActionType, params: JsonValue) -> Action {
+        Action {
+            action_type,
+            parameters: serde_json::from_value(params).unwrap(),
+            on_success: None,
+            on_failure: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remediation_action_uses_named_target() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident.clone());
+
+        let cluster = Arc::new(RecordingRemediationTarget::new("cluster"));
+        let local = Arc::new(RecordingRemediationTarget::new("local"));
+        let targets = RemediationTargets::new()
+            .with_target(cluster.clone())
+            .with_target(local.clone())
+            .with_default("cluster");
+
+        let executor = RemediationActionExecutor::new(RemediationOperation::ScaleHorizontal, targets);
+        let result = executor
+            .execute(
+                &action(
+                    ActionType::ScaleHorizontal,
+                    serde_json::json!({"service": "api", "replicas": 6, "target": "local"}),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.output["target"], serde_json::json!("local"));
+        assert!(cluster.requests().is_empty());
+
+        let requests = local.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].operation, RemediationOperation::ScaleHorizontal);
+        assert_eq!(requests[0].service.as_deref(), Some("api"));
+        assert_eq!(requests[0].incident_id, incident.id);
+        assert_eq!(requests[0].param_i64("replicas"), Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_remediation_failure_fails_step() {
+        let mut context = ExecutionContext::new(create_test_incident());
+        let target = RecordingRemediationTarget::new("mock").with_outcome(
+            RemediationOperation::ServiceRestart,
+            RemediationOutcome::failed("deployment not found"),
+        );
+        let targets = RemediationTargets::new().with_target(Arc::new(target));
+
+        let executor = RemediationActionExecutor::new(RemediationOperation::ServiceRestart, targets);
+        let result = executor
+            .execute(
+                &action(ActionType::ServiceRestart, serde_json::json!({"service": "api"})),
+                &mut context,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("deployment not found"));
+    }
+
+    #[tokio::test]
+    async fn test_verify_resolution_retries_and_resolves() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident.clone());
+        let store = Arc::new(InMemoryStore::new());
+        store.save_incident(&incident).await.unwrap();
+
+        let target = Arc::new(
+            RecordingRemediationTarget::new("mock")
+                .with_outcome(
+                    RemediationOperation::HealthCheck,
+                    RemediationOutcome::failed("1/3 replicas ready"),
+                )
+                .with_outcome(
+                    RemediationOperation::HealthCheck,
+                    RemediationOutcome::succeeded(HashMap::new()),
+                ),
+        );
+        let targets = RemediationTargets::new().with_target(target.clone());
+
+        let executor = VerifyResolutionActionExecutor::new(targets, store.clone());
+        let result = executor
+            .execute(
+                &action(
+                    ActionType::VerifyResolution,
+                    serde_json::json!({
+                        "service": "api",
+                        "attempts": 3,
+                        "interval_secs": 0,
+                        "resolve": true,
+                    }),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.output.get("checks").unwrap().as_u64(), Some(2));
+        assert_eq!(target.requests().len(), 2);
+        assert!(!target.requests()[0].parameters.contains_key("attempts"));
+
+        let updated = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert!(updated.resolution.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_verify_resolution_gives_up() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident.clone());
+        let store = Arc::new(InMemoryStore::new());
+        store.save_incident(&incident).await.unwrap();
+
+        let target = RecordingRemediationTarget::new("mock").with_outcome(
+            RemediationOperation::HealthCheck,
+            RemediationOutcome::failed("still failing"),
+        );
+        let targets = RemediationTargets::new().with_target(Arc::new(target));
+
+        let executor = VerifyResolutionActionExecutor::new(targets, store.clone());
+        let result = executor
+            .execute(
+                &action(
+                    ActionType::VerifyResolution,
+                    serde_json::json!({"service": "api", "attempts": 2, "interval_secs": 0, "resolve": true}),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert_eq!(result.output.get("verified").unwrap(), &JsonValue::Bool(false));
+        let updated = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert!(updated.resolution.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_war_room_and_postmortem_are_recorded() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident.clone());
+        let store = Arc::new(InMemoryStore::new());
+        store.save_incident(&incident).await.unwrap();
+
+        let war_room = CreateWarRoomActionExecutor::new(None, store.clone());
+        let result = war_room
+            .execute(
+                &action(
+                    ActionType::CreateWarRoom,
+                    serde_json::json!({"channel": "#inc-db", "participants": ["alice", "bob"]}),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.get("announced").unwrap(), &JsonValue::Bool(false));
+
+        let postmortem = SchedulePostmortemActionExecutor::new(store.clone());
+        let result = postmortem
+            .execute(
+                &action(
+                    ActionType::SchedulePostmortem,
+                    serde_json::json!({"days": 3, "owner": "alice"}),
+                ),
+                &mut context,
+            )
+            .await
+            .unwrap();
+        assert!(result.success);
+
+        let updated = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(updated.labels.get("war_room").map(String::as_str), Some("#inc-db"));
+        assert_eq!(
+            updated.labels.get("postmortem_due"),
+            result.output.get("due_date").and_then(|v| v.as_str()).map(String::from).as_ref()
+        );
+        assert_eq!(updated.labels.get("postmortem_owner").map(String::as_str), Some("alice"));
+        assert_eq!(updated.timeline.len(), incident.timeline.len() + 2);
+    }
+
+    #[tokio::test]
+    async fn test_default_registry_registers_remediation_actions() {
+        let store = Arc::new(InMemoryStore::new());
+        let registry = create_default_registry(None, store);
+        let mut context = ExecutionContext::new(create_test_incident());
+
+        // Without targets the executor runs and reports the missing configuration
+        let error = registry
+            .execute(
+                &action(ActionType::ServiceRestart, serde_json::json!({"service": "api"})),
+                &mut context,
+            )
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("No remediation targets"));
+    }
 }
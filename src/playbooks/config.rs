@@ -0,0 +1,159 @@
+//! Playbook configuration
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Playbook engine configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaybookConfig {
+    /// Systems remediation actions are applied to
+    pub remediation: RemediationConfig,
+
+    /// Sandbox for `run_script` actions
+    pub scripts: ScriptConfig,
+}
+
+/// Remediation targets available to playbook actions
+///
+/// An action picks a target with its `target` parameter and falls back to
+/// `default_target` (or the only configured target) when it has none.
+///
+/// ```toml
+/// [playbooks.remediation]
+/// default_target = "cluster"
+///
+/// [playbooks.remediation.targets.cluster]
+/// kind = "kubernetes"
+/// api_url = "https://kubernetes.default.svc"
+/// namespace = "production"
+///
+/// [playbooks.remediation.targets.control_plane]
+/// kind = "http"
+/// base_url = "https://remediation.internal/api/v1"
+///
+/// [playbooks.remediation.targets.local]
+/// kind = "process"
+/// commands = { service_restart = ["systemctl", "restart", "{service}"] }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemediationConfig {
+    /// Target used when an action does not name one
+    pub default_target: Option<String>,
+
+    /// Targets by name
+    pub targets: HashMap<String, RemediationTargetConfig>,
+}
+
+/// A single remediation target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RemediationTargetConfig {
+    /// Control plane exposing one `POST {base_url}/{operation}` endpoint per operation
+    Http {
+        base_url: String,
+        #[serde(default)]
+        token: Option<String>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+
+    /// Local commands, one argv template per operation
+    ///
+    /// Arguments may contain `{service}`, `{incident_id}` and `{<parameter>}`
+    /// placeholders that are filled from the action.
+    Process {
+        commands: HashMap<String, Vec<String>>,
+        #[serde(default)]
+        working_dir: Option<PathBuf>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+
+    /// Kubernetes API server; services map to deployments
+    Kubernetes {
+        api_url: String,
+        #[serde(default = "default_namespace")]
+        namespace: String,
+        #[serde(default)]
+        token: Option<String>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_namespace() -> String {
+    "default".to_string()
+}
+
+/// Sandbox for `run_script` actions
+///
+/// Scripts only run when both their interpreter and their location are
+/// allowed, so the defaults disable the action.
+///
+/// ```toml
+/// [playbooks.scripts]
+/// allowed_interpreters = ["/bin/bash", "/usr/bin/python3"]
+/// allowed_paths = ["/opt/runbooks"]
+/// env = ["PATH=/usr/local/bin:/usr/bin:/bin", "RUNBOOK_ENV=production"]
+/// inherit_env = ["HOME"]
+/// max_memory_mb = 512
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptConfig {
+    /// Interpreters scripts may be run with, matched exactly
+    pub allowed_interpreters: Vec<String>,
+
+    /// Interpreter used when an action does not name one
+    pub default_interpreter: Option<String>,
+
+    /// Directories scripts and working directories must be inside
+    pub allowed_paths: Vec<PathBuf>,
+
+    /// Working directory when an action does not set one; defaults to the
+    /// script's directory
+    pub working_dir: Option<PathBuf>,
+
+    /// `KEY=VALUE` pairs every script gets; nothing else is passed through
+    /// unless listed in `inherit_env`
+    pub env: Vec<String>,
+
+    /// Variables copied from the incident manager's own environment
+    pub inherit_env: Vec<String>,
+
+    /// Timeout when an action does not set `timeout_secs`
+    pub default_timeout_secs: u64,
+
+    /// Largest `timeout_secs` an action may ask for
+    pub max_timeout_secs: u64,
+
+    /// Bytes of stdout and of stderr kept; the rest is discarded
+    pub max_output_bytes: usize,
+
+    /// Address space limit for the script process
+    pub max_memory_mb: Option<u64>,
+}
+
+impl Default for ScriptConfig {
+    fn default() -> Self {
+        Self {
+            allowed_interpreters: Vec::new(),
+            default_interpreter: None,
+            allowed_paths: Vec::new(),
+            working_dir: None,
+            env: vec!["PATH=/usr/local/bin:/usr/bin:/bin".to_string()],
+            inherit_env: Vec::new(),
+            default_timeout_secs: 300,
+            max_timeout_secs: 3600,
+            max_output_bytes: 64 * 1024,
+            max_memory_mb: Some(512),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::{AppError, Result};
 use crate::models::Incident;
+use crate::playbooks::expression::{Expression, Path, PathResolver, Segment};
 use serde_json::Value as JsonValue;
 use std::collections::HashMap;
 
@@ -67,23 +68,30 @@ impl ExecutionContext {
     }
 
     /// Substitute variables in a string
-    /// Supports {{variable_name}} syntax
+    /// Supports {{variable_name}} and the condition path syntax, e.g.
+    /// {{incident.severity}} or {{steps.collect.output.error_rate}}
     pub fn substitute_string(&self, template: &str) -> String {
-        let mut result = template.to_string();
-
-        // Find and replace all {{variable}} patterns
-        for (key, value) in &self.variables {
-            let placeholder = format!("{{{{{}}}}}", key);
-            let replacement = match value {
-                JsonValue::String(s) => s.clone(),
-                JsonValue::Number(n) => n.to_string(),
-                JsonValue::Bool(b) => b.to_string(),
-                JsonValue::Null => "null".to_string(),
-                _ => serde_json::to_string(value).unwrap_or_else(|_| "".to_string()),
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
             };
-            result = result.replace(&placeholder, &replacement);
+            let placeholder = &rest[start..start + len + 4];
+            result.push_str(&rest[..start]);
+
+            // Unknown placeholders are left as written
+            let value = Path::parse(&placeholder[2..placeholder.len() - 2])
+                .and_then(|path| self.resolve(&path));
+            match value {
+                Ok(value) => result.push_str(&render(&value)),
+                Err(_) => result.push_str(placeholder),
+            }
+            rest = &rest[start + len + 4..];
         }
 
+        result.push_str(rest);
         result
     }
 
@@ -118,103 +126,98 @@ impl ExecutionContext {
         }
     }
 
-    /// Evaluate a simple condition expression
-    /// Supports: variable == value, variable != value, variable > value, variable < value
+    /// Evaluate a condition expression
+    ///
+    /// See [`Expression`] for the syntax; an empty condition is true.
     pub fn evaluate_condition(&self, condition: &str) -> Result<bool> {
-        let condition = condition.trim();
-
-        if condition.is_empty() {
+        if condition.trim().is_empty() {
             return Ok(true);
         }
+        Expression::parse(condition)?.evaluate_bool(self)
+    }
 
-        // Parse condition
-        if let Some((left, right)) = condition.split_once("==") {
-            let left_val = self.resolve_value(left.trim())?;
-            let right_val = self.resolve_value(right.trim())?;
-            return Ok(left_val == right_val);
-        }
-
-        if let Some((left, right)) = condition.split_once("!=") {
-            let left_val = self.resolve_value(left.trim())?;
-            let right_val = self.resolve_value(right.trim())?;
-            return Ok(left_val != right_val);
-        }
-
-        if let Some((left, right)) = condition.split_once(">=") {
-            return self.compare_numeric(left.trim(), right.trim(), |a, b| a >= b);
-        }
-
-        if let Some((left, right)) = condition.split_once("<=") {
-            return self.compare_numeric(left.trim(), right.trim(), |a, b| a <= b);
-        }
+    /// Step output with per-action keys (`action_0.error_rate`) also
+    /// reachable as nested objects and, for the first action that set them,
+    /// without the action prefix
+    fn step_output_value(output: &HashMap<String, JsonValue>) -> JsonValue {
+        let mut keys: Vec<&String> = output.keys().collect();
+        keys.sort();
 
-        if let Some((left, right)) = condition.split_once(">") {
-            return self.compare_numeric(left.trim(), right.trim(), |a, b| a > b);
+        let mut view = serde_json::Map::new();
+        for key in &keys {
+            view.insert((*key).clone(), output[*key].clone());
         }
-
-        if let Some((left, right)) = condition.split_once("<") {
-            return self.compare_numeric(left.trim(), right.trim(), |a, b| a < b);
-        }
-
-        // If no operator, treat as boolean variable
-        let value = self.resolve_value(condition)?;
-        match value {
-            JsonValue::Bool(b) => Ok(b),
-            JsonValue::String(s) => Ok(s == "true"),
-            _ => Ok(false),
+        for key in keys {
+            let Some((action, field)) = key.split_once('.') else {
+                continue;
+            };
+            if !action.starts_with("action_") {
+                continue;
+            }
+            if let JsonValue::Object(nested) = view
+                .entry(action.to_string())
+                .or_insert_with(|| JsonValue::Object(serde_json::Map::new()))
+            {
+                nested.insert(field.to_string(), output[key].clone());
+            }
+            view.entry(field.to_string()).or_insert_with(|| output[key].clone());
         }
+        JsonValue::Object(view)
     }
+}
 
-    /// Resolve a value (variable or literal)
-    fn resolve_value(&self, expr: &str) -> Result<JsonValue> {
-        let expr = expr.trim();
+impl PathResolver for ExecutionContext {
+    fn resolve(&self, path: &Path) -> Result<JsonValue> {
+        let segments = path.segments();
+        let variable = || match self.variables.get(path.root()) {
+            Some(value) => Ok(Path::walk(value, &segments[1..])),
+            None => Err(AppError::Validation(format!(
+                "Variable '{}' not found",
+                path.root()
+            ))),
+        };
 
-        // Check if it's a variable reference
-        if let Some(var_name) = expr.strip_prefix('$') {
-            if let Some(value) = self.get_variable(var_name) {
-                return Ok(value.clone());
-            }
-            return Err(AppError::Validation(format!("Variable '{}' not found", var_name)));
+        if path.is_variable() {
+            return variable();
         }
 
-        // Try to parse as JSON
-        if let Ok(value) = serde_json::from_str(expr) {
-            return Ok(value);
+        match path.root() {
+            "incident" => {
+                let incident = serde_json::to_value(&self.incident)?;
+                Ok(Path::walk(&incident, &segments[1..]))
+            }
+            "steps" => {
+                let output = match segments.get(1) {
+                    Some(Segment::Key(id)) => self.step_outputs.get(id),
+                    _ => None,
+                };
+                let Some(output) = output else {
+                    return Ok(JsonValue::Null);
+                };
+                let mut step = serde_json::Map::new();
+                step.insert("output".to_string(), Self::step_output_value(output));
+                Ok(Path::walk(&JsonValue::Object(step), &segments[2..]))
+            }
+            "vars" | "variables" => {
+                let value = match segments.get(1) {
+                    Some(Segment::Key(name)) => self.variables.get(name),
+                    _ => None,
+                };
+                Ok(value.map_or(JsonValue::Null, |value| Path::walk(value, &segments[2..])))
+            }
+            _ => variable(),
         }
-
-        // Treat as string literal
-        Ok(JsonValue::String(expr.to_string()))
     }
+}
 
-    /// Compare numeric values
-    fn compare_numeric<F>(&self, left: &str, right: &str, op: F) -> Result<bool>
-    where
-        F: Fn(f64, f64) -> bool,
-    {
-        let left_val = self.resolve_value(left)?;
-        let right_val = self.resolve_value(right)?;
-
-        let left_num = match &left_val {
-            JsonValue::Number(n) => n.as_f64().ok_or_else(|| {
-                AppError::Validation(format!("Cannot convert '{}' to number", left))
-            })?,
-            JsonValue::String(s) => s.parse::<f64>().map_err(|_| {
-                AppError::Validation(format!("Cannot parse '{}' as number", s))
-            })?,
-            _ => return Err(AppError::Validation(format!("'{}' is not a number", left))),
-        };
-
-        let right_num = match &right_val {
-            JsonValue::Number(n) => n.as_f64().ok_or_else(|| {
-                AppError::Validation(format!("Cannot convert '{}' to number", right))
-            })?,
-            JsonValue::String(s) => s.parse::<f64>().map_err(|_| {
-                AppError::Validation(format!("Cannot parse '{}' as number", s))
-            })?,
-            _ => return Err(AppError::Validation(format!("'{}' is not a number", right))),
-        };
-
-        Ok(op(left_num, right_num))
+/// Text a value is substituted as
+fn render(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        JsonValue::Number(n) => n.to_string(),
+        JsonValue::Bool(b) => b.to_string(),
+        JsonValue::Null => "null".to_string(),
+        _ => serde_json::to_string(value).unwrap_or_else(|_| "".to_string()),
     }
 }
 
@@ -312,4 +315,44 @@ mod tests {
         let retrieved = context.get_step_output("step1").unwrap();
         assert_eq!(retrieved.get("result").unwrap().as_str().unwrap(), "success");
     }
+
+    #[test]
+    fn test_condition_paths() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident);
+
+        let mut output = HashMap::new();
+        output.insert("action_0.error_rate".to_string(), serde_json::json!(0.12));
+        output.insert("action_1.error_rate".to_string(), serde_json::json!(0.01));
+        context.set_step_output("collect".to_string(), output);
+        context.set_variable("threshold".to_string(), JsonValue::String("0.05".to_string()));
+
+        assert!(context
+            .evaluate_condition("steps.collect.output.error_rate > vars.threshold")
+            .unwrap());
+        assert!(context
+            .evaluate_condition("steps.collect.output.action_1.error_rate < $threshold")
+            .unwrap());
+        assert!(context
+            .evaluate_condition("incident.severity in ['P0', 'P1'] && incident.source =~ '^test'")
+            .unwrap());
+        assert!(!context.evaluate_condition("steps.skipped.output.ok").unwrap());
+        assert!(context.evaluate_condition("$missing == 1").is_err());
+        assert!(context.evaluate_condition("$count = 5").is_err());
+    }
+
+    #[test]
+    fn test_template_paths() {
+        let incident = create_test_incident();
+        let mut context = ExecutionContext::new(incident);
+
+        let mut output = HashMap::new();
+        output.insert("action_0.pod".to_string(), serde_json::json!("api-7f9c"));
+        context.set_step_output("restart".to_string(), output);
+
+        let result = context.substitute_string(
+            "{{ incident.severity }} {{steps.restart.output.pod}} {{incident_title}} {{unknown}}",
+        );
+        assert_eq!(result, "P1 api-7f9c Test Incident {{unknown}}");
+    }
 }
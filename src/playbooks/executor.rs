@@ -1,10 +1,16 @@
 use crate::error::{AppError, Result};
 use crate::models::{
-    BackoffStrategy, ExecutionStatus, Playbook, PlaybookExecution, PlaybookStep, StepResult,
+    ApprovalPolicy, ApprovalRequest, ApprovalTimeoutAction, BackoffStrategy, ExecutionStatus,
+    Playbook, PlaybookExecution, PlaybookSimulation, PlaybookStep, SimulatedAction,
+    SimulatedOutcome, SimulatedStep, StepResult, StepType, TriggerRule,
+};
+use crate::playbooks::{
+    create_recording_registry, revisions, ActionExecutorRegistry, ExecutionContext,
+    PlaybookGraph,
 };
-use crate::playbooks::{ActionExecutorRegistry, ExecutionContext};
 use crate::state::IncidentStore;
 use chrono::Utc;
+use futures::stream::{FuturesUnordered, StreamExt};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -14,8 +20,9 @@ use uuid::Uuid;
 /// Playbook executor - executes playbooks for incidents
 pub struct PlaybookExecutor {
     action_registry: Arc<ActionExecutorRegistry>,
-    #[allow(dead_code)]
-    store: Arc<dyn IncidentStore>,
+    /// Where executions are checkpointed as they progress; none in a dry run
+    store: Option<Arc<dyn IncidentStore>>,
+    dry_run: bool,
 }
 
 impl PlaybookExecutor {
@@ -23,16 +30,60 @@ impl PlaybookExecutor {
     pub fn new(action_registry: Arc<ActionExecutorRegistry>, store: Arc<dyn IncidentStore>) -> Self {
         Self {
             action_registry,
-            store,
+            store: Some(store),
+            dry_run: false,
+        }
+    }
+
+    /// Create an executor that runs playbooks without side effects
+    ///
+    /// Every action is a [`RecordingActionExecutor`](crate::playbooks::RecordingActionExecutor),
+    /// approval steps are assumed approved, and nothing is checkpointed.
+    /// See [`simulate_playbook`](Self::simulate_playbook).
+    pub fn dry_run() -> Self {
+        Self {
+            action_registry: Arc::new(create_recording_registry()),
+            store: None,
+            dry_run: true,
         }
     }
 
+    /// Trace what a playbook would do for an incident
+    ///
+    /// Only available on a [`dry_run`](Self::dry_run) executor. Step outputs
+    /// are the recorded calls rather than real results, so conditions that
+    /// read other steps' outputs see `null`.
+    pub async fn simulate_playbook(
+        &self,
+        playbook: &Playbook,
+        context: &mut ExecutionContext,
+    ) -> Result<PlaybookSimulation> {
+        if !self.dry_run {
+            return Err(AppError::Configuration(
+                "Playbooks can only be simulated by a dry-run executor".to_string(),
+            ));
+        }
+
+        let execution = self.execute_playbook(playbook, context).await?;
+        Ok(simulation_trace(playbook, &execution))
+    }
+
     /// Execute a playbook for an incident
+    ///
+    /// Steps run once their dependencies allow it. A failed step whose failed
+    /// actions have no `on_failure` step fails the execution. When only
+    /// approval steps are left waiting the execution is returned in
+    /// `WaitingForApproval`; see [`resume_playbook`](Self::resume_playbook).
+    ///
+    /// The execution is checkpointed to the store before and after every
+    /// step so it can be recovered after a restart; see
+    /// [`recover_playbook`](Self::recover_playbook).
     pub async fn execute_playbook(
         &self,
         playbook: &Playbook,
         context: &mut ExecutionContext,
     ) -> Result<PlaybookExecution> {
+        let graph = PlaybookGraph::build(playbook)?;
         let execution_id = Uuid::new_v4();
         let incident_id = context.incident().id;
 
@@ -43,7 +94,7 @@ impl PlaybookExecutor {
             "Starting playbook execution"
         );
 
-        let mut execution = PlaybookExecution {
+        let execution = PlaybookExecution {
             id: execution_id,
             playbook_id: playbook.id,
             incident_id,
@@ -53,29 +104,250 @@ impl PlaybookExecutor {
             current_step: None,
             step_results: std::collections::HashMap::new(),
             error: None,
+            activated_steps: Vec::new(),
+            approvals: Vec::new(),
+            variables: std::collections::HashMap::new(),
+            playbook_revision: playbook.revision,
+            playbook_digest: revisions::digest(playbook),
         };
 
-        // Apply playbook variables to context
-        for (key, value) in &playbook.variables {
-            context.set_variable(key.clone(), serde_json::Value::String(value.clone()));
+        apply_variables(playbook, context);
+        self.run(&graph, execution, context).await
+    }
+
+    /// Continue an execution paused for approval
+    ///
+    /// Applies the decisions recorded in `execution.approvals` since it
+    /// paused: an approved step succeeds, a rejected one cancels the
+    /// execution. `context` must be a fresh context for the incident;
+    /// variables and step outputs are restored from the last checkpoint.
+    pub async fn resume_playbook(
+        &self,
+        playbook: &Playbook,
+        mut execution: PlaybookExecution,
+        context: &mut ExecutionContext,
+    ) -> Result<PlaybookExecution> {
+        if execution.status != ExecutionStatus::WaitingForApproval {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Execution {} is not waiting for approval",
+                execution.id
+            )));
         }
+        let graph = PlaybookGraph::build(playbook)?;
 
-        // Execute steps in sequence
-        for step in &playbook.steps {
-            execution.current_step = Some(step.id.clone());
+        restore_context(playbook, &execution, context);
+        if let Some(error) = apply_decisions(&mut execution, context) {
+            return Ok(self.cancel(execution, context, error).await);
+        }
 
-            info!(
-                execution_id = %execution_id,
-                step_id = %step.id,
-                step_type = ?step.step_type,
-                "Executing step"
+        execution.status = ExecutionStatus::Running;
+        self.run(&graph, execution, context).await
+    }
+
+    /// Pick up an execution that a restart interrupted
+    ///
+    /// Steps that were running when the process stopped run again if they
+    /// are idempotent. If any of them is not, its actions may already have
+    /// taken effect, so the execution is marked `Abandoned` instead.
+    /// `context` must be a fresh context for the incident; variables and
+    /// step outputs are restored from the last checkpoint.
+    pub async fn recover_playbook(
+        &self,
+        playbook: &Playbook,
+        mut execution: PlaybookExecution,
+        context: &mut ExecutionContext,
+    ) -> Result<PlaybookExecution> {
+        if execution.status != ExecutionStatus::Running {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Execution {} was not interrupted",
+                execution.id
+            )));
+        }
+        let graph = PlaybookGraph::build(playbook)?;
+
+        restore_context(playbook, &execution, context);
+
+        let mut interrupted: Vec<String> = execution
+            .step_results
+            .values()
+            .filter(|result| result.status == ExecutionStatus::Running)
+            .map(|result| result.step_id.clone())
+            .collect();
+        interrupted.sort();
+        let unsafe_steps: Vec<&str> = interrupted
+            .iter()
+            .map(String::as_str)
+            .filter(|id| !graph.position(id).is_some_and(|i| graph.step(i).idempotent))
+            .collect();
+
+        if !unsafe_steps.is_empty() {
+            warn!(
+                execution_id = %execution.id,
+                steps = ?unsafe_steps,
+                "Abandoning playbook execution interrupted in steps that are not idempotent"
+            );
+            let now = Utc::now();
+            for id in &unsafe_steps {
+                if let Some(result) = execution.step_results.get_mut(*id) {
+                    result.status = ExecutionStatus::Abandoned;
+                    result.completed_at = Some(now);
+                    result.error = Some("Interrupted by a restart".to_string());
+                }
+            }
+            execution.status = ExecutionStatus::Abandoned;
+            execution.error = Some(format!(
+                "Interrupted in steps that are not idempotent: {}",
+                unsafe_steps.join(", ")
+            ));
+            execution.completed_at = Some(now);
+            self.checkpoint(&mut execution, context).await;
+            return Ok(execution);
+        }
+
+        info!(
+            execution_id = %execution.id,
+            playbook_id = %playbook.id,
+            rerun = ?interrupted,
+            "Recovering interrupted playbook execution"
+        );
+
+        // A restart may also have landed between an approval being decided
+        // and the execution resuming
+        if let Some(error) = apply_decisions(&mut execution, context) {
+            return Ok(self.cancel(execution, context, error).await);
+        }
+        self.run(&graph, execution, context).await
+    }
+
+    /// Cancel an execution whose approval was rejected
+    async fn cancel(
+        &self,
+        mut execution: PlaybookExecution,
+        context: &ExecutionContext,
+        error: String,
+    ) -> PlaybookExecution {
+        warn!(execution_id = %execution.id, error = %error, "Playbook execution rejected");
+        execution.status = ExecutionStatus::Cancelled;
+        execution.error = Some(error);
+        execution.completed_at = Some(Utc::now());
+        self.checkpoint(&mut execution, context).await;
+        execution
+    }
+
+    /// Save an execution's progress to the store
+    ///
+    /// Failures are logged rather than returned: a missed checkpoint only
+    /// matters if the process also stops before the next one.
+    async fn checkpoint(&self, execution: &mut PlaybookExecution, context: &ExecutionContext) {
+        execution.variables = context.variables().clone();
+        let Some(ref store) = self.store else {
+            return;
+        };
+        if let Err(e) = store.save_playbook_execution(execution).await {
+            error!(
+                execution_id = %execution.id,
+                error = %e,
+                "Failed to checkpoint playbook execution"
             );
+        }
+    }
+
+    /// Run the steps of an execution that are still pending, checkpointing
+    /// before and after
+    async fn run(
+        &self,
+        graph: &PlaybookGraph<'_>,
+        mut execution: PlaybookExecution,
+        context: &mut ExecutionContext,
+    ) -> Result<PlaybookExecution> {
+        self.checkpoint(&mut execution, context).await;
+        let mut execution = self.run_steps(graph, execution, context).await?;
+        self.checkpoint(&mut execution, context).await;
+        Ok(execution)
+    }
+
+    /// Run the steps of an execution that are still pending
+    async fn run_steps(
+        &self,
+        graph: &PlaybookGraph<'_>,
+        mut execution: PlaybookExecution,
+        context: &mut ExecutionContext,
+    ) -> Result<PlaybookExecution> {
+        let execution_id = execution.id;
 
-            // Evaluate condition if present
-            if let Some(ref condition) = step.condition {
-                match context.evaluate_condition(condition) {
-                    Ok(should_execute) => {
-                        if !should_execute {
+        // Run steps as their dependencies finish; steps that become ready
+        // together run concurrently
+        let mut states: Vec<StepState> = graph
+            .steps()
+            .iter()
+            .map(|step| match execution.step_results.get(&step.id) {
+                Some(result) => StepState::from_status(&result.status),
+                None => StepState::Pending,
+            })
+            .collect();
+        let mut activated: Vec<bool> = graph
+            .steps()
+            .iter()
+            .map(|step| execution.activated_steps.contains(&step.id))
+            .collect();
+
+        loop {
+            let mut ready = Vec::new();
+            let mut progressed = false;
+            for i in 0..graph.len() {
+                if states[i] != StepState::Pending {
+                    continue;
+                }
+                let step = graph.step(i);
+
+                let skip_reason = if graph.is_branch_target(i) && !activated[i] {
+                    if graph.branch_sources(i).iter().any(|&s| !states[s].is_done()) {
+                        continue;
+                    }
+                    Some("Skipped: branch not taken")
+                } else {
+                    let deps = graph.dependencies(i);
+                    if deps.iter().any(|&d| !states[d].is_done()) {
+                        continue;
+                    }
+                    let satisfied = match graph.trigger_rule(i) {
+                        TriggerRule::AllSuccess => {
+                            deps.iter().all(|&d| states[d] == StepState::Succeeded)
+                        }
+                        TriggerRule::OneSuccess => {
+                            deps.is_empty()
+                                || deps.iter().any(|&d| states[d] == StepState::Succeeded)
+                        }
+                        TriggerRule::AllDone => true,
+                    };
+                    if satisfied {
+                        None
+                    } else {
+                        Some("Skipped: dependencies not satisfied")
+                    }
+                };
+
+                if let Some(reason) = skip_reason {
+                    info!(
+                        execution_id = %execution_id,
+                        step_id = %step.id,
+                        reason = reason,
+                        "Skipping step"
+                    );
+                    states[i] = StepState::Skipped;
+                    progressed = true;
+                    execution.step_results.insert(
+                        step.id.clone(),
+                        skipped_result(step, ExecutionStatus::Cancelled, reason),
+                    );
+                    continue;
+                }
+
+                // Evaluate condition if present
+                if let Some(ref condition) = step.condition {
+                    match context.evaluate_condition(condition) {
+                        Ok(true) => {}
+                        Ok(false) => {
                             info!(
                                 execution_id = %execution_id,
                                 step_id = %step.id,
@@ -83,88 +355,231 @@ impl PlaybookExecutor {
                                 "Skipping step due to condition"
                             );
 
-                            // Add skipped step result
-                            let step_result = StepResult {
-                                step_id: step.id.clone(),
-                                started_at: Utc::now(),
-                                completed_at: Some(Utc::now()),
-                                status: ExecutionStatus::Completed,
-                                output: std::collections::HashMap::new(),
-                                error: Some("Skipped due to condition".to_string()),
-                            };
-                            execution.step_results.insert(step.id.clone(), step_result);
+                            // A step skipped by its condition counts as done
+                            states[i] = StepState::Succeeded;
+                            progressed = true;
+                            execution.step_results.insert(
+                                step.id.clone(),
+                                skipped_result(
+                                    step,
+                                    ExecutionStatus::Completed,
+                                    "Skipped due to condition",
+                                ),
+                            );
                             continue;
                         }
+                        Err(e) => {
+                            error!(
+                                execution_id = %execution_id,
+                                step_id = %step.id,
+                                error = %e,
+                                "Failed to evaluate step condition"
+                            );
+                            execution.status = ExecutionStatus::Failed;
+                            execution.error = Some(format!("Condition evaluation failed: {}", e));
+                            execution.completed_at = Some(Utc::now());
+                            return Ok(execution);
+                        }
                     }
+                }
+
+                if step.step_type == StepType::Approval && self.dry_run {
+                    // Assume approval so the steps after the gate are traced
+                    states[i] = StepState::Succeeded;
+                    progressed = true;
+                    let approvers = step
+                        .approval
+                        .as_ref()
+                        .map(|a| a.approvers.clone())
+                        .unwrap_or_default();
+                    let mut output = std::collections::HashMap::new();
+                    output.insert("dry_run".to_string(), serde_json::Value::Bool(true));
+                    output.insert("approvers".to_string(), serde_json::json!(approvers));
+                    context.set_step_output(step.id.clone(), output.clone());
+                    execution.step_results.insert(
+                        step.id.clone(),
+                        StepResult {
+                            step_id: step.id.clone(),
+                            started_at: Utc::now(),
+                            completed_at: Some(Utc::now()),
+                            status: ExecutionStatus::Completed,
+                            output,
+                            error: None,
+                        },
+                    );
+                    continue;
+                }
+
+                if step.step_type == StepType::Approval {
+                    info!(
+                        execution_id = %execution_id,
+                        step_id = %step.id,
+                        "Waiting for approval"
+                    );
+                    states[i] = StepState::Waiting;
+                    progressed = true;
+                    execution.approvals.push(approval_request(step));
+                    execution.step_results.insert(
+                        step.id.clone(),
+                        StepResult {
+                            step_id: step.id.clone(),
+                            started_at: Utc::now(),
+                            completed_at: None,
+                            status: ExecutionStatus::WaitingForApproval,
+                            output: std::collections::HashMap::new(),
+                            error: None,
+                        },
+                    );
+                    continue;
+                }
+
+                ready.push(i);
+            }
+
+            if ready.is_empty() {
+                // Skipped steps may have unblocked others; otherwise every
+                // step has finished or is waiting for approval
+                if progressed {
+                    continue;
+                }
+                break;
+            }
+
+            execution.current_step = Some(graph.step(ready[0]).id.clone());
+            for &i in &ready {
+                let step = graph.step(i);
+                info!(
+                    execution_id = %execution_id,
+                    step_id = %step.id,
+                    step_type = ?step.step_type,
+                    "Executing step"
+                );
+                execution.step_results.insert(
+                    step.id.clone(),
+                    StepResult {
+                        step_id: step.id.clone(),
+                        started_at: Utc::now(),
+                        completed_at: None,
+                        status: ExecutionStatus::Running,
+                        output: std::collections::HashMap::new(),
+                        error: None,
+                    },
+                );
+            }
+
+            // Record which steps are in flight before any of their actions
+            // run, so a restart can tell what may have happened
+            self.checkpoint(&mut execution, context).await;
+
+            let mut runs: FuturesUnordered<_> = ready
+                .iter()
+                .map(|&i| {
+                    let step = graph.step(i);
+                    let mut step_context = context.clone();
+                    async move { (i, self.execute_step(step, &mut step_context).await) }
+                })
+                .collect();
+
+            let mut unhandled = None;
+            while let Some((i, run)) = runs.next().await {
+                let step = graph.step(i);
+                let (step_result, failed_actions) = match run {
+                    Ok(run) => (run.result, run.failed_actions),
                     Err(e) => {
                         error!(
                             execution_id = %execution_id,
                             step_id = %step.id,
                             error = %e,
-                            "Failed to evaluate step condition"
+                            "Step execution error"
                         );
-                        execution.status = ExecutionStatus::Failed;
-                        execution.error = Some(format!("Condition evaluation failed: {}", e));
-                        execution.completed_at = Some(Utc::now());
-                        return Ok(execution);
+                        let step_result = StepResult {
+                            step_id: step.id.clone(),
+                            started_at: Utc::now(),
+                            completed_at: Some(Utc::now()),
+                            status: ExecutionStatus::Failed,
+                            output: std::collections::HashMap::new(),
+                            error: Some(e.to_string()),
+                        };
+                        (step_result, (0..step.actions.len()).collect())
                     }
-                }
-            }
+                };
 
-            // Execute the step
-            match self.execute_step(step, context).await {
-                Ok(step_result) => {
-                    let step_success = step_result.status == ExecutionStatus::Completed
-                        && step_result.error.is_none();
+                let step_success =
+                    step_result.status == ExecutionStatus::Completed && step_result.error.is_none();
 
-                    // Store step output in context
-                    context.set_step_output(step.id.clone(), step_result.output.clone());
-                    execution.step_results.insert(step.id.clone(), step_result);
+                if step_success {
+                    states[i] = StepState::Succeeded;
+                    for target in step.actions.iter().filter_map(|a| a.on_success.as_deref()) {
+                        activate(graph, &mut activated, &mut execution, target);
+                    }
+                } else {
+                    states[i] = StepState::Failed;
+                    let handlers: Vec<&str> = failed_actions
+                        .iter()
+                        .filter_map(|&idx| step.actions.get(idx)?.on_failure.as_deref())
+                        .collect();
 
-                    if !step_success {
+                    if handlers.is_empty() {
                         warn!(
                             execution_id = %execution_id,
                             step_id = %step.id,
                             "Step failed"
                         );
-                        execution.status = ExecutionStatus::Failed;
-                        execution.completed_at = Some(Utc::now());
-                        return Ok(execution);
+                        if unhandled.is_none() {
+                            unhandled = Some(format!(
+                                "Step {} failed: {}",
+                                step.id,
+                                step_result.error.as_deref().unwrap_or("unknown error")
+                            ));
+                        }
+                    } else {
+                        warn!(
+                            execution_id = %execution_id,
+                            step_id = %step.id,
+                            handlers = ?handlers,
+                            "Step failed, running failure handlers"
+                        );
+                        for target in handlers {
+                            activate(graph, &mut activated, &mut execution, target);
+                        }
                     }
                 }
-                Err(e) => {
-                    error!(
-                        execution_id = %execution_id,
-                        step_id = %step.id,
-                        error = %e,
-                        "Step execution error"
-                    );
 
-                    let step_result = StepResult {
-                        step_id: step.id.clone(),
-                        started_at: Utc::now(),
-                        completed_at: Some(Utc::now()),
-                        status: ExecutionStatus::Failed,
-                        output: std::collections::HashMap::new(),
-                        error: Some(e.to_string()),
-                    };
-                    execution.step_results.insert(step.id.clone(), step_result);
-                    execution.status = ExecutionStatus::Failed;
-                    execution.error = Some(format!("Step {} failed: {}", step.id, e));
-                    execution.completed_at = Some(Utc::now());
-                    return Ok(execution);
-                }
+                // Store step output in context
+                context.set_step_output(step.id.clone(), step_result.output.clone());
+                execution.step_results.insert(step.id.clone(), step_result);
+                self.checkpoint(&mut execution, context).await;
+            }
+
+            if let Some(error) = unhandled {
+                execution.status = ExecutionStatus::Failed;
+                execution.error = Some(error);
+                execution.completed_at = Some(Utc::now());
+                return Ok(execution);
             }
         }
 
-        // All steps completed successfully
+        if let Some(i) = states.iter().position(|&s| s == StepState::Waiting) {
+            execution.status = ExecutionStatus::WaitingForApproval;
+            execution.current_step = Some(graph.step(i).id.clone());
+
+            info!(
+                execution_id = %execution_id,
+                playbook_id = %execution.playbook_id,
+                step_id = %graph.step(i).id,
+                "Playbook execution paused for approval"
+            );
+            return Ok(execution);
+        }
+
+        // Every step ran, was skipped, or failed with a handler
         execution.status = ExecutionStatus::Completed;
         execution.completed_at = Some(Utc::now());
         execution.current_step = None;
 
         info!(
             execution_id = %execution_id,
-            playbook_id = %playbook.id,
+            playbook_id = %execution.playbook_id,
             duration_secs = (execution.completed_at.unwrap() - execution.started_at).num_seconds(),
             "Playbook execution completed successfully"
         );
@@ -177,7 +592,7 @@ impl PlaybookExecutor {
         &self,
         step: &PlaybookStep,
         context: &mut ExecutionContext,
-    ) -> Result<StepResult> {
+    ) -> Result<StepRun> {
         let mut step_result = StepResult {
             step_id: step.id.clone(),
             started_at: Utc::now(),
@@ -221,22 +636,31 @@ impl PlaybookExecutor {
                     step_result.output = output;
                     step_result.status = ExecutionStatus::Completed;
                     step_result.completed_at = Some(Utc::now());
-                    return Ok(step_result);
+                    return Ok(StepRun {
+                        result: step_result,
+                        failed_actions: Vec::new(),
+                    });
                 }
-                Err(e) => {
+                Err(failure) => {
                     warn!(
                         step_id = %step.id,
                         attempt = attempt,
-                        error = %e,
+                        error = %failure.error,
                         "Step attempt failed"
                     );
 
                     if attempt >= max_attempts {
                         // No more retries
                         step_result.status = ExecutionStatus::Failed;
-                        step_result.error = Some(format!("Failed after {} attempts: {}", attempt, e));
+                        step_result.error = Some(format!(
+                            "Failed after {} attempts: {}",
+                            attempt, failure.error
+                        ));
                         step_result.completed_at = Some(Utc::now());
-                        return Ok(step_result);
+                        return Ok(StepRun {
+                            result: step_result,
+                            failed_actions: failure.failed_actions,
+                        });
                     }
 
                     // Calculate backoff and retry
@@ -258,7 +682,7 @@ impl PlaybookExecutor {
         step: &PlaybookStep,
         context: &mut ExecutionContext,
         _timeout: Duration,
-    ) -> Result<std::collections::HashMap<String, serde_json::Value>> {
+    ) -> std::result::Result<ActionOutput, ActionsFailure> {
         let mut combined_output = std::collections::HashMap::new();
 
         for (idx, action) in step.actions.iter().enumerate() {
@@ -269,11 +693,16 @@ impl PlaybookExecutor {
                 "Executing action"
             );
 
-            let result = self.action_registry.execute(action, context).await?;
+            let result = self
+                .action_registry
+                .execute(action, context)
+                .await
+                .map_err(|e| ActionsFailure::new(e, vec![idx]))?;
 
             if !result.success {
-                return Err(AppError::Internal(
-                    result.error.unwrap_or_else(|| "Action failed".to_string()),
+                return Err(ActionsFailure::new(
+                    AppError::Internal(result.error.unwrap_or_else(|| "Action failed".to_string())),
+                    vec![idx],
                 ));
             }
 
@@ -292,7 +721,7 @@ impl PlaybookExecutor {
         step: &PlaybookStep,
         context: &mut ExecutionContext,
         _timeout: Duration,
-    ) -> Result<std::collections::HashMap<String, serde_json::Value>> {
+    ) -> std::result::Result<ActionOutput, ActionsFailure> {
         let mut tasks = Vec::new();
 
         for (idx, action) in step.actions.iter().enumerate() {
@@ -311,12 +740,14 @@ impl PlaybookExecutor {
         // Wait for all tasks
         let mut combined_output = std::collections::HashMap::new();
         let mut errors = Vec::new();
+        let mut failed_actions = Vec::new();
 
-        for task in tasks {
+        for (idx, task) in tasks.into_iter().enumerate() {
             match task.await {
                 Ok((idx, Ok(result))) => {
                     if !result.success {
                         errors.push(result.error.unwrap_or_else(|| "Action failed".to_string()));
+                        failed_actions.push(idx);
                     } else {
                         for (key, value) in result.output {
                             combined_output.insert(format!("action_{}.{}", idx, key), value);
@@ -325,26 +756,277 @@ impl PlaybookExecutor {
                 }
                 Ok((idx, Err(e))) => {
                     errors.push(format!("Action {}: {}", idx, e));
+                    failed_actions.push(idx);
                 }
                 Err(e) => {
                     errors.push(format!("Task execution error: {}", e));
+                    failed_actions.push(idx);
                 }
             }
         }
 
         if !errors.is_empty() {
-            return Err(AppError::Internal(format!(
-                "Parallel action execution failed: {}",
-                errors.join(", ")
-            )));
+            return Err(ActionsFailure::new(
+                AppError::Internal(format!(
+                    "Parallel action execution failed: {}",
+                    errors.join(", ")
+                )),
+                failed_actions,
+            ));
         }
 
         Ok(combined_output)
     }
 }
 
+/// Outcome of a step within an execution
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StepState {
+    Pending,
+    Waiting,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+impl StepState {
+    /// State of a step with a recorded result
+    fn from_status(status: &ExecutionStatus) -> Self {
+        match status {
+            ExecutionStatus::Completed => StepState::Succeeded,
+            ExecutionStatus::Failed => StepState::Failed,
+            ExecutionStatus::Cancelled => StepState::Skipped,
+            ExecutionStatus::WaitingForApproval => StepState::Waiting,
+            // Interrupted mid-step; run it again
+            ExecutionStatus::Running => StepState::Pending,
+            ExecutionStatus::Abandoned => StepState::Failed,
+        }
+    }
+
+    fn is_done(self) -> bool {
+        matches!(self, StepState::Succeeded | StepState::Failed | StepState::Skipped)
+    }
+}
+
+/// Combined output of a step's actions, keyed `action_{index}.{key}`
+type ActionOutput = std::collections::HashMap<String, serde_json::Value>;
+
+/// Result of a step and the actions that failed on its last attempt
+struct StepRun {
+    result: StepResult,
+    failed_actions: Vec<usize>,
+}
+
+/// Why a step's actions failed, and which of them did
+struct ActionsFailure {
+    error: AppError,
+    failed_actions: Vec<usize>,
+}
+
+impl ActionsFailure {
+    fn new(error: AppError, failed_actions: Vec<usize>) -> Self {
+        Self {
+            error,
+            failed_actions,
+        }
+    }
+}
+
+/// Result for a step that did not run
+fn skipped_result(step: &PlaybookStep, status: ExecutionStatus, reason: &str) -> StepResult {
+    StepResult {
+        step_id: step.id.clone(),
+        started_at: Utc::now(),
+        completed_at: Some(Utc::now()),
+        status,
+        output: std::collections::HashMap::new(),
+        error: Some(reason.to_string()),
+    }
+}
+
+/// Mark a branch step to run
+fn activate(
+    graph: &PlaybookGraph<'_>,
+    activated: &mut [bool],
+    execution: &mut PlaybookExecution,
+    step_id: &str,
+) {
+    if let Some(i) = graph.position(step_id) {
+        if !activated[i] {
+            activated[i] = true;
+            execution.activated_steps.push(step_id.to_string());
+        }
+    }
+}
+
+/// Set the playbook's variables on a context
+fn apply_variables(playbook: &Playbook, context: &mut ExecutionContext) {
+    for (key, value) in &playbook.variables {
+        context.set_variable(key.clone(), serde_json::Value::String(value.clone()));
+    }
+}
+
+/// Restore a fresh context from an execution's last checkpoint
+fn restore_context(
+    playbook: &Playbook,
+    execution: &PlaybookExecution,
+    context: &mut ExecutionContext,
+) {
+    apply_variables(playbook, context);
+    for (key, value) in &execution.variables {
+        context.set_variable(key.clone(), value.clone());
+    }
+    for (step_id, result) in &execution.step_results {
+        context.set_step_output(step_id.clone(), result.output.clone());
+    }
+}
+
+/// Apply the approval decisions recorded since an execution paused
+///
+/// An approved step succeeds and a rejected one fails; returns the reason
+/// to cancel the execution if any step was rejected.
+fn apply_decisions(
+    execution: &mut PlaybookExecution,
+    context: &mut ExecutionContext,
+) -> Option<String> {
+    let mut rejected = None;
+    for request in &execution.approvals {
+        let Some(ref decision) = request.decision else {
+            continue;
+        };
+        let Some(result) = execution.step_results.get_mut(&request.step_id) else {
+            continue;
+        };
+        if result.status != ExecutionStatus::WaitingForApproval {
+            continue;
+        }
+
+        info!(
+            execution_id = %execution.id,
+            step_id = %request.step_id,
+            approved = decision.approved,
+            decided_by = %decision.decided_by,
+            "Approval decided"
+        );
+
+        result.completed_at = Some(decision.decided_at);
+        result.output.insert(
+            "decided_by".to_string(),
+            serde_json::Value::String(decision.decided_by.clone()),
+        );
+        if let Some(ref comment) = decision.comment {
+            result
+                .output
+                .insert("comment".to_string(), serde_json::Value::String(comment.clone()));
+        }
+        if decision.approved {
+            result.status = ExecutionStatus::Completed;
+        } else {
+            result.status = ExecutionStatus::Failed;
+            result.error = Some(format!("Rejected by {}", decision.decided_by));
+            if rejected.is_none() {
+                rejected = Some(format!(
+                    "Step {} rejected by {}",
+                    request.step_id, decision.decided_by
+                ));
+            }
+        }
+        context.set_step_output(request.step_id.clone(), result.output.clone());
+    }
+    rejected
+}
+
+/// Trace of a dry-run execution, in playbook order
+fn simulation_trace(playbook: &Playbook, execution: &PlaybookExecution) -> PlaybookSimulation {
+    let steps = playbook
+        .steps
+        .iter()
+        .map(|step| {
+            let result = execution.step_results.get(&step.id);
+            let outcome = match result {
+                None => SimulatedOutcome::NotReached,
+                Some(result) => match result.status {
+                    ExecutionStatus::Cancelled => SimulatedOutcome::Skipped,
+                    ExecutionStatus::Completed if result.error.is_some() => {
+                        SimulatedOutcome::ConditionFalse
+                    }
+                    _ if step.step_type == StepType::Approval => SimulatedOutcome::ApprovalAssumed,
+                    _ => SimulatedOutcome::Run,
+                },
+            };
+
+            let actions = match (outcome, result) {
+                (SimulatedOutcome::Run, Some(result)) => step
+                    .actions
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, action)| {
+                        let parameters = result
+                            .output
+                            .get(&format!("action_{}.parameters", idx))
+                            .and_then(|p| serde_json::from_value(p.clone()).ok())
+                            .unwrap_or_else(|| action.parameters.clone());
+                        SimulatedAction {
+                            action_type: action.action_type.clone(),
+                            parameters,
+                        }
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+
+            SimulatedStep {
+                step_id: step.id.clone(),
+                step_type: step.step_type.clone(),
+                outcome,
+                condition: step.condition.clone(),
+                reason: result.and_then(|r| r.error.clone()),
+                approvers: step
+                    .approval
+                    .as_ref()
+                    .map(|a| a.approvers.clone())
+                    .unwrap_or_default(),
+                actions,
+            }
+        })
+        .collect();
+
+    PlaybookSimulation {
+        playbook_id: playbook.id,
+        playbook_name: playbook.name.clone(),
+        incident_id: execution.incident_id,
+        status: execution.status.clone(),
+        error: execution.error.clone(),
+        steps,
+    }
+}
+
+/// Approval request for an approval step reached now
+fn approval_request(step: &PlaybookStep) -> ApprovalRequest {
+    let requested_at = Utc::now();
+    let policy = step.approval.clone().unwrap_or_else(|| ApprovalPolicy {
+        approvers: Vec::new(),
+        on_timeout: ApprovalTimeoutAction::default(),
+    });
+    let expires_at = step
+        .timeout
+        .as_deref()
+        .and_then(|t| parse_duration(t).ok())
+        .and_then(|d| chrono::Duration::from_std(d).ok())
+        .map(|d| requested_at + d);
+
+    ApprovalRequest {
+        step_id: step.id.clone(),
+        approvers: policy.approvers,
+        requested_at,
+        expires_at,
+        on_timeout: policy.on_timeout,
+        decision: None,
+    }
+}
+
 /// Parse duration string (e.g., "5s", "10m", "1h")
-fn parse_duration(s: &str) -> Result<Duration> {
+pub(crate) fn parse_duration(s: &str) -> Result<Duration> {
     let s = s.trim();
     if s.is_empty() {
         return Err(AppError::Validation("Empty duration string".to_string()));
@@ -379,10 +1061,123 @@ fn calculate_backoff(strategy: &BackoffStrategy, attempt: u32) -> Duration {
 mod tests {
     use super::*;
     use crate::models::{Action, ActionType, Incident, IncidentType, Severity};
-    use crate::playbooks::create_default_registry;
+    use crate::playbooks::{create_default_registry, ActionExecutor, ActionResult};
     use crate::state::InMemoryStore;
+    use async_trait::async_trait;
     use std::collections::HashMap;
 
+    /// Health check that passes unless the action sets `healthy: false`
+    struct StubHealthCheck;
+
+    #[async_trait]
+    impl ActionExecutor for StubHealthCheck {
+        async fn execute(
+            &self,
+            action: &Action,
+            _context: &mut ExecutionContext,
+        ) -> Result<ActionResult> {
+            let healthy = action
+                .parameters
+                .get("healthy")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            if healthy {
+                let mut output = HashMap::new();
+                output.insert("healthy".to_string(), serde_json::json!(true));
+                Ok(ActionResult::success(output))
+            } else {
+                Ok(ActionResult::failure("service unhealthy".to_string()))
+            }
+        }
+    }
+
+    fn stub_executor(store: Arc<InMemoryStore>) -> PlaybookExecutor {
+        let mut registry = create_default_registry(None, store.clone());
+        registry.register(ActionType::HealthCheck, Arc::new(StubHealthCheck));
+        PlaybookExecutor::new(Arc::new(registry), store)
+    }
+
+    fn check_step(id: &str, healthy: bool) -> PlaybookStep {
+        let mut parameters = HashMap::new();
+        parameters.insert("healthy".to_string(), serde_json::json!(healthy));
+        PlaybookStep {
+            id: id.to_string(),
+            step_type: crate::models::StepType::DataCollection,
+            description: None,
+            actions: vec![Action {
+                action_type: ActionType::HealthCheck,
+                parameters,
+                on_success: None,
+                on_failure: None,
+            }],
+            parallel: false,
+            timeout: None,
+            retry: 0,
+            backoff: BackoffStrategy::Fixed,
+            condition: None,
+            depends_on: None,
+            trigger_rule: TriggerRule::AllSuccess,
+            approval: None,
+            idempotent: false,
+        }
+    }
+
+    fn graph_playbook(steps: Vec<PlaybookStep>) -> Playbook {
+        Playbook {
+            id: Uuid::new_v4(),
+            name: "Graph Playbook".to_string(),
+            version: "1.0".to_string(),
+            revision: 0,
+            description: "Test".to_string(),
+            owner: "test".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            triggers: crate::models::PlaybookTriggers {
+                severity_trigger: vec![],
+                type_trigger: vec![],
+                source_trigger: vec![],
+            },
+            variables: HashMap::new(),
+            steps,
+            enabled: true,
+            tags: vec![],
+        }
+    }
+
+    fn approval_step(id: &str) -> PlaybookStep {
+        PlaybookStep {
+            id: id.to_string(),
+            step_type: StepType::Approval,
+            description: None,
+            actions: vec![],
+            parallel: false,
+            timeout: Some("30m".to_string()),
+            retry: 0,
+            backoff: BackoffStrategy::Fixed,
+            condition: None,
+            depends_on: None,
+            trigger_rule: TriggerRule::AllSuccess,
+            approval: Some(ApprovalPolicy {
+                approvers: vec!["alice".to_string()],
+                on_timeout: ApprovalTimeoutAction::Reject,
+            }),
+            idempotent: false,
+        }
+    }
+
+    fn decide(execution: &mut PlaybookExecution, approved: bool) {
+        execution.approvals[0].decision = Some(crate::models::ApprovalDecision {
+            approved,
+            decided_by: "alice".to_string(),
+            decided_at: Utc::now(),
+            comment: None,
+        });
+    }
+
+    fn status_of(execution: &PlaybookExecution, step_id: &str) -> ExecutionStatus {
+        execution.step_results[step_id].status.clone()
+    }
+
     fn create_test_incident() -> Incident {
         Incident::new(
             "test".to_string(),
@@ -428,6 +1223,7 @@ mod tests {
             id: Uuid::new_v4(),
             name: "Test Playbook".to_string(),
             version: "1.0".to_string(),
+            revision: 0,
             description: "Test".to_string(),
             owner: "test".to_string(),
             created_at: Utc::now(),
@@ -457,6 +1253,10 @@ mod tests {
                 retry: 0,
                 backoff: BackoffStrategy::Fixed,
                 condition: None,
+                depends_on: None,
+                trigger_rule: Default::default(),
+                approval: None,
+                idempotent: false,
             }],
             enabled: true,
             tags: vec![],
@@ -469,4 +1269,312 @@ mod tests {
         assert!(result.completed_at.is_some());
         assert_eq!(result.step_results.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_failure_branch_runs_compensation() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+
+        let mut check = check_step("check", false);
+        check.actions[0].on_success = Some("notify".to_string());
+        check.actions[0].on_failure = Some("rollback".to_string());
+        let mut verify = check_step("verify", true);
+        verify.depends_on = Some(vec!["notify".to_string(), "rollback".to_string()]);
+        verify.trigger_rule = TriggerRule::OneSuccess;
+
+        let playbook = graph_playbook(vec![
+            check,
+            check_step("notify", true),
+            check_step("rollback", true),
+            verify,
+        ]);
+
+        let mut context = ExecutionContext::new(create_test_incident());
+        let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        assert_eq!(status_of(&execution, "check"), ExecutionStatus::Failed);
+        assert_eq!(status_of(&execution, "notify"), ExecutionStatus::Cancelled);
+        assert_eq!(status_of(&execution, "rollback"), ExecutionStatus::Completed);
+        assert_eq!(status_of(&execution, "verify"), ExecutionStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_unhandled_failure_stops_execution() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+
+        let playbook = graph_playbook(vec![
+            check_step("first", false),
+            check_step("second", true),
+        ]);
+
+        let mut context = ExecutionContext::new(create_test_incident());
+        let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Failed);
+        assert!(execution.error.unwrap().contains("Step first failed"));
+        assert!(!execution.step_results.contains_key("second"));
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_and_fan_in() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+
+        let mut metrics = check_step("metrics", true);
+        metrics.depends_on = Some(vec![]);
+        let mut logs = check_step("logs", true);
+        logs.depends_on = Some(vec![]);
+        let mut analyze = check_step("analyze", true);
+        analyze.depends_on = Some(vec!["metrics".to_string(), "logs".to_string()]);
+
+        let playbook = graph_playbook(vec![metrics, logs, analyze]);
+
+        let mut context = ExecutionContext::new(create_test_incident());
+        let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        assert_eq!(execution.step_results.len(), 3);
+        assert!(context.get_step_output("metrics").is_some());
+        assert!(context.get_step_output("logs").is_some());
+        assert!(
+            execution.step_results["analyze"].started_at
+                >= execution.step_results["logs"].completed_at.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_approval_pauses_until_approved() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+        let incident = create_test_incident();
+
+        let mut parallel = check_step("snapshot", true);
+        parallel.depends_on = Some(vec![]);
+        let playbook = graph_playbook(vec![
+            check_step("check", true),
+            approval_step("approve"),
+            check_step("rollback", true),
+            parallel,
+        ]);
+
+        let mut context = ExecutionContext::new(incident.clone());
+        let mut execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::WaitingForApproval);
+        assert_eq!(execution.current_step.as_deref(), Some("approve"));
+        assert_eq!(status_of(&execution, "snapshot"), ExecutionStatus::Completed);
+        assert!(!execution.step_results.contains_key("rollback"));
+        let request = &execution.approvals[0];
+        assert_eq!(request.approvers, vec!["alice".to_string()]);
+        assert!(request.expires_at.unwrap() > request.requested_at);
+
+        decide(&mut execution, true);
+        let mut context = ExecutionContext::new(incident);
+        let execution = executor
+            .resume_playbook(&playbook, execution, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        assert_eq!(status_of(&execution, "approve"), ExecutionStatus::Completed);
+        assert_eq!(status_of(&execution, "rollback"), ExecutionStatus::Completed);
+        assert_eq!(
+            context.get_step_output("approve").unwrap()["decided_by"],
+            serde_json::json!("alice")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rejected_approval_cancels_execution() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+        let incident = create_test_incident();
+
+        let playbook = graph_playbook(vec![approval_step("approve"), check_step("rollback", true)]);
+
+        let mut context = ExecutionContext::new(incident.clone());
+        let mut execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        // Nothing decided yet, so resuming keeps waiting
+        let mut context = ExecutionContext::new(incident.clone());
+        execution = executor
+            .resume_playbook(&playbook, execution, &mut context)
+            .await
+            .unwrap();
+        assert_eq!(execution.status, ExecutionStatus::WaitingForApproval);
+
+        decide(&mut execution, false);
+        let mut context = ExecutionContext::new(incident);
+        let execution = executor
+            .resume_playbook(&playbook, execution, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Cancelled);
+        assert_eq!(execution.error.as_deref(), Some("Step approve rejected by alice"));
+        assert_eq!(status_of(&execution, "approve"), ExecutionStatus::Failed);
+        assert!(!execution.step_results.contains_key("rollback"));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_approval_does_not_pause() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+
+        let mut approve = approval_step("approve");
+        approve.condition = Some("incident.severity == 'P0'".to_string());
+        let playbook = graph_playbook(vec![approve, check_step("rollback", true)]);
+
+        let mut context = ExecutionContext::new(create_test_incident());
+        let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        assert!(execution.approvals.is_empty());
+        assert_eq!(status_of(&execution, "rollback"), ExecutionStatus::Completed);
+    }
+    #[tokio::test]
+    async fn test_checkpoints_progress_to_store() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store.clone());
+
+        let mut playbook =
+            graph_playbook(vec![check_step("first", true), check_step("second", true)]);
+        playbook.variables.insert("service".to_string(), "api".to_string());
+
+        let mut context = ExecutionContext::new(create_test_incident());
+        let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+
+        let saved = store.get_playbook_execution(&execution.id).await.unwrap().unwrap();
+        assert_eq!(saved.status, ExecutionStatus::Completed);
+        assert_eq!(status_of(&saved, "second"), ExecutionStatus::Completed);
+        assert_eq!(saved.variables["service"], serde_json::json!("api"));
+    }
+
+    /// An execution as it would be checkpointed while `step_id` was running
+    fn interrupt(execution: &mut PlaybookExecution, step_id: &str) {
+        execution.status = ExecutionStatus::Running;
+        execution.completed_at = None;
+        execution.current_step = Some(step_id.to_string());
+        let result = execution.step_results.get_mut(step_id).unwrap();
+        result.status = ExecutionStatus::Running;
+        result.completed_at = None;
+        result.output.clear();
+    }
+
+    #[tokio::test]
+    async fn test_recover_reruns_idempotent_steps() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store);
+        let incident = create_test_incident();
+
+        let mut second = check_step("second", true);
+        second.idempotent = true;
+        let playbook = graph_playbook(vec![check_step("first", true), second]);
+
+        let mut context = ExecutionContext::new(incident.clone());
+        let mut execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+        interrupt(&mut execution, "second");
+
+        let mut context = ExecutionContext::new(incident);
+        let execution = executor
+            .recover_playbook(&playbook, execution, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Completed);
+        assert_eq!(status_of(&execution, "second"), ExecutionStatus::Completed);
+        assert!(context.get_step_output("first").is_some());
+        assert!(execution.step_results["second"].output.contains_key("action_0.healthy"));
+    }
+
+    #[tokio::test]
+    async fn test_recover_abandons_non_idempotent_steps() {
+        let store = Arc::new(InMemoryStore::new());
+        let executor = stub_executor(store.clone());
+        let incident = create_test_incident();
+
+        let playbook = graph_playbook(vec![
+            check_step("first", true),
+            check_step("second", true),
+            check_step("third", true),
+        ]);
+
+        let mut context = ExecutionContext::new(incident.clone());
+        let mut execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+        interrupt(&mut execution, "second");
+        execution.step_results.remove("third");
+
+        let mut context = ExecutionContext::new(incident.clone());
+        let execution = executor
+            .recover_playbook(&playbook, execution, &mut context)
+            .await
+            .unwrap();
+
+        assert_eq!(execution.status, ExecutionStatus::Abandoned);
+        assert!(execution.error.as_deref().unwrap().contains("second"));
+        assert_eq!(status_of(&execution, "second"), ExecutionStatus::Abandoned);
+        assert!(!execution.step_results.contains_key("third"));
+
+        let saved = store.get_playbook_execution(&execution.id).await.unwrap().unwrap();
+        assert_eq!(saved.status, ExecutionStatus::Abandoned);
+
+        // Only interrupted executions can be recovered
+        let mut context = ExecutionContext::new(incident);
+        assert!(executor
+            .recover_playbook(&playbook, saved, &mut context)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_traces_without_side_effects() {
+        let mut page = check_step("page", true);
+        page.condition = Some("incident.severity == 'P0'".to_string());
+        let mut restart = check_step("restart", true);
+        restart.actions[0] = Action {
+            action_type: ActionType::ServiceRestart,
+            parameters: HashMap::from([
+                ("service".to_string(), serde_json::json!("{{service}}")),
+                ("reason".to_string(), serde_json::json!("{{incident.severity}} incident")),
+            ]),
+            on_success: None,
+            on_failure: Some("rollback".to_string()),
+        };
+        let mut playbook = graph_playbook(vec![
+            page,
+            approval_step("approve"),
+            restart,
+            check_step("rollback", true),
+        ]);
+        playbook.variables.insert("service".to_string(), "api".to_string());
+
+        let executor = PlaybookExecutor::dry_run();
+        let mut context = ExecutionContext::new(create_test_incident());
+        let simulation = executor.simulate_playbook(&playbook, &mut context).await.unwrap();
+
+        assert_eq!(simulation.status, ExecutionStatus::Completed);
+        let outcomes: Vec<SimulatedOutcome> = simulation.steps.iter().map(|s| s.outcome).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                SimulatedOutcome::ConditionFalse,
+                SimulatedOutcome::ApprovalAssumed,
+                SimulatedOutcome::Run,
+                SimulatedOutcome::Skipped,
+            ]
+        );
+        assert_eq!(simulation.steps[1].approvers, vec!["alice".to_string()]);
+
+        let restart = &simulation.steps[2].actions[0];
+        assert_eq!(restart.action_type, ActionType::ServiceRestart);
+        assert_eq!(restart.parameters["service"], serde_json::json!("api"));
+        assert_eq!(restart.parameters["reason"], serde_json::json!("P1 incident"));
+
+        // A regular executor acts for real, so it refuses to simulate
+        let executor = stub_executor(Arc::new(InMemoryStore::new()));
+        let mut context = ExecutionContext::new(create_test_incident());
+        assert!(executor.simulate_playbook(&playbook, &mut context).await.is_err());
+    }
 }
@@ -0,0 +1,833 @@
+//! Playbook condition expressions
+//!
+//! ```text
+//! expr       := and ("||" and)*
+//! and        := unary ("&&" unary)*
+//! unary      := "!" unary | comparison
+//! comparison := operand (("==" | "!=" | "<" | "<=" | ">" | ">=" | "in" | "not in") operand
+//!                        | ("=~" | "!~") string)?
+//! operand    := literal | "[" (expr ("," expr)*)? "]" | path | "$" name | "(" expr ")"
+//! path       := name ("." name | "." index | "[" index "]" | "[" string "]")*
+//! ```
+//!
+//! Paths start at `incident`, `steps.<id>.output`, `vars`, or name a variable
+//! directly, e.g. `steps.collect.output.error_rate > 0.05 && incident.severity in ["P0", "P1"]`.
+
+use crate::error::{AppError, Result};
+use regex::Regex;
+use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A parsed condition
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Node,
+}
+
+/// Resolves paths against execution state
+pub trait PathResolver {
+    fn resolve(&self, path: &Path) -> Result<JsonValue>;
+}
+
+impl Expression {
+    /// Parse an expression, reporting the column of the first error
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = lex(source).map_err(|e| e.into_error(source))?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: source.chars().count() + 1,
+        };
+        let root = parser.parse().map_err(|e| e.into_error(source))?;
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// Expression text as written
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate to a value
+    pub fn evaluate(&self, resolver: &dyn PathResolver) -> Result<JsonValue> {
+        self.root.evaluate(resolver)
+    }
+
+    /// Evaluate as a condition
+    pub fn evaluate_bool(&self, resolver: &dyn PathResolver) -> Result<bool> {
+        truthy(&self.evaluate(resolver)?)
+    }
+}
+
+/// A dotted path such as `steps.collect.output.error_rate`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    variable: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl Path {
+    /// Parse a path on its own, as used by `{{...}}` templates
+    pub fn parse(source: &str) -> Result<Self> {
+        let tokens = lex(source).map_err(|e| e.into_error(source))?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: source.chars().count() + 1,
+        };
+        let path = match parser.next() {
+            Some((Token::Ident(name), _)) => parser.path(name, false),
+            Some((Token::Variable(name), _)) => parser.path(name, true),
+            _ => Err(ParseError::new(1, "expected a path")),
+        }
+        .and_then(|path| parser.expect_end().map(|_| path))
+        .map_err(|e| e.into_error(source))?;
+        Ok(path)
+    }
+
+    /// Whether the path was written `$name`, which always names a variable
+    pub fn is_variable(&self) -> bool {
+        self.variable
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// First segment, e.g. `incident`
+    pub fn root(&self) -> &str {
+        match self.segments.first() {
+            Some(Segment::Key(key)) => key,
+            _ => "",
+        }
+    }
+
+    /// Follow `segments` into `value`; missing entries are null
+    pub fn walk(value: &JsonValue, segments: &[Segment]) -> JsonValue {
+        let mut current = value;
+        for segment in segments {
+            let next = match (segment, current) {
+                (Segment::Key(key), JsonValue::Object(map)) => map.get(key),
+                (Segment::Index(i), JsonValue::Array(items)) => items.get(*i),
+                _ => None,
+            };
+            match next {
+                Some(value) => current = value,
+                None => return JsonValue::Null,
+            }
+        }
+        current.clone()
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.variable {
+            write!(f, "$")?;
+        }
+        for (i, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if i == 0 => write!(f, "{}", key)?,
+                Segment::Key(key) => write!(f, ".{}", key)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Literal(JsonValue),
+    List(Vec<Node>),
+    Path(Path),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(Box<Node>, CompareOp, Box<Node>),
+    Matches(Box<Node>, Regex, bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+    NotIn,
+}
+
+impl Node {
+    fn evaluate(&self, resolver: &dyn PathResolver) -> Result<JsonValue> {
+        match self {
+            Node::Literal(value) => Ok(value.clone()),
+            Node::List(items) => Ok(JsonValue::Array(
+                items
+                    .iter()
+                    .map(|item| item.evaluate(resolver))
+                    .collect::<Result<_>>()?,
+            )),
+            Node::Path(path) => resolver.resolve(path),
+            Node::Not(inner) => Ok(JsonValue::Bool(!truthy(&inner.evaluate(resolver)?)?)),
+            Node::And(left, right) => Ok(JsonValue::Bool(
+                truthy(&left.evaluate(resolver)?)? && truthy(&right.evaluate(resolver)?)?,
+            )),
+            Node::Or(left, right) => Ok(JsonValue::Bool(
+                truthy(&left.evaluate(resolver)?)? || truthy(&right.evaluate(resolver)?)?,
+            )),
+            Node::Compare(left, op, right) => {
+                let left = left.evaluate(resolver)?;
+                let right = right.evaluate(resolver)?;
+                let result = match op {
+                    CompareOp::Eq => values_equal(&left, &right),
+                    CompareOp::Ne => !values_equal(&left, &right),
+                    CompareOp::In => contains(&right, &left)?,
+                    CompareOp::NotIn => !contains(&right, &left)?,
+                    CompareOp::Lt | CompareOp::Le | CompareOp::Gt | CompareOp::Ge => {
+                        match compare(&left, &right)? {
+                            // Missing values satisfy no ordering
+                            None => false,
+                            Some(ordering) => match op {
+                                CompareOp::Lt => ordering == Ordering::Less,
+                                CompareOp::Le => ordering != Ordering::Greater,
+                                CompareOp::Gt => ordering == Ordering::Greater,
+                                _ => ordering != Ordering::Less,
+                            },
+                        }
+                    }
+                };
+                Ok(JsonValue::Bool(result))
+            }
+            Node::Matches(subject, regex, negated) => {
+                let matched = match subject.evaluate(resolver)? {
+                    JsonValue::String(s) => regex.is_match(&s),
+                    JsonValue::Null => false,
+                    JsonValue::Number(n) => regex.is_match(&n.to_string()),
+                    other => {
+                        return Err(AppError::Validation(format!(
+                            "Cannot match {} against a regex",
+                            type_name(&other)
+                        )))
+                    }
+                };
+                Ok(JsonValue::Bool(matched != *negated))
+            }
+        }
+    }
+}
+
+fn type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Null => "null",
+        JsonValue::Bool(_) => "boolean",
+        JsonValue::Number(_) => "number",
+        JsonValue::String(_) => "string",
+        JsonValue::Array(_) => "list",
+        JsonValue::Object(_) => "object",
+    }
+}
+
+/// Playbook variables are strings, so `"true"`/`"false"` count as booleans
+fn truthy(value: &JsonValue) -> Result<bool> {
+    match value {
+        JsonValue::Bool(b) => Ok(*b),
+        JsonValue::Null => Ok(false),
+        JsonValue::String(s) if s == "true" => Ok(true),
+        JsonValue::String(s) if s == "false" => Ok(false),
+        other => Err(AppError::Validation(format!(
+            "Expected a boolean, found {} {}",
+            type_name(other),
+            other
+        ))),
+    }
+}
+
+/// Numbers, including numeric strings, compare by value
+fn as_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn values_equal(left: &JsonValue, right: &JsonValue) -> bool {
+    match (left, right) {
+        (JsonValue::Number(_), _) | (_, JsonValue::Number(_)) => {
+            match (as_number(left), as_number(right)) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            }
+        }
+        _ => left == right,
+    }
+}
+
+fn compare(left: &JsonValue, right: &JsonValue) -> Result<Option<Ordering>> {
+    if left.is_null() || right.is_null() {
+        return Ok(None);
+    }
+    if let (JsonValue::String(a), JsonValue::String(b)) = (left, right) {
+        if as_number(left).is_none() || as_number(right).is_none() {
+            return Ok(Some(a.cmp(b)));
+        }
+    }
+    match (as_number(left), as_number(right)) {
+        (Some(a), Some(b)) => Ok(a.partial_cmp(&b)),
+        _ => Err(AppError::Validation(format!(
+            "Cannot compare {} {} with {} {}",
+            type_name(left),
+            left,
+            type_name(right),
+            right
+        ))),
+    }
+}
+
+fn contains(haystack: &JsonValue, needle: &JsonValue) -> Result<bool> {
+    match haystack {
+        JsonValue::Array(items) => Ok(items.iter().any(|item| values_equal(item, needle))),
+        JsonValue::String(s) => match needle {
+            JsonValue::String(n) => Ok(s.contains(n.as_str())),
+            other => Ok(s.contains(&other.to_string())),
+        },
+        JsonValue::Object(map) => Ok(needle.as_str().is_some_and(|key| map.contains_key(key))),
+        JsonValue::Null => Ok(false),
+        other => Err(AppError::Validation(format!(
+            "'in' needs a list, string or object, found {}",
+            type_name(other)
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Variable(String),
+    Number(JsonValue),
+    Str(String),
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Match,
+    NotMatch,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Comma,
+    Dot,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(name) => write!(f, "'{}'", name),
+            Token::Variable(name) => write!(f, "'${}'", name),
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Str(s) => write!(f, "{:?}", s),
+            Token::And => write!(f, "'&&'"),
+            Token::Or => write!(f, "'||'"),
+            Token::Not => write!(f, "'!'"),
+            Token::Eq => write!(f, "'=='"),
+            Token::Ne => write!(f, "'!='"),
+            Token::Lt => write!(f, "'<'"),
+            Token::Le => write!(f, "'<='"),
+            Token::Gt => write!(f, "'>'"),
+            Token::Ge => write!(f, "'>='"),
+            Token::Match => write!(f, "'=~'"),
+            Token::NotMatch => write!(f, "'!~'"),
+            Token::LParen => write!(f, "'('"),
+            Token::RParen => write!(f, "')'"),
+            Token::LBracket => write!(f, "'['"),
+            Token::RBracket => write!(f, "']'"),
+            Token::Comma => write!(f, "','"),
+            Token::Dot => write!(f, "'.'"),
+        }
+    }
+}
+
+/// Parse error at a 1-based column
+struct ParseError {
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    fn new(column: usize, message: impl Into<String>) -> Self {
+        Self {
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn into_error(self, source: &str) -> AppError {
+        AppError::Validation(format!(
+            "Invalid expression '{}': {} at column {}",
+            source, self.message, self.column
+        ))
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn lex(source: &str) -> ParseResult<Vec<(Token, usize)>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let column = i + 1;
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let two = |token| Some((token, 2));
+        let symbol = match (c, next) {
+            ('&', Some('&')) => two(Token::And),
+            ('|', Some('|')) => two(Token::Or),
+            ('=', Some('=')) => two(Token::Eq),
+            ('=', Some('~')) => two(Token::Match),
+            ('!', Some('=')) => two(Token::Ne),
+            ('!', Some('~')) => two(Token::NotMatch),
+            ('<', Some('=')) => two(Token::Le),
+            ('>', Some('=')) => two(Token::Ge),
+            ('!', _) => Some((Token::Not, 1)),
+            ('<', _) => Some((Token::Lt, 1)),
+            ('>', _) => Some((Token::Gt, 1)),
+            ('(', _) => Some((Token::LParen, 1)),
+            (')', _) => Some((Token::RParen, 1)),
+            ('[', _) => Some((Token::LBracket, 1)),
+            (']', _) => Some((Token::RBracket, 1)),
+            (',', _) => Some((Token::Comma, 1)),
+            ('.', _) => Some((Token::Dot, 1)),
+            _ => None,
+        };
+        if let Some((token, len)) = symbol {
+            tokens.push((token, column));
+            i += len;
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i).copied() {
+                    None => return Err(ParseError::new(column, "unterminated string")),
+                    Some(q) if q == c => break,
+                    Some('\\') => {
+                        let escaped = match chars.get(i + 1).copied() {
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some(other) => other,
+                            None => return Err(ParseError::new(column, "unterminated string")),
+                        };
+                        value.push(escaped);
+                        i += 2;
+                    }
+                    Some(other) => {
+                        value.push(other);
+                        i += 1;
+                    }
+                }
+            }
+            tokens.push((Token::Str(value), column));
+            i += 1;
+            continue;
+        }
+
+        let negative_number = c == '-' && next.is_some_and(|n| n.is_ascii_digit());
+        if c.is_ascii_digit() || negative_number {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let mut float = false;
+            if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()) {
+                float = true;
+                i += 1;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = if float {
+                text.parse::<f64>()
+                    .ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .map(JsonValue::Number)
+            } else {
+                text.parse::<i64>()
+                    .ok()
+                    .map(|n| JsonValue::Number(n.into()))
+            };
+            let number = number
+                .ok_or_else(|| ParseError::new(column, format!("invalid number {}", text)))?;
+            tokens.push((Token::Number(number), column));
+            continue;
+        }
+
+        if c == '$' || is_ident_start(c) {
+            let start = if c == '$' { i + 1 } else { i };
+            i = start;
+            while i < chars.len() && is_ident_char(chars[i]) {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            if name.is_empty() {
+                return Err(ParseError::new(
+                    column,
+                    "expected a variable name after '$'",
+                ));
+            }
+            let token = if c == '$' {
+                Token::Variable(name)
+            } else {
+                Token::Ident(name)
+            };
+            tokens.push((token, column));
+            continue;
+        }
+
+        return Err(ParseError::new(
+            column,
+            format!("unexpected character '{}'", c),
+        ));
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Column reported for errors at the end of input
+    end: usize,
+}
+
+impl Parser {
+    fn parse(&mut self) -> ParseResult<Node> {
+        let node = self.or()?;
+        self.expect_end()?;
+        Ok(node)
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn column(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, column)| *column)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize)> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(token) => ParseError::new(
+                self.column(),
+                format!("expected {}, found {}", expected, token),
+            ),
+            None => ParseError::new(
+                self.end,
+                format!("expected {}, found end of input", expected),
+            ),
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> ParseResult<()> {
+        if self.eat(&token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn expect_end(&self) -> ParseResult<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.unexpected("an operator or end of input")),
+        }
+    }
+
+    fn or(&mut self) -> ParseResult<Node> {
+        let mut node = self.and()?;
+        while self.eat(&Token::Or) {
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> ParseResult<Node> {
+        let mut node = self.unary()?;
+        while self.eat(&Token::And) {
+            node = Node::And(Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> ParseResult<Node> {
+        if self.eat(&Token::Not) {
+            return Ok(Node::Not(Box::new(self.unary()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> ParseResult<Node> {
+        let left = self.operand()?;
+
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::Ne) => CompareOp::Ne,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::Le) => CompareOp::Le,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::Ge) => CompareOp::Ge,
+            Some(Token::Ident(word)) if word == "in" => CompareOp::In,
+            Some(Token::Ident(word)) if word == "not" => {
+                self.pos += 1;
+                if !matches!(self.peek(), Some(Token::Ident(word)) if word == "in") {
+                    return Err(self.unexpected("'in' after 'not'"));
+                }
+                CompareOp::NotIn
+            }
+            Some(Token::Match) | Some(Token::NotMatch) => {
+                let negated = self.peek() == Some(&Token::NotMatch);
+                self.pos += 1;
+                let column = self.column();
+                let pattern = match self.peek() {
+                    Some(Token::Str(pattern)) => pattern.clone(),
+                    _ => return Err(self.unexpected("a regex string")),
+                };
+                self.pos += 1;
+                let regex = Regex::new(&pattern)
+                    .map_err(|e| ParseError::new(column, format!("invalid regex: {}", e)))?;
+                return Ok(Node::Matches(Box::new(left), regex, negated));
+            }
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+
+        let right = self.operand()?;
+        Ok(Node::Compare(Box::new(left), op, Box::new(right)))
+    }
+
+    fn operand(&mut self) -> ParseResult<Node> {
+        let column = self.column();
+        match self.next() {
+            Some((Token::Number(n), _)) => Ok(Node::Literal(n)),
+            Some((Token::Str(s), _)) => Ok(Node::Literal(JsonValue::String(s))),
+            Some((Token::Ident(word), _)) if word == "true" => {
+                Ok(Node::Literal(JsonValue::Bool(true)))
+            }
+            Some((Token::Ident(word), _)) if word == "false" => {
+                Ok(Node::Literal(JsonValue::Bool(false)))
+            }
+            Some((Token::Ident(word), _)) if word == "null" => Ok(Node::Literal(JsonValue::Null)),
+            Some((Token::Ident(word), _)) if word == "in" || word == "not" => Err(ParseError::new(
+                column,
+                format!("expected a value, found '{}'", word),
+            )),
+            Some((Token::Ident(name), _)) => self.path(name, false).map(Node::Path),
+            Some((Token::Variable(name), _)) => self.path(name, true).map(Node::Path),
+            Some((Token::LParen, _)) => {
+                let node = self.or()?;
+                self.expect(Token::RParen, "')'")?;
+                Ok(node)
+            }
+            Some((Token::LBracket, _)) => {
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.or()?);
+                        if self.eat(&Token::RBracket) {
+                            break;
+                        }
+                        self.expect(Token::Comma, "',' or ']'")?;
+                    }
+                }
+                Ok(Node::List(items))
+            }
+            Some(_) => {
+                self.pos -= 1;
+                Err(self.unexpected("a value"))
+            }
+            None => Err(self.unexpected("a value")),
+        }
+    }
+
+    fn path(&mut self, first: String, variable: bool) -> ParseResult<Path> {
+        let mut segments = vec![Segment::Key(first)];
+        loop {
+            if self.eat(&Token::Dot) {
+                match self.next() {
+                    Some((Token::Ident(key), _)) => segments.push(Segment::Key(key)),
+                    Some((Token::Number(JsonValue::Number(n)), _)) if n.is_u64() => {
+                        segments.push(Segment::Index(n.as_u64().unwrap_or_default() as usize))
+                    }
+                    Some(_) => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("a field name"));
+                    }
+                    None => return Err(self.unexpected("a field name")),
+                }
+            } else if self.eat(&Token::LBracket) {
+                match self.next() {
+                    Some((Token::Number(JsonValue::Number(n)), _)) if n.is_u64() => {
+                        segments.push(Segment::Index(n.as_u64().unwrap_or_default() as usize))
+                    }
+                    Some((Token::Str(key), _)) => segments.push(Segment::Key(key)),
+                    Some(_) => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("an index or quoted key"));
+                    }
+                    None => return Err(self.unexpected("an index or quoted key")),
+                }
+                self.expect(Token::RBracket, "']'")?;
+            } else {
+                return Ok(Path { variable, segments });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Values(JsonValue);
+
+    impl PathResolver for Values {
+        fn resolve(&self, path: &Path) -> Result<JsonValue> {
+            Ok(Path::walk(&self.0, path.segments()))
+        }
+    }
+
+    fn eval(expr: &str) -> bool {
+        let values = Values(json!({
+            "incident": { "severity": "P1", "labels": { "team": "payments" } },
+            "steps": { "collect": { "output": { "error_rate": 0.12, "hosts": ["a", "b"] } } },
+            "count": "5",
+        }));
+        Expression::parse(expr)
+            .unwrap()
+            .evaluate_bool(&values)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_operators() {
+        assert!(eval("steps.collect.output.error_rate > 0.05"));
+        assert!(eval("incident.severity in [\"P0\", \"P1\"]"));
+        assert!(eval("incident.severity not in ['P3']"));
+        assert!(eval("'b' in steps.collect.output.hosts"));
+        assert!(eval("steps.collect.output.hosts[1] == 'b'"));
+        assert!(eval("incident.labels.team =~ '^pay'"));
+        assert!(eval("incident.labels.team !~ 'search'"));
+        assert!(eval("count >= 5 && count < 10"));
+        assert!(eval("$count == 5"));
+        assert!(eval("!(incident.severity == 'P0') || false"));
+        assert!(eval(
+            "incident.severity == 'P0' || incident.severity == 'P1' && count == 5"
+        ));
+        assert!(!eval("steps.missing.output.value > 1"));
+        assert!(!eval("steps.missing.output.value"));
+    }
+
+    #[test]
+    fn test_type_errors() {
+        let values = Values(json!({ "name": "api", "items": [1] }));
+        let error = Expression::parse("name && true")
+            .unwrap()
+            .evaluate_bool(&values)
+            .unwrap_err();
+        assert!(error.to_string().contains("Expected a boolean"));
+        assert!(Expression::parse("items > 1")
+            .unwrap()
+            .evaluate(&values)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_report_column() {
+        let error = Expression::parse("incident.severity == ")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("expected a value, found end of input at column 22"),
+            "{}",
+            error
+        );
+
+        let error = Expression::parse("a == 1 b").unwrap_err().to_string();
+        assert!(error.contains("found 'b' at column 8"), "{}", error);
+
+        let error = Expression::parse("name =~ '('").unwrap_err().to_string();
+        assert!(error.contains("invalid regex"), "{}", error);
+
+        assert!(Expression::parse("(a == 1").is_err());
+        assert!(Expression::parse("a == 'open").is_err());
+        assert!(Expression::parse("a # b").is_err());
+    }
+
+    #[test]
+    fn test_path_parse() {
+        let path = Path::parse(" steps.collect.output.action_0.error_rate ").unwrap();
+        assert_eq!(path.root(), "steps");
+        assert_eq!(path.segments().len(), 5);
+        assert_eq!(path.to_string(), "steps.collect.output.action_0.error_rate");
+        assert!(Path::parse("$service").unwrap().is_variable());
+        assert!(Path::parse("a == b").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+//! Playbook step graph
+//!
+//! Steps are connected two ways: a step waits for its `depends_on` steps (or
+//! the previous step in the list when it declares none), and an action's
+//! `on_success`/`on_failure` activates a branch step. A step that some action
+//! branches to only runs when it is activated.
+
+use crate::error::{AppError, Result};
+use crate::models::{Playbook, PlaybookStep, TriggerRule};
+use std::collections::{HashMap, VecDeque};
+
+/// Validated dependency and branch structure of a playbook
+#[derive(Debug, Clone)]
+pub struct PlaybookGraph<'a> {
+    steps: Vec<&'a PlaybookStep>,
+    index: HashMap<&'a str, usize>,
+    /// Steps each step waits for
+    dependencies: Vec<Vec<usize>>,
+    /// Steps whose actions branch to each step
+    branch_sources: Vec<Vec<usize>>,
+}
+
+impl<'a> PlaybookGraph<'a> {
+    /// Build the graph, rejecting unknown references, steps that can never
+    /// start and cycles
+    pub fn build(playbook: &'a Playbook) -> Result<Self> {
+        let mut index = HashMap::new();
+        for (i, step) in playbook.steps.iter().enumerate() {
+            if index.insert(step.id.as_str(), i).is_some() {
+                return Err(AppError::Validation(format!(
+                    "Duplicate step id '{}'",
+                    step.id
+                )));
+            }
+        }
+
+        let lookup = |from: &str, to: &str, relation: &str| {
+            index.get(to).copied().ok_or_else(|| {
+                AppError::Validation(format!(
+                    "Step '{}' {} unknown step '{}'",
+                    from, relation, to
+                ))
+            })
+        };
+
+        let mut branch_sources = vec![Vec::new(); playbook.steps.len()];
+        for (i, step) in playbook.steps.iter().enumerate() {
+            for action in &step.actions {
+                for target in [&action.on_success, &action.on_failure]
+                    .into_iter()
+                    .flatten()
+                {
+                    let target = lookup(&step.id, target, "branches to")?;
+                    if !branch_sources[target].contains(&i) {
+                        branch_sources[target].push(i);
+                    }
+                }
+            }
+        }
+
+        let mut dependencies = Vec::with_capacity(playbook.steps.len());
+        let mut previous = None;
+        for (i, step) in playbook.steps.iter().enumerate() {
+            let is_branch = !branch_sources[i].is_empty();
+            let deps = match &step.depends_on {
+                Some(ids) => ids
+                    .iter()
+                    .map(|id| lookup(&step.id, id, "depends on"))
+                    .collect::<Result<Vec<_>>>()?,
+                None if is_branch => Vec::new(),
+                None => previous.into_iter().collect(),
+            };
+            dependencies.push(deps);
+            if !is_branch {
+                previous = Some(i);
+            }
+        }
+
+        let graph = Self {
+            steps: playbook.steps.iter().collect(),
+            index,
+            dependencies,
+            branch_sources,
+        };
+        graph.check_reachable()?;
+        graph.check_cycles()?;
+        Ok(graph)
+    }
+
+    /// Steps in playbook order
+    pub fn steps(&self) -> &[&'a PlaybookStep] {
+        &self.steps
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub fn step(&self, i: usize) -> &'a PlaybookStep {
+        self.steps[i]
+    }
+
+    /// Position of a step by id
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.index.get(id).copied()
+    }
+
+    /// Steps `i` waits for
+    pub fn dependencies(&self, i: usize) -> &[usize] {
+        &self.dependencies[i]
+    }
+
+    /// Steps whose actions branch to `i`
+    pub fn branch_sources(&self, i: usize) -> &[usize] {
+        &self.branch_sources[i]
+    }
+
+    /// Whether `i` only runs when an action branches to it
+    pub fn is_branch_target(&self, i: usize) -> bool {
+        !self.branch_sources[i].is_empty()
+    }
+
+    pub fn trigger_rule(&self, i: usize) -> TriggerRule {
+        self.steps[i].trigger_rule
+    }
+
+    /// Steps that can start on their own
+    pub fn roots(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.dependencies[i].is_empty() && !self.is_branch_target(i))
+            .collect()
+    }
+
+    /// Steps that follow `i`, through dependencies or branches
+    fn successors(&self, i: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(move |&j| {
+            self.dependencies[j].contains(&i) || self.branch_sources[j].contains(&i)
+        })
+    }
+
+    fn check_cycles(&self) -> Result<()> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Mark {
+            New,
+            Active,
+            Done,
+        }
+
+        fn visit(
+            graph: &PlaybookGraph<'_>,
+            i: usize,
+            marks: &mut [Mark],
+            path: &mut Vec<usize>,
+        ) -> Result<()> {
+            marks[i] = Mark::Active;
+            path.push(i);
+            for j in graph.successors(i) {
+                match marks[j] {
+                    Mark::Active => {
+                        let start = path.iter().position(|&p| p == j).unwrap_or(0);
+                        let cycle: Vec<&str> = path[start..]
+                            .iter()
+                            .chain(std::iter::once(&j))
+                            .map(|&p| graph.steps[p].id.as_str())
+                            .collect();
+                        return Err(AppError::Validation(format!(
+                            "Playbook steps form a cycle: {}",
+                            cycle.join(" -> ")
+                        )));
+                    }
+                    Mark::New => visit(graph, j, marks, path)?,
+                    Mark::Done => {}
+                }
+            }
+            path.pop();
+            marks[i] = Mark::Done;
+            Ok(())
+        }
+
+        let mut marks = vec![Mark::New; self.len()];
+        for i in 0..self.len() {
+            if marks[i] == Mark::New {
+                visit(self, i, &mut marks, &mut Vec::new())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_reachable(&self) -> Result<()> {
+        let mut reached = vec![false; self.len()];
+        let mut queue: VecDeque<usize> = self.roots().into();
+        for &i in &queue {
+            reached[i] = true;
+        }
+        while let Some(i) = queue.pop_front() {
+            for j in self.successors(i) {
+                if !reached[j] {
+                    reached[j] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+
+        let unreachable: Vec<&str> = (0..self.len())
+            .filter(|&i| !reached[i])
+            .map(|i| self.steps[i].id.as_str())
+            .collect();
+        if unreachable.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Validation(format!(
+                "Playbook steps can never run: {}",
+                unreachable.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Action, ActionType, BackoffStrategy, PlaybookTriggers, StepType};
+    use uuid::Uuid;
+
+    fn step(id: &str, depends_on: Option<&[&str]>) -> PlaybookStep {
+        PlaybookStep {
+            id: id.to_string(),
+            step_type: StepType::Custom,
+            description: None,
+            actions: vec![Action {
+                action_type: ActionType::Wait,
+                parameters: HashMap::new(),
+                on_success: None,
+                on_failure: None,
+            }],
+            parallel: false,
+            timeout: None,
+            retry: 0,
+            backoff: BackoffStrategy::Fixed,
+            condition: None,
+            depends_on: depends_on.map(|ids| ids.iter().map(|id| id.to_string()).collect()),
+            trigger_rule: TriggerRule::default(),
+            approval: None,
+            idempotent: false,
+        }
+    }
+
+    fn playbook(steps: Vec<PlaybookStep>) -> Playbook {
+        Playbook {
+            id: Uuid::new_v4(),
+            name: "graph".to_string(),
+            version: "1".to_string(),
+            revision: 0,
+            description: String::new(),
+            owner: "test".to_string(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            triggers: PlaybookTriggers {
+                severity_trigger: vec![],
+                type_trigger: vec![],
+                source_trigger: vec![],
+            },
+            variables: HashMap::new(),
+            steps,
+            enabled: true,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_steps_without_dependencies_run_in_order() {
+        let playbook = playbook(vec![step("a", None), step("b", None), step("c", None)]);
+        let graph = PlaybookGraph::build(&playbook).unwrap();
+
+        assert_eq!(graph.roots(), vec![0]);
+        assert_eq!(graph.dependencies(1), &[0]);
+        assert_eq!(graph.dependencies(2), &[1]);
+    }
+
+    #[test]
+    fn test_branch_targets_are_not_chained() {
+        let mut check = step("check", None);
+        check.actions[0].on_success = Some("notify".to_string());
+        check.actions[0].on_failure = Some("restart".to_string());
+        let mut join = step("join", Some(&["notify", "restart"]));
+        join.trigger_rule = TriggerRule::OneSuccess;
+
+        let playbook = playbook(vec![
+            check,
+            step("restart", None),
+            step("notify", None),
+            join,
+        ]);
+        let graph = PlaybookGraph::build(&playbook).unwrap();
+
+        let restart = graph.position("restart").unwrap();
+        assert!(graph.is_branch_target(restart));
+        assert!(graph.dependencies(restart).is_empty());
+        assert_eq!(graph.branch_sources(restart), &[0]);
+        assert_eq!(graph.dependencies(3), &[2, 1]);
+    }
+
+    #[test]
+    fn test_fan_out_roots() {
+        let playbook = playbook(vec![
+            step("metrics", Some(&[])),
+            step("logs", Some(&[])),
+            step("analyze", Some(&["metrics", "logs"])),
+        ]);
+        let graph = PlaybookGraph::build(&playbook).unwrap();
+        assert_eq!(graph.roots(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_graphs() {
+        let unknown = playbook(vec![step("a", Some(&["missing"]))]);
+        let error = PlaybookGraph::build(&unknown).unwrap_err().to_string();
+        assert!(error.contains("unknown step 'missing'"));
+
+        let duplicate = playbook(vec![step("a", None), step("a", None)]);
+        assert!(PlaybookGraph::build(&duplicate).is_err());
+
+        let cycle = playbook(vec![
+            step("a", Some(&[])),
+            step("b", Some(&["a", "c"])),
+            step("c", Some(&["b"])),
+        ]);
+        let error = PlaybookGraph::build(&cycle).unwrap_err().to_string();
+        assert!(error.contains("b -> c -> b"), "{}", error);
+
+        // A branch back to an earlier step is a cycle too
+        let mut retry = step("b", None);
+        retry.actions[0].on_failure = Some("a".to_string());
+        let mut start = step("a", Some(&[]));
+        start.actions[0].on_success = Some("b".to_string());
+        let loop_back = playbook(vec![start, retry]);
+        assert!(PlaybookGraph::build(&loop_back).is_err());
+
+        // Steps that only wait on each other are never started
+        let unreachable = playbook(vec![
+            step("a", None),
+            step("b", Some(&["c"])),
+            step("c", Some(&["b"])),
+        ]);
+        let error = PlaybookGraph::build(&unreachable).unwrap_err().to_string();
+        assert!(error.contains("can never run: b, c"), "{}", error);
+    }
+}
@@ -1,9 +1,27 @@
 pub mod actions;
+pub mod config;
 pub mod context;
 pub mod executor;
+pub mod expression;
+pub mod graph;
+pub mod remediation;
+pub mod revisions;
+pub mod script;
 pub mod service;
 
-pub use actions::{create_default_registry, ActionExecutor, ActionExecutorRegistry, ActionResult};
+pub use actions::{
+    create_default_registry, create_recording_registry, create_registry, ActionExecutor,
+    ActionExecutorRegistry, ActionResult, RecordingActionExecutor,
+};
+pub use config::{PlaybookConfig, RemediationConfig, RemediationTargetConfig, ScriptConfig};
 pub use context::ExecutionContext;
 pub use executor::PlaybookExecutor;
-pub use service::PlaybookService;
+pub use expression::{Expression, PathResolver};
+pub use graph::PlaybookGraph;
+pub use remediation::{
+    HttpControlPlaneTarget, KubernetesTarget, ProcessTarget, RecordingRemediationTarget,
+    RemediationOperation, RemediationOutcome, RemediationRequest, RemediationTarget,
+    RemediationTargets,
+};
+pub use script::{ScriptInvocation, ScriptOutput, ScriptSandbox};
+pub use service::{validate_playbook, PlaybookService};
@@ -0,0 +1,1291 @@
+//! Remediation targets
+//!
+//! Remediation actions (restarts, scaling, rollbacks, ...) describe *what* to
+//! do; a [`RemediationTarget`] decides *how*. Targets talk to an HTTP control
+//! plane, run local commands or call a Kubernetes API server, and tests swap
+//! in a [`RecordingRemediationTarget`].
+
+use crate::error::{AppError, Result};
+use crate::models::ActionType;
+use crate::playbooks::config::{RemediationConfig, RemediationTargetConfig};
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::info;
+use uuid::Uuid;
+
+/// Operation a remediation target is asked to perform
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationOperation {
+    MetricsSnapshot,
+    LogsCapture,
+    HealthCheck,
+    ServiceRestart,
+    ServiceRollback,
+    ScaleHorizontal,
+    ScaleVertical,
+    ConfigChange,
+    CircuitBreaker,
+}
+
+impl RemediationOperation {
+    pub const ALL: [RemediationOperation; 9] = [
+        RemediationOperation::MetricsSnapshot,
+        RemediationOperation::LogsCapture,
+        RemediationOperation::HealthCheck,
+        RemediationOperation::ServiceRestart,
+        RemediationOperation::ServiceRollback,
+        RemediationOperation::ScaleHorizontal,
+        RemediationOperation::ScaleVertical,
+        RemediationOperation::ConfigChange,
+        RemediationOperation::CircuitBreaker,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemediationOperation::MetricsSnapshot => "metrics_snapshot",
+            RemediationOperation::LogsCapture => "logs_capture",
+            RemediationOperation::HealthCheck => "health_check",
+            RemediationOperation::ServiceRestart => "service_restart",
+            RemediationOperation::ServiceRollback => "service_rollback",
+            RemediationOperation::ScaleHorizontal => "scale_horizontal",
+            RemediationOperation::ScaleVertical => "scale_vertical",
+            RemediationOperation::ConfigChange => "config_change",
+            RemediationOperation::CircuitBreaker => "circuit_breaker",
+        }
+    }
+
+    /// Playbook action that performs this operation
+    pub fn action_type(&self) -> ActionType {
+        match self {
+            RemediationOperation::MetricsSnapshot => ActionType::MetricsSnapshot,
+            RemediationOperation::LogsCapture => ActionType::LogsCapture,
+            RemediationOperation::HealthCheck => ActionType::HealthCheck,
+            RemediationOperation::ServiceRestart => ActionType::ServiceRestart,
+            RemediationOperation::ServiceRollback => ActionType::ServiceRollback,
+            RemediationOperation::ScaleHorizontal => ActionType::ScaleHorizontal,
+            RemediationOperation::ScaleVertical => ActionType::ScaleVertical,
+            RemediationOperation::ConfigChange => ActionType::ConfigChange,
+            RemediationOperation::CircuitBreaker => ActionType::CircuitBreaker,
+        }
+    }
+
+    /// Operation behind a playbook action, if it is a remediation action
+    pub fn from_action(action_type: &ActionType) -> Option<Self> {
+        match action_type {
+            ActionType::MetricsSnapshot => Some(RemediationOperation::MetricsSnapshot),
+            ActionType::LogsCapture => Some(RemediationOperation::LogsCapture),
+            ActionType::HealthCheck => Some(RemediationOperation::HealthCheck),
+            ActionType::ServiceRestart => Some(RemediationOperation::ServiceRestart),
+            ActionType::ServiceRollback => Some(RemediationOperation::ServiceRollback),
+            ActionType::ScaleHorizontal => Some(RemediationOperation::ScaleHorizontal),
+            ActionType::ScaleVertical => Some(RemediationOperation::ScaleVertical),
+            ActionType::ConfigChange => Some(RemediationOperation::ConfigChange),
+            ActionType::CircuitBreaker => Some(RemediationOperation::CircuitBreaker),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RemediationOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RemediationOperation {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|op| op.as_str() == s)
+            .ok_or_else(|| AppError::Validation(format!("Unknown remediation operation: {}", s)))
+    }
+}
+
+/// A remediation operation for one incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationRequest {
+    pub operation: RemediationOperation,
+
+    /// Service the operation applies to
+    pub service: Option<String>,
+
+    /// Incident the playbook is running for
+    pub incident_id: Uuid,
+
+    /// Remaining action parameters
+    pub parameters: HashMap<String, JsonValue>,
+}
+
+impl RemediationRequest {
+    /// Build a request from substituted action parameters
+    ///
+    /// `service` is lifted out of the parameters; `target` only selects the
+    /// target and is dropped.
+    pub fn new(
+        operation: RemediationOperation,
+        incident_id: Uuid,
+        mut parameters: HashMap<String, JsonValue>,
+    ) -> Self {
+        parameters.remove("target");
+        let service = parameters
+            .remove("service")
+            .and_then(|v| v.as_str().map(String::from));
+
+        Self {
+            operation,
+            service,
+            incident_id,
+            parameters,
+        }
+    }
+
+    /// String parameter
+    pub fn param_str(&self, key: &str) -> Option<&str> {
+        self.parameters.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Integer parameter, also accepted as a numeric string
+    pub fn param_i64(&self, key: &str) -> Option<i64> {
+        match self.parameters.get(key)? {
+            JsonValue::Number(n) => n.as_i64(),
+            JsonValue::String(s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    fn require_service(&self) -> Result<&str> {
+        self.service
+            .as_deref()
+            .ok_or_else(|| AppError::Validation("'service' parameter required".to_string()))
+    }
+}
+
+/// Result of applying a remediation request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemediationOutcome {
+    pub success: bool,
+    pub output: HashMap<String, JsonValue>,
+    pub message: Option<String>,
+}
+
+impl RemediationOutcome {
+    pub fn succeeded(output: HashMap<String, JsonValue>) -> Self {
+        Self {
+            success: true,
+            output,
+            message: None,
+        }
+    }
+
+    pub fn failed(message: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            output: HashMap::new(),
+            message: Some(message.into()),
+        }
+    }
+
+    pub fn with_output(mut self, output: HashMap<String, JsonValue>) -> Self {
+        self.output.extend(output);
+        self
+    }
+}
+
+/// A system remediation operations are applied to
+///
+/// Failures reported by the remote system are returned as a failed
+/// [`RemediationOutcome`]; `Err` is reserved for requests the target cannot
+/// attempt at all, such as a missing parameter or an unsupported operation.
+#[async_trait]
+pub trait RemediationTarget: Send + Sync {
+    /// Name the target is configured under
+    fn name(&self) -> &str;
+
+    /// Apply a remediation request
+    async fn apply(&self, request: &RemediationRequest) -> Result<RemediationOutcome>;
+}
+
+/// Named remediation targets
+#[derive(Clone, Default)]
+pub struct RemediationTargets {
+    targets: HashMap<String, Arc<dyn RemediationTarget>>,
+    default_target: Option<String>,
+}
+
+impl RemediationTargets {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the targets described in configuration
+    pub fn from_config(config: &RemediationConfig) -> Result<Self> {
+        let mut targets = Self::new();
+        for (name, target_config) in &config.targets {
+            let target: Arc<dyn RemediationTarget> = match target_config {
+                RemediationTargetConfig::Http {
+                    base_url,
+                    token,
+                    timeout_secs,
+                } => Arc::new(HttpControlPlaneTarget::new(
+                    name.clone(),
+                    base_url.clone(),
+                    token.clone(),
+                    Duration::from_secs(*timeout_secs),
+                )?),
+                RemediationTargetConfig::Process {
+                    commands,
+                    working_dir,
+                    timeout_secs,
+                } => {
+                    let mut target =
+                        ProcessTarget::new(name.clone(), Duration::from_secs(*timeout_secs));
+                    for (operation, argv) in commands {
+                        target = target.with_command(operation.parse()?, argv.clone())?;
+                    }
+                    if let Some(dir) = working_dir {
+                        target = target.with_working_dir(dir.clone());
+                    }
+                    Arc::new(target)
+                }
+                RemediationTargetConfig::Kubernetes {
+                    api_url,
+                    namespace,
+                    token,
+                    timeout_secs,
+                } => Arc::new(KubernetesTarget::new(
+                    name.clone(),
+                    api_url.clone(),
+                    namespace.clone(),
+                    token.clone(),
+                    Duration::from_secs(*timeout_secs),
+                )?),
+            };
+            targets = targets.with_target(target);
+        }
+
+        if let Some(default_target) = &config.default_target {
+            if !targets.targets.contains_key(default_target) {
+                return Err(AppError::Configuration(format!(
+                    "Default remediation target '{}' is not configured",
+                    default_target
+                )));
+            }
+            targets = targets.with_default(default_target.clone());
+        }
+
+        Ok(targets)
+    }
+
+    /// Add a target under its own name
+    pub fn with_target(mut self, target: Arc<dyn RemediationTarget>) -> Self {
+        self.targets.insert(target.name().to_string(), target);
+        self
+    }
+
+    /// Set the target used when an action does not name one
+    pub fn with_default(mut self, name: impl Into<String>) -> Self {
+        self.default_target = Some(name.into());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Look up a target by name, or the default one
+    pub fn resolve(&self, name: Option<&str>) -> Result<Arc<dyn RemediationTarget>> {
+        let name =
+            match name.or(self.default_target.as_deref()) {
+                Some(name) => name,
+                None if self.targets.len() == 1 => {
+                    return Ok(self.targets.values().next().cloned().expect("one target"))
+                }
+                None if self.targets.is_empty() => {
+                    return Err(AppError::Configuration(
+                        "No remediation targets are configured".to_string(),
+                    ))
+                }
+                None => return Err(AppError::Validation(
+                    "'target' parameter required when several remediation targets are configured"
+                        .to_string(),
+                )),
+            };
+
+        self.targets.get(name).cloned().ok_or_else(|| {
+            AppError::Configuration(format!("Unknown remediation target '{}'", name))
+        })
+    }
+}
+
+fn build_client(timeout: Duration) -> Result<Client> {
+    Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| AppError::Configuration(format!("Failed to create HTTP client: {}", e)))
+}
+
+/// Send a request and return the JSON body, or the failure message
+async fn send_json(request: RequestBuilder) -> std::result::Result<JsonValue, String> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+
+    if !status.is_success() {
+        return Err(format!("Request returned status {}: {}", status, body));
+    }
+    if body.trim().is_empty() {
+        return Ok(JsonValue::Null);
+    }
+    Ok(serde_json::from_str(&body).unwrap_or(JsonValue::String(body)))
+}
+
+// ==================== HTTP Control Plane ====================
+
+/// Remediation through an HTTP control plane
+///
+/// Each operation is a `POST {base_url}/{operation}` with the request as JSON.
+/// A JSON object response becomes the action output; `"success": false` or
+/// `"healthy": false` in it marks the operation as failed.
+pub struct HttpControlPlaneTarget {
+    name: String,
+    base_url: String,
+    token: Option<String>,
+    client: Client,
+}
+
+impl HttpControlPlaneTarget {
+    pub fn new(
+        name: String,
+        base_url: String,
+        token: Option<String>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        Ok(Self {
+            name,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            client: build_client(timeout)?,
+        })
+    }
+}
+
+#[async_trait]
+impl RemediationTarget for HttpControlPlaneTarget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn apply(&self, request: &RemediationRequest) -> Result<RemediationOutcome> {
+        let url = format!("{}/{}", self.base_url, request.operation);
+        let mut http_request = self.client.post(&url).json(request);
+        if let Some(token) = &self.token {
+            http_request = http_request.bearer_auth(token);
+        }
+
+        let body = match send_json(http_request).await {
+            Ok(body) => body,
+            Err(message) => return Ok(RemediationOutcome::failed(message)),
+        };
+
+        let output = match body {
+            JsonValue::Object(map) => map.into_iter().collect(),
+            JsonValue::Null => HashMap::new(),
+            other => HashMap::from([("response".to_string(), other)]),
+        };
+        let rejected = ["success", "healthy"]
+            .iter()
+            .any(|key| output.get(*key) == Some(&JsonValue::Bool(false)));
+
+        if rejected {
+            let message = output
+                .get("message")
+                .or_else(|| output.get("error"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| format!("{} reported failure", request.operation));
+            Ok(RemediationOutcome::failed(message).with_output(output))
+        } else {
+            Ok(RemediationOutcome::succeeded(output))
+        }
+    }
+}
+
+// ==================== Local Process ====================
+
+/// Remediation by running local commands
+///
+/// Each supported operation maps to an argv template. The command is run
+/// directly, not through a shell, so placeholders cannot inject arguments.
+pub struct ProcessTarget {
+    name: String,
+    commands: HashMap<RemediationOperation, Vec<String>>,
+    working_dir: Option<PathBuf>,
+    timeout: Duration,
+}
+
+impl ProcessTarget {
+    pub fn new(name: String, timeout: Duration) -> Self {
+        Self {
+            name,
+            commands: HashMap::new(),
+            working_dir: None,
+            timeout,
+        }
+    }
+
+    /// Set the command run for an operation
+    pub fn with_command(
+        mut self,
+        operation: RemediationOperation,
+        argv: Vec<String>,
+    ) -> Result<Self> {
+        if argv.is_empty() {
+            return Err(AppError::Configuration(format!(
+                "Command for {} on remediation target '{}' is empty",
+                operation, self.name
+            )));
+        }
+        self.commands.insert(operation, argv);
+        Ok(self)
+    }
+
+    pub fn with_working_dir(mut self, dir: PathBuf) -> Self {
+        self.working_dir = Some(dir);
+        self
+    }
+
+    /// Fill `{placeholder}`s in an argument from the request
+    fn render(argument: &str, request: &RemediationRequest) -> Result<String> {
+        let placeholder = Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex");
+        let mut rendered = String::with_capacity(argument.len());
+        let mut last = 0;
+
+        for captures in placeholder.captures_iter(argument) {
+            let whole = captures.get(0).expect("match");
+            let key = &captures[1];
+            let value = match key {
+                "service" => request.require_service()?.to_string(),
+                "incident_id" => request.incident_id.to_string(),
+                _ => match request.parameters.get(key) {
+                    Some(JsonValue::String(s)) => s.clone(),
+                    Some(JsonValue::Null) | None => {
+                        return Err(AppError::Validation(format!(
+                            "'{}' parameter required",
+                            key
+                        )))
+                    }
+                    Some(other) => other.to_string(),
+                },
+            };
+            rendered.push_str(&argument[last..whole.start()]);
+            rendered.push_str(&value);
+            last = whole.end();
+        }
+        rendered.push_str(&argument[last..]);
+        Ok(rendered)
+    }
+}
+
+#[async_trait]
+impl RemediationTarget for ProcessTarget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn apply(&self, request: &RemediationRequest) -> Result<RemediationOutcome> {
+        let template = self.commands.get(&request.operation).ok_or_else(|| {
+            AppError::Validation(format!(
+                "Remediation target '{}' has no command for {}",
+                self.name, request.operation
+            ))
+        })?;
+        let argv = template
+            .iter()
+            .map(|argument| Self::render(argument, request))
+            .collect::<Result<Vec<_>>>()?;
+
+        info!(
+            remediation_target = %self.name,
+            operation = %request.operation,
+            command = ?argv,
+            "Running remediation command"
+        );
+
+        let mut command = tokio::process::Command::new(&argv[0]);
+        command.args(&argv[1..]).kill_on_drop(true);
+        if let Some(dir) = &self.working_dir {
+            command.current_dir(dir);
+        }
+
+        let output = match tokio::time::timeout(self.timeout, command.output()).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => {
+                return Ok(RemediationOutcome::failed(format!(
+                    "Failed to run {}: {}",
+                    argv[0], e
+                )))
+            }
+            Err(_) => {
+                return Ok(RemediationOutcome::failed(format!(
+                    "{} timed out after {}s",
+                    argv[0],
+                    self.timeout.as_secs()
+                )))
+            }
+        };
+
+        let mut result = HashMap::new();
+        result.insert("exit_code".to_string(), json!(output.status.code()));
+        result.insert(
+            "stdout".to_string(),
+            JsonValue::String(String::from_utf8_lossy(&output.stdout).into_owned()),
+        );
+        result.insert(
+            "stderr".to_string(),
+            JsonValue::String(String::from_utf8_lossy(&output.stderr).into_owned()),
+        );
+
+        if output.status.success() {
+            Ok(RemediationOutcome::succeeded(result))
+        } else {
+            Ok(
+                RemediationOutcome::failed(format!("{} exited with {}", argv[0], output.status))
+                    .with_output(result),
+            )
+        }
+    }
+}
+
+// ==================== Kubernetes ====================
+
+const RESTARTED_AT_ANNOTATION: &str = "kubectl.kubernetes.io/restartedAt";
+const CIRCUIT_BREAKER_ANNOTATION: &str = "incident-manager.io/circuit-breaker";
+const MERGE_PATCH: &str = "application/merge-patch+json";
+const STRATEGIC_MERGE_PATCH: &str = "application/strategic-merge-patch+json";
+
+/// Remediation through the Kubernetes API
+///
+/// Services are deployments in the target namespace (overridable with a
+/// `namespace` parameter):
+///
+/// - `service_restart` bumps the pod template's restart annotation, like
+///   `kubectl rollout restart`
+/// - `service_rollback` sets a container `image`; apps/v1 has no rollback API
+/// - `scale_horizontal` sets `replicas` or adds `delta` through the scale
+///   subresource
+/// - `scale_vertical` sets `cpu`/`memory` requests and limits of a container
+/// - `config_change` merges `data` into a config map (default: the service name)
+/// - `circuit_breaker` sets `state` (default `open`) as a deployment annotation
+///   for the service mesh to act on
+/// - `health_check` compares ready and desired replicas
+/// - `metrics_snapshot` reads pod usage from metrics.k8s.io
+/// - `logs_capture` tails the logs of the service's pods
+///
+/// Pods are selected with `label_selector`, defaulting to `app=<service>`.
+pub struct KubernetesTarget {
+    name: String,
+    api_url: String,
+    namespace: String,
+    token: Option<String>,
+    client: Client,
+}
+
+impl KubernetesTarget {
+    pub fn new(
+        name: String,
+        api_url: String,
+        namespace: String,
+        token: Option<String>,
+        timeout: Duration,
+    ) -> Result<Self> {
+        Ok(Self {
+            name,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            namespace,
+            token,
+            client: build_client(timeout)?,
+        })
+    }
+
+    fn authorize(&self, request: RequestBuilder) -> RequestBuilder {
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn get(&self, url: &str) -> std::result::Result<JsonValue, String> {
+        send_json(self.authorize(self.client.get(url))).await
+    }
+
+    async fn patch(
+        &self,
+        url: &str,
+        content_type: &str,
+        patch: &JsonValue,
+    ) -> std::result::Result<JsonValue, String> {
+        let request = self
+            .client
+            .patch(url)
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(patch.to_string());
+        send_json(self.authorize(request)).await
+    }
+
+    async fn patch_outcome(
+        &self,
+        url: &str,
+        content_type: &str,
+        patch: JsonValue,
+        output: HashMap<String, JsonValue>,
+    ) -> RemediationOutcome {
+        match self.patch(url, content_type, &patch).await {
+            Ok(_) => RemediationOutcome::succeeded(output),
+            Err(message) => RemediationOutcome::failed(message),
+        }
+    }
+
+    async fn scale_horizontal(
+        &self,
+        request: &RemediationRequest,
+        deployment_url: &str,
+    ) -> Result<RemediationOutcome> {
+        let scale_url = format!("{}/scale", deployment_url);
+        let replicas = match (request.param_i64("replicas"), request.param_i64("delta")) {
+            (Some(replicas), _) => replicas,
+            (None, Some(delta)) => {
+                let current = match self.get(&scale_url).await {
+                    Ok(scale) => scale["spec"]["replicas"].as_i64().unwrap_or(0),
+                    Err(message) => return Ok(RemediationOutcome::failed(message)),
+                };
+                current + delta
+            }
+            (None, None) => {
+                return Err(AppError::Validation(
+                    "'replicas' or 'delta' parameter required".to_string(),
+                ))
+            }
+        }
+        .max(0);
+
+        let output = HashMap::from([("replicas".to_string(), json!(replicas))]);
+        Ok(self
+            .patch_outcome(
+                &scale_url,
+                MERGE_PATCH,
+                json!({ "spec": { "replicas": replicas } }),
+                output,
+            )
+            .await)
+    }
+
+    async fn health_check(&self, deployment_url: &str) -> RemediationOutcome {
+        let deployment = match self.get(deployment_url).await {
+            Ok(deployment) => deployment,
+            Err(message) => return RemediationOutcome::failed(message),
+        };
+
+        let desired = deployment["spec"]["replicas"].as_i64().unwrap_or(1);
+        let ready = deployment["status"]["readyReplicas"].as_i64().unwrap_or(0);
+        let available = deployment["status"]["availableReplicas"]
+            .as_i64()
+            .unwrap_or(0);
+        let healthy = ready >= desired;
+
+        let output = HashMap::from([
+            ("healthy".to_string(), json!(healthy)),
+            ("replicas".to_string(), json!(desired)),
+            ("ready_replicas".to_string(), json!(ready)),
+            ("available_replicas".to_string(), json!(available)),
+        ]);
+        if healthy {
+            RemediationOutcome::succeeded(output)
+        } else {
+            RemediationOutcome::failed(format!("{}/{} replicas ready", ready, desired))
+                .with_output(output)
+        }
+    }
+
+    async fn metrics_snapshot(&self, namespace: &str, selector: &str) -> RemediationOutcome {
+        let url = format!(
+            "{}/apis/metrics.k8s.io/v1beta1/namespaces/{}/pods",
+            self.api_url, namespace
+        );
+        let request = self.client.get(&url).query(&[("labelSelector", selector)]);
+        let metrics = match send_json(self.authorize(request)).await {
+            Ok(metrics) => metrics,
+            Err(message) => return RemediationOutcome::failed(message),
+        };
+
+        let pods: Vec<JsonValue> = metrics["items"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .map(|item| {
+                        json!({
+                            "name": item["metadata"]["name"],
+                            "containers": item["containers"],
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        RemediationOutcome::succeeded(HashMap::from([
+            ("captured_at".to_string(), json!(chrono::Utc::now())),
+            ("pods".to_string(), JsonValue::Array(pods)),
+        ]))
+    }
+
+    async fn logs_capture(
+        &self,
+        request: &RemediationRequest,
+        namespace: &str,
+        selector: &str,
+    ) -> RemediationOutcome {
+        let pods_url = format!("{}/api/v1/namespaces/{}/pods", self.api_url, namespace);
+        let list = self
+            .client
+            .get(&pods_url)
+            .query(&[("labelSelector", selector)]);
+        let pods = match send_json(self.authorize(list)).await {
+            Ok(pods) => pods,
+            Err(message) => return RemediationOutcome::failed(message),
+        };
+
+        let tail_lines = request.param_i64("lines").unwrap_or(100).max(1);
+        let max_pods = request.param_i64("max_pods").unwrap_or(5).max(1) as usize;
+        let names: Vec<&str> = pods["items"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item["metadata"]["name"].as_str())
+                    .take(max_pods)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut logs = serde_json::Map::new();
+        for pod in names {
+            let url = format!("{}/{}/log", pods_url, pod);
+            let request = self
+                .client
+                .get(&url)
+                .query(&[("tailLines", tail_lines.to_string())]);
+            let text = match send_json(self.authorize(request)).await {
+                Ok(JsonValue::String(text)) => text,
+                Ok(other) => other.to_string(),
+                Err(message) => return RemediationOutcome::failed(message),
+            };
+            logs.insert(pod.to_string(), JsonValue::String(text));
+        }
+
+        RemediationOutcome::succeeded(HashMap::from([
+            ("pod_count".to_string(), json!(logs.len())),
+            ("logs".to_string(), JsonValue::Object(logs)),
+        ]))
+    }
+}
+
+#[async_trait]
+impl RemediationTarget for KubernetesTarget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn apply(&self, request: &RemediationRequest) -> Result<RemediationOutcome> {
+        let service = request.require_service()?;
+        let namespace = request.param_str("namespace").unwrap_or(&self.namespace);
+        let container = request.param_str("container").unwrap_or(service);
+        let selector = request
+            .param_str("label_selector")
+            .map(String::from)
+            .unwrap_or_else(|| format!("app={}", service));
+        let deployment_url = format!(
+            "{}/apis/apps/v1/namespaces/{}/deployments/{}",
+            self.api_url, namespace, service
+        );
+        let mut output = HashMap::from([
+            ("namespace".to_string(), json!(namespace)),
+            ("deployment".to_string(), json!(service)),
+        ]);
+
+        let outcome = match request.operation {
+            RemediationOperation::ServiceRestart => {
+                let restarted_at = chrono::Utc::now().to_rfc3339();
+                let patch = json!({
+                    "spec": { "template": { "metadata": { "annotations": {
+                        RESTARTED_AT_ANNOTATION: restarted_at
+                    } } } }
+                });
+                output.insert("restarted_at".to_string(), json!(restarted_at));
+                self.patch_outcome(&deployment_url, STRATEGIC_MERGE_PATCH, patch, output)
+                    .await
+            }
+            RemediationOperation::ServiceRollback => {
+                let image = request.param_str("image").ok_or_else(|| {
+                    AppError::Validation("'image' parameter required".to_string())
+                })?;
+                let patch = json!({
+                    "spec": { "template": { "spec": { "containers": [
+                        { "name": container, "image": image }
+                    ] } } }
+                });
+                output.insert("image".to_string(), json!(image));
+                self.patch_outcome(&deployment_url, STRATEGIC_MERGE_PATCH, patch, output)
+                    .await
+            }
+            RemediationOperation::ScaleHorizontal => self
+                .scale_horizontal(request, &deployment_url)
+                .await?
+                .with_output(output),
+            RemediationOperation::ScaleVertical => {
+                let mut resources = serde_json::Map::new();
+                for key in ["cpu", "memory"] {
+                    if let Some(value) = request.parameters.get(key) {
+                        let value = value
+                            .as_str()
+                            .map(String::from)
+                            .unwrap_or_else(|| value.to_string());
+                        resources.insert(key.to_string(), JsonValue::String(value));
+                    }
+                }
+                if resources.is_empty() {
+                    return Err(AppError::Validation(
+                        "'cpu' or 'memory' parameter required".to_string(),
+                    ));
+                }
+                let patch = json!({
+                    "spec": { "template": { "spec": { "containers": [{
+                        "name": container,
+                        "resources": { "requests": resources, "limits": resources },
+                    }] } } }
+                });
+                output.insert("resources".to_string(), JsonValue::Object(resources));
+                self.patch_outcome(&deployment_url, STRATEGIC_MERGE_PATCH, patch, output)
+                    .await
+            }
+            RemediationOperation::ConfigChange => {
+                let data = request
+                    .parameters
+                    .get("data")
+                    .and_then(|v| v.as_object())
+                    .ok_or_else(|| {
+                        AppError::Validation("'data' parameter required as object".to_string())
+                    })?;
+                // Config map values are always strings
+                let data: serde_json::Map<String, JsonValue> = data
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = value
+                            .as_str()
+                            .map(String::from)
+                            .unwrap_or_else(|| value.to_string());
+                        (key.clone(), JsonValue::String(value))
+                    })
+                    .collect();
+                let config_map = request.param_str("config_map").unwrap_or(service);
+                let url = format!(
+                    "{}/api/v1/namespaces/{}/configmaps/{}",
+                    self.api_url, namespace, config_map
+                );
+                output.insert("config_map".to_string(), json!(config_map));
+                output.insert(
+                    "keys".to_string(),
+                    json!(data.keys().cloned().collect::<Vec<_>>()),
+                );
+                self.patch_outcome(&url, MERGE_PATCH, json!({ "data": data }), output)
+                    .await
+            }
+            RemediationOperation::CircuitBreaker => {
+                let state = request.param_str("state").unwrap_or("open");
+                let patch = json!({
+                    "metadata": { "annotations": { CIRCUIT_BREAKER_ANNOTATION: state } }
+                });
+                output.insert("state".to_string(), json!(state));
+                self.patch_outcome(&deployment_url, MERGE_PATCH, patch, output)
+                    .await
+            }
+            RemediationOperation::HealthCheck => {
+                self.health_check(&deployment_url).await.with_output(output)
+            }
+            RemediationOperation::MetricsSnapshot => self
+                .metrics_snapshot(namespace, &selector)
+                .await
+                .with_output(output),
+            RemediationOperation::LogsCapture => self
+                .logs_capture(request, namespace, &selector)
+                .await
+                .with_output(output),
+        };
+
+        Ok(outcome)
+    }
+}
+
+// ==================== Recording Target ====================
+
+/// Target that records requests instead of applying them
+///
+/// Outcomes can be queued per operation; the last queued outcome repeats once
+/// the queue is down to one. Operations without queued outcomes succeed with
+/// empty output.
+pub struct RecordingRemediationTarget {
+    name: String,
+    requests: Mutex<Vec<RemediationRequest>>,
+    outcomes: Mutex<HashMap<RemediationOperation, VecDeque<RemediationOutcome>>>,
+}
+
+impl RecordingRemediationTarget {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            requests: Mutex::new(Vec::new()),
+            outcomes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Queue the outcome of the next `operation` request
+    pub fn with_outcome(
+        self,
+        operation: RemediationOperation,
+        outcome: RemediationOutcome,
+    ) -> Self {
+        self.outcomes
+            .lock()
+            .unwrap()
+            .entry(operation)
+            .or_default()
+            .push_back(outcome);
+        self
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<RemediationRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl RemediationTarget for RecordingRemediationTarget {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn apply(&self, request: &RemediationRequest) -> Result<RemediationOutcome> {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let mut outcomes = self.outcomes.lock().unwrap();
+        let outcome = match outcomes.get_mut(&request.operation) {
+            Some(queue) if queue.len() > 1 => queue.pop_front(),
+            Some(queue) => queue.front().cloned(),
+            None => None,
+        };
+        Ok(outcome.unwrap_or_else(|| RemediationOutcome::succeeded(HashMap::new())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    fn request(operation: RemediationOperation, params: JsonValue) -> RemediationRequest {
+        let parameters = serde_json::from_value(params).unwrap();
+        RemediationRequest::new(operation, Uuid::new_v4(), parameters)
+    }
+
+    #[test]
+    fn test_operation_names_round_trip() {
+        for operation in RemediationOperation::ALL {
+            assert_eq!(
+                operation.as_str().parse::<RemediationOperation>().unwrap(),
+                operation
+            );
+            assert_eq!(
+                serde_json::to_value(operation).unwrap(),
+                json!(operation.as_str())
+            );
+        }
+        assert!("reboot_everything".parse::<RemediationOperation>().is_err());
+        assert_eq!(
+            RemediationOperation::from_action(&ActionType::ScaleHorizontal),
+            Some(RemediationOperation::ScaleHorizontal)
+        );
+        assert_eq!(RemediationOperation::from_action(&ActionType::Slack), None);
+        for operation in RemediationOperation::ALL {
+            assert_eq!(
+                RemediationOperation::from_action(&operation.action_type()),
+                Some(operation)
+            );
+        }
+    }
+
+    #[test]
+    fn test_request_lifts_service_and_drops_target() {
+        let request = request(
+            RemediationOperation::ServiceRestart,
+            json!({"service": "api", "target": "cluster", "grace_secs": 30}),
+        );
+        assert_eq!(request.service.as_deref(), Some("api"));
+        assert!(!request.parameters.contains_key("target"));
+        assert_eq!(request.param_i64("grace_secs"), Some(30));
+    }
+
+    #[test]
+    fn test_resolve_targets() {
+        let empty = RemediationTargets::new();
+        assert!(empty.resolve(None).is_err());
+
+        let single = RemediationTargets::new()
+            .with_target(Arc::new(RecordingRemediationTarget::new("mock")));
+        assert_eq!(single.resolve(None).unwrap().name(), "mock");
+        assert!(single.resolve(Some("other")).is_err());
+
+        let several = single.with_target(Arc::new(RecordingRemediationTarget::new("other")));
+        assert!(several.resolve(None).is_err());
+        assert_eq!(several.resolve(Some("other")).unwrap().name(), "other");
+        assert_eq!(
+            several.with_default("mock").resolve(None).unwrap().name(),
+            "mock"
+        );
+    }
+
+    #[test]
+    fn test_targets_from_config() {
+        let config: RemediationConfig = serde_json::from_value(json!({
+            "default_target": "cluster",
+            "targets": {
+                "cluster": {"kind": "kubernetes", "api_url": "https://k8s.local"},
+                "local": {"kind": "process", "commands": {"service_restart": ["true"]}},
+                "control_plane": {"kind": "http", "base_url": "http://localhost:9000"},
+            }
+        }))
+        .unwrap();
+        let targets = RemediationTargets::from_config(&config).unwrap();
+        assert_eq!(targets.resolve(None).unwrap().name(), "cluster");
+        assert_eq!(targets.resolve(Some("local")).unwrap().name(), "local");
+
+        let unknown_default: RemediationConfig =
+            serde_json::from_value(json!({"default_target": "missing"})).unwrap();
+        assert!(RemediationTargets::from_config(&unknown_default).is_err());
+
+        let unknown_operation: RemediationConfig = serde_json::from_value(json!({
+            "targets": {"local": {"kind": "process", "commands": {"reboot": ["true"]}}}
+        }))
+        .unwrap();
+        assert!(RemediationTargets::from_config(&unknown_operation).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recording_target_replays_queued_outcomes() {
+        let target = RecordingRemediationTarget::new("mock")
+            .with_outcome(
+                RemediationOperation::HealthCheck,
+                RemediationOutcome::failed("unhealthy"),
+            )
+            .with_outcome(
+                RemediationOperation::HealthCheck,
+                RemediationOutcome::succeeded(HashMap::new()),
+            );
+        let check = request(RemediationOperation::HealthCheck, json!({"service": "api"}));
+
+        assert!(!target.apply(&check).await.unwrap().success);
+        assert!(target.apply(&check).await.unwrap().success);
+        assert!(target.apply(&check).await.unwrap().success);
+        assert_eq!(target.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_http_target_posts_operation() {
+        let mut server = mockito::Server::new_async().await;
+        let restart = server
+            .mock("POST", "/service_restart")
+            .match_header("authorization", "Bearer secret")
+            .match_body(Matcher::PartialJson(json!({
+                "operation": "service_restart",
+                "service": "api",
+            })))
+            .with_status(200)
+            .with_body(r#"{"restarted": true}"#)
+            .create_async()
+            .await;
+        let health = server
+            .mock("POST", "/health_check")
+            .with_status(200)
+            .with_body(r#"{"healthy": false, "message": "3 of 5 pods failing"}"#)
+            .create_async()
+            .await;
+
+        let target = HttpControlPlaneTarget::new(
+            "control_plane".to_string(),
+            format!("{}/", server.url()),
+            Some("secret".to_string()),
+            Duration::from_secs(5),
+        )
+        .unwrap();
+
+        let outcome = target
+            .apply(&request(
+                RemediationOperation::ServiceRestart,
+                json!({"service": "api"}),
+            ))
+            .await
+            .unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.output["restarted"], json!(true));
+        restart.assert_async().await;
+
+        let outcome = target
+            .apply(&request(
+                RemediationOperation::HealthCheck,
+                json!({"service": "api"}),
+            ))
+            .await
+            .unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.message.as_deref(), Some("3 of 5 pods failing"));
+        health.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_kubernetes_target_scales_by_delta() {
+        let mut server = mockito::Server::new_async().await;
+        let path = "/apis/apps/v1/namespaces/prod/deployments/api/scale";
+        let get = server
+            .mock("GET", path)
+            .with_status(200)
+            .with_body(r#"{"spec": {"replicas": 3}}"#)
+            .create_async()
+            .await;
+        let patch = server
+            .mock("PATCH", path)
+            .match_header("content-type", MERGE_PATCH)
+            .match_body(Matcher::Json(json!({"spec": {"replicas": 5}})))
+            .with_status(200)
+            .with_body(r#"{"spec": {"replicas": 5}}"#)
+            .create_async()
+            .await;
+
+        let target = KubernetesTarget::new(
+            "cluster".to_string(),
+            server.url(),
+            "prod".to_string(),
+            None,
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        let outcome = target
+            .apply(&request(
+                RemediationOperation::ScaleHorizontal,
+                json!({"service": "api", "delta": 2}),
+            ))
+            .await
+            .unwrap();
+
+        assert!(outcome.success);
+        assert_eq!(outcome.output["replicas"], json!(5));
+        get.assert_async().await;
+        patch.assert_async().await;
+
+        let missing = target
+            .apply(&request(
+                RemediationOperation::ScaleHorizontal,
+                json!({"service": "api"}),
+            ))
+            .await;
+        assert!(missing.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_kubernetes_health_check_reports_unready_replicas() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/apis/apps/v1/namespaces/default/deployments/api")
+            .with_status(200)
+            .with_body(r#"{"spec": {"replicas": 3}, "status": {"readyReplicas": 1}}"#)
+            .create_async()
+            .await;
+
+        let target = KubernetesTarget::new(
+            "cluster".to_string(),
+            server.url(),
+            "default".to_string(),
+            None,
+            Duration::from_secs(5),
+        )
+        .unwrap();
+        let outcome = target
+            .apply(&request(
+                RemediationOperation::HealthCheck,
+                json!({"service": "api"}),
+            ))
+            .await
+            .unwrap();
+
+        assert!(!outcome.success);
+        assert_eq!(outcome.output["healthy"], json!(false));
+        assert_eq!(outcome.message.as_deref(), Some("1/3 replicas ready"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_target_fills_placeholders() {
+        let target = ProcessTarget::new("local".to_string(), Duration::from_secs(5))
+            .with_command(
+                RemediationOperation::ServiceRestart,
+                vec![
+                    "echo".to_string(),
+                    "restart".to_string(),
+                    "{service}".to_string(),
+                    "--grace={grace_secs}".to_string(),
+                ],
+            )
+            .unwrap();
+
+        let outcome = target
+            .apply(&request(
+                RemediationOperation::ServiceRestart,
+                json!({"service": "api", "grace_secs": 30}),
+            ))
+            .await
+            .unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.output["stdout"], json!("restart api --grace=30\n"));
+        assert_eq!(outcome.output["exit_code"], json!(0));
+
+        // Missing placeholder values and unmapped operations are rejected
+        assert!(target
+            .apply(&request(
+                RemediationOperation::ServiceRestart,
+                json!({"service": "api"})
+            ))
+            .await
+            .is_err());
+        assert!(target
+            .apply(&request(
+                RemediationOperation::HealthCheck,
+                json!({"service": "api"})
+            ))
+            .await
+            .is_err());
+    }
+}
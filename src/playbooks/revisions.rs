@@ -0,0 +1,256 @@
+//! Playbook revision digests and diffs
+
+use crate::models::{ChangeKind, Playbook, PlaybookChange};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeSet, HashMap};
+
+/// Fields that change without the definition changing
+const UNVERSIONED_FIELDS: [&str; 3] = ["revision", "created_at", "updated_at"];
+
+/// SHA-256 of a playbook definition
+///
+/// Two definitions have the same digest when they differ at most in
+/// `revision`, `created_at` and `updated_at`.
+pub fn digest(playbook: &Playbook) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(definition(playbook).to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Changes that turn `from` into `to`
+///
+/// Steps are matched by id rather than position, so inserting a step shows
+/// up as one added step. A change in step order is reported on `steps`.
+pub fn diff(from: &Playbook, to: &Playbook) -> Vec<PlaybookChange> {
+    let mut changes = Vec::new();
+    diff_value("", &definition(from), &definition(to), &mut changes);
+    changes
+}
+
+fn definition(playbook: &Playbook) -> JsonValue {
+    let mut value = serde_json::to_value(playbook).unwrap_or(JsonValue::Null);
+    if let JsonValue::Object(ref mut map) = value {
+        for field in UNVERSIONED_FIELDS {
+            map.remove(field);
+        }
+    }
+    value
+}
+
+fn diff_value(path: &str, from: &JsonValue, to: &JsonValue, changes: &mut Vec<PlaybookChange>) {
+    match (from, to) {
+        (JsonValue::Object(a), JsonValue::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let path = join(path, key);
+                match (a.get(key), b.get(key)) {
+                    (Some(x), Some(y)) => diff_value(&path, x, y, changes),
+                    (Some(x), None) => {
+                        changes.push(change(path, ChangeKind::Removed, Some(x), None))
+                    }
+                    (None, Some(y)) => changes.push(change(path, ChangeKind::Added, None, Some(y))),
+                    (None, None) => {}
+                }
+            }
+        }
+        (JsonValue::Array(a), JsonValue::Array(b)) => match (ids(a), ids(b)) {
+            (Some(a_ids), Some(b_ids)) => diff_by_id(path, a, &a_ids, b, &b_ids, changes),
+            _ => {
+                for i in 0..a.len().max(b.len()) {
+                    let path = format!("{}[{}]", path, i);
+                    match (a.get(i), b.get(i)) {
+                        (Some(x), Some(y)) => diff_value(&path, x, y, changes),
+                        (Some(x), None) => {
+                            changes.push(change(path, ChangeKind::Removed, Some(x), None))
+                        }
+                        (None, Some(y)) => {
+                            changes.push(change(path, ChangeKind::Added, None, Some(y)))
+                        }
+                        (None, None) => {}
+                    }
+                }
+            }
+        },
+        _ if from != to => changes.push(change(
+            path.to_string(),
+            ChangeKind::Modified,
+            Some(from),
+            Some(to),
+        )),
+        _ => {}
+    }
+}
+
+/// Compare lists of objects that all have a unique string `id`
+fn diff_by_id(
+    path: &str,
+    a: &[JsonValue],
+    a_ids: &[&str],
+    b: &[JsonValue],
+    b_ids: &[&str],
+    changes: &mut Vec<PlaybookChange>,
+) {
+    let a_index: HashMap<&str, usize> = a_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let b_index: HashMap<&str, usize> = b_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+    let a_kept: Vec<&str> = a_ids
+        .iter()
+        .copied()
+        .filter(|id| b_index.contains_key(id))
+        .collect();
+    let b_kept: Vec<&str> = b_ids
+        .iter()
+        .copied()
+        .filter(|id| a_index.contains_key(id))
+        .collect();
+    if a_kept != b_kept {
+        changes.push(change(
+            path.to_string(),
+            ChangeKind::Modified,
+            Some(&serde_json::json!(a_ids)),
+            Some(&serde_json::json!(b_ids)),
+        ));
+    }
+
+    for (i, id) in a_ids.iter().enumerate() {
+        let path = join(path, id);
+        match b_index.get(id) {
+            Some(&j) => diff_value(&path, &a[i], &b[j], changes),
+            None => changes.push(change(path, ChangeKind::Removed, Some(&a[i]), None)),
+        }
+    }
+    for (j, id) in b_ids.iter().enumerate() {
+        if !a_index.contains_key(id) {
+            changes.push(change(join(path, id), ChangeKind::Added, None, Some(&b[j])));
+        }
+    }
+}
+
+/// The `id` of every element, if all are objects with distinct string ids
+fn ids(values: &[JsonValue]) -> Option<Vec<&str>> {
+    let ids: Vec<&str> = values
+        .iter()
+        .map(|v| v.get("id").and_then(JsonValue::as_str))
+        .collect::<Option<_>>()?;
+    let distinct: BTreeSet<&str> = ids.iter().copied().collect();
+    (distinct.len() == ids.len()).then_some(ids)
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn change(
+    path: String,
+    kind: ChangeKind,
+    from: Option<&JsonValue>,
+    to: Option<&JsonValue>,
+) -> PlaybookChange {
+    PlaybookChange {
+        path,
+        kind,
+        from: from.cloned(),
+        to: to.cloned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Action, ActionType, PlaybookStep, PlaybookTriggers, StepType};
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn step(id: &str, service: &str) -> PlaybookStep {
+        let mut parameters = HashMap::new();
+        parameters.insert("service".to_string(), serde_json::json!(service));
+        PlaybookStep {
+            id: id.to_string(),
+            step_type: StepType::Remediation,
+            description: None,
+            actions: vec![Action {
+                action_type: ActionType::ServiceRestart,
+                parameters,
+                on_success: None,
+                on_failure: None,
+            }],
+            parallel: false,
+            timeout: None,
+            retry: 0,
+            backoff: Default::default(),
+            condition: None,
+            depends_on: None,
+            trigger_rule: Default::default(),
+            approval: None,
+            idempotent: false,
+        }
+    }
+
+    fn playbook(steps: Vec<PlaybookStep>) -> Playbook {
+        Playbook {
+            id: Uuid::new_v4(),
+            name: "restart".to_string(),
+            version: "1".to_string(),
+            revision: 0,
+            description: "Restart services".to_string(),
+            owner: "sre".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            triggers: PlaybookTriggers {
+                severity_trigger: vec![],
+                type_trigger: vec![],
+                source_trigger: vec![],
+            },
+            variables: HashMap::new(),
+            steps,
+            enabled: true,
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn test_digest_ignores_revision_and_timestamps() {
+        let a = playbook(vec![step("restart", "api")]);
+        let mut b = a.clone();
+        b.revision = 3;
+        b.updated_at = Utc::now() + chrono::Duration::hours(1);
+        assert_eq!(digest(&a), digest(&b));
+
+        b.steps[0].retry = 2;
+        assert_ne!(digest(&a), digest(&b));
+    }
+
+    #[test]
+    fn test_diff_matches_steps_by_id() {
+        let from = playbook(vec![step("check", "api"), step("restart", "api")]);
+        let mut to = from.clone();
+        to.version = "2".to_string();
+        to.steps = vec![
+            step("drain", "lb"),
+            step("check", "api"),
+            step("restart", "web"),
+        ];
+
+        let changes = diff(&from, &to);
+        let paths: Vec<(&str, ChangeKind)> =
+            changes.iter().map(|c| (c.path.as_str(), c.kind)).collect();
+        assert_eq!(
+            paths,
+            vec![
+                (
+                    "steps.restart.actions[0].parameters.service",
+                    ChangeKind::Modified
+                ),
+                ("steps.drain", ChangeKind::Added),
+                ("version", ChangeKind::Modified),
+            ]
+        );
+        assert_eq!(changes[0].to, Some(serde_json::json!("web")));
+        assert!(diff(&from, &from).is_empty());
+    }
+}
@@ -0,0 +1,495 @@
+//! Sandboxed script execution for `run_script` actions
+//!
+//! A script runs under an allow-listed interpreter, from an allow-listed
+//! directory, with a cleared environment, a timeout, capped output and (on
+//! Unix) an address space limit. The script and its children share a process
+//! group that is killed on timeout.
+
+use crate::error::{AppError, Result};
+use crate::playbooks::config::ScriptConfig;
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+
+/// A validated script run
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptInvocation {
+    pub interpreter: String,
+    pub script: PathBuf,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub working_dir: PathBuf,
+    pub timeout: Duration,
+}
+
+/// What a script run produced
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptOutput {
+    /// Exit code; `None` when the script was killed
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub stdout_truncated: bool,
+    pub stderr_truncated: bool,
+    pub timed_out: bool,
+    pub duration: Duration,
+}
+
+impl ScriptOutput {
+    /// Whether the script exited with status 0
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// Why the run counts as failed
+    pub fn failure_reason(&self, script: &Path) -> String {
+        if self.timed_out {
+            format!(
+                "Script {} timed out after {}s",
+                script.display(),
+                self.duration.as_secs()
+            )
+        } else {
+            match self.exit_code {
+                Some(code) => format!("Script {} exited with code {}", script.display(), code),
+                None => format!("Script {} was killed by a signal", script.display()),
+            }
+        }
+    }
+
+    /// Action output
+    pub fn into_output(self) -> HashMap<String, JsonValue> {
+        HashMap::from([
+            ("exit_code".to_string(), json!(self.exit_code)),
+            ("stdout".to_string(), JsonValue::String(self.stdout)),
+            ("stderr".to_string(), JsonValue::String(self.stderr)),
+            (
+                "stdout_truncated".to_string(),
+                JsonValue::Bool(self.stdout_truncated),
+            ),
+            (
+                "stderr_truncated".to_string(),
+                JsonValue::Bool(self.stderr_truncated),
+            ),
+            ("timed_out".to_string(), JsonValue::Bool(self.timed_out)),
+            (
+                "duration_ms".to_string(),
+                json!(self.duration.as_millis() as u64),
+            ),
+        ])
+    }
+}
+
+/// Runs scripts within the limits of a [`ScriptConfig`]
+#[derive(Debug, Clone, Default)]
+pub struct ScriptSandbox {
+    config: ScriptConfig,
+}
+
+impl ScriptSandbox {
+    pub fn new(config: ScriptConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &ScriptConfig {
+        &self.config
+    }
+
+    /// Validate action parameters against the allow-lists
+    ///
+    /// Parameters: `script` (required), `interpreter`, `args`, `env`,
+    /// `working_dir` and `timeout_secs`.
+    pub fn prepare(&self, params: &HashMap<String, JsonValue>) -> Result<ScriptInvocation> {
+        let interpreter = params
+            .get("interpreter")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .or_else(|| self.config.default_interpreter.clone())
+            .ok_or_else(|| AppError::Validation("'interpreter' parameter required".to_string()))?;
+        if !self.config.allowed_interpreters.contains(&interpreter) {
+            return Err(AppError::Validation(format!(
+                "Interpreter '{}' is not allowed",
+                interpreter
+            )));
+        }
+
+        let script = params
+            .get("script")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| AppError::Validation("'script' parameter required".to_string()))?;
+        let script = self.allowed_path(Path::new(script), "Script")?;
+        if !script.is_file() {
+            return Err(AppError::Validation(format!(
+                "Script {} is not a file",
+                script.display()
+            )));
+        }
+
+        let args = match params.get("args") {
+            None | Some(JsonValue::Null) => Vec::new(),
+            Some(JsonValue::Array(args)) => args.iter().map(argument).collect(),
+            Some(_) => {
+                return Err(AppError::Validation(
+                    "'args' parameter must be an array".to_string(),
+                ))
+            }
+        };
+
+        let working_dir = match params.get("working_dir").and_then(|v| v.as_str()) {
+            Some(dir) => self.allowed_path(Path::new(dir), "Working directory")?,
+            None => match &self.config.working_dir {
+                Some(dir) => dir.clone(),
+                None => script
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| PathBuf::from("/")),
+            },
+        };
+
+        let timeout_secs = params
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(self.config.default_timeout_secs);
+        if timeout_secs > self.config.max_timeout_secs {
+            return Err(AppError::Validation(format!(
+                "'timeout_secs' may be at most {}",
+                self.config.max_timeout_secs
+            )));
+        }
+
+        Ok(ScriptInvocation {
+            interpreter,
+            script,
+            args,
+            env: self.environment(params.get("env"))?,
+            working_dir,
+            timeout: Duration::from_secs(timeout_secs),
+        })
+    }
+
+    /// Run a prepared script
+    ///
+    /// `Err` means the script could not be started.
+    pub async fn run(&self, invocation: &ScriptInvocation) -> Result<ScriptOutput> {
+        let mut command = Command::new(&invocation.interpreter);
+        command
+            .arg(&invocation.script)
+            .args(&invocation.args)
+            .env_clear()
+            .envs(&invocation.env)
+            .current_dir(&invocation.working_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        isolate(&mut command, self.config.max_memory_mb);
+
+        let started = Instant::now();
+        let mut child = command.spawn().map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to start {} {}: {}",
+                invocation.interpreter,
+                invocation.script.display(),
+                e
+            ))
+        })?;
+
+        let limit = self.config.max_output_bytes;
+        let stdout = tokio::spawn(read_capped(
+            child.stdout.take().expect("stdout is piped"),
+            limit,
+        ));
+        let stderr = tokio::spawn(read_capped(
+            child.stderr.take().expect("stderr is piped"),
+            limit,
+        ));
+
+        let waited = tokio::time::timeout(invocation.timeout, child.wait()).await;
+        let (exit_code, timed_out) = match waited {
+            Ok(status) => (status?.code(), false),
+            Err(_) => {
+                // Children left behind would keep the pipes open
+                #[cfg(unix)]
+                {
+                    if let Some(pid) = child.id() {
+                        unsafe {
+                            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+                        }
+                    }
+                }
+                let _ = child.kill().await;
+                (None, true)
+            }
+        };
+        let duration = started.elapsed();
+
+        let (stdout, stdout_truncated) = join_output(stdout).await?;
+        let (stderr, stderr_truncated) = join_output(stderr).await?;
+
+        Ok(ScriptOutput {
+            exit_code,
+            stdout,
+            stderr,
+            stdout_truncated,
+            stderr_truncated,
+            timed_out,
+            duration,
+        })
+    }
+
+    /// Resolve `path` and check it is inside an allowed directory
+    fn allowed_path(&self, path: &Path, what: &str) -> Result<PathBuf> {
+        let resolved = path.canonicalize().map_err(|_| {
+            AppError::Validation(format!("{} {} does not exist", what, path.display()))
+        })?;
+        let allowed = self
+            .config
+            .allowed_paths
+            .iter()
+            .filter_map(|dir| dir.canonicalize().ok())
+            .any(|dir| resolved.starts_with(dir));
+
+        if allowed {
+            Ok(resolved)
+        } else {
+            Err(AppError::Validation(format!(
+                "{} {} is outside the allowed paths",
+                what,
+                path.display()
+            )))
+        }
+    }
+
+    /// Configured environment plus the action's `env` object
+    fn environment(&self, extra: Option<&JsonValue>) -> Result<HashMap<String, String>> {
+        let mut env = HashMap::new();
+        for name in &self.config.inherit_env {
+            if let Ok(value) = std::env::var(name) {
+                env.insert(name.clone(), value);
+            }
+        }
+        for pair in &self.config.env {
+            let (key, value) = pair.split_once('=').ok_or_else(|| {
+                AppError::Configuration(format!(
+                    "Script environment entry '{}' is not KEY=VALUE",
+                    pair
+                ))
+            })?;
+            env.insert(key.to_string(), value.to_string());
+        }
+
+        match extra {
+            None | Some(JsonValue::Null) => {}
+            Some(JsonValue::Object(vars)) => {
+                for (key, value) in vars {
+                    // Loader variables would let a playbook run arbitrary code
+                    if key.starts_with("LD_") || key.starts_with("DYLD_") {
+                        return Err(AppError::Validation(format!(
+                            "Environment variable '{}' is not allowed",
+                            key
+                        )));
+                    }
+                    env.insert(key.clone(), argument(value));
+                }
+            }
+            Some(_) => {
+                return Err(AppError::Validation(
+                    "'env' parameter must be an object".to_string(),
+                ))
+            }
+        }
+
+        Ok(env)
+    }
+}
+
+fn argument(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Put the script in its own process group and apply the memory limit
+#[cfg(unix)]
+fn isolate(command: &mut Command, max_memory_mb: Option<u64>) {
+    let max_memory = max_memory_mb.map(|mb| (mb * 1024 * 1024) as libc::rlim_t);
+    // Only async-signal-safe calls between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setpgid(0, 0) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            if let Some(bytes) = max_memory {
+                let limit = libc::rlimit {
+                    rlim_cur: bytes,
+                    rlim_max: bytes,
+                };
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Read a stream to the end, keeping at most `limit` bytes
+///
+/// The rest is drained so the script never blocks on a full pipe.
+async fn read_capped<R: AsyncRead + Unpin>(
+    mut reader: R,
+    limit: usize,
+) -> std::io::Result<(Vec<u8>, bool)> {
+    let mut captured = Vec::new();
+    let mut truncated = false;
+    let mut buf = [0u8; 8192];
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        let room = limit - captured.len();
+        if n > room {
+            captured.extend_from_slice(&buf[..room]);
+            truncated = true;
+        } else {
+            captured.extend_from_slice(&buf[..n]);
+        }
+    }
+
+    Ok((captured, truncated))
+}
+
+async fn join_output(
+    handle: tokio::task::JoinHandle<std::io::Result<(Vec<u8>, bool)>>,
+) -> Result<(String, bool)> {
+    let (bytes, truncated) = handle
+        .await
+        .map_err(|e| AppError::Internal(format!("Output reader failed: {}", e)))??;
+    Ok((String::from_utf8_lossy(&bytes).into_owned(), truncated))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn sandbox(dir: &Path) -> ScriptSandbox {
+        ScriptSandbox::new(ScriptConfig {
+            allowed_interpreters: vec!["/bin/sh".to_string()],
+            default_interpreter: Some("/bin/sh".to_string()),
+            allowed_paths: vec![dir.to_path_buf()],
+            max_output_bytes: 16,
+            ..ScriptConfig::default()
+        })
+    }
+
+    fn params(value: JsonValue) -> HashMap<String, JsonValue> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_prepare_enforces_allow_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let script = dir.path().join("ok.sh");
+        let stray = outside.path().join("stray.sh");
+        fs::write(&script, "exit 0\n").unwrap();
+        fs::write(&stray, "exit 0\n").unwrap();
+        let sandbox = sandbox(dir.path());
+
+        let invocation = sandbox
+            .prepare(&params(json!({"script": script, "args": ["a", 1]})))
+            .unwrap();
+        assert_eq!(invocation.interpreter, "/bin/sh");
+        assert_eq!(invocation.args, vec!["a", "1"]);
+        assert_eq!(invocation.working_dir, dir.path().canonicalize().unwrap());
+        assert_eq!(
+            invocation.env.get("PATH").map(String::as_str),
+            Some("/usr/local/bin:/usr/bin:/bin")
+        );
+
+        // Outside the allowed directories, also through `..`
+        assert!(sandbox.prepare(&params(json!({"script": stray}))).is_err());
+        let escape = dir
+            .path()
+            .join("..")
+            .join(outside.path().file_name().unwrap());
+        assert!(sandbox
+            .prepare(&params(json!({"script": escape.join("stray.sh")})))
+            .is_err());
+
+        assert!(sandbox
+            .prepare(&params(
+                json!({"script": script, "interpreter": "/usr/bin/perl"})
+            ))
+            .is_err());
+        assert!(sandbox
+            .prepare(&params(json!({"script": script, "timeout_secs": 100_000})))
+            .is_err());
+        assert!(sandbox
+            .prepare(&params(
+                json!({"script": script, "env": {"LD_PRELOAD": "x.so"}})
+            ))
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_captures_output_with_clean_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("env.sh");
+        fs::write(&script, "echo \"$GREETING $1\"\necho oops >&2\nexit 3\n").unwrap();
+        std::env::set_var("SCRIPT_SANDBOX_LEAK", "1");
+        let sandbox = sandbox(dir.path());
+
+        let invocation = sandbox
+            .prepare(&params(json!({
+                "script": script,
+                "args": ["world"],
+                "env": {"GREETING": "hello"},
+            })))
+            .unwrap();
+        assert!(!invocation.env.contains_key("SCRIPT_SANDBOX_LEAK"));
+
+        let output = sandbox.run(&invocation).await.unwrap();
+        assert_eq!(output.exit_code, Some(3));
+        assert!(!output.succeeded());
+        assert_eq!(output.stdout, "hello world\n");
+        assert_eq!(output.stderr, "oops\n");
+        assert!(!output.timed_out);
+    }
+
+    #[tokio::test]
+    async fn test_run_truncates_output_and_times_out() {
+        let dir = tempfile::tempdir().unwrap();
+        let noisy = dir.path().join("noisy.sh");
+        let slow = dir.path().join("slow.sh");
+        fs::write(
+            &noisy,
+            "i=0\nwhile [ $i -lt 1000 ]; do echo line$i; i=$((i+1)); done\n",
+        )
+        .unwrap();
+        fs::write(&slow, "sleep 30 &\nwait\n").unwrap();
+        let sandbox = sandbox(dir.path());
+
+        let invocation = sandbox.prepare(&params(json!({"script": noisy}))).unwrap();
+        let output = sandbox.run(&invocation).await.unwrap();
+        assert!(output.succeeded());
+        assert_eq!(output.stdout.len(), 16);
+        assert!(output.stdout_truncated);
+
+        let mut invocation = sandbox.prepare(&params(json!({"script": slow}))).unwrap();
+        invocation.timeout = Duration::from_millis(200);
+        let output = sandbox.run(&invocation).await.unwrap();
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, None);
+        assert!(output.duration < Duration::from_secs(10));
+    }
+}
@@ -1,20 +1,38 @@
 use crate::error::{AppError, Result};
-use crate::models::{Incident, Playbook, PlaybookExecution};
+use crate::models::{
+    ApprovalDecision, ApprovalTimeoutAction, ExecutionStatus, Incident, Playbook, PlaybookDiff,
+    PlaybookExecution, PlaybookRevision, PlaybookSimulation, StepType,
+};
 use crate::notifications::NotificationService;
+use crate::playbooks::remediation::RemediationTargets;
+use crate::playbooks::revisions;
+use crate::playbooks::script::ScriptSandbox;
+use crate::playbooks::expression::Expression;
+use crate::playbooks::executor::parse_duration;
 use crate::playbooks::{
-    create_default_registry, ExecutionContext, PlaybookExecutor,
+    create_registry, ActionExecutorRegistry, ExecutionContext, PlaybookExecutor, PlaybookGraph,
 };
 use crate::state::IncidentStore;
+use chrono::Utc;
 use dashmap::DashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
-use tracing::{error, info};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Recorded as the decider of approvals that timed out
+pub const APPROVAL_TIMEOUT_DECIDER: &str = "timeout";
+
 /// Playbook service manages playbook storage and execution
 pub struct PlaybookService {
-    /// In-memory playbook storage
+    /// In-memory playbook storage, holding the active revision of each
     playbooks: Arc<DashMap<Uuid, Playbook>>,
 
+    /// Every published revision of each playbook, oldest first
+    revisions: Arc<DashMap<Uuid, Vec<PlaybookRevision>>>,
+
     /// Playbook executor
     executor: Arc<PlaybookExecutor>,
 
@@ -22,11 +40,22 @@ pub struct PlaybookService {
     executions: Arc<DashMap<Uuid, PlaybookExecution>>,
 
     /// Store for incidents
-    #[allow(dead_code)]
     store: Arc<dyn IncidentStore>,
 
+    /// Notification service used by notification actions
+    notification_service: Option<Arc<NotificationService>>,
+
+    /// Targets remediation actions are applied to
+    remediation_targets: RemediationTargets,
+
+    /// Sandbox `run_script` actions run in
+    script_sandbox: ScriptSandbox,
+
     /// Whether automatic execution is enabled
     auto_execute: bool,
+
+    /// How often the approval monitor applies timeout policies
+    approval_check_interval_secs: u64,
 }
 
 impl PlaybookService {
@@ -36,31 +65,74 @@ impl PlaybookService {
         notification_service: Option<Arc<NotificationService>>,
         auto_execute: bool,
     ) -> Self {
-        let action_registry = create_default_registry(notification_service, store.clone());
-        let executor = Arc::new(PlaybookExecutor::new(
-            Arc::new(action_registry),
-            store.clone(),
-        ));
-
-        Self {
+        let mut service = Self {
             playbooks: Arc::new(DashMap::new()),
-            executor,
+            revisions: Arc::new(DashMap::new()),
+            executor: Arc::new(PlaybookExecutor::new(
+                Arc::new(ActionExecutorRegistry::new()),
+                store.clone(),
+            )),
             executions: Arc::new(DashMap::new()),
             store,
+            notification_service,
+            remediation_targets: RemediationTargets::new(),
+            script_sandbox: ScriptSandbox::default(),
             auto_execute,
-        }
+            approval_check_interval_secs: 30,
+        };
+        service.rebuild_executor();
+        service
+    }
+
+    /// Apply remediation actions to the given targets
+    pub fn with_remediation_targets(mut self, targets: RemediationTargets) -> Self {
+        self.remediation_targets = targets;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Run `run_script` actions in the given sandbox
+    pub fn with_script_sandbox(mut self, sandbox: ScriptSandbox) -> Self {
+        self.script_sandbox = sandbox;
+        self.rebuild_executor();
+        self
+    }
+
+    /// Set how often the approval monitor checks for timed out approvals
+    pub fn with_approval_check_interval(mut self, secs: u64) -> Self {
+        self.approval_check_interval_secs = secs;
+        self
     }
 
-    /// Register a playbook
-    pub fn register_playbook(&self, playbook: Playbook) -> Result<()> {
+    fn rebuild_executor(&mut self) {
+        let action_registry = create_registry(
+            self.notification_service.clone(),
+            self.store.clone(),
+            self.remediation_targets.clone(),
+            self.script_sandbox.clone(),
+        );
+        self.executor = Arc::new(PlaybookExecutor::new(
+            Arc::new(action_registry),
+            self.store.clone(),
+        ));
+    }
+
+    /// Register a playbook, rejecting step graphs that cannot run and
+    /// conditions that do not parse
+    ///
+    /// The definition is published as a new revision and becomes the active
+    /// one. A definition identical to an earlier revision makes that
+    /// revision active again instead. Returns the active revision.
+    pub fn register_playbook(&self, playbook: Playbook) -> Result<u32> {
+        validate_playbook(&playbook)?;
+
         info!(
             playbook_id = %playbook.id,
             playbook_name = %playbook.name,
             "Registering playbook"
         );
 
-        self.playbooks.insert(playbook.id, playbook);
-        Ok(())
+        Ok(self.publish(playbook))
     }
 
     /// Get a playbook by ID
@@ -73,20 +145,26 @@ impl PlaybookService {
         self.playbooks.iter().map(|entry| entry.value().clone()).collect()
     }
 
-    /// Update a playbook
-    pub fn update_playbook(&self, playbook: Playbook) -> Result<()> {
+    /// Update a playbook, publishing a new revision
+    ///
+    /// Executions already started keep running the revision they started
+    /// with. Returns the active revision.
+    pub fn update_playbook(&self, playbook: Playbook) -> Result<u32> {
         if !self.playbooks.contains_key(&playbook.id) {
             return Err(AppError::NotFound(format!(
                 "Playbook {} not found",
                 playbook.id
             )));
         }
+        validate_playbook(&playbook)?;
 
-        self.playbooks.insert(playbook.id, playbook);
-        Ok(())
+        Ok(self.publish(playbook))
     }
 
     /// Delete a playbook
+    ///
+    /// Its revisions are kept, so executions that started before can still
+    /// finish.
     pub fn delete_playbook(&self, id: &Uuid) -> Result<()> {
         self.playbooks
             .remove(id)
@@ -94,6 +172,142 @@ impl PlaybookService {
         Ok(())
     }
 
+    /// Every published revision of a playbook, oldest first
+    pub fn list_revisions(&self, id: &Uuid) -> Result<Vec<PlaybookRevision>> {
+        self.revisions
+            .get(id)
+            .map(|history| history.clone())
+            .ok_or_else(|| AppError::NotFound(format!("Playbook {} not found", id)))
+    }
+
+    /// Get one revision of a playbook
+    pub fn get_revision(&self, id: &Uuid, revision: u32) -> Result<PlaybookRevision> {
+        self.revisions
+            .get(id)
+            .and_then(|history| history.iter().find(|r| r.revision == revision).cloned())
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Playbook {} has no revision {}", id, revision))
+            })
+    }
+
+    /// Changes between two revisions of a playbook
+    ///
+    /// `to` defaults to the active revision.
+    pub fn diff_revisions(&self, id: &Uuid, from: u32, to: Option<u32>) -> Result<PlaybookDiff> {
+        let to = match to {
+            Some(to) => to,
+            None => self.active_revision(id)?,
+        };
+        let before = self.get_revision(id, from)?;
+        let after = self.get_revision(id, to)?;
+
+        Ok(PlaybookDiff {
+            playbook_id: *id,
+            from_revision: from,
+            to_revision: to,
+            changes: revisions::diff(&before.playbook, &after.playbook),
+        })
+    }
+
+    /// Make an earlier revision of a playbook the active one
+    ///
+    /// Without `revision` the playbook goes back to the revision published
+    /// before the active one. Executions already started are not affected.
+    /// Returns the playbook as it is now.
+    pub fn rollback_playbook(&self, id: &Uuid, revision: Option<u32>) -> Result<Playbook> {
+        let active = self.active_revision(id)?;
+        let target = match revision {
+            Some(revision) => self.get_revision(id, revision)?,
+            None => self
+                .list_revisions(id)?
+                .into_iter()
+                .rev()
+                .find(|r| r.revision < active)
+                .ok_or_else(|| {
+                    AppError::Validation(format!(
+                        "Playbook {} has no revision before {} to roll back to",
+                        id, active
+                    ))
+                })?,
+        };
+
+        info!(
+            playbook_id = %id,
+            from_revision = active,
+            to_revision = target.revision,
+            "Rolling back playbook"
+        );
+
+        self.playbooks.insert(*id, target.playbook.clone());
+        Ok(target.playbook)
+    }
+
+    /// Revision of a playbook that is active
+    fn active_revision(&self, id: &Uuid) -> Result<u32> {
+        self.playbooks
+            .get(id)
+            .map(|p| p.revision)
+            .ok_or_else(|| AppError::NotFound(format!("Playbook {} not found", id)))
+    }
+
+    /// Keep a validated definition as a revision and activate it
+    fn publish(&self, mut playbook: Playbook) -> u32 {
+        let digest = revisions::digest(&playbook);
+        let active = {
+            let mut history = self.revisions.entry(playbook.id).or_default();
+            match history.iter().find(|r| r.digest == digest) {
+                Some(existing) => existing.playbook.clone(),
+                None => {
+                    playbook.revision = history.last().map_or(1, |r| r.revision + 1);
+                    history.push(PlaybookRevision {
+                        revision: playbook.revision,
+                        digest,
+                        published_at: Utc::now(),
+                        playbook: playbook.clone(),
+                    });
+                    info!(
+                        playbook_id = %playbook.id,
+                        revision = playbook.revision,
+                        "Published playbook revision"
+                    );
+                    playbook
+                }
+            }
+        };
+
+        let revision = active.revision;
+        self.playbooks.insert(active.id, active);
+        revision
+    }
+
+    /// Revision of its playbook an execution is pinned to
+    ///
+    /// Executions saved before playbooks had revisions run the active one.
+    fn pinned_playbook(&self, execution: &PlaybookExecution) -> Result<Playbook> {
+        let not_found = || {
+            AppError::NotFound(format!(
+                "Playbook {} revision {} not found",
+                execution.playbook_id, execution.playbook_revision
+            ))
+        };
+        if execution.playbook_revision == 0 {
+            return self.get_playbook(&execution.playbook_id).ok_or_else(not_found);
+        }
+
+        let history = self.revisions.get(&execution.playbook_id).ok_or_else(not_found)?;
+        history
+            .iter()
+            .find(|r| {
+                if execution.playbook_digest.is_empty() {
+                    r.revision == execution.playbook_revision
+                } else {
+                    r.digest == execution.playbook_digest
+                }
+            })
+            .map(|r| r.playbook.clone())
+            .ok_or_else(not_found)
+    }
+
     /// Find matching playbooks for an incident
     pub fn find_matching_playbooks(&self, incident: &Incident) -> Vec<Playbook> {
         self.playbooks
@@ -133,7 +347,7 @@ impl PlaybookService {
         let execution = self.executor.execute_playbook(&playbook, &mut context).await?;
 
         // Store execution result
-        self.executions.insert(execution.id, execution.clone());
+        self.record_execution(&playbook, incident, &execution, &HashSet::new()).await;
 
         info!(
             execution_id = %execution.id,
@@ -146,6 +360,32 @@ impl PlaybookService {
         Ok(execution)
     }
 
+    /// Trace what a playbook would do for an incident without running it
+    ///
+    /// Disabled playbooks can be simulated, so a new playbook can be checked
+    /// before it is enabled. The incident may be synthetic; it is not read
+    /// from or written to the store.
+    pub async fn simulate_playbook(
+        &self,
+        playbook_id: Uuid,
+        incident: &Incident,
+    ) -> Result<PlaybookSimulation> {
+        let playbook = self
+            .get_playbook(&playbook_id)
+            .ok_or_else(|| AppError::NotFound(format!("Playbook {} not found", playbook_id)))?;
+
+        info!(
+            playbook_id = %playbook_id,
+            incident_id = %incident.id,
+            "Simulating playbook"
+        );
+
+        let mut context = ExecutionContext::new(incident.clone());
+        PlaybookExecutor::dry_run()
+            .simulate_playbook(&playbook, &mut context)
+            .await
+    }
+
     /// Auto-execute playbooks for an incident
     pub async fn auto_execute_for_incident(&self, incident: &Incident) -> Vec<PlaybookExecution> {
         if !self.auto_execute {
@@ -189,6 +429,349 @@ impl PlaybookService {
         executions
     }
 
+    /// Approve a step an execution is waiting on and resume the execution
+    ///
+    /// `step_id` may be left out while only one step is waiting.
+    pub async fn approve_execution(
+        &self,
+        execution_id: Uuid,
+        step_id: Option<&str>,
+        decided_by: &str,
+        comment: Option<String>,
+    ) -> Result<PlaybookExecution> {
+        self.decide(execution_id, step_id, true, decided_by, comment, false).await
+    }
+
+    /// Reject a step an execution is waiting on, cancelling the execution
+    ///
+    /// `step_id` may be left out while only one step is waiting.
+    pub async fn reject_execution(
+        &self,
+        execution_id: Uuid,
+        step_id: Option<&str>,
+        decided_by: &str,
+        comment: Option<String>,
+    ) -> Result<PlaybookExecution> {
+        self.decide(execution_id, step_id, false, decided_by, comment, false).await
+    }
+
+    /// Apply the timeout policy of every approval past its deadline
+    ///
+    /// Returns the executions that were decided.
+    pub async fn expire_approvals(&self) -> Vec<PlaybookExecution> {
+        let now = Utc::now();
+        let expired: Vec<(Uuid, String, ApprovalTimeoutAction)> = self
+            .executions
+            .iter()
+            .filter(|entry| entry.value().status == ExecutionStatus::WaitingForApproval)
+            .flat_map(|entry| {
+                let execution = entry.value();
+                execution
+                    .pending_approvals()
+                    .filter(|request| request.is_expired(now))
+                    .map(|request| (execution.id, request.step_id.clone(), request.on_timeout))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let mut decided = Vec::new();
+        for (execution_id, step_id, on_timeout) in expired {
+            let approved = on_timeout == ApprovalTimeoutAction::Approve;
+            info!(
+                execution_id = %execution_id,
+                step_id = %step_id,
+                approved,
+                "Approval timed out"
+            );
+
+            let comment = Some("No decision before the approval timeout".to_string());
+            match self
+                .decide(
+                    execution_id,
+                    Some(&step_id),
+                    approved,
+                    APPROVAL_TIMEOUT_DECIDER,
+                    comment,
+                    true,
+                )
+                .await
+            {
+                Ok(execution) => decided.push(execution),
+                Err(e) => error!(
+                    execution_id = %execution_id,
+                    step_id = %step_id,
+                    error = %e,
+                    "Failed to apply approval timeout"
+                ),
+            }
+        }
+        decided
+    }
+
+    /// Apply approval timeouts periodically
+    pub async fn run_approval_monitor(self: Arc<Self>) {
+        info!(
+            check_interval = self.approval_check_interval_secs,
+            "Starting approval monitor"
+        );
+
+        loop {
+            self.expire_approvals().await;
+            sleep(Duration::from_secs(self.approval_check_interval_secs)).await;
+        }
+    }
+
+    /// Load executions saved by an earlier process
+    ///
+    /// Executions paused for approval can be decided again once the
+    /// playbook revision they started with is registered again. Executions
+    /// the process stopped in the middle of are recovered with that
+    /// revision, which must be registered first: resumed if every
+    /// interrupted step is idempotent, abandoned otherwise. Returns how many
+    /// executions were loaded.
+    pub async fn restore_executions(&self) -> Result<usize> {
+        let executions = self.store.list_playbook_executions(None).await?;
+        let waiting = executions
+            .iter()
+            .filter(|e| e.status == ExecutionStatus::WaitingForApproval)
+            .count();
+        let interrupted: Vec<PlaybookExecution> = executions
+            .iter()
+            .filter(|e| e.status == ExecutionStatus::Running)
+            .cloned()
+            .collect();
+
+        let count = executions.len();
+        for execution in executions {
+            self.executions.insert(execution.id, execution);
+        }
+
+        info!(
+            executions = count,
+            waiting_for_approval = waiting,
+            interrupted = interrupted.len(),
+            "Restored playbook executions"
+        );
+
+        for execution in interrupted {
+            self.recover_execution(execution).await;
+        }
+        Ok(count)
+    }
+
+    /// Resume or abandon an execution interrupted by a restart
+    ///
+    /// An execution that cannot be recovered at all, e.g. because its
+    /// playbook is no longer registered, is abandoned.
+    async fn recover_execution(&self, execution: PlaybookExecution) {
+        let execution_id = execution.id;
+        let recovered = async {
+            let playbook = self.pinned_playbook(&execution)?;
+            let incident = self
+                .store
+                .get_incident(&execution.incident_id)
+                .await?
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Incident {} not found", execution.incident_id))
+                })?;
+
+            let already_waiting: HashSet<String> =
+                execution.pending_approvals().map(|a| a.step_id.clone()).collect();
+            let mut context = ExecutionContext::new(incident.clone());
+            let recovered = self
+                .executor
+                .recover_playbook(&playbook, execution.clone(), &mut context)
+                .await?;
+            self.record_execution(&playbook, &incident, &recovered, &already_waiting).await;
+            Ok::<_, AppError>(recovered)
+        }
+        .await;
+
+        match recovered {
+            Ok(recovered) => info!(
+                execution_id = %execution_id,
+                status = ?recovered.status,
+                "Recovered interrupted playbook execution"
+            ),
+            Err(e) => {
+                error!(
+                    execution_id = %execution_id,
+                    error = %e,
+                    "Failed to recover playbook execution; abandoning it"
+                );
+                let mut execution = execution;
+                execution.status = ExecutionStatus::Abandoned;
+                execution.error = Some(format!("Could not recover after restart: {}", e));
+                execution.completed_at = Some(Utc::now());
+                self.executions.insert(execution_id, execution.clone());
+                if let Err(e) = self.store.save_playbook_execution(&execution).await {
+                    error!(
+                        execution_id = %execution_id,
+                        error = %e,
+                        "Failed to persist playbook execution"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Record a decision on a waiting step and resume the execution
+    async fn decide(
+        &self,
+        execution_id: Uuid,
+        step_id: Option<&str>,
+        approved: bool,
+        decided_by: &str,
+        comment: Option<String>,
+        timed_out: bool,
+    ) -> Result<PlaybookExecution> {
+        let execution = self
+            .get_execution(&execution_id)
+            .ok_or_else(|| AppError::NotFound(format!("Execution {} not found", execution_id)))?;
+        if execution.status != ExecutionStatus::WaitingForApproval {
+            return Err(not_waiting(execution_id));
+        }
+        let playbook = self.pinned_playbook(&execution)?;
+        let incident = self
+            .store
+            .get_incident(&execution.incident_id)
+            .await?
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Incident {} not found", execution.incident_id))
+            })?;
+
+        // Claim the execution so concurrent decisions cannot resume it twice
+        let (decided, waiting) = {
+            let mut entry = self.executions.get_mut(&execution_id).ok_or_else(|| {
+                AppError::NotFound(format!("Execution {} not found", execution_id))
+            })?;
+            if entry.status != ExecutionStatus::WaitingForApproval {
+                return Err(not_waiting(execution_id));
+            }
+            let before = entry.clone();
+
+            let pending: Vec<usize> = entry
+                .approvals
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| {
+                    a.decision.is_none() && step_id.is_none_or(|id| a.step_id == id)
+                })
+                .map(|(i, _)| i)
+                .collect();
+            let index = match (pending.as_slice(), step_id) {
+                ([index], _) => *index,
+                ([], Some(id)) => {
+                    return Err(AppError::NotFound(format!(
+                        "Step {} of execution {} is not waiting for approval",
+                        id, execution_id
+                    )))
+                }
+                (_, _) => {
+                    return Err(AppError::Validation(format!(
+                        "Execution {} is waiting on {} approvals; specify the step",
+                        execution_id,
+                        pending.len()
+                    )))
+                }
+            };
+
+            let request = &mut entry.approvals[index];
+            if !timed_out
+                && !request.approvers.is_empty()
+                && !request.approvers.iter().any(|a| a == decided_by)
+            {
+                return Err(AppError::Authorization(format!(
+                    "{} is not an approver of step {}",
+                    decided_by, request.step_id
+                )));
+            }
+            request.decision = Some(ApprovalDecision {
+                approved,
+                decided_by: decided_by.to_string(),
+                decided_at: Utc::now(),
+                comment,
+            });
+            let decided = entry.clone();
+            entry.status = ExecutionStatus::Running;
+            (decided, before)
+        };
+
+        let already_waiting: HashSet<String> =
+            waiting.pending_approvals().map(|a| a.step_id.clone()).collect();
+        let mut context = ExecutionContext::new(incident.clone());
+        let execution = match self
+            .executor
+            .resume_playbook(&playbook, decided, &mut context)
+            .await
+        {
+            Ok(execution) => execution,
+            Err(e) => {
+                // Leave the execution waiting so the decision can be retried
+                self.executions.insert(execution_id, waiting);
+                return Err(e);
+            }
+        };
+
+        self.record_execution(&playbook, &incident, &execution, &already_waiting).await;
+
+        info!(
+            execution_id = %execution.id,
+            approved,
+            decided_by = %decided_by,
+            status = ?execution.status,
+            "Playbook execution resumed"
+        );
+        Ok(execution)
+    }
+
+    /// Keep an execution and persist it, then ask the approvers of any step
+    /// that started waiting
+    async fn record_execution(
+        &self,
+        playbook: &Playbook,
+        incident: &Incident,
+        execution: &PlaybookExecution,
+        already_waiting: &HashSet<String>,
+    ) {
+        self.executions.insert(execution.id, execution.clone());
+
+        if let Err(e) = self.store.save_playbook_execution(execution).await {
+            error!(
+                execution_id = %execution.id,
+                error = %e,
+                "Failed to persist playbook execution"
+            );
+        }
+
+        if execution.status != ExecutionStatus::WaitingForApproval {
+            return;
+        }
+        let Some(ref notifications) = self.notification_service else {
+            warn!(
+                execution_id = %execution.id,
+                "Playbook execution is waiting for approval but notifications are disabled"
+            );
+            return;
+        };
+        for request in execution.pending_approvals() {
+            if already_waiting.contains(&request.step_id) {
+                continue;
+            }
+            if let Err(e) = notifications
+                .notify_approval_requested(incident, execution.id, &playbook.name, request)
+                .await
+            {
+                error!(
+                    execution_id = %execution.id,
+                    step_id = %request.step_id,
+                    error = %e,
+                    "Failed to notify approvers"
+                );
+            }
+        }
+    }
+
     /// Get execution by ID
     pub fn get_execution(&self, id: &Uuid) -> Option<PlaybookExecution> {
         self.executions.get(id).map(|e| e.clone())
@@ -253,6 +836,53 @@ pub struct PlaybookServiceStats {
     pub auto_execute_enabled: bool,
 }
 
+fn not_waiting(execution_id: Uuid) -> AppError {
+    AppError::InvalidStateTransition(format!(
+        "Execution {} is not waiting for approval",
+        execution_id
+    ))
+}
+
+/// Check a playbook's step graph, step conditions and approval steps
+pub fn validate_playbook(playbook: &Playbook) -> Result<()> {
+    PlaybookGraph::build(playbook)?;
+
+    for step in &playbook.steps {
+        if step.step_type == StepType::Approval {
+            if step.approval.as_ref().is_none_or(|a| a.approvers.is_empty()) {
+                return Err(AppError::Validation(format!(
+                    "Approval step '{}' needs at least one approver",
+                    step.id
+                )));
+            }
+            if !step.actions.is_empty() {
+                return Err(AppError::Validation(format!(
+                    "Approval step '{}' cannot have actions",
+                    step.id
+                )));
+            }
+            if let Some(ref timeout) = step.timeout {
+                parse_duration(timeout).map_err(|_| {
+                    AppError::Validation(format!(
+                        "Approval step '{}' has an invalid timeout: {}",
+                        step.id, timeout
+                    ))
+                })?;
+            }
+        }
+
+        if let Some(condition) = step.condition.as_deref().filter(|c| !c.trim().is_empty()) {
+            Expression::parse(condition).map_err(|e| match e {
+                AppError::Validation(message) => {
+                    AppError::Validation(format!("Step '{}' condition: {}", step.id, message))
+                }
+                other => other,
+            })?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -268,6 +898,7 @@ mod tests {
             id: Uuid::new_v4(),
             name: "Test Playbook".to_string(),
             version: "1.0".to_string(),
+            revision: 0,
             description: "Test playbook".to_string(),
             owner: "test".to_string(),
             created_at: chrono::Utc::now(),
@@ -297,12 +928,32 @@ mod tests {
                 retry: 0,
                 backoff: BackoffStrategy::Fixed,
                 condition: None,
+                depends_on: None,
+                trigger_rule: Default::default(),
+                approval: None,
+                idempotent: false,
             }],
             enabled: true,
             tags: vec![],
         }
     }
 
+    /// Test playbook whose wait step needs approval first
+    fn create_approval_playbook(timeout: &str, on_timeout: ApprovalTimeoutAction) -> Playbook {
+        let mut playbook = create_test_playbook();
+        let mut approve = playbook.steps[0].clone();
+        approve.id = "approve".to_string();
+        approve.step_type = StepType::Approval;
+        approve.actions = vec![];
+        approve.timeout = Some(timeout.to_string());
+        approve.approval = Some(crate::models::ApprovalPolicy {
+            approvers: vec!["alice".to_string()],
+            on_timeout,
+        });
+        playbook.steps.insert(0, approve);
+        playbook
+    }
+
     fn create_test_incident() -> Incident {
         Incident::new(
             "test".to_string(),
@@ -327,6 +978,35 @@ mod tests {
         assert_eq!(retrieved.id, playbook_id);
     }
 
+    #[tokio::test]
+    async fn test_register_rejects_cyclic_playbook() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store, None, false);
+
+        let mut playbook = create_test_playbook();
+        let mut second = playbook.steps[0].clone();
+        second.id = "step2".to_string();
+        playbook.steps[0].depends_on = Some(vec!["step2".to_string()]);
+        playbook.steps.push(second);
+
+        let result = service.register_playbook(playbook.clone());
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(service.get_playbook(&playbook.id).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_register_rejects_invalid_condition() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store, None, false);
+
+        let mut playbook = create_test_playbook();
+        playbook.steps[0].condition = Some("incident.severity == 'P0' &&".to_string());
+
+        let error = service.register_playbook(playbook).unwrap_err().to_string();
+        assert!(error.contains("Step 'step1' condition"), "{}", error);
+        assert!(error.contains("at column 29"), "{}", error);
+    }
+
     #[tokio::test]
     async fn test_find_matching_playbooks() {
         let store = Arc::new(InMemoryStore::new());
@@ -411,4 +1091,256 @@ mod tests {
         assert_eq!(stats.successful_executions, 1);
         assert_eq!(stats.failed_executions, 0);
     }
+
+    #[tokio::test]
+    async fn test_register_rejects_approval_without_approvers() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store, None, false);
+
+        let mut playbook = create_approval_playbook("30m", ApprovalTimeoutAction::Reject);
+        playbook.steps[0].approval = None;
+
+        let error = service.register_playbook(playbook).unwrap_err().to_string();
+        assert!(error.contains("needs at least one approver"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_approval_pauses_and_resumes() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store.clone(), None, false);
+
+        let playbook = create_approval_playbook("30m", ApprovalTimeoutAction::Reject);
+        let playbook_id = playbook.id;
+        service.register_playbook(playbook).unwrap();
+
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let execution = service.execute_playbook(playbook_id, &incident).await.unwrap();
+        assert_eq!(execution.status, ExecutionStatus::WaitingForApproval);
+        assert!(!execution.step_results.contains_key("step1"));
+
+        let saved = store.get_playbook_execution(&execution.id).await.unwrap().unwrap();
+        assert_eq!(saved.status, ExecutionStatus::WaitingForApproval);
+
+        let result = service
+            .approve_execution(execution.id, None, "mallory", None)
+            .await;
+        assert!(matches!(result, Err(AppError::Authorization(_))));
+
+        let resumed = service
+            .approve_execution(execution.id, Some("approve"), "alice", Some("go".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(resumed.status, ExecutionStatus::Completed);
+        assert_eq!(resumed.step_results["step1"].status, ExecutionStatus::Completed);
+
+        let saved = store.get_playbook_execution(&execution.id).await.unwrap().unwrap();
+        assert_eq!(saved.status, ExecutionStatus::Completed);
+
+        let result = service.reject_execution(execution.id, None, "alice", None).await;
+        assert!(matches!(result, Err(AppError::InvalidStateTransition(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revisions_diff_and_rollback() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store, None, false);
+
+        let first = create_test_playbook();
+        let playbook_id = first.id;
+        assert_eq!(service.register_playbook(first.clone()).unwrap(), 1);
+
+        let mut second = first.clone();
+        second.steps[0].actions[0]
+            .parameters
+            .insert("duration".to_string(), serde_json::json!(5));
+        assert_eq!(service.update_playbook(second.clone()).unwrap(), 2);
+        assert_eq!(service.get_playbook(&playbook_id).unwrap().revision, 2);
+
+        let diff = service.diff_revisions(&playbook_id, 1, None).unwrap();
+        assert_eq!(diff.to_revision, 2);
+        assert_eq!(diff.changes.len(), 1);
+        assert_eq!(diff.changes[0].path, "steps.step1.actions[0].parameters.duration");
+
+        let rolled_back = service.rollback_playbook(&playbook_id, None).unwrap();
+        assert_eq!(rolled_back.revision, 1);
+        assert_eq!(service.get_playbook(&playbook_id).unwrap().revision, 1);
+        let result = service.rollback_playbook(&playbook_id, None);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        // Publishing a definition seen before reactivates its revision
+        assert_eq!(service.register_playbook(second).unwrap(), 2);
+        assert_eq!(service.list_revisions(&playbook_id).unwrap().len(), 2);
+        assert!(service.get_revision(&playbook_id, 3).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_execution_pinned_to_revision() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store.clone(), None, false);
+
+        let playbook = create_approval_playbook("30m", ApprovalTimeoutAction::Reject);
+        let playbook_id = playbook.id;
+        service.register_playbook(playbook.clone()).unwrap();
+
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let execution = service.execute_playbook(playbook_id, &incident).await.unwrap();
+        assert_eq!(execution.status, ExecutionStatus::WaitingForApproval);
+        assert_eq!(execution.playbook_revision, 1);
+
+        // Steps published while the execution waits do not reach it
+        let mut updated = playbook;
+        updated.steps[1].id = "renamed".to_string();
+        assert_eq!(service.update_playbook(updated).unwrap(), 2);
+
+        let resumed = service
+            .approve_execution(execution.id, None, "alice", None)
+            .await
+            .unwrap();
+        assert_eq!(resumed.status, ExecutionStatus::Completed);
+        assert_eq!(resumed.playbook_revision, 1);
+        assert!(resumed.step_results.contains_key("step1"));
+        assert!(!resumed.step_results.contains_key("renamed"));
+    }
+
+    #[tokio::test]
+    async fn test_paused_execution_survives_restart() {
+        let store = Arc::new(InMemoryStore::new());
+        let playbook = create_approval_playbook("30m", ApprovalTimeoutAction::Reject);
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let execution_id = {
+            let service = PlaybookService::new(store.clone(), None, false);
+            service.register_playbook(playbook.clone()).unwrap();
+            service.execute_playbook(playbook.id, &incident).await.unwrap().id
+        };
+
+        let service = PlaybookService::new(store.clone(), None, false);
+        service.register_playbook(playbook).unwrap();
+        assert_eq!(service.restore_executions().await.unwrap(), 1);
+
+        let rejected = service
+            .reject_execution(execution_id, None, "alice", Some("too risky".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(rejected.status, ExecutionStatus::Cancelled);
+        assert_eq!(
+            rejected.approvals[0].decision.as_ref().unwrap().comment.as_deref(),
+            Some("too risky")
+        );
+        assert!(!rejected.step_results.contains_key("step1"));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_disabled_playbook() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store.clone(), None, false);
+
+        let mut playbook = create_test_playbook();
+        playbook.enabled = false;
+        playbook.steps[0].actions[0].action_type = ActionType::IncidentResolve;
+        playbook.steps[0].actions[0].parameters = HashMap::from([(
+            "root_cause".to_string(),
+            serde_json::json!("Resolved {{incident_title}}"),
+        )]);
+        service.register_playbook(playbook.clone()).unwrap();
+
+        let incident = create_test_incident();
+        let simulation = service.simulate_playbook(playbook.id, &incident).await.unwrap();
+
+        assert_eq!(simulation.status, ExecutionStatus::Completed);
+        assert_eq!(
+            simulation.steps[0].actions[0].parameters["root_cause"],
+            serde_json::json!("Resolved Test Incident")
+        );
+        assert!(service.list_executions().is_empty());
+        assert!(store.list_playbook_executions(None).await.unwrap().is_empty());
+        assert!(store.get_incident(&incident.id).await.unwrap().is_none());
+
+        assert!(service.simulate_playbook(Uuid::new_v4(), &incident).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_executions_recovered_on_restart() {
+        let store = Arc::new(InMemoryStore::new());
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let mut idempotent = create_test_playbook();
+        idempotent.steps[0].idempotent = true;
+        let not_idempotent = create_test_playbook();
+        let unregistered = create_test_playbook();
+
+        // Checkpoints as they would be left by a process stopped mid-step
+        let mut interrupted = Vec::new();
+        {
+            let service = PlaybookService::new(store.clone(), None, false);
+            for playbook in [&idempotent, &not_idempotent, &unregistered] {
+                service.register_playbook(playbook.clone()).unwrap();
+                let mut execution =
+                    service.execute_playbook(playbook.id, &incident).await.unwrap();
+                execution.status = ExecutionStatus::Running;
+                execution.completed_at = None;
+                let result = execution.step_results.get_mut("step1").unwrap();
+                result.status = ExecutionStatus::Running;
+                result.completed_at = None;
+                store.save_playbook_execution(&execution).await.unwrap();
+                interrupted.push(execution.id);
+            }
+        }
+
+        let service = PlaybookService::new(store.clone(), None, false);
+        service.register_playbook(idempotent).unwrap();
+        service.register_playbook(not_idempotent).unwrap();
+        assert_eq!(service.restore_executions().await.unwrap(), 3);
+
+        let resumed = service.get_execution(&interrupted[0]).unwrap();
+        assert_eq!(resumed.status, ExecutionStatus::Completed);
+        assert_eq!(resumed.step_results["step1"].status, ExecutionStatus::Completed);
+
+        let abandoned = service.get_execution(&interrupted[1]).unwrap();
+        assert_eq!(abandoned.status, ExecutionStatus::Abandoned);
+        assert_eq!(abandoned.step_results["step1"].status, ExecutionStatus::Abandoned);
+
+        let orphaned = store.get_playbook_execution(&interrupted[2]).await.unwrap().unwrap();
+        assert_eq!(orphaned.status, ExecutionStatus::Abandoned);
+        assert!(orphaned.error.unwrap().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_expired_approval_applies_timeout_policy() {
+        let store = Arc::new(InMemoryStore::new());
+        let service = PlaybookService::new(store.clone(), None, false);
+
+        let approve_on_timeout = create_approval_playbook("0s", ApprovalTimeoutAction::Approve);
+        let reject_on_timeout = create_approval_playbook("0s", ApprovalTimeoutAction::Reject);
+        let waits = create_approval_playbook("1h", ApprovalTimeoutAction::Reject);
+        for playbook in [&approve_on_timeout, &reject_on_timeout, &waits] {
+            service.register_playbook(playbook.clone()).unwrap();
+        }
+
+        let incident = create_test_incident();
+        store.save_incident(&incident).await.unwrap();
+        let approved = service.execute_playbook(approve_on_timeout.id, &incident).await.unwrap();
+        let rejected = service.execute_playbook(reject_on_timeout.id, &incident).await.unwrap();
+        let waiting = service.execute_playbook(waits.id, &incident).await.unwrap();
+
+        let decided = service.expire_approvals().await;
+        assert_eq!(decided.len(), 2);
+
+        let approved = service.get_execution(&approved.id).unwrap();
+        assert_eq!(approved.status, ExecutionStatus::Completed);
+        let decision = approved.approvals[0].decision.clone().unwrap();
+        assert_eq!(decision.decided_by, APPROVAL_TIMEOUT_DECIDER);
+
+        let rejected = service.get_execution(&rejected.id).unwrap();
+        assert_eq!(rejected.status, ExecutionStatus::Cancelled);
+
+        let waiting = service.get_execution(&waiting.id).unwrap();
+        assert_eq!(waiting.status, ExecutionStatus::WaitingForApproval);
+    }
 }
@@ -0,0 +1,144 @@
+//! RuVector service client
+//!
+//! Persists post-mortem decision events to ruvector-service, which is the
+//! only permitted persistence path for agent decisions.
+
+use crate::error::{AppError, Result};
+use crate::postmortem::models::DecisionEvent;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{debug, error};
+use uuid::Uuid;
+
+const INTEGRATION_SOURCE: &str = "ruvector";
+
+/// RuVector client configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuvectorConfig {
+    /// Base URL of ruvector-service
+    pub base_url: String,
+
+    /// Bearer token
+    pub api_key: Option<String>,
+
+    /// Request timeout (milliseconds)
+    pub timeout_ms: u64,
+}
+
+impl Default for RuvectorConfig {
+    /// Load configuration from `RUVECTOR_SERVICE_URL`, `RUVECTOR_API_KEY`
+    /// and `RUVECTOR_TIMEOUT_MS`
+    fn default() -> Self {
+        Self {
+            base_url: std::env::var("RUVECTOR_SERVICE_URL")
+                .unwrap_or_else(|_| "http://localhost:8081".to_string()),
+            api_key: std::env::var("RUVECTOR_API_KEY").ok(),
+            timeout_ms: std::env::var("RUVECTOR_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30_000),
+        }
+    }
+}
+
+/// Response returned when a decision event is persisted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersistenceResponse {
+    pub id: Uuid,
+}
+
+/// Client for ruvector-service decision events
+#[derive(Clone)]
+pub struct RuvectorClient {
+    client: Client,
+    config: RuvectorConfig,
+}
+
+impl RuvectorClient {
+    /// Create a new client
+    pub fn new(config: RuvectorConfig) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| {
+                AppError::Configuration(format!("Failed to build ruvector HTTP client: {}", e))
+            })?;
+
+        Ok(Self { client, config })
+    }
+
+    /// Get the client configuration
+    pub fn config(&self) -> &RuvectorConfig {
+        &self.config
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url.trim_end_matches('/'), path)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self.config.api_key {
+            Some(ref key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Persist a decision event
+    pub async fn store_decision_event(&self, event: &DecisionEvent) -> Result<PersistenceResponse> {
+        let url = self.url("/api/v1/decision-events");
+        debug!(url = %url, event_id = %event.id, "Persisting decision event");
+
+        let response = self
+            .authorize(self.client.post(&url).json(event))
+            .send()
+            .await
+            .map_err(|e| integration_error(format!("Failed to persist decision event: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            error!(status = %status, body = %body, "ruvector-service returned error");
+            return Err(integration_error(format!("API returned {}: {}", status, body)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| integration_error(format!("Failed to parse persistence response: {}", e)))
+    }
+
+    /// Fetch a decision event by ID
+    pub async fn get_decision_event(&self, id: &Uuid) -> Result<Option<DecisionEvent>> {
+        let url = self.url(&format!("/api/v1/decision-events/{}", id));
+
+        let response = self
+            .authorize(self.client.get(&url))
+            .send()
+            .await
+            .map_err(|e| integration_error(format!("Failed to fetch decision event: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(integration_error(format!("API returned {}: {}", status, body)));
+        }
+
+        response
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| integration_error(format!("Failed to parse decision event: {}", e)))
+    }
+}
+
+fn integration_error(message: String) -> AppError {
+    AppError::Integration {
+        integration_source: INTEGRATION_SOURCE.to_string(),
+        message,
+    }
+}
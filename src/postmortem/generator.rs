@@ -0,0 +1,843 @@
+//! Post-mortem generation
+//!
+//! Reconstructs the incident lifecycle from immutable records (the incident
+//! timeline, resolution, notes, playbook executions and escalation history)
+//! and produces a draft [`PostMortem`]. Drafts are kept in the incident store
+//! and can be regenerated until they are published.
+
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationEngine;
+use crate::models::{
+    EventType, ExecutionStatus, Incident, IncidentState, IncidentType, ResolutionMethod,
+    Severity, TimelineEvent,
+};
+use crate::playbooks::PlaybookService;
+use crate::postmortem::client::RuvectorClient;
+use crate::postmortem::models::*;
+use crate::state::IncidentStore;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Constraints recorded on every decision event
+const CONSTRAINTS: &[&str] = &[
+    "no_incident_state_modification",
+    "no_remediation_triggered",
+    "no_severity_reassignment",
+    "timeline_reconstructed_from_records",
+    "resolved_or_closed_incidents_only",
+];
+
+/// Generates post-mortems for resolved incidents
+pub struct PostMortemGenerator {
+    store: Arc<dyn IncidentStore>,
+    ruvector: Option<Arc<RuvectorClient>>,
+    playbook_service: Option<Arc<PlaybookService>>,
+    escalation_engine: Option<Arc<EscalationEngine>>,
+}
+
+impl PostMortemGenerator {
+    /// Create a new generator backed by the incident store
+    pub fn new(store: Arc<dyn IncidentStore>) -> Self {
+        Self {
+            store,
+            ruvector: None,
+            playbook_service: None,
+            escalation_engine: None,
+        }
+    }
+
+    /// Persist decision events to ruvector-service
+    pub fn with_ruvector_client(mut self, client: RuvectorClient) -> Self {
+        self.ruvector = Some(Arc::new(client));
+        self
+    }
+
+    /// Include playbook executions in generated post-mortems
+    pub fn with_playbook_service(mut self, service: Arc<PlaybookService>) -> Self {
+        self.playbook_service = Some(service);
+        self
+    }
+
+    /// Include escalation history in generated post-mortems
+    pub fn with_escalation_engine(mut self, engine: Arc<EscalationEngine>) -> Self {
+        self.escalation_engine = Some(engine);
+        self
+    }
+
+    /// Generate (or regenerate) the draft post-mortem for an incident
+    pub async fn generate(&self, incident_id: Uuid) -> Result<PostMortem> {
+        let incident = self
+            .store
+            .get_incident(&incident_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Incident {} not found", incident_id)))?;
+
+        if !matches!(incident.state, IncidentState::Resolved | IncidentState::Closed) {
+            return Err(AppError::Validation(format!(
+                "Post-mortems can only be generated for resolved or closed incidents (incident {} is {:?})",
+                incident_id, incident.state
+            )));
+        }
+
+        // Regenerate an existing draft in place; published records are immutable
+        let existing = self
+            .store
+            .list_postmortems(Some(&incident_id))
+            .await?
+            .into_iter()
+            .max_by_key(|pm| pm.created_at);
+
+        if let Some(ref pm) = existing {
+            if !pm.status.is_mutable() {
+                return Err(AppError::InvalidStateTransition(format!(
+                    "Post-mortem {} for incident {} is already published",
+                    pm.id, incident_id
+                )));
+            }
+        }
+
+        let playbook_executions = self.playbook_executions(&incident_id);
+        let escalation_history = self.escalation_history(&incident_id);
+
+        let timeline = build_timeline(&incident, &playbook_executions, &escalation_history);
+        let metrics = compute_metrics(&timeline, &playbook_executions, &escalation_history);
+        let root_cause = analyze_root_cause(&incident);
+        let impact = analyze_impact(&incident, &metrics);
+        let resolution = analyze_resolution(&incident, &playbook_executions);
+        let action_items = derive_action_items(&incident, &root_cause, &metrics, &playbook_executions);
+        let (what_went_well, what_could_be_improved, lessons_learned) =
+            derive_lessons(&incident, &metrics, &playbook_executions, &escalation_history);
+
+        let now = Utc::now();
+        let mut postmortem = PostMortem {
+            id: existing.as_ref().map(|pm| pm.id).unwrap_or_else(Uuid::new_v4),
+            incident_id,
+            title: incident.title.clone(),
+            severity: incident.severity,
+            status: PostMortemStatus::Draft,
+            summary: build_summary(&incident, &root_cause, &metrics),
+            timeline,
+            root_cause,
+            impact,
+            resolution,
+            action_items,
+            what_went_well,
+            what_could_be_improved,
+            lessons_learned,
+            playbook_executions,
+            escalation_history,
+            notes: incident.notes.clone(),
+            metrics,
+            created_at: existing.as_ref().map(|pm| pm.created_at).unwrap_or(now),
+            updated_at: now,
+            reviewed_by: None,
+            published_at: None,
+            decision_event: None,
+        };
+
+        let inputs = json!({
+            "incident_id": incident.id,
+            "incident_updated_at": incident.updated_at,
+            "timeline_events": incident.timeline.len(),
+            "notes": incident.notes.len(),
+            "playbook_executions": postmortem.playbook_executions.len(),
+            "escalation_notifications": postmortem.escalation_history.len(),
+        });
+        let outputs = serde_json::to_value(postmortem.output()).map_err(|e| {
+            AppError::Serialization(format!("Failed to serialize post-mortem output: {}", e))
+        })?;
+        let event = DecisionEvent::new(
+            &inputs,
+            outputs,
+            confidence(&incident, &postmortem),
+            CONSTRAINTS.iter().map(|c| c.to_string()).collect(),
+            postmortem.id.to_string(),
+        );
+
+        if let Some(ref client) = self.ruvector {
+            if let Err(e) = client.store_decision_event(&event).await {
+                warn!(postmortem_id = %postmortem.id, error = %e, "Failed to persist decision event");
+            }
+        }
+        postmortem.decision_event = Some(event);
+
+        self.store.save_postmortem(&postmortem).await?;
+
+        info!(
+            postmortem_id = %postmortem.id,
+            incident_id = %incident_id,
+            "Post-mortem draft generated"
+        );
+
+        Ok(postmortem)
+    }
+
+    /// Get a post-mortem by ID
+    pub async fn get(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        self.store.get_postmortem(id).await
+    }
+
+    /// List post-mortems, newest first
+    pub async fn list(
+        &self,
+        incident_id: Option<&Uuid>,
+        status: Option<PostMortemStatus>,
+        limit: usize,
+    ) -> Result<Vec<PostMortem>> {
+        let mut postmortems: Vec<PostMortem> = self
+            .store
+            .list_postmortems(incident_id)
+            .await?
+            .into_iter()
+            .filter(|pm| status.is_none_or(|s| pm.status == s))
+            .collect();
+
+        postmortems.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        postmortems.truncate(limit);
+
+        Ok(postmortems)
+    }
+
+    /// Publish a post-mortem; published records are immutable
+    pub async fn publish(&self, id: &Uuid, reviewed_by: String) -> Result<PostMortem> {
+        let mut postmortem = self
+            .store
+            .get_postmortem(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Post-mortem {} not found", id)))?;
+
+        if !postmortem.status.is_mutable() {
+            return Err(AppError::InvalidStateTransition(format!(
+                "Post-mortem {} is already published",
+                id
+            )));
+        }
+
+        let now = Utc::now();
+        postmortem.status = PostMortemStatus::Published;
+        postmortem.reviewed_by = Some(reviewed_by);
+        postmortem.published_at = Some(now);
+        postmortem.updated_at = now;
+
+        self.store.save_postmortem(&postmortem).await?;
+
+        info!(postmortem_id = %id, "Post-mortem published");
+        Ok(postmortem)
+    }
+
+    /// Get the decision event recorded for a post-mortem
+    pub async fn decision_event(&self, id: &Uuid) -> Result<Option<DecisionEvent>> {
+        let postmortem = self
+            .store
+            .get_postmortem(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Post-mortem {} not found", id)))?;
+
+        Ok(postmortem.decision_event)
+    }
+
+    fn playbook_executions(&self, incident_id: &Uuid) -> Vec<PlaybookExecutionSummary> {
+        let Some(ref service) = self.playbook_service else {
+            return Vec::new();
+        };
+
+        let mut executions: Vec<PlaybookExecutionSummary> = service
+            .list_executions_for_incident(incident_id)
+            .into_iter()
+            .map(|execution| PlaybookExecutionSummary {
+                execution_id: execution.id,
+                playbook_id: execution.playbook_id,
+                status: format!("{:?}", execution.status).to_lowercase(),
+                started_at: execution.started_at,
+                completed_at: execution.completed_at,
+                steps_completed: execution
+                    .step_results
+                    .values()
+                    .filter(|r| r.status == ExecutionStatus::Completed)
+                    .count(),
+                steps_failed: execution
+                    .step_results
+                    .values()
+                    .filter(|r| r.status == ExecutionStatus::Failed)
+                    .count(),
+                error: execution.error,
+            })
+            .collect();
+
+        executions.sort_by_key(|e| e.started_at);
+        executions
+    }
+
+    fn escalation_history(&self, incident_id: &Uuid) -> Vec<EscalationHistoryEntry> {
+        self.escalation_engine
+            .as_ref()
+            .and_then(|engine| engine.get_escalation_state(incident_id))
+            .map(|state| {
+                state
+                    .notification_history
+                    .into_iter()
+                    .map(|n| EscalationHistoryEntry {
+                        sent_at: n.sent_at,
+                        level: n.level,
+                        target: n.target,
+                        channel: n.channel,
+                        success: n.success,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Map an incident timeline event to a post-mortem timeline entry type
+fn classify_event(event: &TimelineEvent) -> TimelineEntryType {
+    match event.event_type {
+        EventType::Created | EventType::AlertReceived => TimelineEntryType::Detection,
+        EventType::StateChanged => match event.metadata.get("new_state").map(String::as_str) {
+            Some("Detected") => TimelineEntryType::Detection,
+            Some("Triaged") => TimelineEntryType::Acknowledgment,
+            Some("Investigating") => TimelineEntryType::Investigation,
+            Some("Remediating") => TimelineEntryType::Remediation,
+            Some("Resolved") | Some("Closed") => TimelineEntryType::Resolution,
+            _ => TimelineEntryType::Decision,
+        },
+        EventType::ActionExecuted
+        | EventType::PlaybookStarted
+        | EventType::PlaybookCompleted => TimelineEntryType::Remediation,
+        EventType::NotificationSent => TimelineEntryType::Notification,
+        EventType::AssignmentChanged | EventType::Acknowledged => TimelineEntryType::Acknowledgment,
+        EventType::CommentAdded => TimelineEntryType::Communication,
+        EventType::Escalated => TimelineEntryType::Escalation,
+        EventType::Resolved => TimelineEntryType::Resolution,
+        EventType::SeverityChanged => TimelineEntryType::Decision,
+    }
+}
+
+/// Rebuild the timeline from the incident and related records
+fn build_timeline(
+    incident: &Incident,
+    executions: &[PlaybookExecutionSummary],
+    escalations: &[EscalationHistoryEntry],
+) -> Vec<TimelineEntry> {
+    let mut entries: Vec<TimelineEntry> = incident
+        .timeline
+        .iter()
+        .map(|event| TimelineEntry {
+            timestamp: event.timestamp,
+            entry_type: classify_event(event),
+            description: event.description.clone(),
+            actor: event.actor.clone(),
+            gap_to_next_seconds: None,
+            annotation: None,
+        })
+        .collect();
+
+    for execution in executions {
+        entries.push(TimelineEntry {
+            timestamp: execution.started_at,
+            entry_type: TimelineEntryType::Remediation,
+            description: format!("Playbook {} started", execution.playbook_id),
+            actor: "playbook-executor".to_string(),
+            gap_to_next_seconds: None,
+            annotation: Some(format!("execution {}", execution.execution_id)),
+        });
+
+        if let Some(completed_at) = execution.completed_at {
+            entries.push(TimelineEntry {
+                timestamp: completed_at,
+                entry_type: TimelineEntryType::Remediation,
+                description: format!(
+                    "Playbook {} finished with status {}",
+                    execution.playbook_id, execution.status
+                ),
+                actor: "playbook-executor".to_string(),
+                gap_to_next_seconds: None,
+                annotation: execution.error.clone(),
+            });
+        }
+    }
+
+    for escalation in escalations {
+        entries.push(TimelineEntry {
+            timestamp: escalation.sent_at,
+            entry_type: TimelineEntryType::Escalation,
+            description: format!(
+                "Escalation level {} notified {} via {}",
+                escalation.level, escalation.target, escalation.channel
+            ),
+            actor: "escalation-engine".to_string(),
+            gap_to_next_seconds: None,
+            annotation: (!escalation.success).then(|| "notification failed".to_string()),
+        });
+    }
+
+    entries.sort_by_key(|e| e.timestamp);
+
+    for i in 0..entries.len().saturating_sub(1) {
+        let gap = (entries[i + 1].timestamp - entries[i].timestamp).num_seconds();
+        entries[i].gap_to_next_seconds = Some(gap);
+    }
+
+    entries
+}
+
+/// Compute response metrics from the reconstructed timeline
+fn compute_metrics(
+    timeline: &[TimelineEntry],
+    executions: &[PlaybookExecutionSummary],
+    escalations: &[EscalationHistoryEntry],
+) -> PostMortemMetrics {
+    let start = timeline.first().map(|e| e.timestamp);
+    let first_of = |types: &[TimelineEntryType]| {
+        timeline
+            .iter()
+            .find(|e| types.contains(&e.entry_type))
+            .map(|e| e.timestamp)
+    };
+    let since_start = |t: Option<chrono::DateTime<Utc>>| match (start, t) {
+        (Some(s), Some(t)) => Some((t - s).num_seconds()),
+        _ => None,
+    };
+
+    PostMortemMetrics {
+        time_to_acknowledge_seconds: since_start(first_of(&[
+            TimelineEntryType::Acknowledgment,
+            TimelineEntryType::Investigation,
+        ])),
+        time_to_mitigate_seconds: since_start(first_of(&[
+            TimelineEntryType::Remediation,
+            TimelineEntryType::Mitigation,
+        ])),
+        time_to_resolve_seconds: since_start(first_of(&[TimelineEntryType::Resolution])),
+        escalation_count: escalations.len(),
+        playbook_execution_count: executions.len(),
+    }
+}
+
+/// Infer the root cause from the resolution and incident metadata
+fn analyze_root_cause(incident: &Incident) -> RootCauseAnalysis {
+    let stated = incident
+        .resolution
+        .as_ref()
+        .and_then(|r| r.root_cause.clone());
+
+    let text = format!(
+        "{} {} {}",
+        stated.clone().unwrap_or_default(),
+        incident.title,
+        incident.description
+    )
+    .to_lowercase();
+
+    const KEYWORDS: &[(&[&str], RootCauseCategory)] = &[
+        (&["config", "misconfigur", "setting", "flag"], RootCauseCategory::ConfigurationError),
+        (&["bug", "regression", "deploy", "code", "exception", "panic"], RootCauseCategory::CodeDefect),
+        (&["capacity", "memory", "disk full", "quota", "exhaust", "oom", "rate limit"], RootCauseCategory::CapacityExhaustion),
+        (&["dependency", "upstream", "third-party", "third party", "provider"], RootCauseCategory::DependencyFailure),
+        (&["breach", "intrusion", "unauthorized", "attack", "vulnerab"], RootCauseCategory::SecurityBreach),
+        (&["human", "manual", "operator", "mistake"], RootCauseCategory::HumanError),
+        (&["process", "runbook", "procedure"], RootCauseCategory::ProcessGap),
+        (&["hardware", "network", "node", "host", "infrastructure", "outage"], RootCauseCategory::InfrastructureFailure),
+    ];
+
+    let category = KEYWORDS
+        .iter()
+        .find(|(words, _)| words.iter().any(|w| text.contains(w)))
+        .map(|(_, category)| *category)
+        .unwrap_or(match incident.incident_type {
+            IncidentType::Infrastructure | IncidentType::Availability => {
+                RootCauseCategory::InfrastructureFailure
+            }
+            IncidentType::Performance => RootCauseCategory::CapacityExhaustion,
+            IncidentType::Security => RootCauseCategory::SecurityBreach,
+            IncidentType::Application => RootCauseCategory::CodeDefect,
+            IncidentType::Compliance => RootCauseCategory::ProcessGap,
+            IncidentType::Data | IncidentType::Unknown => RootCauseCategory::Unknown,
+        });
+
+    let mut contributing_factors: Vec<String> = incident
+        .affected_resources
+        .iter()
+        .map(|r| format!("Affected resource: {}", r))
+        .collect();
+    if !incident.related_incidents.is_empty() {
+        contributing_factors.push(format!(
+            "{} related incident(s) correlated",
+            incident.related_incidents.len()
+        ));
+    }
+
+    let detection_gap = match incident.timeline.first() {
+        Some(first) if first.event_type != EventType::AlertReceived && incident.source == "manual" => {
+            Some("Incident was reported manually rather than detected by monitoring".to_string())
+        }
+        _ => None,
+    };
+
+    RootCauseAnalysis {
+        category,
+        description: stated.unwrap_or_else(|| {
+            "Root cause was not recorded at resolution time and requires review".to_string()
+        }),
+        contributing_factors,
+        preventable: !matches!(
+            category,
+            RootCauseCategory::DependencyFailure | RootCauseCategory::Unknown
+        ),
+        detection_gap,
+    }
+}
+
+/// Derive the impact analysis from the incident
+fn analyze_impact(incident: &Incident, metrics: &PostMortemMetrics) -> ImpactAnalysis {
+    let scope = if let Some(region) = incident.labels.get("region") {
+        if region == "global" {
+            ImpactScope::Global
+        } else {
+            ImpactScope::Region
+        }
+    } else if incident.labels.contains_key("tenant") {
+        ImpactScope::Tenant
+    } else if incident.affected_resources.len() > 1 {
+        ImpactScope::MultipleServices
+    } else {
+        ImpactScope::SingleService
+    };
+
+    ImpactAnalysis {
+        scope,
+        services_affected: incident.affected_resources.clone(),
+        users_affected_estimate: incident
+            .labels
+            .get("users_affected")
+            .and_then(|v| v.parse().ok()),
+        user_impact_duration_seconds: metrics.time_to_resolve_seconds,
+        revenue_impact_estimate: None,
+        sla_impact: incident
+            .severity
+            .is_urgent()
+            .then(|| format!("{} incident counts against availability SLO", incident.severity)),
+        data_impact: matches!(incident.incident_type, IncidentType::Data)
+            .then(|| "Data integrity impact should be confirmed during review".to_string()),
+    }
+}
+
+/// Summarise how the incident was resolved
+fn analyze_resolution(
+    incident: &Incident,
+    executions: &[PlaybookExecutionSummary],
+) -> ResolutionAnalysis {
+    let resolution = incident.resolution.as_ref();
+
+    let method = match resolution.map(|r| &r.resolution_method) {
+        Some(ResolutionMethod::Automated) => "automated remediation",
+        Some(ResolutionMethod::AutoAssistedManual) => "manual remediation assisted by automation",
+        Some(ResolutionMethod::Manual) | None => "manual remediation",
+    };
+
+    let mut approach = format!("Resolved via {}", method);
+    if let Some(r) = resolution {
+        approach.push_str(&format!(" by {}", r.resolved_by));
+        if !r.notes.is_empty() {
+            approach.push_str(&format!(": {}", r.notes));
+        }
+    }
+
+    let notes = resolution
+        .map(|r| r.notes.to_lowercase())
+        .unwrap_or_default();
+    let resolution_type = if notes.contains("workaround") {
+        ResolutionType::Workaround
+    } else if notes.contains("temporary") || notes.contains("rollback") {
+        ResolutionType::Temporary
+    } else {
+        ResolutionType::Permanent
+    };
+
+    let mut remaining_risks = Vec::new();
+    if resolution.and_then(|r| r.root_cause.as_ref()).is_none() {
+        remaining_risks.push("Root cause not confirmed".to_string());
+    }
+    for execution in executions.iter().filter(|e| e.steps_failed > 0) {
+        remaining_risks.push(format!(
+            "Playbook {} had {} failed step(s)",
+            execution.playbook_id, execution.steps_failed
+        ));
+    }
+
+    ResolutionAnalysis {
+        approach,
+        effectiveness: match resolution_type {
+            ResolutionType::Permanent => ResolutionEffectiveness::FullyResolved,
+            ResolutionType::Temporary => ResolutionEffectiveness::Mitigated,
+            ResolutionType::Workaround => ResolutionEffectiveness::PartiallyResolved,
+        },
+        follow_up_required: resolution_type != ResolutionType::Permanent
+            || !remaining_risks.is_empty(),
+        resolution_type,
+        remaining_risks,
+    }
+}
+
+/// Derive follow-up action items
+fn derive_action_items(
+    incident: &Incident,
+    root_cause: &RootCauseAnalysis,
+    metrics: &PostMortemMetrics,
+    executions: &[PlaybookExecutionSummary],
+) -> Vec<ActionItem> {
+    let priority = match incident.severity {
+        Severity::P0 => ActionItemPriority::Critical,
+        Severity::P1 => ActionItemPriority::High,
+        Severity::P2 => ActionItemPriority::Medium,
+        Severity::P3 | Severity::P4 => ActionItemPriority::Low,
+    };
+
+    let mut items = Vec::new();
+
+    if root_cause.preventable {
+        items.push(ActionItem::new(
+            format!("Prevent recurrence of {:?}", root_cause.category),
+            format!("Address the root cause: {}", root_cause.description),
+            priority,
+            ActionItemCategory::Prevention,
+        ));
+    }
+
+    if let Some(ref gap) = root_cause.detection_gap {
+        items.push(ActionItem::new(
+            "Improve detection coverage",
+            gap.clone(),
+            priority,
+            ActionItemCategory::Detection,
+        ));
+    }
+
+    if incident
+        .resolution
+        .as_ref()
+        .and_then(|r| r.root_cause.as_ref())
+        .is_none()
+    {
+        items.push(ActionItem::new(
+            "Confirm root cause",
+            "No root cause was recorded when the incident was resolved",
+            ActionItemPriority::High,
+            ActionItemCategory::Process,
+        ));
+    }
+
+    if metrics.escalation_count > 1 {
+        items.push(ActionItem::new(
+            "Review on-call acknowledgment",
+            format!(
+                "Incident escalated {} times before acknowledgment",
+                metrics.escalation_count
+            ),
+            ActionItemPriority::Medium,
+            ActionItemCategory::Response,
+        ));
+    }
+
+    for execution in executions.iter().filter(|e| e.steps_failed > 0) {
+        items.push(ActionItem::new(
+            format!("Fix playbook {}", execution.playbook_id),
+            format!(
+                "{} step(s) failed during execution {}",
+                execution.steps_failed, execution.execution_id
+            ),
+            ActionItemPriority::Medium,
+            ActionItemCategory::Tooling,
+        ));
+    }
+
+    items
+}
+
+/// Derive retrospective lists
+fn derive_lessons(
+    incident: &Incident,
+    metrics: &PostMortemMetrics,
+    executions: &[PlaybookExecutionSummary],
+    escalations: &[EscalationHistoryEntry],
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut went_well = Vec::new();
+    let mut improve = Vec::new();
+    let mut lessons = Vec::new();
+
+    if incident.fingerprint.is_some() || incident.timeline.iter().any(|e| e.event_type == EventType::AlertReceived) {
+        went_well.push("Incident was detected automatically".to_string());
+    }
+
+    let succeeded = executions.iter().filter(|e| e.status == "completed").count();
+    if succeeded > 0 {
+        went_well.push(format!("{} playbook execution(s) completed successfully", succeeded));
+    }
+    if executions.iter().any(|e| e.status == "failed") {
+        improve.push("One or more playbook executions failed".to_string());
+    }
+
+    if escalations.iter().any(|e| !e.success) {
+        improve.push("Some escalation notifications failed to deliver".to_string());
+    }
+
+    match metrics.time_to_acknowledge_seconds {
+        Some(tta) if tta <= 300 => went_well.push(format!("Acknowledged within {}s", tta)),
+        Some(tta) => improve.push(format!("Acknowledgment took {}s", tta)),
+        None => improve.push("No acknowledgment was recorded on the timeline".to_string()),
+    }
+
+    if incident.notes.is_empty() {
+        improve.push("No investigation notes were recorded".to_string());
+    }
+
+    if let Some(root_cause) = incident.resolution.as_ref().and_then(|r| r.root_cause.as_ref()) {
+        lessons.push(format!("Root cause: {}", root_cause));
+    }
+    for note in &incident.notes {
+        lessons.push(format!("{}: {}", note.author, note.content));
+    }
+
+    (went_well, improve, lessons)
+}
+
+fn build_summary(incident: &Incident, root_cause: &RootCauseAnalysis, metrics: &PostMortemMetrics) -> String {
+    let duration = metrics
+        .time_to_resolve_seconds
+        .map(|s| format!(" and was resolved after {}m", s / 60))
+        .unwrap_or_default();
+
+    format!(
+        "{} {} incident \"{}\" from {} was detected at {}{}. Root cause category: {:?}.",
+        incident.severity,
+        incident.incident_type,
+        incident.title,
+        incident.source,
+        incident.created_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        duration,
+        root_cause.category,
+    )
+}
+
+/// Confidence reflects how complete the source records were
+fn confidence(incident: &Incident, postmortem: &PostMortem) -> f64 {
+    let mut score: f64 = 0.5;
+    if incident.resolution.as_ref().and_then(|r| r.root_cause.as_ref()).is_some() {
+        score += 0.2;
+    }
+    if !incident.notes.is_empty() {
+        score += 0.1;
+    }
+    if postmortem.timeline.len() > 2 {
+        score += 0.1;
+    }
+    if !postmortem.playbook_executions.is_empty() || !postmortem.escalation_history.is_empty() {
+        score += 0.1;
+    }
+    score.min(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::InMemoryStore;
+
+    fn resolved_incident() -> Incident {
+        let mut incident = Incident::new(
+            "sentinel".to_string(),
+            "Database connection pool exhausted".to_string(),
+            "Connection pool exhausted on primary".to_string(),
+            Severity::P1,
+            IncidentType::Infrastructure,
+        );
+        incident.affected_resources = vec!["db-primary".to_string()];
+        incident.update_state(IncidentState::Investigating, "oncall@example.com".to_string());
+        incident.add_note("oncall@example.com".to_string(), "Pool size too small".to_string());
+        incident.resolve(
+            "oncall@example.com".to_string(),
+            ResolutionMethod::Manual,
+            "Increased pool size".to_string(),
+            Some("Connection pool capacity exhausted".to_string()),
+        );
+        incident
+    }
+
+    #[tokio::test]
+    async fn test_generate_requires_resolved_incident() {
+        let store = Arc::new(InMemoryStore::new());
+        let incident = Incident::new(
+            "test".to_string(),
+            "Active".to_string(),
+            "Still active".to_string(),
+            Severity::P2,
+            IncidentType::Application,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        let generator = PostMortemGenerator::new(store);
+        let result = generator.generate(incident.id).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_generate_draft() {
+        let store = Arc::new(InMemoryStore::new());
+        let incident = resolved_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let generator = PostMortemGenerator::new(store);
+        let postmortem = generator.generate(incident.id).await.unwrap();
+
+        assert_eq!(postmortem.status, PostMortemStatus::Draft);
+        assert_eq!(postmortem.incident_id, incident.id);
+        assert_eq!(postmortem.root_cause.category, RootCauseCategory::CapacityExhaustion);
+        assert_eq!(postmortem.notes.len(), 1);
+        assert_eq!(postmortem.timeline.first().unwrap().entry_type, TimelineEntryType::Detection);
+        assert_eq!(postmortem.timeline.last().unwrap().entry_type, TimelineEntryType::Resolution);
+        assert!(postmortem.metrics.time_to_resolve_seconds.is_some());
+
+        let event = postmortem.decision_event.as_ref().unwrap();
+        assert_eq!(event.decision_type, DECISION_TYPE);
+        assert_eq!(event.execution_ref, postmortem.id.to_string());
+
+        // Regenerating reuses the draft
+        let regenerated = generator.generate(incident.id).await.unwrap();
+        assert_eq!(regenerated.id, postmortem.id);
+        assert_eq!(generator.list(None, None, 10).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_publish_is_final() {
+        let store = Arc::new(InMemoryStore::new());
+        let incident = resolved_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        let generator = PostMortemGenerator::new(store);
+        let postmortem = generator.generate(incident.id).await.unwrap();
+
+        let published = generator
+            .publish(&postmortem.id, "lead@example.com".to_string())
+            .await
+            .unwrap();
+        assert_eq!(published.status, PostMortemStatus::Published);
+        assert_eq!(published.reviewed_by.as_deref(), Some("lead@example.com"));
+
+        assert!(generator
+            .publish(&postmortem.id, "lead@example.com".to_string())
+            .await
+            .is_err());
+        assert!(matches!(
+            generator.generate(incident.id).await,
+            Err(AppError::InvalidStateTransition(_))
+        ));
+
+        let drafts = generator
+            .list(Some(&incident.id), Some(PostMortemStatus::Draft), 10)
+            .await
+            .unwrap();
+        assert!(drafts.is_empty());
+    }
+}
@@ -50,6 +50,7 @@
 //!     PostMortemGenerator, PostMortem, PostMortemStatus,
 //!     RuvectorClient, RuvectorConfig,
 //! };
+//! use llm_incident_manager::state::create_in_memory_store;
 //! use uuid::Uuid;
 //!
 //! #[tokio::main]
@@ -59,7 +60,8 @@
 //!     let client = RuvectorClient::new(config)?;
 //!
 //!     // Create generator with persistence
-//!     let generator = PostMortemGenerator::new(client);
+//!     let store = create_in_memory_store();
+//!     let generator = PostMortemGenerator::new(store).with_ruvector_client(client);
 //!
 //!     // Generate post-mortem for a resolved incident
 //!     let incident_id = Uuid::new_v4();
@@ -80,16 +82,20 @@ pub mod models;
 // Re-export core types from models
 pub use models::{
     ActionItem,
+    ActionItemCategory,
     ActionItemPriority,
     ActionItemStatus,
     DecisionEvent,
     ImpactAnalysis,
+    ImpactScope,
     PostMortem,
     PostMortemOutput,
     PostMortemStatus,
     ResolutionAnalysis,
     RootCauseAnalysis,
+    RootCauseCategory,
     TimelineEntry,
+    TimelineEntryType,
 };
 
 // Re-export generator
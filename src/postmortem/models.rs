@@ -0,0 +1,508 @@
+//! Post-mortem data models
+//!
+//! These types mirror the `incident-postmortem` agent contract
+//! (`packages/agentics-contracts/src/postmortem-agent.ts`) so that records
+//! produced here can be persisted to ruvector-service unchanged.
+
+use crate::models::{Note, Severity};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write as _;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Agent identifier used in decision events
+pub const AGENT_ID: &str = "incident-postmortem";
+
+/// Agent version used in decision events
+pub const AGENT_VERSION: &str = "1.0.0";
+
+/// Decision type emitted for every generated post-mortem
+pub const DECISION_TYPE: &str = "incident_postmortem_generated";
+
+/// Lifecycle status of a post-mortem
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PostMortemStatus {
+    #[default]
+    Draft,
+    InReview,
+    Approved,
+    Published,
+}
+
+impl PostMortemStatus {
+    /// Whether the post-mortem can still be regenerated or edited
+    pub fn is_mutable(&self) -> bool {
+        !matches!(self, PostMortemStatus::Published)
+    }
+}
+
+impl FromStr for PostMortemStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "draft" => Ok(PostMortemStatus::Draft),
+            "in_review" | "review" => Ok(PostMortemStatus::InReview),
+            "approved" => Ok(PostMortemStatus::Approved),
+            "published" => Ok(PostMortemStatus::Published),
+            other => Err(format!("Invalid post-mortem status: {}", other)),
+        }
+    }
+}
+
+/// Priority of a follow-up action item
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemPriority {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+/// Status of a follow-up action item
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemStatus {
+    Open,
+    InProgress,
+    Completed,
+    WontFix,
+}
+
+/// Category of a follow-up action item
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemCategory {
+    Prevention,
+    Detection,
+    Response,
+    Process,
+    Tooling,
+}
+
+/// Follow-up action item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub priority: ActionItemPriority,
+    pub status: ActionItemStatus,
+    pub assigned_to: Option<String>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub category: ActionItemCategory,
+}
+
+impl ActionItem {
+    /// Create a new open action item
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        priority: ActionItemPriority,
+        category: ActionItemCategory,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            title: title.into(),
+            description: description.into(),
+            priority,
+            status: ActionItemStatus::Open,
+            assigned_to: None,
+            due_date: None,
+            category,
+        }
+    }
+}
+
+/// Type of a reconstructed timeline entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEntryType {
+    Detection,
+    Notification,
+    Acknowledgment,
+    Escalation,
+    Investigation,
+    Remediation,
+    Mitigation,
+    Resolution,
+    Communication,
+    Decision,
+}
+
+/// Reconstructed timeline entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "type")]
+    pub entry_type: TimelineEntryType,
+    pub description: String,
+    pub actor: String,
+    pub gap_to_next_seconds: Option<i64>,
+    pub annotation: Option<String>,
+}
+
+/// Root cause classification
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RootCauseCategory {
+    CodeDefect,
+    ConfigurationError,
+    InfrastructureFailure,
+    DependencyFailure,
+    CapacityExhaustion,
+    SecurityBreach,
+    HumanError,
+    ProcessGap,
+    Unknown,
+}
+
+/// Root cause analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootCauseAnalysis {
+    pub category: RootCauseCategory,
+    pub description: String,
+    pub contributing_factors: Vec<String>,
+    pub preventable: bool,
+    pub detection_gap: Option<String>,
+}
+
+/// Scope of the impact
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImpactScope {
+    SingleService,
+    MultipleServices,
+    Tenant,
+    Region,
+    Global,
+}
+
+/// Impact analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImpactAnalysis {
+    pub scope: ImpactScope,
+    pub services_affected: Vec<String>,
+    pub users_affected_estimate: Option<u64>,
+    pub user_impact_duration_seconds: Option<i64>,
+    pub revenue_impact_estimate: Option<f64>,
+    pub sla_impact: Option<String>,
+    pub data_impact: Option<String>,
+}
+
+/// Whether the fix is permanent
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionType {
+    Permanent,
+    Temporary,
+    Workaround,
+}
+
+/// How effective the resolution was
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionEffectiveness {
+    FullyResolved,
+    PartiallyResolved,
+    Mitigated,
+}
+
+/// Resolution analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolutionAnalysis {
+    pub approach: String,
+    pub resolution_type: ResolutionType,
+    pub effectiveness: ResolutionEffectiveness,
+    pub remaining_risks: Vec<String>,
+    pub follow_up_required: bool,
+}
+
+/// Summary of a playbook execution that ran against the incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaybookExecutionSummary {
+    pub execution_id: Uuid,
+    pub playbook_id: Uuid,
+    pub status: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub steps_completed: usize,
+    pub steps_failed: usize,
+    pub error: Option<String>,
+}
+
+/// Escalation notification recorded for the incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationHistoryEntry {
+    pub sent_at: DateTime<Utc>,
+    pub level: u32,
+    pub target: String,
+    pub channel: String,
+    pub success: bool,
+}
+
+/// Response time metrics derived from the timeline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PostMortemMetrics {
+    /// Seconds from detection to first acknowledgment/triage
+    pub time_to_acknowledge_seconds: Option<i64>,
+    /// Seconds from detection to first remediation activity
+    pub time_to_mitigate_seconds: Option<i64>,
+    /// Seconds from detection to resolution
+    pub time_to_resolve_seconds: Option<i64>,
+    /// Number of escalation notifications sent
+    pub escalation_count: usize,
+    /// Number of playbook executions
+    pub playbook_execution_count: usize,
+}
+
+/// Agent output as defined by the post-mortem contract
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostMortemOutput {
+    pub summary: String,
+    pub timeline: Vec<TimelineEntry>,
+    pub root_cause: RootCauseAnalysis,
+    pub impact: ImpactAnalysis,
+    pub resolution: ResolutionAnalysis,
+    pub action_items: Vec<ActionItem>,
+    pub what_went_well: Vec<String>,
+    pub what_could_be_improved: Vec<String>,
+    pub lessons_learned: Vec<String>,
+}
+
+/// Decision event persisted to ruvector-service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionEvent {
+    pub id: Uuid,
+    pub agent_id: String,
+    pub agent_version: String,
+    pub agent_classification: String,
+    pub decision_type: String,
+    /// SHA-256 of the canonical JSON inputs
+    pub inputs_hash: String,
+    pub outputs: serde_json::Value,
+    pub confidence: f64,
+    pub constraints_applied: Vec<String>,
+    pub execution_ref: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DecisionEvent {
+    /// Build a post-mortem decision event
+    pub fn new(
+        inputs: &serde_json::Value,
+        outputs: serde_json::Value,
+        confidence: f64,
+        constraints_applied: Vec<String>,
+        execution_ref: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            agent_id: AGENT_ID.to_string(),
+            agent_version: AGENT_VERSION.to_string(),
+            agent_classification: "documentation".to_string(),
+            decision_type: DECISION_TYPE.to_string(),
+            inputs_hash: hash_inputs(inputs),
+            outputs,
+            confidence,
+            constraints_applied,
+            execution_ref,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Hash decision inputs with SHA-256
+pub fn hash_inputs(inputs: &serde_json::Value) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(inputs.to_string().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Post-mortem record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostMortem {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub title: String,
+    pub severity: Severity,
+    pub status: PostMortemStatus,
+    pub summary: String,
+    pub timeline: Vec<TimelineEntry>,
+    pub root_cause: RootCauseAnalysis,
+    pub impact: ImpactAnalysis,
+    pub resolution: ResolutionAnalysis,
+    pub action_items: Vec<ActionItem>,
+    pub what_went_well: Vec<String>,
+    pub what_could_be_improved: Vec<String>,
+    pub lessons_learned: Vec<String>,
+    pub playbook_executions: Vec<PlaybookExecutionSummary>,
+    pub escalation_history: Vec<EscalationHistoryEntry>,
+    pub notes: Vec<Note>,
+    pub metrics: PostMortemMetrics,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub reviewed_by: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub decision_event: Option<DecisionEvent>,
+}
+
+impl PostMortem {
+    /// Extract the contract output section
+    pub fn output(&self) -> PostMortemOutput {
+        PostMortemOutput {
+            summary: self.summary.clone(),
+            timeline: self.timeline.clone(),
+            root_cause: self.root_cause.clone(),
+            impact: self.impact.clone(),
+            resolution: self.resolution.clone(),
+            action_items: self.action_items.clone(),
+            what_went_well: self.what_went_well.clone(),
+            what_could_be_improved: self.what_could_be_improved.clone(),
+            lessons_learned: self.lessons_learned.clone(),
+        }
+    }
+
+    /// Render the post-mortem as a Markdown document
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+
+        let _ = writeln!(md, "# Post-Mortem: {}", self.title);
+        let _ = writeln!(md);
+        let _ = writeln!(md, "- **Incident**: {}", self.incident_id);
+        let _ = writeln!(md, "- **Severity**: {}", self.severity);
+        let _ = writeln!(md, "- **Status**: {:?}", self.status);
+        if let Some(ref reviewer) = self.reviewed_by {
+            let _ = writeln!(md, "- **Reviewed by**: {}", reviewer);
+        }
+        let _ = writeln!(md);
+
+        let _ = writeln!(md, "## Summary");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "{}", self.summary);
+        let _ = writeln!(md);
+
+        let _ = writeln!(md, "## Timeline");
+        let _ = writeln!(md);
+        for entry in &self.timeline {
+            let _ = writeln!(
+                md,
+                "- `{}` **{:?}** ({}) {}",
+                entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                entry.entry_type,
+                entry.actor,
+                entry.description
+            );
+        }
+        let _ = writeln!(md);
+
+        let _ = writeln!(md, "## Root Cause");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "**Category**: {:?}", self.root_cause.category);
+        let _ = writeln!(md);
+        let _ = writeln!(md, "{}", self.root_cause.description);
+        for factor in &self.root_cause.contributing_factors {
+            let _ = writeln!(md, "- {}", factor);
+        }
+        let _ = writeln!(md);
+
+        let _ = writeln!(md, "## Impact");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "- **Scope**: {:?}", self.impact.scope);
+        if !self.impact.services_affected.is_empty() {
+            let _ = writeln!(
+                md,
+                "- **Services affected**: {}",
+                self.impact.services_affected.join(", ")
+            );
+        }
+        if let Some(duration) = self.impact.user_impact_duration_seconds {
+            let _ = writeln!(md, "- **Impact duration**: {}s", duration);
+        }
+        let _ = writeln!(md);
+
+        let _ = writeln!(md, "## Resolution");
+        let _ = writeln!(md);
+        let _ = writeln!(md, "{}", self.resolution.approach);
+        let _ = writeln!(md);
+
+        if !self.action_items.is_empty() {
+            let _ = writeln!(md, "## Action Items");
+            let _ = writeln!(md);
+            for item in &self.action_items {
+                let _ = writeln!(
+                    md,
+                    "- [{:?}] **{}**: {}",
+                    item.priority, item.title, item.description
+                );
+            }
+            let _ = writeln!(md);
+        }
+
+        for (heading, items) in [
+            ("What Went Well", &self.what_went_well),
+            ("What Could Be Improved", &self.what_could_be_improved),
+            ("Lessons Learned", &self.lessons_learned),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            let _ = writeln!(md, "## {}", heading);
+            let _ = writeln!(md);
+            for item in items {
+                let _ = writeln!(md, "- {}", item);
+            }
+            let _ = writeln!(md);
+        }
+
+        md
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_from_str() {
+        assert_eq!(
+            PostMortemStatus::from_str("draft").unwrap(),
+            PostMortemStatus::Draft
+        );
+        assert_eq!(
+            PostMortemStatus::from_str("review").unwrap(),
+            PostMortemStatus::InReview
+        );
+        assert_eq!(
+            PostMortemStatus::from_str("Published").unwrap(),
+            PostMortemStatus::Published
+        );
+        assert!(PostMortemStatus::from_str("bogus").is_err());
+    }
+
+    #[test]
+    fn test_timeline_entry_serializes_type() {
+        let entry = TimelineEntry {
+            timestamp: Utc::now(),
+            entry_type: TimelineEntryType::Detection,
+            description: "Detected".to_string(),
+            actor: "system".to_string(),
+            gap_to_next_seconds: None,
+            annotation: None,
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["type"], "detection");
+    }
+
+    #[test]
+    fn test_hash_inputs_is_stable() {
+        let inputs = serde_json::json!({"incident_id": "abc"});
+        assert_eq!(hash_inputs(&inputs), hash_inputs(&inputs));
+        assert_eq!(hash_inputs(&inputs).len(), 64);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::error::Result;
 use crate::models::{Alert, Incident};
-use crate::state::IncidentStore;
+use crate::state::{modify_incident, IncidentStore};
 use chrono::{Duration, Utc};
 use std::sync::Arc;
 
@@ -69,23 +69,25 @@ impl DeduplicationEngine {
         alert: &Alert,
         incident: &mut Incident,
     ) -> Result<()> {
-        // Add timeline event
-        incident.add_timeline_event(crate::models::TimelineEvent {
-            timestamp: Utc::now(),
-            event_type: crate::models::EventType::Created,
-            actor: "deduplication-engine".to_string(),
-            description: format!(
-                "Merged duplicate alert from {} (alert_id: {})",
-                alert.source, alert.external_id
-            ),
-            metadata: std::collections::HashMap::from([
-                ("alert_id".to_string(), alert.id.to_string()),
-                ("external_id".to_string(), alert.external_id.clone()),
-            ]),
-        });
-
-        // Update incident in store
-        self.store.update_incident(incident).await?;
+        // Add timeline event to the latest stored copy, retrying if the
+        // incident is modified concurrently
+        *incident = modify_incident(self.store.as_ref(), &incident.id, |incident| {
+            incident.add_timeline_event(crate::models::TimelineEvent {
+                timestamp: Utc::now(),
+                event_type: crate::models::EventType::Created,
+                actor: "deduplication-engine".to_string(),
+                description: format!(
+                    "Merged duplicate alert from {} (alert_id: {})",
+                    alert.source, alert.external_id
+                ),
+                metadata: std::collections::HashMap::from([
+                    ("alert_id".to_string(), alert.id.to_string()),
+                    ("external_id".to_string(), alert.external_id.clone()),
+                ]),
+            });
+            Ok(())
+        })
+        .await?;
 
         tracing::info!(
             incident_id = %incident.id,
@@ -1,14 +1,19 @@
 use crate::correlation::CorrelationEngine;
 use crate::enrichment::EnrichmentService;
 use crate::error::{AppError, Result};
-use crate::escalation::{EscalationEngine, RoutingRuleEvaluator};
+use crate::escalation::{EscalationEngine, EscalationStatus, RoutingRuleEvaluator};
 use crate::execution::{Artifact, ExecutionContext};
 use crate::ml::MLService;
-use crate::models::{Alert, AlertAck, Incident, IncidentState};
-use crate::notifications::NotificationService;
+use crate::models::{
+    Alert, AlertAck, EventType, Incident, IncidentState, ResolutionMethod, TimelineEvent,
+};
+use crate::notifications::actions::ACTION_KEY_METADATA;
+use crate::notifications::{
+    ActionOutcome, IncidentAction, NotificationAction, NotificationService,
+};
 use crate::playbooks::PlaybookService;
 use crate::processing::DeduplicationEngine;
-use crate::state::IncidentStore;
+use crate::state::{modify_incident, IncidentStore};
 use crate::websocket::EventHandlers;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -59,16 +64,31 @@ impl IncidentProcessor {
         self.notification_service = Some(notification_service);
     }
 
+    /// Get the notification service, if one is set
+    pub fn notification_service(&self) -> Option<&Arc<NotificationService>> {
+        self.notification_service.as_ref()
+    }
+
     /// Set playbook service after construction
     pub fn set_playbook_service(&mut self, playbook_service: Arc<PlaybookService>) {
         self.playbook_service = Some(playbook_service);
     }
 
+    /// Get the playbook service, if one is set
+    pub fn playbook_service(&self) -> Option<&Arc<PlaybookService>> {
+        self.playbook_service.as_ref()
+    }
+
     /// Set escalation engine after construction
     pub fn set_escalation_engine(&mut self, escalation_engine: Arc<EscalationEngine>) {
         self.escalation_engine = Some(escalation_engine);
     }
 
+    /// Get the escalation engine, if one is set
+    pub fn escalation_engine(&self) -> Option<&Arc<EscalationEngine>> {
+        self.escalation_engine.as_ref()
+    }
+
     /// Set routing rule evaluator after construction
     pub fn set_routing_evaluator(&mut self, routing_evaluator: Arc<RoutingRuleEvaluator>) {
         self.routing_evaluator = Some(routing_evaluator);
@@ -423,7 +443,10 @@ impl IncidentProcessor {
             if let Some(ctx) = exec_ctx {
                 let guard = ctx.start_agent_span("EscalationEngine");
                 if let Some(policy) = escalation_engine.find_policy_for_incident(incident) {
-                    match escalation_engine.start_escalation(incident, policy.id) {
+                    match escalation_engine
+                        .start_escalation(incident, policy.id)
+                        .await
+                    {
                         Ok(_) => {
                             tracing::info!(
                                 incident_id = %incident.id,
@@ -462,7 +485,10 @@ impl IncidentProcessor {
                 }
             } else {
                 if let Some(policy) = escalation_engine.find_policy_for_incident(incident) {
-                    match escalation_engine.start_escalation(incident, policy.id) {
+                    match escalation_engine
+                        .start_escalation(incident, policy.id)
+                        .await
+                    {
                         Ok(_) => {
                             tracing::info!(
                                 incident_id = %incident.id,
@@ -593,10 +619,11 @@ impl IncidentProcessor {
         new_state: IncidentState,
         actor: String,
     ) -> Result<Incident> {
-        let mut incident = self.get_incident(id).await?;
-
-        incident.update_state(new_state, actor);
-        self.store.update_incident(&incident).await?;
+        let incident = modify_incident(self.store.as_ref(), id, |incident| {
+            incident.update_state(new_state.clone(), actor.clone());
+            Ok(())
+        })
+        .await?;
 
         tracing::info!(
             incident_id = %id,
@@ -617,21 +644,36 @@ impl IncidentProcessor {
         root_cause: Option<String>,
         exec_ctx: Option<&ExecutionContext>,
     ) -> Result<Incident> {
-        let mut incident = self.get_incident(id).await?;
-
-        incident.resolve(resolved_by, method, notes, root_cause);
-        self.store.update_incident(&incident).await?;
+        let incident = modify_incident(self.store.as_ref(), id, |incident| {
+            incident.resolve(
+                resolved_by.clone(),
+                method.clone(),
+                notes.clone(),
+                root_cause.clone(),
+            );
+            Ok(())
+        })
+        .await?;
 
         tracing::info!(
             incident_id = %id,
             "Incident resolved"
         );
 
+        self.after_resolution(&incident, exec_ctx).await;
+
+        Ok(incident)
+    }
+
+    /// Send resolution notifications and stop escalating a resolved incident
+    async fn after_resolution(&self, incident: &Incident, exec_ctx: Option<&ExecutionContext>) {
+        let id = &incident.id;
+
         // Send resolution notifications
         if let Some(ref notif_service) = self.notification_service {
             if let Some(ctx) = exec_ctx {
                 let guard = ctx.start_agent_span("NotificationService");
-                match notif_service.notify_incident_resolved(&incident).await {
+                match notif_service.notify_incident_resolved(incident).await {
                     Ok(ids) => {
                         guard.complete_ok(vec![Artifact {
                             name: "resolution_notification".to_string(),
@@ -650,7 +692,7 @@ impl IncidentProcessor {
                         guard.complete_err(format!("{}", e));
                     }
                 }
-            } else if let Err(e) = notif_service.notify_incident_resolved(&incident).await {
+            } else if let Err(e) = notif_service.notify_incident_resolved(incident).await {
                 tracing::error!(
                     incident_id = %incident.id,
                     error = %e,
@@ -663,7 +705,7 @@ impl IncidentProcessor {
         if let Some(ref escalation_engine) = self.escalation_engine {
             if let Some(ctx) = exec_ctx {
                 let guard = ctx.start_agent_span("EscalationEngine");
-                match escalation_engine.resolve_escalation(id) {
+                match escalation_engine.resolve_escalation(id).await {
                     Ok(()) => {
                         tracing::info!(incident_id = %id, "Escalation resolved");
                         guard.complete_ok(vec![Artifact {
@@ -684,7 +726,7 @@ impl IncidentProcessor {
                     }
                 }
             } else {
-                if let Err(e) = escalation_engine.resolve_escalation(id) {
+                if let Err(e) = escalation_engine.resolve_escalation(id).await {
                     tracing::error!(
                         incident_id = %id,
                         error = %e,
@@ -698,24 +740,22 @@ impl IncidentProcessor {
                 }
             }
         }
-
-        Ok(incident)
     }
 
     /// Assign incident to users
     pub async fn assign_incident(&self, id: &Uuid, assignees: Vec<String>) -> Result<Incident> {
-        let mut incident = self.get_incident(id).await?;
-
-        incident.assignees = assignees.clone();
-        incident.add_timeline_event(crate::models::TimelineEvent {
-            timestamp: chrono::Utc::now(),
-            event_type: crate::models::EventType::AssignmentChanged,
-            actor: "system".to_string(),
-            description: format!("Assigned to: {}", assignees.join(", ")),
-            metadata: std::collections::HashMap::new(),
-        });
-
-        self.store.update_incident(&incident).await?;
+        let incident = modify_incident(self.store.as_ref(), id, |incident| {
+            incident.assignees = assignees.clone();
+            incident.add_timeline_event(crate::models::TimelineEvent {
+                timestamp: chrono::Utc::now(),
+                event_type: crate::models::EventType::AssignmentChanged,
+                actor: "system".to_string(),
+                description: format!("Assigned to: {}", assignees.join(", ")),
+                metadata: std::collections::HashMap::new(),
+            });
+            Ok(())
+        })
+        .await?;
 
         tracing::info!(
             incident_id = %id,
@@ -725,6 +765,148 @@ impl IncidentProcessor {
 
         Ok(incident)
     }
+
+    /// Apply an action taken from a notification: an email link, a Slack
+    /// button or a PagerDuty webhook
+    ///
+    /// Actions are idempotent. A redelivered action (same key) or one that
+    /// has already taken effect changes nothing and is reported as not
+    /// applied.
+    pub async fn apply_notification_action(
+        &self,
+        action: &NotificationAction,
+    ) -> Result<ActionOutcome> {
+        let already_seen = |incident: &Incident| {
+            incident
+                .timeline
+                .iter()
+                .any(|event| event.metadata.get(ACTION_KEY_METADATA) == Some(&action.key))
+        };
+
+        let current = self.get_incident(&action.incident_id).await?;
+        if already_seen(&current) {
+            return Ok(ActionOutcome::new(
+                action,
+                false,
+                "Action was already applied",
+            ));
+        }
+
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("source".to_string(), action.source.as_str().to_string());
+        metadata.insert(ACTION_KEY_METADATA.to_string(), action.key.clone());
+
+        let mut skipped: Option<String> = None;
+        let incident = modify_incident(self.store.as_ref(), &action.incident_id, |incident| {
+            skipped = None;
+            if already_seen(incident) {
+                skipped = Some("Action was already applied".to_string());
+                return Ok(());
+            }
+
+            match &action.action {
+                IncidentAction::Acknowledge => {
+                    if !incident.is_active() {
+                        skipped = Some(format!("Incident is already {:?}", incident.state));
+                    } else if incident
+                        .timeline
+                        .iter()
+                        .any(|event| event.event_type == EventType::Acknowledged)
+                    {
+                        skipped = Some("Incident is already acknowledged".to_string());
+                    } else {
+                        incident.add_timeline_event(TimelineEvent {
+                            timestamp: chrono::Utc::now(),
+                            event_type: EventType::Acknowledged,
+                            actor: action.actor.clone(),
+                            description: format!("Acknowledged via {}", action.source),
+                            metadata: metadata.clone(),
+                        });
+                    }
+                }
+                IncidentAction::Resolve => {
+                    if !incident.is_active() {
+                        skipped = Some(format!("Incident is already {:?}", incident.state));
+                    } else {
+                        incident.resolve(
+                            action.actor.clone(),
+                            ResolutionMethod::Manual,
+                            format!("Resolved via {}", action.source),
+                            None,
+                        );
+                        if let Some(event) = incident.timeline.last_mut() {
+                            event.metadata.extend(metadata.clone());
+                        }
+                    }
+                }
+                IncidentAction::Reassign { assignee } => {
+                    if incident.assignees == std::slice::from_ref(assignee) {
+                        skipped = Some(format!("Incident is already assigned to {}", assignee));
+                    } else {
+                        incident.assignees = vec![assignee.clone()];
+                        incident.add_timeline_event(TimelineEvent {
+                            timestamp: chrono::Utc::now(),
+                            event_type: EventType::AssignmentChanged,
+                            actor: action.actor.clone(),
+                            description: format!(
+                                "Reassigned to {} via {}",
+                                assignee, action.source
+                            ),
+                            metadata: metadata.clone(),
+                        });
+                    }
+                }
+            }
+            Ok(())
+        })
+        .await?;
+
+        if let Some(reason) = skipped {
+            return Ok(ActionOutcome::new(action, false, reason));
+        }
+
+        tracing::info!(
+            incident_id = %incident.id,
+            action = ?action.action,
+            actor = %action.actor,
+            source = %action.source,
+            "Notification action applied"
+        );
+
+        let message = match &action.action {
+            IncidentAction::Acknowledge => {
+                // Stops the escalation and, for first-to-acknowledge targets,
+                // assigns the incident to the acknowledger
+                if let Some(ref escalation_engine) = self.escalation_engine {
+                    let escalating = escalation_engine
+                        .get_escalation_state(&incident.id)
+                        .is_some_and(|state| state.status == EscalationStatus::Active);
+                    if escalating {
+                        if let Err(e) = escalation_engine
+                            .acknowledge_escalation(&incident.id, action.actor.clone())
+                            .await
+                        {
+                            tracing::error!(
+                                incident_id = %incident.id,
+                                error = %e,
+                                "Failed to acknowledge escalation"
+                            );
+                        }
+                    }
+                }
+                format!("Incident {} acknowledged by {}", incident.id, action.actor)
+            }
+            IncidentAction::Resolve => {
+                self.after_resolution(&incident, None).await;
+                format!("Incident {} resolved by {}", incident.id, action.actor)
+            }
+            IncidentAction::Reassign { assignee } => {
+                format!("Incident {} reassigned to {}", incident.id, assignee)
+            }
+        };
+
+        Ok(ActionOutcome::new(action, true, message))
+    }
 }
 
 #[cfg(test)]
@@ -853,4 +1035,119 @@ mod tests {
             .iter()
             .any(|s| s.name == "DeduplicationEngine"));
     }
+
+    fn notification_action(
+        incident_id: Uuid,
+        action: IncidentAction,
+        key: &str,
+    ) -> NotificationAction {
+        NotificationAction {
+            incident_id,
+            action,
+            actor: "oncall@example.com".to_string(),
+            source: crate::notifications::ActionSource::Slack,
+            key: key.to_string(),
+        }
+    }
+
+    async fn processor_with_incident() -> (IncidentProcessor, Uuid) {
+        let store = Arc::new(InMemoryStore::new());
+        let dedup = Arc::new(DeduplicationEngine::new(store.clone(), 900));
+        let processor = IncidentProcessor::new(store, dedup);
+
+        let incident = Incident::new(
+            "test".to_string(),
+            "Test".to_string(),
+            "Desc".to_string(),
+            Severity::P1,
+            IncidentType::Infrastructure,
+        );
+        let id = incident.id;
+        processor.create_incident(incident, None).await.unwrap();
+        (processor, id)
+    }
+
+    #[tokio::test]
+    async fn test_notification_action_acknowledge_is_idempotent() {
+        let (processor, id) = processor_with_incident().await;
+
+        let ack = notification_action(id, IncidentAction::Acknowledge, "slack:U1:1");
+        let outcome = processor.apply_notification_action(&ack).await.unwrap();
+        assert!(outcome.applied);
+
+        // Slack retries the same delivery
+        let outcome = processor.apply_notification_action(&ack).await.unwrap();
+        assert!(!outcome.applied);
+
+        // A second click is a new delivery but the incident is already acknowledged
+        let again = notification_action(id, IncidentAction::Acknowledge, "slack:U1:2");
+        let outcome = processor.apply_notification_action(&again).await.unwrap();
+        assert!(!outcome.applied);
+
+        let incident = processor.get_incident(&id).await.unwrap();
+        let acks: Vec<_> = incident
+            .timeline
+            .iter()
+            .filter(|event| event.event_type == EventType::Acknowledged)
+            .collect();
+        assert_eq!(acks.len(), 1);
+        assert_eq!(acks[0].actor, "oncall@example.com");
+        assert_eq!(acks[0].metadata.get("source"), Some(&"slack".to_string()));
+        assert_eq!(
+            acks[0].metadata.get(ACTION_KEY_METADATA),
+            Some(&"slack:U1:1".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notification_action_resolve() {
+        let (processor, id) = processor_with_incident().await;
+
+        let resolve = notification_action(id, IncidentAction::Resolve, "pagerduty:evt-1");
+        let outcome = processor.apply_notification_action(&resolve).await.unwrap();
+        assert!(outcome.applied);
+
+        let incident = processor.get_incident(&id).await.unwrap();
+        assert_eq!(incident.state, IncidentState::Resolved);
+        let resolution = incident.resolution.as_ref().unwrap();
+        assert_eq!(resolution.resolved_by, "oncall@example.com");
+        assert_eq!(resolution.resolution_method, ResolutionMethod::Manual);
+        assert!(incident
+            .timeline
+            .iter()
+            .any(|event| event.metadata.get(ACTION_KEY_METADATA)
+                == Some(&"pagerduty:evt-1".to_string())));
+
+        // Resolving again, e.g. from a stale email link, is a no-op
+        let stale = notification_action(id, IncidentAction::Resolve, "email:abc");
+        let outcome = processor.apply_notification_action(&stale).await.unwrap();
+        assert!(!outcome.applied);
+    }
+
+    #[tokio::test]
+    async fn test_notification_action_reassign() {
+        let (processor, id) = processor_with_incident().await;
+
+        let reassign = notification_action(
+            id,
+            IncidentAction::Reassign {
+                assignee: "bob@example.com".to_string(),
+            },
+            "pagerduty:evt-2",
+        );
+        let outcome = processor
+            .apply_notification_action(&reassign)
+            .await
+            .unwrap();
+        assert!(outcome.applied);
+
+        let incident = processor.get_incident(&id).await.unwrap();
+        assert_eq!(incident.assignees, vec!["bob@example.com".to_string()]);
+        let event = incident.timeline.last().unwrap();
+        assert_eq!(event.event_type, EventType::AssignmentChanged);
+        assert_eq!(event.description, "Reassigned to bob@example.com via Slack");
+
+        let missing = notification_action(Uuid::new_v4(), IncidentAction::Resolve, "slack:U1:3");
+        assert!(processor.apply_notification_action(&missing).await.is_err());
+    }
 }
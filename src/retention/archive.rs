@@ -0,0 +1,194 @@
+//! Compressed JSONL incident archives
+
+use crate::error::{AppError, Result};
+use crate::models::Incident;
+use crate::state::IncidentEvent;
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+const ARCHIVE_PREFIX: &str = "incidents-";
+const ARCHIVE_EXTENSION: &str = ".jsonl.gz";
+
+/// An incident as written to an archive, one per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedIncident {
+    pub incident: Incident,
+
+    /// Event log of the incident at the time it was archived
+    #[serde(default)]
+    pub events: Vec<IncidentEvent>,
+
+    pub archived_at: DateTime<Utc>,
+}
+
+/// Directory of gzip-compressed JSONL archive files
+///
+/// Each archive run writes a new file named
+/// `incidents-<timestamp>-<id>.jsonl.gz`; files are never modified once
+/// written, so an incident that is restored and archived again appears in
+/// more than one file.
+#[derive(Debug, Clone)]
+pub struct IncidentArchive {
+    dir: PathBuf,
+}
+
+impl IncidentArchive {
+    /// Create an archive rooted at a directory
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Archive directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write incidents to a new archive file
+    ///
+    /// The file is written under a temporary name, synced to disk and then
+    /// renamed, so a partially written archive is never visible.
+    pub fn write(&self, records: &[ArchivedIncident]) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+
+        let name = format!(
+            "{}{}-{}{}",
+            ARCHIVE_PREFIX,
+            Utc::now().format("%Y%m%dT%H%M%S%.6fZ"),
+            Uuid::new_v4().simple(),
+            ARCHIVE_EXTENSION
+        );
+        let path = self.dir.join(&name);
+        let tmp_path = self.dir.join(format!(".{}.tmp", name));
+
+        let file = File::create(&tmp_path)?;
+        let mut encoder = GzEncoder::new(BufWriter::new(file), Compression::default());
+        for record in records {
+            serde_json::to_writer(&mut encoder, record)?;
+            encoder.write_all(b"\n")?;
+        }
+
+        let file = encoder
+            .finish()?
+            .into_inner()
+            .map_err(|e| AppError::Io(e.into_error()))?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+
+        Ok(path)
+    }
+
+    /// List archive files, oldest first
+    pub fn list(&self) -> Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            let is_archive = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(ARCHIVE_PREFIX) && name.ends_with(ARCHIVE_EXTENSION)
+                });
+            if is_archive {
+                paths.push(path);
+            }
+        }
+
+        // Names start with the creation timestamp
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Read every incident in an archive file
+    pub fn read(&self, path: &Path) -> Result<Vec<ArchivedIncident>> {
+        let reader = BufReader::new(GzDecoder::new(File::open(path)?));
+
+        let mut records = Vec::new();
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record = serde_json::from_str(&line).map_err(|e| {
+                AppError::Serialization(format!(
+                    "Invalid archive record at {}:{}: {}",
+                    path.display(),
+                    line_number + 1,
+                    e
+                ))
+            })?;
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    /// Find the most recently archived copy of an incident
+    pub fn find(&self, id: &Uuid) -> Result<Option<ArchivedIncident>> {
+        for path in self.list()?.iter().rev() {
+            if let Some(record) = self
+                .read(path)?
+                .into_iter()
+                .find(|record| record.incident.id == *id)
+            {
+                return Ok(Some(record));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IncidentType, Severity};
+
+    fn record(title: &str) -> ArchivedIncident {
+        ArchivedIncident {
+            incident: Incident::new(
+                "test-source".to_string(),
+                title.to_string(),
+                "Description".to_string(),
+                Severity::P3,
+                IncidentType::Application,
+            ),
+            events: Vec::new(),
+            archived_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_write_read_and_find() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = IncidentArchive::new(dir.path().join("archive"));
+        assert!(archive.list().unwrap().is_empty());
+
+        let first = vec![record("first"), record("second")];
+        let path = archive.write(&first).unwrap();
+        let second = vec![record("third")];
+        archive.write(&second).unwrap();
+
+        let files = archive.list().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], path);
+
+        let read = archive.read(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0].incident, first[0].incident);
+        assert_eq!(read[1].incident.title, "second");
+
+        let found = archive.find(&second[0].incident.id).unwrap().unwrap();
+        assert_eq!(found.incident.title, "third");
+        assert!(archive.find(&Uuid::new_v4()).unwrap().is_none());
+    }
+}
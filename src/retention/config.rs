@@ -0,0 +1,61 @@
+//! Retention configuration
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Retention and archival configuration
+///
+/// ```toml
+/// [retention]
+/// default_days = 90
+/// archive_dir = "./data/archive"
+///
+/// [retention.severity_days]
+/// P0 = 365
+/// P1 = 365
+///
+/// [[retention.label_rules]]
+/// label = "compliance=sox"
+/// days = 2555
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionConfig {
+    /// Days a resolved incident is kept when no other rule applies
+    pub default_days: u64,
+
+    /// Retention per severity (`P0`..`P4`), overriding the default
+    pub severity_days: HashMap<String, u64>,
+
+    /// Retention per label, checked in order before the severity rules
+    pub label_rules: Vec<LabelRetentionRule>,
+
+    /// Directory compressed archives are written to
+    pub archive_dir: PathBuf,
+
+    /// Incidents archived per file
+    pub batch_size: usize,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            default_days: 90,
+            severity_days: HashMap::new(),
+            label_rules: Vec::new(),
+            archive_dir: PathBuf::from("./data/archive"),
+            batch_size: 100,
+        }
+    }
+}
+
+/// Retention for incidents carrying a label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabelRetentionRule {
+    /// Label selector, `key` or `key=value`
+    pub label: String,
+
+    /// Days a matching incident is kept after resolution
+    pub days: u64,
+}
@@ -0,0 +1,405 @@
+//! Retention engine: archive and delete expired incidents
+
+use crate::error::{AppError, Result};
+use crate::models::{Incident, IncidentState};
+use crate::retention::archive::{ArchivedIncident, IncidentArchive};
+use crate::retention::config::RetentionConfig;
+use crate::retention::policy::RetentionPolicy;
+use crate::search::SearchService;
+use crate::state::{
+    IncidentFilter, IncidentSort, IncidentStore, SortDirection, SortField, TimeRange,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Outcome of a retention run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RetentionReport {
+    /// Resolved incidents old enough to be considered
+    pub scanned: usize,
+
+    /// Incidents past their retention period
+    pub expired: usize,
+
+    /// Expired incidents kept because of a legal hold
+    pub held: usize,
+
+    /// Incidents written to an archive
+    pub archived: usize,
+
+    /// Archived incidents removed from the store
+    pub deleted: usize,
+
+    /// Archived incidents left in the store because they changed meanwhile
+    pub skipped: usize,
+
+    /// Archive files written
+    pub archive_files: Vec<PathBuf>,
+}
+
+/// Outcome of restoring an archive file
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RestoreReport {
+    /// Incidents written back to the store
+    pub restored: usize,
+
+    /// Incidents that were already in the store
+    pub skipped: usize,
+}
+
+/// Applies retention policies to an incident store
+///
+/// Expired incidents are written to a compressed archive first and only
+/// deleted from the store and the search index once the archive file is
+/// safely on disk.
+#[derive(Clone)]
+pub struct RetentionEngine {
+    store: Arc<dyn IncidentStore>,
+    policy: RetentionPolicy,
+    archive: IncidentArchive,
+    search: Option<Arc<SearchService>>,
+    batch_size: usize,
+}
+
+impl RetentionEngine {
+    /// Create an engine from configuration
+    pub fn new(store: Arc<dyn IncidentStore>, config: &RetentionConfig) -> Result<Self> {
+        Ok(Self {
+            store,
+            policy: RetentionPolicy::from_config(config)?,
+            archive: IncidentArchive::new(&config.archive_dir),
+            search: None,
+            batch_size: config.batch_size.max(1),
+        })
+    }
+
+    /// Also remove archived incidents from a search index
+    pub fn with_search(mut self, search: Arc<SearchService>) -> Self {
+        self.search = Some(search);
+        self
+    }
+
+    /// Override the default retention period
+    pub fn with_default_retention_days(mut self, days: u64) -> Result<Self> {
+        self.policy = self.policy.with_default_days(days)?;
+        Ok(self)
+    }
+
+    /// Override the number of incidents archived per file
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Retention policy in use
+    pub fn policy(&self) -> &RetentionPolicy {
+        &self.policy
+    }
+
+    /// Archive incidents are written to
+    pub fn archive(&self) -> &IncidentArchive {
+        &self.archive
+    }
+
+    /// Archive and delete every expired incident
+    pub async fn run(&self) -> Result<RetentionReport> {
+        self.sweep(Utc::now(), false).await
+    }
+
+    /// Report what [`run`](Self::run) would do without changing anything
+    pub async fn dry_run(&self) -> Result<RetentionReport> {
+        self.sweep(Utc::now(), true).await
+    }
+
+    async fn sweep(&self, now: DateTime<Utc>, dry_run: bool) -> Result<RetentionReport> {
+        // Nothing resolved after this can have expired under any rule
+        let filter = IncidentFilter {
+            states: vec![IncidentState::Resolved, IncidentState::Closed],
+            resolved: TimeRange::new(None, Some(now - self.policy.min_retention())),
+            sort: IncidentSort::new(SortField::ResolvedAt, SortDirection::Asc),
+            ..Default::default()
+        };
+
+        let mut report = RetentionReport::default();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let page = self
+                .store
+                .query_incidents(&filter, cursor.as_deref(), self.batch_size as u32)
+                .await?;
+
+            let mut expired = Vec::new();
+            for incident in page.incidents {
+                report.scanned += 1;
+
+                let past_retention = self
+                    .policy
+                    .expires_at(&incident)
+                    .is_some_and(|expires_at| expires_at <= now);
+                if !past_retention {
+                    continue;
+                }
+
+                report.expired += 1;
+                if incident.is_on_legal_hold() {
+                    report.held += 1;
+                } else {
+                    expired.push(incident);
+                }
+            }
+
+            if !dry_run && !expired.is_empty() {
+                self.archive_batch(expired, now, &mut report).await?;
+            }
+
+            // The cursor is keyed on the last incident seen, so deleting the
+            // page does not shift the next one
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        tracing::info!(
+            scanned = report.scanned,
+            expired = report.expired,
+            held = report.held,
+            archived = report.archived,
+            deleted = report.deleted,
+            skipped = report.skipped,
+            dry_run,
+            "Retention run completed"
+        );
+
+        Ok(report)
+    }
+
+    async fn archive_batch(
+        &self,
+        incidents: Vec<Incident>,
+        archived_at: DateTime<Utc>,
+        report: &mut RetentionReport,
+    ) -> Result<()> {
+        let mut records = Vec::with_capacity(incidents.len());
+        for incident in incidents {
+            let events = self.store.get_incident_events(&incident.id).await?;
+            records.push(ArchivedIncident {
+                incident,
+                events,
+                archived_at,
+            });
+        }
+
+        let path = self.archive.write(&records)?;
+        report.archived += records.len();
+        tracing::info!(
+            path = %path.display(),
+            count = records.len(),
+            "Archived expired incidents"
+        );
+        report.archive_files.push(path);
+
+        for record in &records {
+            let id = record.incident.id;
+
+            // Leave incidents that were reopened, held or otherwise changed
+            // since they were read; the next run reconsiders them
+            match self.store.get_incident(&id).await? {
+                Some(current) if current.version == record.incident.version => {}
+                _ => {
+                    report.skipped += 1;
+                    continue;
+                }
+            }
+
+            match self.store.delete_incident(&id).await {
+                Ok(()) => report.deleted += 1,
+                Err(AppError::NotFound(_)) => report.skipped += 1,
+                Err(e) => return Err(e),
+            }
+
+            if let Some(ref search) = self.search {
+                if let Err(e) = search.delete_incident(&id.to_string()).await {
+                    tracing::warn!(
+                        incident_id = %id,
+                        error = %e,
+                        "Failed to remove archived incident from search index"
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restore the most recently archived copy of an incident
+    pub async fn restore(&self, id: &Uuid) -> Result<Incident> {
+        let record = self
+            .archive
+            .find(id)?
+            .ok_or_else(|| AppError::NotFound(format!("Incident {} not found in archive", id)))?;
+
+        if self.store.get_incident(id).await?.is_some() {
+            return Err(AppError::Validation(format!(
+                "Incident {} already exists in the store",
+                id
+            )));
+        }
+
+        self.restore_incident(&record.incident).await?;
+        Ok(record.incident)
+    }
+
+    /// Restore every incident in an archive file that is not in the store
+    pub async fn restore_file(&self, path: &Path) -> Result<RestoreReport> {
+        let mut report = RestoreReport::default();
+
+        for record in self.archive.read(path)? {
+            if self.store.get_incident(&record.incident.id).await?.is_some() {
+                report.skipped += 1;
+                continue;
+            }
+            self.restore_incident(&record.incident).await?;
+            report.restored += 1;
+        }
+
+        tracing::info!(
+            path = %path.display(),
+            restored = report.restored,
+            skipped = report.skipped,
+            "Restored archived incidents"
+        );
+
+        Ok(report)
+    }
+
+    async fn restore_incident(&self, incident: &Incident) -> Result<()> {
+        self.store.save_incident(incident).await?;
+
+        if let Some(ref search) = self.search {
+            if let Err(e) = search.index_incident(incident).await {
+                tracing::warn!(
+                    incident_id = %incident.id,
+                    error = %e,
+                    "Failed to index restored incident"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IncidentType, ResolutionMethod, Severity};
+    use crate::state::InMemoryStore;
+    use chrono::Duration;
+
+    async fn save_resolved(
+        store: &Arc<dyn IncidentStore>,
+        severity: Severity,
+        resolved_days_ago: i64,
+    ) -> Incident {
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Retention".to_string(),
+            "Description".to_string(),
+            severity,
+            IncidentType::Application,
+        );
+        incident.resolve(
+            "oncall".to_string(),
+            ResolutionMethod::Manual,
+            "Fixed".to_string(),
+            None,
+        );
+        if let Some(resolution) = incident.resolution.as_mut() {
+            resolution.resolved_at = Utc::now() - Duration::days(resolved_days_ago);
+        }
+        store.save_incident(&incident).await.unwrap();
+        incident
+    }
+
+    fn engine(store: &Arc<dyn IncidentStore>, dir: &Path) -> RetentionEngine {
+        let config = RetentionConfig {
+            default_days: 30,
+            archive_dir: dir.to_path_buf(),
+            batch_size: 2,
+            ..Default::default()
+        };
+        RetentionEngine::new(store.clone(), &config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_run_archives_expired_incidents() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let engine = engine(&store, dir.path());
+
+        let mut expired = Vec::new();
+        for _ in 0..3 {
+            expired.push(save_resolved(&store, Severity::P3, 40).await);
+        }
+        let recent = save_resolved(&store, Severity::P3, 10).await;
+        let mut held = save_resolved(&store, Severity::P3, 40).await;
+        held.place_legal_hold("Audit".to_string(), "legal@example.com".to_string());
+        store.update_incident(&held).await.unwrap();
+
+        let preview = engine.dry_run().await.unwrap();
+        assert_eq!(preview.expired, 4);
+        assert_eq!(preview.held, 1);
+        assert_eq!(preview.archived, 0);
+        assert_eq!(store.count_incidents(&IncidentFilter::default()).await.unwrap(), 5);
+
+        let report = engine.run().await.unwrap();
+        assert_eq!(report.archived, 3);
+        assert_eq!(report.deleted, 3);
+        assert_eq!(report.held, 1);
+        assert_eq!(report.archive_files.len(), 2);
+
+        for incident in &expired {
+            assert!(store.get_incident(&incident.id).await.unwrap().is_none());
+        }
+        assert!(store.get_incident(&recent.id).await.unwrap().is_some());
+        assert!(store.get_incident(&held.id).await.unwrap().is_some());
+
+        let archived = engine.archive().find(&expired[0].id).unwrap().unwrap();
+        assert_eq!(archived.incident, expired[0]);
+        assert!(!archived.events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let engine = engine(&store, dir.path());
+
+        let first = save_resolved(&store, Severity::P2, 60).await;
+        let second = save_resolved(&store, Severity::P2, 60).await;
+        let report = engine.run().await.unwrap();
+        assert_eq!(report.deleted, 2);
+
+        let restored = engine.restore(&first.id).await.unwrap();
+        assert_eq!(restored, first);
+        assert_eq!(store.get_incident(&first.id).await.unwrap().unwrap(), first);
+        assert!(matches!(
+            engine.restore(&first.id).await,
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            engine.restore(&Uuid::new_v4()).await,
+            Err(AppError::NotFound(_))
+        ));
+
+        let restored = engine.restore_file(&report.archive_files[0]).await.unwrap();
+        assert_eq!(restored.restored, 1);
+        assert_eq!(restored.skipped, 1);
+        assert!(store.get_incident(&second.id).await.unwrap().is_some());
+    }
+}
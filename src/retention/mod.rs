@@ -0,0 +1,37 @@
+//! Incident retention and archival
+//!
+//! Resolved incidents are kept for a retention period chosen per label,
+//! per severity or by default. Once it has passed they are written to
+//! gzip-compressed JSONL files on local disk and then removed from the
+//! incident store and the search index. Incidents under legal hold are never
+//! archived, and archived incidents can be restored into the store.
+//!
+//! The engine is run by the `cleanup_old_incidents` scheduled job or from the
+//! command line with `llm-im-cli retention`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use llm_incident_manager::retention::{RetentionConfig, RetentionEngine};
+//! use llm_incident_manager::state::InMemoryStore;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> llm_incident_manager::Result<()> {
+//! let store = Arc::new(InMemoryStore::new());
+//! let engine = RetentionEngine::new(store, &RetentionConfig::default())?;
+//!
+//! let report = engine.run().await?;
+//! println!("Archived {} incidents", report.archived);
+//! # Ok(())
+//! # }
+//! ```
+
+mod archive;
+mod config;
+mod engine;
+mod policy;
+
+pub use archive::{ArchivedIncident, IncidentArchive};
+pub use config::{LabelRetentionRule, RetentionConfig};
+pub use engine::{RestoreReport, RetentionEngine, RetentionReport};
+pub use policy::RetentionPolicy;
@@ -0,0 +1,207 @@
+//! Retention policy evaluation
+
+use crate::error::{AppError, Result};
+use crate::models::{Incident, Severity};
+use crate::retention::config::RetentionConfig;
+use crate::state::LabelSelector;
+use chrono::{DateTime, Duration, Utc};
+
+/// Compiled retention rules
+///
+/// The retention of an incident is taken from the first label rule that
+/// matches it, then from its severity, then from the default. Retention is
+/// measured from the time the incident was resolved; unresolved incidents and
+/// incidents under legal hold never expire.
+#[derive(Debug, Clone)]
+pub struct RetentionPolicy {
+    default: Duration,
+    by_severity: [Option<Duration>; 5],
+    by_label: Vec<(LabelSelector, Duration)>,
+}
+
+impl RetentionPolicy {
+    /// Build a policy from configuration
+    pub fn from_config(config: &RetentionConfig) -> Result<Self> {
+        let mut by_severity = [None; 5];
+        for (severity, days) in &config.severity_days {
+            // Config keys may have been lowercased by the loader
+            let severity: Severity = severity.to_uppercase().parse().map_err(|_| {
+                AppError::Configuration(format!(
+                    "Invalid severity '{}' in retention.severity_days",
+                    severity
+                ))
+            })?;
+            by_severity[severity.priority() as usize] = Some(days_to_duration(*days)?);
+        }
+
+        let by_label = config
+            .label_rules
+            .iter()
+            .map(|rule| {
+                let selector = rule.label.parse().map_err(|e| {
+                    AppError::Configuration(format!("Invalid retention label rule: {}", e))
+                })?;
+                Ok((selector, days_to_duration(rule.days)?))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            default: days_to_duration(config.default_days)?,
+            by_severity,
+            by_label,
+        })
+    }
+
+    /// Replace the default retention
+    pub fn with_default_days(mut self, days: u64) -> Result<Self> {
+        self.default = days_to_duration(days)?;
+        Ok(self)
+    }
+
+    /// Retention period that applies to an incident
+    pub fn retention_for(&self, incident: &Incident) -> Duration {
+        self.by_label
+            .iter()
+            .find(|(selector, _)| selector.matches(&incident.labels))
+            .map(|(_, retention)| *retention)
+            .or(self.by_severity[incident.severity.priority() as usize])
+            .unwrap_or(self.default)
+    }
+
+    /// Time at which an incident's retention period ends
+    ///
+    /// `None` if the incident is not resolved. Legal holds are not taken into
+    /// account here; see [`is_expired`](Self::is_expired).
+    pub fn expires_at(&self, incident: &Incident) -> Option<DateTime<Utc>> {
+        if incident.is_active() {
+            return None;
+        }
+        let resolved_at = incident.resolution.as_ref()?.resolved_at;
+        Some(resolved_at + self.retention_for(incident))
+    }
+
+    /// Whether an incident has passed its retention period and is not held
+    pub fn is_expired(&self, incident: &Incident, now: DateTime<Utc>) -> bool {
+        !incident.is_on_legal_hold()
+            && self.expires_at(incident).is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Shortest retention of any rule
+    ///
+    /// Incidents resolved more recently than this cannot have expired.
+    pub fn min_retention(&self) -> Duration {
+        self.by_severity
+            .iter()
+            .flatten()
+            .chain(self.by_label.iter().map(|(_, retention)| retention))
+            .fold(self.default, |min, retention| min.min(*retention))
+    }
+}
+
+fn days_to_duration(days: u64) -> Result<Duration> {
+    i64::try_from(days)
+        .ok()
+        .and_then(Duration::try_days)
+        .ok_or_else(|| AppError::Configuration(format!("Retention of {} days is too long", days)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IncidentType, ResolutionMethod};
+    use crate::retention::config::LabelRetentionRule;
+    use std::collections::HashMap;
+
+    fn resolved_incident(severity: Severity, resolved_days_ago: i64) -> Incident {
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Retention".to_string(),
+            "Description".to_string(),
+            severity,
+            IncidentType::Application,
+        );
+        incident.resolve(
+            "oncall".to_string(),
+            ResolutionMethod::Manual,
+            "Fixed".to_string(),
+            None,
+        );
+        if let Some(resolution) = incident.resolution.as_mut() {
+            resolution.resolved_at = Utc::now() - Duration::days(resolved_days_ago);
+        }
+        incident
+    }
+
+    fn policy() -> RetentionPolicy {
+        RetentionPolicy::from_config(&RetentionConfig {
+            default_days: 90,
+            severity_days: HashMap::from([("p0".to_string(), 365)]),
+            label_rules: vec![
+                LabelRetentionRule {
+                    label: "compliance=sox".to_string(),
+                    days: 2555,
+                },
+                LabelRetentionRule {
+                    label: "env=staging".to_string(),
+                    days: 7,
+                },
+            ],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rule_precedence() {
+        let policy = policy();
+        let now = Utc::now();
+
+        assert!(policy.is_expired(&resolved_incident(Severity::P2, 100), now));
+        assert!(!policy.is_expired(&resolved_incident(Severity::P0, 100), now));
+
+        let mut sox = resolved_incident(Severity::P2, 400);
+        sox.labels.insert("compliance".to_string(), "sox".to_string());
+        assert!(!policy.is_expired(&sox, now));
+
+        let mut staging = resolved_incident(Severity::P0, 10);
+        staging.labels.insert("env".to_string(), "staging".to_string());
+        assert!(policy.is_expired(&staging, now));
+
+        assert_eq!(policy.min_retention(), Duration::days(7));
+    }
+
+    #[test]
+    fn test_unresolved_and_held_incidents_never_expire() {
+        let policy = policy();
+        let now = Utc::now();
+
+        let mut held = resolved_incident(Severity::P3, 1000);
+        held.place_legal_hold("Litigation".to_string(), "legal@example.com".to_string());
+        assert!(policy.expires_at(&held).is_some());
+        assert!(!policy.is_expired(&held, now));
+
+        held.release_legal_hold();
+        assert!(policy.is_expired(&held, now));
+
+        let open = Incident::new(
+            "test-source".to_string(),
+            "Open".to_string(),
+            "Description".to_string(),
+            Severity::P3,
+            IncidentType::Application,
+        );
+        assert!(policy.expires_at(&open).is_none());
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let config = RetentionConfig {
+            severity_days: HashMap::from([("P9".to_string(), 30)]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            RetentionPolicy::from_config(&config),
+            Err(AppError::Configuration(_))
+        ));
+    }
+}
@@ -100,7 +100,6 @@ impl Default for JobsConfig {
                 enabled: true,
                 schedule: "*/15 * * * *".to_string(), // Every 15 minutes
                 config: serde_json::json!({
-                    "stale_threshold_hours": 24,
                     "escalate": true
                 }),
             },
@@ -122,7 +121,7 @@ impl Default for JobsConfig {
                 enabled: true,
                 schedule: "0 0 * * 0".to_string(), // Weekly on Sunday at midnight
                 config: serde_json::json!({
-                    "models": ["severity_classifier", "correlation_detector"],
+                    "models": ["severity", "type", "priority"],
                     "min_training_samples": 1000
                 }),
             },
@@ -5,6 +5,7 @@ use super::{
     metrics::SCHEDULER_METRICS,
 };
 use dashmap::DashMap;
+use std::any::Any;
 use std::sync::Arc;
 use tokio_cron_scheduler::{JobScheduler, JobSchedulerError};
 use tracing::{debug, error, info, warn};
@@ -22,6 +23,9 @@ pub struct SchedulerService {
 
     /// Whether the scheduler is running
     running: Arc<tokio::sync::RwLock<bool>>,
+
+    /// Application state handed to every job
+    app_state: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl SchedulerService {
@@ -38,9 +42,18 @@ impl SchedulerService {
             scheduler,
             jobs: Arc::new(DashMap::new()),
             running: Arc::new(tokio::sync::RwLock::new(false)),
+            app_state: None,
         })
     }
 
+    /// Set the application state passed to jobs added after this call
+    ///
+    /// Jobs read it back with [`JobContext::app_state`].
+    pub fn with_app_state<T: Any + Send + Sync>(mut self, state: Arc<T>) -> Self {
+        self.app_state = Some(state);
+        self
+    }
+
     /// Start the scheduler
     pub async fn start(&mut self) -> SchedulerResult<()> {
         if !self.config.enabled {
@@ -107,8 +120,10 @@ impl SchedulerService {
 
         // Create tokio-cron-scheduler job
         let job_arc_clone = job_arc.clone();
+        let app_state = self.app_state.clone();
         let cron_job = tokio_cron_scheduler::Job::new_async(schedule.as_str(), move |_uuid, _l| {
             let job = job_arc_clone.clone();
+            let app_state = app_state.clone();
             Box::pin(async move {
                 let metadata = job.get_metadata().await;
                 let job_name = metadata.name.clone();
@@ -120,7 +135,11 @@ impl SchedulerService {
                 SCHEDULER_METRICS.record_execution_start(&job_name);
 
                 let start = std::time::Instant::now();
-                let ctx = JobContext::new(metadata);
+                // Share the job's metadata so tasks can record results in it
+                let ctx = JobContext {
+                    metadata: job.metadata.clone(),
+                    app_state,
+                };
                 let result = job.execute(ctx).await;
                 let duration = start.elapsed();
 
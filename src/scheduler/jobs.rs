@@ -163,6 +163,11 @@ impl JobContext {
         self
     }
 
+    /// Get the application state if it is of type `T`
+    pub fn app_state<T: std::any::Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.app_state.clone()?.downcast().ok()
+    }
+
     pub async fn get_metadata(&self) -> JobMetadata {
         self.metadata.read().await.clone()
     }
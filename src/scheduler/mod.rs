@@ -45,5 +45,6 @@ pub use jobs::{Job, JobContext, JobId, JobMetadata, JobStatus};
 pub use metrics::{init_scheduler_metrics, SCHEDULER_METRICS};
 pub use tasks::{
     cleanup_old_incidents, generate_daily_reports, monitor_stale_incidents,
-    refresh_correlation_rules, sync_external_systems, update_ml_models,
+    refresh_correlation_rules, report_subscription_jobs, sync_external_systems, update_ml_models,
+    TaskServices,
 };
@@ -1,12 +1,75 @@
 //! Predefined scheduled tasks for incident management
 
-use super::jobs::JobContext;
+use super::jobs::{Job, JobContext, JobMetadata};
+use crate::analytics::{ReportDeliveryService, ReportType};
+use crate::ml::{MLService, ModelTarget};
+use crate::retention::RetentionEngine;
+use crate::staleness::StaleIncidentMonitor;
+use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
+/// Services used by the predefined tasks
+///
+/// Pass to [`SchedulerService::with_app_state`](super::SchedulerService::with_app_state);
+/// a task whose service is not set logs a warning and does nothing.
+#[derive(Clone, Default)]
+pub struct TaskServices {
+    /// Retention engine used by `cleanup_old_incidents`
+    pub retention: Option<Arc<RetentionEngine>>,
+
+    /// Stale incident monitor used by `monitor_stale_incidents`
+    pub staleness: Option<Arc<StaleIncidentMonitor>>,
+
+    /// Report delivery service used by `generate_daily_reports`
+    pub reports: Option<Arc<ReportDeliveryService>>,
+
+    /// ML service retrained by `update_ml_models`
+    pub ml: Option<Arc<MLService>>,
+}
+
+impl TaskServices {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retention(mut self, engine: Arc<RetentionEngine>) -> Self {
+        self.retention = Some(engine);
+        self
+    }
+
+    pub fn with_staleness(mut self, monitor: Arc<StaleIncidentMonitor>) -> Self {
+        self.staleness = Some(monitor);
+        self
+    }
+
+    pub fn with_reports(mut self, service: Arc<ReportDeliveryService>) -> Self {
+        self.reports = Some(service);
+        self
+    }
+
+    pub fn with_ml(mut self, service: Arc<MLService>) -> Self {
+        self.ml = Some(service);
+        self
+    }
+}
+
+/// Store a task's outcome under `last_result` in the job metadata
+async fn record_result(ctx: &JobContext, result: serde_json::Value) {
+    ctx.update_metadata(|metadata| {
+        if !metadata.metadata.is_object() {
+            metadata.metadata = serde_json::json!({});
+        }
+        metadata.metadata["last_result"] = result;
+    })
+    .await;
+}
+
 /// Clean up old resolved incidents based on retention policy
 ///
-/// This task runs daily to remove or archive incidents that have been
-/// resolved for longer than the retention period.
+/// This task runs daily to archive incidents that have been resolved for
+/// longer than their retention period and remove them from the store.
+/// `retention_days` and `batch_size` in the job config override the
+/// `[retention]` defaults.
 ///
 /// Default schedule: Daily at 2 AM (`0 2 * * *`)
 pub async fn cleanup_old_incidents(ctx: JobContext) -> Result<(), String> {
@@ -15,34 +78,36 @@ pub async fn cleanup_old_incidents(ctx: JobContext) -> Result<(), String> {
     let metadata = ctx.get_metadata().await;
     let config = &metadata.metadata;
 
-    // Extract configuration
-    let retention_days = config
-        .get("retention_days")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(90);
-
-    let batch_size = config
-        .get("batch_size")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(100) as usize;
+    let Some(engine) = ctx
+        .app_state::<TaskServices>()
+        .and_then(|services| services.retention.clone())
+    else {
+        warn!("No retention engine configured, skipping cleanup");
+        return Ok(());
+    };
+    let mut engine = engine.as_ref().clone();
 
-    info!(
-        retention_days = retention_days,
-        batch_size = batch_size,
-        "Cleaning up incidents older than {} days",
-        retention_days
-    );
+    // Extract configuration
+    if let Some(retention_days) = config.get("retention_days").and_then(|v| v.as_u64()) {
+        engine = engine
+            .with_default_retention_days(retention_days)
+            .map_err(|e| e.to_string())?;
+    }
 
-    // TODO: Implement actual cleanup logic
-    // This would involve:
-    // 1. Query incidents resolved_at > retention_days ago
-    // 2. Archive or delete in batches
-    // 3. Update metrics
+    if let Some(batch_size) = config.get("batch_size").and_then(|v| v.as_u64()) {
+        engine = engine.with_batch_size(batch_size as usize);
+    }
 
-    let cleaned_count = 0; // Placeholder
+    let report = engine.run().await.map_err(|e| {
+        error!(error = %e, "Retention run failed");
+        format!("Retention run failed: {}", e)
+    })?;
 
     info!(
-        cleaned_count = cleaned_count,
+        cleaned_count = report.deleted,
+        archived = report.archived,
+        held = report.held,
+        skipped = report.skipped,
         "Cleanup task completed successfully"
     );
 
@@ -51,11 +116,13 @@ pub async fn cleanup_old_incidents(ctx: JobContext) -> Result<(), String> {
 
 /// Generate daily incident reports
 ///
-/// Creates comprehensive reports including:
-/// - Daily incident summary
-/// - SLA compliance metrics
-/// - Trend analysis
-/// - Team performance metrics
+/// Generates the reports of the previous UTC day for the `[reporting]`
+/// subscriptions, exports them to the output directory and delivers them by
+/// email or Slack. Without a `subscription` in the job config the task runs
+/// the organisation-wide reports, whose types `report_types` overrides, and
+/// every subscription that has no schedule of its own; see
+/// [`report_subscription_jobs`] for the others. The run summary is stored
+/// under `last_result` in the job metadata.
 ///
 /// Default schedule: Daily at 8 AM (`0 8 * * *`)
 pub async fn generate_daily_reports(ctx: JobContext) -> Result<(), String> {
@@ -64,41 +131,84 @@ pub async fn generate_daily_reports(ctx: JobContext) -> Result<(), String> {
     let metadata = ctx.get_metadata().await;
     let config = &metadata.metadata;
 
+    let Some(service) = ctx
+        .app_state::<TaskServices>()
+        .and_then(|services| services.reports.clone())
+    else {
+        warn!("No report delivery service configured, skipping reports");
+        return Ok(());
+    };
+
     let report_types = config
         .get("report_types")
         .and_then(|v| v.as_array())
         .map(|arr| {
             arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect::<Vec<_>>()
+                .filter_map(|v| v.as_str())
+                .map(|name| name.parse::<ReportType>())
+                .collect::<Result<Vec<_>, _>>()
         })
-        .unwrap_or_else(|| vec!["summary".to_string()]);
+        .transpose()?;
 
-    info!(report_types = ?report_types, "Generating reports");
+    let subscription = config.get("subscription").and_then(|v| v.as_str());
 
-    for report_type in &report_types {
-        debug!(report_type = report_type, "Generating report");
+    info!(report_types = ?report_types, subscription = ?subscription, "Generating reports");
 
-        // TODO: Implement actual report generation
-        // This would involve:
-        // 1. Aggregate incident data for past 24 hours
-        // 2. Calculate metrics (MTTR, MTTD, SLA compliance)
-        // 3. Generate visualizations
-        // 4. Send via email/Slack/store in database
+    let result = match subscription {
+        Some(name) => service.run_subscription(name).await,
+        None => service.run_daily(report_types).await,
+    };
+    let run = result.map_err(|e| {
+        error!(error = %e, "Report generation failed");
+        format!("Report generation failed: {}", e)
+    })?;
+
+    for failure in &run.failures {
+        warn!(failure = %failure, "Report failed");
     }
 
     info!(
-        reports_generated = report_types.len(),
+        reports_generated = run.reports.len(),
+        skipped = run.skipped.len(),
+        failed = run.failures.len(),
         "Daily reports generated successfully"
     );
 
+    record_result(&ctx, serde_json::to_value(&run).map_err(|e| e.to_string())?).await;
+
+    if run.reports.is_empty() && !run.failures.is_empty() {
+        return Err(format!("All reports failed: {:?}", run.failures));
+    }
+
     Ok(())
 }
 
+/// Jobs for the report subscriptions that have their own schedule
+///
+/// Each job runs [`generate_daily_reports`] for one subscription; add them to
+/// the scheduler next to the predefined jobs.
+pub fn report_subscription_jobs(service: &ReportDeliveryService) -> Vec<Job> {
+    service
+        .scheduled_subscriptions()
+        .filter_map(|subscription| {
+            let schedule = subscription.schedule.clone()?;
+            let metadata =
+                JobMetadata::new(format!("generate_reports_{}", subscription.name), schedule)
+                    .with_description(format!("Scheduled reports for {}", subscription.name))
+                    .with_tags(vec!["reports".to_string()])
+                    .with_metadata(serde_json::json!({ "subscription": subscription.name }));
+            Some(Job::new(metadata, generate_daily_reports))
+        })
+        .collect()
+}
+
 /// Monitor and alert on stale incidents
 ///
-/// Identifies incidents that haven't been updated in a configured period
-/// and triggers escalation or notifications.
+/// Checks open incidents against the `[staleness]` rules. Each incident a
+/// rule fires for gets a timeline event and a reminder, and is escalated if
+/// the rule asks for it; `escalate: false` in the job config disables
+/// escalation for every rule. The findings are stored under `last_result` in
+/// the job metadata.
 ///
 /// Default schedule: Every 15 minutes (`*/15 * * * *`)
 pub async fn monitor_stale_incidents(ctx: JobContext) -> Result<(), String> {
@@ -107,30 +217,32 @@ pub async fn monitor_stale_incidents(ctx: JobContext) -> Result<(), String> {
     let metadata = ctx.get_metadata().await;
     let config = &metadata.metadata;
 
-    let stale_threshold_hours = config
-        .get("stale_threshold_hours")
-        .and_then(|v| v.as_u64())
-        .unwrap_or(24);
+    let Some(monitor) = ctx
+        .app_state::<TaskServices>()
+        .and_then(|services| services.staleness.clone())
+    else {
+        warn!("No stale incident monitor configured, skipping check");
+        return Ok(());
+    };
 
     let escalate = config
         .get("escalate")
         .and_then(|v| v.as_bool())
         .unwrap_or(true);
 
-    debug!(
-        stale_threshold_hours = stale_threshold_hours,
-        escalate = escalate,
-        "Checking for stale incidents"
-    );
+    debug!(escalate = escalate, "Checking for stale incidents");
 
-    // TODO: Implement stale incident detection
-    // This would involve:
-    // 1. Query open incidents with updated_at > threshold
-    // 2. Send notifications to assignees
-    // 3. Optionally escalate to higher priority/team
-    // 4. Log stale incident metrics
+    let result = if escalate {
+        monitor.run().await
+    } else {
+        monitor.run_without_escalation().await
+    };
+    let report = result.map_err(|e| {
+        error!(error = %e, "Stale incident check failed");
+        format!("Stale incident check failed: {}", e)
+    })?;
 
-    let stale_count = 0; // Placeholder
+    let stale_count = report.findings.len();
 
     if stale_count > 0 {
         warn!(
@@ -142,6 +254,12 @@ pub async fn monitor_stale_incidents(ctx: JobContext) -> Result<(), String> {
         debug!("No stale incidents found");
     }
 
+    record_result(
+        &ctx,
+        serde_json::to_value(&report).map_err(|e| e.to_string())?,
+    )
+    .await;
+
     Ok(())
 }
 
@@ -244,8 +362,13 @@ pub async fn sync_external_systems(ctx: JobContext) -> Result<(), String> {
 
 /// Update and retrain ML models
 ///
-/// Retrains machine learning models for severity classification,
-/// correlation detection, and anomaly detection using recent incident data.
+/// Retrains the severity, type and priority classifiers, or the `models`
+/// listed in the job config, on resolved incidents. Each retrained model is
+/// promoted only if it beats the current one on a holdout split, and promoted
+/// models are versioned under the ML model path; see
+/// [`MLService::retrain_from_store`]. Nothing is retrained with fewer than
+/// `min_training_samples` resolved incidents. The run summary is stored under
+/// `last_result` in the job metadata.
 ///
 /// Default schedule: Weekly on Sunday at midnight (`0 0 * * 0`)
 pub async fn update_ml_models(ctx: JobContext) -> Result<(), String> {
@@ -254,15 +377,27 @@ pub async fn update_ml_models(ctx: JobContext) -> Result<(), String> {
     let metadata = ctx.get_metadata().await;
     let config = &metadata.metadata;
 
-    let models = config
-        .get("models")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect::<Vec<_>>()
-        })
-        .unwrap_or_else(|| vec!["severity_classifier".to_string()]);
+    let Some(service) = ctx
+        .app_state::<TaskServices>()
+        .and_then(|services| services.ml.clone())
+    else {
+        warn!("No ML service configured, skipping model update");
+        return Ok(());
+    };
+
+    let mut models = Vec::new();
+    match config.get("models").and_then(|v| v.as_array()) {
+        Some(names) => {
+            for name in names.iter().filter_map(|v| v.as_str()) {
+                match name.parse::<ModelTarget>() {
+                    Ok(target) if !models.contains(&target) => models.push(target),
+                    Ok(_) => {}
+                    Err(e) => warn!(model = name, "{}, skipping", e),
+                }
+            }
+        }
+        None => models.extend(ModelTarget::ALL),
+    }
 
     let min_training_samples = config
         .get("min_training_samples")
@@ -275,36 +410,38 @@ pub async fn update_ml_models(ctx: JobContext) -> Result<(), String> {
         "Updating ML models"
     );
 
-    let mut update_results = Vec::new();
-
-    for model_name in &models {
-        info!(model = model_name, "Updating ML model: {}", model_name);
-
-        // TODO: Implement actual model training
-        // This would involve:
-        // 1. Fetch training data from recent incidents
-        // 2. Validate minimum sample size
-        // 3. Train model with new data
-        // 4. Evaluate model performance
-        // 5. Deploy new model if metrics improved
-        // 6. Archive old model version
-
-        update_results.push((model_name.clone(), true));
+    let report = service
+        .retrain_from_store(&models, min_training_samples as usize)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "ML model update failed");
+            format!("ML model update failed: {}", e)
+        })?;
+
+    record_result(
+        &ctx,
+        serde_json::to_value(&report).map_err(|e| e.to_string())?,
+    )
+    .await;
+
+    if let Some(reason) = &report.skipped {
+        info!(reason = %reason, "Skipped ML model update");
+        return Ok(());
     }
 
-    let successful = update_results.iter().filter(|(_, success)| *success).count();
-
     info!(
-        total_models = models.len(),
-        successful = successful,
+        total_models = report.outcomes.len(),
+        promoted = report.promoted(),
+        failed = report.failed(),
         "ML model update completed"
     );
 
-    if successful < models.len() {
-        let failed_models: Vec<_> = update_results
+    if report.failed() > 0 {
+        let failed_models: Vec<_> = report
+            .outcomes
             .iter()
-            .filter(|(_, success)| !*success)
-            .map(|(name, _)| name.as_str())
+            .filter(|outcome| outcome.error.is_some())
+            .map(|outcome| outcome.target.as_str())
             .collect();
 
         error!(failed_models = ?failed_models, "Some models failed to update");
@@ -0,0 +1,125 @@
+//! Staleness rule configuration
+
+use crate::models::{Incident, IncidentState, Severity};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Stale incident detection configuration
+///
+/// ```toml
+/// [[staleness.rules]]
+/// name = "p1-investigating-idle"
+/// severities = ["P0", "P1"]
+/// states = ["Investigating"]
+/// idle_minutes = 30
+/// escalate = true
+///
+/// [[staleness.rules]]
+/// name = "unassigned"
+/// states = ["Detected"]
+/// unassigned = true
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StalenessConfig {
+    /// Rules checked against every open incident, in order
+    pub rules: Vec<StaleRule>,
+}
+
+impl Default for StalenessConfig {
+    fn default() -> Self {
+        Self {
+            rules: vec![StaleRule {
+                name: "inactive".to_string(),
+                idle_minutes: Some(24 * 60),
+                ..StaleRule::default()
+            }],
+        }
+    }
+}
+
+/// Condition under which an open incident counts as stale
+///
+/// Every condition that is set must hold. Empty lists match everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleRule {
+    /// Rule name, recorded on the timeline and in the job report
+    pub name: String,
+
+    /// Severities the rule applies to
+    #[serde(default)]
+    pub severities: Vec<Severity>,
+
+    /// States the rule applies to
+    #[serde(default)]
+    pub states: Vec<IncidentState>,
+
+    /// Minutes without timeline activity
+    ///
+    /// Reminders added by the monitor do not count as activity.
+    #[serde(default)]
+    pub idle_minutes: Option<u64>,
+
+    /// Only match incidents nobody is assigned to
+    #[serde(default)]
+    pub unassigned: bool,
+
+    /// Escalate the incident when the rule fires
+    #[serde(default)]
+    pub escalate: bool,
+
+    /// Minutes before the rule fires again for the same incident
+    #[serde(default = "default_repeat_minutes")]
+    pub repeat_minutes: u64,
+}
+
+impl Default for StaleRule {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            severities: Vec::new(),
+            states: Vec::new(),
+            idle_minutes: None,
+            unassigned: false,
+            escalate: false,
+            repeat_minutes: default_repeat_minutes(),
+        }
+    }
+}
+
+impl StaleRule {
+    /// Whether the rule's conditions hold for an open incident
+    ///
+    /// `last_activity` is the time of the incident's latest timeline event
+    /// that was not a stale reminder.
+    pub fn matches(
+        &self,
+        incident: &Incident,
+        last_activity: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        incident.is_active()
+            && (self.severities.is_empty() || self.severities.contains(&incident.severity))
+            && (self.states.is_empty() || self.states.contains(&incident.state))
+            && (!self.unassigned || incident.assignees.is_empty())
+            && self
+                .idle_minutes
+                .is_none_or(|minutes| now - last_activity >= minutes_to_duration(minutes))
+    }
+
+    /// Minimum time between two reminders for the same incident
+    pub fn repeat_after(&self) -> Duration {
+        minutes_to_duration(self.repeat_minutes)
+    }
+}
+
+fn default_repeat_minutes() -> u64 {
+    60
+}
+
+fn minutes_to_duration(minutes: u64) -> Duration {
+    i64::try_from(minutes)
+        .ok()
+        .and_then(Duration::try_minutes)
+        .unwrap_or(Duration::MAX)
+}
@@ -0,0 +1,34 @@
+//! Stale incident detection
+//!
+//! Open incidents are checked against configurable staleness rules, such as
+//! "a P1 in `Investigating` with no timeline activity for 30 minutes" or "an
+//! incident in `Detected` with nobody assigned". When a rule fires the
+//! monitor records a timeline event on the incident, sends a reminder through
+//! the [`NotificationService`](crate::notifications::NotificationService) and,
+//! if the rule asks for it, escalates through the
+//! [`EscalationEngine`](crate::escalation::EscalationEngine).
+//!
+//! The monitor is run by the `monitor_stale_incidents` scheduled job.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use llm_incident_manager::staleness::{StaleIncidentMonitor, StalenessConfig};
+//! use llm_incident_manager::state::InMemoryStore;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> llm_incident_manager::Result<()> {
+//! let store = Arc::new(InMemoryStore::new());
+//! let monitor = StaleIncidentMonitor::new(store, StalenessConfig::default());
+//!
+//! let report = monitor.run().await?;
+//! println!("Found {} stale incidents", report.findings.len());
+//! # Ok(())
+//! # }
+//! ```
+
+mod config;
+mod monitor;
+
+pub use config::{StaleRule, StalenessConfig};
+pub use monitor::{StaleFinding, StaleIncidentMonitor, StaleReport, STALE_RULE_METADATA_KEY};
@@ -0,0 +1,369 @@
+//! Stale incident monitor
+
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationEngine;
+use crate::models::{EventType, Incident, IncidentState, Severity, TimelineEvent};
+use crate::notifications::NotificationService;
+use crate::staleness::config::{StaleRule, StalenessConfig};
+use crate::state::{IncidentFilter, IncidentStore};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Timeline metadata key marking a stale reminder and naming its rule
+pub const STALE_RULE_METADATA_KEY: &str = "stale_rule";
+
+const ACTOR: &str = "stale-monitor";
+const PAGE_SIZE: u32 = 500;
+
+/// An incident a staleness rule fired for
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleFinding {
+    pub incident_id: Uuid,
+    pub title: String,
+    pub severity: Severity,
+    pub state: IncidentState,
+    pub rule: String,
+
+    /// Minutes since the last timeline activity
+    pub idle_minutes: i64,
+
+    /// Whether a reminder was queued
+    pub notified: bool,
+
+    /// Whether the incident was escalated
+    pub escalated: bool,
+}
+
+/// Outcome of a staleness check
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StaleReport {
+    /// Open incidents checked
+    pub scanned: usize,
+
+    /// Incidents a rule fired for
+    pub findings: Vec<StaleFinding>,
+
+    /// Stale incidents skipped because they changed while being checked
+    pub conflicts: usize,
+}
+
+/// Checks open incidents against staleness rules and nudges their owners
+///
+/// The first rule that matches an incident applies; if that rule already
+/// reminded the incident within its `repeat_minutes`, nothing happens.
+pub struct StaleIncidentMonitor {
+    store: Arc<dyn IncidentStore>,
+    rules: Vec<StaleRule>,
+    notifications: Option<Arc<NotificationService>>,
+    escalation: Option<Arc<EscalationEngine>>,
+}
+
+impl StaleIncidentMonitor {
+    pub fn new(store: Arc<dyn IncidentStore>, config: StalenessConfig) -> Self {
+        Self {
+            store,
+            rules: config.rules,
+            notifications: None,
+            escalation: None,
+        }
+    }
+
+    /// Send reminders through a notification service
+    pub fn with_notifications(mut self, notifications: Arc<NotificationService>) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
+    /// Escalate incidents whose rule has `escalate` set
+    pub fn with_escalation(mut self, escalation: Arc<EscalationEngine>) -> Self {
+        self.escalation = Some(escalation);
+        self
+    }
+
+    /// Check every open incident
+    pub async fn run(&self) -> Result<StaleReport> {
+        self.check(Utc::now(), true).await
+    }
+
+    /// Check every open incident without escalating any of them
+    pub async fn run_without_escalation(&self) -> Result<StaleReport> {
+        self.check(Utc::now(), false).await
+    }
+
+    async fn check(&self, now: DateTime<Utc>, allow_escalation: bool) -> Result<StaleReport> {
+        let filter = IncidentFilter {
+            active_only: true,
+            ..Default::default()
+        };
+
+        let mut report = StaleReport::default();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let page = self
+                .store
+                .query_incidents(&filter, cursor.as_deref(), PAGE_SIZE)
+                .await?;
+
+            for incident in page.incidents {
+                report.scanned += 1;
+
+                let last_activity = last_activity(&incident);
+                let Some(rule) = self
+                    .rules
+                    .iter()
+                    .find(|rule| rule.matches(&incident, last_activity, now))
+                else {
+                    continue;
+                };
+
+                if reminded_since(&incident, &rule.name, now - rule.repeat_after()) {
+                    continue;
+                }
+
+                let idle_minutes = (now - last_activity).num_minutes();
+                match self
+                    .nudge(incident, rule, idle_minutes, allow_escalation)
+                    .await
+                {
+                    Ok(finding) => report.findings.push(finding),
+                    Err(AppError::VersionConflict { .. }) | Err(AppError::NotFound(_)) => {
+                        report.conflicts += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        tracing::info!(
+            scanned = report.scanned,
+            stale = report.findings.len(),
+            conflicts = report.conflicts,
+            "Stale incident check completed"
+        );
+
+        Ok(report)
+    }
+
+    async fn nudge(
+        &self,
+        mut incident: Incident,
+        rule: &StaleRule,
+        idle_minutes: i64,
+        allow_escalation: bool,
+    ) -> Result<StaleFinding> {
+        let message = format!(
+            "Incident is stale (rule '{}'): no activity for {} minutes",
+            rule.name, idle_minutes
+        );
+
+        incident.add_timeline_event(TimelineEvent {
+            timestamp: Utc::now(),
+            event_type: EventType::NotificationSent,
+            actor: ACTOR.to_string(),
+            description: message.clone(),
+            metadata: HashMap::from([
+                (STALE_RULE_METADATA_KEY.to_string(), rule.name.clone()),
+                ("idle_minutes".to_string(), idle_minutes.to_string()),
+            ]),
+        });
+        self.store.update_incident(&incident).await?;
+
+        tracing::warn!(
+            incident_id = %incident.id,
+            rule = %rule.name,
+            idle_minutes,
+            "Stale incident detected"
+        );
+
+        let mut notified = false;
+        if let Some(ref notifications) = self.notifications {
+            match notifications
+                .notify_incident_stale(&incident, &message)
+                .await
+            {
+                Ok(ids) => notified = !ids.is_empty(),
+                Err(e) => tracing::warn!(
+                    incident_id = %incident.id,
+                    error = %e,
+                    "Failed to send stale incident reminder"
+                ),
+            }
+        }
+
+        let mut escalated = false;
+        if rule.escalate && allow_escalation {
+            if let Some(ref escalation) = self.escalation {
+                match escalation.escalate_now(&incident).await {
+                    Ok(result) => escalated = result,
+                    Err(e) => tracing::warn!(
+                        incident_id = %incident.id,
+                        error = %e,
+                        "Failed to escalate stale incident"
+                    ),
+                }
+            }
+        }
+
+        Ok(StaleFinding {
+            incident_id: incident.id,
+            title: incident.title,
+            severity: incident.severity,
+            state: incident.state,
+            rule: rule.name.clone(),
+            idle_minutes,
+            notified,
+            escalated,
+        })
+    }
+}
+
+/// Time of the latest timeline event that is not a stale reminder
+fn last_activity(incident: &Incident) -> DateTime<Utc> {
+    incident
+        .timeline
+        .iter()
+        .filter(|event| !event.metadata.contains_key(STALE_RULE_METADATA_KEY))
+        .map(|event| event.timestamp)
+        .max()
+        .unwrap_or(incident.created_at)
+}
+
+/// Whether a rule already reminded the incident after `since`
+fn reminded_since(incident: &Incident, rule: &str, since: DateTime<Utc>) -> bool {
+    incident.timeline.iter().any(|event| {
+        event.timestamp > since
+            && event
+                .metadata
+                .get(STALE_RULE_METADATA_KEY)
+                .map(String::as_str)
+                == Some(rule)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::IncidentType;
+    use crate::state::InMemoryStore;
+    use chrono::Duration;
+
+    async fn save_incident(
+        store: &Arc<dyn IncidentStore>,
+        severity: Severity,
+        state: IncidentState,
+        idle_minutes: i64,
+    ) -> Incident {
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Stale".to_string(),
+            "Description".to_string(),
+            severity,
+            IncidentType::Application,
+        );
+        incident.state = state;
+        incident.created_at = Utc::now() - Duration::minutes(idle_minutes);
+        for event in incident.timeline.iter_mut() {
+            event.timestamp = incident.created_at;
+        }
+        store.save_incident(&incident).await.unwrap();
+        incident
+    }
+
+    fn monitor(store: &Arc<dyn IncidentStore>) -> StaleIncidentMonitor {
+        StaleIncidentMonitor::new(
+            store.clone(),
+            StalenessConfig {
+                rules: vec![
+                    StaleRule {
+                        name: "p1-idle".to_string(),
+                        severities: vec![Severity::P0, Severity::P1],
+                        states: vec![IncidentState::Investigating],
+                        idle_minutes: Some(30),
+                        ..Default::default()
+                    },
+                    StaleRule {
+                        name: "unassigned".to_string(),
+                        states: vec![IncidentState::Detected],
+                        unassigned: true,
+                        ..Default::default()
+                    },
+                ],
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_rules_fire_and_record_timeline_event() {
+        let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let monitor = monitor(&store);
+
+        let idle = save_incident(&store, Severity::P1, IncidentState::Investigating, 45).await;
+        let busy = save_incident(&store, Severity::P1, IncidentState::Investigating, 10).await;
+        let low = save_incident(&store, Severity::P3, IncidentState::Investigating, 45).await;
+        let unassigned = save_incident(&store, Severity::P3, IncidentState::Detected, 0).await;
+        let mut assigned = save_incident(&store, Severity::P3, IncidentState::Detected, 0).await;
+        assigned.assignees.push("oncall".to_string());
+        store.update_incident(&assigned).await.unwrap();
+
+        let report = monitor.run().await.unwrap();
+        assert_eq!(report.scanned, 5);
+
+        let mut fired: Vec<_> = report
+            .findings
+            .iter()
+            .map(|f| (f.incident_id, f.rule.as_str()))
+            .collect();
+        fired.sort();
+        let mut expected = vec![(idle.id, "p1-idle"), (unassigned.id, "unassigned")];
+        expected.sort();
+        assert_eq!(fired, expected);
+
+        let stored = store.get_incident(&idle.id).await.unwrap().unwrap();
+        let event = stored.timeline.last().unwrap();
+        assert_eq!(event.event_type, EventType::NotificationSent);
+        assert_eq!(
+            event
+                .metadata
+                .get(STALE_RULE_METADATA_KEY)
+                .map(String::as_str),
+            Some("p1-idle")
+        );
+
+        for id in [busy.id, low.id, assigned.id] {
+            let stored = store.get_incident(&id).await.unwrap().unwrap();
+            assert!(stored
+                .timeline
+                .iter()
+                .all(|e| !e.metadata.contains_key(STALE_RULE_METADATA_KEY)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reminders_respect_repeat_interval() {
+        let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let monitor = monitor(&store);
+
+        let incident = save_incident(&store, Severity::P0, IncidentState::Investigating, 45).await;
+
+        assert_eq!(monitor.run().await.unwrap().findings.len(), 1);
+        // The reminder is not activity, but the rule waits before repeating
+        assert!(monitor.run().await.unwrap().findings.is_empty());
+
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(last_activity(&stored), incident.created_at);
+
+        let later = Utc::now() + Duration::minutes(61);
+        let report = monitor.check(later, true).await.unwrap();
+        assert_eq!(report.findings.len(), 1);
+        assert!(report.findings[0].idle_minutes >= 105);
+    }
+}
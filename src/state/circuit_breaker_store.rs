@@ -4,8 +4,10 @@ use crate::circuit_breaker::{
     get_circuit_breaker, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerResult,
 };
 use crate::error::{AppError, Result};
-use crate::models::Incident;
-use crate::state::{IncidentFilter, IncidentStore};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::{IncidentEvent, IncidentFilter, IncidentPage, IncidentStore};
 use async_trait::async_trait;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -90,8 +92,18 @@ impl<S: IncidentStore + 'static> IncidentStore for CircuitBreakerStore<S> {
     async fn update_incident(&self, incident: &Incident) -> Result<()> {
         let inner = Arc::clone(&self.inner);
         let incident = incident.clone();
-        self.execute(move || Box::pin(async move { inner.update_incident(&incident).await }))
-            .await
+        // A version conflict is a normal outcome of optimistic concurrency, so
+        // it must keep its type and must not count against the breaker
+        self.execute(move || {
+            Box::pin(async move {
+                match inner.update_incident(&incident).await {
+                    Err(e @ AppError::VersionConflict { .. }) => Ok(Err(e)),
+                    Err(e) => Err(e),
+                    Ok(()) => Ok(Ok(())),
+                }
+            })
+        })
+        .await?
     }
 
     async fn delete_incident(&self, id: &Uuid) -> Result<()> {
@@ -115,6 +127,21 @@ impl<S: IncidentStore + 'static> IncidentStore for CircuitBreakerStore<S> {
         .await
     }
 
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        let inner = Arc::clone(&self.inner);
+        let filter = filter.clone();
+        let cursor = cursor.map(str::to_string);
+        self.execute(move || {
+            Box::pin(async move { inner.query_incidents(&filter, cursor.as_deref(), limit).await })
+        })
+        .await
+    }
+
     async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
         let inner = Arc::clone(&self.inner);
         let filter = filter.clone();
@@ -128,6 +155,96 @@ impl<S: IncidentStore + 'static> IncidentStore for CircuitBreakerStore<S> {
         self.execute(move || Box::pin(async move { inner.find_by_fingerprint(&fingerprint).await }))
             .await
     }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        let inner = Arc::clone(&self.inner);
+        let id = *id;
+        self.execute(move || Box::pin(async move { inner.get_incident_events(&id).await }))
+            .await
+    }
+
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        let id = *id;
+        let events = events.to_vec();
+        self.execute(move || {
+            Box::pin(async move { inner.replace_incident_events(&id, &events).await })
+        })
+        .await
+    }
+
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        let postmortem = postmortem.clone();
+        self.execute(move || Box::pin(async move { inner.save_postmortem(&postmortem).await }))
+            .await
+    }
+
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        let inner = Arc::clone(&self.inner);
+        let id = *id;
+        self.execute(move || Box::pin(async move { inner.get_postmortem(&id).await }))
+            .await
+    }
+
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
+        let inner = Arc::clone(&self.inner);
+        let incident_id = incident_id.copied();
+        self.execute(move || {
+            Box::pin(async move { inner.list_postmortems(incident_id.as_ref()).await })
+        })
+        .await
+    }
+
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        let execution = execution.clone();
+        self.execute(move || {
+            Box::pin(async move { inner.save_playbook_execution(&execution).await })
+        })
+        .await
+    }
+
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
+        let inner = Arc::clone(&self.inner);
+        let id = *id;
+        self.execute(move || Box::pin(async move { inner.get_playbook_execution(&id).await }))
+            .await
+    }
+
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        let inner = Arc::clone(&self.inner);
+        let incident_id = incident_id.copied();
+        self.execute(move || {
+            Box::pin(async move { inner.list_playbook_executions(incident_id.as_ref()).await })
+        })
+        .await
+    }
+
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        let inner = Arc::clone(&self.inner);
+        let state = state.clone();
+        self.execute(move || Box::pin(async move { inner.save_escalation_state(&state).await }))
+            .await
+    }
+
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        let inner = Arc::clone(&self.inner);
+        let incident_id = *incident_id;
+        self.execute(move || {
+            Box::pin(async move { inner.get_escalation_state(&incident_id).await })
+        })
+        .await
+    }
+
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
+        let inner = Arc::clone(&self.inner);
+        self.execute(move || Box::pin(async move { inner.list_escalation_states().await }))
+            .await
+    }
 }
 
 /// Wrapper for AppError to implement std::error::Error
@@ -229,6 +346,15 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn query_incidents(
+            &self,
+            _filter: &IncidentFilter,
+            _cursor: Option<&str>,
+            _limit: u32,
+        ) -> Result<IncidentPage> {
+            Ok(IncidentPage::default())
+        }
+
         async fn count_incidents(&self, _filter: &IncidentFilter) -> Result<u64> {
             Ok(0)
         }
@@ -236,6 +362,75 @@ mod tests {
         async fn find_by_fingerprint(&self, _fingerprint: &str) -> Result<Vec<Incident>> {
             Ok(vec![])
         }
+
+        async fn get_incident_events(
+            &self,
+            _id: &Uuid,
+        ) -> Result<Vec<crate::state::IncidentEvent>> {
+            Ok(vec![])
+        }
+
+        async fn replace_incident_events(
+            &self,
+            _id: &Uuid,
+            _events: &[crate::state::IncidentEvent],
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_postmortem(&self, _postmortem: &crate::postmortem::PostMortem) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_postmortem(&self, _id: &Uuid) -> Result<Option<crate::postmortem::PostMortem>> {
+            Ok(None)
+        }
+
+        async fn list_postmortems(
+            &self,
+            _incident_id: Option<&Uuid>,
+        ) -> Result<Vec<crate::postmortem::PostMortem>> {
+            Ok(vec![])
+        }
+
+        async fn save_playbook_execution(
+            &self,
+            _execution: &crate::models::PlaybookExecution,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_playbook_execution(
+            &self,
+            _id: &Uuid,
+        ) -> Result<Option<crate::models::PlaybookExecution>> {
+            Ok(None)
+        }
+
+        async fn list_playbook_executions(
+            &self,
+            _incident_id: Option<&Uuid>,
+        ) -> Result<Vec<crate::models::PlaybookExecution>> {
+            Ok(vec![])
+        }
+
+        async fn save_escalation_state(
+            &self,
+            _state: &crate::escalation::EscalationState,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn get_escalation_state(
+            &self,
+            _incident_id: &Uuid,
+        ) -> Result<Option<crate::escalation::EscalationState>> {
+            Ok(None)
+        }
+
+        async fn list_escalation_states(&self) -> Result<Vec<crate::escalation::EscalationState>> {
+            Ok(vec![])
+        }
     }
 
     #[tokio::test]
@@ -0,0 +1,223 @@
+//! Versioned binary encoding of incidents for the embedded stores
+//!
+//! Sled and redb store incidents as raw bytes. Bincode, which they used
+//! originally, is not self-describing: a field added to [`Incident`] changes
+//! the layout, and `#[serde(default)]` cannot fill it in when an older record
+//! is read. Records are now written in an envelope that names their format,
+//! and records written before the envelope existed are decoded with the
+//! layout they were written in.
+
+use crate::error::{AppError, Result};
+use crate::models::{
+    Incident, IncidentState, IncidentType, Note, Resolution, Severity, TimelineEvent,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Marks an enveloped record
+///
+/// Pre-envelope records are bincode and start with the length prefix of the
+/// incident ID (16 as a little-endian u64), so they never start with this.
+const MAGIC: &[u8; 4] = b"LLMI";
+
+/// Envelope format: JSON body, so fields added with `#[serde(default)]` read
+/// back from older records
+const FORMAT_JSON: u8 = 1;
+
+/// Incident layout written by the original bincode encoding, before
+/// `correlation_group_id`, `legal_hold` and `version` were added
+#[derive(Serialize, Deserialize)]
+struct LegacyIncident {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    state: IncidentState,
+    severity: Severity,
+    incident_type: IncidentType,
+    source: String,
+    title: String,
+    description: String,
+    affected_resources: Vec<String>,
+    labels: HashMap<String, String>,
+    related_incidents: Vec<Uuid>,
+    active_playbook: Option<Uuid>,
+    resolution: Option<Resolution>,
+    timeline: Vec<TimelineEvent>,
+    assignees: Vec<String>,
+    notes: Vec<Note>,
+    fingerprint: Option<String>,
+    correlation_score: Option<f64>,
+}
+
+impl From<LegacyIncident> for Incident {
+    fn from(legacy: LegacyIncident) -> Self {
+        Self {
+            id: legacy.id,
+            created_at: legacy.created_at,
+            updated_at: legacy.updated_at,
+            state: legacy.state,
+            severity: legacy.severity,
+            incident_type: legacy.incident_type,
+            source: legacy.source,
+            title: legacy.title,
+            description: legacy.description,
+            affected_resources: legacy.affected_resources,
+            labels: legacy.labels,
+            related_incidents: legacy.related_incidents,
+            active_playbook: legacy.active_playbook,
+            resolution: legacy.resolution,
+            timeline: legacy.timeline,
+            assignees: legacy.assignees,
+            notes: legacy.notes,
+            fingerprint: legacy.fingerprint,
+            correlation_score: legacy.correlation_score,
+            correlation_group_id: None,
+            legal_hold: None,
+            version: 0,
+        }
+    }
+}
+
+/// Encode an incident in the original bincode layout, as databases written
+/// before the envelope hold it
+#[cfg(test)]
+pub(crate) fn encode_legacy_incident(incident: &Incident) -> Vec<u8> {
+    let incident = incident.clone();
+    bincode::serialize(&LegacyIncident {
+        id: incident.id,
+        created_at: incident.created_at,
+        updated_at: incident.updated_at,
+        state: incident.state,
+        severity: incident.severity,
+        incident_type: incident.incident_type,
+        source: incident.source,
+        title: incident.title,
+        description: incident.description,
+        affected_resources: incident.affected_resources,
+        labels: incident.labels,
+        related_incidents: incident.related_incidents,
+        active_playbook: incident.active_playbook,
+        resolution: incident.resolution,
+        timeline: incident.timeline,
+        assignees: incident.assignees,
+        notes: incident.notes,
+        fingerprint: incident.fingerprint,
+        correlation_score: incident.correlation_score,
+    })
+    .unwrap()
+}
+
+/// Encode an incident in the current envelope format
+pub fn encode_incident(incident: &Incident) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(256);
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(FORMAT_JSON);
+    serde_json::to_writer(&mut bytes, incident)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize incident: {}", e)))?;
+    Ok(bytes)
+}
+
+/// Decode an incident written by [`encode_incident`] or by the original
+/// bincode encoding
+pub fn decode_incident(bytes: &[u8]) -> Result<Incident> {
+    let Some(body) = bytes.strip_prefix(MAGIC) else {
+        return bincode::deserialize::<LegacyIncident>(bytes)
+            .map(Incident::from)
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to deserialize legacy incident: {}", e))
+            });
+    };
+
+    match body.split_first() {
+        Some((&FORMAT_JSON, json)) => serde_json::from_slice(json).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize incident: {}", e))
+        }),
+        Some((format, _)) => Err(AppError::Internal(format!(
+            "Unknown incident encoding format {}",
+            format
+        ))),
+        None => Err(AppError::Internal("Truncated incident record".to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EventType;
+
+    fn legacy_incident() -> LegacyIncident {
+        let incident = Incident::new(
+            "monitoring".to_string(),
+            "Disk full".to_string(),
+            "Disk usage above 95%".to_string(),
+            Severity::P1,
+            IncidentType::Infrastructure,
+        );
+
+        LegacyIncident {
+            id: incident.id,
+            created_at: incident.created_at,
+            updated_at: incident.updated_at,
+            state: IncidentState::Investigating,
+            severity: incident.severity,
+            incident_type: incident.incident_type,
+            source: incident.source,
+            title: incident.title,
+            description: incident.description,
+            affected_resources: vec!["db-01".to_string()],
+            labels: HashMap::from([("team".to_string(), "storage".to_string())]),
+            related_incidents: Vec::new(),
+            active_playbook: None,
+            resolution: None,
+            timeline: vec![TimelineEvent {
+                timestamp: incident.created_at,
+                event_type: EventType::Created,
+                actor: "system".to_string(),
+                description: "Incident created".to_string(),
+                metadata: HashMap::new(),
+            }],
+            assignees: vec!["oncall".to_string()],
+            notes: Vec::new(),
+            fingerprint: Some("disk-full-db-01".to_string()),
+            correlation_score: Some(0.5),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut incident: Incident = legacy_incident().into();
+        incident.version = 7;
+        incident.correlation_group_id = Some(Uuid::new_v4());
+        incident.place_legal_hold("audit".to_string(), "legal".to_string());
+
+        let bytes = encode_incident(&incident).unwrap();
+        assert_eq!(decode_incident(&bytes).unwrap(), incident);
+    }
+
+    #[test]
+    fn test_decodes_record_written_before_envelope() {
+        let legacy = legacy_incident();
+        let bytes = bincode::serialize(&legacy).unwrap();
+
+        let incident = decode_incident(&bytes).unwrap();
+        assert_eq!(incident.id, legacy.id);
+        assert_eq!(incident.state, IncidentState::Investigating);
+        assert_eq!(incident.labels["team"], "storage");
+        assert_eq!(incident.timeline.len(), 1);
+        assert_eq!(incident.fingerprint.as_deref(), Some("disk-full-db-01"));
+        assert_eq!(incident.correlation_score, Some(0.5));
+        assert_eq!(incident.version, 0);
+        assert!(incident.correlation_group_id.is_none());
+        assert!(incident.legal_hold.is_none());
+    }
+
+    #[test]
+    fn test_rejects_unknown_format() {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(FORMAT_JSON + 1);
+        assert!(decode_incident(&bytes).is_err());
+        assert!(decode_incident(MAGIC).is_err());
+    }
+}
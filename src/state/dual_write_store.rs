@@ -0,0 +1,280 @@
+//! Dual-write wrapper used during a cutover between state backends.
+
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::event_log::IncidentEvent;
+use crate::state::{IncidentFilter, IncidentPage, IncidentStore};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Store that mirrors every write to a secondary backend
+///
+/// The primary stays the source of truth: all reads are served from it and
+/// its errors are returned to the caller. Writes that succeed on the primary
+/// are then replayed on the secondary. A failed secondary write is logged and
+/// counted but does not fail the request; `llm-im-cli migrate --verify-only`
+/// reports any drift before the backends are switched over.
+pub struct DualWriteStore {
+    primary: Arc<dyn IncidentStore>,
+    secondary: Arc<dyn IncidentStore>,
+    secondary_failures: AtomicU64,
+}
+
+impl DualWriteStore {
+    /// Create a new dual-write store
+    pub fn new(primary: Arc<dyn IncidentStore>, secondary: Arc<dyn IncidentStore>) -> Self {
+        Self {
+            primary,
+            secondary,
+            secondary_failures: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the primary store
+    pub fn primary(&self) -> &Arc<dyn IncidentStore> {
+        &self.primary
+    }
+
+    /// Get the secondary store
+    pub fn secondary(&self) -> &Arc<dyn IncidentStore> {
+        &self.secondary
+    }
+
+    /// Number of writes that failed on the secondary store
+    pub fn secondary_failures(&self) -> u64 {
+        self.secondary_failures.load(Ordering::Relaxed)
+    }
+
+    fn mirror_failed(&self, operation: &str, id: &Uuid, error: AppError) {
+        self.secondary_failures.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            id = %id,
+            operation,
+            error = %error,
+            "Dual-write to secondary store failed"
+        );
+    }
+}
+
+#[async_trait]
+impl IncidentStore for DualWriteStore {
+    async fn save_incident(&self, incident: &Incident) -> Result<()> {
+        self.primary.save_incident(incident).await?;
+
+        if let Err(e) = self.secondary.save_incident(incident).await {
+            self.mirror_failed("save_incident", &incident.id, e);
+        }
+        Ok(())
+    }
+
+    async fn get_incident(&self, id: &Uuid) -> Result<Option<Incident>> {
+        self.primary.get_incident(id).await
+    }
+
+    async fn update_incident(&self, incident: &Incident) -> Result<()> {
+        self.primary.update_incident(incident).await?;
+
+        // The secondary may not have seen earlier writes yet, so mirror the
+        // result of the compare-and-swap rather than repeating it
+        let mut stored = incident.clone();
+        stored.version += 1;
+        if let Err(e) = self.secondary.save_incident(&stored).await {
+            self.mirror_failed("update_incident", &incident.id, e);
+        }
+        Ok(())
+    }
+
+    async fn delete_incident(&self, id: &Uuid) -> Result<()> {
+        self.primary.delete_incident(id).await?;
+
+        match self.secondary.delete_incident(id).await {
+            Ok(()) | Err(AppError::NotFound(_)) => {}
+            Err(e) => self.mirror_failed("delete_incident", id, e),
+        }
+        Ok(())
+    }
+
+    async fn list_incidents(
+        &self,
+        filter: &IncidentFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<Incident>> {
+        self.primary.list_incidents(filter, page, page_size).await
+    }
+
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        self.primary.query_incidents(filter, cursor, limit).await
+    }
+
+    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
+        self.primary.count_incidents(filter).await
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
+        self.primary.find_by_fingerprint(fingerprint).await
+    }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        self.primary.get_incident_events(id).await
+    }
+
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        self.primary.replace_incident_events(id, events).await?;
+
+        if let Err(e) = self.secondary.replace_incident_events(id, events).await {
+            self.mirror_failed("replace_incident_events", id, e);
+        }
+        Ok(())
+    }
+
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        self.primary.save_postmortem(postmortem).await?;
+
+        if let Err(e) = self.secondary.save_postmortem(postmortem).await {
+            self.mirror_failed("save_postmortem", &postmortem.id, e);
+        }
+        Ok(())
+    }
+
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        self.primary.get_postmortem(id).await
+    }
+
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
+        self.primary.list_postmortems(incident_id).await
+    }
+
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        self.primary.save_playbook_execution(execution).await?;
+
+        if let Err(e) = self.secondary.save_playbook_execution(execution).await {
+            self.mirror_failed("save_playbook_execution", &execution.id, e);
+        }
+        Ok(())
+    }
+
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
+        self.primary.get_playbook_execution(id).await
+    }
+
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        self.primary.list_playbook_executions(incident_id).await
+    }
+
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        self.primary.save_escalation_state(state).await?;
+
+        if let Err(e) = self.secondary.save_escalation_state(state).await {
+            self.mirror_failed("save_escalation_state", &state.incident_id, e);
+        }
+        Ok(())
+    }
+
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        self.primary.get_escalation_state(incident_id).await
+    }
+
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
+        self.primary.list_escalation_states().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IncidentState, IncidentType, Severity};
+    use crate::state::InMemoryStore;
+
+    fn create_store() -> (DualWriteStore, Arc<dyn IncidentStore>, Arc<dyn IncidentStore>) {
+        let primary: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let secondary: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        (
+            DualWriteStore::new(primary.clone(), secondary.clone()),
+            primary,
+            secondary,
+        )
+    }
+
+    fn create_incident() -> Incident {
+        Incident::new(
+            "test-source".to_string(),
+            "Dual Write".to_string(),
+            "Description".to_string(),
+            Severity::P1,
+            IncidentType::Application,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_writes_are_mirrored() {
+        let (store, primary, secondary) = create_store();
+
+        let mut incident = create_incident();
+        store.save_incident(&incident).await.unwrap();
+
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+
+        for backend in [&primary, &secondary] {
+            let stored = backend.get_incident(&incident.id).await.unwrap().unwrap();
+            assert_eq!(stored.state, IncidentState::Investigating);
+            assert_eq!(stored.version, 1);
+        }
+
+        store.delete_incident(&incident.id).await.unwrap();
+        assert!(primary.get_incident(&incident.id).await.unwrap().is_none());
+        assert!(secondary.get_incident(&incident.id).await.unwrap().is_none());
+        assert_eq!(store.secondary_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_mirrors_incident_missing_from_secondary() {
+        let (store, primary, secondary) = create_store();
+
+        // Written before dual-write was enabled
+        let mut incident = create_incident();
+        primary.save_incident(&incident).await.unwrap();
+
+        incident.update_state(IncidentState::Resolved, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+
+        let mirrored = secondary.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(mirrored.state, IncidentState::Resolved);
+        assert_eq!(mirrored.version, 1);
+
+        // Deleting is fine even if the secondary never had it
+        let unmirrored = create_incident();
+        primary.save_incident(&unmirrored).await.unwrap();
+        store.delete_incident(&unmirrored.id).await.unwrap();
+        assert_eq!(store.secondary_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_primary_conflict_is_not_mirrored() {
+        let (store, _primary, secondary) = create_store();
+
+        let incident = create_incident();
+        store.save_incident(&incident).await.unwrap();
+        store.update_incident(&incident).await.unwrap();
+
+        // Stale version
+        let result = store.update_incident(&incident).await;
+        assert!(matches!(result, Err(AppError::VersionConflict { .. })));
+
+        let mirrored = secondary.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(mirrored.version, 1);
+    }
+}
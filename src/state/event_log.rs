@@ -0,0 +1,558 @@
+//! Append-only incident event log
+//!
+//! Every write that goes through an [`IncidentStore`](crate::state::IncidentStore)
+//! is recorded as one or more typed [`IncidentEvent`]s. Events are never
+//! modified or removed once appended, so any incident can be rebuilt as it
+//! looked at an arbitrary point in time by replaying its log.
+
+use crate::models::{
+    Incident, IncidentState, IncidentType, LegalHold, Note, Resolution, Severity, TimelineEvent,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A single entry in an incident's event log
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IncidentEvent {
+    /// Incident the event belongs to
+    pub incident_id: Uuid,
+
+    /// Position in the incident's log (starting at 0)
+    pub sequence: u64,
+
+    /// When the event was appended to the log
+    pub recorded_at: DateTime<Utc>,
+
+    /// The incident's `updated_at` after the change
+    pub occurred_at: DateTime<Utc>,
+
+    /// The incident's concurrency version after the change
+    #[serde(default)]
+    pub version: u64,
+
+    /// What changed
+    pub payload: IncidentEventPayload,
+}
+
+impl IncidentEvent {
+    /// Create a new event stamped with the current time
+    pub fn new(
+        incident_id: Uuid,
+        sequence: u64,
+        occurred_at: DateTime<Utc>,
+        payload: IncidentEventPayload,
+    ) -> Self {
+        Self {
+            incident_id,
+            sequence,
+            recorded_at: Utc::now(),
+            occurred_at,
+            version: 0,
+            payload,
+        }
+    }
+
+    /// Set the incident version the event produced
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+}
+
+/// Typed change applied to an incident
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IncidentEventPayload {
+    /// Incident was created (full snapshot)
+    Created { incident: Box<Incident> },
+
+    /// Lifecycle state changed
+    StateChanged {
+        from: IncidentState,
+        to: IncidentState,
+    },
+
+    /// Severity changed
+    SeverityChanged { from: Severity, to: Severity },
+
+    /// Title, description, type or source changed
+    DetailsChanged {
+        title: Option<String>,
+        description: Option<String>,
+        incident_type: Option<IncidentType>,
+        source: Option<String>,
+    },
+
+    /// Assignees replaced
+    AssigneesChanged { assignees: Vec<String> },
+
+    /// Labels replaced
+    LabelsChanged { labels: HashMap<String, String> },
+
+    /// Affected resources replaced
+    AffectedResourcesChanged { resources: Vec<String> },
+
+    /// Related incidents replaced
+    RelatedIncidentsChanged { related: Vec<Uuid> },
+
+    /// Active playbook set or cleared
+    PlaybookChanged { playbook_id: Option<Uuid> },
+
+    /// Resolution recorded, amended or cleared
+    ResolutionChanged { resolution: Option<Resolution> },
+
+    /// Timeline events appended
+    TimelineAppended { events: Vec<TimelineEvent> },
+
+    /// Timeline rewritten (anything other than an append)
+    TimelineReplaced { timeline: Vec<TimelineEvent> },
+
+    /// Notes appended
+    NotesAppended { notes: Vec<Note> },
+
+    /// Notes rewritten (anything other than an append)
+    NotesReplaced { notes: Vec<Note> },
+
+    /// Fingerprint set or cleared
+    FingerprintChanged { fingerprint: Option<String> },
+
+    /// Correlation score set or cleared
+    CorrelationScoreChanged { score: Option<f64> },
+
+    /// Correlation group set or cleared
+    CorrelationGroupChanged { group_id: Option<Uuid> },
+
+    /// Legal hold placed or released
+    LegalHoldChanged { hold: Option<LegalHold> },
+
+    /// Incident was deleted from the store
+    Deleted,
+}
+
+impl IncidentEventPayload {
+    /// Compute the events that turn `before` into `after`
+    ///
+    /// Returns a single `Created` snapshot when there is no previous version.
+    pub fn diff(before: Option<&Incident>, after: &Incident) -> Vec<Self> {
+        let Some(before) = before else {
+            return vec![Self::Created {
+                incident: Box::new(after.clone()),
+            }];
+        };
+
+        let mut events = Vec::new();
+
+        if before.state != after.state {
+            events.push(Self::StateChanged {
+                from: before.state.clone(),
+                to: after.state.clone(),
+            });
+        }
+
+        if before.severity != after.severity {
+            events.push(Self::SeverityChanged {
+                from: before.severity,
+                to: after.severity,
+            });
+        }
+
+        if before.title != after.title
+            || before.description != after.description
+            || before.incident_type != after.incident_type
+            || before.source != after.source
+        {
+            events.push(Self::DetailsChanged {
+                title: (before.title != after.title).then(|| after.title.clone()),
+                description: (before.description != after.description)
+                    .then(|| after.description.clone()),
+                incident_type: (before.incident_type != after.incident_type)
+                    .then(|| after.incident_type.clone()),
+                source: (before.source != after.source).then(|| after.source.clone()),
+            });
+        }
+
+        if before.assignees != after.assignees {
+            events.push(Self::AssigneesChanged {
+                assignees: after.assignees.clone(),
+            });
+        }
+
+        if before.labels != after.labels {
+            events.push(Self::LabelsChanged {
+                labels: after.labels.clone(),
+            });
+        }
+
+        if before.affected_resources != after.affected_resources {
+            events.push(Self::AffectedResourcesChanged {
+                resources: after.affected_resources.clone(),
+            });
+        }
+
+        if before.related_incidents != after.related_incidents {
+            events.push(Self::RelatedIncidentsChanged {
+                related: after.related_incidents.clone(),
+            });
+        }
+
+        if before.active_playbook != after.active_playbook {
+            events.push(Self::PlaybookChanged {
+                playbook_id: after.active_playbook,
+            });
+        }
+
+        if before.resolution != after.resolution {
+            events.push(Self::ResolutionChanged {
+                resolution: after.resolution.clone(),
+            });
+        }
+
+        if before.timeline != after.timeline {
+            events.push(if after.timeline.starts_with(&before.timeline) {
+                Self::TimelineAppended {
+                    events: after.timeline[before.timeline.len()..].to_vec(),
+                }
+            } else {
+                Self::TimelineReplaced {
+                    timeline: after.timeline.clone(),
+                }
+            });
+        }
+
+        if before.notes != after.notes {
+            events.push(if after.notes.starts_with(&before.notes) {
+                Self::NotesAppended {
+                    notes: after.notes[before.notes.len()..].to_vec(),
+                }
+            } else {
+                Self::NotesReplaced {
+                    notes: after.notes.clone(),
+                }
+            });
+        }
+
+        if before.fingerprint != after.fingerprint {
+            events.push(Self::FingerprintChanged {
+                fingerprint: after.fingerprint.clone(),
+            });
+        }
+
+        if before.correlation_score != after.correlation_score {
+            events.push(Self::CorrelationScoreChanged {
+                score: after.correlation_score,
+            });
+        }
+
+        if before.correlation_group_id != after.correlation_group_id {
+            events.push(Self::CorrelationGroupChanged {
+                group_id: after.correlation_group_id,
+            });
+        }
+
+        if before.legal_hold != after.legal_hold {
+            events.push(Self::LegalHoldChanged {
+                hold: after.legal_hold.clone(),
+            });
+        }
+
+        events
+    }
+
+    /// Apply this change to the rebuilt incident
+    fn apply(&self, incident: &mut Option<Incident>, occurred_at: DateTime<Utc>) {
+        if let Self::Created { incident: snapshot } = self {
+            *incident = Some(snapshot.as_ref().clone());
+            return;
+        }
+        if let Self::Deleted = self {
+            *incident = None;
+            return;
+        }
+
+        let Some(incident) = incident.as_mut() else {
+            return;
+        };
+
+        match self {
+            Self::StateChanged { to, .. } => incident.state = to.clone(),
+            Self::SeverityChanged { to, .. } => incident.severity = *to,
+            Self::DetailsChanged {
+                title,
+                description,
+                incident_type,
+                source,
+            } => {
+                if let Some(title) = title {
+                    incident.title = title.clone();
+                }
+                if let Some(description) = description {
+                    incident.description = description.clone();
+                }
+                if let Some(incident_type) = incident_type {
+                    incident.incident_type = incident_type.clone();
+                }
+                if let Some(source) = source {
+                    incident.source = source.clone();
+                }
+            }
+            Self::AssigneesChanged { assignees } => incident.assignees = assignees.clone(),
+            Self::LabelsChanged { labels } => incident.labels = labels.clone(),
+            Self::AffectedResourcesChanged { resources } => {
+                incident.affected_resources = resources.clone()
+            }
+            Self::RelatedIncidentsChanged { related } => {
+                incident.related_incidents = related.clone()
+            }
+            Self::PlaybookChanged { playbook_id } => incident.active_playbook = *playbook_id,
+            Self::ResolutionChanged { resolution } => incident.resolution = resolution.clone(),
+            Self::TimelineAppended { events } => incident.timeline.extend(events.iter().cloned()),
+            Self::TimelineReplaced { timeline } => incident.timeline = timeline.clone(),
+            Self::NotesAppended { notes } => incident.notes.extend(notes.iter().cloned()),
+            Self::NotesReplaced { notes } => incident.notes = notes.clone(),
+            Self::FingerprintChanged { fingerprint } => incident.fingerprint = fingerprint.clone(),
+            Self::CorrelationScoreChanged { score } => incident.correlation_score = *score,
+            Self::CorrelationGroupChanged { group_id } => {
+                incident.correlation_group_id = *group_id
+            }
+            Self::LegalHoldChanged { hold } => incident.legal_hold = hold.clone(),
+            Self::Created { .. } | Self::Deleted => unreachable!(),
+        }
+
+        incident.updated_at = occurred_at;
+    }
+}
+
+/// Snapshot of an incident that was stored before its event log existed
+///
+/// It is dated at the incident's last update, so replaying to any time since
+/// then yields the stored record rather than nothing.
+fn existing_snapshot(incident: &Incident, sequence: u64) -> IncidentEvent {
+    let mut event = IncidentEvent::new(
+        incident.id,
+        sequence,
+        incident.updated_at,
+        IncidentEventPayload::Created {
+            incident: Box::new(incident.clone()),
+        },
+    )
+    .with_version(incident.version);
+    event.recorded_at = incident.updated_at;
+    event
+}
+
+/// Build the events to append for a write, numbering them from `next_sequence`
+///
+/// An incident that exists but has an empty log (`next_sequence` is 0) was
+/// stored before the event log existed; its previous state is recorded as a
+/// snapshot first.
+pub fn events_for_write(
+    before: Option<&Incident>,
+    after: &Incident,
+    next_sequence: u64,
+) -> Vec<IncidentEvent> {
+    let mut events = Vec::new();
+    if let (Some(before), 0) = (before, next_sequence) {
+        events.push(existing_snapshot(before, 0));
+    }
+
+    let first = next_sequence + events.len() as u64;
+    events.extend(
+        IncidentEventPayload::diff(before, after)
+            .into_iter()
+            .enumerate()
+            .map(|(i, payload)| {
+                IncidentEvent::new(after.id, first + i as u64, after.updated_at, payload)
+                    .with_version(after.version)
+            }),
+    );
+    events
+}
+
+/// Build the events to append for deleting `before`, numbering them from
+/// `next_sequence`
+pub fn events_for_delete(before: &Incident, next_sequence: u64) -> Vec<IncidentEvent> {
+    let mut events = Vec::new();
+    if next_sequence == 0 {
+        events.push(existing_snapshot(before, 0));
+    }
+
+    events.push(
+        IncidentEvent::new(
+            before.id,
+            next_sequence + events.len() as u64,
+            Utc::now(),
+            IncidentEventPayload::Deleted,
+        )
+        .with_version(before.version),
+    );
+    events
+}
+
+/// Rebuild an incident by replaying its log
+///
+/// Only events recorded at or before `at` are applied; pass `None` to replay
+/// the full log. Returns `None` if the incident did not exist at that time.
+pub fn replay<'a, I>(events: I, at: Option<DateTime<Utc>>) -> Option<Incident>
+where
+    I: IntoIterator<Item = &'a IncidentEvent>,
+{
+    let mut incident = None;
+
+    for event in events {
+        if at.is_some_and(|at| event.recorded_at > at) {
+            break;
+        }
+        event.payload.apply(&mut incident, event.occurred_at);
+        if let Some(incident) = incident.as_mut() {
+            incident.version = event.version;
+        }
+    }
+
+    incident
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ResolutionMethod;
+
+    fn test_incident() -> Incident {
+        Incident::new(
+            "test-source".to_string(),
+            "Test Incident".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Application,
+        )
+    }
+
+    #[test]
+    fn test_diff_without_previous_is_snapshot() {
+        let incident = test_incident();
+        let events = events_for_write(None, &incident, 0);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0].payload, IncidentEventPayload::Created { .. }));
+    }
+
+    #[test]
+    fn test_diff_produces_typed_events() {
+        let before = test_incident();
+        let mut after = before.clone();
+        after.severity = Severity::P0;
+        after.update_state(IncidentState::Investigating, "oncall".to_string());
+        after.assignees = vec!["oncall".to_string()];
+
+        let payloads = IncidentEventPayload::diff(Some(&before), &after);
+
+        assert!(payloads.contains(&IncidentEventPayload::SeverityChanged {
+            from: Severity::P2,
+            to: Severity::P0,
+        }));
+        assert!(payloads.contains(&IncidentEventPayload::StateChanged {
+            from: IncidentState::Detected,
+            to: IncidentState::Investigating,
+        }));
+        assert!(payloads
+            .iter()
+            .any(|p| matches!(p, IncidentEventPayload::TimelineAppended { events } if events.len() == 1)));
+        assert!(IncidentEventPayload::diff(Some(&after), &after).is_empty());
+    }
+
+    #[test]
+    fn test_replay_rebuilds_each_version() {
+        let v1 = test_incident();
+        let mut log = events_for_write(None, &v1, 0);
+
+        let mut v2 = v1.clone();
+        v2.update_state(IncidentState::Investigating, "oncall".to_string());
+        v2.add_note("oncall".to_string(), "Looking into it".to_string());
+        v2.version = 1;
+        log.extend(events_for_write(Some(&v1), &v2, log.len() as u64));
+        let checkpoint = log.last().unwrap().recorded_at;
+
+        let mut v3 = v2.clone();
+        v3.version = 2;
+        v3.resolve(
+            "oncall".to_string(),
+            ResolutionMethod::Manual,
+            "Fixed".to_string(),
+            None,
+        );
+        // Make sure v3's events are recorded strictly after the checkpoint
+        log.extend(
+            events_for_write(Some(&v2), &v3, log.len() as u64)
+                .into_iter()
+                .map(|mut e| {
+                    e.recorded_at = checkpoint + chrono::Duration::seconds(1);
+                    e
+                }),
+        );
+
+        let at_checkpoint = replay(&log, Some(checkpoint)).unwrap();
+        assert_eq!(at_checkpoint.state, IncidentState::Investigating);
+        assert_eq!(at_checkpoint.notes.len(), 1);
+        assert_eq!(at_checkpoint.timeline, v2.timeline);
+        assert_eq!(at_checkpoint.version, 1);
+
+        let latest = replay(&log, None).unwrap();
+        assert_eq!(latest.state, IncidentState::Resolved);
+        assert_eq!(latest.version, v3.version);
+        assert_eq!(latest.resolution, v3.resolution);
+        assert_eq!(latest.updated_at, v3.updated_at);
+
+        assert!(replay(&log, Some(v1.created_at - chrono::Duration::seconds(1))).is_none());
+    }
+
+    #[test]
+    fn test_replay_after_delete() {
+        let incident = test_incident();
+        let mut log = events_for_write(None, &incident, 0);
+        log.push(IncidentEvent::new(
+            incident.id,
+            1,
+            Utc::now(),
+            IncidentEventPayload::Deleted,
+        ));
+
+        assert!(replay(&log, None).is_none());
+        assert!(replay(&log[..1], None).is_some());
+    }
+
+    #[test]
+    fn test_first_write_of_existing_incident_records_snapshot() {
+        let mut before = test_incident();
+        before.updated_at -= chrono::Duration::minutes(5);
+        let mut after = before.clone();
+        after.update_state(IncidentState::Investigating, "oncall".to_string());
+        after.version = 1;
+
+        // Stored before the event log existed, so its log is empty
+        let log = events_for_write(Some(&before), &after, 0);
+        assert!(matches!(
+            log[0].payload,
+            IncidentEventPayload::Created { .. }
+        ));
+        assert_eq!(log[0].recorded_at, before.updated_at);
+        assert!(log.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+
+        let earlier = replay(&log, Some(before.updated_at)).unwrap();
+        assert_eq!(earlier.state, IncidentState::Detected);
+        assert_eq!(
+            replay(&log, None).unwrap().state,
+            IncidentState::Investigating
+        );
+
+        // Later writes only record the change
+        assert!(!events_for_write(Some(&after), &after, log.len() as u64)
+            .iter()
+            .any(|e| matches!(e.payload, IncidentEventPayload::Created { .. })));
+
+        let deleted = events_for_delete(&before, 0);
+        assert_eq!(deleted.len(), 2);
+        assert!(replay(&deleted[..1], None).is_some());
+        assert!(replay(&deleted, None).is_none());
+    }
+}
@@ -1,10 +1,46 @@
 use crate::config::{StateBackend, StateConfig};
 use crate::error::{AppError, Result};
-use crate::state::{IncidentStore, InMemoryStore, RedisStore, SledStore};
+use crate::state::{
+    DualWriteStore, IncidentStore, InMemoryStore, RedbStore, RedisStore, SledStore,
+};
 use std::sync::Arc;
 
 /// Create an incident store based on configuration
+///
+/// If `dual_write` is set, writes are mirrored to that backend as well.
 pub async fn create_store(config: &StateConfig) -> Result<Arc<dyn IncidentStore>> {
+    if config.dual_write.is_some() {
+        return Ok(create_dual_write_store(config).await?);
+    }
+
+    create_backend(config).await
+}
+
+/// Create a store that mirrors writes to the `dual_write` backend
+pub async fn create_dual_write_store(config: &StateConfig) -> Result<Arc<DualWriteStore>> {
+    let secondary_config = config.dual_write.as_ref().ok_or_else(|| {
+        AppError::Configuration("Dual-write requires a 'dual_write' backend".to_string())
+    })?;
+
+    if secondary_config.dual_write.is_some() {
+        return Err(AppError::Configuration(
+            "Dual-write secondary backend cannot itself use dual-write".to_string(),
+        ));
+    }
+
+    tracing::info!(
+        primary = ?config.backend,
+        secondary = ?secondary_config.backend,
+        "Dual-write enabled"
+    );
+
+    let primary = create_backend(config).await?;
+    let secondary = create_backend(secondary_config).await?;
+    Ok(Arc::new(DualWriteStore::new(primary, secondary)))
+}
+
+/// Create a single incident store backend, ignoring `dual_write`
+pub async fn create_backend(config: &StateConfig) -> Result<Arc<dyn IncidentStore>> {
     match config.backend {
         StateBackend::Sled => {
             let path = config
@@ -34,28 +70,36 @@ pub async fn create_store(config: &StateConfig) -> Result<Arc<dyn IncidentStore>
         }
 
         StateBackend::RedisCluster => {
-            // For now, use standalone Redis with cluster URL
-            // Full cluster support would require redis-cluster crate
-            let redis_url = if !config.redis_cluster_nodes.is_empty() {
-                &config.redis_cluster_nodes[0]
+            let nodes = if !config.redis_cluster_nodes.is_empty() {
+                config.redis_cluster_nodes.clone()
             } else {
-                config.redis_url.as_ref().ok_or_else(|| {
+                vec![config.redis_url.clone().ok_or_else(|| {
                     AppError::Configuration(
                         "RedisCluster backend requires 'redis_cluster_nodes' or 'redis_url' configuration".to_string(),
                     )
-                })?
+                })?]
             };
 
-            tracing::info!(url = %redis_url, "Initializing Redis cluster storage backend");
-            tracing::warn!("Redis cluster support is currently implemented as standalone connection to first node");
+            tracing::info!(nodes = ?nodes, "Initializing Redis cluster storage backend");
 
-            let store = RedisStore::new(redis_url).await?;
+            let store = RedisStore::new_cluster(&nodes).await?;
             Ok(Arc::new(store))
         }
 
         StateBackend::Redb => {
-            tracing::warn!("Redb backend is not yet implemented, falling back to in-memory storage");
-            Ok(Arc::new(InMemoryStore::new()))
+            let path = config
+                .path
+                .as_ref()
+                .ok_or_else(|| {
+                    AppError::Configuration(
+                        "Redb backend requires 'path' configuration".to_string(),
+                    )
+                })?;
+
+            tracing::info!(path = ?path, "Initializing redb storage backend");
+
+            let store = RedbStore::new(path)?;
+            Ok(Arc::new(store))
         }
     }
 }
@@ -81,6 +125,7 @@ mod tests {
             redis_url: None,
             redis_cluster_nodes: vec![],
             pool_size: 10,
+            dual_write: None,
         };
 
         let store = create_store(&config).await.unwrap();
@@ -88,6 +133,23 @@ mod tests {
         assert!(store.count_incidents(&Default::default()).await.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_create_redb_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = StateConfig {
+            backend: StateBackend::Redb,
+            path: Some(temp_dir.path().to_path_buf()),
+            redis_url: None,
+            redis_cluster_nodes: vec![],
+            pool_size: 10,
+            dual_write: None,
+        };
+
+        let store = create_store(&config).await.unwrap();
+        assert!(store.count_incidents(&Default::default()).await.is_ok());
+        assert!(temp_dir.path().join("incidents.redb").exists());
+    }
+
     #[tokio::test]
     async fn test_create_in_memory_store() {
         let store = create_in_memory_store();
@@ -103,6 +165,22 @@ mod tests {
             redis_url: None,
             redis_cluster_nodes: vec![],
             pool_size: 10,
+            dual_write: None,
+        };
+
+        let result = create_store(&config).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_redb_requires_path() {
+        let config = StateConfig {
+            backend: StateBackend::Redb,
+            path: None,
+            redis_url: None,
+            redis_cluster_nodes: vec![],
+            pool_size: 10,
+            dual_write: None,
         };
 
         let result = create_store(&config).await;
@@ -117,9 +195,60 @@ mod tests {
             redis_url: None,
             redis_cluster_nodes: vec![],
             pool_size: 10,
+            dual_write: None,
         };
 
         let result = create_store(&config).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn test_redis_cluster_requires_nodes() {
+        let config = StateConfig {
+            backend: StateBackend::RedisCluster,
+            path: None,
+            redis_url: None,
+            redis_cluster_nodes: vec![],
+            pool_size: 10,
+            dual_write: None,
+        };
+
+        let result = create_store(&config).await;
+        assert!(matches!(result, Err(AppError::Configuration(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_dual_write_store() {
+        let temp_dir = TempDir::new().unwrap();
+        let secondary = StateConfig {
+            backend: StateBackend::Redb,
+            path: Some(temp_dir.path().join("secondary")),
+            redis_url: None,
+            redis_cluster_nodes: vec![],
+            pool_size: 10,
+            dual_write: None,
+        };
+        let config = StateConfig {
+            backend: StateBackend::Sled,
+            path: Some(temp_dir.path().join("primary")),
+            redis_url: None,
+            redis_cluster_nodes: vec![],
+            pool_size: 10,
+            dual_write: Some(Box::new(secondary.clone())),
+        };
+
+        let store = create_store(&config).await.unwrap();
+        let incident = crate::models::Incident::new(
+            "test".to_string(),
+            "Dual Write".to_string(),
+            "Description".to_string(),
+            crate::models::Severity::P2,
+            crate::models::IncidentType::Application,
+        );
+        store.save_incident(&incident).await.unwrap();
+        drop(store);
+
+        let secondary = create_store(&secondary).await.unwrap();
+        assert!(secondary.get_incident(&incident.id).await.unwrap().is_some());
+    }
 }
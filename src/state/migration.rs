@@ -0,0 +1,1043 @@
+//! Migration of incidents, post-mortems, playbook executions and escalation
+//! states between state backends.
+//!
+//! A migration streams every incident out of the source store in
+//! creation-time order with `query_incidents` cursors and saves it into the
+//! target store, which rebuilds the target's fingerprint and filter indexes
+//! as it goes. Each incident's event log is copied with it, so history and
+//! point-in-time reads survive the switch. Playbook executions and
+//! escalation states are copied afterwards, so paused approvals can still be
+//! decided and escalations keep running after cutover. The result is then
+//! verified by comparing counts and an order-independent checksum of both
+//! stores.
+//!
+//! To migrate without downtime, enable dual-write (`[state.dual_write]`)
+//! so new writes reach both backends, run the migration, check that
+//! verification passes and then switch the primary backend.
+
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::state::{
+    IncidentEvent, IncidentFilter, IncidentPage, IncidentSort, IncidentStore, SortDirection,
+    SortField,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Default number of incidents read per page
+pub const DEFAULT_MIGRATION_PAGE_SIZE: u32 = 500;
+
+/// Outcome of a migration run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// Incidents read from the source store
+    pub incidents_read: u64,
+
+    /// Incidents written to the target store
+    pub incidents_written: u64,
+
+    /// Incidents left alone because the target already held the same or a
+    /// newer version and the same event log, e.g. from a dual-write during
+    /// the run
+    pub newer_in_target: u64,
+
+    /// Post-mortems written to the target store
+    pub postmortems_written: u64,
+
+    /// Playbook executions written to the target store
+    pub playbook_executions_written: u64,
+
+    /// Escalation states written to the target store
+    pub escalation_states_written: u64,
+
+    /// Distinct fingerprints checked in the target index
+    pub fingerprints_indexed: u64,
+
+    /// Incidents re-saved because the target fingerprint index missed them
+    pub fingerprints_repaired: u64,
+
+    /// Whether this was a dry run (nothing written)
+    pub dry_run: bool,
+
+    /// Verification of the target against the source
+    pub verification: Option<VerificationReport>,
+}
+
+/// Comparison of the incidents held by two stores
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Incident count reported by the source store
+    pub source_count: u64,
+
+    /// Incident count reported by the target store
+    pub target_count: u64,
+
+    /// Checksum over every incident in the source store
+    pub source_checksum: String,
+
+    /// Checksum over every incident in the target store
+    pub target_checksum: String,
+
+    /// Incidents present in the source but not the target
+    pub missing: Vec<Uuid>,
+
+    /// Incidents present in the target but not the source
+    pub unexpected: Vec<Uuid>,
+
+    /// Incidents whose contents or event logs differ between the stores
+    pub mismatched: Vec<Uuid>,
+
+    /// Playbook executions missing from, extra in or different in the target
+    pub mismatched_executions: Vec<Uuid>,
+
+    /// Incidents whose escalation state is missing from, extra in or
+    /// different in the target
+    pub mismatched_escalations: Vec<Uuid>,
+}
+
+impl VerificationReport {
+    /// Whether both stores hold exactly the same incidents, executions and
+    /// escalation states
+    pub fn is_consistent(&self) -> bool {
+        self.source_count == self.target_count
+            && self.source_checksum == self.target_checksum
+            && self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && self.mismatched.is_empty()
+            && self.mismatched_executions.is_empty()
+            && self.mismatched_escalations.is_empty()
+    }
+}
+
+/// Copies incidents, post-mortems, playbook executions and escalation states
+/// from one store to another
+pub struct StoreMigrator {
+    source: Arc<dyn IncidentStore>,
+    target: Arc<dyn IncidentStore>,
+    page_size: u32,
+    dry_run: bool,
+    include_postmortems: bool,
+    verify: bool,
+}
+
+impl StoreMigrator {
+    /// Create a new migrator
+    pub fn new(source: Arc<dyn IncidentStore>, target: Arc<dyn IncidentStore>) -> Self {
+        Self {
+            source,
+            target,
+            page_size: DEFAULT_MIGRATION_PAGE_SIZE,
+            dry_run: false,
+            include_postmortems: true,
+            verify: true,
+        }
+    }
+
+    /// Set the number of incidents read per page
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Read the source without writing to the target
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Whether to copy post-mortems as well as incidents
+    pub fn with_postmortems(mut self, include_postmortems: bool) -> Self {
+        self.include_postmortems = include_postmortems;
+        self
+    }
+
+    /// Whether to verify the target after copying
+    pub fn with_verification(mut self, verify: bool) -> Self {
+        self.verify = verify;
+        self
+    }
+
+    /// Run the migration
+    ///
+    /// Incidents are saved with their version intact, so a re-run is
+    /// idempotent and optimistic concurrency keeps working after cutover.
+    /// An incident is only written if the target holds an older version, so
+    /// dual-writes that reach the target during the run are not overwritten.
+    /// The target's event log is replaced with the source's whenever they
+    /// differ. Logs of incidents already deleted from the source are not
+    /// copied.
+    pub async fn run(&self) -> Result<MigrationReport> {
+        let mut report = MigrationReport {
+            dry_run: self.dry_run,
+            ..Default::default()
+        };
+
+        let mut fingerprints: HashMap<String, Vec<Uuid>> = HashMap::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self.read_page(cursor.as_deref()).await?;
+
+            for incident in &page.incidents {
+                report.incidents_read += 1;
+
+                if let Some(ref fingerprint) = incident.fingerprint {
+                    fingerprints
+                        .entry(fingerprint.clone())
+                        .or_default()
+                        .push(incident.id);
+                }
+
+                if !self.dry_run {
+                    if self.copy_incident(incident).await? {
+                        report.incidents_written += 1;
+                    } else {
+                        report.newer_in_target += 1;
+                    }
+                }
+            }
+
+            tracing::debug!(
+                read = report.incidents_read,
+                migrated = report.incidents_written,
+                "Migrated incident page"
+            );
+
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        if !self.dry_run {
+            self.rebuild_fingerprint_indexes(&fingerprints, &mut report)
+                .await?;
+
+            if self.include_postmortems {
+                for postmortem in self.source.list_postmortems(None).await? {
+                    self.target.save_postmortem(&postmortem).await?;
+                    report.postmortems_written += 1;
+                }
+            }
+
+            for execution in self.source.list_playbook_executions(None).await? {
+                self.copy_playbook_execution(&execution).await?;
+                report.playbook_executions_written += 1;
+            }
+
+            for state in self.source.list_escalation_states().await? {
+                self.copy_escalation_state(&state).await?;
+                report.escalation_states_written += 1;
+            }
+
+            if self.verify {
+                report.verification = Some(self.verify().await?);
+            }
+        }
+
+        tracing::info!(
+            incidents = report.incidents_written,
+            postmortems = report.postmortems_written,
+            playbook_executions = report.playbook_executions_written,
+            escalation_states = report.escalation_states_written,
+            dry_run = self.dry_run,
+            "State migration finished"
+        );
+
+        Ok(report)
+    }
+
+    /// Compare the incidents, playbook executions and escalation states in
+    /// the source and target stores
+    pub async fn verify(&self) -> Result<VerificationReport> {
+        let source = self.digests(self.source.as_ref()).await?;
+        let target = self.digests(self.target.as_ref()).await?;
+
+        let filter = IncidentFilter::default();
+        let mut report = VerificationReport {
+            source_count: self.source.count_incidents(&filter).await?,
+            target_count: self.target.count_incidents(&filter).await?,
+            source_checksum: checksum(&source),
+            target_checksum: checksum(&target),
+            ..Default::default()
+        };
+
+        for (id, digest) in &source {
+            match target.get(id) {
+                None => report.missing.push(*id),
+                Some(other) if other != digest => report.mismatched.push(*id),
+                Some(_) => {}
+            }
+        }
+        report.unexpected = target
+            .keys()
+            .filter(|id| !source.contains_key(id))
+            .copied()
+            .collect();
+        report.mismatched_executions = differing(
+            &Self::execution_digests(self.source.as_ref()).await?,
+            &Self::execution_digests(self.target.as_ref()).await?,
+        );
+        report.mismatched_escalations = differing(
+            &Self::escalation_digests(self.source.as_ref()).await?,
+            &Self::escalation_digests(self.target.as_ref()).await?,
+        );
+
+        Ok(report)
+    }
+
+    /// Read the next page of a store in creation order
+    ///
+    /// Cursors are positioned by creation time and ID, so incidents created
+    /// during the run neither shift later pages nor get read twice.
+    async fn read_page(&self, cursor: Option<&str>) -> Result<IncidentPage> {
+        Self::page_of(self.source.as_ref(), cursor, self.page_size).await
+    }
+
+    async fn page_of(
+        store: &dyn IncidentStore,
+        cursor: Option<&str>,
+        page_size: u32,
+    ) -> Result<IncidentPage> {
+        let filter = IncidentFilter {
+            sort: IncidentSort::new(SortField::CreatedAt, SortDirection::Asc),
+            ..Default::default()
+        };
+        store.query_incidents(&filter, cursor, page_size).await
+    }
+
+    /// Copy one incident and its event log, returning whether anything was
+    /// written
+    ///
+    /// The incident is only saved if the target holds an older version (or
+    /// different contents at the same version), and the log only if it
+    /// differs from the source's. A dual-write can still reach the target
+    /// between the checks and the writes. The source is read again
+    /// afterwards, and if it has moved on, its latest state (or its
+    /// deletion) is copied over.
+    pub async fn copy_incident(&self, incident: &Incident) -> Result<bool> {
+        let mut incident = incident.clone();
+        let mut written = false;
+
+        loop {
+            let events = self.source.get_incident_events(&incident.id).await?;
+
+            let stale = match self.target.get_incident(&incident.id).await? {
+                Some(current) => {
+                    current.version < incident.version
+                        || (current.version == incident.version && current != incident)
+                }
+                None => true,
+            };
+            if stale {
+                self.target.save_incident(&incident).await?;
+                written = true;
+            }
+
+            // Compared with timestamps, since point-in-time reads use them
+            if self.target.get_incident_events(&incident.id).await? != events {
+                self.target
+                    .replace_incident_events(&incident.id, &events)
+                    .await?;
+                written = true;
+            }
+
+            match self.source.get_incident(&incident.id).await? {
+                Some(latest) => {
+                    let latest_events = self.source.get_incident_events(&incident.id).await?;
+                    if latest == incident && latest_events == events {
+                        return Ok(written);
+                    }
+                    incident = latest;
+                }
+                None => {
+                    match self.target.delete_incident(&incident.id).await {
+                        Ok(()) | Err(AppError::NotFound(_)) => {}
+                        Err(e) => return Err(e),
+                    }
+                    // Keep the source's record of the deletion rather than
+                    // the one the target just appended
+                    let events = self.source.get_incident_events(&incident.id).await?;
+                    self.target
+                        .replace_incident_events(&incident.id, &events)
+                        .await?;
+                    return Ok(written);
+                }
+            }
+        }
+    }
+
+    /// Copy a playbook execution, again if the source moved on meanwhile
+    ///
+    /// Executions carry no version, so the source's copy always wins. An
+    /// update that reaches the source during the save is picked up by
+    /// reading it again.
+    async fn copy_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        let mut execution = execution.clone();
+
+        loop {
+            self.target.save_playbook_execution(&execution).await?;
+
+            match self.source.get_playbook_execution(&execution.id).await? {
+                Some(latest) if value_digest(&latest)? != value_digest(&execution)? => {
+                    execution = latest
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Copy an incident's escalation state, again if the source moved on
+    /// meanwhile (see [`Self::copy_playbook_execution`])
+    async fn copy_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        let mut state = state.clone();
+
+        loop {
+            self.target.save_escalation_state(&state).await?;
+
+            match self.source.get_escalation_state(&state.incident_id).await? {
+                Some(latest) if value_digest(&latest)? != value_digest(&state)? => state = latest,
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    /// Make sure every migrated fingerprint resolves to its incidents
+    ///
+    /// Backends index on save, so this normally finds nothing to do. An
+    /// incident missing from the target index is saved again to re-index it.
+    async fn rebuild_fingerprint_indexes(
+        &self,
+        fingerprints: &HashMap<String, Vec<Uuid>>,
+        report: &mut MigrationReport,
+    ) -> Result<()> {
+        for (fingerprint, ids) in fingerprints {
+            let indexed: HashSet<Uuid> = self
+                .target
+                .find_by_fingerprint(fingerprint)
+                .await?
+                .into_iter()
+                .map(|incident| incident.id)
+                .collect();
+
+            for id in ids.iter().filter(|id| !indexed.contains(id)) {
+                let incident = self.target.get_incident(id).await?.ok_or_else(|| {
+                    AppError::Internal(format!("Migrated incident {} missing from target", id))
+                })?;
+                self.target.save_incident(&incident).await?;
+                report.fingerprints_repaired += 1;
+            }
+
+            report.fingerprints_indexed += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Digest every incident in a store with its event log, keyed by ID
+    async fn digests(&self, store: &dyn IncidentStore) -> Result<BTreeMap<Uuid, String>> {
+        let mut digests = BTreeMap::new();
+        let mut cursor = None;
+
+        loop {
+            let page = Self::page_of(store, cursor.as_deref(), self.page_size).await?;
+            for incident in &page.incidents {
+                let events = store.get_incident_events(&incident.id).await?;
+                digests.insert(
+                    incident.id,
+                    format!("{}:{}", incident_digest(incident)?, events_digest(&events)?),
+                );
+            }
+
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        Ok(digests)
+    }
+
+    /// Digest every playbook execution in a store, keyed by ID
+    async fn execution_digests(store: &dyn IncidentStore) -> Result<BTreeMap<Uuid, String>> {
+        let mut digests = BTreeMap::new();
+        for execution in store.list_playbook_executions(None).await? {
+            digests.insert(execution.id, value_digest(&execution)?);
+        }
+        Ok(digests)
+    }
+
+    /// Digest every escalation state in a store, keyed by incident ID
+    async fn escalation_digests(store: &dyn IncidentStore) -> Result<BTreeMap<Uuid, String>> {
+        let mut digests = BTreeMap::new();
+        for state in store.list_escalation_states().await? {
+            digests.insert(state.incident_id, value_digest(&state)?);
+        }
+        Ok(digests)
+    }
+}
+
+/// SHA-256 of an incident's canonical JSON form
+///
+/// Object keys are sorted so map fields such as `labels` hash the same
+/// regardless of the order a backend returns them in.
+pub fn incident_digest(incident: &Incident) -> Result<String> {
+    value_digest(incident)
+}
+
+/// SHA-256 of any record's canonical JSON form
+fn value_digest<T: Serialize>(record: &T) -> Result<String> {
+    let value = serde_json::to_value(record)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize record: {}", e)))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonicalize(value).to_string().as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// IDs missing from either side or whose digests differ, in order
+fn differing(source: &BTreeMap<Uuid, String>, target: &BTreeMap<Uuid, String>) -> Vec<Uuid> {
+    source
+        .keys()
+        .chain(target.keys())
+        .filter(|id| source.get(id) != target.get(id))
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// SHA-256 of an incident's event log
+///
+/// `recorded_at` is left out: a dual-write appends the same events to both
+/// backends, each stamped with its own clock.
+pub fn events_digest(events: &[IncidentEvent]) -> Result<String> {
+    let mut hasher = Sha256::new();
+    for event in events {
+        let mut value = serde_json::to_value(event).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize incident event: {}", e))
+        })?;
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("recorded_at");
+        }
+        hasher.update(canonicalize(value).to_string().as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Checksum over a set of incident digests
+fn checksum(digests: &BTreeMap<Uuid, String>) -> String {
+    let mut hasher = Sha256::new();
+    for (id, digest) in digests {
+        hasher.update(id.as_bytes());
+        hasher.update(digest.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            serde_json::Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, canonicalize(value)))
+                    .collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(canonicalize).collect())
+        }
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExecutionStatus, IncidentState, IncidentType, Severity};
+    use crate::state::{DualWriteStore, InMemoryStore, SledStore};
+    use tempfile::TempDir;
+
+    fn create_incident(i: usize) -> Incident {
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            format!("Incident {}", i),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Application,
+        );
+        incident.fingerprint = Some(format!("fingerprint-{}", i % 3));
+        incident
+            .labels
+            .insert("team".to_string(), "platform".to_string());
+        incident
+            .labels
+            .insert("env".to_string(), "prod".to_string());
+        incident
+    }
+
+    fn create_execution(incident_id: Uuid, status: ExecutionStatus) -> PlaybookExecution {
+        PlaybookExecution {
+            id: Uuid::new_v4(),
+            playbook_id: Uuid::new_v4(),
+            incident_id,
+            started_at: chrono::Utc::now(),
+            completed_at: None,
+            status,
+            current_step: Some("approve".to_string()),
+            step_results: HashMap::new(),
+            error: None,
+            activated_steps: Vec::new(),
+            approvals: Vec::new(),
+            variables: HashMap::from([("region".to_string(), serde_json::json!("eu-west-1"))]),
+            playbook_revision: 1,
+            playbook_digest: "digest".to_string(),
+        }
+    }
+
+    async fn populate(store: &dyn IncidentStore, count: usize) {
+        for i in 0..count {
+            let incident = create_incident(i);
+            store.save_incident(&incident).await.unwrap();
+            if i % 2 == 0 {
+                crate::state::modify_incident(store, &incident.id, |incident| {
+                    incident.update_state(IncidentState::Investigating, "user".to_string());
+                    Ok(())
+                })
+                .await
+                .unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_between_backends() {
+        let temp_dir = TempDir::new().unwrap();
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+        populate(source.as_ref(), 25).await;
+
+        let report = StoreMigrator::new(source.clone(), target.clone())
+            .with_page_size(7)
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(report.incidents_written, 25);
+        assert_eq!(report.fingerprints_indexed, 3);
+        assert_eq!(report.fingerprints_repaired, 0);
+        let verification = report.verification.unwrap();
+        assert!(verification.is_consistent(), "{:?}", verification);
+        assert_eq!(verification.target_count, 25);
+
+        // Versions survive, so updates keep working after cutover
+        let migrated = source
+            .list_incidents(&IncidentFilter::default(), 0, 1)
+            .await
+            .unwrap();
+        let stored = target.get_incident(&migrated[0].id).await.unwrap().unwrap();
+        assert_eq!(stored.version, migrated[0].version);
+        target.update_incident(&stored).await.unwrap();
+
+        assert_eq!(
+            target
+                .find_by_fingerprint("fingerprint-0")
+                .await
+                .unwrap()
+                .len(),
+            9
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrates_event_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+        populate(source.as_ref(), 4).await;
+
+        StoreMigrator::new(source.clone(), target.clone())
+            .run()
+            .await
+            .unwrap();
+
+        for incident in source
+            .list_incidents(&IncidentFilter::default(), 0, 10)
+            .await
+            .unwrap()
+        {
+            let history = source.get_incident_events(&incident.id).await.unwrap();
+            let migrated = target.get_incident_events(&incident.id).await.unwrap();
+            assert_eq!(migrated.len(), history.len());
+            assert_eq!(
+                events_digest(&migrated).unwrap(),
+                events_digest(&history).unwrap()
+            );
+
+            // Point-in-time reads before the migration still resolve
+            let created_at = history[0].recorded_at;
+            let original = target
+                .get_incident_at(&incident.id, created_at)
+                .await
+                .unwrap();
+            assert_eq!(original.unwrap().version, history[0].version);
+        }
+
+        // Later writes continue the migrated log
+        let investigating = source
+            .list_incidents(&IncidentFilter::default(), 0, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|incident| incident.state == IncidentState::Investigating)
+            .unwrap();
+        let before = target.get_incident_events(&investigating.id).await.unwrap();
+        crate::state::modify_incident(target.as_ref(), &investigating.id, |incident| {
+            incident.severity = Severity::P0;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        let after = target.get_incident_events(&investigating.id).await.unwrap();
+        assert!(after.len() > before.len());
+        assert_eq!(
+            after[before.len()].sequence,
+            before.last().unwrap().sequence + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrates_playbook_executions() {
+        let temp_dir = TempDir::new().unwrap();
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+        populate(source.as_ref(), 2).await;
+
+        let incident_id = source
+            .list_incidents(&IncidentFilter::default(), 0, 1)
+            .await
+            .unwrap()[0]
+            .id;
+        let paused = create_execution(incident_id, ExecutionStatus::WaitingForApproval);
+        let completed = create_execution(incident_id, ExecutionStatus::Completed);
+        source.save_playbook_execution(&paused).await.unwrap();
+        source.save_playbook_execution(&completed).await.unwrap();
+
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+        let report = migrator.run().await.unwrap();
+        assert_eq!(report.playbook_executions_written, 2);
+        assert!(report.verification.unwrap().is_consistent());
+
+        let stored = target
+            .get_playbook_execution(&paused.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, ExecutionStatus::WaitingForApproval);
+        assert_eq!(
+            value_digest(&stored).unwrap(),
+            value_digest(&paused).unwrap()
+        );
+        assert_eq!(
+            target
+                .list_playbook_executions(Some(&incident_id))
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // An execution that moves on in the source shows up as drift
+        let mut resumed = paused.clone();
+        resumed.status = ExecutionStatus::Running;
+        source.save_playbook_execution(&resumed).await.unwrap();
+        let extra = create_execution(incident_id, ExecutionStatus::Failed);
+        target.save_playbook_execution(&extra).await.unwrap();
+
+        let verification = migrator.verify().await.unwrap();
+        assert!(!verification.is_consistent());
+        let mut expected = vec![paused.id, extra.id];
+        expected.sort();
+        assert_eq!(verification.mismatched_executions, expected);
+    }
+
+    #[tokio::test]
+    async fn test_migrates_escalation_states() {
+        let temp_dir = TempDir::new().unwrap();
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+        populate(source.as_ref(), 2).await;
+
+        let incidents = source
+            .list_incidents(&IncidentFilter::default(), 0, 2)
+            .await
+            .unwrap();
+        let active = EscalationState::new(incidents[0].id, Uuid::new_v4(), 5);
+        let mut acknowledged = EscalationState::new(incidents[1].id, Uuid::new_v4(), 5);
+        acknowledged.advance_to_next_level(10);
+        acknowledged.acknowledge("alice".to_string());
+        source.save_escalation_state(&active).await.unwrap();
+        source.save_escalation_state(&acknowledged).await.unwrap();
+
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+        let report = migrator.run().await.unwrap();
+        assert_eq!(report.escalation_states_written, 2);
+        assert!(report.verification.unwrap().is_consistent());
+
+        let stored = target
+            .get_escalation_state(&acknowledged.incident_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.current_level, acknowledged.current_level);
+        assert_eq!(stored.acknowledged_by.as_deref(), Some("alice"));
+        assert_eq!(stored.next_escalation_at, acknowledged.next_escalation_at);
+
+        // An escalation that advances in the source shows up as drift
+        let mut advanced = active.clone();
+        advanced.advance_to_next_level(10);
+        source.save_escalation_state(&advanced).await.unwrap();
+
+        let verification = migrator.verify().await.unwrap();
+        assert!(!verification.is_consistent());
+        assert_eq!(
+            verification.mismatched_escalations,
+            vec![active.incident_id]
+        );
+        assert!(verification.mismatched_executions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_migration_is_idempotent() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 10).await;
+
+        let migrator = StoreMigrator::new(source, target.clone()).with_page_size(4);
+        migrator.run().await.unwrap();
+        let report = migrator.run().await.unwrap();
+
+        assert_eq!(report.incidents_read, 10);
+        assert_eq!(report.incidents_written, 0);
+        assert_eq!(report.newer_in_target, 10);
+        assert!(report.verification.unwrap().is_consistent());
+        assert_eq!(
+            target
+                .find_by_fingerprint("fingerprint-1")
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keeps_dual_write_that_lands_during_batch() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let dual_write = DualWriteStore::new(source.clone(), target.clone());
+        populate(source.as_ref(), 3).await;
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+
+        // The batch read this snapshot, then the application updated the
+        // incident through the dual-write store before the batch saved it
+        let page = migrator.read_page(None).await.unwrap();
+        let snapshot = page.incidents[0].clone();
+        crate::state::modify_incident(&dual_write, &snapshot.id, |incident| {
+            incident.severity = Severity::P0;
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        // The incident itself is kept, but the dual-write only gave the
+        // target a snapshot, so the source's full history is copied over
+        assert!(migrator.copy_incident(&snapshot).await.unwrap());
+        let stored = target.get_incident(&snapshot.id).await.unwrap().unwrap();
+        assert_eq!(stored.severity, Severity::P0);
+        assert_eq!(stored.version, snapshot.version + 1);
+        assert_eq!(
+            events_digest(&target.get_incident_events(&snapshot.id).await.unwrap()).unwrap(),
+            events_digest(&source.get_incident_events(&snapshot.id).await.unwrap()).unwrap()
+        );
+
+        let report = migrator.run().await.unwrap();
+        assert_eq!(report.newer_in_target, 1);
+        assert_eq!(report.incidents_written, 2);
+        assert!(report.verification.unwrap().is_consistent());
+    }
+
+    #[tokio::test]
+    async fn test_recopies_source_that_changed_during_save() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 2).await;
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+
+        let page = migrator.read_page(None).await.unwrap();
+        let (updated, deleted) = (page.incidents[0].clone(), page.incidents[1].clone());
+
+        // Written to the source after the batch read them, with the
+        // dual-write to the target not yet landed
+        crate::state::modify_incident(source.as_ref(), &updated.id, |incident| {
+            incident.severity = Severity::P0;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        source.delete_incident(&deleted.id).await.unwrap();
+
+        assert!(migrator.copy_incident(&updated).await.unwrap());
+        assert!(migrator.copy_incident(&deleted).await.unwrap());
+
+        let stored = target.get_incident(&updated.id).await.unwrap().unwrap();
+        assert_eq!(stored.severity, Severity::P0);
+        assert!(target.get_incident(&deleted.id).await.unwrap().is_none());
+        assert_eq!(
+            target.get_incident_events(&deleted.id).await.unwrap().len(),
+            source.get_incident_events(&deleted.id).await.unwrap().len()
+        );
+        assert!(migrator.verify().await.unwrap().is_consistent());
+    }
+
+    #[tokio::test]
+    async fn test_incidents_created_during_run_do_not_shift_pages() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 6).await;
+        let migrator = StoreMigrator::new(source.clone(), target).with_page_size(4);
+
+        let first = migrator.read_page(None).await.unwrap();
+        source.save_incident(&create_incident(99)).await.unwrap();
+        let second = migrator
+            .read_page(first.next_cursor.as_deref())
+            .await
+            .unwrap();
+
+        let mut ids: Vec<Uuid> = first.incidents.iter().map(|i| i.id).collect();
+        ids.extend(second.incidents.iter().map(|i| i.id));
+        let unique: HashSet<Uuid> = ids.iter().copied().collect();
+        assert_eq!(ids.len(), 7);
+        assert_eq!(unique.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_writes_nothing() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 5).await;
+
+        let report = StoreMigrator::new(source, target.clone())
+            .with_dry_run(true)
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(report.incidents_read, 5);
+        assert_eq!(report.incidents_written, 0);
+        assert!(report.verification.is_none());
+        assert_eq!(
+            target
+                .count_incidents(&IncidentFilter::default())
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_drift() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 3).await;
+
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+        migrator.run().await.unwrap();
+
+        let changed = source
+            .list_incidents(&IncidentFilter::default(), 0, 1)
+            .await
+            .unwrap();
+        crate::state::modify_incident(source.as_ref(), &changed[0].id, |incident| {
+            incident.severity = Severity::P0;
+            Ok(())
+        })
+        .await
+        .unwrap();
+        let added = create_incident(99);
+        source.save_incident(&added).await.unwrap();
+
+        let report = migrator.verify().await.unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(report.mismatched, vec![changed[0].id]);
+        assert_eq!(report.missing, vec![added.id]);
+        assert!(report.unexpected.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_detects_event_log_drift() {
+        let source: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        let target: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+        populate(source.as_ref(), 3).await;
+
+        let migrator = StoreMigrator::new(source.clone(), target.clone());
+        migrator.run().await.unwrap();
+
+        // Same incident in both stores, but the target lost its history
+        let truncated = source
+            .list_incidents(&IncidentFilter::default(), 0, 1)
+            .await
+            .unwrap();
+        let events = target.get_incident_events(&truncated[0].id).await.unwrap();
+        target
+            .replace_incident_events(&truncated[0].id, &events[events.len() - 1..])
+            .await
+            .unwrap();
+
+        let report = migrator.verify().await.unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(report.mismatched, vec![truncated[0].id]);
+        assert_eq!(report.source_count, report.target_count);
+
+        // A re-run copies the log back
+        let report = migrator.run().await.unwrap();
+        assert_eq!(report.incidents_written, 1);
+        assert!(report.verification.unwrap().is_consistent());
+    }
+
+    #[test]
+    fn test_digest_ignores_map_order() {
+        let mut incident = create_incident(1);
+        let mut reordered = incident.clone();
+        for i in 0..20 {
+            incident
+                .labels
+                .insert(format!("label-{}", i), i.to_string());
+        }
+        for i in (0..20).rev() {
+            reordered
+                .labels
+                .insert(format!("label-{}", i), i.to_string());
+        }
+
+        assert_eq!(
+            incident_digest(&incident).unwrap(),
+            incident_digest(&reordered).unwrap()
+        );
+
+        reordered
+            .labels
+            .insert("label-0".to_string(), "changed".to_string());
+        assert_ne!(
+            incident_digest(&incident).unwrap(),
+            incident_digest(&reordered).unwrap()
+        );
+    }
+}
@@ -1,20 +1,39 @@
 pub mod cache;
 pub mod circuit_breaker_store;
+pub mod codec;
+pub mod dual_write_store;
+pub mod event_log;
 pub mod factory;
+pub mod migration;
+pub mod query;
+pub mod redb_store;
 pub mod redis_store;
 pub mod sled_store;
 pub mod store;
 
 pub use cache::*;
 pub use circuit_breaker_store::{CircuitBreakerRedis, CircuitBreakerStore};
-pub use factory::{create_in_memory_store, create_store};
+pub use dual_write_store::DualWriteStore;
+pub use event_log::{IncidentEvent, IncidentEventPayload};
+pub use factory::{
+    create_backend, create_dual_write_store, create_in_memory_store, create_store,
+};
+pub use migration::{MigrationReport, StoreMigrator, VerificationReport};
+pub use query::{
+    IncidentCursor, IncidentFilter, IncidentPage, IncidentSort, LabelSelector, SortDirection,
+    SortField, TimeRange,
+};
+pub use redb_store::RedbStore;
 pub use redis_store::RedisStore;
 pub use sled_store::SledStore;
 pub use store::*;
 
-use crate::error::Result;
-use crate::models::Incident;
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 /// Trait for incident storage operations
@@ -27,12 +46,17 @@ pub trait IncidentStore: Send + Sync {
     async fn get_incident(&self, id: &Uuid) -> Result<Option<Incident>>;
 
     /// Update an incident
+    ///
+    /// This is a compare-and-swap on [`Incident::version`]: the write only
+    /// succeeds if the stored version still equals `incident.version`, in
+    /// which case the stored copy is saved with the version incremented.
+    /// Otherwise `AppError::VersionConflict` is returned and nothing changes.
     async fn update_incident(&self, incident: &Incident) -> Result<()>;
 
     /// Delete an incident
     async fn delete_incident(&self, id: &Uuid) -> Result<()>;
 
-    /// List incidents with filtering
+    /// List incidents with filtering, using page/offset pagination
     async fn list_incidents(
         &self,
         filter: &IncidentFilter,
@@ -40,18 +64,117 @@ pub trait IncidentStore: Send + Sync {
         page_size: u32,
     ) -> Result<Vec<Incident>>;
 
+    /// Query incidents with filtering, using cursor pagination
+    ///
+    /// Returns up to `limit` incidents after `cursor` in `filter.sort` order.
+    /// Pass the returned `next_cursor` to fetch the following page.
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage>;
+
     /// Count incidents matching filter
     async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64>;
 
     /// Find incidents by fingerprint
     async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>>;
+
+    /// Get the append-only event log for an incident, ordered by sequence
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>>;
+
+    /// Rebuild an incident as it was at the given time from its event log
+    ///
+    /// Incidents stored before the event log existed and not written since
+    /// have no events; the stored record is returned for times after its last
+    /// update.
+    async fn get_incident_at(&self, id: &Uuid, at: DateTime<Utc>) -> Result<Option<Incident>> {
+        let events = self.get_incident_events(id).await?;
+        if events.is_empty() {
+            return Ok(self
+                .get_incident(id)
+                .await?
+                .filter(|incident| incident.updated_at <= at));
+        }
+
+        Ok(event_log::replay(&events, Some(at)))
+    }
+
+    /// Replace an incident's event log with `events`, in sequence order
+    ///
+    /// Only used to carry an incident's history over to another backend;
+    /// normal writes append to the log. Later writes continue the sequence
+    /// after the last of `events`.
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()>;
+
+    /// Save (insert or replace) a post-mortem
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()>;
+
+    /// Get a post-mortem by ID
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>>;
+
+    /// List post-mortems, optionally restricted to one incident
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>>;
+
+    /// Save (insert or replace) a playbook execution
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()>;
+
+    /// Get a playbook execution by ID
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>>;
+
+    /// List playbook executions, optionally restricted to one incident
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>>;
+
+    /// Save (insert or replace) the escalation state of an incident
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()>;
+
+    /// Get the escalation state of an incident
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>>;
+
+    /// List the escalation states of all incidents
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>>;
 }
 
-/// Filter for querying incidents
-#[derive(Debug, Clone, Default)]
-pub struct IncidentFilter {
-    pub states: Vec<crate::models::IncidentState>,
-    pub severities: Vec<crate::models::Severity>,
-    pub sources: Vec<String>,
-    pub active_only: bool,
+/// Maximum attempts [`modify_incident`] makes before giving up on a conflict
+pub const MAX_UPDATE_RETRIES: usize = 5;
+
+/// Apply a change to the latest copy of an incident, retrying on conflict
+///
+/// Reads the incident, runs `apply` on it and writes it back with
+/// [`IncidentStore::update_incident`]. If another writer got there first the
+/// incident is re-read and `apply` runs again, so it must be safe to repeat.
+/// Returns the incident as stored, with its new version.
+pub async fn modify_incident<F>(
+    store: &dyn IncidentStore,
+    id: &Uuid,
+    mut apply: F,
+) -> Result<Incident>
+where
+    F: FnMut(&mut Incident) -> Result<()> + Send,
+{
+    let mut attempt = 0;
+    loop {
+        let mut incident = store
+            .get_incident(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Incident {} not found", id)))?;
+
+        apply(&mut incident)?;
+
+        match store.update_incident(&incident).await {
+            Ok(()) => {
+                incident.version += 1;
+                return Ok(incident);
+            }
+            Err(AppError::VersionConflict { .. }) if attempt + 1 < MAX_UPDATE_RETRIES => {
+                attempt += 1;
+                tracing::debug!(incident_id = %id, attempt, "Incident version conflict, retrying");
+            }
+            Err(e) => return Err(e),
+        }
+    }
 }
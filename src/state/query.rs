@@ -0,0 +1,838 @@
+//! Incident query model shared by every store backend
+//!
+//! [`IncidentFilter`] describes which incidents to return and in what order.
+//! Backends narrow the candidate set with their own secondary indexes (see
+//! [`index_terms`]), walk a sort index (keyed by [`SortField::key`] and
+//! incident ID) in the requested order and feed each incident to a
+//! [`PageBuilder`], so that every backend returns the same results for the
+//! same filter. The in-memory store sorts everything and uses [`paginate`].
+//!
+//! Pagination is keyset based: an [`IncidentCursor`] records the sort key and
+//! ID of the last incident returned, and the next page starts strictly after
+//! it. Unlike page/offset pagination, pages stay stable when incidents are
+//! created or deleted between requests, and a backend can seek its sort
+//! index straight to the cursor instead of reading the earlier pages.
+
+use crate::error::{AppError, Result};
+use crate::models::{Incident, IncidentState, IncidentType, Severity};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Maximum number of incidents returned by one cursor page
+pub const MAX_PAGE_LIMIT: u32 = 1000;
+
+/// Filter for querying incidents
+///
+/// List fields match if the incident has any of the given values; label
+/// selectors must all match. Empty lists and `None` match everything.
+#[derive(Debug, Clone, Default)]
+pub struct IncidentFilter {
+    pub states: Vec<IncidentState>,
+    pub severities: Vec<Severity>,
+    /// Sources, matched by substring
+    pub sources: Vec<String>,
+    pub active_only: bool,
+
+    pub incident_types: Vec<IncidentType>,
+
+    /// Assignees, matched exactly
+    pub assignees: Vec<String>,
+
+    /// Label selectors, all of which must match
+    pub labels: Vec<LabelSelector>,
+
+    /// Affected resources, matched exactly
+    pub affected_resources: Vec<String>,
+
+    /// Correlation group the incident belongs to
+    pub correlation_group_id: Option<Uuid>,
+
+    /// Case-insensitive text searched in the title and description
+    pub text: Option<String>,
+
+    pub created: TimeRange,
+    pub updated: TimeRange,
+
+    /// Resolution time; a bounded range only matches resolved incidents
+    pub resolved: TimeRange,
+
+    /// Result order
+    pub sort: IncidentSort,
+}
+
+impl IncidentFilter {
+    /// Check whether an incident matches every constraint of the filter
+    pub fn matches(&self, incident: &Incident) -> bool {
+        let any = |values: &[String], value: &str| values.iter().any(|v| v == value);
+
+        (self.states.is_empty() || self.states.contains(&incident.state))
+            && (self.severities.is_empty() || self.severities.contains(&incident.severity))
+            && (self.sources.is_empty()
+                || self.sources.iter().any(|s| incident.source.contains(s.as_str())))
+            && (!self.active_only || incident.is_active())
+            && (self.incident_types.is_empty()
+                || self.incident_types.contains(&incident.incident_type))
+            && (self.assignees.is_empty()
+                || incident.assignees.iter().any(|a| any(&self.assignees, a)))
+            && self.labels.iter().all(|selector| selector.matches(&incident.labels))
+            && (self.affected_resources.is_empty()
+                || incident
+                    .affected_resources
+                    .iter()
+                    .any(|r| any(&self.affected_resources, r)))
+            && self
+                .correlation_group_id
+                .is_none_or(|group| incident.correlation_group_id == Some(group))
+            && self.text.as_deref().is_none_or(|text| {
+                let text = text.to_lowercase();
+                incident.title.to_lowercase().contains(&text)
+                    || incident.description.to_lowercase().contains(&text)
+            })
+            && self.created.contains(incident.created_at)
+            && self.updated.contains(incident.updated_at)
+            && (self.resolved.is_unbounded()
+                || incident
+                    .resolution
+                    .as_ref()
+                    .is_some_and(|r| self.resolved.contains(r.resolved_at)))
+    }
+
+    /// Index terms the filter requires, for backends with a term index
+    ///
+    /// Each inner list is a union (the incident needs any one of its terms);
+    /// the lists are intersected. States, severities and sources have
+    /// dedicated indexes in every backend and are not included.
+    pub fn required_terms(&self) -> Vec<Vec<String>> {
+        let mut groups = Vec::new();
+
+        if !self.incident_types.is_empty() {
+            groups.push(self.incident_types.iter().map(type_term).collect());
+        }
+        if !self.assignees.is_empty() {
+            groups.push(self.assignees.iter().map(|a| assignee_term(a)).collect());
+        }
+        for selector in &self.labels {
+            groups.push(vec![selector.term()]);
+        }
+        if !self.affected_resources.is_empty() {
+            groups.push(
+                self.affected_resources
+                    .iter()
+                    .map(|r| resource_term(r))
+                    .collect(),
+            );
+        }
+        if let Some(group) = self.correlation_group_id {
+            groups.push(vec![group_term(&group)]);
+        }
+
+        groups
+    }
+}
+
+/// Terms under which an incident is stored in a backend's term index
+///
+/// Covers incident type, assignees, labels (both `key` and `key=value`),
+/// affected resources and correlation group.
+pub fn index_terms(incident: &Incident) -> BTreeSet<String> {
+    let mut terms = BTreeSet::new();
+
+    terms.insert(type_term(&incident.incident_type));
+    for assignee in &incident.assignees {
+        terms.insert(assignee_term(assignee));
+    }
+    for (key, value) in &incident.labels {
+        terms.insert(format!("label:{}", key));
+        terms.insert(format!("label:{}={}", key, value));
+    }
+    for resource in &incident.affected_resources {
+        terms.insert(resource_term(resource));
+    }
+    if let Some(group) = incident.correlation_group_id {
+        terms.insert(group_term(&group));
+    }
+
+    terms
+}
+
+fn type_term(incident_type: &IncidentType) -> String {
+    format!("type:{:?}", incident_type)
+}
+
+fn assignee_term(assignee: &str) -> String {
+    format!("assignee:{}", assignee)
+}
+
+fn resource_term(resource: &str) -> String {
+    format!("resource:{}", resource)
+}
+
+fn group_term(group: &Uuid) -> String {
+    format!("group:{}", group)
+}
+
+/// Half-open time range: `after` is inclusive, `before` exclusive
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeRange {
+    pub after: Option<DateTime<Utc>>,
+    pub before: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Create a range from optional bounds
+    pub fn new(after: Option<DateTime<Utc>>, before: Option<DateTime<Utc>>) -> Self {
+        Self { after, before }
+    }
+
+    /// Whether neither bound is set
+    pub fn is_unbounded(&self) -> bool {
+        self.after.is_none() && self.before.is_none()
+    }
+
+    /// Check whether a timestamp lies within the range
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.after.is_none_or(|after| at >= after) && self.before.is_none_or(|before| at < before)
+    }
+}
+
+/// Label selector: `key` (label present) or `key=value`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSelector {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+impl LabelSelector {
+    /// Select incidents that have the label, whatever its value
+    pub fn exists(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: None,
+        }
+    }
+
+    /// Select incidents whose label has the given value
+    pub fn equals(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: Some(value.into()),
+        }
+    }
+
+    /// Check the selector against an incident's labels
+    pub fn matches(&self, labels: &HashMap<String, String>) -> bool {
+        match (&self.value, labels.get(&self.key)) {
+            (_, None) => false,
+            (None, Some(_)) => true,
+            (Some(expected), Some(actual)) => expected == actual,
+        }
+    }
+
+    fn term(&self) -> String {
+        match self.value {
+            Some(ref value) => format!("label:{}={}", self.key, value),
+            None => format!("label:{}", self.key),
+        }
+    }
+}
+
+impl FromStr for LabelSelector {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (key, value) = match s.split_once('=') {
+            Some((key, value)) => (key.trim(), Some(value.trim().to_string())),
+            None => (s.trim(), None),
+        };
+
+        if key.is_empty() {
+            return Err(AppError::Validation(format!(
+                "Invalid label selector '{}': expected 'key' or 'key=value'",
+                s
+            )));
+        }
+
+        Ok(Self {
+            key: key.to_string(),
+            value,
+        })
+    }
+}
+
+impl fmt::Display for LabelSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value {
+            Some(ref value) => write!(f, "{}={}", self.key, value),
+            None => write!(f, "{}", self.key),
+        }
+    }
+}
+
+/// Field incidents are sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    /// Unresolved incidents sort as the oldest resolution time
+    ResolvedAt,
+    /// Most severe (P0) is the highest value
+    Severity,
+    /// Lifecycle order, Detected lowest and Closed highest
+    State,
+}
+
+impl SortField {
+    /// Every sort field, for backends that keep one sort index per field
+    pub const ALL: [SortField; 5] = [
+        Self::CreatedAt,
+        Self::UpdatedAt,
+        Self::ResolvedAt,
+        Self::Severity,
+        Self::State,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::ResolvedAt => "resolved_at",
+            Self::Severity => "severity",
+            Self::State => "state",
+        }
+    }
+
+    /// Sort key of an incident for this field
+    ///
+    /// Incidents are ordered by this key and then by ID.
+    pub fn key(&self, incident: &Incident) -> i64 {
+        match self {
+            Self::CreatedAt => incident.created_at.timestamp_micros(),
+            Self::UpdatedAt => incident.updated_at.timestamp_micros(),
+            Self::ResolvedAt => incident
+                .resolution
+                .as_ref()
+                .map_or(i64::MIN, |r| r.resolved_at.timestamp_micros()),
+            Self::Severity => 4 - incident.severity.priority() as i64,
+            Self::State => match incident.state {
+                IncidentState::Detected => 0,
+                IncidentState::Triaged => 1,
+                IncidentState::Investigating => 2,
+                IncidentState::Remediating => 3,
+                IncidentState::Resolved => 4,
+                IncidentState::Closed => 5,
+            },
+        }
+    }
+}
+
+impl FromStr for SortField {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "created_at" => Ok(Self::CreatedAt),
+            "updated_at" => Ok(Self::UpdatedAt),
+            "resolved_at" => Ok(Self::ResolvedAt),
+            "severity" => Ok(Self::Severity),
+            "state" => Ok(Self::State),
+            _ => Err(AppError::Validation(format!("Unknown sort field '{}'", s))),
+        }
+    }
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Order of query results; ties are broken by incident ID
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IncidentSort {
+    pub field: SortField,
+    pub direction: SortDirection,
+}
+
+impl IncidentSort {
+    /// Create a sort order
+    pub fn new(field: SortField, direction: SortDirection) -> Self {
+        Self { field, direction }
+    }
+
+    /// Compare two incidents in this order
+    pub fn compare(&self, a: &Incident, b: &Incident) -> Ordering {
+        self.compare_keys((self.field.key(a), a.id), (self.field.key(b), b.id))
+    }
+
+    fn compare_keys(&self, a: (i64, Uuid), b: (i64, Uuid)) -> Ordering {
+        match self.direction {
+            SortDirection::Asc => a.cmp(&b),
+            SortDirection::Desc => b.cmp(&a),
+        }
+    }
+}
+
+impl FromStr for IncidentSort {
+    type Err = AppError;
+
+    /// Parse `field` (ascending) or `-field` (descending)
+    fn from_str(s: &str) -> Result<Self> {
+        match s.strip_prefix('-') {
+            Some(field) => Ok(Self::new(field.parse()?, SortDirection::Desc)),
+            None => Ok(Self::new(s.parse()?, SortDirection::Asc)),
+        }
+    }
+}
+
+/// Position after which the next page starts
+///
+/// Encoded as an opaque string; clients should pass it back unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncidentCursor {
+    field: SortField,
+    key: i64,
+    id: Uuid,
+}
+
+impl IncidentCursor {
+    /// Cursor positioned at an incident
+    pub fn after(incident: &Incident, sort: &IncidentSort) -> Self {
+        Self {
+            field: sort.field,
+            key: sort.field.key(incident),
+            id: incident.id,
+        }
+    }
+
+    /// Sort key of the incident the cursor is positioned at
+    pub fn key(&self) -> i64 {
+        self.key
+    }
+
+    /// ID of the incident the cursor is positioned at
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Encode the cursor for clients
+    pub fn encode(&self) -> String {
+        format!("{}:{}:{}", self.field.as_str(), self.key, self.id)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Decode a cursor produced by [`encode`](Self::encode) for the same sort
+    pub fn decode(cursor: &str, sort: &IncidentSort) -> Result<Self> {
+        let invalid = || AppError::Validation(format!("Invalid cursor '{}'", cursor));
+
+        let bytes = cursor
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .filter(|digits| digits.len() == 2)
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let mut parts = decoded.splitn(3, ':');
+        let (Some(field), Some(key), Some(id)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let field: SortField = field.parse().map_err(|_| invalid())?;
+        if field != sort.field {
+            return Err(AppError::Validation(
+                "Cursor was issued for a different sort order".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            field,
+            key: key.parse().map_err(|_| invalid())?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+
+    /// Whether an incident comes strictly after the cursor in `sort` order
+    fn precedes(&self, incident: &Incident, sort: &IncidentSort) -> bool {
+        sort.compare_keys((self.key, self.id), (sort.field.key(incident), incident.id))
+            == Ordering::Less
+    }
+}
+
+/// One page of a cursor query
+#[derive(Debug, Clone, Default)]
+pub struct IncidentPage {
+    pub incidents: Vec<Incident>,
+
+    /// Cursor for the next page, `None` on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Sort incidents in the filter's order
+pub fn sort_incidents(incidents: &mut [Incident], sort: &IncidentSort) {
+    incidents.sort_by(|a, b| sort.compare(a, b));
+}
+
+/// Apply page/offset pagination to matching incidents
+pub fn page_offset(
+    mut incidents: Vec<Incident>,
+    sort: &IncidentSort,
+    page: u32,
+    page_size: u32,
+) -> Vec<Incident> {
+    sort_incidents(&mut incidents, sort);
+
+    let start = (page as usize).saturating_mul(page_size as usize);
+    incidents
+        .into_iter()
+        .skip(start)
+        .take(page_size as usize)
+        .collect()
+}
+
+/// Apply cursor pagination to matching incidents
+pub fn paginate(
+    mut incidents: Vec<Incident>,
+    sort: &IncidentSort,
+    cursor: Option<&str>,
+    limit: u32,
+) -> Result<IncidentPage> {
+    sort_incidents(&mut incidents, sort);
+
+    let filter = IncidentFilter {
+        sort: *sort,
+        ..Default::default()
+    };
+    let mut page = PageBuilder::new(&filter, cursor, limit)?;
+    for incident in incidents {
+        if !page.push(incident) {
+            break;
+        }
+    }
+
+    Ok(page.finish())
+}
+
+/// Collects one page from incidents visited in sort order
+///
+/// A backend walks its sort index for `filter.sort`, starting from
+/// [`start`](Self::start), and pushes each incident it finds until
+/// [`push`](Self::push) returns `false`. The builder drops incidents that do
+/// not match the filter or do not come after the last one taken, so index
+/// entries that are stale or repeated by a concurrent write are harmless.
+pub struct PageBuilder<'a> {
+    filter: &'a IncidentFilter,
+    start: Option<IncidentCursor>,
+    offset: usize,
+    limit: usize,
+    incidents: Vec<Incident>,
+    has_more: bool,
+}
+
+impl<'a> PageBuilder<'a> {
+    /// Page of at most `limit` incidents after `cursor`
+    pub fn new(filter: &'a IncidentFilter, cursor: Option<&str>, limit: u32) -> Result<Self> {
+        let start = cursor
+            .map(|cursor| IncidentCursor::decode(cursor, &filter.sort))
+            .transpose()?;
+
+        Ok(Self {
+            filter,
+            start,
+            offset: 0,
+            limit: limit.clamp(1, MAX_PAGE_LIMIT) as usize,
+            incidents: Vec::new(),
+            has_more: false,
+        })
+    }
+
+    /// Page `page` of `page_size` incidents, for page/offset pagination
+    pub fn offset(filter: &'a IncidentFilter, page: u32, page_size: u32) -> Self {
+        Self {
+            filter,
+            start: None,
+            offset: (page as usize).saturating_mul(page_size as usize),
+            limit: page_size as usize,
+            incidents: Vec::new(),
+            has_more: false,
+        }
+    }
+
+    /// Position the page starts after, if any
+    pub fn start(&self) -> Option<&IncidentCursor> {
+        self.start.as_ref()
+    }
+
+    /// Offer the next incident in sort order
+    ///
+    /// Returns `false` once the page is complete and the walk can stop.
+    pub fn push(&mut self, incident: Incident) -> bool {
+        if self.is_complete() {
+            return false;
+        }
+
+        let sort = &self.filter.sort;
+        let in_order = match self.incidents.last() {
+            Some(last) => sort.compare(last, &incident) == Ordering::Less,
+            None => self
+                .start
+                .is_none_or(|start| start.precedes(&incident, sort)),
+        };
+        if !in_order || !self.filter.matches(&incident) {
+            return true;
+        }
+
+        if self.offset > 0 {
+            // Skipped incidents still advance the position
+            self.offset -= 1;
+            self.start = Some(IncidentCursor::after(&incident, sort));
+        } else if self.incidents.len() < self.limit {
+            self.incidents.push(incident);
+        } else {
+            self.has_more = true;
+        }
+
+        !self.is_complete()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.has_more || (self.limit == 0 && self.offset == 0)
+    }
+
+    /// Finish the page, with a cursor for the next one if more incidents
+    /// were found
+    pub fn finish(self) -> IncidentPage {
+        let next_cursor = if self.has_more {
+            self.incidents
+                .last()
+                .map(|last| IncidentCursor::after(last, &self.filter.sort).encode())
+        } else {
+            None
+        };
+
+        IncidentPage {
+            incidents: self.incidents,
+            next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn create_incident(title: &str, severity: Severity, age_minutes: i64) -> Incident {
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            title.to_string(),
+            "Database latency is elevated".to_string(),
+            severity,
+            IncidentType::Performance,
+        );
+        incident.created_at = Utc::now() - Duration::minutes(age_minutes);
+        incident.updated_at = incident.created_at;
+        incident
+    }
+
+    #[test]
+    fn test_filter_matches_new_fields() {
+        let mut incident = create_incident("API outage", Severity::P1, 5);
+        incident.assignees.push("alice".to_string());
+        incident.labels.insert("team".to_string(), "platform".to_string());
+        incident.affected_resources.push("api-gateway".to_string());
+
+        let matching = IncidentFilter {
+            incident_types: vec![IncidentType::Performance],
+            assignees: vec!["bob".to_string(), "alice".to_string()],
+            labels: vec![
+                LabelSelector::equals("team", "platform"),
+                LabelSelector::exists("team"),
+            ],
+            affected_resources: vec!["api-gateway".to_string()],
+            text: Some("LATENCY".to_string()),
+            created: TimeRange::new(Some(Utc::now() - Duration::hours(1)), None),
+            ..Default::default()
+        };
+        assert!(matching.matches(&incident));
+
+        let wrong_label = IncidentFilter {
+            labels: vec![LabelSelector::equals("team", "storage")],
+            ..Default::default()
+        };
+        assert!(!wrong_label.matches(&incident));
+
+        let unresolved = IncidentFilter {
+            resolved: TimeRange::new(None, Some(Utc::now())),
+            ..Default::default()
+        };
+        assert!(!unresolved.matches(&incident));
+
+        let other_group = IncidentFilter {
+            correlation_group_id: Some(Uuid::new_v4()),
+            ..Default::default()
+        };
+        assert!(!other_group.matches(&incident));
+    }
+
+    #[test]
+    fn test_index_terms_cover_required_terms() {
+        let mut incident = create_incident("API outage", Severity::P1, 5);
+        incident.assignees.push("alice".to_string());
+        incident.labels.insert("team".to_string(), "platform".to_string());
+        incident.correlation_group_id = Some(Uuid::new_v4());
+
+        let filter = IncidentFilter {
+            incident_types: vec![IncidentType::Performance, IncidentType::Security],
+            assignees: vec!["alice".to_string()],
+            labels: vec![LabelSelector::exists("team")],
+            correlation_group_id: incident.correlation_group_id,
+            ..Default::default()
+        };
+
+        let terms = index_terms(&incident);
+        for group in filter.required_terms() {
+            assert!(group.iter().any(|term| terms.contains(term)), "{:?}", group);
+        }
+    }
+
+    #[test]
+    fn test_parse_selectors_and_sort() {
+        assert_eq!(
+            "env=prod".parse::<LabelSelector>().unwrap(),
+            LabelSelector::equals("env", "prod")
+        );
+        assert_eq!(
+            "env".parse::<LabelSelector>().unwrap(),
+            LabelSelector::exists("env")
+        );
+        assert!("=prod".parse::<LabelSelector>().is_err());
+
+        assert_eq!(
+            "-severity".parse::<IncidentSort>().unwrap(),
+            IncidentSort::new(SortField::Severity, SortDirection::Desc)
+        );
+        assert_eq!(
+            "updated_at".parse::<IncidentSort>().unwrap(),
+            IncidentSort::new(SortField::UpdatedAt, SortDirection::Asc)
+        );
+        assert!("priority".parse::<IncidentSort>().is_err());
+    }
+
+    #[test]
+    fn test_cursor_pagination_visits_every_incident_once() {
+        let incidents: Vec<Incident> = (0..7)
+            .map(|i| create_incident(&format!("Incident {}", i), Severity::P2, i % 3))
+            .collect();
+        let sort = IncidentSort::new(SortField::CreatedAt, SortDirection::Desc);
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = paginate(incidents.clone(), &sort, cursor.as_deref(), 3).unwrap();
+            seen.extend(page.incidents.iter().map(|i| i.id));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        let mut expected = incidents.clone();
+        sort_incidents(&mut expected, &sort);
+        assert_eq!(seen, expected.iter().map(|i| i.id).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_cursor_is_stable_when_incidents_are_added() {
+        let mut incidents: Vec<Incident> = (0..4)
+            .map(|i| create_incident(&format!("Incident {}", i), Severity::P2, i + 1))
+            .collect();
+        let sort = IncidentSort::default();
+
+        let first = paginate(incidents.clone(), &sort, None, 2).unwrap();
+
+        // A newer incident arrives before the second page is requested
+        incidents.push(create_incident("Newest", Severity::P0, 0));
+
+        let second = paginate(incidents, &sort, first.next_cursor.as_deref(), 2).unwrap();
+        assert_eq!(second.incidents.len(), 2);
+        assert!(second.next_cursor.is_none());
+        for incident in &second.incidents {
+            assert!(!first.incidents.iter().any(|i| i.id == incident.id));
+            assert_ne!(incident.title, "Newest");
+        }
+    }
+
+    #[test]
+    fn test_page_builder_ignores_stale_and_repeated_entries() {
+        let incidents: Vec<Incident> = (0..5)
+            .map(|i| create_incident(&format!("Incident {}", i), Severity::P2, i))
+            .collect();
+        let filter = IncidentFilter {
+            sort: IncidentSort::new(SortField::CreatedAt, SortDirection::Desc),
+            ..Default::default()
+        };
+
+        // An index walk that repeats an entry still yields each incident once
+        let mut page = PageBuilder::new(&filter, None, 3).unwrap();
+        for incident in [&incidents[0], &incidents[1], &incidents[1], &incidents[2]] {
+            assert!(page.push(incident.clone()));
+        }
+        assert!(!page.push(incidents[3].clone()));
+        let page = page.finish();
+        let ids: Vec<Uuid> = page.incidents.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![incidents[0].id, incidents[1].id, incidents[2].id]);
+
+        // Entries at or before the cursor are skipped
+        let mut next = PageBuilder::new(&filter, page.next_cursor.as_deref(), 3).unwrap();
+        for incident in &incidents {
+            next.push(incident.clone());
+        }
+        let next = next.finish();
+        assert_eq!(next.incidents.len(), 2);
+        assert_eq!(next.incidents[0].id, incidents[3].id);
+        assert!(next.next_cursor.is_none());
+
+        let mut offset = PageBuilder::offset(&filter, 1, 2);
+        for incident in &incidents {
+            offset.push(incident.clone());
+        }
+        let ids: Vec<Uuid> = offset.finish().incidents.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![incidents[2].id, incidents[3].id]);
+    }
+
+    #[test]
+    fn test_severity_sort_and_cursor_mismatch() {
+        let incidents = vec![
+            create_incident("low", Severity::P3, 1),
+            create_incident("critical", Severity::P0, 2),
+            create_incident("high", Severity::P1, 3),
+        ];
+        let sort = IncidentSort::new(SortField::Severity, SortDirection::Desc);
+
+        let page = paginate(incidents.clone(), &sort, None, 2).unwrap();
+        assert_eq!(page.incidents[0].title, "critical");
+        assert_eq!(page.incidents[1].title, "high");
+
+        let cursor = page.next_cursor.unwrap();
+        assert!(paginate(incidents.clone(), &IncidentSort::default(), Some(&cursor), 2).is_err());
+        assert!(paginate(incidents, &sort, Some("not-a-cursor"), 2).is_err());
+    }
+}
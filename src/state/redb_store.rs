@@ -0,0 +1,1146 @@
+use crate::error::{AppError, Result};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::codec;
+use crate::state::event_log::{self, IncidentEvent};
+use crate::state::query::{
+    self, IncidentFilter, IncidentPage, PageBuilder, SortDirection, SortField,
+};
+use crate::state::IncidentStore;
+use async_trait::async_trait;
+use redb::{
+    Database, MultimapTableDefinition, ReadTransaction, ReadableMultimapTable, ReadableTable,
+    ReadableTableMetadata, TableDefinition, WriteTransaction,
+};
+use std::collections::{BTreeSet, HashSet};
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Incidents keyed by ID (bincode)
+const INCIDENTS: TableDefinition<u128, &[u8]> = TableDefinition::new("incidents");
+
+/// Secondary index: fingerprint -> incident IDs
+const FINGERPRINT_INDEX: MultimapTableDefinition<&str, u128> =
+    MultimapTableDefinition::new("fingerprint_index");
+
+/// Secondary index: state -> incident IDs
+const STATE_INDEX: MultimapTableDefinition<&str, u128> =
+    MultimapTableDefinition::new("state_index");
+
+/// Secondary index: severity -> incident IDs
+const SEVERITY_INDEX: MultimapTableDefinition<&str, u128> =
+    MultimapTableDefinition::new("severity_index");
+
+/// Secondary index: source -> incident IDs
+const SOURCE_INDEX: MultimapTableDefinition<&str, u128> =
+    MultimapTableDefinition::new("source_index");
+
+/// Secondary index: query term (see [`query::index_terms`]) -> incident IDs
+const TERM_INDEX: MultimapTableDefinition<&str, u128> =
+    MultimapTableDefinition::new("term_index");
+
+/// Secondary index: (creation time in microseconds, incident ID)
+const CREATED_INDEX: TableDefinition<(i64, u128), ()> = TableDefinition::new("created_index");
+
+/// Secondary index: (sort field, sort key, incident ID) for every sort field
+/// but creation time, which is ordered by [`CREATED_INDEX`]
+const SORT_INDEX: TableDefinition<(u8, i64, u128), ()> = TableDefinition::new("sort_index");
+
+/// Incident event log keyed by (incident ID, sequence) (JSON)
+const EVENTS: TableDefinition<(u128, u64), &[u8]> = TableDefinition::new("incident_events");
+
+/// Post-mortems keyed by ID (JSON)
+const POSTMORTEMS: TableDefinition<u128, &[u8]> = TableDefinition::new("postmortems");
+
+/// Playbook executions keyed by ID (JSON)
+const EXECUTIONS: TableDefinition<u128, &[u8]> = TableDefinition::new("playbook_executions");
+
+/// Escalation states keyed by incident ID (JSON)
+const ESCALATIONS: TableDefinition<u128, &[u8]> = TableDefinition::new("escalation_states");
+
+/// File name used when the configured path is a directory
+const DATABASE_FILE: &str = "incidents.redb";
+
+/// Extracts an incident's keys in a secondary index
+type IndexKeys = fn(&Incident) -> BTreeSet<String>;
+
+fn fingerprint_keys(incident: &Incident) -> BTreeSet<String> {
+    incident.fingerprint.iter().cloned().collect()
+}
+
+fn state_keys(incident: &Incident) -> BTreeSet<String> {
+    BTreeSet::from([format!("{:?}", incident.state)])
+}
+
+fn severity_keys(incident: &Incident) -> BTreeSet<String> {
+    BTreeSet::from([format!("{:?}", incident.severity)])
+}
+
+fn source_keys(incident: &Incident) -> BTreeSet<String> {
+    BTreeSet::from([incident.source.clone()])
+}
+
+/// Every secondary index and how to compute an incident's keys in it
+const INDICES: [(MultimapTableDefinition<&str, u128>, IndexKeys); 5] = [
+    (FINGERPRINT_INDEX, fingerprint_keys),
+    (STATE_INDEX, state_keys),
+    (SEVERITY_INDEX, severity_keys),
+    (SOURCE_INDEX, source_keys),
+    (TERM_INDEX, query::index_terms),
+];
+
+/// Field number of a sort field in [`SORT_INDEX`]
+fn sort_index_field(field: SortField) -> u8 {
+    match field {
+        SortField::CreatedAt => 0,
+        SortField::UpdatedAt => 1,
+        SortField::ResolvedAt => 2,
+        SortField::Severity => 3,
+        SortField::State => 4,
+    }
+}
+
+/// Map any redb error to an internal error
+fn db_error<E: std::fmt::Display>(e: E) -> AppError {
+    AppError::Internal(format!("redb error: {}", e))
+}
+
+/// Persistent incident store using a single-file redb database
+///
+/// Every write runs in one redb write transaction, so an incident, its
+/// secondary index entries and its event log entries are committed together
+/// or not at all.
+#[derive(Clone)]
+pub struct RedbStore {
+    db: Arc<Database>,
+    path: PathBuf,
+}
+
+impl RedbStore {
+    /// Open (or create) a redb store
+    ///
+    /// `path` is the database file. If it is a directory, or has no file
+    /// extension, the database is created as `incidents.redb` inside it.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = Self::database_path(path.as_ref())?;
+
+        let db = Database::create(&path).map_err(|e| {
+            AppError::Internal(format!("Failed to open redb database: {}", e))
+        })?;
+
+        let store = Self {
+            db: Arc::new(db),
+            path,
+        };
+
+        // Create all tables up front so read transactions never miss one
+        store.write(|txn| {
+            txn.open_table(INCIDENTS).map_err(db_error)?;
+            txn.open_table(EVENTS).map_err(db_error)?;
+            txn.open_table(POSTMORTEMS).map_err(db_error)?;
+            txn.open_table(EXECUTIONS).map_err(db_error)?;
+            txn.open_table(ESCALATIONS).map_err(db_error)?;
+            txn.open_table(CREATED_INDEX).map_err(db_error)?;
+            txn.open_table(SORT_INDEX).map_err(db_error)?;
+            for (definition, _) in INDICES {
+                txn.open_multimap_table(definition).map_err(db_error)?;
+            }
+
+            // Databases written before the query or sort indexes existed
+            let created = txn.open_table(CREATED_INDEX).map_err(db_error)?;
+            let sorted = txn.open_table(SORT_INDEX).map_err(db_error)?;
+            let unindexed =
+                created.is_empty().map_err(db_error)? || sorted.is_empty().map_err(db_error)?;
+            let incidents = txn.open_table(INCIDENTS).map_err(db_error)?;
+            if unindexed && !incidents.is_empty().map_err(db_error)? {
+                drop(created);
+                drop(sorted);
+                Self::rebuild_query_indices(txn, &incidents)?;
+            }
+            Ok(())
+        })?;
+
+        tracing::info!("Initialized redb store at {:?}", store.path);
+
+        Ok(store)
+    }
+
+    /// Path of the database file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Resolve the configured path to a database file, creating directories
+    fn database_path(path: &Path) -> Result<PathBuf> {
+        if path.is_dir() || path.extension().is_none() {
+            std::fs::create_dir_all(path)?;
+            return Ok(path.join(DATABASE_FILE));
+        }
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(path.to_path_buf())
+    }
+
+    /// Run `f` in a write transaction, committing only if it succeeds
+    fn write<T>(&self, f: impl FnOnce(&WriteTransaction) -> Result<T>) -> Result<T> {
+        let txn = self.db.begin_write().map_err(|e| {
+            AppError::Internal(format!("Failed to begin redb write transaction: {}", e))
+        })?;
+
+        match f(&txn) {
+            Ok(value) => {
+                txn.commit().map_err(|e| {
+                    AppError::Internal(format!("Failed to commit redb transaction: {}", e))
+                })?;
+                Ok(value)
+            }
+            Err(e) => {
+                if let Err(abort_error) = txn.abort() {
+                    tracing::warn!(error = %abort_error, "Failed to abort redb transaction");
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Begin a read transaction
+    fn read(&self) -> Result<ReadTransaction> {
+        self.db.begin_read().map_err(|e| {
+            AppError::Internal(format!("Failed to begin redb read transaction: {}", e))
+        })
+    }
+
+    /// Serialize incident to bytes (see [`codec`])
+    fn serialize_incident(incident: &Incident) -> Result<Vec<u8>> {
+        codec::encode_incident(incident)
+    }
+
+    /// Deserialize incident from bytes, including records in the original
+    /// bincode layout
+    fn deserialize_incident(bytes: &[u8]) -> Result<Incident> {
+        codec::decode_incident(bytes)
+    }
+
+    /// Serialize post-mortem to bytes
+    ///
+    /// Post-mortems embed `serde_json::Value`, which bincode cannot
+    /// deserialize, so they are stored as JSON.
+    fn serialize_postmortem(postmortem: &PostMortem) -> Result<Vec<u8>> {
+        serde_json::to_vec(postmortem).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize post-mortem: {}", e))
+        })
+    }
+
+    /// Deserialize post-mortem from bytes
+    fn deserialize_postmortem(bytes: &[u8]) -> Result<PostMortem> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize post-mortem: {}", e))
+        })
+    }
+
+    /// Serialize playbook execution to bytes (JSON, like post-mortems)
+    fn serialize_execution(execution: &PlaybookExecution) -> Result<Vec<u8>> {
+        serde_json::to_vec(execution).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize playbook execution: {}", e))
+        })
+    }
+
+    /// Deserialize playbook execution from bytes
+    fn deserialize_execution(bytes: &[u8]) -> Result<PlaybookExecution> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize playbook execution: {}", e))
+        })
+    }
+
+    /// Serialize escalation state to bytes (JSON, like post-mortems)
+    fn serialize_escalation(state: &EscalationState) -> Result<Vec<u8>> {
+        serde_json::to_vec(state).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize escalation state: {}", e))
+        })
+    }
+
+    /// Deserialize escalation state from bytes
+    fn deserialize_escalation(bytes: &[u8]) -> Result<EscalationState> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize escalation state: {}", e))
+        })
+    }
+
+    /// Move an incident's entries in every secondary index from `before` to `after`
+    fn update_indices(
+        txn: &WriteTransaction,
+        id: u128,
+        before: Option<&Incident>,
+        after: Option<&Incident>,
+    ) -> Result<()> {
+        for (definition, index_keys) in INDICES {
+            let old_keys = before.map(index_keys).unwrap_or_default();
+            let new_keys = after.map(index_keys).unwrap_or_default();
+            if old_keys == new_keys {
+                continue;
+            }
+
+            let mut index = txn.open_multimap_table(definition).map_err(db_error)?;
+            for key in old_keys.difference(&new_keys) {
+                index.remove(key.as_str(), id).map_err(db_error)?;
+            }
+            for key in new_keys.difference(&old_keys) {
+                index.insert(key.as_str(), id).map_err(db_error)?;
+            }
+        }
+
+        let created_key = |incident: &Incident| (incident.created_at.timestamp_micros(), id);
+        let old_created = before.map(created_key);
+        let new_created = after.map(created_key);
+        if old_created != new_created {
+            let mut index = txn.open_table(CREATED_INDEX).map_err(db_error)?;
+            if let Some(key) = old_created {
+                index.remove(key).map_err(db_error)?;
+            }
+            if let Some(key) = new_created {
+                index.insert(key, ()).map_err(db_error)?;
+            }
+        }
+
+        let mut index = txn.open_table(SORT_INDEX).map_err(db_error)?;
+        for field in SortField::ALL {
+            if field == SortField::CreatedAt {
+                continue;
+            }
+
+            let sort_key = |incident: &Incident| (sort_index_field(field), field.key(incident), id);
+            let old_key = before.map(sort_key);
+            let new_key = after.map(sort_key);
+            if old_key == new_key {
+                continue;
+            }
+
+            if let Some(key) = old_key {
+                index.remove(key).map_err(db_error)?;
+            }
+            if let Some(key) = new_key {
+                index.insert(key, ()).map_err(db_error)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index every stored incident in the term, created-time and sort indexes
+    ///
+    /// Inserts are idempotent, so indexes that are already populated are
+    /// left as they were.
+    fn rebuild_query_indices<T: ReadableTable<u128, &'static [u8]>>(
+        txn: &WriteTransaction,
+        incidents: &T,
+    ) -> Result<()> {
+        let mut terms = txn.open_multimap_table(TERM_INDEX).map_err(db_error)?;
+        let mut created = txn.open_table(CREATED_INDEX).map_err(db_error)?;
+        let mut sorted = txn.open_table(SORT_INDEX).map_err(db_error)?;
+
+        let mut count = 0usize;
+        for entry in incidents.iter().map_err(db_error)? {
+            let (id, value) = entry.map_err(db_error)?;
+            let id = id.value();
+            let incident = Self::deserialize_incident(value.value())?;
+
+            for term in query::index_terms(&incident) {
+                terms.insert(term.as_str(), id).map_err(db_error)?;
+            }
+            created
+                .insert((incident.created_at.timestamp_micros(), id), ())
+                .map_err(db_error)?;
+            for field in SortField::ALL {
+                if field != SortField::CreatedAt {
+                    sorted
+                        .insert((sort_index_field(field), field.key(&incident), id), ())
+                        .map_err(db_error)?;
+                }
+            }
+            count += 1;
+        }
+
+        tracing::info!(incidents = count, "Rebuilt redb query indexes");
+        Ok(())
+    }
+
+    /// Append events to an incident's log within the transaction
+    fn append_events(txn: &WriteTransaction, events: &[IncidentEvent]) -> Result<()> {
+        let mut table = txn.open_table(EVENTS).map_err(db_error)?;
+
+        for event in events {
+            // Events use internally tagged enums, which bincode cannot deserialize
+            let value = serde_json::to_vec(event).map_err(|e| {
+                AppError::Internal(format!("Failed to serialize incident event: {}", e))
+            })?;
+            table
+                .insert((event.incident_id.as_u128(), event.sequence), value.as_slice())
+                .map_err(db_error)?;
+        }
+
+        Ok(())
+    }
+
+    /// Next free sequence number in an incident's event log
+    fn next_event_sequence(txn: &WriteTransaction, id: u128) -> Result<u64> {
+        let table = txn.open_table(EVENTS).map_err(db_error)?;
+        let last = table
+            .range((id, 0)..=(id, u64::MAX))
+            .map_err(db_error)?
+            .next_back()
+            .transpose()
+            .map_err(db_error)?;
+
+        Ok(last.map_or(0, |(key, _)| key.value().1 + 1))
+    }
+
+    /// Record a write in the incident's event log within the transaction
+    fn record_write(txn: &WriteTransaction, before: Option<&Incident>, after: &Incident) -> Result<()> {
+        let next_sequence = Self::next_event_sequence(txn, after.id.as_u128())?;
+        Self::append_events(txn, &event_log::events_for_write(before, after, next_sequence))
+    }
+
+    /// Read an incident within a transaction
+    fn load_incident<T: ReadableTable<u128, &'static [u8]>>(
+        table: &T,
+        id: u128,
+    ) -> Result<Option<Incident>> {
+        table
+            .get(id)
+            .map_err(db_error)?
+            .map(|value| Self::deserialize_incident(value.value()))
+            .transpose()
+    }
+
+    /// Union of the IDs stored under `keys` in a secondary index
+    fn index_lookup(
+        txn: &ReadTransaction,
+        definition: MultimapTableDefinition<&'static str, u128>,
+        keys: &[String],
+    ) -> Result<HashSet<u128>> {
+        let index = txn.open_multimap_table(definition).map_err(db_error)?;
+
+        let mut ids = HashSet::new();
+        for key in keys {
+            for id in index.get(key.as_str()).map_err(db_error)? {
+                ids.insert(id.map_err(db_error)?.value());
+            }
+        }
+
+        Ok(ids)
+    }
+
+    /// IDs matching the filter's indexed constraints, or `None` if it has none
+    ///
+    /// Each constraint is a union over its index entries; constraints are
+    /// intersected. Sources match by substring, like the other backends.
+    fn indexed_ids(txn: &ReadTransaction, filter: &IncidentFilter) -> Result<Option<HashSet<u128>>> {
+        let mut result: Option<HashSet<u128>> = None;
+
+        let mut intersect = |ids: HashSet<u128>| {
+            result = Some(match result.take() {
+                Some(current) => current.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        };
+
+        if !filter.states.is_empty() {
+            let keys: Vec<String> = filter.states.iter().map(|s| format!("{:?}", s)).collect();
+            intersect(Self::index_lookup(txn, STATE_INDEX, &keys)?);
+        }
+
+        if !filter.severities.is_empty() {
+            let keys: Vec<String> = filter.severities.iter().map(|s| format!("{:?}", s)).collect();
+            intersect(Self::index_lookup(txn, SEVERITY_INDEX, &keys)?);
+        }
+
+        if !filter.sources.is_empty() {
+            let index = txn.open_multimap_table(SOURCE_INDEX).map_err(db_error)?;
+            let mut keys = Vec::new();
+            for entry in index.iter().map_err(db_error)? {
+                let (source, _) = entry.map_err(db_error)?;
+                let source = source.value();
+                if filter.sources.iter().any(|s| source.contains(s.as_str())) {
+                    keys.push(source.to_string());
+                }
+            }
+            drop(index);
+            intersect(Self::index_lookup(txn, SOURCE_INDEX, &keys)?);
+        }
+
+        for terms in filter.required_terms() {
+            intersect(Self::index_lookup(txn, TERM_INDEX, &terms)?);
+        }
+
+        if !filter.created.is_unbounded() {
+            let start = filter.created.after.map_or(i64::MIN, |t| t.timestamp_micros());
+            let end = filter.created.before.map_or(i64::MAX, |t| t.timestamp_micros());
+
+            let index = txn.open_table(CREATED_INDEX).map_err(db_error)?;
+            let mut ids = HashSet::new();
+            for entry in index.range((start, 0)..(end, 0)).map_err(db_error)? {
+                let (key, _) = entry.map_err(db_error)?;
+                ids.insert(key.value().1);
+            }
+            intersect(ids);
+        }
+
+        Ok(result)
+    }
+
+    /// Walk the sort index for the filter's order from the page's start,
+    /// pushing matching incidents until the page is complete
+    fn scan(&self, filter: &IncidentFilter, page: &mut PageBuilder<'_>) -> Result<()> {
+        let txn = self.read()?;
+        let table = txn.open_table(INCIDENTS).map_err(db_error)?;
+        let candidates = Self::indexed_ids(&txn, filter)?;
+
+        let field = filter.sort.field;
+        let descending = filter.sort.direction == SortDirection::Desc;
+        let start = page
+            .start()
+            .map(|start| (start.key(), start.id().as_u128()));
+        let (lower, upper) = match (start, descending) {
+            (Some(start), false) => (Bound::Excluded(start), Bound::Unbounded),
+            (Some(start), true) => (Bound::Unbounded, Bound::Excluded(start)),
+            (None, _) => (Bound::Unbounded, Bound::Unbounded),
+        };
+
+        type Ids =
+            Box<dyn DoubleEndedIterator<Item = std::result::Result<u128, redb::StorageError>>>;
+        let ids: Ids = if field == SortField::CreatedAt {
+            let index = txn.open_table(CREATED_INDEX).map_err(db_error)?;
+            Box::new(
+                index
+                    .range::<(i64, u128)>((lower, upper))
+                    .map_err(db_error)?
+                    .map(|entry| entry.map(|(key, _)| key.value().1)),
+            )
+        } else {
+            // Keep the walk within this field's entries
+            let number = sort_index_field(field);
+            let within = |bound: Bound<(i64, u128)>, (edge_key, edge_id)| match bound {
+                Bound::Unbounded => Bound::Included((number, edge_key, edge_id)),
+                bound => bound.map(|(key, id)| (number, key, id)),
+            };
+            let range = (
+                within(lower, (i64::MIN, 0)),
+                within(upper, (i64::MAX, u128::MAX)),
+            );
+
+            let index = txn.open_table(SORT_INDEX).map_err(db_error)?;
+            Box::new(
+                index
+                    .range::<(u8, i64, u128)>(range)
+                    .map_err(db_error)?
+                    .map(|entry| entry.map(|(key, _)| key.value().2)),
+            )
+        };
+        let ids: Box<dyn Iterator<Item = _>> = if descending { Box::new(ids.rev()) } else { ids };
+
+        for id in ids {
+            let id = id.map_err(db_error)?;
+            if candidates.as_ref().is_some_and(|ids| !ids.contains(&id)) {
+                continue;
+            }
+            if let Some(incident) = Self::load_incident(&table, id)? {
+                if !page.push(incident) {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// All incidents matching the filter, unordered
+    fn query(&self, filter: &IncidentFilter) -> Result<Vec<Incident>> {
+        let txn = self.read()?;
+        let table = txn.open_table(INCIDENTS).map_err(db_error)?;
+
+        let mut incidents = Vec::new();
+        match Self::indexed_ids(&txn, filter)? {
+            Some(ids) => {
+                for id in ids {
+                    if let Some(incident) = Self::load_incident(&table, id)? {
+                        incidents.push(incident);
+                    }
+                }
+            }
+            None => {
+                for entry in table.iter().map_err(db_error)? {
+                    let (_, value) = entry.map_err(db_error)?;
+                    incidents.push(Self::deserialize_incident(value.value())?);
+                }
+            }
+        }
+
+        incidents.retain(|incident| filter.matches(incident));
+
+        Ok(incidents)
+    }
+}
+
+#[async_trait]
+impl IncidentStore for RedbStore {
+    async fn save_incident(&self, incident: &Incident) -> Result<()> {
+        let key = incident.id.as_u128();
+        let value = Self::serialize_incident(incident)?;
+
+        self.write(|txn| {
+            let previous = {
+                let mut table = txn.open_table(INCIDENTS).map_err(db_error)?;
+                let previous = table.insert(key, value.as_slice()).map_err(db_error)?;
+                previous
+                    .map(|bytes| Self::deserialize_incident(bytes.value()))
+                    .transpose()?
+            };
+
+            Self::update_indices(txn, key, previous.as_ref(), Some(incident))?;
+            Self::record_write(txn, previous.as_ref(), incident)
+        })?;
+
+        tracing::debug!(incident_id = %incident.id, "Incident saved to redb");
+        Ok(())
+    }
+
+    async fn get_incident(&self, id: &Uuid) -> Result<Option<Incident>> {
+        let txn = self.read()?;
+        let table = txn.open_table(INCIDENTS).map_err(db_error)?;
+        Self::load_incident(&table, id.as_u128())
+    }
+
+    async fn update_incident(&self, incident: &Incident) -> Result<()> {
+        let key = incident.id.as_u128();
+
+        let mut updated = incident.clone();
+        updated.version += 1;
+        let value = Self::serialize_incident(&updated)?;
+
+        self.write(|txn| {
+            let current = {
+                let mut table = txn.open_table(INCIDENTS).map_err(db_error)?;
+
+                let Some(current) = Self::load_incident(&table, key)? else {
+                    return Err(AppError::NotFound(format!(
+                        "Incident {} not found",
+                        incident.id
+                    )));
+                };
+
+                if current.version != incident.version {
+                    return Err(AppError::VersionConflict {
+                        resource: format!("incident {}", incident.id),
+                        expected: incident.version,
+                        actual: current.version,
+                    });
+                }
+
+                table.insert(key, value.as_slice()).map_err(db_error)?;
+                current
+            };
+
+            Self::update_indices(txn, key, Some(&current), Some(&updated))?;
+            Self::record_write(txn, Some(&current), &updated)
+        })?;
+
+        tracing::debug!(incident_id = %incident.id, "Incident updated in redb");
+        Ok(())
+    }
+
+    async fn delete_incident(&self, id: &Uuid) -> Result<()> {
+        let key = id.as_u128();
+
+        self.write(|txn| {
+            let removed = {
+                let mut table = txn.open_table(INCIDENTS).map_err(db_error)?;
+                let removed = table.remove(key).map_err(db_error)?;
+                removed
+                    .map(|bytes| Self::deserialize_incident(bytes.value()))
+                    .transpose()?
+            };
+
+            let Some(incident) = removed else {
+                return Err(AppError::NotFound(format!("Incident {} not found", id)));
+            };
+
+            Self::update_indices(txn, key, Some(&incident), None)?;
+
+            let next_sequence = Self::next_event_sequence(txn, key)?;
+            Self::append_events(txn, &event_log::events_for_delete(&incident, next_sequence))
+        })?;
+
+        tracing::debug!(incident_id = %id, "Incident deleted from redb");
+        Ok(())
+    }
+
+    async fn list_incidents(
+        &self,
+        filter: &IncidentFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<Incident>> {
+        let mut page = PageBuilder::offset(filter, page, page_size);
+        self.scan(filter, &mut page)?;
+        Ok(page.finish().incidents)
+    }
+
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        let mut page = PageBuilder::new(filter, cursor, limit)?;
+        self.scan(filter, &mut page)?;
+        Ok(page.finish())
+    }
+
+    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
+        Ok(self.query(filter)?.len() as u64)
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
+        let txn = self.read()?;
+        let index = txn.open_multimap_table(FINGERPRINT_INDEX).map_err(db_error)?;
+        let table = txn.open_table(INCIDENTS).map_err(db_error)?;
+
+        let mut incidents = Vec::new();
+        for id in index.get(fingerprint).map_err(db_error)? {
+            if let Some(incident) = Self::load_incident(&table, id.map_err(db_error)?.value())? {
+                incidents.push(incident);
+            }
+        }
+
+        Ok(incidents)
+    }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        let key = id.as_u128();
+        let txn = self.read()?;
+        let table = txn.open_table(EVENTS).map_err(db_error)?;
+
+        let mut events = Vec::new();
+        for entry in table.range((key, 0)..=(key, u64::MAX)).map_err(db_error)? {
+            let (_, value) = entry.map_err(db_error)?;
+            events.push(serde_json::from_slice(value.value()).map_err(|e| {
+                AppError::Internal(format!("Failed to deserialize incident event: {}", e))
+            })?);
+        }
+
+        Ok(events)
+    }
+
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        let key = id.as_u128();
+
+        self.write(|txn| {
+            {
+                let mut table = txn.open_table(EVENTS).map_err(db_error)?;
+                let mut stored = Vec::new();
+                for entry in table.range((key, 0)..=(key, u64::MAX)).map_err(db_error)? {
+                    stored.push(entry.map_err(db_error)?.0.value());
+                }
+                for stored_key in stored {
+                    table.remove(stored_key).map_err(db_error)?;
+                }
+            }
+
+            Self::append_events(txn, events)
+        })
+    }
+
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        let value = Self::serialize_postmortem(postmortem)?;
+
+        self.write(|txn| {
+            let mut table = txn.open_table(POSTMORTEMS).map_err(db_error)?;
+            table
+                .insert(postmortem.id.as_u128(), value.as_slice())
+                .map_err(db_error)?;
+            Ok(())
+        })?;
+
+        tracing::debug!(postmortem_id = %postmortem.id, "Post-mortem saved to redb");
+        Ok(())
+    }
+
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        let txn = self.read()?;
+        let table = txn.open_table(POSTMORTEMS).map_err(db_error)?;
+
+        table
+            .get(id.as_u128())
+            .map_err(db_error)?
+            .map(|value| Self::deserialize_postmortem(value.value()))
+            .transpose()
+    }
+
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
+        let txn = self.read()?;
+        let table = txn.open_table(POSTMORTEMS).map_err(db_error)?;
+
+        let mut postmortems = Vec::new();
+        for entry in table.iter().map_err(db_error)? {
+            let (_, value) = entry.map_err(db_error)?;
+            let postmortem = Self::deserialize_postmortem(value.value())?;
+            if incident_id.is_none_or(|id| postmortem.incident_id == *id) {
+                postmortems.push(postmortem);
+            }
+        }
+
+        Ok(postmortems)
+    }
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        let value = Self::serialize_execution(execution)?;
+
+        self.write(|txn| {
+            let mut table = txn.open_table(EXECUTIONS).map_err(db_error)?;
+            table
+                .insert(execution.id.as_u128(), value.as_slice())
+                .map_err(db_error)?;
+            Ok(())
+        })?;
+
+        tracing::debug!(execution_id = %execution.id, "Playbook execution saved to redb");
+        Ok(())
+    }
+
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
+        let txn = self.read()?;
+        let table = txn.open_table(EXECUTIONS).map_err(db_error)?;
+
+        table
+            .get(id.as_u128())
+            .map_err(db_error)?
+            .map(|value| Self::deserialize_execution(value.value()))
+            .transpose()
+    }
+
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        let txn = self.read()?;
+        let table = txn.open_table(EXECUTIONS).map_err(db_error)?;
+
+        let mut executions = Vec::new();
+        for entry in table.iter().map_err(db_error)? {
+            let (_, value) = entry.map_err(db_error)?;
+            let execution = Self::deserialize_execution(value.value())?;
+            if incident_id.is_none_or(|id| execution.incident_id == *id) {
+                executions.push(execution);
+            }
+        }
+
+        Ok(executions)
+    }
+
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        let value = Self::serialize_escalation(state)?;
+
+        self.write(|txn| {
+            let mut table = txn.open_table(ESCALATIONS).map_err(db_error)?;
+            table
+                .insert(state.incident_id.as_u128(), value.as_slice())
+                .map_err(db_error)?;
+            Ok(())
+        })?;
+
+        tracing::debug!(incident_id = %state.incident_id, "Escalation state saved to redb");
+        Ok(())
+    }
+
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        let txn = self.read()?;
+        let table = txn.open_table(ESCALATIONS).map_err(db_error)?;
+
+        table
+            .get(incident_id.as_u128())
+            .map_err(db_error)?
+            .map(|value| Self::deserialize_escalation(value.value()))
+            .transpose()
+    }
+
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
+        let txn = self.read()?;
+        let table = txn.open_table(ESCALATIONS).map_err(db_error)?;
+
+        let mut states = Vec::new();
+        for entry in table.iter().map_err(db_error)? {
+            let (_, value) = entry.map_err(db_error)?;
+            states.push(Self::deserialize_escalation(value.value())?);
+        }
+
+        Ok(states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{IncidentState, IncidentType, ResolutionMethod, Severity};
+    use tempfile::TempDir;
+
+    fn create_test_store() -> (RedbStore, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let store = RedbStore::new(temp_dir.path()).unwrap();
+        (store, temp_dir)
+    }
+
+    fn test_incident(severity: Severity) -> Incident {
+        Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            severity,
+            IncidentType::Infrastructure,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_save_and_get_incident() {
+        let (store, _temp_dir) = create_test_store();
+
+        let incident = test_incident(Severity::P1);
+        let id = incident.id;
+        store.save_incident(&incident).await.unwrap();
+
+        let retrieved = store.get_incident(&id).await.unwrap().unwrap();
+        assert_eq!(retrieved, incident);
+        assert!(store.path().ends_with(DATABASE_FILE));
+    }
+
+    #[tokio::test]
+    async fn test_update_moves_index_entries() {
+        let (store, _temp_dir) = create_test_store();
+
+        let mut incident = test_incident(Severity::P2);
+        store.save_incident(&incident).await.unwrap();
+
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        incident.severity = Severity::P0;
+        store.update_incident(&incident).await.unwrap();
+
+        let by_state = |state| IncidentFilter {
+            states: vec![state],
+            ..Default::default()
+        };
+        assert_eq!(store.count_incidents(&by_state(IncidentState::Detected)).await.unwrap(), 0);
+        assert_eq!(
+            store
+                .count_incidents(&by_state(IncidentState::Investigating))
+                .await
+                .unwrap(),
+            1
+        );
+
+        let p0 = IncidentFilter {
+            severities: vec![Severity::P0],
+            ..Default::default()
+        };
+        assert_eq!(store.count_incidents(&p0).await.unwrap(), 1);
+
+        // A writer still holding the old version is rejected
+        let err = store.update_incident(&incident).await.unwrap_err();
+        assert!(matches!(err, AppError::VersionConflict { actual: 1, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_failed_update_is_rolled_back() {
+        let (store, _temp_dir) = create_test_store();
+
+        let incident = test_incident(Severity::P2);
+        store.save_incident(&incident).await.unwrap();
+
+        let mut stale = incident.clone();
+        stale.version = 7;
+        stale.severity = Severity::P0;
+        assert!(store.update_incident(&stale).await.is_err());
+
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(stored, incident);
+        assert_eq!(store.get_incident_events(&incident.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_index_entries() {
+        let (store, _temp_dir) = create_test_store();
+
+        let mut incident = test_incident(Severity::P1);
+        incident.fingerprint = Some("fp-delete".to_string());
+        store.save_incident(&incident).await.unwrap();
+        store.delete_incident(&incident.id).await.unwrap();
+
+        assert!(store.get_incident(&incident.id).await.unwrap().is_none());
+        assert!(store.find_by_fingerprint("fp-delete").await.unwrap().is_empty());
+
+        let p1 = IncidentFilter {
+            severities: vec![Severity::P1],
+            ..Default::default()
+        };
+        assert_eq!(store.count_incidents(&p1).await.unwrap(), 0);
+        assert!(matches!(
+            store.delete_incident(&incident.id).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_source_filter_matches_substring() {
+        let (store, _temp_dir) = create_test_store();
+
+        let mut incident = test_incident(Severity::P2);
+        incident.source = "prometheus-eu".to_string();
+        store.save_incident(&incident).await.unwrap();
+        store.save_incident(&test_incident(Severity::P2)).await.unwrap();
+
+        let filter = IncidentFilter {
+            sources: vec!["prometheus".to_string()],
+            severities: vec![Severity::P2],
+            ..Default::default()
+        };
+        let found = store.list_incidents(&filter, 0, 10).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, incident.id);
+    }
+
+    #[tokio::test]
+    async fn test_data_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut incident = test_incident(Severity::P0);
+        incident.fingerprint = Some("fp-reopen".to_string());
+
+        {
+            let store = RedbStore::new(temp_dir.path()).unwrap();
+            store.save_incident(&incident).await.unwrap();
+            incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+            store.update_incident(&incident).await.unwrap();
+        }
+
+        let store = RedbStore::new(temp_dir.path()).unwrap();
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, IncidentState::Investigating);
+        assert_eq!(stored.version, 1);
+        assert_eq!(store.find_by_fingerprint("fp-reopen").await.unwrap().len(), 1);
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert!(events.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+        assert_eq!(
+            store
+                .get_incident_at(&incident.id, chrono::Utc::now())
+                .await
+                .unwrap()
+                .unwrap(),
+            stored
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_by_assignee_and_group() {
+        let (store, _temp_dir) = create_test_store();
+        let group = Uuid::new_v4();
+
+        let mut grouped = test_incident(Severity::P1);
+        grouped.correlation_group_id = Some(group);
+        grouped.assignees.push("alice".to_string());
+        store.save_incident(&grouped).await.unwrap();
+
+        let mut other = test_incident(Severity::P1);
+        other.assignees.push("alice".to_string());
+        store.save_incident(&other).await.unwrap();
+
+        let by_group = IncidentFilter {
+            assignees: vec!["alice".to_string()],
+            correlation_group_id: Some(group),
+            ..Default::default()
+        };
+        let page = store.query_incidents(&by_group, None, 10).await.unwrap();
+        assert_eq!(page.incidents.len(), 1);
+        assert_eq!(page.incidents[0].id, grouped.id);
+
+        // Reassigning removes the old assignee term
+        grouped.assignees = vec!["bob".to_string()];
+        store.update_incident(&grouped).await.unwrap();
+        assert_eq!(store.count_incidents(&by_group).await.unwrap(), 0);
+
+        let created = IncidentFilter {
+            created: query::TimeRange::new(None, Some(other.created_at)),
+            ..Default::default()
+        };
+        assert_eq!(store.count_incidents(&created).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages_walk_every_sort_index() {
+        let (store, _temp_dir) = create_test_store();
+        let start = chrono::Utc::now() - chrono::Duration::hours(1);
+        let severities = [Severity::P0, Severity::P1, Severity::P2, Severity::P3];
+
+        let mut incidents = Vec::new();
+        for i in 0..12i64 {
+            let mut incident = test_incident(severities[i as usize % 4]);
+            // Repeated creation times and severities exercise the ID tie-break
+            incident.created_at = start + chrono::Duration::minutes(i % 5);
+            if i % 3 == 0 {
+                incident.resolve(
+                    "user".to_string(),
+                    ResolutionMethod::Manual,
+                    "Fixed".to_string(),
+                    None,
+                );
+            }
+            store.save_incident(&incident).await.unwrap();
+            incidents.push(incident);
+        }
+
+        for field in SortField::ALL {
+            for direction in [SortDirection::Asc, SortDirection::Desc] {
+                let filter = IncidentFilter {
+                    sort: query::IncidentSort::new(field, direction),
+                    ..Default::default()
+                };
+                let mut expected = incidents.clone();
+                query::sort_incidents(&mut expected, &filter.sort);
+                let expected: Vec<Uuid> = expected.iter().map(|i| i.id).collect();
+
+                let mut seen = Vec::new();
+                let mut cursor = None;
+                loop {
+                    let page = store
+                        .query_incidents(&filter, cursor.as_deref(), 5)
+                        .await
+                        .unwrap();
+                    seen.extend(page.incidents.iter().map(|i| i.id));
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                assert_eq!(seen, expected, "{:?}", filter.sort);
+
+                let second = store.list_incidents(&filter, 1, 5).await.unwrap();
+                let second: Vec<Uuid> = second.iter().map(|i| i.id).collect();
+                assert_eq!(second, expected[5..10]);
+            }
+        }
+
+        // Updates move the incident in the updated-time index
+        let mut touched = store.get_incident(&incidents[4].id).await.unwrap().unwrap();
+        touched.title = "Touched".to_string();
+        touched.updated_at = chrono::Utc::now() + chrono::Duration::minutes(1);
+        store.update_incident(&touched).await.unwrap();
+        let latest = IncidentFilter {
+            sort: query::IncidentSort::new(SortField::UpdatedAt, SortDirection::Desc),
+            ..Default::default()
+        };
+        let page = store.query_incidents(&latest, None, 1).await.unwrap();
+        assert_eq!(page.incidents[0].id, touched.id);
+        assert!(page.next_cursor.is_some());
+    }
+}
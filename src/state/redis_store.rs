@@ -1,19 +1,139 @@
 use crate::error::{AppError, Result};
-use crate::models::Incident;
-use crate::state::{IncidentFilter, IncidentStore};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::event_log::{self, IncidentEvent};
+use crate::state::query::{
+    self, IncidentCursor, IncidentFilter, IncidentPage, PageBuilder, SortDirection, SortField,
+};
+use crate::state::IncidentStore;
 use async_trait::async_trait;
-use redis::aio::ConnectionManager;
-use redis::{AsyncCommands, Client};
-use std::sync::Arc;
+use redis::aio::{ConnectionLike, ConnectionManager};
+use redis::cluster::ClusterClientBuilder;
+use redis::cluster_async::ClusterConnection;
+use redis::{AsyncCommands, Client, RedisFuture};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use uuid::Uuid;
 
+/// Write an incident and append events to its log in one atomic step
+///
+/// KEYS[1] = incident key, KEYS[2] = event log key, KEYS[3] = event sequence
+/// key. ARGV[1] = incident JSON the write was based on ('' if absent),
+/// ARGV[2] = event sequence it was based on, ARGV[3] = new JSON ('' to
+/// delete), ARGV[4..] = events. Returns 0 without writing anything if the
+/// incident or its log changed since they were read, 1 otherwise.
+const WRITE_INCIDENT_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1]) or ''
+local sequence = redis.call('GET', KEYS[3]) or '0'
+if current ~= ARGV[1] or tonumber(sequence) ~= tonumber(ARGV[2]) then
+    return 0
+end
+if ARGV[3] == '' then
+    redis.call('DEL', KEYS[1])
+else
+    redis.call('SET', KEYS[1], ARGV[3])
+end
+local count = #ARGV - 3
+if count > 0 then
+    redis.call('RPUSH', KEYS[2], unpack(ARGV, 4))
+    redis.call('INCRBY', KEYS[3], count)
+end
+return 1
+"#;
+
+/// Replace an incident's event log in one atomic step
+///
+/// KEYS[1] = event log key, KEYS[2] = event sequence key. ARGV[1] = next
+/// event sequence, ARGV[2..] = events.
+const REPLACE_EVENTS_SCRIPT: &str = r#"
+redis.call('DEL', KEYS[1])
+if #ARGV > 1 then
+    redis.call('RPUSH', KEYS[1], unpack(ARGV, 2))
+end
+redis.call('SET', KEYS[2], ARGV[1])
+return 1
+"#;
+
+/// Clear an incident's pending index marker if it still holds `ARGV[2]`
+///
+/// KEYS[1] = pending index hash. ARGV[1] = incident ID, ARGV[2] = incident
+/// JSON the marker was set with. A marker left by an earlier write whose
+/// index update failed holds an older JSON and is kept for repair.
+const CLEAR_INDEX_PENDING_SCRIPT: &str = r#"
+if redis.call('HGET', KEYS[1], ARGV[1]) == ARGV[2] then
+    return redis.call('HDEL', KEYS[1], ARGV[1])
+end
+return 0
+"#;
+
+/// Number of times an incident write is retried after losing a race with
+/// another writer before the error is surfaced
+const WRITE_RETRIES: u32 = 16;
+
+/// Number of times a cluster request is retried across MOVED/ASK redirects
+/// and failovers before the error is surfaced
+const CLUSTER_RETRIES: u32 = 6;
+
+/// Number of incidents fetched per MGET
+const LOAD_BATCH_SIZE: usize = 200;
+
+/// Connection to either a standalone Redis server or a Redis Cluster
+///
+/// The cluster connection follows MOVED/ASK redirects and refreshes its slot
+/// map after a failover, so the store code is the same for both modes.
+#[derive(Clone)]
+enum RedisConnection {
+    Standalone(ConnectionManager),
+    Cluster(ClusterConnection),
+}
+
+impl ConnectionLike for RedisConnection {
+    fn req_packed_command<'a>(&'a mut self, cmd: &'a redis::Cmd) -> RedisFuture<'a, redis::Value> {
+        match self {
+            Self::Standalone(conn) => conn.req_packed_command(cmd),
+            Self::Cluster(conn) => conn.req_packed_command(cmd),
+        }
+    }
+
+    fn req_packed_commands<'a>(
+        &'a mut self,
+        cmd: &'a redis::Pipeline,
+        offset: usize,
+        count: usize,
+    ) -> RedisFuture<'a, Vec<redis::Value>> {
+        match self {
+            Self::Standalone(conn) => conn.req_packed_commands(cmd, offset, count),
+            Self::Cluster(conn) => conn.req_packed_commands(cmd, offset, count),
+        }
+    }
+
+    fn get_db(&self) -> i64 {
+        match self {
+            Self::Standalone(conn) => conn.get_db(),
+            Self::Cluster(conn) => conn.get_db(),
+        }
+    }
+}
+
+/// Strip any hash tag braces from a key prefix
+fn bare_key_prefix(prefix: &str) -> &str {
+    prefix.trim_matches(|c| c == '{' || c == '}')
+}
+
+/// Wrap a key prefix in a hash tag so all keys sharing it map to one slot
+fn cluster_key_prefix(prefix: &str) -> String {
+    format!("{{{}}}", bare_key_prefix(prefix))
+}
+
 /// Redis-based persistent incident store
 #[derive(Clone)]
 pub struct RedisStore {
-    #[allow(dead_code)]
-    client: Arc<Client>,
-    connection: ConnectionManager,
+    connection: RedisConnection,
+    /// Prefix of index and other shared keys (a hash tag in cluster mode)
     key_prefix: String,
+    /// Prefix of per-incident keys, which are tagged with the incident ID in
+    /// cluster mode
+    incident_prefix: String,
 }
 
 impl RedisStore {
@@ -28,10 +148,63 @@ impl RedisStore {
             AppError::Internal(format!("Failed to create Redis client: {}", e))
         })?;
 
-        let connection = ConnectionManager::new(client.clone())
+        let connection = ConnectionManager::new(client)
             .await
             .map_err(|e| AppError::Internal(format!("Failed to connect to Redis: {}", e)))?;
 
+        Self::connect(
+            RedisConnection::Standalone(connection),
+            prefix.to_string(),
+            prefix.to_string(),
+        )
+        .await
+    }
+
+    /// Create a new Redis Cluster store
+    pub async fn new_cluster(nodes: &[String]) -> Result<Self> {
+        Self::new_cluster_with_prefix(nodes, "llm-im").await
+    }
+
+    /// Create a new Redis Cluster store with custom key prefix
+    ///
+    /// An incident, its event log and its event sequence are tagged with the
+    /// incident ID (`llm-im:incident:{<id>}`), so incidents spread across the
+    /// cluster while each write stays within one slot. Index keys are tagged
+    /// with the prefix (`{llm-im}:severity:P0`) so filter intersections can
+    /// combine them. They live in a different slot from the incident, so
+    /// they cannot be updated in the incident's atomic write; see
+    /// [`Self::repair_indices`] for how a failed index update is recovered.
+    pub async fn new_cluster_with_prefix(nodes: &[String], prefix: &str) -> Result<Self> {
+        if nodes.is_empty() {
+            return Err(AppError::Configuration(
+                "Redis cluster requires at least one node".to_string(),
+            ));
+        }
+
+        let client = ClusterClientBuilder::new(nodes.to_vec())
+            .retries(CLUSTER_RETRIES)
+            .build()
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to create Redis cluster client: {}", e))
+            })?;
+
+        let connection = client.get_async_connection().await.map_err(|e| {
+            AppError::Internal(format!("Failed to connect to Redis cluster: {}", e))
+        })?;
+
+        Self::connect(
+            RedisConnection::Cluster(connection),
+            cluster_key_prefix(prefix),
+            bare_key_prefix(prefix).to_string(),
+        )
+        .await
+    }
+
+    async fn connect(
+        connection: RedisConnection,
+        key_prefix: String,
+        incident_prefix: String,
+    ) -> Result<Self> {
         // Test connection
         let mut test_conn = connection.clone();
         redis::cmd("PING")
@@ -39,18 +212,52 @@ impl RedisStore {
             .await
             .map_err(|e| AppError::Internal(format!("Redis connection test failed: {}", e)))?;
 
-        tracing::info!("Initialized Redis store with prefix '{}'", prefix);
+        tracing::info!("Initialized Redis store with prefix '{}'", key_prefix);
 
-        Ok(Self {
-            client: Arc::new(client),
+        let store = Self {
             connection,
-            key_prefix: prefix.to_string(),
-        })
+            key_prefix,
+            incident_prefix,
+        };
+
+        // Data written before the query or sort indexes existed
+        let (indexed, sorted, stored): (bool, bool, u64) = redis::pipe()
+            .exists(store.created_index_key())
+            .exists(store.sort_index_key(SortField::State))
+            .scard(store.incidents_set_key())
+            .query_async(&mut test_conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to check Redis indexes: {}", e)))?;
+        if !(indexed && sorted) && stored > 0 {
+            store.rebuild_indices().await?;
+        }
+        store.repair_indices().await?;
+
+        Ok(store)
+    }
+
+    /// Whether this store is connected to a Redis Cluster
+    pub fn is_cluster(&self) -> bool {
+        matches!(self.connection, RedisConnection::Cluster(_))
+    }
+
+    /// Incident ID as it appears in per-incident keys (a hash tag in cluster
+    /// mode)
+    fn incident_tag(&self, id: &Uuid) -> String {
+        if self.is_cluster() {
+            format!("{{{}}}", id)
+        } else {
+            id.to_string()
+        }
     }
 
     /// Get incident key
     fn incident_key(&self, id: &Uuid) -> String {
-        format!("{}:incident:{}", self.key_prefix, id)
+        format!(
+            "{}:incident:{}",
+            self.incident_prefix,
+            self.incident_tag(id)
+        )
     }
 
     /// Get all incidents set key
@@ -78,6 +285,183 @@ impl RedisStore {
         format!("{}:source:{}", self.key_prefix, source)
     }
 
+    /// Get incidents by query term index key (see [`query::index_terms`])
+    fn term_index_key(&self, term: &str) -> String {
+        format!("{}:term:{}", self.key_prefix, term)
+    }
+
+    /// Get known sources set key
+    fn sources_set_key(&self) -> String {
+        format!("{}:sources", self.key_prefix)
+    }
+
+    /// Get incidents by creation time sorted set key
+    fn created_index_key(&self) -> String {
+        format!("{}:by_created", self.key_prefix)
+    }
+
+    /// Get sorted set key ordering incidents by a sort field
+    ///
+    /// Scores are [`SortField::key`]; members with equal scores are ordered
+    /// by ID, like the query sort's tie-break.
+    fn sort_index_key(&self, field: SortField) -> String {
+        let name = match field {
+            SortField::CreatedAt => return self.created_index_key(),
+            SortField::UpdatedAt => "updated",
+            SortField::ResolvedAt => "resolved",
+            SortField::Severity => "severity",
+            SortField::State => "state",
+        };
+        format!("{}:by_{}", self.key_prefix, name)
+    }
+
+    /// Sorted set score for a timestamp
+    ///
+    /// Microseconds stay below 2^53 until the year 2255, so scores are exact.
+    fn time_score(at: chrono::DateTime<chrono::Utc>) -> i64 {
+        at.timestamp_micros()
+    }
+
+    /// Get incident event log key
+    fn events_key(&self, id: &Uuid) -> String {
+        format!("{}:events:{}", self.incident_prefix, self.incident_tag(id))
+    }
+
+    /// Get incident event sequence counter key
+    fn events_sequence_key(&self, id: &Uuid) -> String {
+        format!(
+            "{}:events_seq:{}",
+            self.incident_prefix,
+            self.incident_tag(id)
+        )
+    }
+
+    /// Get key of the hash of incidents whose index update has not finished,
+    /// mapping each to the incident JSON its index entries reflect
+    fn index_pending_key(&self) -> String {
+        format!("{}:index_pending", self.key_prefix)
+    }
+
+    /// Write an incident and the events describing the write atomically,
+    /// then move its index entries
+    ///
+    /// `change` receives the stored incident and returns the incident to
+    /// store, or `None` to delete it. The write is applied only if neither
+    /// the incident nor its event sequence changed since they were read, and
+    /// is retried against the new state otherwise.
+    ///
+    /// The index keys sit in another slot in cluster mode, so they are
+    /// updated in a second step. The incident is marked pending in
+    /// [`Self::index_pending_key`] first and unmarked once its index entries
+    /// are moved; if that step fails, the marker remains for
+    /// [`Self::repair_indices`].
+    async fn write_incident<F>(&self, id: &Uuid, change: F) -> Result<()>
+    where
+        F: Fn(Option<&Incident>) -> Result<Option<Incident>> + Send,
+    {
+        let key = self.incident_key(id);
+        let events_key = self.events_key(id);
+        let sequence_key = self.events_sequence_key(id);
+        let script = redis::Script::new(WRITE_INCIDENT_SCRIPT);
+        let mut conn = self.connection.clone();
+
+        for _ in 0..WRITE_RETRIES {
+            let (current, next_sequence): (Option<String>, Option<u64>) = redis::pipe()
+                .get(&key)
+                .get(&sequence_key)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to read incident: {}", e)))?;
+            let next_sequence = next_sequence.unwrap_or(0);
+
+            let before = current
+                .as_deref()
+                .map(Self::deserialize_incident)
+                .transpose()?;
+            let after = change(before.as_ref())?;
+
+            let events = match (&before, &after) {
+                (_, Some(after)) => {
+                    event_log::events_for_write(before.as_ref(), after, next_sequence)
+                }
+                (Some(before), None) => event_log::events_for_delete(before, next_sequence),
+                (None, None) => Vec::new(),
+            };
+
+            let indexed = current.as_deref().unwrap_or("");
+            let _: bool = conn
+                .hset_nx(self.index_pending_key(), id.to_string(), indexed)
+                .await
+                .map_err(|e| {
+                    AppError::Internal(format!("Failed to mark incident index pending: {}", e))
+                })?;
+
+            let mut invocation = script.prepare_invoke();
+            invocation
+                .key(&key)
+                .key(&events_key)
+                .key(&sequence_key)
+                .arg(current.as_deref().unwrap_or(""))
+                .arg(next_sequence)
+                .arg(
+                    after
+                        .as_ref()
+                        .map(Self::serialize_incident)
+                        .transpose()?
+                        .unwrap_or_default(),
+                );
+            for event in &events {
+                invocation.arg(serde_json::to_string(event).map_err(|e| {
+                    AppError::Internal(format!("Failed to serialize incident event: {}", e))
+                })?);
+            }
+
+            let written: i64 = invocation
+                .invoke_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to write incident: {}", e)))?;
+            if written == 1 {
+                self.update_indices(before.as_ref(), after.as_ref()).await?;
+                return self.clear_index_pending(id, indexed).await;
+            }
+        }
+
+        Err(AppError::Internal(format!(
+            "Incident {} kept changing while being written",
+            id
+        )))
+    }
+
+    /// Get post-mortem key
+    fn postmortem_key(&self, id: &Uuid) -> String {
+        format!("{}:postmortem:{}", self.key_prefix, id)
+    }
+
+    /// Get all post-mortems set key
+    fn postmortems_set_key(&self) -> String {
+        format!("{}:postmortems", self.key_prefix)
+    }
+
+    /// Get playbook execution key
+    fn execution_key(&self, id: &Uuid) -> String {
+        format!("{}:playbook_execution:{}", self.key_prefix, id)
+    }
+
+    /// Get all playbook executions set key
+    fn executions_set_key(&self) -> String {
+        format!("{}:playbook_executions", self.key_prefix)
+    }
+
+    /// Get escalation state key
+    fn escalation_key(&self, incident_id: &Uuid) -> String {
+        format!("{}:escalation:{}", self.key_prefix, incident_id)
+    }
+
+    /// Get all escalation states set key
+    fn escalations_set_key(&self) -> String {
+        format!("{}:escalations", self.key_prefix)
+    }
+
     /// Serialize incident to JSON
     fn serialize_incident(incident: &Incident) -> Result<String> {
         serde_json::to_string(incident).map_err(|e| {
@@ -92,417 +476,830 @@ impl RedisStore {
         })
     }
 
-    /// Update indices for an incident
-    async fn update_indices(&mut self, incident: &Incident) -> Result<()> {
-        let incident_id_str = incident.id.to_string();
+    /// Index keys an incident is a member of
+    fn index_keys(&self, incident: &Incident) -> BTreeSet<String> {
+        let mut keys = BTreeSet::new();
+        keys.insert(self.severity_index_key(&format!("{:?}", incident.severity)));
+        keys.insert(self.state_index_key(&format!("{:?}", incident.state)));
+        keys.insert(self.source_index_key(&incident.source));
+        if let Some(ref fingerprint) = incident.fingerprint {
+            keys.insert(self.fingerprint_key(fingerprint));
+        }
+        for term in query::index_terms(incident) {
+            keys.insert(self.term_index_key(&term));
+        }
+        keys
+    }
+
+    /// Move an incident's index memberships from `before` to `after`
+    ///
+    /// Only the sets whose membership actually changes are touched, so a
+    /// state change removes the incident from its old state index.
+    async fn update_indices(&self, before: Option<&Incident>, after: Option<&Incident>) -> Result<()> {
+        let Some(id) = after.or(before).map(|i| i.id.to_string()) else {
+            return Ok(());
+        };
+
+        let old_keys = before.map(|i| self.index_keys(i)).unwrap_or_default();
+        let new_keys = after.map(|i| self.index_keys(i)).unwrap_or_default();
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for key in old_keys.difference(&new_keys) {
+            pipe.srem(key, &id).ignore();
+        }
+        for key in new_keys.difference(&old_keys) {
+            pipe.sadd(key, &id).ignore();
+        }
+
+        match after {
+            Some(incident) => {
+                pipe.sadd(self.incidents_set_key(), &id).ignore();
+                pipe.sadd(self.sources_set_key(), &incident.source).ignore();
+                for field in SortField::ALL {
+                    pipe.zadd(self.sort_index_key(field), &id, field.key(incident))
+                        .ignore();
+                }
+            }
+            None => {
+                pipe.srem(self.incidents_set_key(), &id).ignore();
+                for field in SortField::ALL {
+                    pipe.zrem(self.sort_index_key(field), &id).ignore();
+                }
+            }
+        }
 
-        // Add to all incidents set
-        let _: () = self
-            .connection
-            .sadd(self.incidents_set_key(), &incident_id_str)
+        let mut conn = self.connection.clone();
+        let _: () = pipe
+            .query_async(&mut conn)
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to update incidents set: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Failed to update incident indices: {}", e)))?;
+
+        Ok(())
+    }
 
-        // Add to severity index
-        let severity_str = format!("{:?}", incident.severity);
-        let _: () = self
-            .connection
-            .sadd(self.severity_index_key(&severity_str), &incident_id_str)
+    /// Clear an incident's pending index marker if it was set with `indexed`
+    async fn clear_index_pending(&self, id: &Uuid, indexed: &str) -> Result<()> {
+        let mut conn = self.connection.clone();
+        let _: i64 = redis::Script::new(CLEAR_INDEX_PENDING_SCRIPT)
+            .key(self.index_pending_key())
+            .arg(id.to_string())
+            .arg(indexed)
+            .invoke_async(&mut conn)
             .await
             .map_err(|e| {
-                AppError::Internal(format!("Failed to update severity index: {}", e))
+                AppError::Internal(format!("Failed to clear incident index marker: {}", e))
             })?;
+        Ok(())
+    }
 
-        // Add to state index
-        let state_str = format!("{:?}", incident.state);
-        let _: () = self
-            .connection
-            .sadd(self.state_index_key(&state_str), &incident_id_str)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to update state index: {}", e)))?;
+    /// Move the index entries of every incident whose index update did not
+    /// finish to match the stored incident, returning how many were repaired
+    ///
+    /// An incident write and its index update are separate steps, so a
+    /// failure or crash between them leaves the incident stored but indexed
+    /// as before (or not at all). Such incidents keep their pending marker,
+    /// which records the state their index entries reflect. Called on
+    /// connect; safe to run at any time, including alongside writes.
+    pub async fn repair_indices(&self) -> Result<usize> {
+        let mut conn = self.connection.clone();
+        let pending: HashMap<String, String> =
+            conn.hgetall(self.index_pending_key()).await.map_err(|e| {
+                AppError::Internal(format!("Failed to read pending incident indexes: {}", e))
+            })?;
 
-        // Add to source index
-        let _: () = self
-            .connection
-            .sadd(self.source_index_key(&incident.source), &incident_id_str)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to update source index: {}", e)))?;
+        for (id, indexed) in &pending {
+            let id = Uuid::parse_str(id).map_err(|e| {
+                AppError::Internal(format!("Invalid pending incident ID {}: {}", id, e))
+            })?;
+            self.repair_incident_indices(&id, indexed).await?;
+        }
 
-        // Add to fingerprint index if present
-        if let Some(ref fingerprint) = incident.fingerprint {
-            let _: () = self
-                .connection
-                .sadd(self.fingerprint_key(fingerprint), &incident_id_str)
+        if !pending.is_empty() {
+            tracing::warn!(incidents = pending.len(), "Repaired Redis incident indexes");
+        }
+        Ok(pending.len())
+    }
+
+    /// Move an incident's index entries from the state in its pending
+    /// marker to the stored incident
+    ///
+    /// A write can land between reading the incident and moving the entries,
+    /// so the incident is read again afterwards and the move repeated until
+    /// it holds still.
+    async fn repair_incident_indices(&self, id: &Uuid, marker: &str) -> Result<()> {
+        let key = self.incident_key(id);
+        let mut conn = self.connection.clone();
+        let read = |json: &str| -> Result<Option<Incident>> {
+            (!json.is_empty())
+                .then(|| Self::deserialize_incident(json))
+                .transpose()
+        };
+
+        let mut indexed = marker.to_string();
+        loop {
+            let current: Option<String> = conn
+                .get(&key)
                 .await
-                .map_err(|e| {
-                    AppError::Internal(format!("Failed to update fingerprint index: {}", e))
-                })?;
+                .map_err(|e| AppError::Internal(format!("Failed to get incident: {}", e)))?;
+            let current = current.unwrap_or_default();
+
+            self.update_indices(read(&indexed)?.as_ref(), read(&current)?.as_ref())
+                .await?;
+
+            let latest: Option<String> = conn
+                .get(&key)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to get incident: {}", e)))?;
+            if latest.unwrap_or_default() == current {
+                break;
+            }
+            indexed = current;
         }
 
-        Ok(())
+        self.clear_index_pending(id, marker).await
     }
 
-    /// Remove incident from indices
-    async fn remove_from_indices(&mut self, incident: &Incident) -> Result<()> {
-        let incident_id_str = incident.id.to_string();
+    /// Rebuild every index from the stored incidents
+    ///
+    /// Called on connect when the creation-time index is missing, which is
+    /// the case for data written before the query indexes existed.
+    pub async fn rebuild_indices(&self) -> Result<()> {
+        let mut conn = self.connection.clone();
 
-        // Remove from all incidents set
-        let _: () = self
-            .connection
-            .srem(self.incidents_set_key(), &incident_id_str)
+        let ids: Vec<String> = conn
+            .smembers(self.incidents_set_key())
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to update incidents set: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Failed to get all incidents: {}", e)))?;
 
-        // Remove from severity index
-        let severity_str = format!("{:?}", incident.severity);
-        let _: () = self
-            .connection
-            .srem(self.severity_index_key(&severity_str), &incident_id_str)
-            .await
-            .map_err(|e| {
-                AppError::Internal(format!("Failed to update severity index: {}", e))
-            })?;
+        let incidents = self.load_incidents(&ids).await?;
+        for incident in &incidents {
+            self.update_indices(None, Some(incident)).await?;
+        }
 
-        // Remove from state index
-        let state_str = format!("{:?}", incident.state);
-        let _: () = self
-            .connection
-            .srem(self.state_index_key(&state_str), &incident_id_str)
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to update state index: {}", e)))?;
+        tracing::info!(incidents = incidents.len(), "Rebuilt Redis incident indexes");
+        Ok(())
+    }
+
+    /// Store the union of `keys` in a temporary set and return its key
+    async fn union_key(&self, keys: Vec<String>) -> Result<String> {
+        if keys.len() == 1 {
+            return Ok(keys.into_iter().next().unwrap_or_default());
+        }
+
+        let temp_key = format!("{}:temp:union:{}", self.key_prefix, Uuid::new_v4());
+        let mut conn = self.connection.clone();
 
-        // Remove from source index
-        let _: () = self
-            .connection
-            .srem(self.source_index_key(&incident.source), &incident_id_str)
+        let _: () = redis::pipe()
+            .cmd("SUNIONSTORE")
+            .arg(&temp_key)
+            .arg(&keys)
+            .ignore()
+            .expire(&temp_key, 60)
+            .ignore()
+            .query_async(&mut conn)
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to update source index: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Failed to union index sets: {}", e)))?;
 
-        // Remove from fingerprint index if present
-        if let Some(ref fingerprint) = incident.fingerprint {
-            let _: () = self
-                .connection
-                .srem(self.fingerprint_key(fingerprint), &incident_id_str)
-                .await
-                .map_err(|e| {
-                    AppError::Internal(format!("Failed to update fingerprint index: {}", e))
-                })?;
-        }
+        Ok(temp_key)
+    }
 
-        Ok(())
+    /// Whether the filter has constraints that
+    /// [`get_filtered_incident_ids`](Self::get_filtered_incident_ids) answers
+    /// from the indexes, rather than returning every incident
+    fn has_indexed_constraints(filter: &IncidentFilter) -> bool {
+        !filter.severities.is_empty()
+            || !filter.states.is_empty()
+            || !filter.sources.is_empty()
+            || !filter.required_terms().is_empty()
+            || !filter.created.is_unbounded()
     }
 
-    /// Get incident IDs matching filter
-    async fn get_filtered_incident_ids(&mut self, filter: &IncidentFilter) -> Result<Vec<String>> {
-        let mut sets_to_intersect: Vec<String> = Vec::new();
+    /// Get incident IDs matching the filter's indexed constraints
+    ///
+    /// Each constraint is a union over its index sets; constraints are
+    /// intersected with SINTER. The creation-time range is read from the
+    /// sorted set. The caller still checks the full filter on each incident.
+    async fn get_filtered_incident_ids(&self, filter: &IncidentFilter) -> Result<Vec<String>> {
+        let mut conn = self.connection.clone();
+        let mut groups: Vec<Vec<String>> = Vec::new();
 
-        // If severity filter is specified
         if !filter.severities.is_empty() {
-            let severity_keys: Vec<String> = filter
-                .severities
+            groups.push(
+                filter
+                    .severities
+                    .iter()
+                    .map(|s| self.severity_index_key(&format!("{:?}", s)))
+                    .collect(),
+            );
+        }
+
+        if !filter.states.is_empty() {
+            groups.push(
+                filter
+                    .states
+                    .iter()
+                    .map(|s| self.state_index_key(&format!("{:?}", s)))
+                    .collect(),
+            );
+        }
+
+        if !filter.sources.is_empty() {
+            // Sources match by substring, so find the known sources first
+            let sources: Vec<String> = conn
+                .smembers(self.sources_set_key())
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to get sources: {}", e)))?;
+
+            let keys: Vec<String> = sources
                 .iter()
-                .map(|s| self.severity_index_key(&format!("{:?}", s)))
+                .filter(|source| filter.sources.iter().any(|s| source.contains(s.as_str())))
+                .map(|source| self.source_index_key(source))
                 .collect();
+            if keys.is_empty() {
+                return Ok(Vec::new());
+            }
+            groups.push(keys);
+        }
 
-            // For multiple severities, we need to union them
-            if severity_keys.len() == 1 {
-                sets_to_intersect.push(severity_keys[0].clone());
-            } else {
-                // Create temporary union key
-                let temp_key = format!("{}:temp:severity_union:{}", self.key_prefix, Uuid::new_v4());
-                let _: () = redis::cmd("SUNIONSTORE")
-                    .arg(&temp_key)
-                    .arg(&severity_keys)
-                    .query_async(&mut self.connection)
+        for terms in filter.required_terms() {
+            groups.push(terms.iter().map(|t| self.term_index_key(t)).collect());
+        }
+
+        let mut sets_to_intersect = Vec::with_capacity(groups.len());
+        for keys in groups {
+            sets_to_intersect.push(self.union_key(keys).await?);
+        }
+
+        // Get incident IDs
+        let incident_ids: Vec<String> = if sets_to_intersect.is_empty() {
+            if filter.created.is_unbounded() {
+                // No filters, return all incidents
+                conn.smembers(self.incidents_set_key())
                     .await
                     .map_err(|e| {
-                        AppError::Internal(format!("Failed to union severity sets: {}", e))
-                    })?;
+                        AppError::Internal(format!("Failed to get all incidents: {}", e))
+                    })?
+            } else {
+                Vec::new()
+            }
+        } else {
+            redis::cmd("SINTER")
+                .arg(&sets_to_intersect)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| {
+                    AppError::Internal(format!("Failed to intersect incident sets: {}", e))
+                })?
+        };
 
-                // Set expiration on temp key
-                let _: () = self
-                    .connection
-                    .expire(&temp_key, 60)
-                    .await
-                    .map_err(|e| AppError::Internal(format!("Failed to expire temp key: {}", e)))?;
+        if filter.created.is_unbounded() {
+            return Ok(incident_ids);
+        }
 
-                sets_to_intersect.push(temp_key);
-            }
+        let min = filter
+            .created
+            .after
+            .map_or("-inf".to_string(), |t| Self::time_score(t).to_string());
+        let max = filter
+            .created
+            .before
+            .map_or("+inf".to_string(), |t| format!("({}", Self::time_score(t)));
+        let in_range: Vec<String> = conn
+            .zrangebyscore(self.created_index_key(), min, max)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to query created index: {}", e)))?;
+
+        if sets_to_intersect.is_empty() {
+            return Ok(in_range);
         }
 
-        // If state filter is specified
-        if !filter.states.is_empty() {
-            let state_keys: Vec<String> = filter
-                .states
+        let in_range: HashSet<String> = in_range.into_iter().collect();
+        Ok(incident_ids
+            .into_iter()
+            .filter(|id| in_range.contains(id))
+            .collect())
+    }
+
+    /// Load incidents by ID, skipping any deleted in the meantime
+    async fn load_incidents(&self, ids: &[String]) -> Result<Vec<Incident>> {
+        let mut conn = self.connection.clone();
+        let mut incidents = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(LOAD_BATCH_SIZE) {
+            let keys: Vec<String> = chunk
                 .iter()
-                .map(|s| self.state_index_key(&format!("{:?}", s)))
+                .filter_map(|id| Uuid::parse_str(id).ok())
+                .map(|id| self.incident_key(&id))
                 .collect();
+            if keys.is_empty() {
+                continue;
+            }
 
-            if state_keys.len() == 1 {
-                sets_to_intersect.push(state_keys[0].clone());
-            } else {
-                let temp_key = format!("{}:temp:state_union:{}", self.key_prefix, Uuid::new_v4());
-                let _: () = redis::cmd("SUNIONSTORE")
-                    .arg(&temp_key)
-                    .arg(&state_keys)
-                    .query_async(&mut self.connection)
-                    .await
-                    .map_err(|e| {
-                        AppError::Internal(format!("Failed to union state sets: {}", e))
-                    })?;
-
-                let _: () = self
-                    .connection
-                    .expire(&temp_key, 60)
-                    .await
-                    .map_err(|e| AppError::Internal(format!("Failed to expire temp key: {}", e)))?;
+            let values: Vec<Option<String>> = redis::cmd("MGET")
+                .arg(&keys)
+                .query_async(&mut conn)
+                .await
+                .map_err(|e| AppError::Internal(format!("Failed to get incidents: {}", e)))?;
 
-                sets_to_intersect.push(temp_key);
+            for json in values.into_iter().flatten() {
+                incidents.push(Self::deserialize_incident(&json)?);
             }
         }
 
-        // If source filter is specified
-        if !filter.sources.is_empty() {
-            let source_keys: Vec<String> = filter
-                .sources
+        Ok(incidents)
+    }
+
+    /// Number of entries at the cursor's score that a walk in `descending`
+    /// order visits up to and including the cursor
+    ///
+    /// Zero if the cursor's incident has since been deleted or moved; the
+    /// page builder then skips the entries before the cursor itself.
+    async fn entries_through(
+        &self,
+        key: &str,
+        cursor: &IncidentCursor,
+        descending: bool,
+    ) -> Result<usize> {
+        let id = cursor.id().to_string();
+        let mut pipe = redis::pipe();
+        pipe.atomic().zscore(key, &id);
+        if descending {
+            pipe.zrevrank(key, &id)
+                .zcount(key, format!("({}", cursor.key()), "+inf");
+        } else {
+            pipe.zrank(key, &id)
+                .zcount(key, "-inf", format!("({}", cursor.key()));
+        }
+
+        let mut conn = self.connection.clone();
+        let (score, rank, before): (Option<f64>, Option<usize>, usize) = pipe
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to locate cursor: {}", e)))?;
+
+        Ok(match (score, rank) {
+            (Some(score), Some(rank)) if score as i64 == cursor.key() => rank + 1 - before,
+            _ => 0,
+        })
+    }
+
+    /// Walk the sort index for the filter's order from the page's start,
+    /// pushing matching incidents until the page is complete
+    ///
+    /// Each batch is a `ZRANGEBYSCORE ... LIMIT` from the last score read,
+    /// offset past the entries at that score which were already visited.
+    async fn scan(&self, filter: &IncidentFilter, page: &mut PageBuilder<'_>) -> Result<()> {
+        let candidates: Option<HashSet<String>> = if Self::has_indexed_constraints(filter) {
+            Some(
+                self.get_filtered_incident_ids(filter)
+                    .await?
+                    .into_iter()
+                    .collect(),
+            )
+        } else {
+            None
+        };
+
+        let key = self.sort_index_key(filter.sort.field);
+        let descending = filter.sort.direction == SortDirection::Desc;
+        let (mut score, mut skip) = match page.start() {
+            Some(start) => (
+                Some(start.key()),
+                self.entries_through(&key, start, descending).await?,
+            ),
+            None => (None, 0),
+        };
+
+        let mut conn = self.connection.clone();
+        loop {
+            let entries: Vec<(String, f64)> = if descending {
+                let max = score.map_or("+inf".to_string(), |score| score.to_string());
+                conn.zrevrangebyscore_limit_withscores(
+                    &key,
+                    max,
+                    "-inf",
+                    skip as isize,
+                    LOAD_BATCH_SIZE as isize,
+                )
+                .await
+            } else {
+                let min = score.map_or("-inf".to_string(), |score| score.to_string());
+                conn.zrangebyscore_limit_withscores(
+                    &key,
+                    min,
+                    "+inf",
+                    skip as isize,
+                    LOAD_BATCH_SIZE as isize,
+                )
+                .await
+            }
+            .map_err(|e| AppError::Internal(format!("Failed to read sort index: {}", e)))?;
+
+            if let Some(&(_, last)) = entries.last() {
+                let last = last as i64;
+                let at_last = entries
+                    .iter()
+                    .rev()
+                    .take_while(|(_, entry_score)| *entry_score as i64 == last)
+                    .count();
+                if score == Some(last) && at_last == entries.len() {
+                    skip += at_last;
+                } else {
+                    score = Some(last);
+                    skip = at_last;
+                }
+            }
+
+            let ids: Vec<String> = entries
                 .iter()
-                .map(|s| self.source_index_key(s))
+                .map(|(id, _)| id)
+                .filter(|id| candidates.as_ref().is_none_or(|ids| ids.contains(*id)))
+                .cloned()
                 .collect();
+            let mut complete = false;
+            for incident in self.load_incidents(&ids).await? {
+                if !page.push(incident) {
+                    complete = true;
+                    break;
+                }
+            }
 
-            if source_keys.len() == 1 {
-                sets_to_intersect.push(source_keys[0].clone());
-            } else {
-                let temp_key = format!("{}:temp:source_union:{}", self.key_prefix, Uuid::new_v4());
-                let _: () = redis::cmd("SUNIONSTORE")
-                    .arg(&temp_key)
-                    .arg(&source_keys)
-                    .query_async(&mut self.connection)
-                    .await
-                    .map_err(|e| {
-                        AppError::Internal(format!("Failed to union source sets: {}", e))
-                    })?;
+            if complete || entries.len() < LOAD_BATCH_SIZE {
+                return Ok(());
+            }
+        }
+    }
 
-                let _: () = self
-                    .connection
-                    .expire(&temp_key, 60)
-                    .await
-                    .map_err(|e| AppError::Internal(format!("Failed to expire temp key: {}", e)))?;
+    /// All incidents matching the filter, unordered
+    async fn query(&self, filter: &IncidentFilter) -> Result<Vec<Incident>> {
+        let ids = self.get_filtered_incident_ids(filter).await?;
+        let mut incidents = self.load_incidents(&ids).await?;
+        incidents.retain(|incident| filter.matches(incident));
+        Ok(incidents)
+    }
+}
+
+#[async_trait]
+impl IncidentStore for RedisStore {
+    async fn save_incident(&self, incident: &Incident) -> Result<()> {
+        self.write_incident(&incident.id, |_| Ok(Some(incident.clone())))
+            .await?;
+
+        tracing::debug!(incident_id = %incident.id, "Incident saved to Redis");
+        Ok(())
+    }
+
+    async fn get_incident(&self, id: &Uuid) -> Result<Option<Incident>> {
+        let key = self.incident_key(id);
+
+        let mut conn = self.connection.clone();
+
+        let value: Option<String> = conn
+            .get(&key)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get incident: {}", e)))?;
 
-                sets_to_intersect.push(temp_key);
+        match value {
+            Some(json) => {
+                let incident = Self::deserialize_incident(&json)?;
+                Ok(Some(incident))
             }
+            None => Ok(None),
         }
+    }
 
-        // Get incident IDs
-        let incident_ids: Vec<String> = if sets_to_intersect.is_empty() {
-            // No filters, return all incidents
-            self.connection
-                .smembers(self.incidents_set_key())
-                .await
-                .map_err(|e| {
-                    AppError::Internal(format!("Failed to get all incidents: {}", e))
-                })?
-        } else if sets_to_intersect.len() == 1 {
-            // Single set
-            self.connection
-                .smembers(&sets_to_intersect[0])
-                .await
-                .map_err(|e| AppError::Internal(format!("Failed to get incidents: {}", e)))?
-        } else {
-            // Multiple sets - intersect them
-            redis::cmd("SINTER")
-                .arg(&sets_to_intersect)
-                .query_async(&mut self.connection)
-                .await
-                .map_err(|e| {
-                    AppError::Internal(format!("Failed to intersect incident sets: {}", e))
-                })?
-        };
+    async fn update_incident(&self, incident: &Incident) -> Result<()> {
+        self.write_incident(&incident.id, |current| {
+            let Some(current) = current else {
+                return Err(AppError::NotFound(format!(
+                    "Incident {} not found",
+                    incident.id
+                )));
+            };
+            if current.version != incident.version {
+                return Err(AppError::VersionConflict {
+                    resource: format!("incident {}", incident.id),
+                    expected: incident.version,
+                    actual: current.version,
+                });
+            }
+
+            let mut updated = incident.clone();
+            updated.version += 1;
+            Ok(Some(updated))
+        })
+        .await?;
+
+        tracing::debug!(incident_id = %incident.id, "Incident updated in Redis");
+        Ok(())
+    }
+
+    async fn delete_incident(&self, id: &Uuid) -> Result<()> {
+        self.write_incident(id, |current| match current {
+            Some(_) => Ok(None),
+            None => Err(AppError::NotFound(format!("Incident {} not found", id))),
+        })
+        .await?;
+
+        tracing::debug!(incident_id = %id, "Incident deleted from Redis");
+        Ok(())
+    }
+
+    async fn list_incidents(
+        &self,
+        filter: &IncidentFilter,
+        page: u32,
+        page_size: u32,
+    ) -> Result<Vec<Incident>> {
+        let mut page = PageBuilder::offset(filter, page, page_size);
+        self.scan(filter, &mut page).await?;
+        Ok(page.finish().incidents)
+    }
+
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        let mut page = PageBuilder::new(filter, cursor, limit)?;
+        self.scan(filter, &mut page).await?;
+        Ok(page.finish())
+    }
+
+    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
+        // The indexes answer everything except these exactly
+        let indexed_only = !filter.active_only
+            && filter.text.is_none()
+            && filter.updated.is_unbounded()
+            && filter.resolved.is_unbounded();
+        if indexed_only {
+            return Ok(self.get_filtered_incident_ids(filter).await?.len() as u64);
+        }
+
+        Ok(self.query(filter).await?.len() as u64)
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
+        let key = self.fingerprint_key(fingerprint);
+
+        let mut conn = self.connection.clone();
+
+        let incident_ids: Vec<String> = conn
+            .smembers(&key)
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to query fingerprint index: {}", e))
+            })?;
+
+        let mut incidents = Vec::new();
+        for id_str in incident_ids {
+            if let Ok(id) = Uuid::parse_str(&id_str) {
+                if let Some(incident) = self.get_incident(&id).await? {
+                    incidents.push(incident);
+                }
+            }
+        }
+
+        Ok(incidents)
+    }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        let mut conn = self.connection.clone();
 
-        Ok(incident_ids)
+        let values: Vec<String> = conn
+            .lrange(self.events_key(id), 0, -1)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to read incident events: {}", e)))?;
+
+        let mut events = values
+            .iter()
+            .map(|json| {
+                serde_json::from_str::<IncidentEvent>(json).map_err(|e| {
+                    AppError::Internal(format!("Failed to deserialize incident event: {}", e))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        events.sort_by_key(|e| e.sequence);
+        Ok(events)
     }
-}
 
-#[async_trait]
-impl IncidentStore for RedisStore {
-    async fn save_incident(&self, incident: &Incident) -> Result<()> {
-        let key = self.incident_key(&incident.id);
-        let value = Self::serialize_incident(incident)?;
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        let script = redis::Script::new(REPLACE_EVENTS_SCRIPT);
+        let mut invocation = script.prepare_invoke();
+        invocation
+            .key(self.events_key(id))
+            .key(self.events_sequence_key(id))
+            .arg(events.last().map_or(0, |event| event.sequence + 1));
+        for event in events {
+            invocation.arg(serde_json::to_string(event).map_err(|e| {
+                AppError::Internal(format!("Failed to serialize incident event: {}", e))
+            })?);
+        }
+
+        let mut conn = self.connection.clone();
+        let _: i64 = invocation
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to replace incident events: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        let value = serde_json::to_string(postmortem).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize post-mortem: {}", e))
+        })?;
 
         let mut conn = self.connection.clone();
 
-        // Save incident data
         let _: () = conn
-            .set(&key, &value)
+            .set(self.postmortem_key(&postmortem.id), &value)
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to save incident: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Failed to save post-mortem: {}", e)))?;
 
-        // Update indices
-        self.clone().update_indices(incident).await?;
+        let _: () = conn
+            .sadd(self.postmortems_set_key(), postmortem.id.to_string())
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to update post-mortems set: {}", e))
+            })?;
 
-        tracing::debug!(incident_id = %incident.id, "Incident saved to Redis");
+        tracing::debug!(postmortem_id = %postmortem.id, "Post-mortem saved to Redis");
         Ok(())
     }
 
-    async fn get_incident(&self, id: &Uuid) -> Result<Option<Incident>> {
-        let key = self.incident_key(id);
-
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
         let mut conn = self.connection.clone();
 
         let value: Option<String> = conn
-            .get(&key)
+            .get(self.postmortem_key(id))
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to get incident: {}", e)))?;
-
-        match value {
-            Some(json) => {
-                let incident = Self::deserialize_incident(&json)?;
-                Ok(Some(incident))
-            }
-            None => Ok(None),
-        }
+            .map_err(|e| AppError::Internal(format!("Failed to get post-mortem: {}", e)))?;
+
+        value
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    AppError::Internal(format!("Failed to deserialize post-mortem: {}", e))
+                })
+            })
+            .transpose()
     }
 
-    async fn update_incident(&self, incident: &Incident) -> Result<()> {
-        let key = self.incident_key(&incident.id);
-
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
         let mut conn = self.connection.clone();
 
-        // Check if incident exists
-        let exists: bool = conn
-            .exists(&key)
+        let ids: Vec<String> = conn
+            .smembers(self.postmortems_set_key())
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to check incident existence: {}", e)))?;
+            .map_err(|e| AppError::Internal(format!("Failed to list post-mortems: {}", e)))?;
 
-        if !exists {
-            return Err(AppError::NotFound(format!(
-                "Incident {} not found",
-                incident.id
-            )));
+        let mut postmortems = Vec::new();
+        for id_str in ids {
+            if let Ok(id) = Uuid::parse_str(&id_str) {
+                if let Some(postmortem) = self.get_postmortem(&id).await? {
+                    if incident_id.is_none_or(|wanted| postmortem.incident_id == *wanted) {
+                        postmortems.push(postmortem);
+                    }
+                }
+            }
         }
 
-        let value = Self::serialize_incident(incident)?;
+        Ok(postmortems)
+    }
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        let value = serde_json::to_string(execution).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize playbook execution: {}", e))
+        })?;
+
+        let mut conn = self.connection.clone();
 
-        // Update incident data
         let _: () = conn
-            .set(&key, &value)
+            .set(self.execution_key(&execution.id), &value)
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to update incident: {}", e)))?;
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to save playbook execution: {}", e))
+            })?;
 
-        // Update indices
-        self.clone().update_indices(incident).await?;
+        let _: () = conn
+            .sadd(self.executions_set_key(), execution.id.to_string())
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to update playbook executions set: {}", e))
+            })?;
 
-        tracing::debug!(incident_id = %incident.id, "Incident updated in Redis");
+        tracing::debug!(execution_id = %execution.id, "Playbook execution saved to Redis");
         Ok(())
     }
 
-    async fn delete_incident(&self, id: &Uuid) -> Result<()> {
-        // Get incident first to access its data for index cleanup
-        let incident = match self.get_incident(id).await? {
-            Some(i) => i,
-            None => {
-                return Err(AppError::NotFound(format!("Incident {} not found", id)));
-            }
-        };
-
-        let key = self.incident_key(id);
-
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
         let mut conn = self.connection.clone();
 
-        // Delete incident data
-        let _: () = conn
-            .del(&key)
+        let value: Option<String> = conn
+            .get(self.execution_key(id))
             .await
-            .map_err(|e| AppError::Internal(format!("Failed to delete incident: {}", e)))?;
-
-        // Remove from indices
-        self.clone().remove_from_indices(&incident).await?;
-
-        tracing::debug!(incident_id = %id, "Incident deleted from Redis");
-        Ok(())
+            .map_err(|e| AppError::Internal(format!("Failed to get playbook execution: {}", e)))?;
+
+        value
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    AppError::Internal(format!("Failed to deserialize playbook execution: {}", e))
+                })
+            })
+            .transpose()
     }
 
-    async fn list_incidents(
+    async fn list_playbook_executions(
         &self,
-        filter: &IncidentFilter,
-        page: u32,
-        page_size: u32,
-    ) -> Result<Vec<Incident>> {
-        let _conn = self.connection.clone();
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        let mut conn = self.connection.clone();
 
-        // Get filtered incident IDs
-        let incident_ids = self.clone().get_filtered_incident_ids(filter).await?;
+        let ids: Vec<String> = conn
+            .smembers(self.executions_set_key())
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to list playbook executions: {}", e))
+            })?;
 
-        // Fetch incidents
-        let mut incidents: Vec<Incident> = Vec::new();
-        for id_str in incident_ids {
+        let mut executions = Vec::new();
+        for id_str in ids {
             if let Ok(id) = Uuid::parse_str(&id_str) {
-                if let Some(incident) = self.get_incident(&id).await? {
-                    // Apply active filter
-                    if !filter.active_only || incident.is_active() {
-                        incidents.push(incident);
+                if let Some(execution) = self.get_playbook_execution(&id).await? {
+                    if incident_id.is_none_or(|wanted| execution.incident_id == *wanted) {
+                        executions.push(execution);
                     }
                 }
             }
         }
 
-        // Sort by creation time (newest first)
-        incidents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
-        // Apply pagination
-        let start = (page * page_size) as usize;
-
-        Ok(incidents
-            .into_iter()
-            .skip(start)
-            .take(page_size as usize)
-            .collect())
+        Ok(executions)
     }
 
-    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
-        // Get filtered incident IDs
-        let incident_ids = self.clone().get_filtered_incident_ids(filter).await?;
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        let value = serde_json::to_string(state).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize escalation state: {}", e))
+        })?;
 
-        if !filter.active_only {
-            return Ok(incident_ids.len() as u64);
-        }
+        let mut conn = self.connection.clone();
 
-        // If active_only filter is set, need to check each incident
-        let mut count = 0u64;
-        for id_str in incident_ids {
-            if let Ok(id) = Uuid::parse_str(&id_str) {
-                if let Some(incident) = self.get_incident(&id).await? {
-                    if incident.is_active() {
-                        count += 1;
-                    }
-                }
-            }
-        }
+        let _: () = conn
+            .set(self.escalation_key(&state.incident_id), &value)
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to save escalation state: {}", e))
+            })?;
+
+        let _: () = conn
+            .sadd(self.escalations_set_key(), state.incident_id.to_string())
+            .await
+            .map_err(|e| {
+                AppError::Internal(format!("Failed to update escalation states set: {}", e))
+            })?;
 
-        Ok(count)
+        tracing::debug!(incident_id = %state.incident_id, "Escalation state saved to Redis");
+        Ok(())
     }
 
-    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
-        let key = self.fingerprint_key(fingerprint);
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        let mut conn = self.connection.clone();
+
+        let value: Option<String> = conn
+            .get(self.escalation_key(incident_id))
+            .await
+            .map_err(|e| AppError::Internal(format!("Failed to get escalation state: {}", e)))?;
+
+        value
+            .map(|json| {
+                serde_json::from_str(&json).map_err(|e| {
+                    AppError::Internal(format!("Failed to deserialize escalation state: {}", e))
+                })
+            })
+            .transpose()
+    }
 
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
         let mut conn = self.connection.clone();
 
-        let incident_ids: Vec<String> = conn
-            .smembers(&key)
+        let ids: Vec<String> = conn
+            .smembers(self.escalations_set_key())
             .await
             .map_err(|e| {
-                AppError::Internal(format!("Failed to query fingerprint index: {}", e))
+                AppError::Internal(format!("Failed to list escalation states: {}", e))
             })?;
 
-        let mut incidents = Vec::new();
-        for id_str in incident_ids {
+        let mut states = Vec::new();
+        for id_str in ids {
             if let Ok(id) = Uuid::parse_str(&id_str) {
-                if let Some(incident) = self.get_incident(&id).await? {
-                    incidents.push(incident);
+                if let Some(state) = self.get_escalation_state(&id).await? {
+                    states.push(state);
                 }
             }
         }
 
-        Ok(incidents)
+        Ok(states)
     }
 }
 
@@ -510,6 +1307,7 @@ impl IncidentStore for RedisStore {
 mod tests {
     use super::*;
     use crate::models::{IncidentState, IncidentType, Severity};
+    use crate::state::event_log::IncidentEventPayload;
 
     // Helper to check if Redis is available
     async fn redis_available() -> bool {
@@ -537,6 +1335,113 @@ mod tests {
             .ok()
     }
 
+    // Cluster nodes started by scripts/redis-cluster.sh, overridable with
+    // REDIS_CLUSTER_NODES
+    async fn create_cluster_test_store() -> Option<RedisStore> {
+        let nodes: Vec<String> = std::env::var("REDIS_CLUSTER_NODES")
+            .unwrap_or_else(|_| {
+                "redis://127.0.0.1:7000,redis://127.0.0.1:7001,redis://127.0.0.1:7002".to_string()
+            })
+            .split(',')
+            .map(|node| node.trim().to_string())
+            .collect();
+
+        RedisStore::new_cluster_with_prefix(&nodes, &format!("test-{}", Uuid::new_v4()))
+            .await
+            .ok()
+    }
+
+    #[test]
+    fn test_cluster_key_prefix() {
+        assert_eq!(cluster_key_prefix("llm-im"), "{llm-im}");
+        assert_eq!(cluster_key_prefix("{llm-im}"), "{llm-im}");
+        assert_eq!(bare_key_prefix("{llm-im}"), "llm-im");
+
+        let slot = redis::cluster_routing::get_slot(b"{llm-im}:incidents");
+        for key in [
+            "{llm-im}:fingerprint:abc123",
+            "{llm-im}:severity:P0",
+            "{llm-im}:temp:union:1",
+        ] {
+            assert_eq!(redis::cluster_routing::get_slot(key.as_bytes()), slot);
+        }
+
+        // Each incident's keys share a slot of their own
+        let id = "6a1f3c52-1b1e-4a5e-9a43-0f6f1b8b7c01";
+        let incident_slot =
+            redis::cluster_routing::get_slot(format!("llm-im:incident:{{{}}}", id).as_bytes());
+        for key in [
+            format!("llm-im:events:{{{}}}", id),
+            format!("llm-im:events_seq:{{{}}}", id),
+        ] {
+            assert_eq!(
+                redis::cluster_routing::get_slot(key.as_bytes()),
+                incident_slot
+            );
+        }
+        assert_ne!(incident_slot, slot);
+    }
+
+    #[tokio::test]
+    async fn test_cluster_operations() {
+        let Some(store) = create_cluster_test_store().await else {
+            eprintln!("Skipping test: Redis cluster not available");
+            return;
+        };
+        assert!(store.is_cluster());
+
+        let mut incident = Incident::new(
+            "cluster-source".to_string(),
+            "Cluster Incident".to_string(),
+            "Description".to_string(),
+            Severity::P1,
+            IncidentType::Application,
+        );
+        incident.fingerprint = Some("cluster-fingerprint".to_string());
+        let id = incident.id;
+
+        // The incident's own keys share its slot; the indexes share another
+        let slot = redis::cluster_routing::get_slot(store.incident_key(&id).as_bytes());
+        for key in [store.events_key(&id), store.events_sequence_key(&id)] {
+            assert_eq!(redis::cluster_routing::get_slot(key.as_bytes()), slot);
+        }
+        let index_slot = redis::cluster_routing::get_slot(store.incidents_set_key().as_bytes());
+        for key in [
+            store.fingerprint_key("cluster-fingerprint"),
+            store.severity_index_key("P1"),
+            store.state_index_key("Detected"),
+            store.source_index_key("cluster-source"),
+            store.created_index_key(),
+        ] {
+            assert_eq!(redis::cluster_routing::get_slot(key.as_bytes()), index_slot);
+        }
+
+        store.save_incident(&incident).await.unwrap();
+
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+        assert!(matches!(
+            store.update_incident(&incident).await,
+            Err(AppError::VersionConflict { .. })
+        ));
+
+        // Multi-key SINTER/SUNIONSTORE must not fail with CROSSSLOT
+        let filter = IncidentFilter {
+            severities: vec![Severity::P0, Severity::P1],
+            states: vec![IncidentState::Investigating],
+            sources: vec!["cluster-source".to_string()],
+            ..Default::default()
+        };
+        let results = store.list_incidents(&filter, 0, 10).await.unwrap();
+        assert_eq!(results.len(), 1);
+
+        let found = store.find_by_fingerprint("cluster-fingerprint").await.unwrap();
+        assert_eq!(found.len(), 1);
+
+        store.delete_incident(&id).await.unwrap();
+        assert!(store.get_incident(&id).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_save_and_get_incident() {
         let Some(store) = create_test_store().await else {
@@ -563,6 +1468,65 @@ mod tests {
         store.delete_incident(&id).await.ok();
     }
 
+    #[tokio::test]
+    async fn test_repair_indices_after_failed_index_update() {
+        let Some(store) = create_test_store().await else {
+            eprintln!("Skipping test: Redis not available");
+            return;
+        };
+
+        let source = format!("repair-{}", Uuid::new_v4());
+        let incident = Incident::new(
+            source.clone(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        // The incident write lands but its index update never runs
+        let indexed = RedisStore::serialize_incident(&incident).unwrap();
+        let mut updated = incident.clone();
+        updated.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        updated.version += 1;
+        let mut conn = store.connection.clone();
+        let _: bool = conn
+            .hset_nx(store.index_pending_key(), incident.id.to_string(), &indexed)
+            .await
+            .unwrap();
+        let _: () = conn
+            .set(
+                store.incident_key(&incident.id),
+                RedisStore::serialize_incident(&updated).unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let in_state = |state: IncidentState| IncidentFilter {
+            states: vec![state],
+            sources: vec![source.clone()],
+            ..Default::default()
+        };
+        let investigating = in_state(IncidentState::Investigating);
+        let detected = in_state(IncidentState::Detected);
+        assert_eq!(store.count_incidents(&investigating).await.unwrap(), 0);
+
+        assert!(store.repair_indices().await.unwrap() >= 1);
+        assert_eq!(store.count_incidents(&investigating).await.unwrap(), 1);
+        assert_eq!(store.count_incidents(&detected).await.unwrap(), 0);
+
+        // Repaired incidents are unmarked
+        let pending: Option<String> = conn
+            .hget(store.index_pending_key(), incident.id.to_string())
+            .await
+            .unwrap();
+        assert!(pending.is_none());
+
+        // Cleanup
+        store.delete_incident(&incident.id).await.ok();
+    }
+
     #[tokio::test]
     async fn test_update_incident() {
         let Some(store) = create_test_store().await else {
@@ -585,6 +1549,11 @@ mod tests {
 
         let retrieved = store.get_incident(&incident.id).await.unwrap().unwrap();
         assert_eq!(retrieved.state, IncidentState::Investigating);
+        assert_eq!(retrieved.version, 1);
+
+        // A writer still holding the old version is rejected
+        let err = store.update_incident(&incident).await.unwrap_err();
+        assert!(matches!(err, AppError::VersionConflict { actual: 1, .. }));
 
         // Cleanup
         store.delete_incident(&incident.id).await.ok();
@@ -657,4 +1626,152 @@ mod tests {
         // Cleanup
         store.delete_incident(&incident1.id).await.ok();
     }
+
+    #[tokio::test]
+    async fn test_incident_history() {
+        let Some(store) = create_test_store().await else {
+            eprintln!("Skipping test: Redis not available");
+            return;
+        };
+
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+
+        store.save_incident(&incident).await.unwrap();
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+        store.delete_incident(&incident.id).await.unwrap();
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert_eq!(events.len(), 4); // created, state changed, timeline appended, deleted
+        assert!(matches!(events[3].payload, IncidentEventPayload::Deleted));
+
+        let rebuilt = store
+            .get_incident_at(&incident.id, events[2].recorded_at)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rebuilt.state, IncidentState::Investigating);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writes_keep_event_log_consistent() {
+        let Some(store) = create_test_store().await else {
+            eprintln!("Skipping test: Redis not available");
+            return;
+        };
+
+        let incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let store = store.clone();
+                let mut incident = incident.clone();
+                tokio::spawn(async move {
+                    incident.title = format!("Title {}", i);
+                    store.save_incident(&incident).await.unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert_eq!(events.len(), 9);
+        assert!(events
+            .iter()
+            .enumerate()
+            .all(|(i, e)| e.sequence == i as u64));
+        let rebuilt = event_log::replay(&events, None).unwrap();
+        assert_eq!(
+            rebuilt,
+            store.get_incident(&incident.id).await.unwrap().unwrap()
+        );
+
+        store.delete_incident(&incident.id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages_walk_every_sort_index() {
+        if !redis_available().await {
+            eprintln!("Skipping test: Redis not available");
+            return;
+        }
+        let prefix = format!("test-{}", Uuid::new_v4());
+        let store = RedisStore::new_with_prefix("redis://127.0.0.1:6379/15", &prefix)
+            .await
+            .unwrap();
+
+        // More incidents per severity than one sort index batch holds
+        let severities = [Severity::P0, Severity::P1, Severity::P2, Severity::P3];
+        let mut incidents = Vec::new();
+        for i in 0..(LOAD_BATCH_SIZE + 50) {
+            let mut incident = Incident::new(
+                "test-source".to_string(),
+                format!("Incident {}", i),
+                "Description".to_string(),
+                if i < LOAD_BATCH_SIZE {
+                    Severity::P2
+                } else {
+                    severities[i % 4]
+                },
+                IncidentType::Application,
+            );
+            incident.created_at -= chrono::Duration::minutes((i % 7) as i64);
+            store.save_incident(&incident).await.unwrap();
+            incidents.push(incident);
+        }
+
+        for field in SortField::ALL {
+            for direction in [SortDirection::Asc, SortDirection::Desc] {
+                let filter = IncidentFilter {
+                    sort: query::IncidentSort::new(field, direction),
+                    ..Default::default()
+                };
+                let mut expected = incidents.clone();
+                query::sort_incidents(&mut expected, &filter.sort);
+                let expected: Vec<Uuid> = expected.iter().map(|i| i.id).collect();
+
+                let mut seen = Vec::new();
+                let mut cursor = None;
+                loop {
+                    let page = store
+                        .query_incidents(&filter, cursor.as_deref(), 40)
+                        .await
+                        .unwrap();
+                    seen.extend(page.incidents.iter().map(|i| i.id));
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                assert_eq!(seen, expected, "{:?}", filter.sort);
+            }
+        }
+
+        let p0 = IncidentFilter {
+            severities: vec![Severity::P0],
+            ..Default::default()
+        };
+        let page = store.query_incidents(&p0, None, 100).await.unwrap();
+        assert_eq!(page.incidents.len(), 13);
+        assert!(page.next_cursor.is_none());
+
+        for incident in &incidents {
+            store.delete_incident(&incident.id).await.ok();
+        }
+    }
 }
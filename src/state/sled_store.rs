@@ -1,18 +1,71 @@
 use crate::error::{AppError, Result};
-use crate::models::Incident;
-use crate::state::{IncidentFilter, IncidentStore};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::codec;
+use crate::state::event_log::{self, IncidentEvent};
+use crate::state::query::{
+    self, IncidentFilter, IncidentPage, PageBuilder, SortDirection, SortField,
+};
+use crate::state::IncidentStore;
 use async_trait::async_trait;
-use sled::Db;
+use chrono::{DateTime, Utc};
+use sled::transaction::{
+    ConflictableTransactionError, ConflictableTransactionResult, TransactionError,
+    TransactionalTree,
+};
+use sled::{Db, Transactional};
+use std::collections::{BTreeSet, HashSet};
+use std::ops::Bound;
 use std::path::Path;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Terms an incident is stored under in the term index
+fn index_terms(incident: &Incident) -> BTreeSet<String> {
+    let mut terms = query::index_terms(incident);
+    terms.insert(format!("state:{:?}", incident.state));
+    terms.insert(format!("severity:{:?}", incident.severity));
+    terms.insert(format!("source:{}", incident.source));
+    terms
+}
+
+/// Order-preserving big-endian encoding of a sort key
+fn order_key(key: i64) -> [u8; 8] {
+    ((key as u64) ^ (1 << 63)).to_be_bytes()
+}
+
+/// Order-preserving big-endian encoding of a timestamp
+fn time_key(at: DateTime<Utc>) -> [u8; 8] {
+    order_key(at.timestamp_micros())
+}
+
 /// Persistent incident store using Sled embedded database
 #[derive(Clone)]
 pub struct SledStore {
     db: Arc<Db>,
     incidents_tree: sled::Tree,
     fingerprint_tree: sled::Tree,
+    postmortems_tree: sled::Tree,
+    executions_tree: sled::Tree,
+    escalations_tree: sled::Tree,
+    events_tree: sled::Tree,
+    /// Next free event sequence number per incident
+    event_sequences_tree: sled::Tree,
+    /// `term \0 id` for every term in [`index_terms`]
+    index_tree: sled::Tree,
+    /// `created_at id` for time-range queries and creation-time order
+    created_tree: sled::Tree,
+    /// `field sort_key id` for the other sort fields
+    sort_tree: sled::Tree,
+}
+
+/// The index trees as seen from inside an incident write transaction
+struct IndexViews<'a> {
+    fingerprints: &'a TransactionalTree,
+    terms: &'a TransactionalTree,
+    created: &'a TransactionalTree,
+    sort: &'a TransactionalTree,
 }
 
 impl SledStore {
@@ -31,26 +84,123 @@ impl SledStore {
             AppError::Internal(format!("Failed to open fingerprints tree: {}", e))
         })?;
 
+        let postmortems_tree = db.open_tree("postmortems").map_err(|e| {
+            AppError::Internal(format!("Failed to open postmortems tree: {}", e))
+        })?;
+
+        let executions_tree = db.open_tree("playbook_executions").map_err(|e| {
+            AppError::Internal(format!("Failed to open playbook executions tree: {}", e))
+        })?;
+
+        let escalations_tree = db.open_tree("escalation_states").map_err(|e| {
+            AppError::Internal(format!("Failed to open escalation states tree: {}", e))
+        })?;
+
+        let events_tree = db.open_tree("incident_events").map_err(|e| {
+            AppError::Internal(format!("Failed to open incident events tree: {}", e))
+        })?;
+
+        let event_sequences_tree = db.open_tree("incident_event_sequences").map_err(|e| {
+            AppError::Internal(format!(
+                "Failed to open incident event sequences tree: {}",
+                e
+            ))
+        })?;
+
+        let index_tree = db.open_tree("incident_index").map_err(|e| {
+            AppError::Internal(format!("Failed to open incident index tree: {}", e))
+        })?;
+
+        let created_tree = db.open_tree("incident_created").map_err(|e| {
+            AppError::Internal(format!("Failed to open created index tree: {}", e))
+        })?;
+
+        let sort_tree = db
+            .open_tree("incident_sort")
+            .map_err(|e| AppError::Internal(format!("Failed to open sort index tree: {}", e)))?;
+
         tracing::info!("Initialized Sled store at {:?}", path_str);
 
-        Ok(Self {
+        let store = Self {
             db: Arc::new(db),
             incidents_tree,
             fingerprint_tree,
-        })
+            postmortems_tree,
+            executions_tree,
+            escalations_tree,
+            events_tree,
+            event_sequences_tree,
+            index_tree,
+            created_tree,
+            sort_tree,
+        };
+
+        // Databases written before the query or sort indexes existed, or
+        // before index entries were written in the incident's transaction
+        // and left behind by a crash between the two
+        let unindexed = store.index_tree.is_empty()
+            || store.sort_tree.is_empty()
+            || store.created_tree.len() != store.incidents_tree.len();
+        if unindexed && !store.incidents_tree.is_empty() {
+            store.rebuild_indices()?;
+        }
+
+        Ok(store)
     }
 
-    /// Serialize incident to bytes
+    /// Serialize incident to bytes (see [`codec`])
     fn serialize_incident(incident: &Incident) -> Result<Vec<u8>> {
-        bincode::serialize(incident).map_err(|e| {
-            AppError::Internal(format!("Failed to serialize incident: {}", e))
-        })
+        codec::encode_incident(incident)
     }
 
-    /// Deserialize incident from bytes
+    /// Deserialize incident from bytes, including records in the original
+    /// bincode layout
     fn deserialize_incident(bytes: &[u8]) -> Result<Incident> {
-        bincode::deserialize(bytes).map_err(|e| {
-            AppError::Internal(format!("Failed to deserialize incident: {}", e))
+        codec::decode_incident(bytes)
+    }
+
+    /// Serialize post-mortem to bytes
+    ///
+    /// Post-mortems embed `serde_json::Value` (decision event outputs), which
+    /// bincode cannot deserialize, so they are stored as JSON.
+    fn serialize_postmortem(postmortem: &PostMortem) -> Result<Vec<u8>> {
+        serde_json::to_vec(postmortem).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize post-mortem: {}", e))
+        })
+    }
+
+    /// Deserialize post-mortem from bytes
+    fn deserialize_postmortem(bytes: &[u8]) -> Result<PostMortem> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize post-mortem: {}", e))
+        })
+    }
+
+    /// Serialize playbook execution to bytes
+    fn serialize_execution(execution: &PlaybookExecution) -> Result<Vec<u8>> {
+        serde_json::to_vec(execution).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize playbook execution: {}", e))
+        })
+    }
+
+    /// Deserialize playbook execution from bytes
+    fn deserialize_execution(bytes: &[u8]) -> Result<PlaybookExecution> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize playbook execution: {}", e))
+        })
+    }
+
+    /// Serialize escalation state to bytes
+    fn serialize_escalation(state: &EscalationState) -> Result<Vec<u8>> {
+        serde_json::to_vec(state).map_err(|e| {
+            AppError::Internal(format!("Failed to serialize escalation state: {}", e))
+        })
+    }
+
+    /// Deserialize escalation state from bytes
+    fn deserialize_escalation(bytes: &[u8]) -> Result<EscalationState> {
+        serde_json::from_slice(bytes).map_err(|e| {
+            AppError::Internal(format!("Failed to deserialize escalation state: {}", e))
         })
     }
 
@@ -59,71 +209,494 @@ impl SledStore {
         id.as_bytes().to_vec()
     }
 
+    /// Get event key (incident ID followed by big-endian sequence, so a
+    /// prefix scan returns an incident's events in order)
+    fn event_key(incident_id: &Uuid, sequence: u64) -> Vec<u8> {
+        let mut key = incident_id.as_bytes().to_vec();
+        key.extend_from_slice(&sequence.to_be_bytes());
+        key
+    }
+
+    /// Next free sequence number in an incident's event log
+    fn next_event_sequence(&self, incident_id: &Uuid) -> Result<u64> {
+        match self.events_tree.scan_prefix(incident_id.as_bytes()).next_back() {
+            Some(Ok((key, _))) => {
+                let mut sequence = [0u8; 8];
+                sequence.copy_from_slice(&key[16..24]);
+                Ok(u64::from_be_bytes(sequence) + 1)
+            }
+            Some(Err(e)) => Err(AppError::Internal(format!(
+                "Failed to read incident event log: {}",
+                e
+            ))),
+            None => Ok(0),
+        }
+    }
+
+    /// Write an incident, the events describing the write and the incident's
+    /// index entries in one transaction
+    ///
+    /// `change` receives the stored incident and returns the incident to
+    /// store, or `None` to delete it.
+    fn write_incident<F>(&self, id: &Uuid, change: F) -> Result<()>
+    where
+        F: Fn(Option<&Incident>) -> Result<Option<Incident>>,
+    {
+        let key = Self::incident_key(id);
+        // Logs written before the sequence counter existed
+        let fallback_sequence = self.next_event_sequence(id)?;
+
+        (
+            &self.incidents_tree,
+            &self.events_tree,
+            &self.event_sequences_tree,
+            &self.fingerprint_tree,
+            &self.index_tree,
+            &self.created_tree,
+            &self.sort_tree,
+        )
+            .transaction(|trees| {
+                let (incidents, events, sequences, fingerprints, terms, created, sort) = trees;
+                let abort = ConflictableTransactionError::Abort;
+
+                let before = incidents
+                    .get(&key)?
+                    .map(|bytes| Self::deserialize_incident(&bytes))
+                    .transpose()
+                    .map_err(abort)?;
+                let after = change(before.as_ref()).map_err(abort)?;
+
+                let next_sequence = match sequences.get(&key)? {
+                    Some(bytes) => {
+                        let mut sequence = [0u8; 8];
+                        sequence.copy_from_slice(&bytes);
+                        u64::from_be_bytes(sequence)
+                    }
+                    None => fallback_sequence,
+                };
+                let log = match (&before, &after) {
+                    (_, Some(after)) => {
+                        event_log::events_for_write(before.as_ref(), after, next_sequence)
+                    }
+                    (Some(before), None) => event_log::events_for_delete(before, next_sequence),
+                    (None, None) => Vec::new(),
+                };
+
+                match &after {
+                    Some(after) => {
+                        let value = Self::serialize_incident(after).map_err(abort)?;
+                        incidents.insert(key.as_slice(), value)?;
+                    }
+                    None => {
+                        incidents.remove(key.as_slice())?;
+                    }
+                }
+
+                for event in &log {
+                    // Events use internally tagged enums, which bincode cannot deserialize
+                    let value = serde_json::to_vec(event)
+                        .map_err(|e| {
+                            AppError::Internal(format!("Failed to serialize incident event: {}", e))
+                        })
+                        .map_err(abort)?;
+                    events.insert(Self::event_key(id, event.sequence), value)?;
+                }
+                sequences.insert(
+                    key.as_slice(),
+                    &(next_sequence + log.len() as u64).to_be_bytes()[..],
+                )?;
+
+                let indexes = IndexViews {
+                    fingerprints,
+                    terms,
+                    created,
+                    sort,
+                };
+                Self::update_indices(&indexes, before.as_ref(), after.as_ref())?;
+
+                Ok(())
+            })
+            .map_err(|e| match e {
+                TransactionError::Abort(e) => e,
+                TransactionError::Storage(e) => {
+                    AppError::Internal(format!("Failed to write incident: {}", e))
+                }
+            })
+    }
+
     /// Get fingerprint key
     fn fingerprint_key(fingerprint: &str) -> Vec<u8> {
         fingerprint.as_bytes().to_vec()
     }
 
-    /// Update fingerprint index
-    fn update_fingerprint_index(&self, incident: &Incident) -> Result<()> {
-        if let Some(ref fingerprint) = incident.fingerprint {
-            let key = Self::fingerprint_key(fingerprint);
+    /// Add an incident to its fingerprint's index entry
+    fn update_fingerprint_index(
+        fingerprints: &TransactionalTree,
+        incident_id: &Uuid,
+        fingerprint: &str,
+    ) -> ConflictableTransactionResult<(), AppError> {
+        let key = Self::fingerprint_key(fingerprint);
 
-            // Get existing incident IDs for this fingerprint
-            let mut incident_ids: Vec<Uuid> = if let Some(existing) = self.fingerprint_tree.get(&key).map_err(|e| {
-                AppError::Internal(format!("Failed to read fingerprint index: {}", e))
-            })? {
-                bincode::deserialize(&existing).unwrap_or_default()
+        // Get existing incident IDs for this fingerprint
+        let mut incident_ids: Vec<Uuid> = match fingerprints.get(&key)? {
+            Some(existing) => bincode::deserialize(&existing).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        if incident_ids.contains(incident_id) {
+            return Ok(());
+        }
+        incident_ids.push(*incident_id);
+
+        let serialized = bincode::serialize(&incident_ids).map_err(|e| {
+            ConflictableTransactionError::Abort(AppError::Internal(format!(
+                "Failed to serialize fingerprint index: {}",
+                e
+            )))
+        })?;
+        fingerprints.insert(key, serialized)?;
+
+        Ok(())
+    }
+
+    /// Remove an incident from a fingerprint's index entry
+    fn remove_from_fingerprint_index(
+        fingerprints: &TransactionalTree,
+        incident_id: &Uuid,
+        fingerprint: &str,
+    ) -> ConflictableTransactionResult<(), AppError> {
+        let key = Self::fingerprint_key(fingerprint);
+
+        if let Some(existing) = fingerprints.get(&key)? {
+            let mut incident_ids: Vec<Uuid> = bincode::deserialize(&existing).unwrap_or_default();
+            incident_ids.retain(|id| id != incident_id);
+
+            if incident_ids.is_empty() {
+                fingerprints.remove(key)?;
             } else {
-                Vec::new()
+                let serialized = bincode::serialize(&incident_ids).map_err(|e| {
+                    ConflictableTransactionError::Abort(AppError::Internal(format!(
+                        "Failed to serialize fingerprint index: {}",
+                        e
+                    )))
+                })?;
+                fingerprints.insert(key, serialized)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get term index key
+    fn term_key(term: &str, id: &Uuid) -> Vec<u8> {
+        let mut key = term.as_bytes().to_vec();
+        key.push(0);
+        key.extend_from_slice(id.as_bytes());
+        key
+    }
+
+    /// Tree and key prefix of the sort index for a field
+    ///
+    /// Creation time uses the created-time index; the other fields share the
+    /// sort tree under a one-byte field prefix.
+    fn sort_index(&self, field: SortField) -> (&sled::Tree, Vec<u8>) {
+        match Self::sort_prefix(field) {
+            Some(prefix) => (&self.sort_tree, vec![prefix]),
+            None => (&self.created_tree, Vec::new()),
+        }
+    }
+
+    /// One-byte prefix of a field in the sort tree (`None` for creation time)
+    fn sort_prefix(field: SortField) -> Option<u8> {
+        match field {
+            SortField::CreatedAt => None,
+            SortField::UpdatedAt => Some(1),
+            SortField::ResolvedAt => Some(2),
+            SortField::Severity => Some(3),
+            SortField::State => Some(4),
+        }
+    }
+
+    /// Get sort index key
+    fn sort_key(prefix: &[u8], key: i64, id: &Uuid) -> Vec<u8> {
+        let mut sort_key = prefix.to_vec();
+        sort_key.extend_from_slice(&order_key(key));
+        sort_key.extend_from_slice(id.as_bytes());
+        sort_key
+    }
+
+    /// Move an incident's fingerprint, term and sort index entries from
+    /// `before` to `after`
+    fn update_indices(
+        indexes: &IndexViews<'_>,
+        before: Option<&Incident>,
+        after: Option<&Incident>,
+    ) -> ConflictableTransactionResult<(), AppError> {
+        let old_fingerprint = before.and_then(|i| i.fingerprint.as_deref());
+        let new_fingerprint = after.and_then(|i| i.fingerprint.as_deref());
+        if let (Some(before), Some(fingerprint)) = (before, old_fingerprint) {
+            if new_fingerprint != Some(fingerprint) {
+                Self::remove_from_fingerprint_index(indexes.fingerprints, &before.id, fingerprint)?;
+            }
+        }
+        if let (Some(after), Some(fingerprint)) = (after, new_fingerprint) {
+            Self::update_fingerprint_index(indexes.fingerprints, &after.id, fingerprint)?;
+        }
+
+        let old_terms = before.map(index_terms).unwrap_or_default();
+        let new_terms = after.map(index_terms).unwrap_or_default();
+        if let Some(before) = before {
+            for term in old_terms.difference(&new_terms) {
+                indexes.terms.remove(Self::term_key(term, &before.id))?;
+            }
+        }
+        if let Some(after) = after {
+            for term in new_terms.difference(&old_terms) {
+                indexes
+                    .terms
+                    .insert(Self::term_key(term, &after.id), &[] as &[u8])?;
+            }
+        }
+
+        for field in SortField::ALL {
+            let (tree, prefix) = match Self::sort_prefix(field) {
+                Some(prefix) => (indexes.sort, vec![prefix]),
+                None => (indexes.created, Vec::new()),
             };
+            let sort_key = |i: &Incident| Self::sort_key(&prefix, field.key(i), &i.id);
+            let old_key = before.map(sort_key);
+            let new_key = after.map(sort_key);
+            if old_key == new_key {
+                continue;
+            }
 
-            // Add this incident if not already present
-            if !incident_ids.contains(&incident.id) {
-                incident_ids.push(incident.id);
+            if let Some(key) = old_key {
+                tree.remove(key)?;
+            }
+            if let Some(key) = new_key {
+                tree.insert(key, &[] as &[u8])?;
             }
+        }
 
-            // Serialize and store
-            let serialized = bincode::serialize(&incident_ids).map_err(|e| {
-                AppError::Internal(format!("Failed to serialize fingerprint index: {}", e))
-            })?;
+        Ok(())
+    }
 
-            self.fingerprint_tree.insert(&key, serialized).map_err(|e| {
-                AppError::Internal(format!("Failed to update fingerprint index: {}", e))
+    /// Rebuild the fingerprint, term and sort indexes from the stored
+    /// incidents
+    pub fn rebuild_indices(&self) -> Result<()> {
+        let clear_error =
+            |e: sled::Error| AppError::Internal(format!("Failed to clear incident index: {}", e));
+        self.fingerprint_tree.clear().map_err(clear_error)?;
+        self.index_tree.clear().map_err(clear_error)?;
+        self.created_tree.clear().map_err(clear_error)?;
+        self.sort_tree.clear().map_err(clear_error)?;
+
+        let mut count = 0usize;
+        for result in self.incidents_tree.iter() {
+            let (_, value) = result.map_err(|e| {
+                AppError::Internal(format!("Failed to iterate incidents: {}", e))
             })?;
+            let incident = Self::deserialize_incident(&value)?;
+
+            (
+                &self.fingerprint_tree,
+                &self.index_tree,
+                &self.created_tree,
+                &self.sort_tree,
+            )
+                .transaction(|(fingerprints, terms, created, sort)| {
+                    let indexes = IndexViews {
+                        fingerprints,
+                        terms,
+                        created,
+                        sort,
+                    };
+                    Self::update_indices(&indexes, None, Some(&incident))
+                })
+                .map_err(|e| match e {
+                    TransactionError::Abort(e) => e,
+                    TransactionError::Storage(e) => {
+                        AppError::Internal(format!("Failed to update incident index: {}", e))
+                    }
+                })?;
+            count += 1;
         }
 
+        tracing::info!(incidents = count, "Rebuilt Sled incident indexes");
         Ok(())
     }
 
-    /// Remove from fingerprint index
-    fn remove_from_fingerprint_index(&self, incident_id: &Uuid, fingerprint: &str) -> Result<()> {
-        let key = Self::fingerprint_key(fingerprint);
-
-        if let Some(existing) = self.fingerprint_tree.get(&key).map_err(|e| {
-            AppError::Internal(format!("Failed to read fingerprint index: {}", e))
-        })? {
-            let mut incident_ids: Vec<Uuid> = bincode::deserialize(&existing).unwrap_or_default();
-            incident_ids.retain(|id| id != incident_id);
+    /// Union of the IDs stored under `terms` in the term index
+    fn term_ids<'a>(&self, terms: impl IntoIterator<Item = &'a str>) -> Result<HashSet<Uuid>> {
+        let mut ids = HashSet::new();
+        for term in terms {
+            let mut prefix = term.as_bytes().to_vec();
+            prefix.push(0);
 
-            if incident_ids.is_empty() {
-                self.fingerprint_tree.remove(&key).map_err(|e| {
-                    AppError::Internal(format!("Failed to remove fingerprint index: {}", e))
+            for result in self.index_tree.scan_prefix(&prefix) {
+                let (key, _) = result.map_err(|e| {
+                    AppError::Internal(format!("Failed to read incident index: {}", e))
                 })?;
-            } else {
-                let serialized = bincode::serialize(&incident_ids).map_err(|e| {
-                    AppError::Internal(format!("Failed to serialize fingerprint index: {}", e))
+                if let Ok(id) = Uuid::from_slice(&key[prefix.len()..]) {
+                    ids.insert(id);
+                }
+            }
+        }
+        Ok(ids)
+    }
+
+    /// IDs satisfying the filter's indexed constraints, or `None` if it has none
+    ///
+    /// Each constraint is a union over its index entries; constraints are
+    /// intersected. The caller still checks the full filter on each incident.
+    fn candidate_ids(&self, filter: &IncidentFilter) -> Result<Option<HashSet<Uuid>>> {
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        if !filter.states.is_empty() {
+            groups.push(filter.states.iter().map(|s| format!("state:{:?}", s)).collect());
+        }
+        if !filter.severities.is_empty() {
+            groups.push(
+                filter
+                    .severities
+                    .iter()
+                    .map(|s| format!("severity:{:?}", s))
+                    .collect(),
+            );
+        }
+        if !filter.sources.is_empty() {
+            // Sources match by substring, so find the indexed sources first
+            let mut sources = BTreeSet::new();
+            for result in self.index_tree.scan_prefix(b"source:") {
+                let (key, _) = result.map_err(|e| {
+                    AppError::Internal(format!("Failed to read incident index: {}", e))
                 })?;
+                let term = String::from_utf8_lossy(&key[..key.len().saturating_sub(17)]);
+                let source = &term["source:".len()..];
+                if filter.sources.iter().any(|s| source.contains(s.as_str())) {
+                    sources.insert(term.into_owned());
+                }
+            }
+            groups.push(sources.into_iter().collect());
+        }
+        groups.extend(filter.required_terms());
+
+        let mut result: Option<HashSet<Uuid>> = None;
+        for group in &groups {
+            let ids = self.term_ids(group.iter().map(String::as_str))?;
+            result = Some(match result {
+                Some(current) => current.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        }
+
+        if !filter.created.is_unbounded() {
+            let start = filter.created.after.map_or([0; 8], time_key);
+            let end = filter.created.before.map_or([0xff; 8], time_key);
 
-                self.fingerprint_tree.insert(&key, serialized).map_err(|e| {
-                    AppError::Internal(format!("Failed to update fingerprint index: {}", e))
+            let mut ids = HashSet::new();
+            for entry in self.created_tree.range(start.to_vec()..end.to_vec()) {
+                let (key, _) = entry.map_err(|e| {
+                    AppError::Internal(format!("Failed to read created index: {}", e))
                 })?;
+                if let Ok(id) = Uuid::from_slice(&key[8..]) {
+                    ids.insert(id);
+                }
+            }
+            result = Some(match result {
+                Some(current) => current.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Walk the sort index for the filter's order from the page's start,
+    /// pushing matching incidents until the page is complete
+    fn scan(&self, filter: &IncidentFilter, page: &mut PageBuilder<'_>) -> Result<()> {
+        let candidates = self.candidate_ids(filter)?;
+        let (tree, prefix) = self.sort_index(filter.sort.field);
+
+        let mut lower = Bound::Included(prefix.clone());
+        let mut upper = match prefix.first() {
+            Some(&field) => Bound::Excluded(vec![field + 1]),
+            None => Bound::Unbounded,
+        };
+        let descending = filter.sort.direction == SortDirection::Desc;
+        if let Some(start) = page.start() {
+            let key = Self::sort_key(&prefix, start.key(), &start.id());
+            if descending {
+                upper = Bound::Excluded(key);
+            } else {
+                lower = Bound::Excluded(key);
+            }
+        }
+
+        let entries = tree.range::<Vec<u8>, _>((lower, upper));
+        let entries: Box<dyn Iterator<Item = sled::Result<(sled::IVec, sled::IVec)>>> =
+            if descending {
+                Box::new(entries.rev())
+            } else {
+                Box::new(entries)
+            };
+
+        for entry in entries {
+            let (key, _) = entry
+                .map_err(|e| AppError::Internal(format!("Failed to read sort index: {}", e)))?;
+            let Ok(id) = Uuid::from_slice(&key[key.len().saturating_sub(16)..]) else {
+                continue;
+            };
+            if candidates.as_ref().is_some_and(|ids| !ids.contains(&id)) {
+                continue;
+            }
+
+            let value = self
+                .incidents_tree
+                .get(Self::incident_key(&id))
+                .map_err(|e| AppError::Internal(format!("Failed to get incident: {}", e)))?;
+            if let Some(value) = value {
+                if !page.push(Self::deserialize_incident(&value)?) {
+                    break;
+                }
             }
         }
 
         Ok(())
     }
 
+    /// All incidents matching the filter, unordered
+    fn query(&self, filter: &IncidentFilter) -> Result<Vec<Incident>> {
+        let mut incidents = Vec::new();
+
+        match self.candidate_ids(filter)? {
+            Some(ids) => {
+                for id in ids {
+                    let value = self.incidents_tree.get(Self::incident_key(&id)).map_err(|e| {
+                        AppError::Internal(format!("Failed to get incident: {}", e))
+                    })?;
+                    if let Some(value) = value {
+                        let incident = Self::deserialize_incident(&value)?;
+                        if filter.matches(&incident) {
+                            incidents.push(incident);
+                        }
+                    }
+                }
+            }
+            None => {
+                for result in self.incidents_tree.iter() {
+                    let (_, value) = result.map_err(|e| {
+                        AppError::Internal(format!("Failed to iterate incidents: {}", e))
+                    })?;
+                    let incident = Self::deserialize_incident(&value)?;
+                    if filter.matches(&incident) {
+                        incidents.push(incident);
+                    }
+                }
+            }
+        }
+
+        Ok(incidents)
+    }
+
     /// Flush pending writes to disk
     pub async fn flush(&self) -> Result<()> {
         self.db.flush_async().await.map_err(|e| {
@@ -143,15 +716,7 @@ impl SledStore {
 #[async_trait]
 impl IncidentStore for SledStore {
     async fn save_incident(&self, incident: &Incident) -> Result<()> {
-        let key = Self::incident_key(&incident.id);
-        let value = Self::serialize_incident(incident)?;
-
-        self.incidents_tree.insert(&key, value).map_err(|e| {
-            AppError::Internal(format!("Failed to save incident: {}", e))
-        })?;
-
-        // Update fingerprint index
-        self.update_fingerprint_index(incident)?;
+        self.write_incident(&incident.id, |_| Ok(Some(incident.clone())))?;
 
         // Flush to ensure durability
         self.incidents_tree.flush().map_err(|e| {
@@ -179,27 +744,26 @@ impl IncidentStore for SledStore {
     }
 
     async fn update_incident(&self, incident: &Incident) -> Result<()> {
-        let key = Self::incident_key(&incident.id);
-
-        // Check if incident exists
-        if !self.incidents_tree.contains_key(&key).map_err(|e| {
-            AppError::Internal(format!("Failed to check incident existence: {}", e))
-        })? {
-            return Err(AppError::NotFound(format!(
-                "Incident {} not found",
-                incident.id
-            )));
-        }
-
-        let value = Self::serialize_incident(incident)?;
+        self.write_incident(&incident.id, |current| {
+            let Some(current) = current else {
+                return Err(AppError::NotFound(format!(
+                    "Incident {} not found",
+                    incident.id
+                )));
+            };
+            if current.version != incident.version {
+                return Err(AppError::VersionConflict {
+                    resource: format!("incident {}", incident.id),
+                    expected: incident.version,
+                    actual: current.version,
+                });
+            }
 
-        self.incidents_tree.insert(&key, value).map_err(|e| {
-            AppError::Internal(format!("Failed to update incident: {}", e))
+            let mut updated = incident.clone();
+            updated.version += 1;
+            Ok(Some(updated))
         })?;
 
-        // Update fingerprint index
-        self.update_fingerprint_index(incident)?;
-
         // Flush to ensure durability
         self.incidents_tree.flush().map_err(|e| {
             AppError::Internal(format!("Failed to flush incidents tree: {}", e))
@@ -210,26 +774,11 @@ impl IncidentStore for SledStore {
     }
 
     async fn delete_incident(&self, id: &Uuid) -> Result<()> {
-        let key = Self::incident_key(id);
-
-        // Get incident first to access fingerprint
-        let incident = match self.get_incident(id).await? {
-            Some(i) => i,
-            None => {
-                return Err(AppError::NotFound(format!("Incident {} not found", id)));
-            }
-        };
-
-        // Remove from incidents tree
-        self.incidents_tree.remove(&key).map_err(|e| {
-            AppError::Internal(format!("Failed to delete incident: {}", e))
+        self.write_incident(id, |current| match current {
+            Some(_) => Ok(None),
+            None => Err(AppError::NotFound(format!("Incident {} not found", id))),
         })?;
 
-        // Remove from fingerprint index
-        if let Some(ref fingerprint) = incident.fingerprint {
-            self.remove_from_fingerprint_index(id, fingerprint)?;
-        }
-
         // Flush to ensure durability
         self.incidents_tree.flush().map_err(|e| {
             AppError::Internal(format!("Failed to flush incidents tree: {}", e))
@@ -245,112 +794,247 @@ impl IncidentStore for SledStore {
         page: u32,
         page_size: u32,
     ) -> Result<Vec<Incident>> {
-        let mut incidents: Vec<Incident> = Vec::new();
+        let mut page = PageBuilder::offset(filter, page, page_size);
+        self.scan(filter, &mut page)?;
+        Ok(page.finish().incidents)
+    }
 
-        // Iterate over all incidents
-        for result in self.incidents_tree.iter() {
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        let mut page = PageBuilder::new(filter, cursor, limit)?;
+        self.scan(filter, &mut page)?;
+        Ok(page.finish())
+    }
+
+    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
+        Ok(self.query(filter)?.len() as u64)
+    }
+
+    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
+        let key = Self::fingerprint_key(fingerprint);
+
+        match self.fingerprint_tree.get(&key) {
+            Ok(Some(bytes)) => {
+                let incident_ids: Vec<Uuid> = bincode::deserialize(&bytes).unwrap_or_default();
+
+                let mut incidents = Vec::new();
+                for id in incident_ids {
+                    if let Some(incident) = self.get_incident(&id).await? {
+                        incidents.push(incident);
+                    }
+                }
+
+                Ok(incidents)
+            }
+            Ok(None) => Ok(Vec::new()),
+            Err(e) => Err(AppError::Internal(format!(
+                "Failed to query fingerprint index: {}",
+                e
+            ))),
+        }
+    }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        let mut events = Vec::new();
+
+        for result in self.events_tree.scan_prefix(id.as_bytes()) {
             let (_, value) = result.map_err(|e| {
-                AppError::Internal(format!("Failed to iterate incidents: {}", e))
+                AppError::Internal(format!("Failed to iterate incident events: {}", e))
             })?;
 
-            let incident = Self::deserialize_incident(&value)?;
+            events.push(serde_json::from_slice(&value).map_err(|e| {
+                AppError::Internal(format!("Failed to deserialize incident event: {}", e))
+            })?);
+        }
 
-            // Apply filters
-            let state_match =
-                filter.states.is_empty() || filter.states.contains(&incident.state);
+        Ok(events)
+    }
 
-            let severity_match = filter.severities.is_empty()
-                || filter.severities.contains(&incident.severity);
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        let key = Self::incident_key(id);
+        // Logs written before the sequence counter existed
+        let fallback_sequence = self.next_event_sequence(id)?;
+        let next_sequence = events.last().map_or(0, |event| event.sequence + 1);
+
+        let mut entries = Vec::with_capacity(events.len());
+        for event in events {
+            let value = serde_json::to_vec(event).map_err(|e| {
+                AppError::Internal(format!("Failed to serialize incident event: {}", e))
+            })?;
+            entries.push((Self::event_key(id, event.sequence), value));
+        }
 
-            let source_match = filter.sources.is_empty()
-                || filter
-                    .sources
-                    .iter()
-                    .any(|s| incident.source.contains(s));
+        (&self.events_tree, &self.event_sequences_tree)
+            .transaction(|(events_tree, sequences)| {
+                let stored_sequence = match sequences.get(&key)? {
+                    Some(bytes) => {
+                        let mut sequence = [0u8; 8];
+                        sequence.copy_from_slice(&bytes);
+                        u64::from_be_bytes(sequence)
+                    }
+                    None => fallback_sequence,
+                };
 
-            let active_match = !filter.active_only || incident.is_active();
+                for sequence in 0..stored_sequence {
+                    events_tree.remove(Self::event_key(id, sequence))?;
+                }
+                for (event_key, value) in &entries {
+                    events_tree.insert(event_key.as_slice(), value.as_slice())?;
+                }
+                sequences.insert(key.as_slice(), &next_sequence.to_be_bytes()[..])?;
+
+                Ok::<_, ConflictableTransactionError<AppError>>(())
+            })
+            .map_err(|e| match e {
+                TransactionError::Abort(e) => e,
+                TransactionError::Storage(e) => {
+                    AppError::Internal(format!("Failed to replace incident events: {}", e))
+                }
+            })
+    }
 
-            if state_match && severity_match && source_match && active_match {
-                incidents.push(incident);
-            }
-        }
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        let key = Self::incident_key(&postmortem.id);
+        let value = Self::serialize_postmortem(postmortem)?;
 
-        // Sort by creation time (newest first)
-        incidents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        self.postmortems_tree.insert(&key, value).map_err(|e| {
+            AppError::Internal(format!("Failed to save post-mortem: {}", e))
+        })?;
 
-        // Apply pagination
-        let start = (page * page_size) as usize;
+        self.postmortems_tree.flush().map_err(|e| {
+            AppError::Internal(format!("Failed to flush postmortems tree: {}", e))
+        })?;
 
-        Ok(incidents
-            .into_iter()
-            .skip(start)
-            .take(page_size as usize)
-            .collect())
+        tracing::debug!(postmortem_id = %postmortem.id, "Post-mortem saved to Sled");
+        Ok(())
     }
 
-    async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
-        let mut count = 0u64;
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        match self.postmortems_tree.get(Self::incident_key(id)) {
+            Ok(Some(bytes)) => Ok(Some(Self::deserialize_postmortem(&bytes)?)),
+            Ok(None) => Ok(None),
+            Err(e) => Err(AppError::Internal(format!(
+                "Failed to get post-mortem: {}",
+                e
+            ))),
+        }
+    }
 
-        // Iterate over all incidents
-        for result in self.incidents_tree.iter() {
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
+        let mut postmortems = Vec::new();
+
+        for result in self.postmortems_tree.iter() {
             let (_, value) = result.map_err(|e| {
-                AppError::Internal(format!("Failed to iterate incidents: {}", e))
+                AppError::Internal(format!("Failed to iterate post-mortems: {}", e))
             })?;
 
-            let incident = Self::deserialize_incident(&value)?;
+            let postmortem = Self::deserialize_postmortem(&value)?;
+            if incident_id.is_none_or(|id| postmortem.incident_id == *id) {
+                postmortems.push(postmortem);
+            }
+        }
 
-            // Apply filters
-            let state_match =
-                filter.states.is_empty() || filter.states.contains(&incident.state);
+        Ok(postmortems)
+    }
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        let key = Self::incident_key(&execution.id);
+        let value = Self::serialize_execution(execution)?;
 
-            let severity_match = filter.severities.is_empty()
-                || filter.severities.contains(&incident.severity);
+        self.executions_tree.insert(&key, value).map_err(|e| {
+            AppError::Internal(format!("Failed to save playbook execution: {}", e))
+        })?;
 
-            let source_match = filter.sources.is_empty()
-                || filter
-                    .sources
-                    .iter()
-                    .any(|s| incident.source.contains(s));
+        self.executions_tree.flush().map_err(|e| {
+            AppError::Internal(format!("Failed to flush playbook executions tree: {}", e))
+        })?;
+
+        tracing::debug!(execution_id = %execution.id, "Playbook execution saved to Sled");
+        Ok(())
+    }
+
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
+        match self.executions_tree.get(Self::incident_key(id)) {
+            Ok(Some(bytes)) => Ok(Some(Self::deserialize_execution(&bytes)?)),
+            Ok(None) => Ok(None),
+            Err(e) => Err(AppError::Internal(format!(
+                "Failed to get playbook execution: {}",
+                e
+            ))),
+        }
+    }
 
-            let active_match = !filter.active_only || incident.is_active();
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        let mut executions = Vec::new();
+
+        for result in self.executions_tree.iter() {
+            let (_, value) = result.map_err(|e| {
+                AppError::Internal(format!("Failed to iterate playbook executions: {}", e))
+            })?;
 
-            if state_match && severity_match && source_match && active_match {
-                count += 1;
+            let execution = Self::deserialize_execution(&value)?;
+            if incident_id.is_none_or(|id| execution.incident_id == *id) {
+                executions.push(execution);
             }
         }
 
-        Ok(count)
+        Ok(executions)
     }
 
-    async fn find_by_fingerprint(&self, fingerprint: &str) -> Result<Vec<Incident>> {
-        let key = Self::fingerprint_key(fingerprint);
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        let key = Self::incident_key(&state.incident_id);
+        let value = Self::serialize_escalation(state)?;
 
-        match self.fingerprint_tree.get(&key) {
-            Ok(Some(bytes)) => {
-                let incident_ids: Vec<Uuid> = bincode::deserialize(&bytes).unwrap_or_default();
+        self.escalations_tree.insert(&key, value).map_err(|e| {
+            AppError::Internal(format!("Failed to save escalation state: {}", e))
+        })?;
 
-                let mut incidents = Vec::new();
-                for id in incident_ids {
-                    if let Some(incident) = self.get_incident(&id).await? {
-                        incidents.push(incident);
-                    }
-                }
+        self.escalations_tree.flush().map_err(|e| {
+            AppError::Internal(format!("Failed to flush escalation states tree: {}", e))
+        })?;
 
-                Ok(incidents)
-            }
-            Ok(None) => Ok(Vec::new()),
+        tracing::debug!(incident_id = %state.incident_id, "Escalation state saved to Sled");
+        Ok(())
+    }
+
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        match self.escalations_tree.get(Self::incident_key(incident_id)) {
+            Ok(Some(bytes)) => Ok(Some(Self::deserialize_escalation(&bytes)?)),
+            Ok(None) => Ok(None),
             Err(e) => Err(AppError::Internal(format!(
-                "Failed to query fingerprint index: {}",
+                "Failed to get escalation state: {}",
                 e
             ))),
         }
     }
+
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
+        let mut states = Vec::new();
+
+        for result in self.escalations_tree.iter() {
+            let (_, value) = result.map_err(|e| {
+                AppError::Internal(format!("Failed to iterate escalation states: {}", e))
+            })?;
+
+            states.push(Self::deserialize_escalation(&value)?);
+        }
+
+        Ok(states)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{IncidentState, IncidentType, Severity};
+    use crate::models::{IncidentState, IncidentType, ResolutionMethod, Severity};
+    use crate::state::event_log::IncidentEventPayload;
+    use crate::state::query::{IncidentSort, LabelSelector, TimeRange};
     use tempfile::TempDir;
 
     fn create_test_store() -> (SledStore, TempDir) {
@@ -398,6 +1082,11 @@ mod tests {
 
         let retrieved = store.get_incident(&incident.id).await.unwrap().unwrap();
         assert_eq!(retrieved.state, IncidentState::Investigating);
+        assert_eq!(retrieved.version, 1);
+
+        // A writer still holding the old version is rejected
+        let err = store.update_incident(&incident).await.unwrap_err();
+        assert!(matches!(err, AppError::VersionConflict { actual: 1, .. }));
     }
 
     #[tokio::test]
@@ -506,6 +1195,47 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_reopen_rebuilds_lagging_indexes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().to_path_buf();
+
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test Incident".to_string(),
+            "Description".to_string(),
+            Severity::P1,
+            IncidentType::Application,
+        );
+        incident.fingerprint = Some("fp-lagging".to_string());
+
+        // Index entries lost by a crash after the incident write, as older
+        // versions wrote them outside the incident's transaction
+        {
+            let store = SledStore::new(&path).unwrap();
+            store.save_incident(&incident).await.unwrap();
+            let found = store.find_by_fingerprint("fp-lagging").await.unwrap();
+            assert_eq!(found.len(), 1);
+
+            store.fingerprint_tree.clear().unwrap();
+            let created_key = SortField::CreatedAt.key(&incident);
+            store
+                .created_tree
+                .remove(SledStore::sort_key(&[], created_key, &incident.id))
+                .unwrap();
+            store.flush().await.unwrap();
+        }
+
+        let store = SledStore::new(&path).unwrap();
+        let incidents = store
+            .list_incidents(&IncidentFilter::default(), 0, 10)
+            .await
+            .unwrap();
+        assert_eq!(incidents.len(), 1);
+        let found = store.find_by_fingerprint("fp-lagging").await.unwrap();
+        assert_eq!(found.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_count_incidents() {
         let (store, _temp_dir) = create_test_store();
@@ -536,4 +1266,319 @@ mod tests {
         let count = store.count_incidents(&filter).await.unwrap();
         assert_eq!(count, 3);
     }
+
+    #[tokio::test]
+    async fn test_incident_history_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+
+        {
+            let store = SledStore::new(temp_dir.path()).unwrap();
+            store.save_incident(&incident).await.unwrap();
+            incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+            store.update_incident(&incident).await.unwrap();
+        }
+
+        let store = SledStore::new(temp_dir.path()).unwrap();
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert_eq!(events.len(), 3); // created, state changed, timeline appended
+        assert!(events.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+
+        let rebuilt = store
+            .get_incident_at(&incident.id, chrono::Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(rebuilt, store.get_incident(&incident.id).await.unwrap().unwrap());
+        assert_eq!(rebuilt.version, 1);
+
+        let before_creation = incident.created_at - chrono::Duration::seconds(1);
+        assert!(store
+            .get_incident_at(&incident.id, before_creation)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_writes_keep_event_log_consistent() {
+        let (store, _temp_dir) = create_test_store();
+        let incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let store = store.clone();
+                let mut incident = incident.clone();
+                tokio::spawn(async move {
+                    incident.title = format!("Title {}", i);
+                    store.save_incident(&incident).await.unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert_eq!(events.len(), 9);
+        assert!(events
+            .iter()
+            .enumerate()
+            .all(|(i, e)| e.sequence == i as u64));
+        let rebuilt = event_log::replay(&events, None).unwrap();
+        assert_eq!(
+            rebuilt,
+            store.get_incident(&incident.id).await.unwrap().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_history_of_incident_stored_before_event_log() {
+        let (store, _temp_dir) = create_test_store();
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        store
+            .incidents_tree
+            .insert(
+                SledStore::incident_key(&incident.id),
+                SledStore::serialize_incident(&incident).unwrap(),
+            )
+            .unwrap();
+        let stored_at = incident.updated_at;
+
+        // Falls back to the stored record while the log is empty
+        let now = chrono::Utc::now();
+        let at_now = store.get_incident_at(&incident.id, now).await.unwrap();
+        assert_eq!(at_now.as_ref(), Some(&incident));
+
+        // The first write snapshots the stored record before changing it
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert!(matches!(
+            events[0].payload,
+            IncidentEventPayload::Created { .. }
+        ));
+        let before_update = store
+            .get_incident_at(&incident.id, stored_at)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(before_update.state, IncidentState::Detected);
+        let latest = store
+            .get_incident_at(&incident.id, chrono::Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(latest.state, IncidentState::Investigating);
+    }
+
+    #[tokio::test]
+    async fn test_reads_incidents_written_before_envelope() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        incident.fingerprint = Some("legacy".to_string());
+
+        {
+            let store = SledStore::new(temp_dir.path()).unwrap();
+            store
+                .incidents_tree
+                .insert(
+                    SledStore::incident_key(&incident.id),
+                    codec::encode_legacy_incident(&incident),
+                )
+                .unwrap();
+        }
+
+        // Reopening rebuilds the indexes from the old record
+        let store = SledStore::new(temp_dir.path()).unwrap();
+        assert_eq!(
+            store.get_incident(&incident.id).await.unwrap(),
+            Some(incident.clone())
+        );
+        assert_eq!(
+            store
+                .count_incidents(&IncidentFilter::default())
+                .await
+                .unwrap(),
+            1
+        );
+
+        // The next write stores it in the current format
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(stored.version, 1);
+        assert_eq!(stored.state, IncidentState::Investigating);
+    }
+
+    #[tokio::test]
+    async fn test_query_uses_term_and_time_indexes() {
+        let (store, _temp_dir) = create_test_store();
+        let start = chrono::Utc::now();
+
+        let mut ids = Vec::new();
+        for i in 0..6i64 {
+            let mut incident = Incident::new(
+                "test-source".to_string(),
+                format!("Incident {}", i),
+                "Description".to_string(),
+                Severity::P2,
+                IncidentType::Application,
+            );
+            incident.created_at = start + chrono::Duration::minutes(i);
+            let team = if i % 2 == 0 { "db" } else { "web" };
+            incident.labels.insert("team".to_string(), team.to_string());
+            store.save_incident(&incident).await.unwrap();
+            ids.push(incident.id);
+        }
+
+        // Label and created range together, oldest first
+        let filter = IncidentFilter {
+            labels: vec![LabelSelector::equals("team", "db")],
+            created: TimeRange::new(Some(start + chrono::Duration::minutes(1)), None),
+            sort: IncidentSort::new(SortField::CreatedAt, SortDirection::Asc),
+            ..Default::default()
+        };
+        let first = store.query_incidents(&filter, None, 1).await.unwrap();
+        assert_eq!(first.incidents[0].id, ids[2]);
+        let second = store
+            .query_incidents(&filter, first.next_cursor.as_deref(), 1)
+            .await
+            .unwrap();
+        assert_eq!(second.incidents[0].id, ids[4]);
+        assert!(second.next_cursor.is_none());
+
+        // Relabelling moves the incident in the term index
+        let mut incident = store.get_incident(&ids[4]).await.unwrap().unwrap();
+        incident.labels.insert("team".to_string(), "web".to_string());
+        store.update_incident(&incident).await.unwrap();
+        assert_eq!(store.count_incidents(&filter).await.unwrap(), 1);
+
+        // Indexes are rebuilt for databases that predate them
+        store.index_tree.clear().unwrap();
+        store.created_tree.clear().unwrap();
+        store.rebuild_indices().unwrap();
+        assert_eq!(store.count_incidents(&filter).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages_walk_every_sort_index() {
+        let (store, _temp_dir) = create_test_store();
+        let start = chrono::Utc::now() - chrono::Duration::hours(1);
+        let severities = [Severity::P0, Severity::P1, Severity::P2, Severity::P3];
+
+        let mut incidents = Vec::new();
+        for i in 0..12i64 {
+            let mut incident = Incident::new(
+                "test-source".to_string(),
+                format!("Incident {}", i),
+                "Description".to_string(),
+                severities[i as usize % 4],
+                IncidentType::Application,
+            );
+            // Repeated creation times and severities exercise the ID tie-break
+            incident.created_at = start + chrono::Duration::minutes(i % 5);
+            if i % 3 == 0 {
+                incident.resolve(
+                    "user".to_string(),
+                    ResolutionMethod::Manual,
+                    "Fixed".to_string(),
+                    None,
+                );
+            }
+            store.save_incident(&incident).await.unwrap();
+            incidents.push(incident);
+        }
+
+        for field in SortField::ALL {
+            for direction in [SortDirection::Asc, SortDirection::Desc] {
+                let filter = IncidentFilter {
+                    sort: IncidentSort::new(field, direction),
+                    ..Default::default()
+                };
+                let mut expected = incidents.clone();
+                query::sort_incidents(&mut expected, &filter.sort);
+                let expected: Vec<Uuid> = expected.iter().map(|i| i.id).collect();
+
+                let mut seen = Vec::new();
+                let mut cursor = None;
+                loop {
+                    let page = store
+                        .query_incidents(&filter, cursor.as_deref(), 5)
+                        .await
+                        .unwrap();
+                    seen.extend(page.incidents.iter().map(|i| i.id));
+                    cursor = page.next_cursor;
+                    if cursor.is_none() {
+                        break;
+                    }
+                }
+                assert_eq!(seen, expected, "{:?}", filter.sort);
+
+                let second = store.list_incidents(&filter, 1, 5).await.unwrap();
+                let second: Vec<Uuid> = second.iter().map(|i| i.id).collect();
+                assert_eq!(second, expected[5..10]);
+            }
+        }
+
+        // Index candidates are visited in sort order too
+        let filter = IncidentFilter {
+            severities: vec![Severity::P1, Severity::P2],
+            sort: IncidentSort::new(SortField::State, SortDirection::Desc),
+            ..Default::default()
+        };
+        let mut expected: Vec<Incident> = incidents
+            .iter()
+            .filter(|i| filter.matches(i))
+            .cloned()
+            .collect();
+        query::sort_incidents(&mut expected, &filter.sort);
+
+        let first = store.query_incidents(&filter, None, 4).await.unwrap();
+        let second = store
+            .query_incidents(&filter, first.next_cursor.as_deref(), 4)
+            .await
+            .unwrap();
+        assert!(second.next_cursor.is_none());
+        let seen: Vec<Uuid> = first
+            .incidents
+            .iter()
+            .chain(&second.incidents)
+            .map(|i| i.id)
+            .collect();
+        assert_eq!(seen, expected.iter().map(|i| i.id).collect::<Vec<_>>());
+
+        // The sort index is rebuilt for databases that predate it
+        store.sort_tree.clear().unwrap();
+        store.rebuild_indices().unwrap();
+        let page = store.query_incidents(&filter, None, 10).await.unwrap();
+        assert_eq!(page.incidents.len(), 6);
+    }
 }
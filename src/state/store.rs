@@ -1,6 +1,10 @@
 use crate::error::{AppError, Result};
-use crate::models::Incident;
-use crate::state::{IncidentFilter, IncidentStore};
+use crate::escalation::EscalationState;
+use crate::models::{Incident, PlaybookExecution};
+use crate::postmortem::PostMortem;
+use crate::state::event_log::{self, IncidentEvent};
+use crate::state::query::{self, IncidentFilter, IncidentPage};
+use crate::state::IncidentStore;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use std::sync::Arc;
@@ -11,6 +15,10 @@ use uuid::Uuid;
 pub struct InMemoryStore {
     incidents: Arc<DashMap<Uuid, Incident>>,
     fingerprint_index: Arc<DashMap<String, Vec<Uuid>>>,
+    postmortems: Arc<DashMap<Uuid, PostMortem>>,
+    playbook_executions: Arc<DashMap<Uuid, PlaybookExecution>>,
+    escalation_states: Arc<DashMap<Uuid, EscalationState>>,
+    events: Arc<DashMap<Uuid, Vec<IncidentEvent>>>,
 }
 
 impl InMemoryStore {
@@ -18,8 +26,28 @@ impl InMemoryStore {
         Self {
             incidents: Arc::new(DashMap::new()),
             fingerprint_index: Arc::new(DashMap::new()),
+            postmortems: Arc::new(DashMap::new()),
+            playbook_executions: Arc::new(DashMap::new()),
+            escalation_states: Arc::new(DashMap::new()),
+            events: Arc::new(DashMap::new()),
         }
     }
+
+    /// Append the events describing a write to the incident's log
+    fn record_write(&self, before: Option<&Incident>, after: &Incident) {
+        let mut log = self.events.entry(after.id).or_default();
+        let next_sequence = log.len() as u64;
+        log.extend(event_log::events_for_write(before, after, next_sequence));
+    }
+
+    /// All incidents matching the filter, unordered
+    fn matching(&self, filter: &IncidentFilter) -> Vec<Incident> {
+        self.incidents
+            .iter()
+            .filter(|entry| filter.matches(entry.value()))
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
 }
 
 impl Default for InMemoryStore {
@@ -31,14 +59,18 @@ impl Default for InMemoryStore {
 #[async_trait]
 impl IncidentStore for InMemoryStore {
     async fn save_incident(&self, incident: &Incident) -> Result<()> {
-        self.incidents.insert(incident.id, incident.clone());
+        let previous = self.incidents.insert(incident.id, incident.clone());
+        self.record_write(previous.as_ref(), incident);
 
         // Update fingerprint index if present
         if let Some(ref fingerprint) = incident.fingerprint {
-            self.fingerprint_index
+            let mut ids = self
+                .fingerprint_index
                 .entry(fingerprint.clone())
-                .or_insert_with(Vec::new)
-                .push(incident.id);
+                .or_default();
+            if !ids.contains(&incident.id) {
+                ids.push(incident.id);
+            }
         }
 
         tracing::debug!(incident_id = %incident.id, "Incident saved");
@@ -50,16 +82,30 @@ impl IncidentStore for InMemoryStore {
     }
 
     async fn update_incident(&self, incident: &Incident) -> Result<()> {
-        if self.incidents.contains_key(&incident.id) {
-            self.incidents.insert(incident.id, incident.clone());
-            tracing::debug!(incident_id = %incident.id, "Incident updated");
-            Ok(())
-        } else {
-            Err(AppError::NotFound(format!(
+        let Some(mut entry) = self.incidents.get_mut(&incident.id) else {
+            return Err(AppError::NotFound(format!(
                 "Incident {} not found",
                 incident.id
-            )))
+            )));
+        };
+
+        if entry.version != incident.version {
+            return Err(AppError::VersionConflict {
+                resource: format!("incident {}", incident.id),
+                expected: incident.version,
+                actual: entry.version,
+            });
         }
+
+        let mut updated = incident.clone();
+        updated.version += 1;
+        let previous = std::mem::replace(entry.value_mut(), updated);
+        // Record while the entry is still locked so the log follows write order
+        self.record_write(Some(&previous), entry.value());
+        drop(entry);
+
+        tracing::debug!(incident_id = %incident.id, "Incident updated");
+        Ok(())
     }
 
     async fn delete_incident(&self, id: &Uuid) -> Result<()> {
@@ -70,6 +116,12 @@ impl IncidentStore for InMemoryStore {
                     entry.retain(|&incident_id| incident_id != *id);
                 }
             }
+
+            let mut log = self.events.entry(*id).or_default();
+            let next_sequence = log.len() as u64;
+            log.extend(event_log::events_for_delete(&incident, next_sequence));
+            drop(log);
+
             tracing::debug!(incident_id = %id, "Incident deleted");
             Ok(())
         } else {
@@ -83,64 +135,23 @@ impl IncidentStore for InMemoryStore {
         page: u32,
         page_size: u32,
     ) -> Result<Vec<Incident>> {
-        let mut incidents: Vec<Incident> = self
-            .incidents
-            .iter()
-            .map(|entry| entry.value().clone())
-            .filter(|incident| {
-                // Apply state filter
-                let state_match = filter.states.is_empty()
-                    || filter.states.contains(&incident.state);
-
-                // Apply severity filter
-                let severity_match = filter.severities.is_empty()
-                    || filter.severities.contains(&incident.severity);
-
-                // Apply source filter
-                let source_match = filter.sources.is_empty()
-                    || filter.sources.iter().any(|s| incident.source.contains(s));
-
-                // Apply active filter
-                let active_match = !filter.active_only || incident.is_active();
-
-                state_match && severity_match && source_match && active_match
-            })
-            .collect();
-
-        // Sort by creation time (newest first)
-        incidents.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
-        // Apply pagination
-        let start = (page * page_size) as usize;
-        let _end = start + page_size as usize;
+        Ok(query::page_offset(self.matching(filter), &filter.sort, page, page_size))
+    }
 
-        Ok(incidents
-            .into_iter()
-            .skip(start)
-            .take(page_size as usize)
-            .collect())
+    async fn query_incidents(
+        &self,
+        filter: &IncidentFilter,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<IncidentPage> {
+        query::paginate(self.matching(filter), &filter.sort, cursor, limit)
     }
 
     async fn count_incidents(&self, filter: &IncidentFilter) -> Result<u64> {
         let count = self
             .incidents
             .iter()
-            .filter(|entry| {
-                let incident = entry.value();
-
-                let state_match = filter.states.is_empty()
-                    || filter.states.contains(&incident.state);
-
-                let severity_match = filter.severities.is_empty()
-                    || filter.severities.contains(&incident.severity);
-
-                let source_match = filter.sources.is_empty()
-                    || filter.sources.iter().any(|s| incident.source.contains(s));
-
-                let active_match = !filter.active_only || incident.is_active();
-
-                state_match && severity_match && source_match && active_match
-            })
+            .filter(|entry| filter.matches(entry.value()))
             .count();
 
         Ok(count as u64)
@@ -157,12 +168,87 @@ impl IncidentStore for InMemoryStore {
             Ok(Vec::new())
         }
     }
+
+    async fn get_incident_events(&self, id: &Uuid) -> Result<Vec<IncidentEvent>> {
+        Ok(self
+            .events
+            .get(id)
+            .map(|entry| entry.value().clone())
+            .unwrap_or_default())
+    }
+
+    async fn replace_incident_events(&self, id: &Uuid, events: &[IncidentEvent]) -> Result<()> {
+        self.events.insert(*id, events.to_vec());
+        Ok(())
+    }
+
+    async fn save_postmortem(&self, postmortem: &PostMortem) -> Result<()> {
+        self.postmortems.insert(postmortem.id, postmortem.clone());
+        tracing::debug!(postmortem_id = %postmortem.id, "Post-mortem saved");
+        Ok(())
+    }
+
+    async fn get_postmortem(&self, id: &Uuid) -> Result<Option<PostMortem>> {
+        Ok(self.postmortems.get(id).map(|entry| entry.clone()))
+    }
+
+    async fn list_postmortems(&self, incident_id: Option<&Uuid>) -> Result<Vec<PostMortem>> {
+        Ok(self
+            .postmortems
+            .iter()
+            .filter(|entry| incident_id.is_none_or(|id| entry.value().incident_id == *id))
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn save_playbook_execution(&self, execution: &PlaybookExecution) -> Result<()> {
+        self.playbook_executions.insert(execution.id, execution.clone());
+        Ok(())
+    }
+
+    async fn get_playbook_execution(&self, id: &Uuid) -> Result<Option<PlaybookExecution>> {
+        Ok(self.playbook_executions.get(id).map(|entry| entry.clone()))
+    }
+
+    async fn list_playbook_executions(
+        &self,
+        incident_id: Option<&Uuid>,
+    ) -> Result<Vec<PlaybookExecution>> {
+        Ok(self
+            .playbook_executions
+            .iter()
+            .filter(|entry| incident_id.is_none_or(|id| entry.value().incident_id == *id))
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
+
+    async fn save_escalation_state(&self, state: &EscalationState) -> Result<()> {
+        self.escalation_states
+            .insert(state.incident_id, state.clone());
+        Ok(())
+    }
+
+    async fn get_escalation_state(&self, incident_id: &Uuid) -> Result<Option<EscalationState>> {
+        Ok(self
+            .escalation_states
+            .get(incident_id)
+            .map(|entry| entry.clone()))
+    }
+
+    async fn list_escalation_states(&self) -> Result<Vec<EscalationState>> {
+        Ok(self
+            .escalation_states
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::models::{IncidentState, IncidentType, Severity};
+    use crate::state::event_log::IncidentEventPayload;
 
     #[tokio::test]
     async fn test_save_and_get_incident() {
@@ -203,6 +289,42 @@ mod tests {
 
         let retrieved = store.get_incident(&incident.id).await.unwrap().unwrap();
         assert_eq!(retrieved.state, IncidentState::Investigating);
+        assert_eq!(retrieved.version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_incident_version_conflict() {
+        let store = InMemoryStore::new();
+
+        let incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Infrastructure,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        let mut first = incident.clone();
+        first.update_state(IncidentState::Investigating, "alice".to_string());
+        store.update_incident(&first).await.unwrap();
+
+        // Second writer still holds version 0
+        let mut second = incident.clone();
+        second.severity = Severity::P0;
+        let err = store.update_incident(&second).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::VersionConflict {
+                expected: 0,
+                actual: 1,
+                ..
+            }
+        ));
+
+        let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+        assert_eq!(stored.state, IncidentState::Investigating);
+        assert_eq!(stored.severity, Severity::P2);
     }
 
     #[tokio::test]
@@ -255,4 +377,46 @@ mod tests {
         assert_eq!(found.len(), 1);
         assert_eq!(found[0].id, incident1.id);
     }
+
+    #[tokio::test]
+    async fn test_incident_history() {
+        let store = InMemoryStore::new();
+
+        let mut incident = Incident::new(
+            "test-source".to_string(),
+            "Test".to_string(),
+            "Description".to_string(),
+            Severity::P2,
+            IncidentType::Application,
+        );
+        store.save_incident(&incident).await.unwrap();
+
+        incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+        incident.version += 1;
+        let checkpoint = chrono::Utc::now();
+
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        incident.update_state(IncidentState::Remediating, "user@example.com".to_string());
+        store.update_incident(&incident).await.unwrap();
+        store.delete_incident(&incident.id).await.unwrap();
+
+        let events = store.get_incident_events(&incident.id).await.unwrap();
+        assert!(events.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+        assert!(matches!(events.last().unwrap().payload, IncidentEventPayload::Deleted));
+
+        let past = store
+            .get_incident_at(&incident.id, checkpoint)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(past.state, IncidentState::Investigating);
+        assert_eq!(past.timeline.len(), 2);
+
+        assert!(store
+            .get_incident_at(&incident.id, chrono::Utc::now())
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -1,4 +1,9 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
 use llm_incident_manager::{
+    api::{build_router, AppState},
     escalation::{
         EscalationEngine, EscalationStatus, RoutingRuleEvaluator, ScheduleResolver,
     },
@@ -10,9 +15,11 @@ use llm_incident_manager::{
         },
         Incident, IncidentType, Severity,
     },
-    state::InMemoryStore,
+    processing::{DeduplicationEngine, IncidentProcessor},
+    state::{InMemoryStore, IncidentStore},
 };
 use std::sync::Arc;
+use tower::ServiceExt;
 use uuid::Uuid;
 
 fn create_test_incident() -> Incident {
@@ -74,7 +81,7 @@ async fn test_escalation_engine_end_to_end() {
     store.save_incident(&incident).await.unwrap();
 
     // Start escalation
-    engine.start_escalation(&incident, policy_id).unwrap();
+    engine.start_escalation(&incident, policy_id).await.unwrap();
 
     // Verify escalation state
     let state = engine.get_escalation_state(&incident.id).unwrap();
@@ -100,11 +107,12 @@ async fn test_escalation_acknowledgment() {
     let incident = create_test_incident();
     store.save_incident(&incident).await.unwrap();
 
-    engine.start_escalation(&incident, policy_id).unwrap();
+    engine.start_escalation(&incident, policy_id).await.unwrap();
 
     // Acknowledge escalation
     engine
         .acknowledge_escalation(&incident.id, "oncall@example.com".to_string())
+        .await
         .unwrap();
 
     // Verify acknowledgment
@@ -140,7 +148,10 @@ async fn test_escalation_with_schedule() {
             ],
             rotation: RotationStrategy::Daily { handoff_hour: 9 },
             restrictions: None,
+            holidays: vec![],
         }],
+        overrides: vec![],
+        swaps: vec![],
     };
 
     let schedule_id = schedule.id.to_string();
@@ -162,7 +173,7 @@ async fn test_escalation_with_schedule() {
     store.save_incident(&incident).await.unwrap();
 
     // Start escalation
-    engine.start_escalation(&incident, policy_id).unwrap();
+    engine.start_escalation(&incident, policy_id).await.unwrap();
 
     // Verify escalation started
     let state = engine.get_escalation_state(&incident.id).unwrap();
@@ -197,7 +208,7 @@ async fn test_escalation_with_team() {
     store.save_incident(&incident).await.unwrap();
 
     // Start escalation
-    engine.start_escalation(&incident, policy_id).unwrap();
+    engine.start_escalation(&incident, policy_id).await.unwrap();
 
     // Verify escalation started
     let state = engine.get_escalation_state(&incident.id).unwrap();
@@ -356,7 +367,10 @@ async fn test_schedule_resolver_daily_rotation() {
             ],
             rotation: RotationStrategy::Daily { handoff_hour: 9 },
             restrictions: None,
+            holidays: vec![],
         }],
+        overrides: vec![],
+        swaps: vec![],
     };
 
     let oncall_users = resolver.resolve_oncall(&schedule).unwrap();
@@ -385,7 +399,10 @@ async fn test_schedule_resolver_weekly_rotation() {
                 handoff_hour: 9,
             },
             restrictions: None,
+            holidays: vec![],
         }],
+        overrides: vec![],
+        swaps: vec![],
     };
 
     let oncall_users = resolver.resolve_oncall(&schedule).unwrap();
@@ -407,14 +424,18 @@ async fn test_schedule_resolver_multiple_layers() {
                 users: vec!["primary@example.com".to_string()],
                 rotation: RotationStrategy::Daily { handoff_hour: 9 },
                 restrictions: None,
+                holidays: vec![],
             },
             ScheduleLayer {
                 name: "Secondary".to_string(),
                 users: vec!["secondary@example.com".to_string()],
                 rotation: RotationStrategy::Daily { handoff_hour: 9 },
                 restrictions: None,
+                holidays: vec![],
             },
         ],
+        overrides: vec![],
+        swaps: vec![],
     };
 
     let oncall_users = resolver.resolve_oncall(&schedule).unwrap();
@@ -510,17 +531,27 @@ async fn test_escalation_stats() {
     store.save_incident(&incident2).await.unwrap();
     store.save_incident(&incident3).await.unwrap();
 
-    engine.start_escalation(&incident1, policy1.id).unwrap();
-    engine.start_escalation(&incident2, policy1.id).unwrap();
-    engine.start_escalation(&incident3, policy1.id).unwrap();
+    engine
+        .start_escalation(&incident1, policy1.id)
+        .await
+        .unwrap();
+    engine
+        .start_escalation(&incident2, policy1.id)
+        .await
+        .unwrap();
+    engine
+        .start_escalation(&incident3, policy1.id)
+        .await
+        .unwrap();
 
     // Acknowledge one
     engine
         .acknowledge_escalation(&incident1.id, "user@example.com".to_string())
+        .await
         .unwrap();
 
     // Resolve one
-    engine.resolve_escalation(&incident2.id).unwrap();
+    engine.resolve_escalation(&incident2.id).await.unwrap();
 
     // Get stats
     let stats = engine.get_stats();
@@ -545,7 +576,9 @@ async fn test_routing_stats() {
             operator: ConditionOperator::Equals,
             value: serde_json::json!("P0"),
         }],
-        actions: vec![],
+        actions: vec![RoutingAction::Notify {
+            channels: vec!["#p0".to_string()],
+        }],
     };
 
     let rule2 = RoutingRule {
@@ -558,7 +591,9 @@ async fn test_routing_stats() {
             operator: ConditionOperator::Equals,
             value: serde_json::json!("P1"),
         }],
-        actions: vec![],
+        actions: vec![RoutingAction::Notify {
+            channels: vec!["#p1".to_string()],
+        }],
     };
 
     evaluator.register_rule(rule1).unwrap();
@@ -569,3 +604,67 @@ async fn test_routing_stats() {
     assert_eq!(stats.total_rules, 2);
     assert_eq!(stats.enabled_rules, 1);
 }
+
+/// Calendar apps subscribe to feeds with a plain GET and cannot send
+/// execution context headers
+#[tokio::test]
+async fn test_calendar_feeds_through_router() {
+    let store = Arc::new(InMemoryStore::new());
+    let engine = Arc::new(EscalationEngine::new(None, store.clone()));
+
+    let schedule = OnCallSchedule {
+        id: Uuid::new_v4(),
+        name: "Primary On-Call".to_string(),
+        timezone: "UTC".to_string(),
+        layers: vec![ScheduleLayer {
+            name: "Primary".to_string(),
+            users: vec![
+                "user1@example.com".to_string(),
+                "user2@example.com".to_string(),
+            ],
+            rotation: RotationStrategy::Daily { handoff_hour: 9 },
+            restrictions: None,
+            holidays: vec![],
+        }],
+        overrides: vec![],
+        swaps: vec![],
+    };
+    let schedule_id = schedule.id;
+    engine.executor().register_schedule(schedule);
+
+    let dedup_engine = Arc::new(DeduplicationEngine::new(store.clone(), 900));
+    let mut processor = IncidentProcessor::new(store, dedup_engine);
+    processor.set_escalation_engine(engine);
+    let router = build_router(AppState::new(Arc::new(processor)));
+
+    for uri in [
+        format!("/v1/schedules/{}/calendar.ics", schedule_id),
+        "/v1/oncall/user1@example.com/calendar.ics".to_string(),
+    ] {
+        let response = router
+            .clone()
+            .oneshot(Request::builder().uri(&uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", uri);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with("BEGIN:VCALENDAR"));
+        assert!(body.contains("BEGIN:VEVENT"));
+    }
+
+    // Other schedule routes still require execution context
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/schedules/{}", schedule_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -1,10 +1,19 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+};
+use hmac::{Hmac, Mac};
 use llm_incident_manager::{
+    api::{build_router, AppState},
     config::NotificationConfig,
-    models::{Incident, IncidentType, NotificationChannel, Severity},
-    notifications::NotificationService,
-    state::InMemoryStore,
+    models::{EventType, Incident, IncidentState, IncidentType, NotificationChannel, Severity},
+    notifications::{NotificationActionsConfig, NotificationService},
+    processing::{DeduplicationEngine, IncidentProcessor},
+    state::{InMemoryStore, IncidentStore},
 };
+use sha2::Sha256;
 use std::sync::Arc;
+use tower::ServiceExt;
 
 /// Test notification service initialization with no providers
 #[tokio::test]
@@ -33,6 +42,7 @@ async fn test_notification_service_init_no_providers() {
         retry_backoff_secs: 1, // Shorter for tests
         queue_size: 100,
         worker_threads: 2,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -67,6 +77,7 @@ async fn test_notification_service_stats() {
         retry_backoff_secs: 1,
         queue_size: 1000,
         worker_threads: 4,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -108,6 +119,7 @@ async fn test_queue_webhook_notification() {
         retry_backoff_secs: 1,
         queue_size: 100,
         worker_threads: 2,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -168,6 +180,7 @@ async fn test_notify_incident_detected() {
         retry_backoff_secs: 1,
         queue_size: 100,
         worker_threads: 1,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -217,6 +230,7 @@ async fn test_notify_incident_resolved() {
         retry_backoff_secs: 1,
         queue_size: 100,
         worker_threads: 1,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -270,6 +284,7 @@ async fn test_multiple_notification_channels() {
         retry_backoff_secs: 1,
         queue_size: 100,
         worker_threads: 2,
+        actions: Default::default(),
     };
 
     let store = Arc::new(InMemoryStore::new());
@@ -304,3 +319,183 @@ async fn test_multiple_notification_channels() {
     let notification_ids = result.unwrap();
     assert_eq!(notification_ids.len(), 2);
 }
+
+fn hmac_hex(secret: &str, parts: &[&[u8]]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    for part in parts {
+        mac.update(part);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Notification actions come from Slack, PagerDuty and mail clients, which
+/// send no execution context headers, so they must get through the router
+#[tokio::test]
+async fn test_notification_actions_through_router() {
+    std::env::set_var("TEST_ACTION_LINK_SECRET", "link-secret");
+    std::env::set_var("TEST_SLACK_SIGNING_SECRET", "slack-secret");
+    std::env::set_var("TEST_PAGERDUTY_WEBHOOK_SECRET", "pd-secret");
+
+    let config = NotificationConfig {
+        slack_enabled: false,
+        slack_webhook_env: None,
+        slack_bot_token_env: None,
+        slack_default_channel: None,
+        email_enabled: false,
+        smtp_server: None,
+        smtp_port: 587,
+        smtp_use_tls: true,
+        smtp_username_env: None,
+        smtp_password_env: None,
+        email_from: None,
+        email_from_name: None,
+        pagerduty_enabled: false,
+        pagerduty_api_token_env: None,
+        pagerduty_integration_key_env: None,
+        pagerduty_api_url: "https://events.pagerduty.com/v2/enqueue".to_string(),
+        webhook_enabled: false,
+        default_webhook_url: None,
+        webhook_timeout_secs: 10,
+        max_retries: 1,
+        retry_backoff_secs: 1,
+        queue_size: 100,
+        worker_threads: 2,
+        actions: NotificationActionsConfig {
+            base_url: Some("https://im.example.com".to_string()),
+            link_secret_env: Some("TEST_ACTION_LINK_SECRET".to_string()),
+            slack_signing_secret_env: Some("TEST_SLACK_SIGNING_SECRET".to_string()),
+            pagerduty_webhook_secret_env: Some("TEST_PAGERDUTY_WEBHOOK_SECRET".to_string()),
+            ..Default::default()
+        },
+    };
+
+    let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+    let service = Arc::new(NotificationService::new(config, store.clone()).unwrap());
+    let dedup_engine = Arc::new(DeduplicationEngine::new(store.clone(), 900));
+    let processor = Arc::new(
+        IncidentProcessor::new(store.clone(), dedup_engine).with_notifications(service.clone()),
+    );
+    let router = build_router(AppState::new(processor));
+
+    let incident = Incident::new(
+        "router-test".to_string(),
+        "Router Test".to_string(),
+        "Testing notification actions".to_string(),
+        Severity::P1,
+        IncidentType::Application,
+    );
+    store.save_incident(&incident).await.unwrap();
+
+    // Email link: the GET shows a confirmation page, its form POST acknowledges
+    let link = service.actions().links().unwrap().link(
+        incident.id,
+        "acknowledge",
+        "alice@example.com",
+        chrono::Utc::now(),
+    );
+    let uri = link.strip_prefix("https://im.example.com").unwrap();
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let form = uri.split_once('?').unwrap().1.to_string();
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/notification-actions/email")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .body(Body::from(form))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Slack button click taking ownership
+    let payload = serde_json::json!({
+        "type": "block_actions",
+        "user": { "id": "U123", "username": "bob" },
+        "actions": [{
+            "action_id": "take_ownership",
+            "value": incident.id.to_string(),
+            "action_ts": "1700000000.000100"
+        }]
+    });
+    let body = serde_urlencoded::to_string([("payload", payload.to_string())]).unwrap();
+    let timestamp = chrono::Utc::now().timestamp().to_string();
+    let signature = hmac_hex(
+        "slack-secret",
+        &[b"v0:", timestamp.as_bytes(), b":", body.as_bytes()],
+    );
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/notification-actions/slack")
+                .header("content-type", "application/x-www-form-urlencoded")
+                .header("x-slack-request-timestamp", &timestamp)
+                .header("x-slack-signature", format!("v0={}", signature))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // PagerDuty webhook resolving the incident
+    let body = serde_json::json!({
+        "event": {
+            "id": "01DEN3RQLQHTMGM7MQEEF1EQFF",
+            "event_type": "incident.resolved",
+            "resource_type": "incident",
+            "agent": { "id": "PLH1HKV", "summary": "carol", "type": "user_reference" },
+            "data": {
+                "id": "PGR0VU2",
+                "type": "incident",
+                "incident_key": incident.id.to_string()
+            }
+        }
+    })
+    .to_string();
+    let signature = hmac_hex("pd-secret", &[body.as_bytes()]);
+    let response = router
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/notification-actions/pagerduty")
+                .header("content-type", "application/json")
+                .header("x-pagerduty-signature", format!("v1={}", signature))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+    assert!(stored
+        .timeline
+        .iter()
+        .any(|event| event.event_type == EventType::Acknowledged));
+    assert!(stored.assignees.contains(&"bob".to_string()));
+    assert_eq!(stored.state, IncidentState::Resolved);
+
+    // Other /v1/ routes still require execution context
+    let response = router
+        .oneshot(
+            Request::builder()
+                .uri("/v1/incidents")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
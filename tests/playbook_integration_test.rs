@@ -4,7 +4,7 @@ use llm_incident_manager::{
         PlaybookTriggers, Severity, StepType,
     },
     playbooks::{create_default_registry, ExecutionContext, PlaybookExecutor, PlaybookService},
-    state::InMemoryStore,
+    state::{InMemoryStore, IncidentStore},
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -25,6 +25,7 @@ fn create_wait_playbook() -> Playbook {
         id: Uuid::new_v4(),
         name: "Wait Playbook".to_string(),
         version: "1.0".to_string(),
+        revision: 0,
         description: "Simple wait test".to_string(),
         owner: "test".to_string(),
         created_at: chrono::Utc::now(),
@@ -54,6 +55,10 @@ fn create_wait_playbook() -> Playbook {
             retry: 0,
             backoff: BackoffStrategy::Fixed,
             condition: None,
+            depends_on: None,
+            trigger_rule: Default::default(),
+            approval: None,
+            idempotent: false,
         }],
         enabled: true,
         tags: vec!["test".to_string()],
@@ -294,6 +299,7 @@ async fn test_multi_step_playbook() {
         id: Uuid::new_v4(),
         name: "Multi-Step Playbook".to_string(),
         version: "1.0".to_string(),
+        revision: 0,
         description: "Multiple steps".to_string(),
         owner: "test".to_string(),
         created_at: chrono::Utc::now(),
@@ -324,6 +330,10 @@ async fn test_multi_step_playbook() {
                 retry: 0,
                 backoff: BackoffStrategy::Fixed,
                 condition: None,
+                depends_on: None,
+                trigger_rule: Default::default(),
+                approval: None,
+                idempotent: false,
             },
             PlaybookStep {
                 id: "step2".to_string(),
@@ -344,6 +354,10 @@ async fn test_multi_step_playbook() {
                 retry: 0,
                 backoff: BackoffStrategy::Fixed,
                 condition: None,
+                depends_on: None,
+                trigger_rule: Default::default(),
+                approval: None,
+                idempotent: false,
             },
         ],
         enabled: true,
@@ -358,3 +372,75 @@ async fn test_multi_step_playbook() {
     assert!(execution.step_results.contains_key("step1"));
     assert!(execution.step_results.contains_key("step2"));
 }
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_run_script_output_is_available_to_later_steps() {
+    use llm_incident_manager::playbooks::{
+        create_registry, RemediationTargets, ScriptConfig, ScriptSandbox,
+    };
+
+    let scripts_dir = tempfile::tempdir().unwrap();
+    let script = scripts_dir.path().join("diagnose.sh");
+    std::fs::write(&script, "echo \"disk=91 incident=$1\"\necho degraded >&2\nexit 2\n").unwrap();
+
+    let incident = create_test_incident();
+    let store = Arc::new(InMemoryStore::new());
+    store.save_incident(&incident).await.unwrap();
+
+    let sandbox = ScriptSandbox::new(ScriptConfig {
+        allowed_interpreters: vec!["/bin/sh".to_string()],
+        allowed_paths: vec![scripts_dir.path().to_path_buf()],
+        ..ScriptConfig::default()
+    });
+    let registry = create_registry(None, store.clone(), RemediationTargets::new(), sandbox);
+    let executor = PlaybookExecutor::new(Arc::new(registry), store);
+
+    let mut playbook = create_wait_playbook();
+    playbook.steps[0] = PlaybookStep {
+        id: "diagnose".to_string(),
+        step_type: StepType::DataCollection,
+        description: None,
+        actions: vec![Action {
+            action_type: ActionType::RunScript,
+            parameters: serde_json::from_value(serde_json::json!({
+                "interpreter": "/bin/sh",
+                "script": script,
+                "args": ["{{incident_id}}"],
+                "fail_on_error": false,
+            }))
+            .unwrap(),
+            on_success: None,
+            on_failure: None,
+        }],
+        parallel: false,
+        timeout: Some("30s".to_string()),
+        retry: 0,
+        backoff: BackoffStrategy::Fixed,
+        condition: None,
+        depends_on: None,
+        trigger_rule: Default::default(),
+        approval: None,
+        idempotent: false,
+    };
+
+    let mut context = ExecutionContext::new(incident.clone());
+    let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+    assert_eq!(execution.status, llm_incident_manager::models::ExecutionStatus::Completed);
+
+    let output = context.get_step_output("diagnose").unwrap();
+    assert_eq!(output["action_0.exit_code"], serde_json::json!(2));
+    assert_eq!(
+        output["action_0.stdout"],
+        serde_json::json!(format!("disk=91 incident={}\n", incident.id))
+    );
+    assert_eq!(output["action_0.stderr"], serde_json::json!("degraded\n"));
+
+    // Without the opt-out a non-zero exit fails the playbook
+    playbook.steps[0].actions[0]
+        .parameters
+        .remove("fail_on_error");
+    let mut context = ExecutionContext::new(incident);
+    let execution = executor.execute_playbook(&playbook, &mut context).await.unwrap();
+    assert_eq!(execution.status, llm_incident_manager::models::ExecutionStatus::Failed);
+}
@@ -1,7 +1,9 @@
 //! Comprehensive tests for the scheduler module
 
 use llm_incident_manager::scheduler::{
-    Job, JobContext, JobMetadata, SchedulerConfig, SchedulerService,
+    cleanup_old_incidents, generate_daily_reports, monitor_stale_incidents,
+    report_subscription_jobs, update_ml_models, Job, JobContext, JobMetadata, SchedulerConfig,
+    SchedulerService, TaskServices,
 };
 use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
 use std::time::Duration;
@@ -274,3 +276,198 @@ async fn test_concurrent_jobs() {
 
     scheduler.shutdown().await.expect("Failed to stop scheduler");
 }
+
+#[tokio::test]
+async fn test_cleanup_old_incidents_archives_expired() {
+    use llm_incident_manager::models::{Incident, IncidentType, ResolutionMethod, Severity};
+    use llm_incident_manager::retention::{RetentionConfig, RetentionEngine};
+    use llm_incident_manager::state::{InMemoryStore, IncidentStore};
+
+    let dir = tempfile::tempdir().unwrap();
+    let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+
+    let mut incident = Incident::new(
+        "test-source".to_string(),
+        "Old incident".to_string(),
+        "Description".to_string(),
+        Severity::P3,
+        IncidentType::Application,
+    );
+    incident.resolve("oncall".to_string(), ResolutionMethod::Manual, "Fixed".to_string(), None);
+    if let Some(resolution) = incident.resolution.as_mut() {
+        resolution.resolved_at = chrono::Utc::now() - chrono::Duration::days(10);
+    }
+    store.save_incident(&incident).await.unwrap();
+
+    let config = RetentionConfig {
+        archive_dir: dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let engine = RetentionEngine::new(store.clone(), &config).unwrap();
+    let services = TaskServices::new().with_retention(Arc::new(engine));
+
+    // Default retention of 90 days keeps it
+    let metadata = JobMetadata::new("cleanup_old_incidents", "0 2 * * *");
+    let ctx = JobContext::new(metadata).with_app_state(Arc::new(services.clone()));
+    cleanup_old_incidents(ctx).await.unwrap();
+    assert!(store.get_incident(&incident.id).await.unwrap().is_some());
+
+    // Job config overrides it
+    let metadata = JobMetadata::new("cleanup_old_incidents", "0 2 * * *")
+        .with_metadata(serde_json::json!({ "retention_days": 7 }));
+    let ctx = JobContext::new(metadata).with_app_state(Arc::new(services));
+    cleanup_old_incidents(ctx).await.unwrap();
+    assert!(store.get_incident(&incident.id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_monitor_stale_incidents_records_findings() {
+    use llm_incident_manager::models::{Incident, IncidentType, Severity};
+    use llm_incident_manager::staleness::{StaleIncidentMonitor, StaleRule, StalenessConfig};
+    use llm_incident_manager::state::{InMemoryStore, IncidentStore};
+
+    let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+    let incident = Incident::new(
+        "test-source".to_string(),
+        "Unowned incident".to_string(),
+        "Description".to_string(),
+        Severity::P2,
+        IncidentType::Application,
+    );
+    store.save_incident(&incident).await.unwrap();
+
+    let config = StalenessConfig {
+        rules: vec![StaleRule {
+            name: "unassigned".to_string(),
+            unassigned: true,
+            ..Default::default()
+        }],
+    };
+    let monitor = StaleIncidentMonitor::new(store.clone(), config);
+    let services = TaskServices::new().with_staleness(Arc::new(monitor));
+
+    let metadata = JobMetadata::new("monitor_stale_incidents", "*/15 * * * *")
+        .with_metadata(serde_json::json!({ "escalate": false }));
+    let ctx = JobContext::new(metadata).with_app_state(Arc::new(services));
+    monitor_stale_incidents(ctx.clone()).await.unwrap();
+
+    let result = ctx.get_metadata().await.metadata["last_result"].clone();
+    assert_eq!(result["scanned"], 1);
+    assert_eq!(result["findings"][0]["incident_id"], incident.id.to_string());
+    assert_eq!(result["findings"][0]["rule"], "unassigned");
+
+    let stored = store.get_incident(&incident.id).await.unwrap().unwrap();
+    assert_eq!(stored.timeline.len(), 2);
+}
+
+#[tokio::test]
+async fn test_generate_daily_reports_exports_previous_day() {
+    use llm_incident_manager::analytics::{
+        AnalyticsEngine, ExportFormat, ReportDeliveryService, ReportFilter, ReportSubscription,
+        ReportType, ReportingConfig,
+    };
+    use llm_incident_manager::models::{Incident, IncidentType, Severity};
+    use llm_incident_manager::state::{InMemoryStore, IncidentStore};
+
+    let dir = tempfile::tempdir().unwrap();
+    let store: Arc<dyn IncidentStore> = Arc::new(InMemoryStore::new());
+
+    let yesterday = chrono::Utc::now().date_naive().pred_opt().unwrap();
+    let mut incident = Incident::new(
+        "test-source".to_string(),
+        "Yesterday's incident".to_string(),
+        "Description".to_string(),
+        Severity::P2,
+        IncidentType::Application,
+    );
+    incident.created_at = yesterday.and_hms_opt(12, 0, 0).unwrap().and_utc();
+    store.save_incident(&incident).await.unwrap();
+
+    let config = ReportingConfig {
+        output_dir: dir.path().to_path_buf(),
+        report_types: vec![ReportType::SLA],
+        formats: vec![ExportFormat::Json],
+        subscriptions: vec![ReportSubscription {
+            name: "web-team".to_string(),
+            report_types: vec![ReportType::Summary],
+            filters: ReportFilter::new().with_teams(vec!["web".to_string()]),
+            formats: Vec::new(),
+            schedule: Some("0 7 * * 1-5".to_string()),
+            email: Vec::new(),
+            slack_channel: None,
+        }],
+        ..Default::default()
+    };
+    let service = ReportDeliveryService::new(store, AnalyticsEngine::with_defaults(), config);
+
+    let jobs = report_subscription_jobs(&service);
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].get_metadata().await.schedule, "0 7 * * 1-5");
+
+    let services = TaskServices::new().with_reports(Arc::new(service));
+    let metadata = JobMetadata::new("generate_daily_reports", "0 8 * * *")
+        .with_metadata(serde_json::json!({ "report_types": ["summary"] }));
+    let ctx = JobContext::new(metadata).with_app_state(Arc::new(services));
+    generate_daily_reports(ctx.clone()).await.unwrap();
+
+    // The scheduled subscription is not part of the daily run
+    let result = ctx.get_metadata().await.metadata["last_result"].clone();
+    assert_eq!(result["incidents"], 1);
+    assert_eq!(result["reports"].as_array().unwrap().len(), 1);
+    assert_eq!(result["reports"][0]["subscription"], "default");
+
+    let path = dir
+        .path()
+        .join(yesterday.format("%Y-%m-%d").to_string())
+        .join("default-summary.json");
+    assert!(path.exists());
+}
+
+#[tokio::test]
+async fn test_update_ml_models_task_promotes_and_versions() {
+    use llm_incident_manager::ml::{MLConfig, MLService, ModelTarget};
+    use llm_incident_manager::models::{Incident, IncidentType, ResolutionMethod, Severity};
+    use llm_incident_manager::state::{InMemoryStore, IncidentStore};
+
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(InMemoryStore::new());
+    for i in 0..30 {
+        let (title, severity, incident_type) = if i % 2 == 0 {
+            ("Database outage", Severity::P1, IncidentType::Infrastructure)
+        } else {
+            ("Login errors", Severity::P2, IncidentType::Security)
+        };
+        let mut incident = Incident::new(
+            "monitor".to_string(),
+            format!("{} {}", title, i),
+            title.to_string(),
+            severity,
+            incident_type,
+        );
+        incident.resolve("oncall".to_string(), ResolutionMethod::Manual, "Fixed".to_string(), None);
+        store.save_incident(&incident).await.unwrap();
+    }
+
+    let config = MLConfig {
+        model_path: Some(dir.path().to_string_lossy().into_owned()),
+        ..MLConfig::default()
+    };
+    let service = Arc::new(MLService::new(config, store));
+
+    let services = TaskServices::new().with_ml(service.clone());
+    let metadata = JobMetadata::new("update_ml_models", "0 0 * * 0").with_metadata(
+        serde_json::json!({ "models": ["severity", "type"], "min_training_samples": 20 }),
+    );
+    let ctx = JobContext::new(metadata).with_app_state(Arc::new(services));
+    update_ml_models(ctx.clone()).await.unwrap();
+
+    let result = ctx.get_metadata().await.metadata["last_result"].clone();
+    assert_eq!(result["samples"], 30);
+    assert_eq!(result["outcomes"].as_array().unwrap().len(), 2);
+    assert_eq!(result["outcomes"][0]["promoted"], true);
+    assert_eq!(result["outcomes"][0]["version"], "v1");
+
+    assert_eq!(service.model_versions(ModelTarget::Severity).unwrap().len(), 1);
+    assert!(service.model_versions(ModelTarget::Priority).unwrap().is_empty());
+    assert!(dir.path().join("severity").join("v1").join("model.json").exists());
+}
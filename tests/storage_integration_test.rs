@@ -1,6 +1,10 @@
 use llm_incident_manager::{
+    error::AppError,
     models::{Incident, IncidentState, IncidentType, Severity},
-    state::{IncidentFilter, IncidentStore, InMemoryStore, RedisStore, SledStore},
+    state::{
+        IncidentEventPayload, IncidentFilter, IncidentSort, IncidentStore, InMemoryStore,
+        LabelSelector, RedbStore, RedisStore, SledStore, SortDirection, SortField, TimeRange,
+    },
 };
 use std::sync::Arc;
 use tempfile::TempDir;
@@ -90,7 +94,7 @@ async fn test_filtering<S: IncidentStore + Send + Sync + 'static>(store: Arc<S>)
     };
 
     let incidents = store.list_incidents(&filter, 0, 100).await.unwrap();
-    assert_eq!(incidents.len(), 5); // P0: 0,4,8 + P1: 1,5,9
+    assert_eq!(incidents.len(), 6); // P0: 0,4,8 + P1: 1,5,9
 
     // Test state filter
     let mut investigating_incident = incidents[0].clone();
@@ -232,6 +236,155 @@ async fn test_concurrent_operations<S: IncidentStore + Send + Sync + 'static>(st
     }
 }
 
+async fn test_event_history<S: IncidentStore + Send + Sync + 'static>(store: Arc<S>) {
+    let mut incident = create_test_incident("History Test", Severity::P2);
+    let id = incident.id;
+
+    store.save_incident(&incident).await.unwrap();
+
+    incident.update_state(IncidentState::Investigating, "user@example.com".to_string());
+    incident.assignees = vec!["user@example.com".to_string()];
+    store.update_incident(&incident).await.unwrap();
+    incident.version += 1;
+    let investigating = incident.clone();
+
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let checkpoint = chrono::Utc::now();
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    incident.severity = Severity::P0;
+    incident.update_state(IncidentState::Remediating, "user@example.com".to_string());
+    store.update_incident(&incident).await.unwrap();
+    incident.version += 1;
+
+    // Events are sequential and typed
+    let events = store.get_incident_events(&id).await.unwrap();
+    assert!(events.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+    assert!(matches!(events[0].payload, IncidentEventPayload::Created { .. }));
+    assert!(events.iter().any(|e| matches!(
+        e.payload,
+        IncidentEventPayload::SeverityChanged { to: Severity::P0, .. }
+    )));
+
+    // Point-in-time reconstruction
+    let at_checkpoint = store.get_incident_at(&id, checkpoint).await.unwrap().unwrap();
+    assert_eq!(at_checkpoint, investigating);
+
+    let latest = store.get_incident_at(&id, chrono::Utc::now()).await.unwrap().unwrap();
+    assert_eq!(latest, incident);
+
+    // Deletion is recorded, earlier history is kept
+    store.delete_incident(&id).await.unwrap();
+    assert!(store.get_incident_at(&id, chrono::Utc::now()).await.unwrap().is_none());
+    assert!(store.get_incident_at(&id, checkpoint).await.unwrap().is_some());
+}
+
+async fn test_concurrent_updates<S: IncidentStore + Send + Sync + 'static>(store: Arc<S>) {
+    let incident = create_test_incident("Concurrency Test", Severity::P2);
+    let id = incident.id;
+    store.save_incident(&incident).await.unwrap();
+
+    // Two writers read the same version
+    let mut first = store.get_incident(&id).await.unwrap().unwrap();
+    let mut second = first.clone();
+
+    first.update_state(IncidentState::Investigating, "alice@example.com".to_string());
+    store.update_incident(&first).await.unwrap();
+
+    second.severity = Severity::P0;
+    let err = store.update_incident(&second).await.unwrap_err();
+    assert!(matches!(
+        err,
+        AppError::VersionConflict {
+            expected: 0,
+            actual: 1,
+            ..
+        }
+    ));
+
+    // The first write wins and the loser can retry on the fresh copy
+    let mut retry = store.get_incident(&id).await.unwrap().unwrap();
+    assert_eq!(retry.state, IncidentState::Investigating);
+    assert_eq!(retry.severity, Severity::P2);
+    retry.severity = Severity::P0;
+    store.update_incident(&retry).await.unwrap();
+
+    let stored = store.get_incident(&id).await.unwrap().unwrap();
+    assert_eq!(stored.version, 2);
+    assert_eq!(stored.severity, Severity::P0);
+
+    store.delete_incident(&id).await.ok();
+}
+
+async fn test_rich_query<S: IncidentStore + Send + Sync + 'static>(store: Arc<S>) {
+    // Scope every filter to this run, since Redis stores are shared
+    let run = uuid::Uuid::new_v4().to_string();
+    let start = chrono::Utc::now();
+
+    let mut ids = Vec::new();
+    for i in 0..6i64 {
+        let mut incident = create_test_incident(&format!("Rich query {}", i), Severity::P2);
+        incident.created_at = start + chrono::Duration::seconds(i);
+        incident.labels.insert("run".to_string(), run.clone());
+        if i % 2 == 0 {
+            incident.assignees.push("alice".to_string());
+            incident.affected_resources.push("db-primary".to_string());
+        }
+        store.save_incident(&incident).await.unwrap();
+        ids.push(incident.id);
+    }
+
+    let base = IncidentFilter {
+        labels: vec![LabelSelector::equals("run", run.clone())],
+        ..Default::default()
+    };
+
+    // Assignee and resource, oldest first, two per page
+    let filter = IncidentFilter {
+        assignees: vec!["alice".to_string()],
+        affected_resources: vec!["db-primary".to_string()],
+        sort: IncidentSort::new(SortField::CreatedAt, SortDirection::Asc),
+        ..base.clone()
+    };
+    let first = store.query_incidents(&filter, None, 2).await.unwrap();
+    assert_eq!(
+        first.incidents.iter().map(|i| i.id).collect::<Vec<_>>(),
+        vec![ids[0], ids[2]]
+    );
+    let second = store
+        .query_incidents(&filter, first.next_cursor.as_deref(), 2)
+        .await
+        .unwrap();
+    assert_eq!(second.incidents.len(), 1);
+    assert_eq!(second.incidents[0].id, ids[4]);
+    assert!(second.next_cursor.is_none());
+
+    // Created time range is half-open
+    let filter = IncidentFilter {
+        created: TimeRange::new(
+            Some(start + chrono::Duration::seconds(1)),
+            Some(start + chrono::Duration::seconds(3)),
+        ),
+        ..base.clone()
+    };
+    assert_eq!(store.count_incidents(&filter).await.unwrap(), 2);
+
+    // Text search and a label that no longer matches after an update
+    let mut incident = store.get_incident(&ids[5]).await.unwrap().unwrap();
+    incident.labels.insert("run".to_string(), "finished".to_string());
+    store.update_incident(&incident).await.unwrap();
+
+    let filter = IncidentFilter {
+        text: Some("RICH QUERY".to_string()),
+        ..base.clone()
+    };
+    assert_eq!(store.count_incidents(&filter).await.unwrap(), 5);
+
+    for id in ids {
+        store.delete_incident(&id).await.ok();
+    }
+}
+
 // InMemoryStore tests
 #[tokio::test]
 async fn test_inmemory_operations() {
@@ -263,6 +416,24 @@ async fn test_inmemory_concurrent() {
     test_concurrent_operations(store).await;
 }
 
+#[tokio::test]
+async fn test_inmemory_event_history() {
+    let store = Arc::new(InMemoryStore::new());
+    test_event_history(store).await;
+}
+
+#[tokio::test]
+async fn test_inmemory_concurrent_updates() {
+    let store = Arc::new(InMemoryStore::new());
+    test_concurrent_updates(store).await;
+}
+
+#[tokio::test]
+async fn test_inmemory_rich_query() {
+    let store = Arc::new(InMemoryStore::new());
+    test_rich_query(store).await;
+}
+
 // SledStore tests
 #[tokio::test]
 async fn test_sled_operations() {
@@ -299,6 +470,27 @@ async fn test_sled_concurrent() {
     test_concurrent_operations(store).await;
 }
 
+#[tokio::test]
+async fn test_sled_event_history() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+    test_event_history(store).await;
+}
+
+#[tokio::test]
+async fn test_sled_concurrent_updates() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+    test_concurrent_updates(store).await;
+}
+
+#[tokio::test]
+async fn test_sled_rich_query() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(SledStore::new(temp_dir.path()).unwrap());
+    test_rich_query(store).await;
+}
+
 #[tokio::test]
 async fn test_sled_persistence() {
     let temp_dir = TempDir::new().unwrap();
@@ -323,6 +515,86 @@ async fn test_sled_persistence() {
     }
 }
 
+// RedbStore tests
+#[tokio::test]
+async fn test_redb_operations() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_store_operations(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_filtering() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_filtering(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_pagination() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_pagination(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_fingerprint() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_fingerprint_indexing(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_concurrent() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_concurrent_operations(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_event_history() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_event_history(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_concurrent_updates() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_concurrent_updates(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_rich_query() {
+    let temp_dir = TempDir::new().unwrap();
+    let store = Arc::new(RedbStore::new(temp_dir.path()).unwrap());
+    test_rich_query(store).await;
+}
+
+#[tokio::test]
+async fn test_redb_persistence() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("incidents.redb");
+
+    let incident_id = {
+        // Create and save incident
+        let store = RedbStore::new(&path).unwrap();
+        let incident = create_test_incident("Persistence Test", Severity::P0);
+        let id = incident.id;
+        store.save_incident(&incident).await.unwrap();
+        id
+    };
+
+    // Reopen database and verify incident persisted
+    {
+        let store = RedbStore::new(&path).unwrap();
+        let retrieved = store.get_incident(&incident_id).await.unwrap();
+        assert!(retrieved.is_some());
+        assert_eq!(retrieved.unwrap().title, "Persistence Test");
+    }
+}
+
 // RedisStore tests (conditional on Redis being available)
 async fn redis_available() -> bool {
     RedisStore::new("redis://127.0.0.1:6379/15")
@@ -405,6 +677,141 @@ async fn test_redis_concurrent() {
     test_concurrent_operations(store).await;
 }
 
+#[tokio::test]
+async fn test_redis_event_history() {
+    if !redis_available().await {
+        eprintln!("Skipping test: Redis not available");
+        return;
+    }
+
+    let store = Arc::new(
+        RedisStore::new_with_prefix("redis://127.0.0.1:6379/15", "test")
+            .await
+            .unwrap(),
+    );
+    test_event_history(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_concurrent_updates() {
+    if !redis_available().await {
+        eprintln!("Skipping test: Redis not available");
+        return;
+    }
+
+    let store = Arc::new(
+        RedisStore::new_with_prefix("redis://127.0.0.1:6379/15", "test")
+            .await
+            .unwrap(),
+    );
+    test_concurrent_updates(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_rich_query() {
+    if !redis_available().await {
+        eprintln!("Skipping test: Redis not available");
+        return;
+    }
+
+    let store = Arc::new(
+        RedisStore::new_with_prefix("redis://127.0.0.1:6379/15", "test")
+            .await
+            .unwrap(),
+    );
+    test_rich_query(store).await;
+}
+
+// RedisStore cluster tests (conditional on a cluster being available, see
+// scripts/redis-cluster.sh)
+async fn redis_cluster_store() -> Option<Arc<RedisStore>> {
+    let nodes: Vec<String> = std::env::var("REDIS_CLUSTER_NODES")
+        .unwrap_or_else(|_| {
+            "redis://127.0.0.1:7000,redis://127.0.0.1:7001,redis://127.0.0.1:7002".to_string()
+        })
+        .split(',')
+        .map(|node| node.trim().to_string())
+        .collect();
+
+    // Unique prefix per test so counts are not affected by other runs
+    RedisStore::new_cluster_with_prefix(&nodes, &format!("test-{}", uuid::Uuid::new_v4()))
+        .await
+        .ok()
+        .map(Arc::new)
+}
+
+#[tokio::test]
+async fn test_redis_cluster_operations() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_store_operations(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_filtering() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_filtering(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_pagination() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_pagination(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_fingerprint() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_fingerprint_indexing(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_concurrent() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_concurrent_operations(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_event_history() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_event_history(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_concurrent_updates() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_concurrent_updates(store).await;
+}
+
+#[tokio::test]
+async fn test_redis_cluster_rich_query() {
+    let Some(store) = redis_cluster_store().await else {
+        eprintln!("Skipping test: Redis cluster not available");
+        return;
+    };
+    test_rich_query(store).await;
+}
+
 // Cross-store consistency tests
 #[tokio::test]
 async fn test_store_parity() {
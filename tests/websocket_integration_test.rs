@@ -56,10 +56,11 @@ fn test_config() -> Config {
         },
         state: StateConfig {
             backend: StateBackend::Sled,
-            path: Some(format!("./data/test-ws-{}", Uuid::new_v4())),
+            path: Some(std::env::temp_dir().join(format!("test-ws-{}", Uuid::new_v4()))),
             redis_url: None,
             redis_cluster_nodes: vec![],
             pool_size: 10,
+            dual_write: None,
         },
         messaging: None,
         integrations: IntegrationsConfig::default(),
@@ -102,7 +103,13 @@ fn test_config() -> Config {
             retry_backoff_secs: 5,
             queue_size: 1000,
             worker_threads: 2,
+            actions: Default::default(),
         },
+        retention: Default::default(),
+        staleness: Default::default(),
+        reporting: Default::default(),
+        playbooks: Default::default(),
+        gitops: Default::default(),
     }
 }
 